@@ -64,13 +64,13 @@ fn bench_kdl_generation(c: &mut Criterion) {
     // Keyboard KDL
     group.bench_function("keyboard", |b| {
         let keyboard = KeyboardSettings::default();
-        b.iter(|| generate_keyboard_kdl(black_box(&keyboard)))
+        b.iter(|| generate_keyboard_kdl(black_box(&keyboard), false))
     });
 
     // Mouse KDL
     group.bench_function("mouse", |b| {
         let mouse = MouseSettings::default();
-        b.iter(|| generate_mouse_kdl(black_box(&mouse)))
+        b.iter(|| generate_mouse_kdl(black_box(&mouse), false))
     });
 
     // Animations KDL
@@ -8,8 +8,10 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
 
 /// Derive macro for generating Slint UI index conversion methods.
 ///
-/// This macro generates `to_index(&self) -> i32` and `from_index(idx: i32) -> Self`
-/// methods for enums used in Slint combobox bindings.
+/// This macro generates `to_index(&self) -> i32`, `from_index(idx: i32) -> Self`
+/// and `try_from_index(idx: i32) -> Option<Self>` methods for enums used in
+/// Slint combobox bindings, plus `ALL` (every variant in declaration order)
+/// and `COUNT` constants for populating the combobox.
 ///
 /// # Attributes
 ///
@@ -33,8 +35,11 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
 ///
 /// // Generates:
 /// // impl AccelProfile {
+/// //     pub const ALL: &'static [Self] = &[Self::Adaptive, Self::Flat];
+/// //     pub const COUNT: usize = 2;
 /// //     pub fn to_index(&self) -> i32 { ... }
 /// //     pub fn from_index(idx: i32) -> Self { ... }
+/// //     pub fn try_from_index(idx: i32) -> Option<Self> { ... }
 /// // }
 /// ```
 #[proc_macro_derive(SlintIndex, attributes(slint_index))]
@@ -130,6 +135,20 @@ pub fn derive_slint_index(input: TokenStream) -> TokenStream {
             }
         });
 
+    // Generate try_from_index match arms (every variant, default included)
+    let try_from_index_arms = variants_info.iter().map(|(ident, index, _)| {
+        quote! {
+            #index => Some(Self::#ident)
+        }
+    });
+
+    let all_variants = variants_info.iter().map(|(ident, _, _)| {
+        quote! {
+            Self::#ident
+        }
+    });
+    let count = variants_info.len();
+
     // Get the default variant
     let default_variant = variants_info
         .iter()
@@ -140,6 +159,12 @@ pub fn derive_slint_index(input: TokenStream) -> TokenStream {
     // Generate the impl block
     let expanded = quote! {
         impl #name {
+            /// Every variant, in declaration order.
+            pub const ALL: &'static [Self] = &[#(#all_variants),*];
+
+            /// Number of variants.
+            pub const COUNT: usize = #count;
+
             /// Convert this enum variant to its Slint UI combobox index.
             #[inline]
             pub fn to_index(&self) -> i32 {
@@ -158,16 +183,31 @@ pub fn derive_slint_index(input: TokenStream) -> TokenStream {
                     _ => Self::#default_variant
                 }
             }
+
+            /// Convert a Slint UI combobox index to this enum.
+            ///
+            /// Unknown indices return `None`, so a bad value can be told
+            /// apart from the default variant.
+            #[inline]
+            pub fn try_from_index(idx: i32) -> Option<Self> {
+                match idx {
+                    #(#try_from_index_arms,)*
+                    _ => None
+                }
+            }
         }
     };
 
     TokenStream::from(expanded)
 }
 
-/// Derive macro for generating `has_any()` method on structs with Option fields.
+/// Derive macro for generating `has_any()`, `set_field_count()` and `clear_all()`
+/// methods on structs with Option fields.
 ///
 /// This macro generates a `has_any(&self) -> bool` method that returns true
-/// if any of the struct's Option fields are Some.
+/// if any of the struct's Option fields are Some, a `set_field_count(&self) -> usize`
+/// method counting how many are, and a `clear_all(&mut self)` method that sets
+/// them all back to None.
 ///
 /// # Requirements
 ///
@@ -193,6 +233,18 @@ pub fn derive_slint_index(input: TokenStream) -> TokenStream {
 /// //             || self.strut_left.is_some()
 /// //             || self.center_focused.is_some()
 /// //     }
+/// //
+/// //     pub fn set_field_count(&self) -> usize {
+/// //         self.gaps.is_some() as usize
+/// //             + self.strut_left.is_some() as usize
+/// //             + self.center_focused.is_some() as usize
+/// //     }
+/// //
+/// //     pub fn clear_all(&mut self) {
+/// //         self.gaps = None;
+/// //         self.strut_left = None;
+/// //         self.center_focused = None;
+/// //     }
 /// // }
 /// ```
 #[proc_macro_derive(HasAny)]
@@ -221,33 +273,152 @@ pub fn derive_has_any(input: TokenStream) -> TokenStream {
     };
 
     // Collect Option fields
-    let option_checks: Vec<_> = fields
+    let option_fields: Vec<_> = fields
         .iter()
-        .filter_map(|field| {
-            let field_name = field.ident.as_ref()?;
-
-            // Check if the field type is Option<T>
-            if is_option_type(&field.ty) {
-                Some(quote! { self.#field_name.is_some() })
-            } else {
-                None
-            }
-        })
+        .filter(|field| is_option_type(&field.ty))
+        .filter_map(|field| field.ident.as_ref())
         .collect();
 
-    if option_checks.is_empty() {
+    if option_fields.is_empty() {
         return syn::Error::new_spanned(&input, "HasAny requires at least one Option field")
             .to_compile_error()
             .into();
     }
 
-    // Build the has_any method
+    // Build the has_any, set_field_count and clear_all methods
     let expanded = quote! {
         impl #name {
             /// Returns true if any optional field is set (not None).
             #[inline]
             pub fn has_any(&self) -> bool {
-                #(#option_checks)||*
+                #(self.#option_fields.is_some())||*
+            }
+
+            /// Returns how many optional fields are set (not None).
+            #[inline]
+            pub fn set_field_count(&self) -> usize {
+                #(self.#option_fields.is_some() as usize)+*
+            }
+
+            /// Sets every optional field back to None, leaving other fields as they are.
+            #[inline]
+            pub fn clear_all(&mut self) {
+                #(self.#option_fields = None;)*
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Derive macro for layering an override struct's set fields onto a base struct.
+///
+/// This macro generates a `merge_into(&self, base: &mut Base)` method that,
+/// for every `Option` field that is `Some`, assigns a clone of the value to
+/// the base field of the same name. Unset fields leave the base untouched.
+///
+/// # Attributes
+///
+/// - `#[merge(base = "Type")]` - The struct the override applies to (required)
+///
+/// # Requirements
+///
+/// - The struct must have named fields
+/// - Each `Option<T>` field must match a base field of type `T` by name
+///   (non-Option fields are ignored)
+///
+/// # Example
+///
+/// ```ignore
+/// use nirify_macros::MergeOverride;
+///
+/// #[derive(MergeOverride)]
+/// #[merge(base = "Spacing")]
+/// pub struct SpacingOverride {
+///     pub gaps: Option<f32>,
+///     pub strut_left: Option<f32>,
+/// }
+///
+/// // Generates:
+/// // impl SpacingOverride {
+/// //     pub fn merge_into(&self, base: &mut Spacing) {
+/// //         if let Some(value) = &self.gaps {
+/// //             base.gaps = value.clone();
+/// //         }
+/// //         if let Some(value) = &self.strut_left {
+/// //             base.strut_left = value.clone();
+/// //         }
+/// //     }
+/// // }
+/// ```
+#[proc_macro_derive(MergeOverride, attributes(merge))]
+pub fn derive_merge_override(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    // Only works on structs with named fields
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input,
+                    "MergeOverride can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input,
+                "MergeOverride can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // Find the base type in #[merge(base = "...")]
+    let mut base: Option<syn::Path> = None;
+    for attr in &input.attrs {
+        if attr.path().is_ident("merge") {
+            let parsed = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("base") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    base = Some(lit.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `base = \"Type\"`"))
+                }
+            });
+            if let Err(e) = parsed {
+                return e.to_compile_error().into();
+            }
+        }
+    }
+    let Some(base) = base else {
+        return syn::Error::new_spanned(&input, "MergeOverride requires #[merge(base = \"Type\")]")
+            .to_compile_error()
+            .into();
+    };
+
+    // Collect Option fields
+    let option_fields: Vec<_> = fields
+        .iter()
+        .filter(|field| is_option_type(&field.ty))
+        .filter_map(|field| field.ident.as_ref())
+        .collect();
+
+    let expanded = quote! {
+        impl #name {
+            /// Copies every set (Some) field onto `base`, leaving the rest of it as it is.
+            pub fn merge_into(&self, base: &mut #base) {
+                #(
+                    if let Some(value) = &self.#option_fields {
+                        base.#option_fields = value.clone();
+                    }
+                )*
             }
         }
     };
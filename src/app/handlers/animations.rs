@@ -1,6 +1,6 @@
 //! Animations settings message handler
 
-use crate::config::models::{AnimationId, AnimationType, EasingCurve};
+use crate::config::models::{AnimationId, AnimationSettings, AnimationType, EasingCurve};
 use crate::config::SettingsCategory;
 use crate::messages::{AnimationsMessage, Message};
 use iced::Task;
@@ -8,109 +8,7 @@ use iced::Task;
 impl super::super::App {
     /// Updates animation settings
     pub(in crate::app) fn update_animations(&mut self, msg: AnimationsMessage) -> Task<Message> {
-        match msg {
-            AnimationsMessage::ToggleSlowdown(enabled) => {
-                // Toggle between slowdown factor and normal speed (1.0)
-                if enabled {
-                    // Enable slowdown (if it's at 1.0, set to default 3.0)
-                    if (self.settings.animations.slowdown - 1.0).abs() < 0.01 {
-                        self.settings.animations.slowdown = 3.0;
-                    }
-                } else {
-                    // Disable slowdown (set to 1.0 = normal speed)
-                    self.settings.animations.slowdown = 1.0;
-                }
-            }
-            AnimationsMessage::SetSlowdownFactor(value) => {
-                self.settings.animations.slowdown = value.clamp(0.1, 10.0) as f64;
-            }
-            AnimationsMessage::SetAnimationEnabled(name, enabled) => {
-                // Parse animation name to AnimationId
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.animation_type = if enabled {
-                        AnimationType::Spring // Default to spring when enabled
-                    } else {
-                        AnimationType::Off
-                    };
-                }
-            }
-            AnimationsMessage::SetAnimationDuration(name, duration) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.easing.duration_ms = duration.clamp(50, 5000);
-                }
-            }
-            AnimationsMessage::SetAnimationCurve(name, curve_name) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.easing.curve = EasingCurve::from_kdl(&curve_name);
-                }
-            }
-            AnimationsMessage::SetAnimationSpringDampingRatio(name, ratio) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.spring.damping_ratio = ratio.clamp(0.1, 2.0) as f64;
-                }
-            }
-            AnimationsMessage::SetAnimationSpringEpsilon(name, epsilon) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.spring.epsilon = epsilon.clamp(0.0001, 1.0) as f64;
-                }
-            }
-            AnimationsMessage::SetAnimationType(name, type_index) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.animation_type = match type_index {
-                        0 => AnimationType::Default,
-                        1 => AnimationType::Off,
-                        2 => AnimationType::Spring,
-                        3 => AnimationType::Easing,
-                        4 if anim_id.supports_custom_shader() => AnimationType::CustomShader,
-                        _ => AnimationType::Default,
-                    };
-                }
-            }
-            AnimationsMessage::SetCustomShader(name, code) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    if anim_id.supports_custom_shader() {
-                        let anim_config =
-                            anim_id.get_mut(&mut self.settings.animations.per_animation);
-                        anim_config.custom_shader = Some(code);
-                        anim_config.animation_type = AnimationType::CustomShader;
-                    }
-                }
-            }
-            AnimationsMessage::ClearCustomShader(name) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    let anim_config = anim_id.get_mut(&mut self.settings.animations.per_animation);
-                    anim_config.custom_shader = None;
-                    // Revert to default if clearing shader
-                    if anim_config.animation_type == AnimationType::CustomShader {
-                        anim_config.animation_type = AnimationType::Default;
-                    }
-                }
-            }
-            AnimationsMessage::InsertShaderTemplate(name) => {
-                if let Some(anim_id) = Self::parse_animation_name(&name) {
-                    if let Some(func_name) = anim_id.shader_function_name() {
-                        let template = format!(
-                            r#"vec4 {}(vec3 coords_geo, vec3 size_geo) {{
-    float progress = niri_clamped_progress;
-    // Your GLSL code here
-    return vec4(1.0);
-}}"#,
-                            func_name
-                        );
-                        let anim_config =
-                            anim_id.get_mut(&mut self.settings.animations.per_animation);
-                        anim_config.custom_shader = Some(template);
-                        anim_config.animation_type = AnimationType::CustomShader;
-                    }
-                }
-            }
-        }
+        apply_animations_message(&mut self.settings.animations, msg);
 
         self.save.dirty_tracker.mark(SettingsCategory::Animations);
         self.mark_changed();
@@ -139,3 +37,149 @@ impl super::super::App {
         }
     }
 }
+
+/// Applies an animations message to the settings
+///
+/// The Animations page and the overview animation card on the Overview page
+/// both send these, so either way the edit lands in the same config.
+fn apply_animations_message(animations: &mut AnimationSettings, msg: AnimationsMessage) {
+    match msg {
+        AnimationsMessage::ToggleSlowdown(enabled) => {
+            // Toggle between slowdown factor and normal speed (1.0)
+            if enabled {
+                // Enable slowdown (if it's at 1.0, set to default 3.0)
+                if (animations.slowdown - 1.0).abs() < 0.01 {
+                    animations.slowdown = 3.0;
+                }
+            } else {
+                // Disable slowdown (set to 1.0 = normal speed)
+                animations.slowdown = 1.0;
+            }
+        }
+        AnimationsMessage::SetSlowdownFactor(value) => {
+            animations.slowdown = value.clamp(0.1, 10.0) as f64;
+        }
+        AnimationsMessage::SetAnimationEnabled(name, enabled) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                anim_id.get_mut(&mut animations.per_animation).enabled = enabled;
+            }
+        }
+        AnimationsMessage::SetAnimationDuration(name, duration) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                anim_config.easing.duration_ms = duration.clamp(50, 5000);
+            }
+        }
+        AnimationsMessage::SetAnimationCurve(name, curve_name) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                anim_config.easing.curve = EasingCurve::from_kdl(&curve_name);
+            }
+        }
+        AnimationsMessage::SetAnimationSpringDampingRatio(name, ratio) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                anim_config.spring.damping_ratio = ratio.clamp(0.1, 2.0) as f64;
+            }
+        }
+        AnimationsMessage::SetAnimationSpringEpsilon(name, epsilon) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                anim_config.spring.epsilon = epsilon.clamp(0.0001, 1.0) as f64;
+            }
+        }
+        AnimationsMessage::SetAnimationType(name, type_index) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                anim_config.animation_type = match type_index {
+                    0 => AnimationType::Default,
+                    1 => AnimationType::Spring,
+                    2 => AnimationType::Easing,
+                    3 if anim_id.supports_custom_shader() => AnimationType::CustomShader,
+                    _ => AnimationType::Default,
+                };
+            }
+        }
+        AnimationsMessage::SetCustomShader(name, code) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                if anim_id.supports_custom_shader() {
+                    let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                    anim_config.custom_shader = Some(code);
+                    anim_config.animation_type = AnimationType::CustomShader;
+                }
+            }
+        }
+        AnimationsMessage::ClearCustomShader(name) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                anim_config.custom_shader = None;
+                // Revert to default if clearing shader
+                if anim_config.animation_type == AnimationType::CustomShader {
+                    anim_config.animation_type = AnimationType::Default;
+                }
+            }
+        }
+        AnimationsMessage::InsertShaderTemplate(name) => {
+            if let Some(anim_id) = super::super::App::parse_animation_name(&name) {
+                if let Some(func_name) = anim_id.shader_function_name() {
+                    let template = format!(
+                        r#"vec4 {}(vec3 coords_geo, vec3 size_geo) {{
+    float progress = niri_clamped_progress;
+    // Your GLSL code here
+    return vec4(1.0);
+}}"#,
+                        func_name
+                    );
+                    let anim_config = anim_id.get_mut(&mut animations.per_animation);
+                    anim_config.custom_shader = Some(template);
+                    anim_config.animation_type = AnimationType::CustomShader;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::animations::OVERVIEW_ANIMATION;
+
+    #[test]
+    fn test_overview_page_edits_shared_animation_config() {
+        let mut animations = AnimationSettings::default();
+        let name = || OVERVIEW_ANIMATION.to_string();
+
+        // What the card on the Overview page sends
+        apply_animations_message(
+            &mut animations,
+            AnimationsMessage::SetAnimationType(name(), 2),
+        );
+        apply_animations_message(
+            &mut animations,
+            AnimationsMessage::SetAnimationDuration(name(), 400),
+        );
+
+        let overview = &animations.per_animation.overview_open_close;
+        assert_eq!(overview.animation_type, AnimationType::Easing);
+        assert_eq!(overview.easing.duration_ms, 400);
+        // Nothing else changed
+        assert_eq!(
+            animations.per_animation.workspace_switch,
+            AnimationSettings::default().per_animation.workspace_switch
+        );
+    }
+
+    #[test]
+    fn test_shader_template_body_is_indented() {
+        let mut animations = AnimationSettings::default();
+        apply_animations_message(
+            &mut animations,
+            AnimationsMessage::InsertShaderTemplate("window-open".to_string()),
+        );
+
+        let shader = animations.per_animation.window_open.custom_shader.unwrap();
+        assert!(shader.starts_with("vec4 open_color(vec3 coords_geo, vec3 size_geo) {\n"));
+        assert!(shader.contains("\n    float progress = niri_clamped_progress;\n"));
+        assert!(shader.contains("\n    return vec4(1.0);\n}"));
+    }
+}
@@ -1,77 +1,183 @@
 //! Appearance settings message handler
 
 use crate::app::helpers::apply_gradient_message;
+use crate::config::models::AppearanceSettings;
 use crate::config::SettingsCategory;
 use crate::messages::{AppearanceMessage, Message};
+use crate::types::ColorOrGradient;
 use iced::Task;
 
 impl super::super::App {
     /// Updates appearance settings
     pub(in crate::app) fn update_appearance(&mut self, msg: AppearanceMessage) -> Task<Message> {
-        match msg {
-            // Focus ring
-            AppearanceMessage::ToggleFocusRing(value) => {
-                self.settings.appearance.focus_ring_enabled = value;
-            }
-            AppearanceMessage::SetFocusRingWidth(value) => {
-                self.settings.appearance.focus_ring_width = value.clamp(1.0, 20.0);
-            }
-            AppearanceMessage::FocusRingActive(gradient_msg) => {
-                apply_gradient_message(
-                    &mut self.settings.appearance.focus_ring_active,
-                    gradient_msg,
-                );
-            }
-            AppearanceMessage::FocusRingInactive(gradient_msg) => {
-                apply_gradient_message(
-                    &mut self.settings.appearance.focus_ring_inactive,
-                    gradient_msg,
-                );
-            }
-            AppearanceMessage::FocusRingUrgent(gradient_msg) => {
-                apply_gradient_message(
-                    &mut self.settings.appearance.focus_ring_urgent,
-                    gradient_msg,
-                );
-            }
+        let was_linked = self.settings.preferences.link_border_colors;
+        apply_appearance_message(
+            &mut self.settings.appearance,
+            &mut self.settings.preferences.link_border_colors,
+            &mut self.ui.unlinked_border_colors,
+            msg,
+        );
 
-            // Border
-            AppearanceMessage::ToggleBorder(value) => {
-                self.settings.appearance.border_enabled = value;
-            }
-            AppearanceMessage::SetBorderThickness(value) => {
-                self.settings.appearance.border_thickness = value.clamp(1.0, 20.0);
-            }
-            AppearanceMessage::BorderActive(gradient_msg) => {
-                apply_gradient_message(&mut self.settings.appearance.border_active, gradient_msg);
-            }
-            AppearanceMessage::BorderInactive(gradient_msg) => {
-                apply_gradient_message(&mut self.settings.appearance.border_inactive, gradient_msg);
-            }
-            AppearanceMessage::BorderUrgent(gradient_msg) => {
-                apply_gradient_message(&mut self.settings.appearance.border_urgent, gradient_msg);
-            }
+        // Mark as dirty for auto-save
+        if self.settings.preferences.link_border_colors != was_linked {
+            self.save.dirty_tracker.mark(SettingsCategory::Preferences);
+        }
+        self.save.dirty_tracker.mark(SettingsCategory::Appearance);
+        self.mark_changed();
 
-            // Layout
-            AppearanceMessage::SetGaps(value) => {
-                self.settings.appearance.gaps = value.clamp(0.0, 64.0);
-            }
-            AppearanceMessage::SetCornerRadius(value) => {
-                self.settings.appearance.corner_radius = value.clamp(0.0, 32.0);
+        Task::none()
+    }
+}
+
+/// Applies an appearance message; while `link_border_colors` is set, the
+/// border colors follow the focus ring colors
+///
+/// Linking keeps the border's own colors in `unlinked_colors`, and unlinking
+/// puts them back.
+fn apply_appearance_message(
+    appearance: &mut AppearanceSettings,
+    link_border_colors: &mut bool,
+    unlinked_colors: &mut Option<[ColorOrGradient; 3]>,
+    msg: AppearanceMessage,
+) {
+    match msg {
+        // Focus ring
+        AppearanceMessage::ToggleFocusRing(value) => {
+            appearance.focus_ring_enabled = value;
+        }
+        AppearanceMessage::SetFocusRingWidth(value) => {
+            appearance.focus_ring_width = value.clamp(1.0, 20.0);
+        }
+        AppearanceMessage::FocusRingActive(gradient_msg) => {
+            apply_gradient_message(&mut appearance.focus_ring_active, gradient_msg);
+        }
+        AppearanceMessage::FocusRingInactive(gradient_msg) => {
+            apply_gradient_message(&mut appearance.focus_ring_inactive, gradient_msg);
+        }
+        AppearanceMessage::FocusRingUrgent(gradient_msg) => {
+            apply_gradient_message(&mut appearance.focus_ring_urgent, gradient_msg);
+        }
+
+        // Border
+        AppearanceMessage::ToggleBorder(value) => {
+            appearance.border_enabled = value;
+        }
+        AppearanceMessage::SetBorderThickness(value) => {
+            appearance.border_thickness = value.clamp(1.0, 20.0);
+        }
+        AppearanceMessage::BorderActive(gradient_msg) => {
+            apply_gradient_message(&mut appearance.border_active, gradient_msg);
+        }
+        AppearanceMessage::BorderInactive(gradient_msg) => {
+            apply_gradient_message(&mut appearance.border_inactive, gradient_msg);
+        }
+        AppearanceMessage::BorderUrgent(gradient_msg) => {
+            apply_gradient_message(&mut appearance.border_urgent, gradient_msg);
+        }
+        AppearanceMessage::LinkBorderColors(value) => {
+            if value && !*link_border_colors {
+                *unlinked_colors = Some([
+                    appearance.border_active.clone(),
+                    appearance.border_inactive.clone(),
+                    appearance.border_urgent.clone(),
+                ]);
+            } else if !value && *link_border_colors {
+                // Linked since startup, there's nothing to go back to
+                if let Some([active, inactive, urgent]) = unlinked_colors.take() {
+                    appearance.border_active = active;
+                    appearance.border_inactive = inactive;
+                    appearance.border_urgent = urgent;
+                }
             }
+            *link_border_colors = value;
+        }
+
+        // Layout
+        AppearanceMessage::SetGaps(value) => {
+            appearance.gaps = value.clamp(0.0, 64.0);
+        }
+        AppearanceMessage::SetCornerRadius(value) => {
+            appearance.corner_radius = value.clamp(0.0, 32.0);
+        }
 
-            // Background
-            AppearanceMessage::SetBackgroundColor(hex_opt) => {
-                use crate::types::Color;
-                self.settings.appearance.background_color =
-                    hex_opt.and_then(|hex| Color::from_hex(&hex));
+        // Background
+        AppearanceMessage::SetBackgroundColor(None) => {
+            appearance.background_color = None;
+        }
+        AppearanceMessage::SetBackgroundColor(Some(hex)) => {
+            // Half-typed hex keeps the last valid color instead of clearing it
+            if let Some(color) = crate::types::Color::parse(&hex) {
+                appearance.background_color = Some(color);
             }
         }
+    }
 
-        // Mark as dirty for auto-save
-        self.save.dirty_tracker.mark(SettingsCategory::Appearance);
-        self.mark_changed();
+    if *link_border_colors {
+        appearance.copy_focus_ring_colors_to_border();
+    }
+}
 
-        Task::none()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Color;
+    use crate::views::widgets::GradientPickerMessage;
+
+    fn color(hex: &str) -> ColorOrGradient {
+        ColorOrGradient::Color(Color::from_hex(hex).unwrap())
+    }
+
+    #[test]
+    fn test_linked_border_follows_focus_ring() {
+        let mut appearance = AppearanceSettings {
+            border_active: color("#ff0000"),
+            ..Default::default()
+        };
+        let original = appearance.clone();
+        let mut linked = false;
+        let mut stash = None;
+
+        apply_appearance_message(
+            &mut appearance,
+            &mut linked,
+            &mut stash,
+            AppearanceMessage::LinkBorderColors(true),
+        );
+        assert!(linked);
+        assert_eq!(appearance.border_active, appearance.focus_ring_active);
+        assert_eq!(appearance.border_inactive, appearance.focus_ring_inactive);
+        assert_eq!(appearance.border_urgent, appearance.focus_ring_urgent);
+
+        // Later focus ring edits carry over
+        apply_appearance_message(
+            &mut appearance,
+            &mut linked,
+            &mut stash,
+            AppearanceMessage::FocusRingActive(GradientPickerMessage::SetFromColor(
+                "#123456".to_string(),
+            )),
+        );
+        assert_eq!(appearance.focus_ring_active, color("#123456"));
+        assert_eq!(appearance.border_active, color("#123456"));
+
+        // Unlinking brings back the border's own colors, and it is edited
+        // on its own again
+        apply_appearance_message(
+            &mut appearance,
+            &mut linked,
+            &mut stash,
+            AppearanceMessage::LinkBorderColors(false),
+        );
+        apply_appearance_message(
+            &mut appearance,
+            &mut linked,
+            &mut stash,
+            AppearanceMessage::FocusRingActive(GradientPickerMessage::SetFromColor(
+                "#abcdef".to_string(),
+            )),
+        );
+        assert_eq!(appearance.border_active, original.border_active);
+        assert_eq!(appearance.border_inactive, original.border_inactive);
+        assert_eq!(appearance.border_urgent, original.border_urgent);
     }
 }
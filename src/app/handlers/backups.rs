@@ -1,6 +1,8 @@
 //! Backups message handler
 
+use crate::config::{ConfigPaths, RestoreOutcome};
 use crate::messages::{BackupEntry, BackupsMessage, DialogState, Message};
+use crate::save_manager::ReloadResult;
 use iced::Task;
 use std::path::PathBuf;
 
@@ -24,12 +26,16 @@ impl super::super::App {
                 match result {
                     Ok(backups) => {
                         let count = backups.len();
+                        self.ui.backups_state.snapshots = crate::config::backup::group_by_timestamp(
+                            backups.iter().map(|b| b.filename.as_str()),
+                        );
                         self.ui.backups_state.backups = backups;
                         self.ui.backups_state.status_message =
                             Some(format!("Found {} backup(s)", count));
                         // Clear selection when list refreshes
                         self.ui.backups_state.selected_backup = None;
                         self.ui.backups_state.preview_content = None;
+                        self.ui.backups_state.preview_diff = None;
                     }
                     Err(e) => {
                         self.ui.backups_state.status_message = Some(format!("Error: {}", e));
@@ -38,22 +44,73 @@ impl super::super::App {
                 Task::none()
             }
 
+            BackupsMessage::SetSnapshotLabel(label) => {
+                self.ui.backups_state.snapshot_label = label;
+                Task::none()
+            }
+
+            BackupsMessage::CreateSnapshot => {
+                self.ui.backups_state.creating_snapshot = true;
+                let paths = self.paths.clone();
+                let label = self.ui.backups_state.snapshot_label.clone();
+                Task::perform(
+                    async move {
+                        crate::config::backup::create_named_backup(&paths, &label)
+                            .map(|files| files.len())
+                            .map_err(|e| e.to_string())
+                    },
+                    |result| Message::Backups(BackupsMessage::SnapshotCreated(result)),
+                )
+            }
+
+            BackupsMessage::SnapshotCreated(result) => {
+                self.ui.backups_state.creating_snapshot = false;
+                match result {
+                    Ok(count) => {
+                        self.ui.backups_state.snapshot_label.clear();
+                        self.ui.toast = Some(format!("Snapshot of {} files created", count));
+                        self.ui.toast_shown_at = Some(std::time::Instant::now());
+                        Task::done(Message::Backups(BackupsMessage::RefreshList))
+                    }
+                    Err(e) => {
+                        self.ui.backups_state.status_message =
+                            Some(format!("Failed to create snapshot: {}", e));
+                        Task::none()
+                    }
+                }
+            }
+
             BackupsMessage::SelectBackup(idx) => {
                 self.ui.backups_state.selected_backup = Some(idx);
                 self.ui.backups_state.loading_preview = true;
                 self.ui.backups_state.preview_content = None;
+                self.ui.backups_state.preview_diff = None;
 
                 // Load preview
                 if let Some(backup) = self.ui.backups_state.backups.get(idx) {
                     let path = backup.path.clone();
-
-                    Task::perform(
-                        async move {
-                            std::fs::read_to_string(&path)
-                                .map_err(|e| format!("Failed to read backup: {}", e))
-                        },
-                        |result| Message::Backups(BackupsMessage::PreviewLoaded(result)),
-                    )
+                    let diff_path = backup.path.clone();
+                    let target = backup_target(backup, &self.paths);
+
+                    Task::batch([
+                        Task::perform(
+                            async move {
+                                std::fs::read_to_string(&path)
+                                    .map_err(|e| format!("Failed to read backup: {}", e))
+                            },
+                            |result| Message::Backups(BackupsMessage::PreviewLoaded(result)),
+                        ),
+                        Task::perform(
+                            async move {
+                                let target = target.ok_or_else(|| {
+                                    "No current file to compare this backup with".to_string()
+                                })?;
+                                crate::config::backup::diff(&diff_path, &target)
+                                    .map_err(|e| format!("Failed to compare backup: {}", e))
+                            },
+                            |result| Message::Backups(BackupsMessage::DiffLoaded(result)),
+                        ),
+                    ])
                 } else {
                     self.ui.backups_state.loading_preview = false;
                     Task::none()
@@ -66,55 +123,173 @@ impl super::super::App {
                 Task::none()
             }
 
+            BackupsMessage::DiffLoaded(result) => {
+                self.ui.backups_state.preview_diff = Some(result);
+                Task::none()
+            }
+
+            BackupsMessage::ShowDiff(show) => {
+                self.ui.backups_state.show_raw = !show;
+                Task::none()
+            }
+
             BackupsMessage::ConfirmRestore(idx) => {
                 if let Some(backup) = self.ui.backups_state.backups.get(idx) {
+                    let Some(target) = backup_target(backup, &self.paths) else {
+                        self.ui.backups_state.status_message = Some(format!(
+                            "Error: '{}' has nowhere to be restored to",
+                            backup.filename
+                        ));
+                        return Task::none();
+                    };
+                    let target_name = target.file_name().map_or_else(
+                        || target.display().to_string(),
+                        |name| name.to_string_lossy().into_owned(),
+                    );
                     // Show confirmation dialog
                     self.ui.dialog_state = DialogState::Confirm {
                         title: "Restore Backup".to_string(),
                         message: format!(
                             "Are you sure you want to restore '{}'?\n\n\
-                             This will overwrite your current config.kdl file. \
-                             A backup of your current config will be created first.",
-                            backup.filename
+                             This will overwrite your current {} file and reload niri. \
+                             A backup of your current config will be created first, and it \
+                             will be put back if niri rejects the restored config.",
+                            backup.filename, target_name
                         ),
                         confirm_label: "Restore".to_string(),
-                        on_confirm: crate::messages::ConfirmAction::ResetSettings, // We'll handle this specially
+                        on_confirm: crate::messages::ConfirmAction::RestoreBackup(idx),
                     };
-                    // Store the index for later
-                    self.ui.pending_restore_idx = Some(idx);
                 }
                 Task::none()
             }
 
-            BackupsMessage::RestoreBackup(idx) => {
+            BackupsMessage::ConfirmRestoreSnapshot(idx) => {
+                let files: Vec<String> = self
+                    .ui
+                    .backups_state
+                    .snapshot_of(idx)
+                    .iter()
+                    .filter_map(|&i| self.ui.backups_state.backups.get(i))
+                    .map(|backup| {
+                        crate::config::backup::parse_backup_name(&backup.filename)
+                            .map_or(backup.filename.as_str(), |(original, _)| original)
+                            .to_string()
+                    })
+                    .collect();
+                if !files.is_empty() {
+                    self.ui.dialog_state = DialogState::Confirm {
+                        title: "Restore Snapshot".to_string(),
+                        message: format!(
+                            "Restore these {} files as they were backed up together?\n\n{}\n\n\
+                             They are all written before niri reloads, and all put back if \
+                             niri rejects the result. Backups of the current files are \
+                             created first.",
+                            files.len(),
+                            files.join(", ")
+                        ),
+                        confirm_label: "Restore All".to_string(),
+                        on_confirm: crate::messages::ConfirmAction::RestoreSnapshot(idx),
+                    };
+                }
+                Task::none()
+            }
+
+            BackupsMessage::RestoreSnapshot(idx) => {
+                let state = &self.ui.backups_state;
+                let files: Option<Vec<(std::path::PathBuf, std::path::PathBuf)>> = state
+                    .snapshot_of(idx)
+                    .iter()
+                    .filter_map(|&i| state.backups.get(i))
+                    .map(|backup| {
+                        let (original, _) =
+                            crate::config::backup::parse_backup_name(&backup.filename)?;
+                        let target = crate::config::backup::restore_target(original, &self.paths)?;
+                        Some((backup.path.clone(), target))
+                    })
+                    .collect();
+                let Some(files) = files.filter(|files| !files.is_empty()) else {
+                    self.ui.backups_state.status_message = Some(
+                        "Error: Some files in this snapshot have nowhere to be restored to"
+                            .to_string(),
+                    );
+                    return Task::none();
+                };
+
                 self.ui.backups_state.restoring = true;
                 self.ui.backups_state.status_message = Some("Restoring...".to_string());
+                let backup_dir = self.paths.backup_dir.clone();
+                Task::perform(
+                    async move {
+                        crate::config::restore_set_with_rollback(&files, &backup_dir, || {
+                            crate::ipc::is_niri_running()
+                                .then(|| crate::ipc::reload_config().map_err(|e| e.to_string()))
+                        })
+                    },
+                    |result| Message::Backups(BackupsMessage::RestoreCompleted(result)),
+                )
+            }
 
-                if let Some(backup) = self.ui.backups_state.backups.get(idx) {
-                    let backup_path = backup.path.clone();
-                    let config_path = self.paths.niri_config.clone();
-                    let backup_dir = self.paths.backup_dir.clone();
-
-                    Task::perform(
-                        async move { restore_backup(&backup_path, &config_path, &backup_dir) },
-                        |result| Message::Backups(BackupsMessage::RestoreCompleted(result)),
-                    )
-                } else {
-                    self.ui.backups_state.restoring = false;
+            BackupsMessage::RestoreBackup(idx) => {
+                let Some(backup) = self.ui.backups_state.backups.get(idx) else {
                     self.ui.backups_state.status_message =
                         Some("Error: Backup not found".to_string());
-                    Task::none()
-                }
+                    return Task::none();
+                };
+                let Some(config_path) = backup_target(backup, &self.paths) else {
+                    self.ui.backups_state.status_message = Some(format!(
+                        "Error: '{}' has nowhere to be restored to",
+                        backup.filename
+                    ));
+                    return Task::none();
+                };
+                let backup_path = backup.path.clone();
+                let backup_dir = self.paths.backup_dir.clone();
+                self.ui.backups_state.restoring = true;
+                self.ui.backups_state.status_message = Some("Restoring...".to_string());
+
+                Task::perform(
+                    async move {
+                        crate::config::restore_with_rollback(
+                            &backup_path,
+                            &config_path,
+                            &backup_dir,
+                            || {
+                                crate::ipc::is_niri_running()
+                                    .then(|| crate::ipc::reload_config().map_err(|e| e.to_string()))
+                            },
+                        )
+                    },
+                    |result| Message::Backups(BackupsMessage::RestoreCompleted(result)),
+                )
             }
 
             BackupsMessage::RestoreCompleted(result) => {
                 self.ui.backups_state.restoring = false;
                 match result {
-                    Ok(()) => {
-                        self.ui.backups_state.status_message =
-                            Some("Backup restored successfully!".to_string());
-                        self.ui.toast =
-                            Some("Backup restored! Restart Nirify to see changes.".to_string());
+                    Ok(outcome) => {
+                        self.ui.backups_state.status_message = Some(outcome.status_message());
+                        match &outcome {
+                            RestoreOutcome::Applied { .. } => {
+                                self.ui.record_reload(ReloadResult::Success)
+                            }
+                            RestoreOutcome::RolledBack { reload_error, .. } => {
+                                self.ui.record_reload(ReloadResult::Error {
+                                    message: reload_error.clone(),
+                                })
+                            }
+                            RestoreOutcome::Unverified { .. } => {}
+                        }
+                        // Also takes the restored files as Nirify's own; a rolled
+                        // back restore leaves them as they were, outside edits
+                        // included
+                        if outcome.is_applied() {
+                            self.reload_settings_from_disk();
+                        }
+                        self.ui.toast = Some(if outcome.is_applied() {
+                            "Backup restored".to_string()
+                        } else {
+                            "Restore rolled back: niri rejected the backup".to_string()
+                        });
                         self.ui.toast_shown_at = Some(std::time::Instant::now());
                     }
                     Err(e) => {
@@ -122,12 +297,19 @@ impl super::super::App {
                             Some(format!("Failed to restore: {}", e));
                     }
                 }
-                Task::none()
+                // The restore added a safety backup to the list
+                Task::done(Message::Backups(BackupsMessage::RefreshList))
             }
         }
     }
 }
 
+/// The file `backup` was taken of, which restoring it overwrites
+fn backup_target(backup: &BackupEntry, paths: &ConfigPaths) -> Option<PathBuf> {
+    let (original, _) = crate::config::backup::parse_backup_name(&backup.filename)?;
+    crate::config::backup::restore_target(original, paths)
+}
+
 /// List all backups in the backup directory
 fn list_backups(backup_dir: &std::path::Path) -> Result<Vec<BackupEntry>, String> {
     if !backup_dir.exists() {
@@ -162,6 +344,7 @@ fn list_backups(backup_dir: &std::path::Path) -> Result<Vec<BackupEntry>, String
                     date,
                     size,
                     path: path.clone(),
+                    label: crate::config::backup::backup_label(filename).map(str::to_string),
                 });
             }
         }
@@ -235,55 +418,3 @@ fn format_file_size(bytes: u64) -> String {
         format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
     }
 }
-
-/// Restore a backup to the main config file
-fn restore_backup(
-    backup_path: &PathBuf,
-    config_path: &std::path::Path,
-    backup_dir: &std::path::Path,
-) -> Result<(), String> {
-    use chrono::Local;
-
-    // Read backup content first (validates it exists and is readable)
-    let backup_content = std::fs::read_to_string(backup_path)
-        .map_err(|e| format!("Failed to read backup file: {}", e))?;
-
-    // Validate backup contains valid KDL before restoring
-    if let Err(e) = backup_content.parse::<kdl::KdlDocument>() {
-        return Err(format!("Backup contains invalid KDL: {}", e));
-    }
-
-    // Create a backup of the current config (read first to avoid TOCTOU)
-    if config_path.exists() {
-        let current_content = std::fs::read_to_string(config_path)
-            .map_err(|e| format!("Failed to read current config: {}", e))?;
-
-        // Use microsecond precision to avoid timestamp collisions
-        let timestamp = Local::now().format("%Y%m%dT%H%M%S%.6f");
-        let current_backup_name = format!("config.kdl.backup-{}", timestamp);
-        let current_backup_path = backup_dir.join(current_backup_name);
-
-        // Ensure backup directory exists
-        if !backup_dir.exists() {
-            std::fs::create_dir_all(backup_dir)
-                .map_err(|e| format!("Failed to create backup directory: {}", e))?;
-        }
-
-        // Use atomic write for backup
-        crate::config::atomic_write(&current_backup_path, &current_content)
-            .map_err(|e| format!("Failed to backup current config: {}", e))?;
-
-        log::info!(
-            "Created backup of current config: {}",
-            current_backup_path.display()
-        );
-    }
-
-    // Write to config file using atomic write (safe against crashes)
-    crate::config::atomic_write(config_path, &backup_content)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
-
-    log::info!("Restored backup from: {}", backup_path.display());
-
-    Ok(())
-}
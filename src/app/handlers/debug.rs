@@ -7,6 +7,16 @@ use iced::Task;
 impl super::super::App {
     /// Handle debug settings messages
     pub(in crate::app) fn update_debug(&mut self, msg: DebugMessage) -> Task<Message> {
+        let restart_field = match &msg {
+            DebugMessage::SetRenderDrmDevice(_) => Some("render-drm-device"),
+            DebugMessage::AddIgnoreDrmDevice(_) | DebugMessage::RemoveIgnoreDrmDevice(_) => {
+                Some("ignore-drm-device")
+            }
+            DebugMessage::SetDbusInterfacesInNonSessionInstances(_) => {
+                Some("dbus-interfaces-in-non-session-instances")
+            }
+            _ => None,
+        };
         let debug = &mut self.settings.debug;
 
         match msg {
@@ -66,6 +76,9 @@ impl super::super::App {
 
         self.save.dirty_tracker.mark(SettingsCategory::Debug);
         self.mark_changed();
+        if let Some(field) = restart_field {
+            self.note_restart_required(SettingsCategory::Debug, field);
+        }
         Task::none()
     }
 }
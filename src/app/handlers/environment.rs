@@ -33,6 +33,9 @@ impl super::super::App {
                     var.value = value;
                 }
             }
+            EnvironmentMessage::Reorder(reorder) => {
+                reorder.apply(&mut env.variables);
+            }
         }
 
         self.save.dirty_tracker.mark(SettingsCategory::Environment);
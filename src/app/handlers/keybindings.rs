@@ -1,9 +1,10 @@
 //! Keybindings settings message handler
 
 use crate::app::helpers::{parse_spawn_command, validate_spawn_command};
-use crate::config::models::KeybindAction;
+use crate::config::models::{convert_action, KeybindAction, BINDING_TEMPLATES};
 use crate::config::SettingsCategory;
 use crate::messages::{KeybindingsMessage as M, Message};
+use crate::search::{EntryId, SettingEntry};
 use crate::types::ModKey;
 use iced::Task;
 
@@ -70,14 +71,32 @@ fn build_key_combo(modifiers: &[ModKey], base_key: &str) -> String {
 impl super::super::App {
     /// Updates keybindings settings
     pub(in crate::app) fn update_keybindings(&mut self, msg: M) -> Task<Message> {
+        // Binding whose search entry (title, combo, action) the edit changes
+        let reindex = match &msg {
+            M::UpdateModifiers(idx, _)
+            | M::UpdateAction(idx, _)
+            | M::SetActionKind(idx, _)
+            | M::SetActionArgs(idx, _)
+            | M::SetCommand(idx, _)
+            | M::SetKeyCombo(idx, _)
+            | M::SetHotkeyOverlayTitle(idx, _) => Some(*idx),
+            M::CapturedKey(_) | M::CommitKeyCapture => self.ui.key_capture_active,
+            _ => None,
+        };
+
         match msg {
             M::AddKeybinding => {
+                let id = self.settings.keybindings.next_id();
+                let action = "close-window";
                 let new_binding = crate::config::models::Keybinding {
-                    id: self.settings.keybindings.bindings.len() as u32,
+                    id,
                     key_combo: String::new(),
-                    action: KeybindAction::NiriAction("close-window".to_string()),
+                    repeat: crate::config::models::is_repeatable_action(action),
+                    action: KeybindAction::NiriAction(action.to_string()),
                     ..Default::default()
                 };
+                let (entry_id, entry) = SettingEntry::keybinding(&new_binding);
+                self.search_index.add_entry(entry_id, entry);
                 self.settings.keybindings.bindings.push(new_binding);
                 let new_idx = self.settings.keybindings.bindings.len() - 1;
                 self.ui.selected_keybinding_index = Some(new_idx);
@@ -87,7 +106,9 @@ impl super::super::App {
 
             M::RemoveKeybinding(idx) => {
                 if idx < self.settings.keybindings.bindings.len() {
-                    self.settings.keybindings.bindings.remove(idx);
+                    let removed = self.settings.keybindings.bindings.remove(idx);
+                    self.search_index
+                        .remove_entry(EntryId::Keybinding(removed.id));
                     if self.ui.selected_keybinding_index == Some(idx) {
                         self.ui.selected_keybinding_index =
                             if self.settings.keybindings.bindings.is_empty() {
@@ -120,22 +141,58 @@ impl super::super::App {
 
             M::StartKeyCapture(idx) => {
                 self.ui.key_capture_active = Some(idx);
+                self.ui.key_capture_pending = None;
                 // Don't mark dirty for UI-only changes
                 return Task::none();
             }
 
             M::CapturedKey(key_combo) => {
                 if let Some(idx) = self.ui.key_capture_active {
+                    // Hold an already-used combo back so it can be re-captured
+                    // or kept deliberately
+                    if let Some(other) = self.settings.keybindings.binding_on_combo(&key_combo, idx)
+                    {
+                        log::info!(
+                            "Captured {} for binding {}, already bound to {}",
+                            key_combo,
+                            idx,
+                            other.display_name()
+                        );
+                        self.ui.key_capture_pending = Some(key_combo);
+                        return Task::none();
+                    }
                     if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
                         binding.key_combo = key_combo;
                         log::info!("Captured key combo for binding {}", idx);
                     }
                 }
                 self.ui.key_capture_active = None;
+                self.ui.key_capture_pending = None;
+            }
+
+            M::CommitKeyCapture => {
+                let (Some(idx), Some(key_combo)) = (
+                    self.ui.key_capture_active.take(),
+                    self.ui.key_capture_pending.take(),
+                ) else {
+                    return Task::none();
+                };
+                if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
+                    binding.key_combo = key_combo;
+                    log::info!("Kept conflicting key combo for binding {}", idx);
+                }
+            }
+
+            M::SetKeyCombo(idx, key_combo) => {
+                if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
+                    binding.key_combo = key_combo;
+                    log::info!("Set key combo for binding {}", idx);
+                }
             }
 
             M::CancelKeyCapture => {
                 self.ui.key_capture_active = None;
+                self.ui.key_capture_pending = None;
                 // Don't mark dirty for UI-only changes
                 return Task::none();
             }
@@ -162,12 +219,39 @@ impl super::super::App {
                             }
                         }
                     } else {
-                        binding.action = KeybindAction::NiriAction(action_str);
+                        // Keep arguments when only the action name changes
+                        binding.action = match &binding.action {
+                            KeybindAction::NiriActionWithArgs(_, args) => {
+                                KeybindAction::NiriActionWithArgs(action_str, args.clone())
+                            }
+                            _ => KeybindAction::NiriAction(action_str),
+                        };
                         log::info!("Updated action for binding {}", idx);
                     }
                 }
             }
 
+            M::SetActionKind(idx, kind) => {
+                if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
+                    binding.action = convert_action(&binding.action, kind);
+                    log::info!("Changed action kind for binding {} to {:?}", idx, kind);
+                }
+            }
+
+            M::SetActionArgs(idx, value) => {
+                if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
+                    if let KeybindAction::NiriActionWithArgs(_, args) = &mut binding.action {
+                        match parse_spawn_command(&value) {
+                            Ok(parsed) => *args = parsed.args,
+                            Err(e) => {
+                                log::error!("Failed to parse arguments for binding {}: {}", idx, e);
+                                return Task::none();
+                            }
+                        }
+                    }
+                }
+            }
+
             M::SetCommand(idx, command) => {
                 if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
                     // Use proper command parsing with quote handling and validation
@@ -217,6 +301,58 @@ impl super::super::App {
                 }
             }
 
+            M::SetHotkeyOverlayHidden(idx, hidden) => {
+                if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
+                    binding.hotkey_overlay_hidden = hidden;
+                    log::info!("Set hotkey_overlay_hidden={} for binding {}", hidden, idx);
+                }
+            }
+
+            M::SetSection(idx, section) => {
+                if let Some(binding) = self.settings.keybindings.bindings.get_mut(idx) {
+                    binding.section = section;
+                    log::info!("Set section for binding {}", idx);
+                }
+            }
+
+            M::ToggleTemplateGallery => {
+                self.ui.show_binding_templates = !self.ui.show_binding_templates;
+                return Task::none();
+            }
+
+            M::ApplyTemplate(template_idx) => {
+                let Some(template) = BINDING_TEMPLATES.get(template_idx) else {
+                    return Task::none();
+                };
+                let total = (template.bindings)().len();
+                let added = self.settings.keybindings.insert_template(template);
+                for binding in &self.settings.keybindings.bindings[added.clone()] {
+                    let (entry_id, entry) = SettingEntry::keybinding(binding);
+                    self.search_index.add_entry(entry_id, entry);
+                }
+
+                let skipped = total - added.len();
+                self.ui.toast = Some(match skipped {
+                    0 => format!("Added {} bindings from \"{}\"", added.len(), template.name),
+                    _ => format!(
+                        "Added {} bindings from \"{}\" ({} already bound)",
+                        added.len(),
+                        template.name,
+                        skipped
+                    ),
+                });
+                self.ui.toast_shown_at = Some(std::time::Instant::now());
+                log::info!(
+                    "Applied keybinding template {:?}: {} added, {} skipped",
+                    template.name,
+                    added.len(),
+                    skipped
+                );
+                if added.is_empty() {
+                    return Task::none();
+                }
+            }
+
             M::ToggleSection(section) => {
                 let expanded = self
                     .ui
@@ -230,9 +366,20 @@ impl super::super::App {
                 // Don't mark dirty for UI-only changes
                 return Task::none();
             }
+
+            M::ToggleGroup(group) => {
+                let collapsed = &mut self.ui.keybinding_groups_collapsed;
+                if !collapsed.remove(&group) {
+                    collapsed.insert(group);
+                }
+                return Task::none();
+            }
         }
 
-        // Update the cache for view borrowing
+        if let Some(binding) = reindex.and_then(|idx| self.settings.keybindings.bindings.get(idx)) {
+            let (entry_id, entry) = SettingEntry::keybinding(binding);
+            self.search_index.update_entry(entry_id, entry);
+        }
 
         self.save.dirty_tracker.mark(SettingsCategory::Keybindings);
         self.mark_changed();
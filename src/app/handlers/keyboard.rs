@@ -14,8 +14,11 @@ impl super::super::App {
             KeyboardMessage::SetXkbVariant(value) => {
                 self.settings.keyboard.xkb_variant = value;
             }
-            KeyboardMessage::SetXkbOptions(value) => {
-                self.settings.keyboard.xkb_options = value;
+            KeyboardMessage::ToggleXkbOption(option, enabled) => {
+                self.settings.keyboard.set_xkb_option(&option, enabled);
+            }
+            KeyboardMessage::SetXkbExtraOptions(value) => {
+                self.settings.keyboard.set_extra_xkb_options(&value);
             }
             KeyboardMessage::SetXkbModel(value) => {
                 self.settings.keyboard.xkb_model = value;
@@ -38,6 +41,11 @@ impl super::super::App {
             KeyboardMessage::SetNumlock(value) => {
                 self.settings.keyboard.numlock = value;
             }
+            KeyboardMessage::SetTestInput(value) => {
+                // Echo area only; typing here doesn't touch the config
+                self.ui.keyboard_test_input = value;
+                return Task::none();
+            }
         }
 
         self.save.dirty_tracker.mark(SettingsCategory::Keyboard);
@@ -2,11 +2,23 @@
 
 use crate::config::SettingsCategory;
 use crate::messages::{LayerRulesMessage as M, Message};
+use crate::search::{EntryId, SettingEntry};
+use crate::types::Color;
+use crate::views::widgets::{move_down, move_up};
 use iced::Task;
 
 impl super::super::App {
     /// Updates layer rules settings
     pub(in crate::app) fn update_layer_rules(&mut self, msg: M) -> Task<Message> {
+        // Rule whose search entry (name, namespaces) the edit changes
+        let reindex = match &msg {
+            M::SetRuleName(id, _)
+            | M::AddMatch(id)
+            | M::RemoveMatch(id, _)
+            | M::SetMatchNamespace(id, ..) => Some(*id),
+            _ => None,
+        };
+
         match msg {
             M::AddRule => {
                 let new_rule = crate::config::models::LayerRule {
@@ -14,6 +26,8 @@ impl super::super::App {
                     ..Default::default()
                 };
                 self.settings.layer_rules.next_id += 1;
+                let (entry_id, entry) = SettingEntry::layer_rule(&new_rule);
+                self.search_index.add_entry(entry_id, entry);
                 self.settings.layer_rules.rules.push(new_rule.clone());
                 self.ui.selected_layer_rule_id = Some(new_rule.id);
                 self.ui.editing_layer_rule_id = Some(new_rule.id);
@@ -22,6 +36,7 @@ impl super::super::App {
 
             M::DeleteRule(rule_id) => {
                 self.settings.layer_rules.remove(rule_id);
+                self.search_index.remove_entry(EntryId::LayerRule(rule_id));
                 if self.ui.selected_layer_rule_id == Some(rule_id) {
                     self.ui.selected_layer_rule_id =
                         self.settings.layer_rules.rules.first().map(|r| r.id);
@@ -39,6 +54,8 @@ impl super::super::App {
                     new_rule.id = self.settings.layer_rules.next_id;
                     self.settings.layer_rules.next_id += 1;
                     new_rule.name = format!("{} (copy)", new_rule.name);
+                    let (entry_id, entry) = SettingEntry::layer_rule(&new_rule);
+                    self.search_index.add_entry(entry_id, entry);
                     self.settings.layer_rules.rules.push(new_rule.clone());
                     self.ui.selected_layer_rule_id = Some(new_rule.id);
                     log::info!("Duplicated layer rule {} to {}", rule_id, new_rule.id);
@@ -58,6 +75,7 @@ impl super::super::App {
 
             M::CloseEditor => {
                 self.ui.editing_layer_rule_id = None;
+                self.ui.layer_rule_color_drafts.clear();
                 return Task::none();
             }
 
@@ -71,7 +89,7 @@ impl super::super::App {
                 return Task::none();
             }
 
-            M::ReorderRule(rule_id, move_up) => {
+            M::ReorderRule(rule_id, up) => {
                 if let Some(idx) = self
                     .settings
                     .layer_rules
@@ -79,17 +97,11 @@ impl super::super::App {
                     .iter()
                     .position(|r| r.id == rule_id)
                 {
-                    let new_idx = if move_up && idx > 0 {
-                        idx - 1
-                    } else if !move_up && idx < self.settings.layer_rules.rules.len() - 1 {
-                        idx + 1
+                    let rules = &mut self.settings.layer_rules.rules;
+                    if up {
+                        move_up(rules, idx);
                     } else {
-                        idx
-                    };
-
-                    if new_idx != idx {
-                        let rule = self.settings.layer_rules.rules.remove(idx);
-                        self.settings.layer_rules.rules.insert(new_idx, rule);
+                        move_down(rules, idx);
                     }
                 }
             }
@@ -171,6 +183,28 @@ impl super::super::App {
                 }
             }
 
+            M::SetShadowColor(rule_id, field_name, hex) => {
+                let color = Color::parse(&hex);
+                self.ui
+                    .layer_rule_color_drafts
+                    .insert((rule_id, field_name.clone()), hex);
+                // A half-typed color only updates the draft
+                let Some(color) = color else {
+                    return Task::none();
+                };
+                if let Some(shadow) = self
+                    .settings
+                    .layer_rules
+                    .find_mut(rule_id)
+                    .and_then(|rule| rule.shadow.as_mut())
+                {
+                    match field_name.as_str() {
+                        "inactive-color" => shadow.inactive_color = color,
+                        _ => shadow.color = color,
+                    }
+                }
+            }
+
             M::ToggleSection(rule_id, section_name) => {
                 let key = (rule_id, section_name);
                 let expanded = self
@@ -209,6 +243,11 @@ impl super::super::App {
             }
         }
 
+        if let Some(rule) = reindex.and_then(|id| self.settings.layer_rules.find(id)) {
+            let (entry_id, entry) = SettingEntry::layer_rule(rule);
+            self.search_index.update_entry(entry_id, entry);
+        }
+
         self.save.dirty_tracker.mark(SettingsCategory::LayerRules);
         self.mark_changed();
         Task::none()
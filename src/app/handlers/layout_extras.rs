@@ -24,12 +24,12 @@ impl super::super::App {
                 layout.shadow.draw_behind_window = v
             }
             LayoutExtrasMessage::SetShadowColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     layout.shadow.color = color;
                 }
             }
             LayoutExtrasMessage::SetShadowInactiveColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     layout.shadow.inactive_color = color;
                 }
             }
@@ -57,17 +57,17 @@ impl super::super::App {
             }
             LayoutExtrasMessage::SetTabIndicatorPosition(v) => layout.tab_indicator.position = v,
             LayoutExtrasMessage::SetTabIndicatorActiveColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     layout.tab_indicator.active = ColorOrGradient::Color(color);
                 }
             }
             LayoutExtrasMessage::SetTabIndicatorInactiveColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     layout.tab_indicator.inactive = ColorOrGradient::Color(color);
                 }
             }
             LayoutExtrasMessage::SetTabIndicatorUrgentColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     layout.tab_indicator.urgent = ColorOrGradient::Color(color);
                 }
             }
@@ -75,7 +75,7 @@ impl super::super::App {
             // Insert hint
             LayoutExtrasMessage::SetInsertHintEnabled(v) => layout.insert_hint.enabled = v,
             LayoutExtrasMessage::SetInsertHintColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     layout.insert_hint.color = ColorOrGradient::Color(color);
                 }
             }
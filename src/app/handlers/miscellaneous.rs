@@ -10,6 +10,8 @@ impl super::super::App {
         &mut self,
         msg: MiscellaneousMessage,
     ) -> Task<Message> {
+        let restart_field = matches!(msg, MiscellaneousMessage::SetHotkeyOverlaySkipAtStartup(_))
+            .then_some("skip-at-startup");
         let misc = &mut self.settings.miscellaneous;
 
         match msg {
@@ -35,6 +37,9 @@ impl super::super::App {
             .dirty_tracker
             .mark(SettingsCategory::Miscellaneous);
         self.mark_changed();
+        if let Some(field) = restart_field {
+            self.note_restart_required(SettingsCategory::Miscellaneous, field);
+        }
         Task::none()
     }
 }
@@ -20,6 +20,7 @@ mod miscellaneous;
 mod mouse;
 mod outputs;
 mod overview;
+mod paste_config;
 mod preferences;
 mod recent_windows;
 mod startup;
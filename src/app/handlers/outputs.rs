@@ -1,7 +1,9 @@
 //! Outputs (displays) settings message handler
 
+use crate::config::models::configured_output_size;
 use crate::config::SettingsCategory;
 use crate::messages::{Message, OutputsMessage as M, ToolsMessage};
+use crate::views::screens::displays::{self, OutputDrag};
 use iced::Task;
 
 impl super::super::App {
@@ -49,12 +51,89 @@ impl super::super::App {
                 return Task::none();
             }
 
+            M::ApplyArrangement(preset) => {
+                let outputs = &mut self.settings.outputs.outputs;
+                let enabled: Vec<usize> = (0..outputs.len())
+                    .filter(|&idx| outputs[idx].enabled)
+                    .collect();
+                let sizes: Vec<(u32, u32)> = enabled
+                    .iter()
+                    .map(|&idx| {
+                        let (w, h) =
+                            configured_output_size(&outputs[idx], &self.ui.tools_state.outputs);
+                        (w.max(0) as u32, h.max(0) as u32)
+                    })
+                    .collect();
+                let Some(positions) = preset.positions(&sizes) else {
+                    return Task::none();
+                };
+                for (idx, (x, y)) in enabled.into_iter().zip(positions) {
+                    outputs[idx].set_position(x, y);
+                }
+                log::info!("Arranged outputs: {}", preset.name());
+            }
+
+            M::StartDrag(idx, frame) => {
+                if let Some(output) = self.settings.outputs.outputs.get(idx) {
+                    let origin = (output.position_x, output.position_y);
+                    self.ui.output_drag = Some(OutputDrag::new(idx, frame, origin));
+                }
+                return Task::none();
+            }
+
+            M::Drag(point) => {
+                let Some(drag) = self.ui.output_drag.as_mut() else {
+                    return Task::none();
+                };
+                let Some(output) = self.settings.outputs.outputs.get(drag.index) else {
+                    return Task::none();
+                };
+                if drag.anchor.is_none() {
+                    drag.anchor = Some(point);
+                    return Task::none();
+                }
+                let (x, y) = drag.target(point);
+                let available = &self.ui.tools_state.outputs;
+                let others = displays::snap_targets(&self.settings.outputs, available, drag.index);
+                drag.position = displays::output_rect(output, available, x, y).snapped(&others);
+                return Task::none();
+            }
+
+            M::EndDrag => {
+                let Some(drag) = self.ui.output_drag.take() else {
+                    return Task::none();
+                };
+                if drag.position == drag.origin {
+                    return Task::none();
+                }
+                let Some(output) = self.settings.outputs.outputs.get_mut(drag.index) else {
+                    return Task::none();
+                };
+                output.set_position(drag.position.0, drag.position.1);
+                log::info!(
+                    "Moved output {} to {}, {}",
+                    output.name,
+                    drag.position.0,
+                    drag.position.1
+                );
+                let apply = self.apply_output_live(drag.index);
+                self.save.dirty_tracker.mark(SettingsCategory::Outputs);
+                self.mark_changed();
+                return apply;
+            }
+
             M::SetOutputName(idx, name) => {
                 if let Some(output) = self.settings.outputs.outputs.get_mut(idx) {
                     output.name = name;
                 }
             }
 
+            M::SetMatchByIdentity(idx, value) => {
+                if let Some(output) = self.settings.outputs.outputs.get_mut(idx) {
+                    output.match_by_identity = value;
+                }
+            }
+
             M::SetEnabled(idx, value) => {
                 if let Some(output) = self.settings.outputs.outputs.get_mut(idx) {
                     output.enabled = value;
@@ -87,13 +166,13 @@ impl super::super::App {
 
             M::SetPositionX(idx, value) => {
                 if let Some(output) = self.settings.outputs.outputs.get_mut(idx) {
-                    output.position_x = value;
+                    output.set_position(value, output.position_y);
                 }
             }
 
             M::SetPositionY(idx, value) => {
                 if let Some(output) = self.settings.outputs.outputs.get_mut(idx) {
-                    output.position_y = value;
+                    output.set_position(output.position_x, value);
                 }
             }
 
@@ -109,10 +188,8 @@ impl super::super::App {
                 }
             }
 
-            M::SetFocusAtStartup(idx, value) => {
-                if let Some(output) = self.settings.outputs.outputs.get_mut(idx) {
-                    output.focus_at_startup = value;
-                }
+            M::SetStartupFocus(index) => {
+                self.settings.outputs.set_startup_focus(index);
             }
 
             M::SetBackdropColor(idx, value) => {
@@ -208,11 +285,30 @@ impl super::super::App {
             M::OpenEditor(idx) => {
                 self.ui.editing_output_index = Some(idx);
                 self.ui.selected_output_index = Some(idx);
+                self.ui.editing_output_original = self.settings.outputs.outputs.get(idx).cloned();
                 return Task::none();
             }
 
             M::CloseEditor => {
-                self.ui.editing_output_index = None;
+                let Some(idx) = self.ui.editing_output_index.take() else {
+                    return Task::none();
+                };
+                let original = self.ui.editing_output_original.take();
+                if original.as_ref() == self.settings.outputs.outputs.get(idx) {
+                    return Task::none();
+                }
+                return self.apply_output_live(idx);
+            }
+
+            M::AppliedLive(result) => {
+                if let Err(e) = result {
+                    log::warn!("Couldn't apply output changes live: {}", e);
+                    self.ui.toast = Some(format!(
+                        "Display changes will apply on the next reload ({})",
+                        e
+                    ));
+                    self.ui.toast_shown_at = Some(std::time::Instant::now());
+                }
                 return Task::none();
             }
         }
@@ -223,4 +319,35 @@ impl super::super::App {
         self.mark_changed();
         Task::none()
     }
+
+    /// Applies output `idx` to niri now instead of waiting for the save's
+    /// reload, which redoes every monitor; the save still persists it
+    ///
+    /// Nothing happens unless niri is running with the output connected.
+    fn apply_output_live(&self, idx: usize) -> Task<Message> {
+        let is_connected = matches!(
+            self.ui.niri_status,
+            crate::views::status_bar::NiriStatus::Connected
+        );
+        match self.settings.outputs.outputs.get(idx) {
+            Some(output)
+                if is_connected
+                    && self
+                        .ui
+                        .tools_state
+                        .outputs
+                        .iter()
+                        .any(|live| live.name == output.name) =>
+            {
+                let output = output.clone();
+                Task::perform(
+                    async move {
+                        crate::ipc::set_output(&output.name, &output).map_err(|e| e.to_string())
+                    },
+                    |result| Message::Outputs(M::AppliedLive(result)),
+                )
+            }
+            _ => Task::none(),
+        }
+    }
 }
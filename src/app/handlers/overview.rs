@@ -16,7 +16,7 @@ impl super::super::App {
             }
 
             M::SetBackdropColor(color_hex) => {
-                self.settings.overview.backdrop_color = color_hex.and_then(|h| Color::from_hex(&h));
+                self.settings.overview.backdrop_color = color_hex.and_then(|h| Color::parse(&h));
                 log::info!("Set overview backdrop color");
             }
 
@@ -67,7 +67,7 @@ impl super::super::App {
 
             M::SetWorkspaceShadowColor(color_hex) => {
                 if let Some(ref mut shadow) = self.settings.overview.workspace_shadow {
-                    if let Some(color) = Color::from_hex(&color_hex) {
+                    if let Some(color) = Color::parse(&color_hex) {
                         shadow.color = color;
                     }
                 }
@@ -0,0 +1,119 @@
+//! Pasted config import message handler
+
+use crate::messages::{DialogState, Message, PasteConfigMessage};
+use iced::widget::text_editor;
+use iced::Task;
+
+impl super::super::App {
+    /// Updates the paste config dialog
+    pub(in crate::app) fn update_paste_config(&mut self, msg: PasteConfigMessage) -> Task<Message> {
+        match msg {
+            PasteConfigMessage::Open => {
+                self.ui.paste_config_content = text_editor::Content::new();
+                self.ui.dialog_state = DialogState::PasteConfig {
+                    error: None,
+                    preview: None,
+                };
+            }
+
+            PasteConfigMessage::Edit(action) => {
+                self.ui.paste_config_content.perform(action);
+            }
+
+            PasteConfigMessage::Preview => {
+                let result = crate::config::import_from_str(&self.ui.paste_config_content.text());
+                self.ui.dialog_state = match result.parse_error.clone() {
+                    Some(error) => DialogState::PasteConfig {
+                        error: Some(error),
+                        preview: None,
+                    },
+                    None => DialogState::PasteConfig {
+                        error: None,
+                        preview: Some(Box::new(result)),
+                    },
+                };
+            }
+
+            PasteConfigMessage::Back => {
+                self.ui.dialog_state = DialogState::PasteConfig {
+                    error: None,
+                    preview: None,
+                };
+            }
+
+            PasteConfigMessage::ChooseFile => {
+                return Task::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("KDL", &["kdl"])
+                            .pick_file()
+                    },
+                    |path| Message::PasteConfig(PasteConfigMessage::FileChosen(path)),
+                );
+            }
+
+            PasteConfigMessage::FileChosen(None) => {}
+
+            PasteConfigMessage::FileChosen(Some(path)) => {
+                // Shown in the paste box too, so Back leaves it there to edit
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => {
+                        self.ui.paste_config_content = text_editor::Content::with_text(&text);
+                        // Read from disk so its includes are followed
+                        let result = crate::config::import_from_niri_config_with_result(&path);
+                        self.ui.dialog_state = DialogState::PasteConfig {
+                            error: None,
+                            preview: Some(Box::new(result)),
+                        };
+                    }
+                    Err(e) => {
+                        self.ui.paste_config_content = text_editor::Content::new();
+                        self.ui.dialog_state = DialogState::PasteConfig {
+                            error: Some(format!("Could not read {}: {}", path.display(), e)),
+                            preview: None,
+                        };
+                    }
+                }
+            }
+
+            PasteConfigMessage::Replace => {
+                let DialogState::PasteConfig {
+                    preview: Some(result),
+                    ..
+                } = std::mem::take(&mut self.ui.dialog_state)
+                else {
+                    return Task::none();
+                };
+
+                // Preferences belong to this app, not the niri config
+                let preferences = std::mem::take(&mut self.settings.preferences);
+                self.settings = result.settings.clone();
+                self.settings.preferences = preferences;
+                self.apply_import("Replaced settings with the imported config".to_string());
+            }
+
+            PasteConfigMessage::Merge(strategy) => {
+                let DialogState::PasteConfig {
+                    preview: Some(result),
+                    ..
+                } = std::mem::take(&mut self.ui.dialog_state)
+                else {
+                    return Task::none();
+                };
+
+                let merged = result.merge_with(&mut self.settings, strategy);
+                self.apply_import(merged.summary());
+            }
+        }
+        Task::none()
+    }
+
+    /// Saves settings an import just changed and says what it did
+    fn apply_import(&mut self, toast: String) {
+        self.search_index.rebuild(&self.settings);
+        self.save.dirty_tracker.mark_all();
+        self.mark_changed();
+        self.ui.toast = Some(toast);
+        self.ui.toast_shown_at = Some(std::time::Instant::now());
+    }
+}
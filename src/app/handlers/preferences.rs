@@ -2,7 +2,12 @@
 //!
 //! Handles app preferences like float/tile behavior.
 
+use crate::config::models::BackupRetention;
 use crate::config::SettingsCategory;
+use crate::constants::{
+    BACKUP_RETENTION_DAYS_MAX, BACKUP_RETENTION_DAYS_MIN, BACKUP_RETENTION_MAX,
+    BACKUP_RETENTION_MIN, DEFAULT_BACKUP_RETENTION_DAYS,
+};
 use crate::messages::{Message, PreferencesMessage};
 use iced::Task;
 
@@ -116,6 +121,75 @@ impl super::super::App {
 
                 Task::none()
             }
+
+            PreferencesMessage::SetEmitAllDefaults(emit) => {
+                self.settings.preferences.emit_all_defaults = emit;
+
+                // Mark preferences as dirty for auto-save
+                self.save.dirty_tracker.mark(SettingsCategory::Preferences);
+                // Rewrite the generated files this affects in the new style
+                self.save.dirty_tracker.mark_many(&[
+                    SettingsCategory::Keyboard,
+                    SettingsCategory::Mouse,
+                    SettingsCategory::Touchpad,
+                    SettingsCategory::Trackpoint,
+                    SettingsCategory::Trackball,
+                ]);
+                self.mark_changed();
+
+                Task::none()
+            }
+
+            PreferencesMessage::ToggleFavorite(setting_name) => {
+                self.settings.preferences.toggle_favorite(&setting_name);
+
+                // Mark preferences as dirty for auto-save
+                self.save.dirty_tracker.mark(SettingsCategory::Preferences);
+                self.mark_changed();
+
+                Task::none()
+            }
+
+            PreferencesMessage::SetBackupRetention(retention) => {
+                self.settings.preferences.backup_retention = match retention {
+                    BackupRetention::Count(keep) => BackupRetention::Count(
+                        keep.clamp(BACKUP_RETENTION_MIN, BACKUP_RETENTION_MAX),
+                    ),
+                    BackupRetention::Age(_) => BackupRetention::days(
+                        retention
+                            .age_days()
+                            .unwrap_or(DEFAULT_BACKUP_RETENTION_DAYS)
+                            .clamp(BACKUP_RETENTION_DAYS_MIN, BACKUP_RETENTION_DAYS_MAX),
+                    ),
+                    BackupRetention::Unlimited => BackupRetention::Unlimited,
+                };
+
+                // Mark preferences as dirty for auto-save
+                self.save.dirty_tracker.mark(SettingsCategory::Preferences);
+                self.mark_changed();
+
+                Task::none()
+            }
+
+            PreferencesMessage::SetMatchNiriColors(matched) => {
+                self.settings.preferences.match_niri_colors = matched;
+
+                // Mark preferences as dirty for auto-save
+                self.save.dirty_tracker.mark(SettingsCategory::Preferences);
+                self.mark_changed();
+
+                Task::none()
+            }
+
+            PreferencesMessage::SetAutoReload(enabled) => {
+                self.settings.preferences.auto_reload = enabled;
+
+                // Mark preferences as dirty for auto-save
+                self.save.dirty_tracker.mark(SettingsCategory::Preferences);
+                self.mark_changed();
+
+                Task::none()
+            }
         }
     }
 }
@@ -20,12 +20,12 @@ impl super::super::App {
 
             // Highlight settings
             RecentWindowsMessage::SetActiveColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     recent.highlight.active_color = color;
                 }
             }
             RecentWindowsMessage::SetUrgentColor(hex) => {
-                if let Some(color) = crate::types::Color::from_hex(&hex) {
+                if let Some(color) = crate::types::Color::parse(&hex) {
                     recent.highlight.urgent_color = color;
                 }
             }
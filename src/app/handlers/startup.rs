@@ -45,10 +45,14 @@ impl super::super::App {
                     }
                 }
             }
+            StartupMessage::Reorder(reorder) => {
+                reorder.apply(&mut startup.commands);
+            }
         }
 
         self.save.dirty_tracker.mark(SettingsCategory::Startup);
         self.mark_changed();
+        self.note_restart_required(SettingsCategory::Startup, "spawn-at-startup");
         Task::none()
     }
 }
@@ -1,5 +1,6 @@
 //! Tablet settings message handler
 
+use crate::app::helpers::apply_focused_output;
 use crate::config::SettingsCategory;
 use crate::messages::{Message, TabletMessage};
 use crate::views::widgets::format_matrix_values;
@@ -14,6 +15,17 @@ impl super::super::App {
             TabletMessage::SetOff(v) => tablet.off = v,
             TabletMessage::SetLeftHanded(v) => tablet.left_handed = v,
             TabletMessage::SetMapToOutput(v) => tablet.map_to_output = v,
+            TabletMessage::MapToFocusedOutput => {
+                return Task::perform(
+                    async { crate::ipc::get_focused_output().map_err(|e| e.to_string()) },
+                    |result| Message::Tablet(TabletMessage::FocusedOutputLoaded(result)),
+                );
+            }
+            TabletMessage::FocusedOutputLoaded(result) => {
+                if !apply_focused_output(&mut tablet.map_to_output, result) {
+                    return Task::none();
+                }
+            }
             TabletMessage::SetCalibrationMatrix(v) => {
                 tablet.calibration_matrix = v;
                 // Update cache
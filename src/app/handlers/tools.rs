@@ -1,7 +1,16 @@
 //! Tools page message handler (IPC operations)
 
+use crate::app::helpers::refresh_live_outputs;
+use crate::config::models::SCREENCAST_BLOCK_RULE_NAME;
+use crate::config::SettingsCategory;
+use crate::ipc::effective::{compare_outputs, ConfigEffectiveness};
+use crate::ipc::NiriEvent;
 use crate::messages::{Message, ToolsMessage};
+use crate::save_manager::ReloadResult;
+use crate::search::SettingEntry;
+use crate::views::status_bar::NiriStatus;
 use iced::Task;
+use std::path::Path;
 
 impl super::super::App {
     /// Handle tools page messages (IPC operations)
@@ -80,7 +89,23 @@ impl super::super::App {
                 self.ui.tools_state.loading_outputs = false;
                 match result {
                     Ok(outputs) => {
-                        self.ui.tools_state.outputs = outputs;
+                        self.ui.available_outputs =
+                            outputs.iter().map(|o| o.name.clone()).collect();
+                        let refresh = refresh_live_outputs(
+                            &mut self.ui.tools_state.outputs,
+                            outputs,
+                            &mut self.settings.outputs.outputs,
+                        );
+                        if !refresh.disconnected.is_empty() {
+                            log::debug!(
+                                "Configured outputs not connected: {:?}",
+                                refresh.disconnected
+                            );
+                        }
+                        if refresh.configured_changed {
+                            self.save.dirty_tracker.mark(SettingsCategory::Outputs);
+                            self.mark_changed();
+                        }
                     }
                     Err(e) => {
                         self.ui.tools_state.last_error = Some(e);
@@ -111,6 +136,15 @@ impl super::super::App {
                 }
                 Task::none()
             }
+            ToolsMessage::KeyboardLayoutsLoaded(result) => {
+                match result {
+                    Ok(layouts) => self.ui.tools_state.keyboard_layouts = Some(layouts),
+                    // Only the Keyboard page shows these, and it falls back
+                    // to the configured layouts
+                    Err(e) => log::debug!("Could not read keyboard layouts: {}", e),
+                }
+                Task::none()
+            }
 
             // Actions
             ToolsMessage::ReloadConfig => {
@@ -131,6 +165,20 @@ impl super::super::App {
                 )
             }
 
+            ToolsMessage::CheckConfigEffective => {
+                self.ui.tools_state.checking_effective = true;
+                self.ui.tools_state.effective_result = None;
+                Task::perform(
+                    async { crate::ipc::get_full_outputs().map_err(|e| e.to_string()) },
+                    |result| Message::Tools(ToolsMessage::EffectiveOutputsLoaded(result)),
+                )
+            }
+
+            ToolsMessage::AdoptLayoutFromNiri => Task::perform(
+                async { crate::ipc::get_layout_info().map_err(|e| e.to_string()) },
+                |result| Message::Tools(ToolsMessage::LayoutInfoLoaded(result)),
+            ),
+
             // Action results
             ToolsMessage::ReloadCompleted(result) => {
                 self.ui.tools_state.reloading = false;
@@ -138,18 +186,233 @@ impl super::super::App {
                     Ok(()) => {
                         self.ui.toast = Some("Config reloaded successfully".to_string());
                         self.ui.toast_shown_at = Some(std::time::Instant::now());
+                        self.ui.record_reload(ReloadResult::Success);
                     }
                     Err(e) => {
                         self.ui.tools_state.last_error = Some(format!("Reload failed: {}", e));
+                        self.ui.record_reload(ReloadResult::Error { message: e });
                     }
                 }
                 Task::none()
             }
+            ToolsMessage::LintConfig => {
+                self.ui.tools_state.lint_results = Some(crate::config::lint(&self.settings));
+                Task::none()
+            }
+            ToolsMessage::ToggleEffectiveConfig => {
+                let state = &mut self.ui.tools_state;
+                state.effective_config = match state.effective_config {
+                    Some(_) => None,
+                    None => Some(crate::config::render_effective(&self.paths)),
+                };
+                Task::none()
+            }
+            ToolsMessage::ExportConfig => {
+                self.ui.tools_state.exporting = true;
+                self.ui.tools_state.export_result = None;
+                let kdl = crate::config::export_standalone(&self.settings, self.ui.feature_compat);
+                Task::perform(async move { export_to_chosen_file(kdl) }, |result| {
+                    Message::Tools(ToolsMessage::ExportCompleted(result))
+                })
+            }
+            ToolsMessage::ExportCompleted(result) => {
+                self.ui.tools_state.exporting = false;
+                self.ui.tools_state.export_result = result;
+                Task::none()
+            }
+            ToolsMessage::OpenLintTarget(category) => {
+                self.open_page(crate::messages::Page::for_category(category));
+                self.restore_page_scroll()
+            }
             ToolsMessage::ValidateCompleted(result) => {
                 self.ui.tools_state.validating = false;
+                self.ui.tools_state.validation_targets = match &result {
+                    Ok(report) => report
+                        .errors
+                        .iter()
+                        .map(|e| self.paths.category_for_path(Path::new(&e.file)))
+                        .collect(),
+                    Err(_) => Vec::new(),
+                };
                 self.ui.tools_state.validation_result = Some(result);
                 Task::none()
             }
+            ToolsMessage::EffectiveOutputsLoaded(result) => {
+                let live = match result {
+                    Ok(outputs) => outputs,
+                    Err(e) => {
+                        self.ui.tools_state.checking_effective = false;
+                        self.ui.tools_state.effective_result =
+                            Some(Err(format!("Could not query niri: {}", e)));
+                        return Task::none();
+                    }
+                };
+
+                // niri only knows what was saved, not edits still waiting to be
+                match compare_outputs(&self.save.last_saved.outputs.outputs, &live) {
+                    // Nothing to read back, so settle for niri accepting the config
+                    ConfigEffectiveness::Unverifiable => Task::perform(
+                        async { crate::ipc::validate_config().map_err(|e| e.to_string()) },
+                        |result| Message::Tools(ToolsMessage::EffectiveValidateCompleted(result)),
+                    ),
+                    effectiveness => {
+                        self.ui.tools_state.checking_effective = false;
+                        self.ui.tools_state.effective_result = Some(effectiveness.summary());
+                        Task::none()
+                    }
+                }
+            }
+            ToolsMessage::EffectiveValidateCompleted(result) => {
+                self.ui.tools_state.checking_effective = false;
+                self.ui.tools_state.effective_result = Some(match result {
+                    Ok(report) if report.ok => {
+                        Ok("No output settings to compare; niri accepts the config".to_string())
+                    }
+                    Ok(report) => Err(format!("niri rejects the config: {}", report.summary())),
+                    Err(e) => Err(format!("Could not validate the config: {}", e)),
+                });
+                Task::none()
+            }
+            ToolsMessage::LayoutInfoLoaded(result) => {
+                let info = match result {
+                    Ok(info) => info,
+                    Err(e) => {
+                        self.ui.toast = Some(format!("Could not read layout from niri: {}", e));
+                        self.ui.toast_shown_at = Some(std::time::Instant::now());
+                        return Task::none();
+                    }
+                };
+
+                let changed =
+                    info.apply(&mut self.settings.appearance, &mut self.settings.behavior);
+                let toast = if info.is_empty() {
+                    "niri has no tiles side by side to measure gaps from".to_string()
+                } else if changed.is_empty() {
+                    "Layout already matches niri".to_string()
+                } else {
+                    format!("Adopted {} from niri", changed.join(", "))
+                };
+                self.ui.toast = Some(toast);
+                self.ui.toast_shown_at = Some(std::time::Instant::now());
+                if changed.is_empty() {
+                    return Task::none();
+                }
+
+                if changed.contains(&"gaps") {
+                    self.save.dirty_tracker.mark(SettingsCategory::Appearance);
+                }
+                if changed.iter().any(|name| name.ends_with("strut")) {
+                    self.save.dirty_tracker.mark(SettingsCategory::Behavior);
+                }
+                self.mark_changed();
+                Task::none()
+            }
+
+            ToolsMessage::SetScreencastBlockAppIds(value) => {
+                self.ui.tools_state.screencast_block_app_ids = value;
+                Task::none()
+            }
+            ToolsMessage::ToggleScreencastBlock => {
+                let window_rules = &mut self.settings.window_rules;
+                let (toast, changed) = match window_rules
+                    .rules
+                    .iter_mut()
+                    .find(|r| r.enabled && r.name == SCREENCAST_BLOCK_RULE_NAME)
+                {
+                    Some(rule) => {
+                        rule.enabled = false;
+                        ("Screencast privacy rule turned off".to_string(), true)
+                    }
+                    None => {
+                        let app_ids: Vec<String> = self
+                            .ui
+                            .tools_state
+                            .screencast_block_app_ids
+                            .split(',')
+                            .map(str::to_string)
+                            .collect();
+                        let changed = window_rules.sync_screencast_block(&app_ids);
+                        match window_rules.screencast_block_rule() {
+                            Some(rule) if changed => {
+                                let (entry_id, entry) = SettingEntry::window_rule(rule);
+                                self.search_index.add_entry(entry_id, entry);
+                                let count =
+                                    rule.matches.iter().filter(|m| m.app_id.is_some()).count();
+                                (format!("Blocking {} app(s) from screencasts", count), true)
+                            }
+                            _ => ("Enter at least one app-id to block".to_string(), false),
+                        }
+                    }
+                };
+                self.ui.toast = Some(toast);
+                self.ui.toast_shown_at = Some(std::time::Instant::now());
+                if changed {
+                    self.save.dirty_tracker.mark(SettingsCategory::WindowRules);
+                    self.mark_changed();
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Applies a live update from niri's event stream
+    pub(in crate::app) fn update_niri_event(&mut self, event: NiriEvent) -> Task<Message> {
+        // Events only arrive while niri runs; catch up on a reconnect without
+        // waiting for the next status check
+        let reconnected = !matches!(event, NiriEvent::Disconnected)
+            && !matches!(self.ui.niri_status, NiriStatus::Connected);
+        let task = match event {
+            NiriEvent::WindowsChanged(windows) => {
+                self.update_tools(ToolsMessage::WindowsLoaded(Ok(windows)))
+            }
+            NiriEvent::WorkspacesChanged(workspaces) => {
+                self.update_tools(ToolsMessage::WorkspacesLoaded(Ok(workspaces)))
+            }
+            NiriEvent::WindowFocusChanged(id) => {
+                let windows = &mut self.ui.tools_state.windows;
+                for window in windows.iter_mut() {
+                    window.is_focused = Some(window.id) == id;
+                }
+                self.ui.tools_state.focused_window = windows.iter().find(|w| w.is_focused).cloned();
+                Task::none()
+            }
+            NiriEvent::KeyboardLayoutsChanged(layouts) => {
+                self.update_tools(ToolsMessage::KeyboardLayoutsLoaded(Ok(layouts)))
+            }
+            NiriEvent::Disconnected => self.update(Message::NiriStatusChecked(false)),
+        };
+        if reconnected {
+            return Task::batch([task, self.update(Message::NiriStatusChecked(true))]);
         }
+        task
     }
 }
+
+/// Asks where to save `kdl`, writes it there and has niri check it
+///
+/// Returns `None` if the user cancels. An export niri rejects is still
+/// written, so it can be looked at, but is reported as an error.
+fn export_to_chosen_file(kdl: String) -> Option<Result<String, String>> {
+    let path = rfd::FileDialog::new()
+        .set_file_name(crate::config::export::EXPORT_FILE_NAME)
+        .add_filter("KDL", &["kdl"])
+        .save_file()?;
+    if let Err(e) = std::fs::write(&path, kdl) {
+        return Some(Err(format!("Could not write {}: {}", path.display(), e)));
+    }
+
+    Some(match crate::ipc::validate_config_file(&path) {
+        Ok(report) if report.ok => Ok(format!("Exported to {}", path.display())),
+        Ok(report) => Err(format!(
+            "Exported to {}, but niri rejects it: {}",
+            path.display(),
+            report.summary()
+        )),
+        // niri isn't installed here; the export is still written
+        Err(e) => Ok(format!(
+            "Exported to {} (not checked: {})",
+            path.display(),
+            e
+        )),
+    })
+}
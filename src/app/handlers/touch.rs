@@ -1,5 +1,6 @@
 //! Touch settings message handler
 
+use crate::app::helpers::apply_focused_output;
 use crate::config::SettingsCategory;
 use crate::messages::{Message, TouchMessage};
 use crate::views::widgets::format_matrix_values;
@@ -13,6 +14,17 @@ impl super::super::App {
         match msg {
             TouchMessage::SetOff(v) => touch.off = v,
             TouchMessage::SetMapToOutput(v) => touch.map_to_output = v,
+            TouchMessage::MapToFocusedOutput => {
+                return Task::perform(
+                    async { crate::ipc::get_focused_output().map_err(|e| e.to_string()) },
+                    |result| Message::Touch(TouchMessage::FocusedOutputLoaded(result)),
+                );
+            }
+            TouchMessage::FocusedOutputLoaded(result) => {
+                if !apply_focused_output(&mut touch.map_to_output, result) {
+                    return Task::none();
+                }
+            }
             TouchMessage::SetCalibrationMatrix(v) => {
                 touch.calibration_matrix = v;
                 // Update cache
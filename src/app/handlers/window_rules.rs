@@ -1,14 +1,29 @@
 //! Window rules settings message handler
 
+use crate::app::helpers::apply_gradient_message;
 use crate::config::models::{WindowRule, WindowRuleMatch};
-use crate::config::SettingsCategory;
+use crate::config::{ColumnWidthType, SettingsCategory};
+use crate::constants::{DEFAULT_COLUMN_FIXED, DEFAULT_COLUMN_PROPORTION};
 use crate::messages::{Message, WindowRulesMessage as M};
+use crate::search::{EntryId, SettingEntry};
+use crate::types::ColorOrGradient;
+use crate::views::widgets::GradientPickerMessage;
+use crate::views::window_rules::UNSET_RULE_COLOR;
 use iced::Task;
 
 impl super::super::App {
     /// Updates window rules settings
     pub(in crate::app) fn update_window_rules(&mut self, msg: M) -> Task<Message> {
         let mut should_mark_dirty = true;
+        // Rule whose search entry (name, app IDs, titles) the edit changes
+        let reindex = match &msg {
+            M::SetRuleName(id, _)
+            | M::AddMatch(id)
+            | M::RemoveMatch(id, _)
+            | M::SetMatchAppId(id, ..)
+            | M::SetMatchTitle(id, ..) => Some(*id),
+            _ => None,
+        };
 
         match msg {
             M::AddRule => {
@@ -19,6 +34,8 @@ impl super::super::App {
                     name: format!("Rule {}", new_id + 1),
                     ..Default::default()
                 };
+                let (entry_id, entry) = SettingEntry::window_rule(&new_rule);
+                self.search_index.add_entry(entry_id, entry);
                 self.settings.window_rules.rules.push(new_rule);
                 self.ui.selected_window_rule_id = Some(new_id);
                 self.ui.editing_window_rule_id = Some(new_id);
@@ -26,6 +43,7 @@ impl super::super::App {
 
             M::DeleteRule(id) => {
                 self.settings.window_rules.remove(id);
+                self.search_index.remove_entry(EntryId::WindowRule(id));
                 if self.ui.selected_window_rule_id == Some(id) {
                     self.ui.selected_window_rule_id =
                         self.settings.window_rules.rules.first().map(|r| r.id);
@@ -44,6 +62,8 @@ impl super::super::App {
                     let mut new_rule = rule;
                     new_rule.id = new_id;
                     new_rule.name = format!("{} (copy)", new_rule.name);
+                    let (entry_id, entry) = SettingEntry::window_rule(&new_rule);
+                    self.search_index.add_entry(entry_id, entry);
                     self.settings.window_rules.rules.push(new_rule);
                     self.ui.selected_window_rule_id = Some(new_id);
                 }
@@ -89,10 +109,9 @@ impl super::super::App {
 
             M::RemoveMatch(id, match_idx) => {
                 if let Some(rule) = self.settings.window_rules.find_mut(id) {
-                    if match_idx < rule.matches.len() && rule.matches.len() > 1 {
-                        rule.matches.remove(match_idx);
-                    }
+                    rule.remove_match(match_idx);
                 }
+                self.revalidate_rule_regexes(id);
             }
 
             M::SetMatchAppId(id, match_idx, value) => {
@@ -188,6 +207,7 @@ impl super::super::App {
                         rule.excludes.remove(exclude_idx);
                     }
                 }
+                self.revalidate_rule_regexes(id);
             }
 
             M::SetExcludeAppId(id, exclude_idx, value) => {
@@ -301,6 +321,31 @@ impl super::super::App {
             M::SetDefaultColumnWidth(id, value) => {
                 if let Some(rule) = self.settings.window_rules.find_mut(id) {
                     rule.default_column_width = value;
+                    rule.default_column_width_fixed = None;
+                }
+            }
+
+            M::SetDefaultColumnWidthType(id, width_type) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    match width_type {
+                        ColumnWidthType::Proportion => {
+                            rule.default_column_width_fixed = None;
+                            rule.default_column_width
+                                .get_or_insert(DEFAULT_COLUMN_PROPORTION);
+                        }
+                        ColumnWidthType::Fixed => {
+                            rule.default_column_width = None;
+                            rule.default_column_width_fixed
+                                .get_or_insert(DEFAULT_COLUMN_FIXED as i32);
+                        }
+                    }
+                }
+            }
+
+            M::SetDefaultColumnWidthFixed(id, value) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    rule.default_column_width_fixed = value;
+                    rule.default_column_width = None;
                 }
             }
 
@@ -412,6 +457,42 @@ impl super::super::App {
                 }
             }
 
+            M::FocusRingActiveColor(id, msg) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    edit_rule_color(&mut rule.focus_ring_active, msg);
+                }
+            }
+
+            M::FocusRingInactiveColor(id, msg) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    edit_rule_color(&mut rule.focus_ring_inactive, msg);
+                }
+            }
+
+            M::FocusRingUrgentColor(id, msg) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    edit_rule_color(&mut rule.focus_ring_urgent, msg);
+                }
+            }
+
+            M::BorderActiveColor(id, msg) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    edit_rule_color(&mut rule.border_active, msg);
+                }
+            }
+
+            M::BorderInactiveColor(id, msg) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    edit_rule_color(&mut rule.border_inactive, msg);
+                }
+            }
+
+            M::BorderUrgentColor(id, msg) => {
+                if let Some(rule) = self.settings.window_rules.find_mut(id) {
+                    edit_rule_color(&mut rule.border_urgent, msg);
+                }
+            }
+
             M::SetShadow(id, value) => {
                 if let Some(rule) = self.settings.window_rules.find_mut(id) {
                     rule.shadow = value;
@@ -485,6 +566,11 @@ impl super::super::App {
             }
         }
 
+        if let Some(rule) = reindex.and_then(|id| self.settings.window_rules.find(id)) {
+            let (entry_id, entry) = SettingEntry::window_rule(rule);
+            self.search_index.update_entry(entry_id, entry);
+        }
+
         if should_mark_dirty {
             self.save.dirty_tracker.mark(SettingsCategory::WindowRules);
             self.mark_changed();
@@ -493,6 +579,32 @@ impl super::super::App {
         Task::none()
     }
 
+    /// Re-checks every regex in a rule after a match or exclude was removed
+    ///
+    /// Errors are keyed by position, so the ones after the removed entry
+    /// would otherwise show under the wrong criteria.
+    fn revalidate_rule_regexes(&mut self, id: u32) {
+        self.ui
+            .window_rule_regex_errors
+            .retain(|(rule_id, _), _| *rule_id != id);
+        let Some(rule) = self.settings.window_rules.find(id) else {
+            return;
+        };
+
+        let mut patterns = Vec::new();
+        for (idx, m) in rule.matches.iter().enumerate() {
+            patterns.push((format!("app_id_{}", idx), m.app_id.clone()));
+            patterns.push((format!("title_{}", idx), m.title.clone()));
+        }
+        for (idx, m) in rule.excludes.iter().enumerate() {
+            patterns.push((format!("exclude_app_id_{}", idx), m.app_id.clone()));
+            patterns.push((format!("exclude_title_{}", idx), m.title.clone()));
+        }
+        for (key, pattern) in patterns {
+            self.validate_regex(&(id, key), pattern.as_deref());
+        }
+    }
+
     /// Helper to validate regex and update error cache
     fn validate_regex(&mut self, error_key: &(u32, String), regex_str: Option<&str>) {
         match regex_str {
@@ -511,3 +623,10 @@ impl super::super::App {
         }
     }
 }
+
+/// Applies a gradient picker edit to a rule's color override, starting from
+/// the placeholder the editor shows when the override isn't set yet
+fn edit_rule_color(slot: &mut Option<ColorOrGradient>, msg: GradientPickerMessage) {
+    let color = slot.get_or_insert(ColorOrGradient::Color(UNSET_RULE_COLOR));
+    apply_gradient_message(color, msg);
+}
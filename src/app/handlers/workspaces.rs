@@ -40,15 +40,8 @@ impl super::super::App {
                     workspace.open_on_output = output;
                 }
             }
-            WorkspacesMessage::MoveWorkspaceUp(index) => {
-                if index > 0 && index < self.settings.workspaces.workspaces.len() {
-                    self.settings.workspaces.workspaces.swap(index - 1, index);
-                }
-            }
-            WorkspacesMessage::MoveWorkspaceDown(index) => {
-                if index < self.settings.workspaces.workspaces.len().saturating_sub(1) {
-                    self.settings.workspaces.workspaces.swap(index, index + 1);
-                }
+            WorkspacesMessage::Reorder(reorder) => {
+                reorder.apply(&mut self.settings.workspaces.workspaces);
             }
         }
 
@@ -2,6 +2,8 @@
 //!
 //! Contains utility functions used across multiple handlers.
 
+use crate::config::models::OutputConfig;
+use crate::ipc::FullOutputInfo;
 use crate::types::{Color, ColorOrGradient, Gradient};
 use crate::views::widgets::GradientPickerMessage;
 
@@ -183,6 +185,91 @@ pub fn validate_spawn_command(command: &str) -> Result<Vec<String>, String> {
     Ok(parsed.args)
 }
 
+/// Applies a focused-output IPC result to a device's `map_to_output`
+///
+/// Returns whether the field changed. When niri can't report a focused output
+/// (not running, query failed, nothing focused) the field is left untouched.
+pub fn apply_focused_output(
+    map_to_output: &mut String,
+    result: Result<Option<String>, String>,
+) -> bool {
+    match result {
+        Ok(Some(output)) if *map_to_output != output => {
+            *map_to_output = output;
+            true
+        }
+        Ok(_) => false,
+        Err(e) => {
+            log::warn!("Could not query focused output: {}", e);
+            false
+        }
+    }
+}
+
+/// What a refresh of niri's outputs changed
+#[derive(Debug, Default, PartialEq)]
+pub struct OutputRefresh {
+    /// Configured outputs that aren't connected now
+    pub disconnected: Vec<String>,
+    /// Whether a configured output changed in a way that needs saving
+    pub configured_changed: bool,
+}
+
+/// Takes in niri's outputs after an IPC refresh (e.g. a hotplug)
+///
+/// The IPC-derived cache that feeds the mode dropdowns and preview is
+/// replaced. Configured outputs only pick up monitor identities from it (see
+/// [`apply_live_identities`]), so an unplug never drops or resets an output
+/// that is being edited; it's reported as disconnected instead.
+pub fn refresh_live_outputs(
+    live: &mut Vec<FullOutputInfo>,
+    refreshed: Vec<FullOutputInfo>,
+    configured: &mut [OutputConfig],
+) -> OutputRefresh {
+    *live = refreshed;
+    let configured_changed = apply_live_identities(configured, live);
+    let disconnected = configured
+        .iter()
+        .filter(|output| !output.name.is_empty())
+        .filter(|output| !live.iter().any(|info| info.name == output.name))
+        .map(|output| output.name.clone())
+        .collect();
+    OutputRefresh {
+        disconnected,
+        configured_changed,
+    }
+}
+
+/// Copies make/model/serial from the live outputs onto configured ones
+///
+/// Outputs matched by connector learn the identity of the monitor on it, so
+/// switching them to identity matching needs no typing. Outputs matched by
+/// identity instead follow their monitor to whichever connector it's on now.
+/// Returns whether anything that gets saved changed; the identity of an
+/// output matched by connector isn't written, so learning it doesn't count.
+pub fn apply_live_identities(configured: &mut [OutputConfig], live: &[FullOutputInfo]) -> bool {
+    let mut changed = false;
+    for output in configured {
+        let before = output.clone();
+        match output.identity().filter(|_| output.match_by_identity) {
+            Some(identity) => {
+                if let Some(info) = live.iter().find(|info| info.identity() == identity) {
+                    output.name.clone_from(&info.name);
+                }
+            }
+            None => {
+                if let Some(info) = live.iter().find(|info| info.name == output.name) {
+                    output.make = Some(info.make.clone()).filter(|make| !make.is_empty());
+                    output.model = Some(info.model.clone()).filter(|model| !model.is_empty());
+                    output.serial.clone_from(&info.serial);
+                }
+            }
+        }
+        changed |= output.match_by_identity && *output != before;
+    }
+    changed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,6 +343,88 @@ mod tests {
         let result = parse_spawn_command("firefox https://example.com").unwrap();
         assert!(result.warning.is_none());
     }
+
+    #[test]
+    fn test_apply_focused_output_sets_field() {
+        let mut map_to_output = String::new();
+        assert!(apply_focused_output(
+            &mut map_to_output,
+            Ok(Some("eDP-1".to_string()))
+        ));
+        assert_eq!(map_to_output, "eDP-1");
+    }
+
+    #[test]
+    fn test_apply_focused_output_unavailable_is_noop() {
+        let mut map_to_output = "HDMI-A-1".to_string();
+        assert!(!apply_focused_output(&mut map_to_output, Ok(None)));
+        assert!(!apply_focused_output(
+            &mut map_to_output,
+            Err("niri is not running".to_string())
+        ));
+        assert_eq!(map_to_output, "HDMI-A-1");
+    }
+
+    #[test]
+    fn test_hotplug_refresh_keeps_edited_output() {
+        let monitor = |name: &str, model: &str| FullOutputInfo {
+            name: name.to_string(),
+            make: "Dell".to_string(),
+            model: model.to_string(),
+            serial: Some("ABC123".to_string()),
+            ..Default::default()
+        };
+        let mut configured = vec![
+            OutputConfig {
+                name: "DP-1".to_string(),
+                scale: 1.25,
+                mode: "2560x1440@143.97".to_string(),
+                position_x: 1920,
+                ..Default::default()
+            },
+            OutputConfig {
+                name: "HDMI-A-1".to_string(),
+                ..Default::default()
+            },
+        ];
+        let mut live = Vec::new();
+
+        // First refresh: both connected, the identities are learned. They
+        // aren't saved for outputs matched by connector, so nothing to save
+        let refresh = refresh_live_outputs(
+            &mut live,
+            vec![monitor("DP-1", "U2720Q"), monitor("HDMI-A-1", "P2419H")],
+            &mut configured,
+        );
+        assert!(refresh.disconnected.is_empty());
+        assert!(!refresh.configured_changed);
+        assert_eq!(
+            configured[0].identity().as_deref(),
+            Some("Dell U2720Q ABC123")
+        );
+
+        // The user edits DP-1 and switches it to identity matching
+        configured[0].scale = 1.5;
+        configured[0].match_by_identity = true;
+        let edited = configured.clone();
+
+        // Unplug HDMI: it's reported, and nothing configured is touched
+        let refresh =
+            refresh_live_outputs(&mut live, vec![monitor("DP-1", "U2720Q")], &mut configured);
+        assert_eq!(refresh.disconnected, vec!["HDMI-A-1".to_string()]);
+        assert!(!refresh.configured_changed);
+        assert_eq!(configured, edited);
+
+        // The DP-1 monitor comes back on another connector and is followed there
+        let refresh =
+            refresh_live_outputs(&mut live, vec![monitor("DP-2", "U2720Q")], &mut configured);
+        assert_eq!(refresh.disconnected, vec!["HDMI-A-1".to_string()]);
+        assert!(refresh.configured_changed);
+        assert_eq!(configured[0].name, "DP-2");
+        assert_eq!(configured[0].scale, 1.5);
+        assert_eq!(configured[0].mode, "2560x1440@143.97");
+        assert_eq!(configured[0].position_x, 1920);
+    }
 }
 
 /// Checks if a pressed key combo matches a configured hotkey string
@@ -504,7 +673,7 @@ pub fn apply_gradient_message(target: &mut ColorOrGradient, msg: GradientPickerM
             };
         }
         GradientPickerMessage::SetFromColor(hex) => {
-            if let Some(color) = Color::from_hex(&hex) {
+            if let Some(color) = Color::parse(&hex) {
                 match target {
                     ColorOrGradient::Color(c) => *c = color,
                     ColorOrGradient::Gradient(g) => g.from = color,
@@ -513,7 +682,7 @@ pub fn apply_gradient_message(target: &mut ColorOrGradient, msg: GradientPickerM
         }
         GradientPickerMessage::SetToColor(hex) => {
             if let ColorOrGradient::Gradient(gradient) = target {
-                if let Some(color) = Color::from_hex(&hex) {
+                if let Some(color) = Color::parse(&hex) {
                     gradient.to = color;
                 }
             }
@@ -0,0 +1,97 @@
+//! Render memoization for settings-only views
+//!
+//! iced calls `view()` after every message, so the 5-second niri status poll
+//! and the save ticks rebuilt every widget of the current page. Views that
+//! show nothing but settings are wrapped in `lazy` with a [`MemoKey`] built
+//! from the revisions of the categories they show; iced reuses the previous
+//! widget tree while the key hashes the same.
+
+use crate::config::{DirtyTracker, SettingsCategory};
+use crate::messages::{Page, Screen};
+
+/// What a memoized view shows, so two views never share a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoView {
+    Page(Page),
+    Screen(Screen),
+}
+
+impl From<Page> for MemoView {
+    fn from(page: Page) -> Self {
+        Self::Page(page)
+    }
+}
+
+impl From<Screen> for MemoView {
+    fn from(screen: Screen) -> Self {
+        Self::Screen(screen)
+    }
+}
+
+/// Memo key of a view: changes only when a category it depends on is edited
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoKey {
+    view: MemoView,
+    revisions: Vec<u64>,
+}
+
+impl MemoKey {
+    pub fn new(
+        view: impl Into<MemoView>,
+        categories: &[SettingsCategory],
+        tracker: &DirtyTracker,
+    ) -> Self {
+        Self {
+            view: view.into(),
+            revisions: categories
+                .iter()
+                .map(|category| tracker.revision(*category))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPEARANCE: &[SettingsCategory] = &[SettingsCategory::Appearance];
+
+    #[test]
+    fn test_status_update_keeps_appearance_key() {
+        let tracker = DirtyTracker::new();
+        tracker.mark(SettingsCategory::Appearance);
+        let before = MemoKey::new(Page::Appearance, APPEARANCE, &tracker);
+
+        // A status poll only touches UI state and marks nothing
+        assert_eq!(MemoKey::new(Page::Appearance, APPEARANCE, &tracker), before);
+
+        // Edits elsewhere, and saving, leave the key alone too
+        tracker.mark(SettingsCategory::Behavior);
+        tracker.take();
+        assert_eq!(MemoKey::new(Page::Appearance, APPEARANCE, &tracker), before);
+    }
+
+    #[test]
+    fn test_settings_edit_invalidates_key() {
+        let tracker = DirtyTracker::new();
+        let before = MemoKey::new(Page::Appearance, APPEARANCE, &tracker);
+
+        tracker.mark(SettingsCategory::Appearance);
+        let after = MemoKey::new(Page::Appearance, APPEARANCE, &tracker);
+        assert_ne!(after, before);
+
+        // A reset marks everything
+        tracker.mark_all();
+        assert_ne!(MemoKey::new(Page::Appearance, APPEARANCE, &tracker), after);
+    }
+
+    #[test]
+    fn test_views_with_same_revisions_get_distinct_keys() {
+        let tracker = DirtyTracker::new();
+        assert_ne!(
+            MemoKey::new(Page::Appearance, APPEARANCE, &tracker),
+            MemoKey::new(Screen::Visuals, APPEARANCE, &tracker)
+        );
+    }
+}
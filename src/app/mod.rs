@@ -7,7 +7,9 @@
 
 mod handlers;
 mod helpers;
+mod memo;
 mod ui_state;
+mod undo;
 
 pub use ui_state::UiState;
 
@@ -21,6 +23,7 @@ use iced::{alignment::Horizontal, Element, Length, Subscription, Task};
 use crate::config::{ConfigPaths, DirtyTracker, Settings, SettingsCategory};
 use crate::messages::{DialogState, Message, Page, SaveMessage};
 use crate::save_manager::{ReloadResult, SaveResult};
+use crate::search::EntryId;
 use crate::theme::{fonts, neon};
 use crate::views;
 
@@ -32,14 +35,35 @@ pub struct SaveState {
     pub last_change_time: Option<std::time::Instant>,
     /// Whether a save is currently in progress
     pub in_progress: bool,
+    /// Settings as of the last successful save, for the pending changes panel
+    pub last_saved: Settings,
+    /// Settings being written by the save in progress
+    pub in_flight: Option<Settings>,
+    /// Whether a restore point from this session can be reverted to
+    pub can_revert: bool,
+    /// Single-instance lock, held for as long as the app runs
+    pub instance_lock: Option<crate::config::InstanceLock>,
+    /// Set while another instance holds the lock; nothing is saved
+    pub read_only: bool,
+    /// Managed files as last written or loaded, to spot edits made outside
+    pub fingerprints: crate::save_manager::FileFingerprints,
+    /// Category whose file changed outside; nothing is saved until resolved
+    pub external_change: Option<SettingsCategory>,
 }
 
 impl SaveState {
-    fn new() -> Self {
+    fn new(last_saved: Settings) -> Self {
         Self {
             dirty_tracker: DirtyTracker::new(),
             last_change_time: None,
             in_progress: false,
+            last_saved,
+            in_flight: None,
+            can_revert: false,
+            instance_lock: None,
+            read_only: false,
+            fingerprints: Default::default(),
+            external_change: None,
         }
     }
 }
@@ -58,6 +82,9 @@ pub struct App {
     /// Search index (domain data, not UI state)
     search_index: crate::search::SearchIndex,
 
+    /// Settings edits that can be undone and redone
+    undo: undo::UndoStack,
+
     /// UI-only state (selections, expansions, dialogs, etc.)
     ui: UiState,
 }
@@ -79,16 +106,28 @@ impl App {
             }
         };
 
+        // Taken before anything below writes to the config, so a second copy
+        // leaves the files alone while the first one is using them
+        let instance_lock = crate::config::acquire_instance_lock(&paths);
+        let owns_config = !matches!(instance_lock, crate::config::LockResult::HeldByOther { .. });
+
         // Migrate old tilde-based include paths to relative paths
         // This fixes configs created before the XDG_CONFIG_HOME fix
-        if let Err(e) = paths.migrate_include_line() {
-            log::warn!("Failed to migrate include line: {}", e);
+        if owns_config {
+            if let Err(e) = paths.migrate_include_line() {
+                log::warn!("Failed to migrate include line: {}", e);
+            }
         }
 
+        // Load settings before config.kdl is touched: without managed files they
+        // are imported from it, and the smart replace below strips what it holds
+        let settings = crate::config::load_or_import_settings(&paths);
+        log::info!("Settings loaded successfully");
+
         // Ensure config.kdl is properly set up with include directive
         // This replaces managed nodes with the include, preserving custom content
         // Safe to call every time - it early-returns if no changes needed
-        if paths.niri_config.exists() && paths.managed_dir.exists() {
+        if owns_config && paths.niri_config.exists() && !paths.is_first_run() {
             match crate::config::smart_replace_config(&paths.niri_config, &paths.backup_dir) {
                 Ok(result) => {
                     if result.replaced_count > 0 || result.include_added {
@@ -106,16 +145,14 @@ impl App {
             }
         }
 
-        // Clean up old backups to prevent directory from growing indefinitely
-        // Keep the 10 most recent backups
-        if let Err(e) = paths.cleanup_old_backups(10) {
-            log::warn!("Failed to clean up old backups: {}", e);
+        // Clean up old backups to prevent directory from growing indefinitely,
+        // keeping as many as the user's preference allows
+        if owns_config {
+            if let Err(e) = paths.cleanup_old_backups(settings.preferences.backup_retention) {
+                log::warn!("Failed to clean up old backups: {}", e);
+            }
         }
 
-        // Load settings from disk (load_settings returns Settings, not Result)
-        let settings = crate::config::load_settings(&paths);
-        log::info!("Settings loaded successfully");
-
         // Parse theme from settings
         let current_theme = settings
             .preferences
@@ -153,9 +190,10 @@ impl App {
             );
         }
 
-        // Ensure all required config files exist (handles upgrades from older versions)
+        // Ensure all required config files exist (handles upgrades from older versions
+        // and a managed dir that was deleted while config.kdl still includes it)
         // This creates any missing .kdl files that main.kdl includes
-        if paths.managed_dir.exists() {
+        if owns_config && !paths.is_first_run() {
             match crate::config::ensure_required_files_exist(&paths, &settings, feature_compat) {
                 Ok(created) if !created.is_empty() => {
                     log::info!(
@@ -188,13 +226,58 @@ impl App {
             ui.dialog_state = DialogState::FirstRunWizard {
                 step: crate::messages::WizardStep::Welcome,
             };
+            ui.wizard_includes = crate::config::existing_includes(&paths.niri_config);
+        } else if let Err(e) = paths.check_writable() {
+            // Say so now rather than on the first failed save
+            log::error!("Config directories not writable: {}", e);
+            ui.dialog_state = DialogState::Error {
+                title: "Config Folder Not Writable".to_string(),
+                message: "Changes can't be saved until its permissions or mount are fixed."
+                    .to_string(),
+                details: Some(e),
+            };
         }
 
+        let mut save = SaveState::new(settings.clone());
+        save.fingerprints = crate::save_manager::FileFingerprints::read_all(&paths);
+        match instance_lock {
+            crate::config::LockResult::Acquired(lock) => {
+                save.instance_lock = Some(lock);
+                // Restore points only cover this session's saves. Only the
+                // instance holding the lock clears them, so a second, read-only
+                // window doesn't wipe the running one's
+                crate::config::clear_restore_points(&paths);
+            }
+            crate::config::LockResult::HeldByOther { pid } => {
+                log::warn!("Another instance (PID {}) holds the config lock", pid);
+                save.read_only = true;
+                // Don't hide the first-run wizard or a permissions error
+                if matches!(ui.dialog_state, DialogState::None) {
+                    ui.dialog_state = DialogState::Confirm {
+                        title: "Nirify Is Already Running".to_string(),
+                        message: format!(
+                            "Another copy of Nirify (PID {}) is editing the same config.\n\n\
+                             This window opened read-only so the two don't overwrite each \
+                             other's saves. Edit anyway only if the other copy is stuck.",
+                            pid
+                        ),
+                        confirm_label: "Edit Anyway".to_string(),
+                        on_confirm: crate::messages::ConfirmAction::TakeInstanceLock,
+                    };
+                }
+            }
+            crate::config::LockResult::Unavailable(e) => {
+                log::warn!("Could not take the instance lock: {}", e);
+            }
+        }
+
+        let search_index = crate::search::SearchIndex::with_settings(&settings);
         let app = Self {
+            save,
+            undo: undo::UndoStack::new(&settings),
             settings,
             paths,
-            save: SaveState::new(),
-            search_index: crate::search::SearchIndex::new(),
+            search_index,
             ui,
         };
 
@@ -238,11 +321,13 @@ impl App {
 
         let paths = Arc::new(ConfigPaths::default());
 
+        let search_index = crate::search::SearchIndex::with_settings(&settings);
         let app = Self {
+            save: SaveState::new(settings.clone()),
+            undo: undo::UndoStack::new(&settings),
             settings,
             paths,
-            save: SaveState::new(),
-            search_index: crate::search::SearchIndex::new(),
+            search_index,
             ui,
         };
 
@@ -274,15 +359,22 @@ impl App {
                     );
                 }
 
-                // Auto-refresh workspaces when navigating to Window Rules page
-                // (for the workspace dropdown)
+                // Auto-refresh workspaces and outputs when navigating to Window
+                // Rules page (for the placement dropdowns)
                 if page == Page::WindowRules && is_connected {
-                    return Task::perform(
+                    let workspaces = Task::perform(
                         async { crate::ipc::get_workspaces().map_err(|e| e.to_string()) },
                         |result| {
                             Message::Tools(crate::messages::ToolsMessage::WorkspacesLoaded(result))
                         },
                     );
+                    let outputs = Task::perform(
+                        async { crate::ipc::get_full_outputs().map_err(|e| e.to_string()) },
+                        |result| {
+                            Message::Tools(crate::messages::ToolsMessage::OutputsLoaded(result))
+                        },
+                    );
+                    return Task::batch([workspaces, outputs]);
                 }
 
                 Task::none()
@@ -295,45 +387,35 @@ impl App {
 
             // Search (Phase 9)
             Message::SearchQueryChanged(query) => {
-                self.ui.search_query = query;
-                self.ui.last_search_time = Some(std::time::Instant::now());
-
-                // Perform search immediately
-                self.ui.search_results = self.search_index.search(&self.ui.search_query);
+                // The search runs from CheckSearch once typing pauses
+                self.ui.set_search_query(query, std::time::Instant::now());
 
                 // Re-focus search input to maintain typing focus
                 iced::widget::operation::focus(views::navigation::search_input_id())
             }
 
+            Message::CheckSearch => {
+                if self.ui.take_due_search(
+                    std::time::Instant::now(),
+                    Duration::from_millis(crate::constants::SEARCH_DEBOUNCE_MS),
+                ) {
+                    self.ui.search_results = self.search_index.search(&self.ui.search_query);
+                }
+                Task::none()
+            }
+
             Message::SearchResultSelected(index) => {
                 // Navigate to the correct screen + sub-tab
                 if let Some(result) = self.ui.search_results.get(index) {
-                    use crate::messages::{GearSubTab, RulesSubTab, Screen};
-
                     let page = result.page;
-                    let screen = Screen::from_page(page);
-                    self.ui.current_screen = screen;
-                    self.ui.current_page = page;
-
-                    // Set appropriate sub-tab based on page
-                    if let Some(input_tab) = Screen::input_sub_tab_from_page(page) {
-                        self.ui.input_sub_tab = input_tab;
-                    }
-                    match page {
-                        Page::WindowRules => self.ui.rules_sub_tab = RulesSubTab::WindowRules,
-                        Page::LayerRules => self.ui.rules_sub_tab = RulesSubTab::LayerRules,
-                        Page::Tools => self.ui.gear_sub_tab = GearSubTab::Tools,
-                        Page::Preferences => self.ui.gear_sub_tab = GearSubTab::Preferences,
-                        Page::ConfigEditor => self.ui.gear_sub_tab = GearSubTab::ConfigEditor,
-                        Page::Backups => self.ui.gear_sub_tab = GearSubTab::Backups,
-                        _ => {}
-                    }
-
                     // Store setting name for highlighting on the target page
                     self.ui.highlight_setting = Some(result.setting_name.clone());
+                    self.open_page(page);
+
                     // Clear search after navigation
                     self.ui.search_query.clear();
                     self.ui.search_results.clear();
+                    self.ui.last_search_time = None;
                     // Close search modal if open
                     self.ui.search_focused = false;
                 }
@@ -347,6 +429,11 @@ impl App {
                 Task::none()
             }
 
+            Message::FocusNext => iced::widget::operation::focus_next(),
+            Message::FocusPrevious => iced::widget::operation::focus_previous(),
+
+            Message::Undo => self.step_history(true),
+            Message::Redo => self.step_history(false),
             Message::ToggleSearch => {
                 // If search bar is visible, just focus it
                 // If hidden, show it as focused (modal mode)
@@ -358,10 +445,24 @@ impl App {
                     // Clear search when closing
                     self.ui.search_query.clear();
                     self.ui.search_results.clear();
+                    self.ui.last_search_time = None;
                     Task::none()
                 }
             }
 
+            Message::SelectFavorite(setting_name) => {
+                let found = self
+                    .search_index
+                    .favorites(std::slice::from_ref(&setting_name));
+                if let Some(favorite) = found.first() {
+                    // Same as picking it from search: open its page with it highlighted
+                    let page = favorite.page;
+                    self.ui.highlight_setting = Some(setting_name);
+                    self.open_page(page);
+                }
+                Task::none()
+            }
+
             // Theme
             Message::ChangeTheme(theme) => {
                 self.ui.current_theme = theme;
@@ -418,15 +519,130 @@ impl App {
                     Task::none()
                 }
             }
+            Message::Save(SaveMessage::ShowPendingChanges) => {
+                let diff = crate::diff::generate_pending_diff(
+                    &self.settings,
+                    &self.save.last_saved,
+                    &self.paths,
+                    self.ui.feature_compat,
+                );
+                self.ui.dialog_state = DialogState::PendingChanges { diff };
+                Task::none()
+            }
+            Message::Save(SaveMessage::RevertLastChange) => {
+                if self.save.in_progress {
+                    return Task::none();
+                }
+                if self.save.dirty_tracker.is_dirty() {
+                    self.ui.dialog_state = DialogState::Confirm {
+                        title: "Discard Unsaved Changes?".to_string(),
+                        message: "Reverting loads the settings as they were before the last \
+                                  save. Changes you haven't saved yet will be lost."
+                            .to_string(),
+                        confirm_label: "Revert".to_string(),
+                        on_confirm: crate::messages::ConfirmAction::RevertLastChange,
+                    };
+                    return Task::none();
+                }
+                self.revert_last_change()
+            }
+            Message::Save(SaveMessage::RevertCompleted(result)) => match result {
+                Ok(remaining) => {
+                    self.save.can_revert = remaining > 0;
+                    self.reload_settings_from_disk();
+                    self.ui.toast = Some("Reverted the last change".to_string());
+                    self.ui.toast_shown_at = Some(std::time::Instant::now());
+                    if self.settings.preferences.auto_reload {
+                        self.reload_niri_config_task()
+                    } else {
+                        Task::none()
+                    }
+                }
+                Err(e) => {
+                    self.ui.toast = Some(format!("Revert failed: {}", e));
+                    self.ui.toast_shown_at = Some(std::time::Instant::now());
+                    Task::none()
+                }
+            },
+            Message::Save(SaveMessage::ExternalChangeDetected(category)) => {
+                log::warn!("{} was edited outside Nirify", category.name());
+                self.save.external_change = Some(category);
+                self.ui.dialog_state = DialogState::ExternalChange { category };
+                Task::none()
+            }
+            Message::Save(SaveMessage::KeepMine(category)) => {
+                // Accept the file as it is now, so the next save writes over it
+                self.save.fingerprints.record(&self.paths, [category]);
+                self.save.external_change = None;
+                self.ui.dialog_state = DialogState::None;
+                self.mark_changed();
+                Task::none()
+            }
+            Message::Save(SaveMessage::ReloadTheirs(category)) => {
+                let on_disk = crate::config::load_settings(&self.paths);
+                self.settings.copy_category(&on_disk, category);
+                self.save.last_saved.copy_category(&on_disk, category);
+                self.save.fingerprints.record(&self.paths, [category]);
+                self.undo.reset(&self.settings);
+                let mut dirty = self.save.dirty_tracker.take();
+                dirty.remove(&category);
+                let dirty: Vec<_> = dirty.into_iter().collect();
+                self.save.dirty_tracker.mark_many(&dirty);
+                // Not dirty any more, but its page still shows the dropped edits
+                self.save.dirty_tracker.bump_revisions(&[category]);
+                self.search_index.rebuild(&self.settings);
+                self.save.external_change = None;
+                self.ui.dialog_state = DialogState::None;
+                self.ui.toast = Some(format!("Loaded {} from disk", category.name()));
+                self.ui.toast_shown_at = Some(std::time::Instant::now());
+                Task::none()
+            }
 
             Message::SaveCompleted(result) => {
                 self.save.in_progress = false;
+                let saved = self.save.in_flight.take();
+                let reload = result.needs_reload(self.settings.preferences.auto_reload);
                 match result {
-                    SaveResult::Success { files_written, .. } => {
-                        self.ui.toast = Some(format!("Saved {} file(s)", files_written));
+                    SaveResult::Success {
+                        files_written,
+                        categories,
+                    } => {
+                        if let Some(saved) = saved {
+                            self.save.last_saved = saved;
+                        }
+                        self.save
+                            .fingerprints
+                            .record(&self.paths, categories.iter().copied());
+                        // The file now holds normalized combos; show the same in the UI
+                        if categories.contains(&SettingsCategory::Keybindings) {
+                            for binding in &mut self.settings.keybindings.bindings {
+                                binding.key_combo =
+                                    crate::config::keys::normalize_combo(&binding.key_combo);
+                            }
+                            self.search_index.rebuild(&self.settings);
+                        }
+                        if files_written == 0 {
+                            // The files already held this content; nothing to reload
+                            return Task::none();
+                        }
+                        self.save.can_revert = true;
+                        let restart_required = std::mem::take(&mut self.ui.restart_required);
+                        self.ui.toast = Some(if restart_required.is_empty() {
+                            format!("Saved {} file(s)", files_written)
+                        } else {
+                            format!(
+                                "Saved {} file(s) - restart niri to apply {}",
+                                files_written,
+                                restart_required.into_iter().collect::<Vec<_>>().join(", ")
+                            )
+                        });
                         self.ui.toast_shown_at = Some(std::time::Instant::now());
-                        // Trigger niri config reload
-                        self.reload_niri_config_task()
+                        // Trigger niri config reload, unless the user reloads by hand
+                        if reload {
+                            self.reload_niri_config_task()
+                        } else {
+                            Task::none()
+                        }
                     }
                     SaveResult::Error { message } => {
                         self.ui.toast = Some(format!("Save failed: {}", message));
@@ -445,19 +661,32 @@ impl App {
                         self.ui.toast_shown_at = None;
                     }
                 }
+                self.ui
+                    .clear_expired_reload(std::time::Duration::from_millis(
+                        crate::constants::TOAST_DISMISS_MS,
+                    ));
                 Task::none()
             }
 
             Message::ReloadCompleted(result) => {
-                match result {
+                match &result {
                     ReloadResult::Success => {
                         log::info!("Niri config reloaded");
                     }
                     ReloadResult::Error { message } => {
                         log::warn!("Failed to reload niri config: {}", message);
-                        // Don't show error to user - niri might not be running
                     }
                 }
+                // Only surface failures while niri is up; the status bar already
+                // says when it isn't running
+                if result.is_success()
+                    || matches!(
+                        self.ui.niri_status,
+                        crate::views::status_bar::NiriStatus::Connected
+                    )
+                {
+                    self.ui.record_reload(result);
+                }
                 Task::none()
             }
 
@@ -468,6 +697,10 @@ impl App {
             }
 
             Message::CloseDialog => {
+                // Saving is on hold until the user picks a version
+                if matches!(self.ui.dialog_state, DialogState::ExternalChange { .. }) {
+                    return Task::none();
+                }
                 // If this was an initialization error dialog, exit the app gracefully
                 if let DialogState::Error { title, .. } = &self.ui.dialog_state {
                     if title == "Initialization Failed" {
@@ -491,6 +724,7 @@ impl App {
             }
 
             Message::DialogConfirm => {
+                let mut task = Task::none();
                 // Handle confirmation based on current dialog type
                 match &self.ui.dialog_state {
                     DialogState::Confirm { on_confirm, .. } => {
@@ -501,6 +735,7 @@ impl App {
                                 let rule_id = *rule_id;
                                 if self.settings.window_rules.remove(rule_id) {
                                     log::info!("Deleted window rule {}", rule_id);
+                                    self.search_index.remove_entry(EntryId::WindowRule(rule_id));
                                     if self.ui.selected_window_rule_id == Some(rule_id) {
                                         self.ui.selected_window_rule_id =
                                             self.settings.window_rules.rules.first().map(|r| r.id);
@@ -510,6 +745,7 @@ impl App {
                                         .mark(crate::config::SettingsCategory::WindowRules);
                                 } else if self.settings.layer_rules.remove(rule_id) {
                                     log::info!("Deleted layer rule {}", rule_id);
+                                    self.search_index.remove_entry(EntryId::LayerRule(rule_id));
                                     if self.ui.selected_layer_rule_id == Some(rule_id) {
                                         self.ui.selected_layer_rule_id =
                                             self.settings.layer_rules.rules.first().map(|r| r.id);
@@ -523,17 +759,43 @@ impl App {
                             ConfirmAction::ResetSettings => {
                                 log::info!("Resetting all settings to defaults");
                                 self.settings = crate::config::models::Settings::default();
+                                self.search_index.rebuild(&self.settings);
                                 self.save.dirty_tracker.mark_all();
                                 self.mark_changed();
                             }
                             ConfirmAction::ClearAllKeybindings => {
                                 log::info!("Clearing all keybindings");
                                 self.settings.keybindings.bindings.clear();
+                                self.search_index.rebuild(&self.settings);
                                 self.save
                                     .dirty_tracker
                                     .mark(crate::config::SettingsCategory::Keybindings);
                                 self.mark_changed();
                             }
+                            ConfirmAction::RestoreBackup(idx) => {
+                                task = Task::done(Message::Backups(
+                                    crate::messages::BackupsMessage::RestoreBackup(*idx),
+                                ));
+                            }
+                            ConfirmAction::RestoreSnapshot(idx) => {
+                                task = Task::done(Message::Backups(
+                                    crate::messages::BackupsMessage::RestoreSnapshot(*idx),
+                                ));
+                            }
+                            ConfirmAction::RevertLastChange => {
+                                task = self.revert_last_change();
+                            }
+                            ConfirmAction::TakeInstanceLock => {
+                                log::info!("Taking over the instance lock");
+                                if let crate::config::LockResult::Acquired(lock) =
+                                    crate::config::take_over_instance_lock(&self.paths)
+                                {
+                                    self.save.instance_lock = Some(lock);
+                                }
+                                self.save.read_only = false;
+                                // Save anything edited while read-only
+                                self.mark_changed();
+                            }
                         }
                     }
                     DialogState::DiffView { .. } => {
@@ -546,7 +808,7 @@ impl App {
                     }
                 }
                 self.ui.dialog_state = DialogState::None;
-                Task::none()
+                task
             }
 
             Message::WizardNext => {
@@ -601,17 +863,22 @@ impl App {
                 Task::none()
             }
 
+            Message::WizardIncludeHandling(handling) => {
+                self.ui.wizard_include_handling = handling;
+                Task::none()
+            }
+
             Message::WizardSetupConfig => {
                 // Set up the config: create directories and add include line
                 log::info!("Wizard: Setting up config...");
 
-                // Ensure directories exist
-                if let Err(e) = self.paths.ensure_directories() {
-                    log::error!("Failed to create directories: {}", e);
+                // Ensure directories exist and can be written to
+                if let Err(e) = self.paths.check_writable() {
+                    log::error!("Failed to set up directories: {}", e);
                     self.ui.dialog_state = DialogState::Error {
                         title: "Setup Error".to_string(),
                         message: "Failed to create configuration directories.".to_string(),
-                        details: Some(e.to_string()),
+                        details: Some(e),
                     };
                     return Task::none();
                 }
@@ -639,9 +906,10 @@ impl App {
 
                 // Replace user's config.kdl with our managed version
                 // This removes managed nodes and adds the include directive
-                match crate::config::smart_replace_config(
+                match crate::config::smart_replace_config_with(
                     &self.paths.niri_config,
                     &self.paths.backup_dir,
+                    self.ui.wizard_include_handling,
                 ) {
                     Ok(result) => {
                         log::info!(
@@ -695,6 +963,8 @@ impl App {
                                 merged_pattern: s.merged_pattern.clone(),
                                 is_window_rule: true,
                                 selected: true, // Pre-select in wizard
+                                detail: s.detail(),
+                                expanded: false,
                             });
                     }
                     for s in &analysis.layer_suggestions {
@@ -708,6 +978,8 @@ impl App {
                                 merged_pattern: s.merged_pattern.clone(),
                                 is_window_rule: false,
                                 selected: true, // Pre-select in wizard
+                                detail: s.detail(),
+                                expanded: false,
                             });
                     }
                     log::info!(
@@ -733,6 +1005,13 @@ impl App {
                 Task::none()
             }
 
+            Message::WizardConsolidationToggleDetail(index) => {
+                if let Some(suggestion) = self.ui.wizard_suggestions.get_mut(index) {
+                    suggestion.expanded = !suggestion.expanded;
+                }
+                Task::none()
+            }
+
             Message::WizardConsolidationApply => {
                 // Apply selected wizard consolidation suggestions
                 let selected: Vec<_> = self
@@ -756,6 +1035,7 @@ impl App {
                             self.apply_layer_rule_consolidation(suggestion);
                         }
                     }
+                    self.search_index.rebuild(&self.settings);
                 }
 
                 // Clear suggestions and move to complete
@@ -796,6 +1076,8 @@ impl App {
                             merged_pattern: s.merged_pattern.clone(),
                             is_window_rule: true,
                             selected: true, // Select all by default
+                            detail: s.detail(),
+                            expanded: false,
                         });
                     }
 
@@ -809,6 +1091,8 @@ impl App {
                             merged_pattern: s.merged_pattern.clone(),
                             is_window_rule: false,
                             selected: true, // Select all by default
+                            detail: s.detail(),
+                            expanded: false,
                         });
                     }
 
@@ -838,6 +1122,15 @@ impl App {
                 Task::none()
             }
 
+            Message::ConsolidationToggleDetail(index) => {
+                if let DialogState::Consolidation { suggestions } = &mut self.ui.dialog_state {
+                    if let Some(suggestion) = suggestions.get_mut(index) {
+                        suggestion.expanded = !suggestion.expanded;
+                    }
+                }
+                Task::none()
+            }
+
             Message::ConsolidationApply => {
                 // Apply selected consolidation suggestions
                 if let DialogState::Consolidation { suggestions } = &self.ui.dialog_state {
@@ -862,6 +1155,7 @@ impl App {
                                 layer_rules_changed = true;
                             }
                         }
+                        self.search_index.rebuild(&self.settings);
 
                         // Mark affected categories as dirty
                         if window_rules_changed {
@@ -887,11 +1181,31 @@ impl App {
             // System
             Message::WindowCloseRequested => {
                 // Perform final save before exiting (blocking to prevent data loss)
-                if self.save.dirty_tracker.is_dirty() {
+                if self.save.read_only {
+                    // Another instance owns the config files
+                    if self.save.dirty_tracker.is_dirty() {
+                        log::warn!("Window closing in read-only mode, unsaved changes discarded");
+                    }
+                } else if self.save.dirty_tracker.is_dirty() {
                     log::info!("Window closing with unsaved changes, performing blocking save...");
 
                     // Take dirty categories for blocking save
-                    let dirty = self.save.dirty_tracker.take();
+                    let mut dirty = self.save.dirty_tracker.take();
+
+                    // Leave files edited outside Nirify alone rather than
+                    // overwrite them with no one left to ask
+                    if let Some(category) = self.save.external_change {
+                        dirty.remove(&category);
+                    }
+                    while let Some(category) =
+                        self.save.fingerprints.changed_on_disk(&self.paths, &dirty)
+                    {
+                        log::warn!(
+                            "{} was edited outside Nirify, not saving it on exit",
+                            category.name()
+                        );
+                        dirty.remove(&category);
+                    }
 
                     // Perform blocking save (acceptable since typically <100ms)
                     match crate::config::save_dirty(
@@ -943,7 +1257,11 @@ impl App {
                         async { crate::ipc::get_version().map_err(|e| e.to_string()) },
                         |r| Message::Tools(crate::messages::ToolsMessage::VersionLoaded(r)),
                     );
-                    return Task::batch([t1, t2, t3]);
+                    let t4 = Task::perform(
+                        async { crate::ipc::get_keyboard_layouts().map_err(|e| e.to_string()) },
+                        |r| Message::Tools(crate::messages::ToolsMessage::KeyboardLayoutsLoaded(r)),
+                    );
+                    return Task::batch([t1, t2, t3, t4]);
                 }
                 Task::none()
             }
@@ -961,9 +1279,11 @@ impl App {
             Message::LayoutExtras(msg) => self.update_layout_extras(msg),
             Message::Startup(msg) => self.update_startup(msg),
             Message::Tools(msg) => self.update_tools(msg),
+            Message::NiriEvent(event) => self.update_niri_event(event),
             Message::Preferences(msg) => self.update_preferences(msg),
             Message::ConfigEditor(msg) => self.update_config_editor(msg),
             Message::Backups(msg) => self.update_backups(msg),
+            Message::PasteConfig(msg) => self.update_paste_config(msg),
 
             Message::None => Task::none(),
 
@@ -972,6 +1292,7 @@ impl App {
                 use crate::messages::Screen;
                 self.ui.current_screen = screen;
                 self.ui.highlight_setting = None;
+                let restore = self.restore_page_scroll();
 
                 let is_connected = matches!(
                     self.ui.niri_status,
@@ -993,30 +1314,38 @@ impl App {
                             Message::Tools(crate::messages::ToolsMessage::WorkspacesLoaded(result))
                         },
                     );
-                    return Task::batch([t1, t2]);
+                    return Task::batch([restore, t1, t2]);
                 }
                 if screen == Screen::Displays && is_connected {
-                    return Task::perform(
+                    let load = Task::perform(
                         async { crate::ipc::get_full_outputs().map_err(|e| e.to_string()) },
                         |result| {
                             Message::Tools(crate::messages::ToolsMessage::OutputsLoaded(result))
                         },
                     );
+                    return Task::batch([restore, load]);
                 }
                 if screen == Screen::Rules && is_connected {
-                    return Task::perform(
+                    let workspaces = Task::perform(
                         async { crate::ipc::get_workspaces().map_err(|e| e.to_string()) },
                         |result| {
                             Message::Tools(crate::messages::ToolsMessage::WorkspacesLoaded(result))
                         },
                     );
+                    let outputs = Task::perform(
+                        async { crate::ipc::get_full_outputs().map_err(|e| e.to_string()) },
+                        |result| {
+                            Message::Tools(crate::messages::ToolsMessage::OutputsLoaded(result))
+                        },
+                    );
+                    return Task::batch([restore, workspaces, outputs]);
                 }
-                Task::none()
+                restore
             }
             Message::SetInputSubTab(tab) => {
                 self.ui.input_sub_tab = tab;
                 self.ui.highlight_setting = None;
-                Task::none()
+                self.restore_page_scroll()
             }
             Message::OpenSectionEditor(section) => {
                 self.ui.editing_section = Some(section);
@@ -1047,14 +1376,29 @@ impl App {
                 self.ui.keybindings_search = text;
                 Task::none()
             }
+            Message::KeybindingsScrolled(viewport) => {
+                self.ui.keybindings_viewport = viewport;
+                Task::none()
+            }
             Message::SetRulesSubTab(tab) => {
                 self.ui.rules_sub_tab = tab;
                 self.ui.highlight_setting = None;
-                Task::none()
+                self.restore_page_scroll()
+            }
+            Message::RulesScrolled(page, viewport) => {
+                self.ui.rules_viewports.insert(page, viewport);
+                self.update(Message::PageScrolled(viewport.offset))
             }
             Message::SetGearSubTab(tab) => {
                 self.ui.gear_sub_tab = tab;
                 self.ui.highlight_setting = None;
+                self.restore_page_scroll()
+            }
+            Message::PageScrolled(offset) => {
+                // The search view shows a different page than the screen's
+                if self.ui.highlight_setting.is_none() {
+                    self.ui.remember_scroll(offset);
+                }
                 Task::none()
             }
         }
@@ -1067,8 +1411,12 @@ impl App {
         // Add keyboard subscription based on current mode
         if self.ui.key_capture_active.is_some() {
             subs.push(self.key_capture_subscription());
-        } else if !self.settings.preferences.search_hotkey.is_empty() {
-            subs.push(self.search_hotkey_subscription());
+        } else {
+            subs.push(Self::focus_navigation_subscription());
+            subs.push(Self::undo_hotkey_subscription());
+            if !self.settings.preferences.search_hotkey.is_empty() {
+                subs.push(self.search_hotkey_subscription());
+            }
         }
 
         Subscription::batch(subs)
@@ -1083,10 +1431,17 @@ impl App {
             time::every(Duration::from_secs(5)).map(|_| Message::CheckNiriStatus),
             // System theme detection (portal or file watcher)
             crate::system_theme::subscription().map(Message::SystemThemeEvent),
+            // Live window/workspace updates from niri
+            crate::ipc::event_stream().map(Message::NiriEvent),
         ];
 
-        // Toast auto-clear check (every 500ms, only when toast is showing)
-        if self.ui.toast.is_some() {
+        // Debounced search check (every 50ms, only while a typed query waits)
+        if self.ui.search_pending() {
+            subs.push(time::every(Duration::from_millis(50)).map(|_| Message::CheckSearch));
+        }
+
+        // Toast auto-clear check (every 500ms, only when a toast or reload status is showing)
+        if self.ui.toast.is_some() || self.ui.last_reload.is_some() {
             subs.push(time::every(Duration::from_millis(500)).map(|_| Message::ClearToast));
         }
 
@@ -1127,6 +1482,43 @@ impl App {
         })
     }
 
+    /// Subscription for Tab/Shift+Tab moving focus between controls
+    fn focus_navigation_subscription() -> Subscription<Message> {
+        use iced::keyboard;
+
+        keyboard::listen().map(|event| match event {
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Tab),
+                modifiers,
+                ..
+            } => {
+                if modifiers.shift() {
+                    Message::FocusPrevious
+                } else {
+                    Message::FocusNext
+                }
+            }
+            _ => Message::None,
+        })
+    }
+
+    /// Subscription for Ctrl+Z / Ctrl+Y undoing and redoing settings edits
+    fn undo_hotkey_subscription() -> Subscription<Message> {
+        use iced::keyboard;
+
+        keyboard::listen().map(|event| match event {
+            keyboard::Event::KeyPressed { key, modifiers, .. } if modifiers.command() => {
+                match key.as_ref() {
+                    keyboard::Key::Character("z") if modifiers.shift() => Message::Redo,
+                    keyboard::Key::Character("z") => Message::Undo,
+                    keyboard::Key::Character("y") => Message::Redo,
+                    _ => Message::None,
+                }
+            }
+            _ => Message::None,
+        })
+    }
+
     /// Subscription for search hotkey (when not in key capture mode)
     fn search_hotkey_subscription(&self) -> Subscription<Message> {
         use iced::keyboard;
@@ -1167,8 +1559,12 @@ impl App {
         let status_bar = views::status_bar::view(
             is_dirty,
             save_status,
+            self.ui.last_reload.as_ref(),
+            self.save.can_revert,
+            self.save.read_only,
             self.ui.current_theme,
             self.ui.niri_status,
+            self.settings.preferences.auto_reload,
         );
 
         // Sidebar + content/status stacked horizontally
@@ -1196,13 +1592,14 @@ impl App {
             // Build results list if there are results
             let results_content: Element<'_, Message> = if !self.ui.search_query.trim().is_empty() {
                 if self.ui.search_results.is_empty() {
-                    container(
-                        text("No matching settings found")
-                            .size(14)
-                            .color([0.6, 0.6, 0.6]),
-                    )
-                    .padding(16)
-                    .into()
+                    let message = if self.ui.search_pending() {
+                        "Searching…"
+                    } else {
+                        "No matching settings found"
+                    };
+                    container(text(message).size(14).color([0.6, 0.6, 0.6]))
+                        .padding(16)
+                        .into()
                 } else {
                     let mut results_col = col![].spacing(4).padding(8);
                     for (index, result) in self.ui.search_results.iter().take(8).enumerate() {
@@ -1298,9 +1695,11 @@ impl App {
 
         // Check for search dropdown overlay (when search bar is visible)
         let with_dropdown = if self.ui.show_search_bar {
-            if let Some(dropdown) =
-                views::search_dropdown::view(&self.ui.search_results, &self.ui.search_query)
-            {
+            if let Some(dropdown) = views::search_dropdown::view(
+                &self.ui.search_results,
+                &self.ui.search_query,
+                self.ui.search_pending(),
+            ) {
                 use iced::widget::{column as col, Space};
                 // Position dropdown at top-right, below nav bar
                 let dropdown_overlay = col![
@@ -1327,6 +1726,7 @@ impl App {
                     &self.ui.window_rule_sections_expanded,
                     &self.ui.window_rule_regex_errors,
                     &self.ui.available_workspaces,
+                    &self.ui.available_outputs,
                 );
                 stack![with_dropdown, modal].into()
             } else {
@@ -1338,6 +1738,7 @@ impl App {
                     rule,
                     &self.ui.layer_rule_sections_expanded,
                     &self.ui.layer_rule_regex_errors,
+                    &self.ui.layer_rule_color_drafts,
                 );
                 stack![with_dropdown, modal].into()
             } else {
@@ -1346,10 +1747,12 @@ impl App {
         } else if let Some(idx) = self.ui.editing_keybinding_index {
             if let Some(binding) = self.settings.keybindings.bindings.get(idx) {
                 let modal = views::keybindings::editor_modal(
+                    &self.settings.keybindings,
                     binding,
                     idx,
                     &self.ui.keybinding_sections_expanded,
                     self.ui.key_capture_active,
+                    self.ui.key_capture_pending.as_deref(),
                 );
                 stack![with_dropdown, modal].into()
             } else {
@@ -1383,7 +1786,10 @@ impl App {
         if let Some(dialog) = views::dialogs::view(
             &self.ui.dialog_state,
             &self.ui.wizard_suggestions,
+            &self.ui.wizard_includes,
+            self.ui.wizard_include_handling,
             self.ui.niri_version,
+            &self.ui.paste_config_content,
         ) {
             dialog
         } else {
@@ -1430,7 +1836,10 @@ impl App {
             S::NamedWorkspaces => views::workspaces::view(&self.settings.workspaces),
             // Visuals sections
             S::FocusRing => views::appearance::focus_ring_section(&self.settings.appearance),
-            S::WindowBorder => views::appearance::border_section(&self.settings.appearance),
+            S::WindowBorder => views::appearance::border_section(
+                &self.settings.appearance,
+                self.settings.preferences.link_border_colors,
+            ),
             S::WindowShadow => views::layout_extras::shadow_section(&self.settings.layout_extras),
             S::ModifierKeys => views::behavior::modifier_keys_section(&self.settings.behavior),
             S::Animations => views::animations::view(&self.settings.animations),
@@ -1458,46 +1867,76 @@ impl App {
                 self.ui.niri_version,
                 &self.ui.tools_state,
                 &self.settings,
+                self.save.dirty_tracker.dirty_count(),
             ),
+            Screen::Favorites => {
+                let favorites = self
+                    .search_index
+                    .favorites(&self.settings.preferences.favorites);
+                views::screens::favorites::view(favorites, &self.settings)
+            }
             Screen::Layout => views::screens::layout::view(
                 &self.settings.layout_extras,
                 &self.settings.workspaces,
                 &self.settings.behavior,
                 &self.settings.appearance,
+                matches!(
+                    self.ui.niri_status,
+                    views::status_bar::NiriStatus::Connected
+                ),
             ),
-            Screen::Visuals => views::screens::visuals::view(
-                &self.settings.appearance,
-                &self.settings.animations,
-                &self.settings.cursor,
-                &self.settings.layout_extras,
-                &self.settings.behavior,
+            Screen::Visuals => self.memoized(
+                Screen::Visuals,
+                &[
+                    SettingsCategory::Appearance,
+                    SettingsCategory::Animations,
+                    SettingsCategory::Cursor,
+                    SettingsCategory::LayoutExtras,
+                    SettingsCategory::Behavior,
+                ],
+                |s| {
+                    views::screens::visuals::view(
+                        &s.appearance,
+                        &s.animations,
+                        &s.cursor,
+                        &s.layout_extras,
+                        &s.behavior,
+                    )
+                },
             ),
             Screen::Input => views::screens::input::view(&self.settings, &self.ui),
             Screen::Rules => views::screens::rules::view(
-                self.ui.rules_sub_tab,
                 &self.settings.window_rules,
-                &self.ui.rules_search,
-                self.ui.rules_filter,
-                &self.ui.window_rule_sections_expanded,
-                &self.ui.window_rule_regex_errors,
-                &self.ui.available_workspaces,
                 &self.settings.layer_rules,
-                &self.ui.layer_rule_sections_expanded,
-                &self.ui.layer_rule_regex_errors,
+                &self.ui,
             ),
             Screen::Displays => views::screens::displays::view(
                 &self.settings.outputs,
                 self.ui.selected_output_index,
                 &self.ui.output_sections_expanded,
                 &self.ui.tools_state.outputs,
+                self.ui.output_drag.as_ref(),
             ),
-            Screen::System => views::screens::system::view(
-                &self.settings.startup,
-                &self.settings.environment,
-                &self.settings.miscellaneous,
-                &self.settings.switch_events,
-                &self.settings.debug,
-                &self.settings.recent_windows,
+            Screen::System => self.memoized(
+                Screen::System,
+                &[
+                    SettingsCategory::Startup,
+                    SettingsCategory::Environment,
+                    SettingsCategory::Miscellaneous,
+                    SettingsCategory::SwitchEvents,
+                    SettingsCategory::Debug,
+                    SettingsCategory::RecentWindows,
+                ],
+                |s| {
+                    views::screens::system::view(
+                        &s.startup,
+                        &s.environment,
+                        &s.miscellaneous,
+                        &s.switch_events,
+                        &s.debug,
+                        &s.recent_windows,
+                    )
+                },
             ),
             Screen::Gear => views::screens::gear::view(
                 self.ui.gear_sub_tab,
@@ -1512,9 +1951,30 @@ impl App {
         }
     }
 
+    /// Builds a view that shows only settings, reusing the previous widget
+    /// tree until one of `categories` is edited
+    ///
+    /// `categories` must cover everything `build` reads, or edits to the rest
+    /// won't show up.
+    fn memoized(
+        &self,
+        view: impl Into<memo::MemoView>,
+        categories: &[SettingsCategory],
+        build: fn(&Settings) -> Element<'static, Message>,
+    ) -> Element<'_, Message> {
+        let key = memo::MemoKey::new(view, categories, &self.save.dirty_tracker);
+        let settings = &self.settings;
+        iced::widget::lazy(key, move |key| {
+            log::trace!("Building memoized view {:?}", key);
+            build(settings)
+        })
+        .into()
+    }
+
     /// Shows the detailed legacy page for a matched search result.
     fn search_result_content(&self) -> Element<'_, Message> {
         let setting_name = self.ui.highlight_setting.as_deref().unwrap_or_default();
+        let is_favorite = self.settings.preferences.is_favorite(setting_name);
 
         let banner = container(
             column![
@@ -1530,7 +1990,12 @@ impl App {
                 ]
                 .spacing(12)
                 .align_y(iced::Alignment::Center),
-                text(setting_name).size(24).font(fonts::UI_FONT_SEMIBOLD),
+                row![
+                    text(setting_name).size(24).font(fonts::UI_FONT_SEMIBOLD),
+                    views::screens::favorites::favorite_star(setting_name, is_favorite),
+                ]
+                .spacing(12)
+                .align_y(iced::Alignment::Center),
                 text("Showing the detailed page for the matched setting.")
                     .size(12)
                     .color(neon::ON_SURFACE_VARIANT),
@@ -1560,64 +2025,114 @@ impl App {
     }
 
     /// Creates the content area for the current page (legacy — used during transition)
-    #[allow(dead_code)]
     fn page_content(&self) -> Element<'_, Message> {
+        self.page_view(self.ui.current_page)
+    }
+
+    /// Creates the content area for `page`
+    fn page_view(&self, page: Page) -> Element<'_, Message> {
         // Each page handles its own scrollable container
-        match self.ui.current_page {
+        match page {
             Page::Overview => return self.overview_page(),
             Page::Appearance => {
-                return views::appearance::view(&self.settings.appearance);
+                return self.memoized(
+                    Page::Appearance,
+                    &[SettingsCategory::Appearance, SettingsCategory::Preferences],
+                    |s| views::appearance::view(&s.appearance, s.preferences.link_border_colors),
+                );
             }
             Page::Behavior => {
-                return views::behavior::view(&self.settings.behavior);
+                return self.memoized(Page::Behavior, &[SettingsCategory::Behavior], |s| {
+                    views::behavior::view(&s.behavior)
+                });
             }
             Page::Keyboard => {
-                return views::keyboard::view(&self.settings.keyboard);
+                let niri_connected = matches!(
+                    self.ui.niri_status,
+                    views::status_bar::NiriStatus::Connected
+                );
+                return views::keyboard::view(
+                    &self.settings.keyboard,
+                    &self.ui.keyboard_test_input,
+                    self.ui
+                        .tools_state
+                        .keyboard_layouts
+                        .as_ref()
+                        .filter(|_| niri_connected),
+                );
             }
             Page::Mouse => {
-                return views::mouse::view(&self.settings.mouse);
+                return self.memoized(Page::Mouse, &[SettingsCategory::Mouse], |s| {
+                    views::mouse::view(&s.mouse)
+                });
             }
             Page::Touchpad => {
-                return views::touchpad::view(&self.settings.touchpad);
+                return self.memoized(Page::Touchpad, &[SettingsCategory::Touchpad], |s| {
+                    views::touchpad::view(&s.touchpad)
+                });
             }
             Page::Trackpoint => {
-                return views::trackpoint::view(&self.settings.trackpoint);
+                return self.memoized(Page::Trackpoint, &[SettingsCategory::Trackpoint], |s| {
+                    views::trackpoint::view(&s.trackpoint)
+                });
             }
             Page::Trackball => {
-                return views::trackball::view(&self.settings.trackball);
+                return self.memoized(Page::Trackball, &[SettingsCategory::Trackball], |s| {
+                    views::trackball::view(&s.trackball)
+                });
             }
             Page::Tablet => {
                 return views::tablet::view(
                     &self.settings.tablet,
                     &self.ui.tablet_calibration_cache,
+                    matches!(
+                        self.ui.niri_status,
+                        crate::views::status_bar::NiriStatus::Connected
+                    ),
                 );
             }
             Page::Touch => {
-                return views::touch::view(&self.settings.touch, &self.ui.touch_calibration_cache);
+                return views::touch::view(
+                    &self.settings.touch,
+                    &self.ui.touch_calibration_cache,
+                    matches!(
+                        self.ui.niri_status,
+                        crate::views::status_bar::NiriStatus::Connected
+                    ),
+                );
             }
             Page::Animations => {
                 return views::animations::view(&self.settings.animations);
             }
             Page::Cursor => {
-                return views::cursor::view(&self.settings.cursor);
+                return self.memoized(Page::Cursor, &[SettingsCategory::Cursor], |s| {
+                    views::cursor::view(&s.cursor)
+                });
             }
             Page::LayoutExtras => {
-                return views::layout_extras::view(&self.settings.layout_extras);
+                return self.memoized(Page::LayoutExtras, &[SettingsCategory::LayoutExtras], |s| {
+                    views::layout_extras::view(&s.layout_extras)
+                });
             }
             Page::Gestures => {
-                return views::gestures::view(&self.settings.gestures);
+                return self.memoized(Page::Gestures, &[SettingsCategory::Gestures], |s| {
+                    views::gestures::view(&s.gestures)
+                });
             }
             Page::Workspaces => {
-                return views::workspaces::view(&self.settings.workspaces);
+                return self.memoized(Page::Workspaces, &[SettingsCategory::Workspaces], |s| {
+                    views::workspaces::view(&s.workspaces)
+                });
             }
+            // Shown from the top here, outside the rules screen's scrollable,
+            // so that screen's scroll position doesn't apply
             Page::WindowRules => {
                 return views::window_rules::view(
                     &self.settings.window_rules,
                     &self.ui.rules_search,
                     self.ui.rules_filter,
-                    &self.ui.window_rule_sections_expanded,
-                    &self.ui.window_rule_regex_errors,
-                    &self.ui.available_workspaces,
+                    views::widgets::ListViewport::default(),
+                    self.ui.editing_window_rule_id,
                 );
             }
             Page::LayerRules => {
@@ -1625,8 +2140,8 @@ impl App {
                     &self.settings.layer_rules,
                     &self.ui.rules_search,
                     self.ui.rules_filter,
-                    &self.ui.layer_rule_sections_expanded,
-                    &self.ui.layer_rule_regex_errors,
+                    views::widgets::ListViewport::default(),
+                    self.ui.editing_layer_rule_id,
                 );
             }
             Page::Keybindings => {
@@ -1635,6 +2150,7 @@ impl App {
                     self.ui.selected_keybinding_index,
                     &self.ui.keybinding_sections_expanded,
                     self.ui.key_capture_active,
+                    self.ui.key_capture_pending.as_deref(),
                 );
             }
             Page::Outputs => {
@@ -1646,22 +2162,36 @@ impl App {
                 );
             }
             Page::Miscellaneous => {
-                return views::miscellaneous::view(&self.settings.miscellaneous);
+                return self.memoized(
+                    Page::Miscellaneous,
+                    &[SettingsCategory::Miscellaneous],
+                    |s| views::miscellaneous::view(&s.miscellaneous),
+                );
             }
             Page::Startup => {
-                return views::startup::view(&self.settings.startup);
+                return self.memoized(Page::Startup, &[SettingsCategory::Startup], |s| {
+                    views::startup::view(&s.startup)
+                });
             }
             Page::Environment => {
-                return views::environment::view(&self.settings.environment);
+                return self.memoized(Page::Environment, &[SettingsCategory::Environment], |s| {
+                    views::environment::view(&s.environment)
+                });
             }
             Page::Debug => {
                 return views::debug::view(&self.settings.debug);
             }
             Page::SwitchEvents => {
-                return views::switch_events::view(&self.settings.switch_events);
+                return self.memoized(Page::SwitchEvents, &[SettingsCategory::SwitchEvents], |s| {
+                    views::switch_events::view(&s.switch_events)
+                });
             }
             Page::RecentWindows => {
-                return views::recent_windows::view(&self.settings.recent_windows);
+                return self.memoized(
+                    Page::RecentWindows,
+                    &[SettingsCategory::RecentWindows],
+                    |s| views::recent_windows::view(&s.recent_windows),
+                );
             }
             Page::Tools => {
                 let niri_connected = matches!(
@@ -1672,9 +2202,8 @@ impl App {
             }
             Page::Preferences => {
                 return views::preferences::view(
-                    self.settings.preferences.float_settings_app,
+                    &self.settings.preferences,
                     self.ui.show_search_bar,
-                    &self.settings.preferences.search_hotkey,
                 );
             }
             Page::ConfigEditor => {
@@ -1684,7 +2213,10 @@ impl App {
                 );
             }
             Page::Backups => {
-                return views::backups::view(&self.ui.backups_state);
+                return views::backups::view(
+                    &self.ui.backups_state,
+                    self.settings.preferences.backup_retention,
+                );
             }
         }
     }
@@ -1837,6 +2369,17 @@ impl App {
             }
 
             overview_section
+                .push(spacer(16.0))
+                .push(text("Open/Close Animation").size(18))
+                .push(
+                    text("Same setting as the overview card on the Animations page")
+                        .size(12)
+                        .color([0.7, 0.7, 0.7]),
+                )
+                .push(spacer(4.0))
+                .push(views::animations::overview_animation_card(
+                    &settings.animations,
+                ))
         };
 
         let summary = column![
@@ -1966,14 +2509,143 @@ impl App {
             .into()
     }
 
+    /// Shows `page`, switching to its screen and sub-tab
+    pub(in crate::app) fn open_page(&mut self, page: Page) {
+        use crate::messages::{GearSubTab, RulesSubTab, Screen};
+
+        self.ui.current_screen = Screen::from_page(page);
+        self.ui.current_page = page;
+
+        // Set appropriate sub-tab based on page
+        if let Some(input_tab) = Screen::input_sub_tab_from_page(page) {
+            self.ui.input_sub_tab = input_tab;
+        }
+        match page {
+            Page::WindowRules => self.ui.rules_sub_tab = RulesSubTab::WindowRules,
+            Page::LayerRules => self.ui.rules_sub_tab = RulesSubTab::LayerRules,
+            Page::Tools => self.ui.gear_sub_tab = GearSubTab::Tools,
+            Page::Preferences => self.ui.gear_sub_tab = GearSubTab::Preferences,
+            Page::ConfigEditor => self.ui.gear_sub_tab = GearSubTab::ConfigEditor,
+            Page::Backups => self.ui.gear_sub_tab = GearSubTab::Backups,
+            _ => {}
+        }
+    }
+
+    /// Scrolls the shown page back to where it was left
+    ///
+    /// Does nothing while a search match is shown, so the highlighted
+    /// setting stays in view.
+    pub(in crate::app) fn restore_page_scroll(&self) -> Task<Message> {
+        if self.ui.highlight_setting.is_some() {
+            return Task::none();
+        }
+        iced::widget::operation::scroll_to(
+            views::screens::page_scroll_id(),
+            iced::widget::scrollable::AbsoluteOffset {
+                x: 0.0,
+                y: self.ui.saved_scroll(),
+            },
+        )
+    }
+
     /// Mark that settings have changed (triggers debounced save)
     pub(crate) fn mark_changed(&mut self) {
-        self.save.last_change_time = Some(std::time::Instant::now());
+        let now = std::time::Instant::now();
+        self.save.last_change_time = Some(now);
+        self.undo
+            .record(&self.settings, self.save.dirty_tracker.peek(), now);
+    }
+
+    /// Replaces the in-memory settings with what's on disk now
+    ///
+    /// For after Nirify itself rewrote the config files, so there's nothing
+    /// left to save and nothing to undo back to.
+    pub(in crate::app) fn reload_settings_from_disk(&mut self) {
+        self.settings = crate::config::load_settings(&self.paths);
+        self.save.last_saved = self.settings.clone();
+        self.save.dirty_tracker.take();
+        // Nothing to save, but every page needs redrawing
+        self.save
+            .dirty_tracker
+            .bump_revisions(SettingsCategory::all());
+        self.save.fingerprints = crate::save_manager::FileFingerprints::read_all(&self.paths);
+        self.undo.reset(&self.settings);
+        self.search_index.rebuild(&self.settings);
+    }
+
+    /// Puts the managed files back as they were before the last save
+    fn revert_last_change(&mut self) -> Task<Message> {
+        self.save.can_revert = false;
+        let paths = self.paths.clone();
+        Task::perform(
+            async move { crate::config::revert_last_restore_point(&paths).map_err(|e| e.to_string()) },
+            |result| Message::Save(SaveMessage::RevertCompleted(result)),
+        )
+    }
+
+    /// Undoes (or redoes) the last settings edit
+    fn step_history(&mut self, undo: bool) -> Task<Message> {
+        let stepped = if undo {
+            self.undo.undo(&mut self.settings)
+        } else {
+            self.undo.redo(&mut self.settings)
+        };
+        let Some(categories) = stepped else {
+            return Task::none();
+        };
+        self.save.dirty_tracker.mark_many(&categories);
+        self.mark_changed();
+        self.search_index.rebuild(&self.settings);
+
+        let names: Vec<&str> = categories.iter().map(SettingsCategory::name).collect();
+        self.ui.toast = Some(format!(
+            "{} {}",
+            if undo {
+                "Undid changes to"
+            } else {
+                "Redid changes to"
+            },
+            names.join(", ")
+        ));
+        self.ui.toast_shown_at = Some(std::time::Instant::now());
+        Task::none()
+    }
+
+    /// Warns when `field` only takes effect after niri restarts
+    ///
+    /// The warning is shown the first time the field changes this session,
+    /// and repeated in the toast of every save that includes it.
+    pub(crate) fn note_restart_required(
+        &mut self,
+        category: SettingsCategory,
+        field: &'static str,
+    ) {
+        if !crate::config::requires_restart(category, field) {
+            return;
+        }
+        self.ui.restart_required.insert(field);
+        if !self.ui.restart_warned.insert(field) {
+            return;
+        }
+        self.ui.toast = Some(format!(
+            "`{}` only takes effect after restarting niri",
+            field
+        ));
+        self.ui.toast_shown_at = Some(std::time::Instant::now());
     }
 
     /// Check if we should save now (debounce: 300ms since last change)
+    ///
+    /// Held while the output editor is open: its edits go to niri live when
+    /// it closes, and reloading after every field change would make the
+    /// output flicker in the meantime.
     fn should_save(&self) -> bool {
-        if self.save.in_progress || !self.save.dirty_tracker.is_dirty() {
+        if self.save.in_progress
+            || self.save.read_only
+            || self.save.external_change.is_some()
+            || self.ui.editing_output_index.is_some()
+            || !self.save.dirty_tracker.is_dirty()
+        {
             return false;
         }
 
@@ -1985,15 +2657,36 @@ impl App {
 
     /// Create an async save task
     fn save_task(&mut self) -> Task<Message> {
+        if let Some(category) = self
+            .save
+            .fingerprints
+            .changed_on_disk(&self.paths, &self.save.dirty_tracker.peek())
+        {
+            // Held here so the next check doesn't ask again before the dialog opens
+            self.save.external_change = Some(category);
+            return Task::done(Message::Save(SaveMessage::ExternalChangeDetected(category)));
+        }
         self.save.in_progress = true;
         let settings = self.settings.clone();
+        self.save.in_flight = Some(settings.clone());
         let dirty = self.save.dirty_tracker.take();
         let paths = self.paths.clone();
         let feature_compat = self.ui.feature_compat;
 
         Task::perform(
             async move {
-                match crate::config::save_dirty(&paths, &settings, &dirty, feature_compat) {
+                // Taken before writing, so the reload that follows can be undone
+                let restore_point = crate::config::capture_restore_point(&paths)
+                    .map_err(|e| log::warn!("Failed to capture restore point: {}", e))
+                    .ok();
+                let result = crate::config::save_dirty(&paths, &settings, &dirty, feature_compat);
+                // Kept only if the save changed a file, even one it wrote before failing
+                if let Some(point) = &restore_point {
+                    if crate::config::restore_point_is_current(&paths, point) {
+                        crate::config::discard_restore_point(point);
+                    }
+                }
+                match result {
                     Ok(count) => SaveResult::Success {
                         files_written: count,
                         categories: dirty.into_iter().collect(),
@@ -2127,9 +2820,16 @@ pub fn run() -> iced::Result {
         .subscription(App::subscription)
         .theme(|app: &App| {
             use crate::theme::AppTheme;
-            match app.ui.current_theme {
+            let theme = match app.ui.current_theme {
                 AppTheme::System => app.ui.system_theme_state.build_theme(),
                 other => other.to_iced_theme(),
+            };
+            if app.settings.preferences.match_niri_colors {
+                let accent =
+                    crate::theme::niri_accent(&app.settings.appearance, theme.palette().background);
+                crate::theme::with_accent(&theme, accent)
+            } else {
+                theme
             }
         })
         .settings(iced::Settings {
@@ -2146,3 +2846,75 @@ pub fn run() -> iced::Result {
         })
         .run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{GearSubTab, OutputsMessage, RulesSubTab, Screen};
+    use std::time::Instant;
+
+    /// App with default settings, touching neither disk nor niri
+    fn app() -> App {
+        let (mut app, _) = App::new_with_error(String::new(), None);
+        app.ui.dialog_state = DialogState::None;
+        app
+    }
+
+    #[test]
+    fn test_screen_keeps_its_sub_tab() {
+        let mut app = app();
+        let _ = app.update(Message::NavigateToScreen(Screen::Gear));
+        let _ = app.update(Message::SetGearSubTab(GearSubTab::Backups));
+        let _ = app.update(Message::NavigateToScreen(Screen::Rules));
+        let _ = app.update(Message::SetRulesSubTab(RulesSubTab::LayerRules));
+
+        // Going back to a screen returns to the sub-tab last open on it
+        let _ = app.update(Message::NavigateToScreen(Screen::Gear));
+        assert_eq!(app.ui.current_screen, Screen::Gear);
+        assert_eq!(app.ui.scroll_page(), Some(Page::Backups));
+        let _ = app.update(Message::NavigateToScreen(Screen::Rules));
+        assert_eq!(app.ui.scroll_page(), Some(Page::LayerRules));
+    }
+
+    #[test]
+    fn test_output_editor_holds_saving_until_closed() {
+        let mut app = app();
+        let _ = app.update(Message::Outputs(OutputsMessage::AddOutput));
+        let _ = app.update(Message::Outputs(OutputsMessage::OpenEditor(0)));
+        let _ = app.update(Message::Outputs(OutputsMessage::SetScale(0, 1.5)));
+        app.save.last_change_time = Some(Instant::now() - Duration::from_secs(1));
+        assert!(!app.should_save());
+
+        let _ = app.update(Message::Outputs(OutputsMessage::CloseEditor));
+        assert!(app.should_save());
+    }
+
+    #[test]
+    fn test_restart_warning_shown_once_per_field() {
+        let mut app = app();
+        app.note_restart_required(SettingsCategory::Startup, "spawn-at-startup");
+        assert!(app.ui.toast.take().is_some());
+
+        // Later edits still count for the save toast, but don't toast again
+        app.ui.restart_required.clear();
+        app.note_restart_required(SettingsCategory::Startup, "spawn-at-startup");
+        assert!(app.ui.toast.is_none());
+        assert!(app.ui.restart_required.contains("spawn-at-startup"));
+    }
+
+    #[test]
+    fn test_rules_sub_tabs_keep_their_own_viewport() {
+        let mut app = app();
+        let _ = app.update(Message::NavigateToScreen(Screen::Rules));
+        let scrolled = views::widgets::ListViewport {
+            offset: 2400.0,
+            height: 700.0,
+        };
+        let _ = app.update(Message::RulesScrolled(Page::WindowRules, scrolled));
+
+        // Layer rules were never scrolled, so their grid starts at the top
+        let _ = app.update(Message::SetRulesSubTab(RulesSubTab::LayerRules));
+        assert_eq!(app.ui.rules_viewport(Page::LayerRules).offset, 0.0);
+        assert_eq!(app.ui.rules_viewport(Page::WindowRules), scrolled);
+    }
+}
@@ -6,7 +6,7 @@
 //! - Test business logic without UI concerns
 //! - Potentially serialize/restore UI state separately
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::messages::RulesFilter;
 
@@ -30,11 +30,13 @@ pub struct UiState {
     pub rules_sub_tab: RulesSubTab,
     /// Active sub-tab within the Gear screen
     pub gear_sub_tab: GearSubTab,
+    /// Scroll offset of each page, restored when it's shown again
+    pub page_scroll_offsets: HashMap<Page, f32>,
     /// Search query
     pub search_query: String,
     /// Search results
     pub search_results: Vec<crate::search::SearchResult>,
-    /// Last search timestamp for debouncing
+    /// When the query last changed, while its search is still pending
     pub last_search_time: Option<std::time::Instant>,
     /// Whether sidebar is expanded (for responsive design)
     pub sidebar_expanded: bool,
@@ -50,6 +52,16 @@ pub struct UiState {
     pub toast: Option<String>,
     /// When the toast was shown (for auto-clear)
     pub toast_shown_at: Option<std::time::Instant>,
+    /// Restart-only options changed since the last save, mentioned in the
+    /// save toast so the reload doesn't look like it failed
+    pub restart_required: std::collections::BTreeSet<&'static str>,
+    /// Restart-only options already warned about this session, so editing
+    /// one (e.g. typing a startup command) doesn't toast on every keystroke
+    pub restart_warned: std::collections::HashSet<&'static str>,
+    /// Outcome of the last niri config reload, shown in the status bar
+    pub last_reload: Option<crate::save_manager::ReloadResult>,
+    /// When the last reload outcome was recorded (for auto-clear)
+    pub last_reload_at: Option<std::time::Instant>,
     /// Active modal dialog (if any)
     pub dialog_state: DialogState,
     /// Current theme
@@ -68,14 +80,22 @@ pub struct UiState {
     pub selected_output_index: Option<usize>,
     /// Output being edited in modal
     pub editing_output_index: Option<usize>,
+    /// The edited output as it was when the modal opened, to tell whether
+    /// closing it has changes to apply live
+    pub editing_output_original: Option<crate::config::models::OutputConfig>,
     /// Expanded sections in outputs view
     pub output_sections_expanded: HashMap<String, bool>,
+    /// Output being dragged in the arrangement preview
+    pub output_drag: Option<views::screens::displays::OutputDrag>,
 
     // Rules shared state
     /// Search text for rules card grid
     pub rules_search: String,
     /// Filter mode for rules card grid
     pub rules_filter: RulesFilter,
+    /// Scroll position of each rules card grid, so switching sub-tabs
+    /// doesn't build one grid from the other's offset
+    pub rules_viewports: HashMap<Page, views::widgets::ListViewport>,
 
     // Layer Rules state
     /// Selected layer rule ID for list-detail view
@@ -86,6 +106,8 @@ pub struct UiState {
     pub layer_rule_sections_expanded: HashMap<(u32, String), bool>,
     /// Regex validation errors
     pub layer_rule_regex_errors: HashMap<(u32, String), String>,
+    /// Shadow colors as typed, kept while they don't parse yet
+    pub layer_rule_color_drafts: HashMap<(u32, String), String>,
 
     // Window Rules state
     /// Selected window rule ID for list-detail view
@@ -98,6 +120,8 @@ pub struct UiState {
     pub window_rule_regex_errors: HashMap<(u32, String), String>,
     /// Available workspace names from niri (for dropdowns)
     pub available_workspaces: Vec<String>,
+    /// Connected output names from niri (for dropdowns)
+    pub available_outputs: Vec<String>,
 
     // Section editor state (Layout/Visuals/System)
     /// Which section modal is open
@@ -106,6 +130,8 @@ pub struct UiState {
     // Input device editor state
     /// Which device modal is open
     pub editing_device: Option<crate::messages::EditableDevice>,
+    /// Text typed into the keyboard page's layout test field (never saved)
+    pub keyboard_test_input: String,
 
     // Keybindings state
     /// Selected keybinding index for list-detail view
@@ -114,10 +140,18 @@ pub struct UiState {
     pub editing_keybinding_index: Option<usize>,
     /// Expanded sections in keybindings view
     pub keybinding_sections_expanded: HashMap<String, bool>,
+    /// Collapsed groups in the keybindings table
+    pub keybinding_groups_collapsed: HashSet<String>,
     /// Which keybinding is currently capturing key input
     pub key_capture_active: Option<usize>,
+    /// Captured combo held back because another binding already uses it
+    pub key_capture_pending: Option<String>,
     /// Search filter for keybindings table
     pub keybindings_search: String,
+    /// Whether the keybinding template gallery is open
+    pub show_binding_templates: bool,
+    /// Scroll position of the keybindings table
+    pub keybindings_viewport: views::widgets::ListViewport,
 
     // Calibration matrix caches
     /// Cached formatted values for tablet calibration matrix
@@ -132,12 +166,19 @@ pub struct UiState {
     pub config_editor_state: views::config_editor::ConfigEditorState,
     /// Text editor content for Config Editor (stored here because Content isn't Clone)
     pub config_editor_content: text_editor::Content,
+    /// Text in the paste config dialog
+    pub paste_config_content: text_editor::Content,
     /// State for the Backups page
     pub backups_state: views::backups::BackupsState,
-    /// Pending restore index (for confirmation dialog)
-    pub pending_restore_idx: Option<usize>,
     /// Consolidation suggestions for the first-run wizard
     pub wizard_suggestions: Vec<crate::messages::ConsolidationSuggestion>,
+    /// Files config.kdl already includes, shown in the wizard's setup step
+    pub wizard_includes: Vec<std::path::PathBuf>,
+    /// Whether setup keeps those includes or imports them
+    pub wizard_include_handling: crate::config::IncludeHandling,
+    /// Border colors from before they were linked to the focus ring's, put
+    /// back on unlinking
+    pub unlinked_border_colors: Option<[crate::types::ColorOrGradient; 3]>,
 }
 
 impl UiState {
@@ -147,13 +188,232 @@ impl UiState {
         tablet_calibration_cache: [String; 6],
         touch_calibration_cache: [String; 6],
     ) -> Self {
-        Self {
+        let mut ui = Self {
             current_theme,
             tablet_calibration_cache,
             touch_calibration_cache,
             sidebar_expanded: true,
             show_search_bar: true,
             ..Default::default()
+        };
+        ui.tools_state.screencast_block_app_ids =
+            crate::config::models::DEFAULT_SCREENCAST_BLOCK_APP_IDS.join(", ");
+        ui
+    }
+
+    /// Records a new search query; the search itself runs once typing pauses
+    ///
+    /// An empty query clears the results right away, since there is nothing
+    /// to wait for.
+    pub fn set_search_query(&mut self, query: String, now: std::time::Instant) {
+        if query.trim().is_empty() {
+            self.search_results.clear();
+            self.last_search_time = None;
+        } else {
+            self.last_search_time = Some(now);
+        }
+        self.search_query = query;
+    }
+
+    /// Whether a search is waiting for typing to pause
+    pub fn search_pending(&self) -> bool {
+        self.last_search_time.is_some()
+    }
+
+    /// Whether the query has been unchanged for `debounce` and should be
+    /// searched now
+    ///
+    /// Clears the pending mark when it returns `true`, so a burst of
+    /// keystrokes leads to a single search.
+    pub fn take_due_search(
+        &mut self,
+        now: std::time::Instant,
+        debounce: std::time::Duration,
+    ) -> bool {
+        let due = self
+            .last_search_time
+            .is_some_and(|changed_at| now.saturating_duration_since(changed_at) >= debounce);
+        if due {
+            self.last_search_time = None;
+        }
+        due
+    }
+
+    /// Page whose scroll offset the current screen and sub-tab use
+    ///
+    /// `None` for screens without a single page scrollable.
+    pub fn scroll_page(&self) -> Option<Page> {
+        let page = match self.current_screen {
+            Screen::Dashboard => Page::Overview,
+            Screen::Favorites => return None,
+            Screen::Layout => Page::Behavior,
+            Screen::Visuals => Page::Appearance,
+            Screen::Input => self.input_sub_tab.page(),
+            Screen::Rules => match self.rules_sub_tab {
+                RulesSubTab::WindowRules => Page::WindowRules,
+                RulesSubTab::LayerRules => Page::LayerRules,
+            },
+            Screen::Displays => Page::Outputs,
+            Screen::System => Page::Miscellaneous,
+            Screen::Gear => match self.gear_sub_tab {
+                GearSubTab::Tools => Page::Tools,
+                GearSubTab::Preferences => Page::Preferences,
+                GearSubTab::ConfigEditor => Page::ConfigEditor,
+                GearSubTab::Backups => Page::Backups,
+            },
+        };
+        Some(page)
+    }
+
+    /// Remembers the current page's scroll offset
+    pub fn remember_scroll(&mut self, offset: f32) {
+        if let Some(page) = self.scroll_page() {
+            self.page_scroll_offsets.insert(page, offset);
+        }
+    }
+
+    /// Scroll offset the current page was left at, top if never scrolled
+    pub fn saved_scroll(&self) -> f32 {
+        self.scroll_page()
+            .and_then(|page| self.page_scroll_offsets.get(&page))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Scroll position of `page`'s rules card grid, top if never scrolled
+    pub fn rules_viewport(&self, page: Page) -> views::widgets::ListViewport {
+        self.rules_viewports.get(&page).copied().unwrap_or_default()
+    }
+
+    /// Records a reload outcome so the status bar can show it
+    pub fn record_reload(&mut self, result: crate::save_manager::ReloadResult) {
+        self.last_reload = Some(result);
+        self.last_reload_at = Some(std::time::Instant::now());
+    }
+
+    /// Clears the reload outcome once it has been shown for `after`
+    pub fn clear_expired_reload(&mut self, after: std::time::Duration) {
+        if self
+            .last_reload_at
+            .is_some_and(|shown_at| shown_at.elapsed() >= after)
+        {
+            self.last_reload = None;
+            self.last_reload_at = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save_manager::ReloadResult;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_failed_reload_sets_failure_status() {
+        let mut ui = UiState::default();
+        ui.record_reload(ReloadResult::Error {
+            message: "config error at line 3".to_string(),
+        });
+
+        let status = ui.last_reload.as_ref().unwrap();
+        assert!(!status.is_success());
+        assert_eq!(
+            status.status_message(),
+            "reload failed: config error at line 3"
+        );
+    }
+
+    #[test]
+    fn test_search_burst_runs_once_after_quiet_period() {
+        let mut ui = UiState::default();
+        let debounce = Duration::from_millis(100);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        // Three keystrokes 30ms apart, polled every 50ms
+        let keystrokes = [(0, "w"), (30, "wi"), (60, "win")];
+        let mut searched = Vec::new();
+        for tick in (0..=300).step_by(10) {
+            if let Some((_, query)) = keystrokes.iter().find(|(t, _)| *t == tick) {
+                ui.set_search_query(query.to_string(), at(tick));
+            }
+            if tick % 50 == 0 && ui.take_due_search(at(tick), debounce) {
+                searched.push((tick, ui.search_query.clone()));
+            }
         }
+
+        // One search, with the last query, once 100ms passed since it was typed
+        assert_eq!(searched, vec![(200, "win".to_string())]);
+        assert!(!ui.search_pending());
+    }
+
+    #[test]
+    fn test_clearing_search_query_cancels_pending_search() {
+        let mut ui = UiState::default();
+        let now = Instant::now();
+        ui.search_results = vec![crate::search::SearchResult {
+            page: crate::messages::Page::Appearance,
+            setting_name: "Window Gaps".to_string(),
+            description: String::new(),
+            relevance_score: 1,
+        }];
+
+        ui.set_search_query("ga".to_string(), now);
+        assert!(ui.search_pending());
+        ui.set_search_query(String::new(), now);
+        assert!(!ui.search_pending());
+        assert!(ui.search_results.is_empty());
+        assert!(!ui.take_due_search(now + Duration::from_secs(1), Duration::ZERO));
+    }
+
+    #[test]
+    fn test_reload_status_clears_after_delay() {
+        let mut ui = UiState::default();
+        ui.record_reload(ReloadResult::Success);
+
+        ui.clear_expired_reload(Duration::from_secs(60));
+        assert!(ui.last_reload.is_some());
+
+        ui.clear_expired_reload(Duration::ZERO);
+        assert!(ui.last_reload.is_none());
+        assert!(ui.last_reload_at.is_none());
+    }
+
+    #[test]
+    fn test_scroll_offset_is_kept_per_page() {
+        let mut ui = UiState {
+            current_screen: Screen::Displays,
+            ..Default::default()
+        };
+        ui.remember_scroll(120.0);
+
+        ui.current_screen = Screen::Rules;
+        assert_eq!(ui.saved_scroll(), 0.0);
+        ui.remember_scroll(40.0);
+
+        // Each rules sub-tab has its own offset
+        ui.rules_sub_tab = RulesSubTab::LayerRules;
+        assert_eq!(ui.saved_scroll(), 0.0);
+
+        ui.current_screen = Screen::Displays;
+        assert_eq!(ui.saved_scroll(), 120.0);
+        ui.current_screen = Screen::Rules;
+        ui.rules_sub_tab = RulesSubTab::WindowRules;
+        assert_eq!(ui.saved_scroll(), 40.0);
+
+        // So does each input sub-tab
+        ui.current_screen = Screen::Input;
+        ui.remember_scroll(75.0);
+        ui.input_sub_tab = InputSubTab::Touchpad;
+        assert_eq!(ui.saved_scroll(), 0.0);
+        ui.input_sub_tab = InputSubTab::Keybindings;
+        assert_eq!(ui.saved_scroll(), 75.0);
+
+        // Favorites has no page scrollable to remember
+        ui.current_screen = Screen::Favorites;
+        ui.remember_scroll(10.0);
+        assert_eq!(ui.saved_scroll(), 0.0);
+        assert_eq!(ui.page_scroll_offsets.len(), 3);
     }
 }
@@ -0,0 +1,270 @@
+//! Undo and redo for settings edits
+//!
+//! Handlers change settings in place and then call `mark_changed`, so the
+//! state before an edit is only known by remembering it: the stack keeps a
+//! copy of the settings as of the last recorded edit, and on each change
+//! compares the dirty categories against it. Each entry holds the previous
+//! contents of just the categories that changed.
+//!
+//! A slider drag marks a change on every step. Changes to the same categories
+//! within [`COALESCE_WINDOW`] of each other extend the entry in progress
+//! instead of adding one, so the whole drag undoes in one go.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::config::{Settings, SettingsCategory};
+
+/// Most edits kept for undo; the oldest are dropped first
+pub const UNDO_LIMIT: usize = 50;
+
+/// Changes closer together than this, to the same settings, undo together
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(750);
+
+/// One undoable edit
+#[derive(Debug, Clone)]
+struct UndoEntry {
+    /// Categories the edit changed
+    categories: Vec<SettingsCategory>,
+    /// Those categories as they were; the rest are left at defaults
+    settings: Settings,
+}
+
+impl UndoEntry {
+    fn capture(from: &Settings, categories: Vec<SettingsCategory>) -> Self {
+        let mut settings = Settings::default();
+        for category in &categories {
+            settings.copy_category(from, *category);
+        }
+        Self {
+            categories,
+            settings,
+        }
+    }
+
+    /// Puts these categories into `settings`, returning what they replaced
+    fn apply(self, settings: &mut Settings) -> Self {
+        let replaced = Self::capture(settings, self.categories.clone());
+        for category in &self.categories {
+            settings.copy_category(&self.settings, *category);
+        }
+        replaced
+    }
+}
+
+/// Undo and redo history of settings edits
+pub struct UndoStack {
+    undo: VecDeque<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    /// Settings as of the last recorded change
+    baseline: Settings,
+    /// Categories and time of the last recorded change, while it can grow
+    last_change: Option<(Vec<SettingsCategory>, Instant)>,
+}
+
+impl UndoStack {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            baseline: settings.clone(),
+            last_change: None,
+        }
+    }
+
+    /// Forgets all history, starting over from `settings`
+    ///
+    /// For when settings are replaced from disk rather than edited.
+    pub fn reset(&mut self, settings: &Settings) {
+        *self = Self::new(settings);
+    }
+
+    /// Records whatever changed in `candidates` since the last record
+    ///
+    /// Returns whether anything had changed. A new edit clears redo.
+    pub fn record(
+        &mut self,
+        settings: &Settings,
+        candidates: impl IntoIterator<Item = SettingsCategory>,
+        now: Instant,
+    ) -> bool {
+        let mut changed: Vec<SettingsCategory> = candidates
+            .into_iter()
+            .filter(|category| differs(&self.baseline, settings, *category))
+            .collect();
+        if changed.is_empty() {
+            return false;
+        }
+        changed.sort_by_key(|category| SettingsCategory::all().iter().position(|c| c == category));
+        changed.dedup();
+
+        let coalesce = match &self.last_change {
+            Some((categories, at)) => {
+                *categories == changed && now.duration_since(*at) < COALESCE_WINDOW
+            }
+            None => false,
+        };
+        if !coalesce {
+            self.undo
+                .push_back(UndoEntry::capture(&self.baseline, changed.clone()));
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.pop_front();
+            }
+        }
+
+        for category in &changed {
+            self.baseline.copy_category(settings, *category);
+        }
+        self.last_change = Some((changed, now));
+        self.redo.clear();
+        true
+    }
+
+    /// Reverts the last edit, returning the categories it touched
+    pub fn undo(&mut self, settings: &mut Settings) -> Option<Vec<SettingsCategory>> {
+        let entry = self.undo.pop_back()?;
+        let categories = entry.categories.clone();
+        self.redo.push(entry.apply(settings));
+        self.sync(settings, &categories);
+        Some(categories)
+    }
+
+    /// Makes the last undone edit again, returning the categories it touched
+    pub fn redo(&mut self, settings: &mut Settings) -> Option<Vec<SettingsCategory>> {
+        let entry = self.redo.pop()?;
+        let categories = entry.categories.clone();
+        self.undo.push_back(entry.apply(settings));
+        self.sync(settings, &categories);
+        Some(categories)
+    }
+
+    /// Takes `settings` as the new baseline after an undo or redo
+    fn sync(&mut self, settings: &Settings, categories: &[SettingsCategory]) {
+        for category in categories {
+            self.baseline.copy_category(settings, *category);
+        }
+        // The next edit starts its own entry
+        self.last_change = None;
+    }
+}
+
+/// Whether `category` holds different settings in `a` and `b`
+fn differs(a: &Settings, b: &Settings, category: SettingsCategory) -> bool {
+    let mut a_only = Settings::default();
+    a_only.copy_category(a, category);
+    let mut b_only = Settings::default();
+    b_only.copy_category(b, category);
+    a_only != b_only
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaps(settings: &Settings) -> f32 {
+        settings.appearance.gaps
+    }
+
+    #[test]
+    fn test_slider_drag_undoes_in_one_step() {
+        let mut settings = Settings::default();
+        let original = gaps(&settings);
+        let mut stack = UndoStack::new(&settings);
+        let start = Instant::now();
+
+        // A drag: many steps, each well within the window of the last
+        for step in 1..=20 {
+            settings.appearance.gaps = original + step as f32;
+            let at = start + Duration::from_millis(step * 50);
+            assert!(stack.record(&settings, [SettingsCategory::Appearance], at));
+        }
+
+        // A later edit to the same setting is its own entry
+        settings.appearance.gaps = 99.0;
+        stack.record(
+            &settings,
+            [SettingsCategory::Appearance],
+            start + Duration::from_secs(5),
+        );
+
+        assert_eq!(
+            stack.undo(&mut settings),
+            Some(vec![SettingsCategory::Appearance])
+        );
+        assert_eq!(gaps(&settings), original + 20.0);
+        stack.undo(&mut settings);
+        assert_eq!(gaps(&settings), original);
+        assert_eq!(stack.undo(&mut settings), None);
+
+        stack.redo(&mut settings);
+        assert_eq!(gaps(&settings), original + 20.0);
+        stack.redo(&mut settings);
+        assert_eq!(gaps(&settings), 99.0);
+        assert_eq!(stack.redo(&mut settings), None);
+    }
+
+    #[test]
+    fn test_different_categories_do_not_coalesce() {
+        let mut settings = Settings::default();
+        let mut stack = UndoStack::new(&settings);
+        let now = Instant::now();
+
+        settings.appearance.gaps = 30.0;
+        stack.record(&settings, [SettingsCategory::Appearance], now);
+        settings.behavior.focus_follows_mouse = !settings.behavior.focus_follows_mouse;
+        stack.record(&settings, [SettingsCategory::Behavior], now);
+
+        assert_eq!(
+            stack.undo(&mut settings),
+            Some(vec![SettingsCategory::Behavior])
+        );
+        assert_eq!(settings.appearance.gaps, 30.0);
+        assert_eq!(
+            stack.undo(&mut settings),
+            Some(vec![SettingsCategory::Appearance])
+        );
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut settings = Settings::default();
+        let mut stack = UndoStack::new(&settings);
+        let start = Instant::now();
+
+        for step in 0..UNDO_LIMIT + 10 {
+            settings.appearance.gaps = step as f32;
+            let at = start + COALESCE_WINDOW * (step as u32 + 1);
+            stack.record(&settings, [SettingsCategory::Appearance], at);
+        }
+
+        let mut undone = 0;
+        while stack.undo(&mut settings).is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, UNDO_LIMIT);
+        // The oldest edits were dropped, so undo stops short of the start
+        assert_eq!(gaps(&settings), 9.0);
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let mut settings = Settings::default();
+        let mut stack = UndoStack::new(&settings);
+        let now = Instant::now();
+
+        settings.appearance.gaps = 30.0;
+        stack.record(&settings, [SettingsCategory::Appearance], now);
+        stack.undo(&mut settings);
+
+        // Nothing changed: not an edit, redo stays
+        assert!(!stack.record(&settings, [SettingsCategory::Appearance], now));
+        assert!(stack.redo(&mut settings).is_some());
+        assert_eq!(gaps(&settings), 30.0);
+        stack.undo(&mut settings);
+
+        settings.appearance.gaps = 12.0;
+        stack.record(&settings, [SettingsCategory::Appearance], now);
+        assert_eq!(stack.redo(&mut settings), None);
+        assert_eq!(gaps(&settings), 12.0);
+    }
+}
@@ -0,0 +1,476 @@
+//! Backup file names, snapshots, and comparing a backup with the config it
+//! would replace
+//!
+//! Backups are named `<file>.backup-<timestamp>` (older ones use `.backup.`
+//! or `.backup.migration.` before the timestamp). Files backed up together
+//! share a timestamp and form a snapshot that is restored as one. Snapshots
+//! taken by hand add a label after the timestamp: `.backup-<timestamp>--<label>`.
+//!
+//! The diff reads from the current config to the backup, so "added" lines
+//! are the ones a restore would bring back and "removed" lines are the ones
+//! it would drop.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+
+use super::models::BackupRetention;
+use super::{atomic_write, ConfigError, ConfigPaths};
+
+/// Longest label kept in a snapshot's file names
+pub const MAX_LABEL_LEN: usize = 48;
+
+/// Separates a snapshot's timestamp from its label
+const LABEL_SEPARATOR: &str = "--";
+
+/// The backed-up file's name and the timestamp from a backup's file name
+pub fn parse_backup_name(filename: &str) -> Option<(&str, &str)> {
+    let (original, rest) = filename.split_once(".backup")?;
+    let timestamp = rest.strip_prefix('-').or_else(|| rest.strip_prefix('.'))?;
+    let timestamp = timestamp.strip_prefix("migration.").unwrap_or(timestamp);
+    (!original.is_empty() && !timestamp.is_empty()).then_some((original, timestamp))
+}
+
+/// When a backup was taken, from the timestamp in its file name
+///
+/// Understands every format backups have been named with.
+pub fn backup_time(filename: &str) -> Option<NaiveDateTime> {
+    let (_, timestamp) = parse_backup_name(filename)?;
+    let timestamp = timestamp
+        .split_once(LABEL_SEPARATOR)
+        .map_or(timestamp, |(timestamp, _)| timestamp);
+    ["%Y%m%dT%H%M%S%.f", "%Y-%m-%dT%H-%M-%S", "%Y%m%d-%H%M%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(timestamp, format).ok())
+}
+
+/// Which of `filenames` `policy` says to delete, as of `now`
+///
+/// Backups are counted and aged a snapshot at a time, so files saved
+/// together go together. Labeled snapshots are never deleted, and neither
+/// are files whose names carry no readable timestamp.
+pub fn expired_backups<'a>(
+    filenames: &[&'a str],
+    policy: BackupRetention,
+    now: NaiveDateTime,
+) -> Vec<&'a str> {
+    let mut snapshots: Vec<(NaiveDateTime, Vec<usize>)> =
+        group_by_timestamp(filenames.iter().copied())
+            .into_iter()
+            .filter(|members| backup_label(filenames[members[0]]).is_none())
+            .filter_map(|members| Some((backup_time(filenames[members[0]])?, members)))
+            .collect();
+    // Newest first
+    snapshots.sort_by_key(|(time, _)| std::cmp::Reverse(*time));
+
+    let expired: Vec<Vec<usize>> = match policy {
+        BackupRetention::Unlimited => Vec::new(),
+        BackupRetention::Count(keep) => snapshots
+            .into_iter()
+            .skip(keep)
+            .map(|(_, members)| members)
+            .collect(),
+        BackupRetention::Age(age) => {
+            let age = chrono::Duration::from_std(age).unwrap_or(chrono::Duration::MAX);
+            let cutoff = now.checked_sub_signed(age);
+            snapshots
+                .into_iter()
+                .filter(|(time, _)| cutoff.is_some_and(|cutoff| *time < cutoff))
+                .map(|(_, members)| members)
+                .collect()
+        }
+    };
+    expired
+        .into_iter()
+        .flatten()
+        .map(|i| filenames[i])
+        .collect()
+}
+
+/// The label of a backup taken as a named snapshot
+pub fn backup_label(filename: &str) -> Option<&str> {
+    let (_, timestamp) = parse_backup_name(filename)?;
+    let (_, label) = timestamp.split_once(LABEL_SEPARATOR)?;
+    Some(label).filter(|label| !label.is_empty())
+}
+
+/// A label made safe to put in a file name: ASCII letters, digits, `-` and
+/// `_`, with anything else turned into single dashes
+///
+/// `None` when nothing usable is left.
+pub fn sanitize_label(label: &str) -> Option<String> {
+    let mut sanitized = String::new();
+    for c in label.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            sanitized.push(c);
+        } else if !sanitized.is_empty() && !sanitized.ends_with('-') {
+            sanitized.push('-');
+        }
+    }
+    sanitized.truncate(MAX_LABEL_LEN);
+    let sanitized = sanitized.trim_end_matches('-');
+    (!sanitized.is_empty()).then(|| sanitized.to_string())
+}
+
+/// Copies niri's config.kdl and every managed file into a snapshot tagged
+/// with `label`, returning the backups written
+pub fn create_named_backup(paths: &ConfigPaths, label: &str) -> Result<Vec<PathBuf>, ConfigError> {
+    let label = sanitize_label(label)
+        .ok_or_else(|| ConfigError::backup_error(&paths.backup_dir, "The label is empty"))?;
+    let timestamp = Local::now().format("%Y%m%dT%H%M%S%.6f");
+    let suffix = format!(".backup-{}{}{}", timestamp, LABEL_SEPARATOR, label);
+
+    let mut files = super::restore_point::managed_files(&paths.managed_dir)?;
+    if paths.niri_config.exists() {
+        files.insert(0, paths.niri_config.clone());
+    }
+
+    std::fs::create_dir_all(&paths.backup_dir)?;
+    let mut written = Vec::with_capacity(files.len());
+    for file in files {
+        let Some(name) = file.file_name() else {
+            continue;
+        };
+        let content = std::fs::read_to_string(&file)?;
+        let backup = paths
+            .backup_dir
+            .join(format!("{}{}", name.to_string_lossy(), suffix));
+        atomic_write(&backup, &content)
+            .map_err(|e| ConfigError::backup_error(&backup, e.to_string()))?;
+        written.push(backup);
+    }
+    log::info!("Created snapshot '{}' of {} files", label, written.len());
+    Ok(written)
+}
+
+/// Indices of `filenames` grouped into snapshots by the timestamp in their
+/// names, in order of each snapshot's first file
+///
+/// A name without a timestamp is a snapshot of its own.
+pub fn group_by_timestamp<'a>(filenames: impl IntoIterator<Item = &'a str>) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(Option<&str>, Vec<usize>)> = Vec::new();
+    for (i, filename) in filenames.into_iter().enumerate() {
+        let timestamp = parse_backup_name(filename).map(|(_, timestamp)| timestamp);
+        match groups
+            .iter_mut()
+            .find(|(t, _)| timestamp.is_some() && *t == timestamp)
+        {
+            Some((_, members)) => members.push(i),
+            None => groups.push((timestamp, vec![i])),
+        }
+    }
+    groups.into_iter().map(|(_, members)| members).collect()
+}
+
+/// Where a backup of the file named `original` is restored to: niri's
+/// config.kdl, or the managed file of that name
+pub fn restore_target(original: &str, paths: &ConfigPaths) -> Option<PathBuf> {
+    if original == "config.kdl" {
+        return Some(paths.niri_config.clone());
+    }
+    super::restore_point::managed_files(&paths.managed_dir)
+        .ok()?
+        .into_iter()
+        .find(|file| file.file_name().is_some_and(|name| name == original))
+}
+
+/// One line of a line-level diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Only in the backup
+    Added(String),
+    /// Only in the current config
+    Removed(String),
+    /// In both
+    Unchanged(String),
+}
+
+impl DiffLine {
+    pub fn text(&self) -> &str {
+        match self {
+            DiffLine::Added(line) | DiffLine::Removed(line) | DiffLine::Unchanged(line) => line,
+        }
+    }
+
+    pub fn is_change(&self) -> bool {
+        !matches!(self, DiffLine::Unchanged(_))
+    }
+}
+
+/// What restoring `backup_path` over `current_path` would change
+///
+/// A missing current file counts as empty, so every line of the backup
+/// shows as added.
+pub fn diff(backup_path: &Path, current_path: &Path) -> std::io::Result<Vec<DiffLine>> {
+    let backup = std::fs::read_to_string(backup_path)?;
+    let current = match std::fs::read_to_string(current_path) {
+        Ok(current) => current,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    Ok(diff_lines(&current, &backup))
+}
+
+/// Line diff turning `old` into `new`, by longest common subsequence
+///
+/// Within a changed stretch, removed lines come before added ones.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Matching ends are common in small edits and keep the table small
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the common length of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0u32; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let unchanged = |line: &&str| DiffLine::Unchanged(line.to_string());
+    let mut lines: Vec<DiffLine> = old[..prefix].iter().map(unchanged).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            lines.push(DiffLine::Unchanged(old_mid[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j == new_mid.len() || (i < old_mid.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine::Removed(old_mid[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new_mid[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(unchanged));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CURRENT: &str = "layout {\n    gaps 16\n    center-focused-column \"never\"\n}\n";
+    const BACKUP: &str = "layout {\n    gaps 8\n    center-focused-column \"never\"\n    always-center-single-column\n}\n";
+
+    #[test]
+    fn test_parse_backup_names() {
+        assert_eq!(
+            parse_backup_name("config.kdl.backup-2024-01-15T14-30-45"),
+            Some(("config.kdl", "2024-01-15T14-30-45"))
+        );
+        assert_eq!(
+            parse_backup_name("binds.kdl.backup.20240115T143045.123456"),
+            Some(("binds.kdl", "20240115T143045.123456"))
+        );
+        assert_eq!(
+            parse_backup_name("config.kdl.backup.migration.20240115T143045.123456"),
+            Some(("config.kdl", "20240115T143045.123456"))
+        );
+        assert_eq!(parse_backup_name("config.kdl"), None);
+        assert_eq!(parse_backup_name("config.kdl.backup-"), None);
+    }
+
+    #[test]
+    fn test_group_backups_by_timestamp() {
+        let names = [
+            "config.kdl.backup-20240115T143045.000001",
+            "binds.kdl.backup-20240115T143045.000001",
+            "config.kdl.backup-20240114T090000.000000",
+            "main.kdl.backup-20240115T143045.000001",
+            "notes.txt",
+            "other.txt",
+        ];
+        assert_eq!(
+            group_by_timestamp(names),
+            vec![vec![0, 1, 3], vec![2], vec![4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_label() {
+        assert_eq!(
+            sanitize_label("  before I broke my keybindings! "),
+            Some("before-I-broke-my-keybindings".to_string())
+        );
+        assert_eq!(
+            sanitize_label("../../etc/passwd"),
+            Some("etc-passwd".to_string())
+        );
+        assert_eq!(sanitize_label("a/b\\c:d"), Some("a-b-c-d".to_string()));
+        assert_eq!(
+            sanitize_label("dual_monitor"),
+            Some("dual_monitor".to_string())
+        );
+        assert_eq!(sanitize_label("   "), None);
+        assert_eq!(sanitize_label("\u{1F600}"), None);
+        assert_eq!(
+            sanitize_label(&"x".repeat(100)).unwrap().len(),
+            MAX_LABEL_LEN
+        );
+    }
+
+    #[test]
+    fn test_named_backup_copies_every_file_as_one_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+        paths.ensure_directories().unwrap();
+        std::fs::write(&paths.niri_config, "include \"nirify/main.kdl\"\n").unwrap();
+        std::fs::write(paths.managed_dir.join("main.kdl"), "// main\n").unwrap();
+        std::fs::write(paths.managed_dir.join("input").join("keyboard.kdl"), "").unwrap();
+
+        let written = create_named_backup(&paths, "Before tweaks").unwrap();
+        let names: Vec<&str> = written
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert_eq!(
+            group_by_timestamp(names.iter().copied()),
+            vec![vec![0, 1, 2]]
+        );
+        assert!(names
+            .iter()
+            .all(|name| backup_label(name) == Some("Before-tweaks")));
+        assert_eq!(
+            std::fs::read_to_string(&written[0]).unwrap(),
+            "include \"nirify/main.kdl\"\n"
+        );
+
+        assert!(create_named_backup(&paths, "!!!").is_err());
+    }
+
+    const BACKUPS: [&str; 6] = [
+        "config.kdl.backup-20240110T120000.000000",
+        "config.kdl.backup-2024-01-12T08-00-00",
+        "config.kdl.backup-20240114T090000.000000",
+        "main.kdl.backup-20240114T090000.000000",
+        "config.kdl.backup-20240101T000000.000000--known-good",
+        "config.kdl.backup-undated",
+    ];
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2024-01-15 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_count_retention_keeps_newest_snapshots() {
+        // The two files from the 14th count as one snapshot
+        assert_eq!(
+            expired_backups(&BACKUPS, BackupRetention::Count(2), now()),
+            vec!["config.kdl.backup-20240110T120000.000000"]
+        );
+        assert_eq!(
+            expired_backups(&BACKUPS, BackupRetention::Count(1), now()),
+            vec![
+                "config.kdl.backup-2024-01-12T08-00-00",
+                "config.kdl.backup-20240110T120000.000000",
+            ]
+        );
+        assert!(expired_backups(&BACKUPS, BackupRetention::Count(3), now()).is_empty());
+    }
+
+    #[test]
+    fn test_age_retention_uses_filename_timestamps() {
+        assert_eq!(
+            expired_backups(&BACKUPS, BackupRetention::days(4), now()),
+            vec!["config.kdl.backup-20240110T120000.000000"]
+        );
+        assert_eq!(
+            expired_backups(&BACKUPS, BackupRetention::days(2), now()),
+            vec![
+                "config.kdl.backup-2024-01-12T08-00-00",
+                "config.kdl.backup-20240110T120000.000000",
+            ]
+        );
+        // Both files of the snapshot from the 14th go at once
+        assert_eq!(
+            expired_backups(&BACKUPS, BackupRetention::days(1), now()).len(),
+            4
+        );
+        assert!(expired_backups(&BACKUPS, BackupRetention::days(30), now()).is_empty());
+    }
+
+    #[test]
+    fn test_unlimited_retention_keeps_everything() {
+        assert!(expired_backups(&BACKUPS, BackupRetention::Unlimited, now()).is_empty());
+        // Even keeping nothing leaves labeled and undated backups alone
+        let expired = expired_backups(&BACKUPS, BackupRetention::Count(0), now());
+        assert_eq!(expired.len(), 4);
+        assert!(!expired
+            .iter()
+            .any(|name| name.contains("known-good") || name.contains("undated")));
+    }
+
+    #[test]
+    fn test_restore_target() {
+        let dir = TempDir::new().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+        paths.ensure_directories().unwrap();
+        std::fs::write(paths.managed_dir.join("input").join("keyboard.kdl"), "").unwrap();
+
+        assert_eq!(
+            restore_target("config.kdl", &paths),
+            Some(paths.niri_config.clone())
+        );
+        assert_eq!(
+            restore_target("keyboard.kdl", &paths),
+            Some(paths.managed_dir.join("input").join("keyboard.kdl"))
+        );
+        assert_eq!(restore_target("unknown.kdl", &paths), None);
+    }
+
+    #[test]
+    fn test_diff_marks_changed_lines() {
+        assert_eq!(
+            diff_lines(CURRENT, BACKUP),
+            vec![
+                DiffLine::Unchanged("layout {".to_string()),
+                DiffLine::Removed("    gaps 16".to_string()),
+                DiffLine::Added("    gaps 8".to_string()),
+                DiffLine::Unchanged("    center-focused-column \"never\"".to_string()),
+                DiffLine::Added("    always-center-single-column".to_string()),
+                DiffLine::Unchanged("}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_files_have_no_changes() {
+        assert!(diff_lines(CURRENT, CURRENT).iter().all(|l| !l.is_change()));
+    }
+
+    #[test]
+    fn test_diff_reads_files_and_treats_missing_current_as_empty() {
+        let dir = TempDir::new().unwrap();
+        let backup = dir.path().join("config.kdl.backup-20240115-143045");
+        let current = dir.path().join("config.kdl");
+        std::fs::write(&backup, BACKUP).unwrap();
+
+        let lines = diff(&backup, &current).unwrap();
+        assert_eq!(lines.len(), BACKUP.lines().count());
+        assert!(lines.iter().all(|l| matches!(l, DiffLine::Added(_))));
+
+        std::fs::write(&current, CURRENT).unwrap();
+        assert_eq!(
+            diff(&backup, &current).unwrap(),
+            diff_lines(CURRENT, BACKUP)
+        );
+    }
+}
@@ -14,6 +14,8 @@ pub struct ConsolidationSuggestion {
     pub description: String,
     /// IDs of rules that could be merged
     pub rule_ids: Vec<u32>,
+    /// Names of those rules, in the same order as `patterns`
+    pub rule_names: Vec<String>,
     /// Names/app-ids that would be combined
     pub patterns: Vec<String>,
     /// The suggested merged regex pattern
@@ -22,6 +24,20 @@ pub struct ConsolidationSuggestion {
     pub shared_settings: String,
 }
 
+impl ConsolidationSuggestion {
+    /// One line per merged rule (its name and pattern), then the merged pattern
+    pub fn detail(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .rule_names
+            .iter()
+            .zip(&self.patterns)
+            .map(|(name, pattern)| format!("{}: {}", name, pattern))
+            .collect();
+        lines.push(format!("Merged into: {}", self.merged_pattern));
+        lines
+    }
+}
+
 /// Result of analyzing rules for consolidation opportunities
 #[derive(Debug, Clone, Default)]
 pub struct ConsolidationAnalysis {
@@ -87,16 +103,20 @@ fn analyze_window_rules(rules: &[WindowRule]) -> Vec<ConsolidationSuggestion> {
         .into_iter()
         .filter(|(_, group)| group.len() >= 2)
         .map(|(key, group)| {
-            let patterns: Vec<String> = group
+            let (rule_names, patterns): (Vec<String>, Vec<String>) = group
                 .iter()
-                .filter_map(|r| r.matches.first().and_then(|m| m.app_id.clone()))
-                .collect();
+                .filter_map(|r| {
+                    let app_id = r.matches.first()?.app_id.clone()?;
+                    Some((member_name(&r.name, r.id), app_id))
+                })
+                .unzip();
 
             let merged = create_merged_regex(&patterns);
 
             ConsolidationSuggestion {
                 description: format!("{} window rules with same settings", group.len()),
                 rule_ids: group.iter().map(|r| r.id).collect(),
+                rule_names,
                 patterns: patterns.clone(),
                 merged_pattern: merged,
                 shared_settings: key.describe(),
@@ -125,16 +145,20 @@ fn analyze_layer_rules(rules: &[LayerRule]) -> Vec<ConsolidationSuggestion> {
         .into_iter()
         .filter(|(_, group)| group.len() >= 2)
         .map(|(key, group)| {
-            let patterns: Vec<String> = group
+            let (rule_names, patterns): (Vec<String>, Vec<String>) = group
                 .iter()
-                .filter_map(|r| r.matches.first().and_then(|m| m.namespace.clone()))
-                .collect();
+                .filter_map(|r| {
+                    let namespace = r.matches.first()?.namespace.clone()?;
+                    Some((member_name(&r.name, r.id), namespace))
+                })
+                .unzip();
 
             let merged = create_merged_regex(&patterns);
 
             ConsolidationSuggestion {
                 description: format!("{} layer rules with same settings", group.len()),
                 rule_ids: group.iter().map(|r| r.id).collect(),
+                rule_names,
                 patterns: patterns.clone(),
                 merged_pattern: merged,
                 shared_settings: key.describe(),
@@ -143,6 +167,15 @@ fn analyze_layer_rules(rules: &[LayerRule]) -> Vec<ConsolidationSuggestion> {
         .collect()
 }
 
+/// Rule name for display, falling back to its ID when unnamed
+fn member_name(name: &str, id: u32) -> String {
+    if name.is_empty() {
+        format!("Rule {}", id)
+    } else {
+        name.to_string()
+    }
+}
+
 /// Check if a window rule has only a simple app-id match (no other criteria)
 fn is_simple_app_id_rule(rule: &WindowRule) -> bool {
     // Must have exactly one match with only app_id set
@@ -209,6 +242,7 @@ struct WindowRuleEffectKey {
     open_on_output: Option<String>,
     open_on_workspace: Option<String>,
     default_column_width: Option<u32>,
+    default_column_width_fixed: Option<i32>,
     default_window_height: Option<u32>,
     open_maximized_to_edges: Option<bool>,
     scroll_factor: Option<u64>,
@@ -230,6 +264,7 @@ impl From<&WindowRule> for WindowRuleEffectKey {
             open_on_output: rule.open_on_output.clone(),
             open_on_workspace: rule.open_on_workspace.clone(),
             default_column_width: rule.default_column_width.map(|f| f.to_bits()),
+            default_column_width_fixed: rule.default_column_width_fixed,
             default_window_height: rule.default_window_height.map(|f| f.to_bits()),
             open_maximized_to_edges: rule.open_maximized_to_edges,
             scroll_factor: rule.scroll_factor.map(|f| f.to_bits()),
@@ -428,6 +463,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detail_lists_every_member() {
+        let rules: Vec<WindowRule> = ["steam", "lutris", "heroic"]
+            .iter()
+            .enumerate()
+            .map(|(i, app_id)| WindowRule {
+                id: i as u32 + 1,
+                name: if i == 2 {
+                    String::new()
+                } else {
+                    format!("{} rule", app_id)
+                },
+                matches: vec![WindowRuleMatch {
+                    app_id: Some(app_id.to_string()),
+                    ..Default::default()
+                }],
+                opacity: Some(0.9),
+                ..Default::default()
+            })
+            .collect();
+
+        let analysis = analyze_rules(&rules, &[]);
+        assert_eq!(
+            analysis.window_suggestions[0].detail(),
+            vec![
+                "steam rule: steam",
+                "lutris rule: lutris",
+                "Rule 3: heroic",
+                "Merged into: ^(steam|lutris|heroic)$",
+            ]
+        );
+    }
+
     #[test]
     fn test_no_consolidation_different_settings() {
         let rules = vec![
@@ -10,8 +10,12 @@
 //! The `DirtyTracker` is shared across the application and is marked when
 //! callbacks modify settings. When the save timer fires, only dirty categories
 //! are written to disk.
+//!
+//! Every mark also bumps the category's revision, which never resets. Views
+//! use revisions as memo keys: a screen showing only appearance settings can
+//! reuse its widget tree until the appearance revision moves.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 
 /// Categories of settings that map to individual KDL config files.
@@ -162,6 +166,8 @@ impl SettingsCategory {
 /// ```
 pub struct DirtyTracker {
     dirty: Mutex<HashSet<SettingsCategory>>,
+    /// How often each category was marked; unlike `dirty`, never cleared
+    revisions: Mutex<HashMap<SettingsCategory, u64>>,
 }
 
 impl DirtyTracker {
@@ -169,6 +175,32 @@ impl DirtyTracker {
     pub fn new() -> Self {
         Self {
             dirty: Mutex::new(HashSet::new()),
+            revisions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of times `category` has been marked, saved or not
+    ///
+    /// Changes whenever the category's settings change, so it can key caches
+    /// of anything derived from them.
+    pub fn revision(&self, category: SettingsCategory) -> u64 {
+        match self.revisions.lock() {
+            Ok(revisions) => revisions.get(&category).copied().unwrap_or(0),
+            Err(poisoned) => poisoned.into_inner().get(&category).copied().unwrap_or(0),
+        }
+    }
+
+    /// Counts `categories` as changed without marking them dirty
+    ///
+    /// For settings replaced by what's already on disk, which need redrawing
+    /// but not saving.
+    pub fn bump_revisions(&self, categories: &[SettingsCategory]) {
+        let mut revisions = match self.revisions.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        for category in categories {
+            *revisions.entry(*category).or_insert(0) += 1;
         }
     }
 
@@ -185,6 +217,8 @@ impl DirtyTracker {
             }
         };
         set.insert(category);
+        drop(set);
+        self.bump_revisions(&[category]);
         log::trace!("Marked {} as dirty", category.name());
     }
 
@@ -203,6 +237,8 @@ impl DirtyTracker {
         for category in categories {
             set.insert(*category);
         }
+        drop(set);
+        self.bump_revisions(categories);
         if !categories.is_empty() {
             log::trace!("Marked {} categories as dirty", categories.len());
         }
@@ -268,6 +304,8 @@ impl DirtyTracker {
             set.insert(*category);
         }
         log::debug!("Marked all {} categories as dirty", set.len());
+        drop(set);
+        self.bump_revisions(SettingsCategory::all());
     }
 }
 
@@ -337,6 +375,26 @@ mod tests {
         assert_eq!(tracker.dirty_count(), 1);
     }
 
+    #[test]
+    fn test_revisions_count_marks_and_survive_take() {
+        let tracker = DirtyTracker::new();
+        assert_eq!(tracker.revision(SettingsCategory::Appearance), 0);
+
+        tracker.mark(SettingsCategory::Appearance);
+        tracker.mark(SettingsCategory::Appearance);
+        tracker.mark_many(&[SettingsCategory::Appearance, SettingsCategory::Mouse]);
+        assert_eq!(tracker.revision(SettingsCategory::Appearance), 3);
+        assert_eq!(tracker.revision(SettingsCategory::Mouse), 1);
+        assert_eq!(tracker.revision(SettingsCategory::Cursor), 0);
+
+        // Saving clears dirty state but the settings didn't change
+        tracker.take();
+        assert_eq!(tracker.revision(SettingsCategory::Appearance), 3);
+
+        tracker.mark_all();
+        assert_eq!(tracker.revision(SettingsCategory::Cursor), 1);
+    }
+
     #[test]
     fn test_category_all_count() {
         // Ensure we have all 26 categories
@@ -0,0 +1,114 @@
+//! The config niri actually loads, as one read-only text
+//!
+//! niri reads `config.kdl` and splices each `include` in where it appears,
+//! so the effective config is `config.kdl` with every include replaced by
+//! the file it names: Nirify's managed files in `main.kdl` order, plus any
+//! of the user's own includes, around the hand-written content that setup
+//! preserved. Each spliced file is marked with a comment naming it.
+
+use std::fs;
+use std::path::Path;
+
+use super::replace::resolve_include_path;
+use super::ConfigPaths;
+
+/// How deep includes are followed, so an include cycle can't recurse forever
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Renders the config niri loads for `paths`, with every include expanded
+pub fn render_effective(paths: &ConfigPaths) -> String {
+    let root = paths.niri_config.parent().unwrap_or(Path::new("."));
+    let mut out = String::new();
+    render_file(&paths.niri_config, root, 0, &mut out);
+    out
+}
+
+/// Appends `file` to `out`, expanding its includes in place
+fn render_file(file: &Path, root: &Path, depth: usize, out: &mut String) {
+    let name = file.strip_prefix(root).unwrap_or(file).display();
+    let content = match fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            out.push_str(&format!("// {} could not be read: {}\n", name, e));
+            return;
+        }
+    };
+
+    out.push_str(&format!("// ── {} ──\n", name));
+    let dir = file.parent().unwrap_or(root);
+    for line in content.lines() {
+        let included = include_target(line).and_then(|target| resolve_include_path(target, dir));
+        match included {
+            Some(path) if depth < MAX_INCLUDE_DEPTH => {
+                out.push_str(&format!("// {}\n", line.trim()));
+                render_file(&path, root, depth + 1, out);
+                let included = path.strip_prefix(root).unwrap_or(&path).display();
+                out.push_str(&format!("// ── end of {} ──\n", included));
+            }
+            _ => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+}
+
+/// Path named by an `include "path"` line
+fn include_target(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix("include")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start().strip_prefix('"')?;
+    rest.split_once('"').map(|(target, _)| target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_include_target() {
+        assert_eq!(
+            include_target("include \"nirify/main.kdl\""),
+            Some("nirify/main.kdl")
+        );
+        assert_eq!(include_target("  include \"a.kdl\" // note"), Some("a.kdl"));
+        assert_eq!(include_target("// include \"a.kdl\""), None);
+        assert_eq!(include_target("includes \"a.kdl\""), None);
+    }
+
+    #[test]
+    fn test_effective_config_has_managed_and_preserved_content() {
+        let temp = tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(temp.path());
+        paths.ensure_directories().unwrap();
+        fs::write(
+            &paths.niri_config,
+            "spawn-at-startup \"waybar\"\n\ninclude \"nirify/main.kdl\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &paths.main_kdl,
+            "include \"advanced/window-rules.kdl\"\ninclude \"missing.kdl\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &paths.window_rules_kdl,
+            "window-rule {\n    match app-id=\"firefox\"\n    open-maximized true\n}\n",
+        )
+        .unwrap();
+
+        let effective = render_effective(&paths);
+        let custom = effective.find("spawn-at-startup \"waybar\"").unwrap();
+        let rule = effective.find("match app-id=\"firefox\"").unwrap();
+        // Included content lands where the include line was
+        assert!(custom < rule);
+        assert!(effective.contains("// ── nirify/advanced/window-rules.kdl ──"));
+        assert!(effective.contains("// ── end of nirify/advanced/window-rules.kdl ──"));
+        assert!(!effective.contains("// ── end of config.kdl ──"));
+        assert!(effective.contains("// include \"nirify/main.kdl\""));
+        assert!(effective.contains("nirify/missing.kdl could not be read"));
+    }
+}
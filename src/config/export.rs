@@ -0,0 +1,345 @@
+//! The whole config as one standalone file, for sharing
+//!
+//! Nirify saves settings as one file per category, pulled in through
+//! `main.kdl` and an include in `config.kdl`. An export is the same content
+//! in one file with no includes, in `main.kdl` order, so it can go in a
+//! dotfiles repo or a forum post and be loaded with `niri -c`.
+//!
+//! Several managed files write to the same section (keyboard, mouse and
+//! behavior each add to `input`), so the parts are merged into one block per
+//! section rather than repeating it. Blocks that niri allows many of, like
+//! `output` or `window-rule`, are kept apart. App preferences aren't niri
+//! config and are left out.
+
+use super::models::Settings;
+use super::storage::{
+    generate_animations_kdl, generate_appearance_kdl, generate_behavior_kdl, generate_cursor_kdl,
+    generate_debug_kdl, generate_environment_kdl, generate_gestures_kdl, generate_keybindings_kdl,
+    generate_keyboard_kdl, generate_layer_rules_kdl, generate_layout_extras_kdl, generate_misc_kdl,
+    generate_mouse_kdl, generate_outputs_kdl, generate_overview_kdl, generate_recent_windows_kdl,
+    generate_startup_kdl, generate_switch_events_kdl, generate_tablet_kdl, generate_touch_kdl,
+    generate_touchpad_kdl, generate_trackball_kdl, generate_trackpoint_kdl,
+    generate_window_rules_kdl, generate_workspaces_kdl,
+};
+use crate::version::FeatureCompat;
+
+/// Default file name offered when exporting
+pub const EXPORT_FILE_NAME: &str = "niri-config.kdl";
+
+/// Top-level blocks that can appear more than once and are never merged
+const REPEATABLE: &[&str] = &[
+    "output",
+    "workspace",
+    "window-rule",
+    "layer-rule",
+    "spawn-at-startup",
+    "spawn-sh-at-startup",
+];
+
+const HEADER: &str = "// niri config exported from Nirify\n\
+                      // Standalone: everything is in this file, nothing is included\n\n";
+
+/// Every niri setting in `settings` as one self-contained KDL file
+pub fn export_standalone(settings: &Settings, compat: FeatureCompat) -> String {
+    let emit_defaults = settings.preferences.emit_all_defaults;
+    // In main.kdl order
+    let mut parts = vec![
+        generate_appearance_kdl(&settings.appearance, &settings.behavior),
+        generate_behavior_kdl(&settings.behavior),
+        generate_keyboard_kdl(&settings.keyboard, emit_defaults),
+        generate_mouse_kdl(&settings.mouse, emit_defaults),
+        generate_touchpad_kdl(&settings.touchpad, emit_defaults, compat),
+        generate_trackpoint_kdl(&settings.trackpoint, emit_defaults),
+        generate_trackball_kdl(&settings.trackball, emit_defaults),
+        generate_tablet_kdl(&settings.tablet),
+        generate_touch_kdl(&settings.touch),
+        generate_outputs_kdl(&settings.outputs),
+        generate_animations_kdl(&settings.animations),
+        generate_cursor_kdl(&settings.cursor),
+        generate_overview_kdl(&settings.overview),
+        generate_workspaces_kdl(&settings.workspaces),
+        generate_keybindings_kdl(&settings.keybindings),
+        generate_layout_extras_kdl(&settings.layout_extras),
+        generate_gestures_kdl(&settings.gestures),
+        generate_layer_rules_kdl(&settings.layer_rules),
+        generate_misc_kdl(&settings.miscellaneous),
+        generate_window_rules_kdl(
+            &settings.window_rules,
+            settings.preferences.float_settings_app,
+        ),
+        generate_startup_kdl(&settings.startup),
+        generate_environment_kdl(&settings.environment),
+        generate_debug_kdl(&settings.debug),
+        generate_switch_events_kdl(&settings.switch_events),
+    ];
+    // Recent windows requires niri 25.11+
+    if compat.recent_windows {
+        parts.push(generate_recent_windows_kdl(&settings.recent_windows));
+    }
+
+    let joined: String = parts
+        .iter()
+        .map(|kdl| format!("{}\n", kdl.trim_end()))
+        .collect();
+    let mut items = Vec::new();
+    for item in parse_items(&mut joined.lines()) {
+        // Each part's file header and notes don't apply to a merged file
+        if !item.line.starts_with("//") {
+            merge_item(&mut items, item);
+        }
+    }
+
+    let mut out = String::from(HEADER);
+    for item in &items {
+        render(item, 0, &mut out);
+        out.push('\n');
+    }
+    out
+}
+
+/// A line of generated KDL, with the lines inside it if it opens a block
+struct Item {
+    line: String,
+    children: Option<Vec<Item>>,
+}
+
+impl Item {
+    /// Name of a plain `name {` block that can be merged with another
+    ///
+    /// Slashdashed blocks are disabled copies, each kept as it is.
+    fn section(&self) -> Option<&str> {
+        self.children.as_ref()?;
+        let name = self.line.strip_suffix('{')?.trim_end();
+        (!name.starts_with("/-")
+            && !name.contains(char::is_whitespace)
+            && !REPEATABLE.contains(&name))
+        .then_some(name)
+    }
+}
+
+/// Reads items up to the `}` closing the current block
+///
+/// Generated files put each node on its own line, and a block's opening and
+/// closing braces at the ends of lines. A raw string running over several
+/// lines (a custom shader) is kept verbatim as part of the line it starts on.
+fn parse_items<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Vec<Item> {
+    let mut items = Vec::new();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line == "}" {
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let mut line = line.to_string();
+        if let Some(closer) = unclosed_raw_string(&line) {
+            for next in lines.by_ref() {
+                line.push('\n');
+                line.push_str(next);
+                if next.contains(closer.as_str()) {
+                    break;
+                }
+            }
+        }
+        let opens_block = line.ends_with('{') && !line.starts_with("//");
+        let children = opens_block.then(|| parse_items(lines));
+        items.push(Item { line, children });
+    }
+    items
+}
+
+/// The delimiter closing a raw string left open at the end of `line`
+fn unclosed_raw_string(line: &str) -> Option<String> {
+    let mut rest = line;
+    loop {
+        let start = rest.find(['"', 'r'])?;
+        let (before, from) = rest.split_at(start);
+        if before.contains("//") {
+            return None;
+        }
+        if let Some(string) = from.strip_prefix('"') {
+            // An ordinary string: skip to its end, past escaped quotes
+            let mut escaped = false;
+            let end = string.find(|c| {
+                let end = c == '"' && !escaped;
+                escaped = c == '\\' && !escaped;
+                end
+            })?;
+            rest = &string[end + 1..];
+            continue;
+        }
+        let after_r = &from[1..];
+        let hashes = after_r.len() - after_r.trim_start_matches('#').len();
+        let starts_token = before.is_empty() || before.ends_with([' ', '=', '(']);
+        match after_r[hashes..].strip_prefix('"') {
+            Some(raw) if starts_token => {
+                let closer = format!("\"{}", "#".repeat(hashes));
+                match raw.find(closer.as_str()) {
+                    Some(end) => rest = &raw[end + closer.len()..],
+                    None => return Some(closer),
+                }
+            }
+            _ => rest = after_r,
+        }
+    }
+}
+
+/// Adds `item` to `into`, folding its children into an earlier block of the
+/// same section if there is one
+fn merge_item(into: &mut Vec<Item>, item: Item) {
+    let existing = match item.section() {
+        Some(name) => into.iter_mut().find(|i| i.section() == Some(name)),
+        None => None,
+    };
+    match (existing, item.children) {
+        (Some(existing), Some(children)) => {
+            let target = existing.children.get_or_insert_with(Vec::new);
+            for child in children {
+                merge_item(target, child);
+            }
+        }
+        (_, children) => into.push(Item {
+            line: item.line,
+            children,
+        }),
+    }
+}
+
+fn render(item: &Item, depth: usize, out: &mut String) {
+    let indent = "    ".repeat(depth);
+    out.push_str(&indent);
+    out.push_str(&item.line);
+    out.push('\n');
+    if let Some(children) = &item.children {
+        for child in children {
+            render(child, depth + 1, out);
+        }
+        out.push_str(&indent);
+        out.push_str("}\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::import_from_str;
+    use crate::config::loader::helpers::extract_slashdash_rule_blocks;
+    use crate::config::models::{
+        AnimationType, EnvironmentVariable, OutputConfig, WindowRule, WindowRuleMatch,
+    };
+
+    fn customized() -> Settings {
+        let mut settings = Settings::default();
+        settings.appearance.gaps = 24.0;
+        settings.behavior.focus_follows_mouse = true;
+        settings.keyboard.xkb_layout = "us,de".to_string();
+        settings.cursor.size = 32;
+        settings.outputs.outputs.push(OutputConfig {
+            name: "DP-1".to_string(),
+            scale: 1.5,
+            position_x: 1920,
+            ..Default::default()
+        });
+        settings.environment.variables.push(EnvironmentVariable {
+            id: 1,
+            name: "MOZ_ENABLE_WAYLAND".to_string(),
+            value: "1".to_string(),
+        });
+        settings
+    }
+
+    #[test]
+    fn test_export_has_no_includes() {
+        let kdl = export_standalone(&customized(), FeatureCompat::all_enabled());
+        assert!(!kdl
+            .lines()
+            .any(|line| line.trim_start().starts_with("include")));
+        // One block per section, however many parts wrote to it
+        let doc: kdl::KdlDocument = kdl.parse().unwrap();
+        let count = |name: &str| {
+            doc.nodes()
+                .iter()
+                .filter(|n| n.name().value() == name)
+                .count()
+        };
+        assert_eq!(count("input"), 1);
+        assert_eq!(count("layout"), 1);
+        assert_eq!(count("output"), 1);
+        let input = doc.get("input").unwrap();
+        assert!(input.children().unwrap().get("keyboard").is_some());
+        assert!(input
+            .children()
+            .unwrap()
+            .get("focus-follows-mouse")
+            .is_some());
+    }
+
+    #[test]
+    fn test_export_round_trips_through_import() {
+        let settings = customized();
+        let kdl = export_standalone(&settings, FeatureCompat::all_enabled());
+        let imported = import_from_str(&kdl);
+        assert!(imported.parse_error.is_none());
+
+        let back = &imported.settings;
+        assert_eq!(back.appearance.gaps, 24.0);
+        assert!(back.behavior.focus_follows_mouse);
+        assert_eq!(back.keyboard.xkb_layout, "us,de");
+        assert_eq!(back.cursor.size, 32);
+        assert_eq!(back.outputs.outputs[0].name, "DP-1");
+        assert_eq!(back.outputs.outputs[0].position_x, 1920);
+        assert_eq!(back.environment.variables[0].name, "MOZ_ENABLE_WAYLAND");
+
+        // Exporting what was imported gives the same file
+        let again = export_standalone(back, FeatureCompat::all_enabled());
+        assert_eq!(again, kdl);
+    }
+
+    #[test]
+    fn test_export_keeps_shaders_and_disabled_rules_apart() {
+        let shader = "vec4 open_color(vec3 coords_geo, vec3 size_geo) {\n    \
+                      if (niri_clamped_progress < 0.5) {\n        \
+                      return vec4(0.0);\n    \
+                      } else {\n\n        \
+                      return texture2D(niri_tex, coords_geo.st);\n    \
+                      }\n}";
+        let mut settings = customized();
+        let window_open = &mut settings.animations.per_animation.window_open;
+        window_open.animation_type = AnimationType::CustomShader;
+        window_open.custom_shader = Some(shader.to_string());
+        for (id, app_id) in [(1, "firefox"), (2, "kitty")] {
+            settings.window_rules.rules.push(WindowRule {
+                id,
+                enabled: false,
+                name: format!("Rule {}", id),
+                matches: vec![WindowRuleMatch {
+                    app_id: Some(app_id.to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+
+        let kdl = export_standalone(&settings, FeatureCompat::all_enabled());
+        // The shader is copied as it was, blank line and all
+        assert!(kdl.contains(shader));
+        // Each disabled rule stays its own block, as the loader reads them
+        let disabled = extract_slashdash_rule_blocks(&kdl);
+        assert_eq!(disabled.len(), 2);
+        assert!(disabled[0].inner_content.contains("firefox"));
+        assert!(disabled[1].inner_content.contains("kitty"));
+
+        let imported = import_from_str(&kdl);
+        assert!(imported.parse_error.is_none());
+        let back = &imported.settings;
+        let window_open = &back.animations.per_animation.window_open;
+        assert_eq!(window_open.animation_type, AnimationType::CustomShader);
+        assert_eq!(window_open.custom_shader.as_deref(), Some(shader));
+
+        // Importing skips disabled rules, so put them back before comparing
+        let mut back = back.clone();
+        back.window_rules = settings.window_rules.clone();
+        assert_eq!(export_standalone(&back, FeatureCompat::all_enabled()), kdl);
+    }
+}
@@ -0,0 +1,195 @@
+//! Single-instance lock, so two copies of the app don't race on saves
+//!
+//! The lock is a file next to config.kdl holding the owner's PID. A lock
+//! whose process is gone is stale and reclaimed automatically.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::ConfigPaths;
+
+/// Name of the lock file in the niri config directory
+const LOCK_FILE_NAME: &str = ".nirify.lock";
+
+/// Outcome of trying to take the instance lock
+#[derive(Debug)]
+pub enum LockResult {
+    /// This instance holds the lock until the guard is dropped
+    Acquired(InstanceLock),
+    /// Another running instance holds the lock
+    HeldByOther { pid: u32 },
+    /// The lock file couldn't be used; saving is left unguarded
+    Unavailable(String),
+}
+
+/// Held instance lock; the lock file is removed on drop
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+    pid: u32,
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // Leave the file alone if another instance has taken it over since
+        if read_pid(&self.path) == Some(self.pid) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Path of the instance lock for `paths`
+pub fn instance_lock_path(paths: &ConfigPaths) -> PathBuf {
+    paths.niri_config.with_file_name(LOCK_FILE_NAME)
+}
+
+/// Takes the instance lock for this process
+pub fn acquire_instance_lock(paths: &ConfigPaths) -> LockResult {
+    acquire_lock_at(
+        &instance_lock_path(paths),
+        std::process::id(),
+        is_process_alive,
+    )
+}
+
+/// Takes the lock even though another instance holds it
+///
+/// That instance keeps running but no longer owns the lock, so it won't
+/// remove this one's lock file when it exits.
+pub fn take_over_instance_lock(paths: &ConfigPaths) -> LockResult {
+    let path = instance_lock_path(paths);
+    if let Err(e) = fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return LockResult::Unavailable(e.to_string());
+        }
+    }
+    acquire_instance_lock(paths)
+}
+
+/// Whether a process with `pid` is running
+fn is_process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// PID stored in the lock file at `path`
+fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Takes the lock at `path` for `pid`, asking `is_alive` whether the
+/// current holder is still running
+fn acquire_lock_at(path: &Path, pid: u32, is_alive: impl Fn(u32) -> bool) -> LockResult {
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            return LockResult::Unavailable(e.to_string());
+        }
+    }
+
+    // The PID is written to a file of our own first and then linked into
+    // place, so the lock never exists without it. Another instance reading a
+    // half-written lock would take it for stale and remove it
+    let staged = path.with_extension(format!("lock.{}", pid));
+    if let Err(e) = write_pid(&staged, pid) {
+        let _ = fs::remove_file(&staged);
+        return LockResult::Unavailable(e.to_string());
+    }
+    let result = link_lock(&staged, path, pid, is_alive);
+    let _ = fs::remove_file(&staged);
+    result
+}
+
+/// Writes `pid` to a fresh file at `path`
+fn write_pid(path: &Path, pid: u32) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    write!(file, "{}", pid)?;
+    file.sync_all()
+}
+
+/// Links the `staged` lock to `path` unless a live instance already holds it
+fn link_lock(staged: &Path, path: &Path, pid: u32, is_alive: impl Fn(u32) -> bool) -> LockResult {
+    // Second attempt runs after a stale lock was removed
+    for _ in 0..2 {
+        match fs::hard_link(staged, path) {
+            Ok(()) => {
+                return LockResult::Acquired(InstanceLock {
+                    path: path.to_path_buf(),
+                    pid,
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                match read_pid(path) {
+                    Some(holder) if holder != pid && is_alive(holder) => {
+                        return LockResult::HeldByOther { pid: holder };
+                    }
+                    // Dead holder, our own leftover, or unreadable: reclaim it
+                    _ => {
+                        log::info!("Reclaiming stale instance lock {:?}", path);
+                        if let Err(e) = fs::remove_file(path) {
+                            return LockResult::Unavailable(e.to_string());
+                        }
+                    }
+                }
+            }
+            Err(e) => return LockResult::Unavailable(e.to_string()),
+        }
+    }
+
+    LockResult::Unavailable("lock file keeps reappearing".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_stale_lock_is_reclaimed() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join(LOCK_FILE_NAME);
+        fs::write(&path, "4242").unwrap();
+
+        let result = acquire_lock_at(&path, 100, |pid| pid != 4242);
+        assert!(matches!(result, LockResult::Acquired(_)));
+        assert_eq!(read_pid(&path), Some(100));
+        // The staged PID file doesn't outlive the lock
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+
+        // Dropping the guard releases the lock
+        drop(result);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_live_lock_is_detected() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join(LOCK_FILE_NAME);
+
+        let first = acquire_lock_at(&path, 100, |_| true);
+        assert!(matches!(first, LockResult::Acquired(_)));
+
+        let second = acquire_lock_at(&path, 200, |_| true);
+        assert!(matches!(second, LockResult::HeldByOther { pid: 100 }));
+        // The holder's lock is untouched
+        assert_eq!(read_pid(&path), Some(100));
+    }
+
+    #[test]
+    fn test_take_over_keeps_new_owner_lock() {
+        let temp = tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(temp.path());
+        let path = instance_lock_path(&paths);
+
+        let old = acquire_lock_at(&path, 1, |_| true);
+        let new = take_over_instance_lock(&paths);
+        assert!(matches!(new, LockResult::Acquired(_)));
+
+        // The old owner exiting doesn't remove the new owner's lock
+        drop(old);
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+    }
+}
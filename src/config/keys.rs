@@ -0,0 +1,402 @@
+//! Key combo helpers shared by the keybinding loader, storage and UI
+
+use thiserror::Error;
+
+use crate::config::models::KeybindingsSettings;
+
+/// Modifiers in the order niri's own default config writes them, with the
+/// spellings niri accepts for each
+const MODIFIER_ORDER: &[(&str, &[&str])] = &[
+    ("Mod", &["mod"]),
+    ("Super", &["super", "win"]),
+    ("Ctrl", &["ctrl", "control"]),
+    ("Alt", &["alt"]),
+    ("Shift", &["shift"]),
+    ("Mod3", &["mod3"]),
+    ("ISO_Level3_Shift", &["iso_level3_shift"]),
+    ("Mod5", &["mod5"]),
+    ("ISO_Level5_Shift", &["iso_level5_shift"]),
+];
+
+/// Position of `name` in [`MODIFIER_ORDER`], in any spelling niri accepts
+fn modifier_index(name: &str) -> Option<usize> {
+    let lower = name.to_ascii_lowercase();
+    MODIFIER_ORDER
+        .iter()
+        .position(|(_, aliases)| aliases.contains(&lower.as_str()))
+}
+
+/// Whether niri recognizes `name` as a modifier
+pub fn is_modifier(name: &str) -> bool {
+    modifier_index(name).is_some()
+}
+
+/// Returns a key combo with canonical modifier spelling and order
+///
+/// `Shift+mod+q` becomes `Mod+Shift+q`. The key itself is left as written,
+/// since keysym names like `q`, `Q` and `XF86AudioMute` are case-sensitive
+/// for xkb. Unrecognized modifiers keep their spelling and go after the known
+/// ones; duplicate modifiers are dropped. Combos that don't look like
+/// `modifiers+key` (empty, trailing `+`) are returned trimmed but otherwise
+/// unchanged, so a half-typed combo isn't mangled.
+pub fn normalize_combo(combo: &str) -> String {
+    let trimmed = combo.trim();
+    let parts: Vec<&str> = trimmed.split('+').map(str::trim).collect();
+    let Some((key, modifiers)) = parts.split_last() else {
+        return trimmed.to_string();
+    };
+    if key.is_empty() || modifiers.iter().any(|m| m.is_empty()) {
+        return trimmed.to_string();
+    }
+
+    let mut known: Vec<usize> = Vec::new();
+    let mut unknown: Vec<&str> = Vec::new();
+    for modifier in modifiers {
+        match modifier_index(modifier) {
+            Some(idx) if !known.contains(&idx) => known.push(idx),
+            Some(_) => {}
+            None if !unknown.contains(modifier) => unknown.push(modifier),
+            None => {}
+        }
+    }
+    known.sort_unstable();
+
+    let mut normalized: Vec<&str> = known.iter().map(|&idx| MODIFIER_ORDER[idx].0).collect();
+    normalized.extend(unknown);
+    normalized.push(key);
+    normalized.join("+")
+}
+
+/// Whether two combos trigger on the same keys
+///
+/// Compared normalized and ignoring case, as niri matches key names, so
+/// `mod+q` and `Mod+Q` are the same binding.
+pub fn same_combo(a: &str, b: &str) -> bool {
+    combo_key(a) == combo_key(b)
+}
+
+/// Form of a combo that's equal for every combo on the same keys, for
+/// grouping bindings by what they trigger on
+pub fn combo_key(combo: &str) -> String {
+    normalize_combo(combo).to_ascii_lowercase()
+}
+
+/// Whether `combo` looks like something niri would accept: one key, after
+/// modifiers niri knows
+pub fn is_plausible_combo(combo: &str) -> bool {
+    parse_combo(combo).is_ok()
+}
+
+/// A key combo split into its parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedCombo {
+    /// Canonical modifier names, in niri's order
+    pub modifiers: Vec<&'static str>,
+    /// The key, as written
+    pub key: String,
+}
+
+/// Why a key combo isn't one niri would accept
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ComboError {
+    #[error("No key combination set")]
+    Empty,
+    #[error("Empty part between `+` signs (the + key is written `plus`)")]
+    EmptyPart,
+    #[error("Add a key after the modifiers")]
+    MissingKey,
+    #[error(
+        "`{0}` isn't a modifier niri knows (Mod, Super, Ctrl, Alt, Shift, \
+         ISO_Level3_Shift, ISO_Level5_Shift)"
+    )]
+    UnknownModifier(String),
+    #[error("{0} is used twice")]
+    DuplicateModifier(&'static str),
+    #[error("`{0}` isn't a key name; keys are written like `Return`, `comma` or `XF86AudioMute`")]
+    InvalidKey(String),
+}
+
+/// Modifier spellings that aren't also xkb key names, so can't end a combo
+const MODIFIER_ONLY_NAMES: &[&str] = &[
+    "mod", "super", "win", "ctrl", "control", "alt", "shift", "mod3", "mod5",
+];
+
+/// Checks a combo the way niri parses it: modifiers it knows, each once,
+/// then one key
+///
+/// Keys are xkb keysym names or niri's mouse, wheel and touchpad names, all
+/// made of letters, digits and underscores. Whether the keysym exists isn't
+/// checked, as that needs xkb's own tables.
+pub fn parse_combo(combo: &str) -> Result<ParsedCombo, ComboError> {
+    let trimmed = combo.trim();
+    if trimmed.is_empty() {
+        return Err(ComboError::Empty);
+    }
+    let parts: Vec<&str> = trimmed.split('+').map(str::trim).collect();
+    let (key, modifiers) = parts.split_last().ok_or(ComboError::Empty)?;
+    if modifiers.iter().any(|m| m.is_empty()) {
+        return Err(ComboError::EmptyPart);
+    }
+
+    let mut known: Vec<usize> = Vec::new();
+    for modifier in modifiers {
+        let idx = modifier_index(modifier)
+            .ok_or_else(|| ComboError::UnknownModifier(modifier.to_string()))?;
+        if known.contains(&idx) {
+            return Err(ComboError::DuplicateModifier(MODIFIER_ORDER[idx].0));
+        }
+        known.push(idx);
+    }
+    known.sort_unstable();
+
+    if key.is_empty() || MODIFIER_ONLY_NAMES.contains(&key.to_ascii_lowercase().as_str()) {
+        return Err(ComboError::MissingKey);
+    }
+    if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(ComboError::InvalidKey(key.to_string()));
+    }
+
+    Ok(ParsedCombo {
+        modifiers: known.iter().map(|&idx| MODIFIER_ORDER[idx].0).collect(),
+        key: key.to_string(),
+    })
+}
+
+/// Modifier sets tried on top of a taken combo, after Shift
+const EXTRA_MODIFIERS: &[&[&str]] = &[
+    &["Ctrl"],
+    &["Alt"],
+    &["Ctrl", "Shift"],
+    &["Alt", "Shift"],
+    &["Ctrl", "Alt"],
+    &["Ctrl", "Alt", "Shift"],
+];
+
+/// Nearest combo to `base` that no binding uses
+///
+/// Tries, in order: adding Shift, moving a number key to the next number,
+/// adding other modifiers, then all of that again with `Mod` added. The
+/// same bindings always give the same answer. Returns `base` normalized if
+/// it's free already, isn't a plausible combo, or every candidate is taken.
+pub fn suggest_free_combo(base: &str, settings: &KeybindingsSettings) -> String {
+    let base = normalize_combo(base);
+    let is_free = |combo: &str| {
+        !settings
+            .bindings
+            .iter()
+            .any(|b| same_combo(&b.key_combo, combo))
+    };
+    if !is_plausible_combo(&base) || is_free(&base) {
+        return base;
+    }
+
+    let (modifiers, key) = base.rsplit_once('+').unwrap_or(("", &base));
+    let combo = |added: &[&str], key: &str| {
+        let mut parts = added.to_vec();
+        parts.extend(modifiers.split('+').filter(|m| !m.is_empty()));
+        parts.push(key);
+        normalize_combo(&parts.join("+"))
+    };
+    let number = key.parse::<u32>().ok().filter(|n| *n <= 9);
+
+    let mut candidates = Vec::new();
+    for mod_added in [&[][..], &["Mod"][..]] {
+        if !mod_added.is_empty() {
+            if base.starts_with("Mod+") {
+                break;
+            }
+            candidates.push(combo(mod_added, key));
+        }
+        candidates.push(combo(&[mod_added, &["Shift"]].concat(), key));
+        if let Some(number) = number {
+            // 3 goes to 4, 5, ... 9, 0, 1, 2
+            candidates
+                .extend((1..10).map(|step| combo(mod_added, &((number + step) % 10).to_string())));
+        }
+        for extra in EXTRA_MODIFIERS {
+            candidates.push(combo(&[mod_added, extra].concat(), key));
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find(|candidate| is_plausible_combo(candidate) && is_free(candidate))
+        .unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorders_modifiers() {
+        assert_eq!(normalize_combo("Shift+Mod+q"), "Mod+Shift+q");
+        assert_eq!(
+            normalize_combo("Shift+Alt+Ctrl+Mod+T"),
+            "Mod+Ctrl+Alt+Shift+T"
+        );
+    }
+
+    #[test]
+    fn test_canonicalizes_modifier_spelling() {
+        assert_eq!(normalize_combo("mod+control+Return"), "Mod+Ctrl+Return");
+        assert_eq!(normalize_combo("Win+SHIFT+e"), "Super+Shift+e");
+    }
+
+    #[test]
+    fn test_preserves_key_case() {
+        assert_eq!(normalize_combo("Mod+Q"), "Mod+Q");
+        assert_eq!(normalize_combo("Mod+q"), "Mod+q");
+        assert_eq!(normalize_combo("XF86AudioMute"), "XF86AudioMute");
+        assert_eq!(
+            normalize_combo("mod+WheelScrollDown"),
+            "Mod+WheelScrollDown"
+        );
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        for combo in [
+            "Shift+Mod+q",
+            "ctrl+alt+Delete",
+            "Mod+Hyper+Shift+1",
+            "Super+Mod+Super+Left",
+            "Print",
+            "Mod+",
+            "",
+        ] {
+            let once = normalize_combo(combo);
+            assert_eq!(
+                normalize_combo(&once),
+                once,
+                "not idempotent for {:?}",
+                combo
+            );
+        }
+    }
+
+    #[test]
+    fn test_drops_duplicates_and_keeps_unknown_modifiers() {
+        assert_eq!(normalize_combo("Mod+Shift+mod+q"), "Mod+Shift+q");
+        assert_eq!(normalize_combo("Hyper+Shift+Mod+q"), "Mod+Shift+Hyper+q");
+    }
+
+    #[test]
+    fn test_leaves_incomplete_combos_alone() {
+        assert_eq!(normalize_combo(" Shift+Mod+ "), "Shift+Mod+");
+        assert_eq!(normalize_combo(""), "");
+    }
+
+    fn bound(combos: &[&str]) -> KeybindingsSettings {
+        KeybindingsSettings {
+            bindings: combos
+                .iter()
+                .map(|combo| crate::config::models::Keybinding {
+                    key_combo: combo.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_suggest_free_combo_adds_shift_first() {
+        let settings = bound(&["Mod+T", "Mod+Return"]);
+        assert_eq!(suggest_free_combo("Mod+T", &settings), "Mod+Shift+T");
+        // A free combo is returned as is
+        assert_eq!(suggest_free_combo("Mod+Y", &settings), "Mod+Y");
+    }
+
+    #[test]
+    fn test_suggest_free_combo_bumps_numbers() {
+        let settings = bound(&["Mod+1", "Mod+Shift+1", "Mod+2"]);
+        assert_eq!(suggest_free_combo("mod+1", &settings), "Mod+3");
+    }
+
+    #[test]
+    fn test_suggest_free_combo_avoids_all_bound_combos() {
+        let taken = [
+            "Mod+Q",
+            "Mod+Shift+Q",
+            "Mod+Ctrl+Q",
+            "Mod+Alt+Q",
+            "Mod+Ctrl+Shift+Q",
+            "Ctrl+Q",
+            "Shift+Q",
+        ];
+        for base in ["Mod+Q", "Ctrl+Q", "Shift+Q"] {
+            let settings = bound(&taken);
+            let suggestion = suggest_free_combo(base, &settings);
+            assert!(is_plausible_combo(&suggestion), "{}", suggestion);
+            assert!(
+                !taken.iter().any(|t| same_combo(t, &suggestion)),
+                "{} suggested taken {}",
+                base,
+                suggestion
+            );
+            // Same bindings, same answer
+            assert_eq!(suggest_free_combo(base, &settings), suggestion);
+        }
+    }
+
+    #[test]
+    fn test_is_plausible_combo() {
+        assert!(is_plausible_combo("Mod+Shift+T"));
+        assert!(is_plausible_combo("XF86AudioMute"));
+        assert!(!is_plausible_combo("Mod+"));
+        assert!(!is_plausible_combo("Hyper+T"));
+        assert!(!is_plausible_combo(""));
+    }
+
+    #[test]
+    fn test_parse_combo_canonicalizes_modifiers() {
+        assert_eq!(
+            parse_combo("shift+mod+q"),
+            Ok(ParsedCombo {
+                modifiers: vec!["Mod", "Shift"],
+                key: "q".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_combo("ISO_Level3_Shift+Ctrl+WheelScrollDown")
+                .unwrap()
+                .modifiers,
+            vec!["Ctrl", "ISO_Level3_Shift"]
+        );
+        // ISO_Level3_Shift is also a key of its own
+        assert!(parse_combo("Mod+ISO_Level3_Shift").is_ok());
+        assert!(parse_combo("XF86AudioMute").is_ok());
+    }
+
+    #[test]
+    fn test_parse_combo_rejects_malformed_input() {
+        assert_eq!(parse_combo(""), Err(ComboError::Empty));
+        assert_eq!(parse_combo("  "), Err(ComboError::Empty));
+        assert_eq!(parse_combo("Mod++"), Err(ComboError::EmptyPart));
+        assert_eq!(parse_combo("+Q"), Err(ComboError::EmptyPart));
+        assert_eq!(parse_combo("Mod+"), Err(ComboError::MissingKey));
+        assert_eq!(parse_combo("Mod+Shift"), Err(ComboError::MissingKey));
+        assert_eq!(
+            parse_combo("Supr+Q"),
+            Err(ComboError::UnknownModifier("Supr".to_string()))
+        );
+        assert_eq!(
+            parse_combo("Mod+Shift+shift+Q"),
+            Err(ComboError::DuplicateModifier("Shift"))
+        );
+        // Different spellings of one modifier are still a duplicate
+        assert_eq!(
+            parse_combo("Super+Win+Q"),
+            Err(ComboError::DuplicateModifier("Super"))
+        );
+        assert_eq!(
+            parse_combo("Mod+,"),
+            Err(ComboError::InvalidKey(",".to_string()))
+        );
+        assert_eq!(
+            parse_combo("Mod+Page Up"),
+            Err(ComboError::InvalidKey("Page Up".to_string()))
+        );
+    }
+}
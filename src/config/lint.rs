@@ -0,0 +1,252 @@
+//! Best-practice checks over the settings
+//!
+//! Unlike validation, nothing here is an error niri would reject: each lint
+//! points at a setup that is legal but probably not what the user meant.
+
+use std::collections::HashSet;
+
+use super::models::{Settings, WindowRuleMatch};
+use super::SettingsCategory;
+
+/// How much a lint matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    /// Something is very likely broken or ineffective
+    Warning,
+    /// Works, but could be cleaner
+    Suggestion,
+}
+
+/// One finding from [`lint`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub severity: LintSeverity,
+    pub message: String,
+    /// Settings page where it can be fixed
+    pub category: SettingsCategory,
+}
+
+impl Lint {
+    fn warning(category: SettingsCategory, message: impl Into<String>) -> Self {
+        Self {
+            severity: LintSeverity::Warning,
+            message: message.into(),
+            category,
+        }
+    }
+
+    fn suggestion(category: SettingsCategory, message: impl Into<String>) -> Self {
+        Self {
+            severity: LintSeverity::Suggestion,
+            message: message.into(),
+            category,
+        }
+    }
+}
+
+/// Checks `settings` for likely mistakes, warnings first
+pub fn lint(settings: &Settings) -> Vec<Lint> {
+    let mut lints = Vec::new();
+
+    let appearance = &settings.appearance;
+    if !appearance.focus_ring_enabled && !appearance.border_enabled {
+        lints.push(Lint::warning(
+            SettingsCategory::Appearance,
+            "Focus ring and border are both off, so nothing shows which window is focused",
+        ));
+    }
+
+    lint_struts(settings, &mut lints);
+
+    let outputs = &settings.outputs.outputs;
+    if !outputs.is_empty() && outputs.iter().all(|output| !output.enabled) {
+        lints.push(Lint::warning(
+            SettingsCategory::Outputs,
+            "Every configured output is disabled; a connected screen may stay dark",
+        ));
+    }
+
+    for rule in settings
+        .window_rules
+        .rules
+        .iter()
+        .filter(|rule| rule.enabled)
+    {
+        let name = if rule.name.is_empty() {
+            format!("Rule {}", rule.id)
+        } else {
+            format!("\"{}\"", rule.name)
+        };
+        // Any one match left unexcluded still lets the rule apply
+        if !rule.matches.is_empty()
+            && rule
+                .matches
+                .iter()
+                .all(|m| *m != WindowRuleMatch::default() && rule.excludes.contains(m))
+        {
+            lints.push(Lint::warning(
+                SettingsCategory::WindowRules,
+                format!(
+                    "Window rule {} excludes exactly what it matches, so it never applies",
+                    name
+                ),
+            ));
+        } else if rule.matches.contains(&WindowRuleMatch::default()) {
+            lints.push(Lint::suggestion(
+                SettingsCategory::WindowRules,
+                format!(
+                    "Window rule {} has an empty match, so it applies to every window",
+                    name
+                ),
+            ));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    for variable in &settings.environment.variables {
+        if !seen.insert(variable.name.as_str()) && reported.insert(variable.name.as_str()) {
+            lints.push(Lint::suggestion(
+                SettingsCategory::Environment,
+                format!(
+                    "Environment variable {} is set more than once; only the last value is used",
+                    variable.name
+                ),
+            ));
+        }
+    }
+
+    lints.sort_by_key(|lint| lint.severity);
+    lints
+}
+
+/// Struts that leave no room on the smallest configured output
+fn lint_struts(settings: &Settings, lints: &mut Vec<Lint>) {
+    let behavior = &settings.behavior;
+    let smallest = settings
+        .outputs
+        .outputs
+        .iter()
+        .filter(|output| output.enabled)
+        .filter_map(|output| {
+            let (w, h) = mode_size(&output.mode)?;
+            Some(output.logical_size(w, h))
+        })
+        .min_by_key(|&(w, h)| w.min(h));
+    let Some((width, height)) = smallest else {
+        return;
+    };
+
+    let horizontal = behavior.strut_left + behavior.strut_right;
+    let vertical = behavior.strut_top + behavior.strut_bottom;
+    if horizontal >= width as f32 || vertical >= height as f32 {
+        lints.push(Lint::warning(
+            SettingsCategory::Behavior,
+            format!(
+                "Struts ({} px across, {} px down) leave no room on a {}x{} output",
+                horizontal, vertical, width, height
+            ),
+        ));
+    }
+}
+
+/// Width and height from a mode like `2560x1440@144.000`
+fn mode_size(mode: &str) -> Option<(u32, u32)> {
+    let resolution = mode.split('@').next()?;
+    let (w, h) = resolution.split_once('x')?;
+    Some((w.trim().parse().ok()?, h.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::models::{EnvironmentVariable, OutputConfig, WindowRule};
+
+    #[test]
+    fn test_default_settings_are_clean() {
+        assert!(lint(&Settings::default()).is_empty());
+    }
+
+    #[test]
+    fn test_no_focus_indication() {
+        let mut settings = Settings::default();
+        settings.appearance.focus_ring_enabled = false;
+        settings.appearance.border_enabled = false;
+
+        let lints = lint(&settings);
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].severity, LintSeverity::Warning);
+        assert_eq!(lints[0].category, SettingsCategory::Appearance);
+    }
+
+    #[test]
+    fn test_struts_wider_than_output() {
+        let mut settings = Settings::default();
+        settings.outputs.outputs.push(OutputConfig {
+            mode: "1920x1080@60".to_string(),
+            scale: 2.0,
+            ..Default::default()
+        });
+        settings.behavior.strut_left = 500.0;
+        settings.behavior.strut_right = 500.0;
+
+        let lints = lint(&settings);
+        assert!(lints
+            .iter()
+            .any(|l| l.category == SettingsCategory::Behavior));
+
+        // Fits at scale 1
+        settings.outputs.outputs[0].scale = 1.0;
+        assert!(lint(&settings).is_empty());
+    }
+
+    #[test]
+    fn test_rule_excluding_its_own_match() {
+        let mut settings = Settings::default();
+        let matcher = WindowRuleMatch {
+            app_id: Some("firefox".to_string()),
+            ..Default::default()
+        };
+        settings.window_rules.rules.push(WindowRule {
+            name: "Browser".to_string(),
+            matches: vec![matcher.clone()],
+            excludes: vec![matcher],
+            ..Default::default()
+        });
+
+        let lints = lint(&settings);
+        assert_eq!(lints.len(), 1);
+        assert!(lints[0].message.contains("\"Browser\""));
+
+        // A second match that isn't excluded still applies
+        settings.window_rules.rules[0]
+            .matches
+            .push(WindowRuleMatch {
+                app_id: Some("chromium".to_string()),
+                ..Default::default()
+            });
+        assert!(lint(&settings).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_env_var_and_disabled_outputs() {
+        let mut settings = Settings::default();
+        for value in ["1", "0", "1"] {
+            settings.environment.variables.push(EnvironmentVariable {
+                name: "QT_QPA_PLATFORM".to_string(),
+                value: value.to_string(),
+                ..Default::default()
+            });
+        }
+        settings.outputs.outputs.push(OutputConfig {
+            enabled: false,
+            ..Default::default()
+        });
+
+        let lints = lint(&settings);
+        assert_eq!(lints.len(), 2);
+        // Warnings sort first
+        assert_eq!(lints[0].category, SettingsCategory::Outputs);
+        assert_eq!(lints[1].category, SettingsCategory::Environment);
+    }
+}
@@ -43,7 +43,7 @@ fn parse_styled_feature(children: &KdlDocument, feature_name: &str) -> Option<St
     let feature_children = node.children()?;
 
     // Check for "off" flag
-    if feature_children.get("off").is_some() {
+    if has_flag(feature_children, &["off"]) {
         return Some(StyledFeatureData {
             enabled: false,
             width: None,
@@ -2,7 +2,8 @@
 //!
 //! Loads settings for animations, cursor, overview, and outputs.
 
-use super::super::parser::{get_f64, get_i64, get_string, has_flag};
+use super::super::parser::{get_f64, get_i64, get_string, has_flag, parse_document};
+use super::gradient::load_color_or_gradient;
 use super::helpers::{parse_color, read_kdl_file};
 use crate::config::models::{
     AnimationType, EasingCurve, LayoutOverride, OutputConfig, OutputHotCorners, Settings,
@@ -13,7 +14,7 @@ use crate::constants::{
     EPSILON_MIN, STIFFNESS_MAX, STIFFNESS_MIN,
 };
 use crate::types::{CenterFocusedColumn, Transform, VrrMode};
-use kdl::KdlDocument;
+use kdl::{KdlDocument, KdlNode};
 use log::debug;
 use std::path::Path;
 
@@ -24,9 +25,11 @@ type AnimationAccessor = (
 );
 
 /// Parse spring parameters from a KDL node's children
-fn parse_spring_params(children: &KdlDocument) -> Option<SpringParams> {
+///
+/// Parameters missing from the node keep their value from `base`.
+fn parse_spring_params(children: &KdlDocument, base: &SpringParams) -> Option<SpringParams> {
     let spring_node = children.get("spring")?;
-    let mut params = SpringParams::default();
+    let mut params = base.clone();
 
     // Parse "spring damping-ratio=1.0 stiffness=1000 epsilon=0.0001"
     for entry in spring_node.entries() {
@@ -56,21 +59,23 @@ fn parse_spring_params(children: &KdlDocument) -> Option<SpringParams> {
 
 /// Parse a single animation configuration from KDL
 ///
-/// Shared parsing logic used by both file loader and import.
-pub fn parse_single_animation(children: &KdlDocument) -> SingleAnimationConfig {
-    let mut config = SingleAnimationConfig::default();
+/// Shared parsing logic used by both file loader and import. `name` is the
+/// animation's node name; anything the block leaves out (a curve, a spring
+/// parameter) is filled from niri's default for that animation.
+pub fn parse_single_animation(name: &str, children: &KdlDocument) -> SingleAnimationConfig {
+    let mut config = SingleAnimationConfig::with_niri_defaults(name);
 
-    // Check for "off"
-    if has_flag(children, &["off"]) {
-        config.animation_type = AnimationType::Off;
-        return config;
-    }
+    // "off" disables just this animation; a curve next to it is still read
+    config.enabled = !has_flag(children, &["off"]);
 
     // Check for custom-shader (raw GLSL code)
     // Only supported for window-open, window-close, window-resize
     if let Some(shader_node) = children.get("custom-shader") {
         if let Some(first_entry) = shader_node.entries().first() {
             if let Some(code) = first_entry.value().as_string() {
+                // Saved with the code on lines of its own between the quotes
+                let code = code.strip_prefix('\n').unwrap_or(code);
+                let code = code.strip_suffix('\n').unwrap_or(code);
                 config.animation_type = AnimationType::CustomShader;
                 config.custom_shader = Some(code.to_string());
                 return config;
@@ -79,17 +84,19 @@ pub fn parse_single_animation(children: &KdlDocument) -> SingleAnimationConfig {
     }
 
     // Check for spring
-    if let Some(spring) = parse_spring_params(children) {
+    if let Some(spring) = parse_spring_params(children, &config.spring) {
         config.animation_type = AnimationType::Spring;
         config.spring = spring;
         return config;
     }
 
-    // Check for easing (duration-ms + curve)
-    if let Some(duration) = get_i64(children, &["duration-ms"]) {
+    // Check for easing (duration-ms and/or curve)
+    if children.get("duration-ms").is_some() || children.get("curve").is_some() {
         config.animation_type = AnimationType::Easing;
-        config.easing.duration_ms =
-            (duration as i32).clamp(EASING_DURATION_MIN, EASING_DURATION_MAX);
+        if let Some(duration) = get_i64(children, &["duration-ms"]) {
+            config.easing.duration_ms =
+                (duration as i32).clamp(EASING_DURATION_MIN, EASING_DURATION_MAX);
+        }
 
         // Parse curve - can be preset (e.g., "ease-out-quad") or cubic-bezier
         if let Some(curve_node) = children.get("curve") {
@@ -131,7 +138,7 @@ pub fn parse_single_animation(children: &KdlDocument) -> SingleAnimationConfig {
 /// Parse a layout override block from KDL children
 pub fn parse_layout_override(layout_children: &KdlDocument) -> Option<LayoutOverride> {
     use crate::config::models::{DefaultColumnDisplay, PresetHeight, PresetWidth};
-    use crate::types::{Color, ColorOrGradient};
+    use crate::types::Color;
 
     let mut layout = LayoutOverride::default();
 
@@ -261,15 +268,11 @@ pub fn parse_layout_override(layout_children: &KdlDocument) -> Option<LayoutOver
             if let Some(v) = get_i64(fr_children, &["width"]) {
                 layout.focus_ring_width = Some(v as i32);
             }
-            if let Some(s) = get_string(fr_children, &["active-color"]) {
-                if let Some(c) = Color::from_hex(&s) {
-                    layout.focus_ring_active = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(fr_children, "active") {
+                layout.focus_ring_active = Some(cog);
             }
-            if let Some(s) = get_string(fr_children, &["inactive-color"]) {
-                if let Some(c) = Color::from_hex(&s) {
-                    layout.focus_ring_inactive = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(fr_children, "inactive") {
+                layout.focus_ring_inactive = Some(cog);
             }
         }
     }
@@ -285,15 +288,11 @@ pub fn parse_layout_override(layout_children: &KdlDocument) -> Option<LayoutOver
             if let Some(v) = get_i64(b_children, &["width"]) {
                 layout.border_width = Some(v as i32);
             }
-            if let Some(s) = get_string(b_children, &["active-color"]) {
-                if let Some(c) = Color::from_hex(&s) {
-                    layout.border_active = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(b_children, "active") {
+                layout.border_active = Some(cog);
             }
-            if let Some(s) = get_string(b_children, &["inactive-color"]) {
-                if let Some(c) = Color::from_hex(&s) {
-                    layout.border_inactive = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(b_children, "inactive") {
+                layout.border_inactive = Some(cog);
             }
         }
     }
@@ -469,7 +468,7 @@ pub fn parse_animations_from_children(a_children: &KdlDocument, settings: &mut S
     for (name, getter) in animation_names {
         if let Some(anim_node) = a_children.get(name) {
             if let Some(anim_children) = anim_node.children() {
-                *getter(settings) = parse_single_animation(anim_children);
+                *getter(settings) = parse_single_animation(name, anim_children);
             }
         }
     }
@@ -557,6 +556,23 @@ pub fn load_overview(path: &Path, settings: &mut Settings) {
     debug!("Loaded overview settings from {:?}", path);
 }
 
+/// Output sub-nodes that `parse_output_node_children` understands.
+///
+/// Every other child of an `output` block is preserved in `extra_nodes`.
+const KNOWN_OUTPUT_NODES: &[&str] = &[
+    "off",
+    "scale",
+    "mode",
+    "modeline",
+    "position",
+    "transform",
+    "variable-refresh-rate",
+    "focus-at-startup",
+    "backdrop-color",
+    "hot-corners",
+    "layout",
+];
+
 /// Parse output settings into an OutputConfig
 ///
 /// Shared parsing logic used by both file loader and import.
@@ -599,6 +615,7 @@ pub fn parse_output_node_children(o_children: &KdlDocument, output: &mut OutputC
 
     // Position
     if let Some(pos) = o_children.get("position") {
+        output.position_set = true;
         for entry in pos.entries() {
             if let Some(name) = entry.name() {
                 if let Some(val) = entry.value().as_integer() {
@@ -674,6 +691,69 @@ pub fn parse_output_node_children(o_children: &KdlDocument, output: &mut OutputC
             output.layout_override = parse_layout_override(layout_children);
         }
     }
+
+    // Anything we don't model is kept verbatim so a save doesn't strip it
+    output.extra_nodes = o_children
+        .nodes()
+        .iter()
+        .filter(|node| !KNOWN_OUTPUT_NODES.contains(&node.name().value()))
+        .map(|node| {
+            let mut node = node.clone();
+            node.autoformat();
+            node.to_string().trim().to_string()
+        })
+        .collect();
+}
+
+/// Reads an `output` node, named by connector or by make/model/serial
+///
+/// Outputs we write matched by identity carry a `// identity` comment with
+/// its parts and the connector; it's only trusted while it still agrees with
+/// the node's name. Anything else, including an identity written by hand,
+/// keeps the node's name as is.
+pub fn parse_output_node(node: &KdlNode) -> Option<OutputConfig> {
+    let name = node.entries().first()?.value().as_string()?;
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut output = match identity_from_leading_comment(node) {
+        Some(identity) if identity.identity().as_deref() == Some(name) => identity,
+        _ => OutputConfig {
+            name: name.to_string(),
+            ..Default::default()
+        },
+    };
+
+    if let Some(o_children) = node.children() {
+        parse_output_node_children(o_children, &mut output);
+    }
+    Some(output)
+}
+
+/// Reads the `// identity name=".." make=".." model=".." serial=".."` comment
+/// above an output node
+fn identity_from_leading_comment(node: &KdlNode) -> Option<OutputConfig> {
+    let leading = &node.format()?.leading;
+    let start = leading.rfind("// identity ")?;
+    let line = leading[start + "// ".len()..].lines().next()?;
+    let doc = parse_document(line).ok()?;
+    let identity = doc.get("identity")?;
+    let prop = |key: &str| {
+        identity
+            .get(key)
+            .and_then(|value| value.as_string())
+            .map(str::to_string)
+    };
+
+    Some(OutputConfig {
+        name: prop("name").unwrap_or_default(),
+        make: Some(prop("make")?),
+        model: Some(prop("model")?),
+        serial: prop("serial"),
+        match_by_identity: true,
+        ..Default::default()
+    })
 }
 
 /// Load output settings from KDL file
@@ -687,28 +767,9 @@ pub fn load_outputs(path: &Path, settings: &mut Settings) {
     // Find all output nodes
     for node in doc.nodes() {
         if node.name().value() == "output" {
-            // Get output name from first argument
-            let name = node
-                .entries()
-                .first()
-                .and_then(|e| e.value().as_string())
-                .map(|s| s.to_string())
-                .unwrap_or_default();
-
-            if name.is_empty() {
-                continue;
+            if let Some(output) = parse_output_node(node) {
+                settings.outputs.outputs.push(output);
             }
-
-            let mut output = OutputConfig {
-                name,
-                ..Default::default()
-            };
-
-            if let Some(o_children) = node.children() {
-                parse_output_node_children(o_children, &mut output);
-            }
-
-            settings.outputs.outputs.push(output);
         }
     }
 
@@ -236,11 +236,26 @@ pub fn ensure_required_files_exist(
                     generate_appearance_kdl(&settings.appearance, &settings.behavior)
                 }
                 ConfigFile::Behavior => generate_behavior_kdl(&settings.behavior),
-                ConfigFile::Keyboard => generate_keyboard_kdl(&settings.keyboard),
-                ConfigFile::Mouse => generate_mouse_kdl(&settings.mouse),
-                ConfigFile::Touchpad => generate_touchpad_kdl(&settings.touchpad),
-                ConfigFile::Trackpoint => generate_trackpoint_kdl(&settings.trackpoint),
-                ConfigFile::Trackball => generate_trackball_kdl(&settings.trackball),
+                ConfigFile::Keyboard => generate_keyboard_kdl(
+                    &settings.keyboard,
+                    settings.preferences.emit_all_defaults,
+                ),
+                ConfigFile::Mouse => {
+                    generate_mouse_kdl(&settings.mouse, settings.preferences.emit_all_defaults)
+                }
+                ConfigFile::Touchpad => generate_touchpad_kdl(
+                    &settings.touchpad,
+                    settings.preferences.emit_all_defaults,
+                    compat,
+                ),
+                ConfigFile::Trackpoint => generate_trackpoint_kdl(
+                    &settings.trackpoint,
+                    settings.preferences.emit_all_defaults,
+                ),
+                ConfigFile::Trackball => generate_trackball_kdl(
+                    &settings.trackball,
+                    settings.preferences.emit_all_defaults,
+                ),
                 ConfigFile::Tablet => generate_tablet_kdl(&settings.tablet),
                 ConfigFile::Touch => generate_touch_kdl(&settings.touch),
                 ConfigFile::Outputs => generate_outputs_kdl(&settings.outputs),
@@ -7,17 +7,19 @@
 //! code duplication. The import functions primarily delegate to these shared
 //! parsers, with some import-specific handling (e.g., global corner radius).
 
-use super::super::models::{LayerRule, NamedWorkspace, OutputConfig, Settings, WindowRule};
-use super::super::parser::get_i64;
+use super::super::dirty::SettingsCategory;
+use super::super::models::{LayerRule, NamedWorkspace, Settings, WindowRule};
+use super::super::parser::{get_i64, parse_document};
 use super::{
-    helpers, load_keybindings, parse_animations_from_children, parse_appearance_from_doc,
-    parse_behavior_from_doc, parse_cursor_from_children, parse_debug_from_doc,
-    parse_environment_from_doc, parse_gestures_from_doc, parse_keyboard_from_children,
-    parse_layer_rule_node_children, parse_layout_extras_from_children, parse_misc_from_doc,
-    parse_mouse_from_children, parse_output_node_children, parse_overview_from_children,
-    parse_startup_from_doc, parse_switch_events_from_doc, parse_tablet_from_children,
-    parse_touch_from_children, parse_touchpad_from_children, parse_trackball_from_children,
-    parse_trackpoint_from_children, parse_window_rule_node_children, parse_workspace_node_children,
+    helpers, load_keybindings, load_keybindings_from_document, parse_animations_from_children,
+    parse_appearance_from_doc, parse_behavior_from_doc, parse_cursor_from_children,
+    parse_debug_from_doc, parse_environment_from_doc, parse_gestures_from_doc,
+    parse_keyboard_from_children, parse_layer_rule_node_children,
+    parse_layout_extras_from_children, parse_misc_from_doc, parse_mouse_from_children,
+    parse_output_node, parse_overview_from_children, parse_startup_from_doc,
+    parse_switch_events_from_doc, parse_tablet_from_children, parse_touch_from_children,
+    parse_touchpad_from_children, parse_trackball_from_children, parse_trackpoint_from_children,
+    parse_window_rule_node_children, parse_workspace_node_children,
 };
 use kdl::KdlDocument;
 use log::{debug, info, warn};
@@ -30,7 +32,7 @@ const MAX_INCLUDE_DEPTH: usize = 10;
 ///
 /// Provides detailed feedback about what was imported, what used defaults,
 /// and any warnings encountered during import.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ImportResult {
     /// The imported settings
     pub settings: Settings,
@@ -42,6 +44,41 @@ pub struct ImportResult {
     pub warnings: Vec<String>,
     /// Number of include files processed
     pub includes_processed: usize,
+    /// Why the config text couldn't be parsed (only set by `import_from_str`)
+    pub parse_error: Option<String>,
+}
+
+/// How a merge treats sections the current settings have already changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Each section the import found replaces the current one
+    #[default]
+    Overwrite,
+    /// Sections already changed from the defaults are kept as they are
+    KeepExisting,
+}
+
+/// Which of the sections an import found were merged, and which were kept
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeSummary {
+    pub applied: Vec<&'static str>,
+    pub kept: Vec<&'static str>,
+}
+
+impl MergeSummary {
+    /// Returns a summary string suitable for display
+    pub fn summary(&self) -> String {
+        match (self.applied.is_empty(), self.kept.is_empty()) {
+            (true, true) => "Nothing to merge".to_string(),
+            (false, true) => format!("Merged {}", self.applied.join(", ")),
+            (true, false) => format!("Kept your {}", self.kept.join(", ")),
+            (false, false) => format!(
+                "Merged {}; kept your {}",
+                self.applied.join(", "),
+                self.kept.join(", ")
+            ),
+        }
+    }
 }
 
 impl ImportResult {
@@ -62,6 +99,69 @@ impl ImportResult {
             )
         }
     }
+
+    /// Copies the sections the import found onto `settings`
+    ///
+    /// A section counts as found when it differs from the defaults, and then
+    /// replaces the current one whole. Other sections and the app preferences
+    /// are kept.
+    pub fn merge_into(&self, settings: &mut Settings) {
+        self.merge_with(settings, MergeStrategy::Overwrite);
+    }
+
+    /// Like [`Self::merge_into`], but with `KeepExisting` a found section
+    /// only replaces one still at its defaults
+    pub fn merge_with(&self, settings: &mut Settings, strategy: MergeStrategy) -> MergeSummary {
+        let defaults = Settings::default();
+        let mut summary = MergeSummary::default();
+
+        macro_rules! merge_sections {
+            ($($field:ident => $category:ident),* $(,)?) => {
+                $(
+                    if self.settings.$field != defaults.$field {
+                        let name = SettingsCategory::$category.name();
+                        if strategy == MergeStrategy::KeepExisting
+                            && settings.$field != defaults.$field
+                        {
+                            summary.kept.push(name);
+                        } else {
+                            settings.$field = self.settings.$field.clone();
+                            summary.applied.push(name);
+                        }
+                    }
+                )*
+            };
+        }
+
+        merge_sections!(
+            appearance => Appearance,
+            behavior => Behavior,
+            keyboard => Keyboard,
+            mouse => Mouse,
+            touchpad => Touchpad,
+            trackpoint => Trackpoint,
+            trackball => Trackball,
+            tablet => Tablet,
+            touch => Touch,
+            animations => Animations,
+            cursor => Cursor,
+            overview => Overview,
+            outputs => Outputs,
+            layout_extras => LayoutExtras,
+            gestures => Gestures,
+            miscellaneous => Miscellaneous,
+            workspaces => Workspaces,
+            layer_rules => LayerRules,
+            window_rules => WindowRules,
+            keybindings => Keybindings,
+            startup => Startup,
+            environment => Environment,
+            debug => Debug,
+            switch_events => SwitchEvents,
+            recent_windows => RecentWindows,
+        );
+        summary
+    }
 }
 
 /// Import settings from user's existing niri config.kdl
@@ -86,7 +186,6 @@ pub fn import_from_niri_config(niri_config: &Path) -> Settings {
 /// information about what was imported, what used defaults, and any warnings.
 pub fn import_from_niri_config_with_result(niri_config: &Path) -> ImportResult {
     let mut settings = Settings::default();
-    let default_settings = Settings::default();
     let mut warnings = Vec::new();
     let mut includes_processed = 0;
 
@@ -105,6 +204,54 @@ pub fn import_from_niri_config_with_result(niri_config: &Path) -> ImportResult {
     // Validate and clamp all values to valid ranges
     settings.validate();
 
+    let result = summarize_import(settings, warnings, includes_processed);
+    info!(
+        "Import complete: {} sections imported, {} includes processed",
+        result.imported_sections.len(),
+        includes_processed
+    );
+    result
+}
+
+/// Import settings from config text, such as a pasted snippet
+///
+/// Works like `import_from_niri_config_with_result` without a file on disk,
+/// so includes can't be followed and are reported as warnings instead. A
+/// parse error leaves every section at its default and is set in `parse_error`.
+pub fn import_from_str(contents: &str) -> ImportResult {
+    let mut settings = Settings::default();
+    let mut warnings = Vec::new();
+    let mut parse_error = None;
+
+    match parse_document(contents) {
+        Ok(doc) => {
+            import_from_document(&doc, &mut settings);
+            load_keybindings_from_document(&doc, &mut settings.keybindings);
+
+            for node in doc.nodes().iter().filter(|n| n.name().value() == "include") {
+                if let Some(path) = node.entries().first().and_then(|e| e.value().as_string()) {
+                    warnings.push(format!("Skipped include (pasted config): {}", path));
+                }
+            }
+        }
+        Err(e) => parse_error = Some(e.to_string()),
+    }
+
+    settings.validate();
+    ImportResult {
+        parse_error,
+        ..summarize_import(settings, warnings, 0)
+    }
+}
+
+/// Sorts imported settings into imported and defaulted sections
+fn summarize_import(
+    settings: Settings,
+    warnings: Vec<String>,
+    includes_processed: usize,
+) -> ImportResult {
+    let default_settings = Settings::default();
+
     // Determine which sections were imported vs defaulted
     // Use &'static str to avoid allocations, convert to String only at the end
     let mut imported: Vec<&'static str> = Vec::new();
@@ -179,18 +326,13 @@ pub fn import_from_niri_config_with_result(niri_config: &Path) -> ImportResult {
         ));
     }
 
-    info!(
-        "Import complete: {} sections imported, {} includes processed",
-        imported_sections.len(),
-        includes_processed
-    );
-
     ImportResult {
         settings,
         imported_sections,
         defaulted_sections,
         warnings,
         includes_processed,
+        parse_error: None,
     }
 }
 
@@ -309,6 +451,21 @@ fn import_from_document(doc: &KdlDocument, settings: &mut Settings) {
 ///
 /// Returns None if the path escapes the allowed config directories for security.
 fn resolve_include_path(include_path: &str, config_dir: &Path) -> Option<PathBuf> {
+    // Get allowed base directory - respects $XDG_CONFIG_HOME and --config-dir
+    let niri_config_dir = crate::config::paths::niri_config_dir()?;
+    resolve_include_within(include_path, config_dir, &niri_config_dir)
+}
+
+/// Resolve an include path relative to `config_dir`, the way import does,
+/// allowing only files under `allowed_dir`
+///
+/// Returns None for paths import wouldn't follow, so callers can tell which
+/// included files had their settings imported.
+pub(crate) fn resolve_include_within(
+    include_path: &str,
+    config_dir: &Path,
+    allowed_dir: &Path,
+) -> Option<PathBuf> {
     let path = include_path.trim_matches('"');
 
     let resolved = if let Some(stripped) = path.strip_prefix("~/") {
@@ -331,12 +488,12 @@ fn resolve_include_path(include_path: &str, config_dir: &Path) -> Option<PathBuf
         }
     };
 
-    // Get allowed base directories - respect $XDG_CONFIG_HOME
-    let config_base = dirs::config_dir()?;
-    let niri_config_dir = config_base.join("niri");
+    let allowed_dir = allowed_dir
+        .canonicalize()
+        .unwrap_or_else(|_| allowed_dir.to_path_buf());
 
-    // Only allow paths under the XDG-compliant niri config dir
-    if canonical.starts_with(&niri_config_dir) {
+    // Only allow paths under the niri config dir
+    if canonical.starts_with(&allowed_dir) {
         Some(canonical)
     } else {
         warn!(
@@ -456,27 +613,9 @@ fn parse_global_input_settings(doc: &KdlDocument, settings: &mut Settings) {
 fn import_outputs_from_doc(doc: &KdlDocument, settings: &mut Settings) {
     for node in doc.nodes() {
         if node.name().value() == "output" {
-            let name = node
-                .entries()
-                .first()
-                .and_then(|e| e.value().as_string())
-                .map(|s| s.to_string())
-                .unwrap_or_default();
-
-            if name.is_empty() {
-                continue;
+            if let Some(output) = parse_output_node(node) {
+                settings.outputs.outputs.push(output);
             }
-
-            let mut output = OutputConfig {
-                name,
-                ..Default::default()
-            };
-
-            if let Some(o_children) = node.children() {
-                parse_output_node_children(o_children, &mut output);
-            }
-
-            settings.outputs.outputs.push(output);
         }
     }
 }
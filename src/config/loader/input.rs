@@ -11,7 +11,7 @@
 //! The `load_input_device` helper eliminates boilerplate for loading device settings from
 //! KDL files by handling file reading, node navigation, and logging generically.
 
-use super::super::parser::{get_f64, get_i64, get_string, has_flag};
+use super::super::parser::{get_bool, get_f64, get_i64, get_string, has_flag};
 use super::helpers::{
     parse_accel_profile, parse_click_method, parse_scroll_method, parse_tap_button_map,
     read_kdl_file,
@@ -200,6 +200,30 @@ pub fn load_mouse(path: &Path, settings: &mut Settings) {
     load_input_device(path, "mouse", settings, parse_mouse_from_children);
 }
 
+/// Touchpad sub-nodes that `parse_touchpad_from_children` understands
+///
+/// Every other child of the `touchpad` block is preserved in `extra_nodes`.
+const KNOWN_TOUCHPAD_NODES: &[&str] = &[
+    "off",
+    "tap",
+    "dwt",
+    "dwtp",
+    "drag",
+    "drag-lock",
+    "natural-scroll",
+    "left-handed",
+    "middle-emulation",
+    "disabled-on-external-mouse",
+    "accel-speed",
+    "accel-profile",
+    "scroll-factor",
+    "scroll-method",
+    "scroll-button",
+    "scroll-button-lock",
+    "click-method",
+    "tap-button-map",
+];
+
 /// Parse touchpad settings from touchpad node children
 ///
 /// Shared parsing logic used by both file loader and import.
@@ -211,7 +235,10 @@ pub fn parse_touchpad_from_children(tp_children: &KdlDocument, settings: &mut Se
     settings.touchpad.tap = has_flag(tp_children, &["tap"]);
     settings.touchpad.dwt = has_flag(tp_children, &["dwt"]);
     settings.touchpad.dwtp = has_flag(tp_children, &["dwtp"]);
-    settings.touchpad.drag = has_flag(tp_children, &["drag"]);
+    // Without a `drag` node libinput's default (on) applies
+    if let Some(drag) = get_bool(tp_children, &["drag"]) {
+        settings.touchpad.drag = drag;
+    }
     settings.touchpad.drag_lock = has_flag(tp_children, &["drag-lock"]);
     settings.touchpad.disabled_on_external_mouse =
         has_flag(tp_children, &["disabled-on-external-mouse"]);
@@ -230,6 +257,18 @@ pub fn parse_touchpad_from_children(tp_children: &KdlDocument, settings: &mut Se
     if let Some(v) = get_string(tp_children, &["tap-button-map"]) {
         settings.touchpad.tap_button_map = parse_tap_button_map(&v);
     }
+
+    // Anything we don't model is kept verbatim so a save doesn't strip it
+    settings.touchpad.extra_nodes = tp_children
+        .nodes()
+        .iter()
+        .filter(|node| !KNOWN_TOUCHPAD_NODES.contains(&node.name().value()))
+        .map(|node| {
+            let mut node = node.clone();
+            node.autoformat();
+            node.to_string().trim().to_string()
+        })
+        .collect();
 }
 
 /// Load touchpad settings from KDL file
@@ -5,6 +5,7 @@
 //! Supports following include directives within the niri config directory.
 
 use super::helpers::read_kdl_file;
+use crate::config::keys::is_modifier;
 use crate::config::models::{KeybindAction, Keybinding, KeybindingsSettings};
 use crate::config::parser::parse_document;
 use kdl::{KdlDocument, KdlNode};
@@ -105,6 +106,21 @@ pub fn load_keybindings(niri_config_path: &Path, settings: &mut KeybindingsSetti
     );
 }
 
+/// Load keybindings from the `binds` block of an already parsed document
+///
+/// Includes are not followed; the document doesn't come from a file.
+pub fn load_keybindings_from_document(doc: &KdlDocument, settings: &mut KeybindingsSettings) {
+    settings.bindings.clear();
+    settings.error = None;
+    settings.source_file = None;
+
+    let mut id_counter = 0u32;
+    if let Some(binds_doc) = doc.get("binds").and_then(|node| node.children()) {
+        parse_binds_block(binds_doc, &mut settings.bindings, &mut id_counter);
+    }
+    settings.loaded = !settings.bindings.is_empty();
+}
+
 /// Resolve an include path relative to the config directory
 ///
 /// Returns None if the path escapes the allowed config directories for security.
@@ -132,11 +148,11 @@ fn resolve_include_path(include_path: &str, config_dir: &Path) -> Option<PathBuf
         }
     };
 
-    // Get allowed base directories - respect $XDG_CONFIG_HOME
-    let config_base = dirs::config_dir()?;
-    let niri_config_dir = config_base.join("niri");
+    // Get allowed base directory - respects $XDG_CONFIG_HOME and --config-dir
+    let niri_config_dir = crate::config::paths::niri_config_dir()?;
+    let niri_config_dir = niri_config_dir.canonicalize().unwrap_or(niri_config_dir);
 
-    // Only allow paths under the XDG-compliant niri config dir
+    // Only allow paths under the niri config dir
     if canonical.starts_with(&niri_config_dir) {
         Some(canonical)
     } else {
@@ -170,85 +186,166 @@ fn parse_single_binding(node: &KdlNode, id_counter: &mut u32) -> Option<Keybindi
         return None;
     }
 
+    let mut warnings = Vec::new();
     let mut binding = Keybinding {
         id: *id_counter,
         key_combo,
         hotkey_overlay_title: None,
+        hotkey_overlay_hidden: false,
+        section: section_from_leading_comment(node),
         allow_when_locked: false,
         cooldown_ms: None,
-        repeat: false,
+        repeat: true,
         action: KeybindAction::NiriAction(String::new()),
+        extra_properties: Vec::new(),
+        parse_warning: None,
     };
     *id_counter += 1;
 
+    check_modifiers(&binding.key_combo, &mut warnings);
+
     // Parse properties from entries
     for entry in node.entries() {
-        if let Some(name) = entry.name() {
-            match name.value() {
-                "hotkey-overlay-title" => {
-                    if let Some(title) = entry.value().as_string() {
-                        binding.hotkey_overlay_title = Some(title.to_string());
-                    }
-                }
-                "allow-when-locked" => {
-                    if let Some(val) = entry.value().as_bool() {
-                        binding.allow_when_locked = val;
-                    }
-                }
-                "cooldown-ms" => {
-                    if let Some(val) = entry.value().as_integer() {
-                        binding.cooldown_ms = Some(val as i32);
-                    }
-                }
-                "repeat" => {
-                    if let Some(val) = entry.value().as_bool() {
-                        binding.repeat = val;
-                    }
-                }
-                _ => {}
+        let Some(name) = entry.name() else {
+            warnings.push(format!("Ignored unexpected argument {}", entry.value()));
+            continue;
+        };
+        let parsed = match name.value() {
+            "hotkey-overlay-title" if entry.value().is_null() => {
+                binding.hotkey_overlay_hidden = true;
+                Some(())
             }
+            "hotkey-overlay-title" => entry
+                .value()
+                .as_string()
+                .map(|title| binding.hotkey_overlay_title = Some(title.to_string())),
+            "allow-when-locked" => entry
+                .value()
+                .as_bool()
+                .map(|val| binding.allow_when_locked = val),
+            "cooldown-ms" => entry
+                .value()
+                .as_integer()
+                .map(|val| binding.cooldown_ms = Some(val as i32)),
+            "repeat" => entry.value().as_bool().map(|val| binding.repeat = val),
+            other => {
+                debug!(
+                    "Keeping unknown property `{}` on {}",
+                    other, binding.key_combo
+                );
+                binding
+                    .extra_properties
+                    .push((other.to_string(), entry.value().clone()));
+                continue;
+            }
+        };
+        if parsed.is_none() {
+            warnings.push(format!("Invalid value for `{}`", name.value()));
         }
     }
 
     // Parse action from children
-    if let Some(children) = node.children() {
-        binding.action = parse_action(children);
+    match node.children() {
+        Some(children) => binding.action = parse_action(children, &mut warnings),
+        None => warnings.push("No action found".to_string()),
+    }
+
+    if !warnings.is_empty() {
+        binding.parse_warning = Some(warnings.join("; "));
     }
 
     Some(binding)
 }
 
+/// Reads the app-only section from a `// section: Name` comment above the node
+fn section_from_leading_comment(node: &KdlNode) -> Option<String> {
+    let leading = &node.format()?.leading;
+    let start = leading.rfind("// section:")?;
+    let section = leading[start + "// section:".len()..]
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim();
+    (!section.is_empty()).then(|| section.to_string())
+}
+
+/// Flags modifiers in a key combo that niri wouldn't recognize
+fn check_modifiers(key_combo: &str, warnings: &mut Vec<String>) {
+    let parts: Vec<&str> = key_combo.split('+').collect();
+    let Some((_, modifiers)) = parts.split_last() else {
+        return;
+    };
+    for modifier in modifiers {
+        if !is_modifier(modifier) {
+            warnings.push(format!("Unrecognized modifier `{}`", modifier));
+        }
+    }
+}
+
 /// Parse the action from a binding's children
-fn parse_action(doc: &KdlDocument) -> KeybindAction {
+///
+/// Anything that can't be represented (extra actions, properties, argument
+/// types we don't keep) is reported through `warnings`.
+fn parse_action(doc: &KdlDocument, warnings: &mut Vec<String>) -> KeybindAction {
     // Get the first action node (only one action per binding)
     let Some(node) = doc.nodes().first() else {
+        warnings.push("No action found".to_string());
         return KeybindAction::NiriAction("(unknown)".to_string());
     };
+    if doc.nodes().len() > 1 {
+        warnings.push(format!(
+            "Only the first of {} actions was imported",
+            doc.nodes().len()
+        ));
+    }
 
     let action_name = node.name().value();
 
+    // Properties (e.g. `show-pointer=false`) can't be represented in the model
+    for entry in node.entries() {
+        if let Some(name) = entry.name() {
+            warnings.push(format!(
+                "Dropped `{}` property of `{}`",
+                name.value(),
+                action_name
+            ));
+        }
+    }
+    let positional = node.entries().iter().filter(|e| e.name().is_none());
+
     if action_name == "spawn" {
         // Spawn action: collect all string arguments
-        let args: Vec<String> = node
-            .entries()
-            .iter()
-            .filter_map(|e| e.value().as_string().map(|s| s.to_string()))
-            .collect();
+        let mut args = Vec::new();
+        for entry in positional {
+            match entry.value().as_string() {
+                Some(s) => args.push(s.to_string()),
+                None => warnings.push(format!(
+                    "Dropped non-string spawn argument {}",
+                    entry.value()
+                )),
+            }
+        }
         return KeybindAction::Spawn(args);
     }
 
     // For other niri actions, check if there are arguments
-    let args: Vec<String> = node
-        .entries()
-        .iter()
-        .filter_map(|e| {
-            // Get string or integer arguments
-            e.value()
-                .as_string()
-                .map(|s| s.to_string())
-                .or_else(|| e.value().as_integer().map(|i| i.to_string()))
-        })
-        .collect();
+    let mut args = Vec::new();
+    for entry in positional {
+        // Get string or integer arguments
+        match entry
+            .value()
+            .as_string()
+            .map(|s| s.to_string())
+            .or_else(|| entry.value().as_integer().map(|i| i.to_string()))
+        {
+            Some(arg) => args.push(arg),
+            None => warnings.push(format!(
+                "Could not read argument {} of `{}`",
+                entry.value(),
+                action_name
+            )),
+        }
+    }
 
     // Also check for children that might be action arguments
     // Niri uses several formats:
@@ -265,6 +362,11 @@ fn parse_action(doc: &KdlDocument) -> KeybindAction {
                     child_args.push(format!("{}: {}", child_name, s));
                 } else if let Some(i) = entry.value().as_integer() {
                     child_args.push(format!("{}: {}", child_name, i));
+                } else {
+                    warnings.push(format!(
+                        "Could not read `{}` argument of `{}`",
+                        child_name, action_name
+                    ));
                 }
             } else {
                 // The child node name itself is the argument (e.g., "1" in focus-workspace { "1"; })
@@ -315,6 +417,25 @@ binds {
         );
     }
 
+    #[test]
+    fn test_parse_binding_hidden_from_overlay() {
+        let content = r#"
+binds {
+    Mod+O hotkey-overlay-title=null { toggle-overview; }
+}
+"#;
+        let doc = parse_document(content).unwrap();
+        let binds_doc = doc.get("binds").unwrap().children().unwrap();
+
+        let mut bindings = Vec::new();
+        let mut id = 0;
+        parse_binds_block(binds_doc, &mut bindings, &mut id);
+
+        assert!(bindings[0].hotkey_overlay_hidden);
+        assert_eq!(bindings[0].hotkey_overlay_title, None);
+        assert_eq!(bindings[0].parse_warning, None);
+    }
+
     #[test]
     fn test_parse_media_key_binding() {
         // KDL v2 (kdl crate 6.x) uses space-separated properties
@@ -362,4 +483,82 @@ binds {
             matches!(&bindings[0].action, KeybindAction::NiriAction(action) if action == "close-window")
         );
     }
+
+    fn parse_bindings(content: &str) -> Vec<Keybinding> {
+        let doc = parse_document(content).unwrap();
+        let binds_doc = doc.get("binds").unwrap().children().unwrap();
+        let mut bindings = Vec::new();
+        let mut id = 0;
+        parse_binds_block(binds_doc, &mut bindings, &mut id);
+        bindings
+    }
+
+    #[test]
+    fn test_well_formed_bindings_have_no_parse_warning() {
+        let bindings = parse_bindings(
+            r#"
+binds {
+    Mod+Shift+ISO_Level3_Shift+Q cooldown-ms=150 { close-window; }
+    Mod+1 { focus-workspace 1; }
+}
+"#,
+        );
+        assert!(bindings.iter().all(|b| b.parse_warning.is_none()));
+    }
+
+    #[test]
+    fn test_unknown_modifier_sets_parse_warning() {
+        let bindings = parse_bindings(
+            r#"
+binds {
+    Hyper+Q { close-window; }
+}
+"#,
+        );
+        let warning = bindings[0].parse_warning.as_deref().unwrap();
+        assert!(warning.contains("Unrecognized modifier `Hyper`"));
+    }
+
+    #[test]
+    fn test_dropped_action_parts_set_parse_warning() {
+        let bindings = parse_bindings(
+            r#"
+binds {
+    Print { screenshot show-pointer=#false; }
+    Mod+X { close-window; quit; }
+    Mod+Y
+}
+"#,
+        );
+        assert!(bindings[0]
+            .parse_warning
+            .as_deref()
+            .unwrap()
+            .contains("show-pointer"));
+        assert!(bindings[1]
+            .parse_warning
+            .as_deref()
+            .unwrap()
+            .contains("Only the first of 2 actions"));
+        assert_eq!(
+            bindings[2].parse_warning.as_deref(),
+            Some("No action found")
+        );
+    }
+
+    #[test]
+    fn test_section_comment_sets_section() {
+        let bindings = parse_bindings(
+            r#"
+binds {
+    // section: Media
+    XF86AudioMute { spawn "wpctl" "set-mute" "@DEFAULT_AUDIO_SINK@" "toggle"; }
+    // Just a note
+    Mod+Q { close-window; }
+}
+"#,
+        );
+        assert_eq!(bindings[0].section.as_deref(), Some("Media"));
+        assert_eq!(bindings[1].section, None);
+    }
 }
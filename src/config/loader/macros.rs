@@ -23,7 +23,7 @@
 
 /// Load a boolean flag from KDL (presence-based or explicit boolean)
 ///
-/// Uses `has_flag` which handles:
+/// Uses `get_bool` which handles:
 /// - `flag` (no value) → true
 /// - `flag true` → true
 /// - `flag false` → false
@@ -31,9 +31,9 @@
 #[macro_export]
 macro_rules! load_flag {
     ($doc:expr, $target:expr, [$($path:expr),+]) => {{
-        use $crate::config::parser::has_flag;
-        if has_flag($doc, &[$($path),+]) {
-            $target = true;
+        use $crate::config::parser::get_bool;
+        if let Some(value) = get_bool($doc, &[$($path),+]) {
+            $target = value;
         }
     }};
 }
@@ -44,9 +44,9 @@ macro_rules! load_flag {
 #[macro_export]
 macro_rules! load_flag_inverted {
     ($doc:expr, $target:expr, [$($path:expr),+]) => {{
-        use $crate::config::parser::has_flag;
-        if has_flag($doc, &[$($path),+]) {
-            $target = false;
+        use $crate::config::parser::get_bool;
+        if let Some(value) = get_bool($doc, &[$($path),+]) {
+            $target = !value;
         }
     }};
 }
@@ -236,7 +236,7 @@ macro_rules! with_block_or_off {
     ($doc:expr, $block_name:expr, $enabled_field:expr, $block_var:ident, $body:block) => {{
         if let Some(node) = $doc.get($block_name) {
             if let Some($block_var) = node.children() {
-                if $block_var.get("off").is_some() {
+                if $crate::config::parser::has_flag($block_var, &["off"]) {
                     $enabled_field = false;
                 } else {
                     $enabled_field = true;
@@ -276,6 +276,14 @@ mod tests {
         assert!(value);
     }
 
+    #[test]
+    fn test_load_flag_explicit_false() {
+        let doc = parse_document("enabled false").unwrap();
+        let mut value = true;
+        load_flag!(&doc, value, ["enabled"]);
+        assert!(!value);
+    }
+
     #[test]
     fn test_load_flag_missing() {
         let doc = parse_document("other").unwrap();
@@ -56,7 +56,7 @@ pub use appearance::{load_appearance, parse_appearance_from_doc, parse_layout_ch
 pub use behavior::{load_behavior, parse_behavior_from_doc};
 pub use display::{
     load_animations, load_cursor, load_outputs, load_overview, parse_animations_from_children,
-    parse_cursor_from_children, parse_layout_override, parse_output_node_children,
+    parse_cursor_from_children, parse_layout_override, parse_output_node,
     parse_overview_from_children, parse_single_animation,
 };
 pub use gestures::{load_gestures, parse_gestures_from_doc};
@@ -67,7 +67,7 @@ pub use input::{
     parse_tablet_from_children, parse_touch_from_children, parse_touchpad_from_children,
     parse_trackball_from_children, parse_trackpoint_from_children,
 };
-pub use keybindings::load_keybindings;
+pub use keybindings::{load_keybindings, load_keybindings_from_document};
 pub use layout_extras::{load_layout_extras, parse_layout_extras_from_children};
 pub use misc::{load_misc, parse_misc_from_doc};
 pub use preferences::{load_preferences, parse_preferences_from_doc};
@@ -89,7 +89,11 @@ pub use health::{
 };
 
 // Re-export import module items
-pub use import::{import_from_niri_config, import_from_niri_config_with_result, ImportResult};
+pub(crate) use import::resolve_include_within;
+pub use import::{
+    import_from_niri_config, import_from_niri_config_with_result, import_from_str, ImportResult,
+    MergeStrategy, MergeSummary,
+};
 
 // Re-export FileLoadStatus for tracking individual file load results
 pub use helpers::FileLoadStatus;
@@ -98,7 +102,7 @@ use super::models::Settings;
 use super::paths::ConfigPaths;
 use helpers::read_kdl_file_with_status;
 use kdl::KdlDocument;
-use log::debug;
+use log::{debug, info};
 
 /// Result of loading settings, including feedback about what was loaded
 #[derive(Debug, Clone, Default)]
@@ -166,6 +170,28 @@ impl LoadResult {
     }
 }
 
+/// Load settings at startup, from the managed files when there are any
+///
+/// Managed files always win over `config.kdl`: once setup has moved settings
+/// out of `config.kdl`, importing it again would only find what was left
+/// behind. Without managed files (first run, or the managed dir was deleted),
+/// settings are imported from `config.kdl` so whatever it still holds is kept
+/// rather than replaced by defaults.
+pub fn load_or_import_settings(paths: &ConfigPaths) -> Settings {
+    if paths.has_managed_files() {
+        return load_settings(paths);
+    }
+    if !paths.niri_config.exists() {
+        return Settings::default();
+    }
+
+    info!("No managed config files found, importing from config.kdl");
+    let mut settings = import_from_niri_config(&paths.niri_config);
+    // App preferences never live in config.kdl, but may have survived on their own
+    load_preferences(&paths.preferences_kdl, &mut settings);
+    settings
+}
+
 /// Load settings from all KDL files, falling back to defaults for missing/invalid files
 pub fn load_settings(paths: &ConfigPaths) -> Settings {
     load_settings_with_result(paths).settings
@@ -399,23 +425,9 @@ pub fn load_settings_with_result(paths: &ConfigPaths) -> LoadResult {
             result.settings.outputs.outputs.clear();
             for node in doc.nodes() {
                 if node.name().value() == "output" {
-                    let name = node
-                        .entries()
-                        .first()
-                        .and_then(|e| e.value().as_string())
-                        .map(|s| s.to_string())
-                        .unwrap_or_default();
-                    if name.is_empty() {
-                        continue;
-                    }
-                    let mut output = crate::config::models::OutputConfig {
-                        name,
-                        ..Default::default()
-                    };
-                    if let Some(o_children) = node.children() {
-                        parse_output_node_children(o_children, &mut output);
+                    if let Some(output) = parse_output_node(node) {
+                        result.settings.outputs.outputs.push(output);
                     }
-                    result.settings.outputs.outputs.push(output);
                 }
             }
         }
@@ -591,6 +603,34 @@ layout {
         assert!(!settings.appearance.border_enabled);
     }
 
+    #[test]
+    fn test_import_accepts_flag_and_value_booleans() {
+        for (node, expected) in [
+            ("natural-scroll", true),
+            ("natural-scroll true", true),
+            ("natural-scroll false", false),
+            ("", false),
+        ] {
+            let doc = crate::config::parser::parse_document(node).unwrap();
+            let mut settings = Settings::default();
+            parse_touchpad_from_children(&doc, &mut settings);
+            assert_eq!(settings.touchpad.natural_scroll, expected, "{:?}", node);
+        }
+
+        // `off false` doesn't turn a block off
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("appearance.kdl");
+        std::fs::write(
+            &path,
+            "layout {\n    border {\n        off false\n    }\n}\n",
+        )
+        .unwrap();
+        let mut settings = Settings::default();
+        settings.appearance.border_enabled = false;
+        load_appearance(&path, &mut settings);
+        assert!(settings.appearance.border_enabled);
+    }
+
     #[test]
     fn test_load_animations_off() {
         let dir = tempdir().unwrap();
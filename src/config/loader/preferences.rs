@@ -3,8 +3,12 @@
 //! Preferences are app-specific settings (like UI theme) that are not part
 //! of niri's configuration.
 
-use crate::config::models::Settings;
+use crate::config::models::{BackupRetention, Settings};
 use crate::config::parser;
+use crate::constants::{
+    BACKUP_RETENTION_DAYS_MAX, BACKUP_RETENTION_DAYS_MIN, BACKUP_RETENTION_MAX,
+    BACKUP_RETENTION_MIN,
+};
 use kdl::KdlDocument;
 use std::path::Path;
 
@@ -28,22 +32,81 @@ pub fn parse_preferences_from_doc(doc: &KdlDocument, settings: &mut Settings) {
 
             // Read float-settings-app
             // Only update if explicitly set (otherwise keep default of true)
-            if children.get("float-settings-app").is_some() {
-                settings.preferences.float_settings_app =
-                    parser::has_flag(children, &["float-settings-app"]);
+            if let Some(value) = parser::get_bool(children, &["float-settings-app"]) {
+                settings.preferences.float_settings_app = value;
             }
 
             // Read show-search-bar
             // Only update if explicitly set (otherwise keep default of true)
-            if children.get("show-search-bar").is_some() {
-                settings.preferences.show_search_bar =
-                    parser::has_flag(children, &["show-search-bar"]);
+            if let Some(value) = parser::get_bool(children, &["show-search-bar"]) {
+                settings.preferences.show_search_bar = value;
             }
 
             // Read search-hotkey
             if let Some(hotkey) = parser::get_string(children, &["search-hotkey"]) {
                 settings.preferences.search_hotkey = hotkey.to_string();
             }
+
+            // Read emit-all-defaults (defaults to minimal output)
+            if let Some(value) = parser::get_bool(children, &["emit-all-defaults"]) {
+                settings.preferences.emit_all_defaults = value;
+            }
+
+            // Read backup-retention: a count, days=N, or "unlimited" (kept
+            // within the ranges the UI offers)
+            if let Some(entry) = children
+                .get("backup-retention")
+                .and_then(|node| node.entries().first())
+            {
+                let value = entry.value();
+                let retention = match entry.name().map(|name| name.value()) {
+                    None if value.as_string() == Some("unlimited") => {
+                        Some(BackupRetention::Unlimited)
+                    }
+                    None => value.as_integer().map(|keep| {
+                        BackupRetention::Count(
+                            keep.clamp(BACKUP_RETENTION_MIN as i128, BACKUP_RETENTION_MAX as i128)
+                                as usize,
+                        )
+                    }),
+                    Some("days") => value.as_integer().map(|days| {
+                        BackupRetention::days(days.clamp(
+                            BACKUP_RETENTION_DAYS_MIN as i128,
+                            BACKUP_RETENTION_DAYS_MAX as i128,
+                        ) as u64)
+                    }),
+                    Some(_) => None,
+                };
+                if let Some(retention) = retention {
+                    settings.preferences.backup_retention = retention;
+                }
+            }
+
+            // Read link-border-colors (defaults to independent border colors)
+            if let Some(value) = parser::get_bool(children, &["link-border-colors"]) {
+                settings.preferences.link_border_colors = value;
+            }
+
+            // Read match-niri-colors (defaults to the theme's own accent)
+            if let Some(value) = parser::get_bool(children, &["match-niri-colors"]) {
+                settings.preferences.match_niri_colors = value;
+            }
+
+            // Read auto-reload (defaults to reloading niri after each save)
+            if let Some(value) = parser::get_bool(children, &["auto-reload"]) {
+                settings.preferences.auto_reload = value;
+            }
+
+            // Read favorites (one string argument per starred setting)
+            if let Some(favorites) = children.get("favorites") {
+                settings.preferences.favorites = favorites
+                    .entries()
+                    .iter()
+                    .filter(|e| e.name().is_none())
+                    .filter_map(|e| e.value().as_string())
+                    .map(String::from)
+                    .collect();
+            }
         }
     }
 }
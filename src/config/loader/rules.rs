@@ -5,12 +5,13 @@
 //! Uses generic `load_rules` helper to eliminate boilerplate between
 //! window rules and layer rules loaders.
 
+use super::gradient::load_color_or_gradient;
 use super::helpers::{extract_slashdash_rule_blocks, parse_color, read_kdl_file, read_raw_file};
 use crate::config::models::{
     BlockOutFrom, FloatingPosition, LayerRule, LayerRuleMatch, OpenBehavior, PositionRelativeTo,
     Settings, ShadowSettings, TabIndicatorSettings, WindowRule, WindowRuleMatch,
 };
-use crate::config::parser::{get_f64, get_i64, get_string, has_flag, parse_document};
+use crate::config::parser::{get_bool, get_f64, get_i64, get_string, has_flag, parse_document};
 use crate::config::validation::validate_regex_pattern;
 use crate::types::{Color, ColorOrGradient};
 use kdl::{KdlDocument, KdlNode};
@@ -307,7 +308,12 @@ pub fn parse_layer_rule_node_children(children: &KdlDocument, rule: &mut LayerRu
 
     // shadow (complex nested block)
     if let Some(shadow_node) = children.get("shadow") {
-        if has_flag_in_node(shadow_node, "on") {
+        // niri writes `shadow { on }`; also accept the argument form
+        let on = has_flag_in_node(shadow_node, "on")
+            || shadow_node
+                .children()
+                .is_some_and(|shadow_children| has_flag(shadow_children, &["on"]));
+        if on {
             let mut shadow = ShadowSettings {
                 enabled: true,
                 ..Default::default()
@@ -536,11 +542,6 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
         }
     }
 
-    // If no matches were parsed, add a default empty match
-    if rule.matches.is_empty() {
-        rule.matches.push(WindowRuleMatch::default());
-    }
-
     // Open behavior
     if has_flag(wr_children, &["open-maximized"]) {
         rule.open_behavior = OpenBehavior::Maximized;
@@ -597,13 +598,7 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
     }
 
     // Clip to geometry
-    if let Some(ctg) = wr_children.get("clip-to-geometry") {
-        if let Some(entry) = ctg.entries().first() {
-            if let Some(b) = entry.value().as_bool() {
-                rule.clip_to_geometry = Some(b);
-            }
-        }
-    }
+    rule.clip_to_geometry = get_bool(wr_children, &["clip-to-geometry"]);
 
     // Block screencast
     if let Some(bof) = wr_children.get("block-out-from") {
@@ -625,13 +620,7 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
     }
 
     // Open focused
-    if let Some(node) = wr_children.get("open-focused") {
-        if let Some(entry) = node.entries().first() {
-            if let Some(val) = entry.value().as_bool() {
-                rule.open_focused = Some(val);
-            }
-        }
-    }
+    rule.open_focused = get_bool(wr_children, &["open-focused"]);
 
     // Default column width
     if let Some(dcw) = wr_children.get("default-column-width") {
@@ -639,6 +628,10 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
             if let Some(v) = get_f64(dcw_children, &["proportion"]) {
                 rule.default_column_width = Some(v as f32);
             }
+            if let Some(v) = get_i64(dcw_children, &["fixed"]) {
+                rule.default_column_width_fixed =
+                    safe_i64_to_i32(v, "window rule default-column-width fixed");
+            }
         }
     }
 
@@ -652,9 +645,7 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
     }
 
     // Open maximized to edges
-    if has_flag(wr_children, &["open-maximized-to-edges"]) {
-        rule.open_maximized_to_edges = Some(true);
-    }
+    rule.open_maximized_to_edges = get_bool(wr_children, &["open-maximized-to-edges"]);
 
     // Scroll factor
     if let Some(v) = get_f64(wr_children, &["scroll-factor"]) {
@@ -662,9 +653,7 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
     }
 
     // Draw border with background
-    if has_flag(wr_children, &["draw-border-with-background"]) {
-        rule.draw_border_with_background = Some(true);
-    }
+    rule.draw_border_with_background = get_bool(wr_children, &["draw-border-with-background"]);
 
     // Size constraints
     if let Some(v) = get_i64(wr_children, &["min-width"]) {
@@ -691,20 +680,14 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
             if let Some(v) = get_i64(fr_children, &["width"]) {
                 rule.focus_ring_width = safe_i64_to_i32(v, "focus-ring width");
             }
-            if let Some(hex) = get_string(fr_children, &["active-color"]) {
-                if let Some(c) = parse_color(&hex) {
-                    rule.focus_ring_active = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(fr_children, "active") {
+                rule.focus_ring_active = Some(cog);
             }
-            if let Some(hex) = get_string(fr_children, &["inactive-color"]) {
-                if let Some(c) = parse_color(&hex) {
-                    rule.focus_ring_inactive = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(fr_children, "inactive") {
+                rule.focus_ring_inactive = Some(cog);
             }
-            if let Some(hex) = get_string(fr_children, &["urgent-color"]) {
-                if let Some(c) = parse_color(&hex) {
-                    rule.focus_ring_urgent = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(fr_children, "urgent") {
+                rule.focus_ring_urgent = Some(cog);
             }
         }
     }
@@ -720,20 +703,14 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
             if let Some(v) = get_i64(border_children, &["width"]) {
                 rule.border_width = safe_i64_to_i32(v, "border width");
             }
-            if let Some(hex) = get_string(border_children, &["active-color"]) {
-                if let Some(c) = parse_color(&hex) {
-                    rule.border_active = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(border_children, "active") {
+                rule.border_active = Some(cog);
             }
-            if let Some(hex) = get_string(border_children, &["inactive-color"]) {
-                if let Some(c) = parse_color(&hex) {
-                    rule.border_inactive = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(border_children, "inactive") {
+                rule.border_inactive = Some(cog);
             }
-            if let Some(hex) = get_string(border_children, &["urgent-color"]) {
-                if let Some(c) = parse_color(&hex) {
-                    rule.border_urgent = Some(ColorOrGradient::Color(c));
-                }
+            if let Some(cog) = load_color_or_gradient(border_children, "urgent") {
+                rule.border_urgent = Some(cog);
             }
         }
     }
@@ -745,12 +722,7 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
         } else if has_flag_in_node(vrr, "off") {
             rule.variable_refresh_rate = Some(false);
         } else {
-            // Check for boolean value
-            if let Some(entry) = vrr.entries().first() {
-                if let Some(b) = entry.value().as_bool() {
-                    rule.variable_refresh_rate = Some(b);
-                }
-            }
+            rule.variable_refresh_rate = get_bool(wr_children, &["variable-refresh-rate"]);
         }
     }
 
@@ -765,22 +737,16 @@ pub fn parse_window_rule_node_children(wr_children: &KdlDocument, rule: &mut Win
 
     // Tiled state (niri expects a boolean argument: `tiled-state true`)
     if let Some(ts) = wr_children.get("tiled-state") {
-        if let Some(entry) = ts.entries().first() {
-            if let Some(b) = entry.value().as_bool() {
-                rule.tiled_state = Some(b);
-            } else if entry.value().as_string() == Some("tiled") {
-                // Backwards-compat with older Nirify output
-                rule.tiled_state = Some(true);
-            } else if entry.value().as_string() == Some("floating") {
-                rule.tiled_state = Some(false);
-            }
-        }
+        rule.tiled_state = match ts.entries().first().map(|entry| entry.value()) {
+            // Backwards-compat with older Nirify output
+            Some(value) if value.as_string() == Some("tiled") => Some(true),
+            Some(value) if value.as_string() == Some("floating") => Some(false),
+            _ => get_bool(wr_children, &["tiled-state"]),
+        };
     }
 
     // Baba is float (animated floating effect)
-    if has_flag(wr_children, &["baba-is-float"]) {
-        rule.baba_is_float = Some(true);
-    }
+    rule.baba_is_float = get_bool(wr_children, &["baba-is-float"]);
 
     // Per-window shadow settings
     if let Some(shadow_node) = wr_children.get("shadow") {
@@ -942,6 +908,55 @@ mod tests {
         assert!(!rule.enabled);
     }
 
+    #[test]
+    fn layer_rule_shadow_and_corner_radius_round_trip() {
+        use crate::config::models::{LayerRulesSettings, ShadowSettings};
+        use crate::config::storage::generate_layer_rules_kdl;
+        use crate::types::Color;
+
+        let shadow = ShadowSettings {
+            softness: 12,
+            spread: 3,
+            offset_x: -4,
+            offset_y: 8,
+            draw_behind_window: true,
+            color: Color::from_hex("#11223380").unwrap(),
+            ..Default::default()
+        };
+        let settings = LayerRulesSettings {
+            rules: vec![LayerRule {
+                shadow: Some(shadow.clone()),
+                geometry_corner_radius: Some(14),
+                ..Default::default()
+            }],
+            next_id: 1,
+        };
+
+        let content = generate_layer_rules_kdl(&settings);
+        let document = parse_document(&content).unwrap();
+        let children = document.get("layer-rule").unwrap().children().unwrap();
+        let mut rule = LayerRule::default();
+        parse_layer_rule_node_children(children, &mut rule);
+
+        assert_eq!(rule.shadow, Some(shadow));
+        assert_eq!(rule.geometry_corner_radius, Some(14));
+    }
+
+    #[test]
+    fn layer_rule_without_shadow_writes_none() {
+        use crate::config::models::LayerRulesSettings;
+        use crate::config::storage::generate_layer_rules_kdl;
+
+        let settings = LayerRulesSettings {
+            rules: vec![LayerRule::default()],
+            next_id: 1,
+        };
+
+        let content = generate_layer_rules_kdl(&settings);
+        assert!(!content.contains("shadow"));
+        assert!(!content.contains("geometry-corner-radius"));
+    }
+
     #[test]
     fn parse_window_rule_node_children_reads_off_flag() {
         let document = parse_document("window-rule {\n    off\n}\n").unwrap();
@@ -1,26 +1,40 @@
+pub mod backup;
 pub mod category_section;
 pub mod consolidation;
 pub mod dirty;
+pub mod effective;
 pub mod error;
+pub mod export;
+pub mod instance_lock;
+pub mod keys;
+pub mod lint;
 pub mod loader;
 pub mod models;
 pub mod parser;
 pub mod paths;
 pub mod registry;
 pub mod replace;
+pub mod restart;
+pub mod restore;
+pub mod restore_point;
 pub mod storage;
 pub mod validation;
 
 pub use crate::types::CenterFocusedColumn;
+pub use backup::DiffLine;
 pub use category_section::CategorySection;
 pub use consolidation::{analyze_rules, ConsolidationAnalysis, ConsolidationSuggestion};
 pub use dirty::{DirtyTracker, SettingsCategory};
+pub use effective::render_effective;
 pub use error::ConfigError;
+pub use export::export_standalone;
+pub use instance_lock::{acquire_instance_lock, take_over_instance_lock, InstanceLock, LockResult};
+pub use lint::{lint, Lint, LintSeverity};
 pub use loader::{
     check_config_health, ensure_required_files_exist, import_from_niri_config,
-    import_from_niri_config_with_result, load_settings, load_settings_with_result,
-    repair_corrupted_configs, ConfigFileStatus, ConfigHealthReport, FileLoadStatus, ImportResult,
-    LoadResult,
+    import_from_niri_config_with_result, import_from_str, load_or_import_settings, load_settings,
+    load_settings_with_result, repair_corrupted_configs, ConfigFileStatus, ConfigHealthReport,
+    FileLoadStatus, ImportResult, LoadResult, MergeStrategy, MergeSummary,
 };
 pub use models::{
     ColumnWidthType, LayoutOverride, OutputConfig, OutputHotCorners, OutputSettings, Settings,
@@ -28,6 +42,15 @@ pub use models::{
 };
 pub use paths::ConfigPaths;
 pub use registry::ConfigFile;
-pub use replace::{smart_replace_config, SmartReplaceResult};
+pub use replace::{
+    existing_includes, smart_replace_config, smart_replace_config_with, IncludeHandling,
+    SmartReplaceResult,
+};
+pub use restart::requires_restart;
+pub use restore::{restore_set_with_rollback, restore_with_rollback, RestoreOutcome};
+pub use restore_point::{
+    capture_restore_point, clear_restore_points, discard_restore_point, restore_point_is_current,
+    revert_last_restore_point,
+};
 pub use storage::{atomic_write, save_dirty, save_settings};
 pub use validation::{validate_string, validate_string_opt};
@@ -13,8 +13,6 @@ pub enum AnimationType {
     #[default]
     #[slint_index(default)]
     Default,
-    /// Animation disabled
-    Off,
     /// Spring physics animation
     Spring,
     /// Easing curve animation
@@ -240,9 +238,12 @@ impl Default for EasingParams {
 }
 
 /// Configuration for a single animation
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SingleAnimationConfig {
-    /// Animation type (Default, Off, Spring, Easing, CustomShader)
+    /// Whether the animation plays; when false it's written as `off`, and
+    /// the curve below is kept for turning it back on
+    pub enabled: bool,
+    /// Animation type (Default, Spring, Easing, CustomShader)
     pub animation_type: AnimationType,
     /// Spring parameters (used when animation_type == Spring)
     pub spring: SpringParams,
@@ -253,6 +254,65 @@ pub struct SingleAnimationConfig {
     pub custom_shader: Option<String>,
 }
 
+impl Default for SingleAnimationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            animation_type: AnimationType::Default,
+            spring: SpringParams::default(),
+            easing: EasingParams::default(),
+            custom_shader: None,
+        }
+    }
+}
+
+impl SingleAnimationConfig {
+    /// Config for the named animation carrying niri's built-in parameters
+    ///
+    /// The type stays `Default`; the spring and easing parameters are what niri
+    /// uses for `name` (a KDL node name such as `window-open`), so a partially
+    /// specified block can be merged onto this without changing behavior.
+    /// Unknown names get the generic defaults.
+    pub fn with_niri_defaults(name: &str) -> Self {
+        let spring = |damping_ratio, stiffness, epsilon| SpringParams {
+            damping_ratio,
+            stiffness,
+            epsilon,
+        };
+        let easing = |duration_ms, curve| EasingParams { duration_ms, curve };
+
+        let (spring, easing) = match name {
+            "workspace-switch" => (spring(1.0, 1000, 0.0001), EasingParams::default()),
+            "window-open" => (
+                SpringParams::default(),
+                easing(150, EasingCurve::EaseOutExpo),
+            ),
+            "window-close" => (
+                SpringParams::default(),
+                easing(150, EasingCurve::EaseOutQuad),
+            ),
+            "horizontal-view-movement" | "window-movement" | "window-resize" => {
+                (spring(1.0, 800, 0.0001), EasingParams::default())
+            }
+            "config-notification-open-close" => (spring(0.6, 1000, 0.001), EasingParams::default()),
+            "exit-confirmation-open-close" => (spring(0.6, 500, 0.01), EasingParams::default()),
+            "screenshot-ui-open" => (
+                SpringParams::default(),
+                easing(200, EasingCurve::EaseOutQuad),
+            ),
+            "overview-open-close" => (spring(1.0, 900, 0.0001), EasingParams::default()),
+            "recent-windows-close" => (spring(1.0, 800, 0.001), EasingParams::default()),
+            _ => (SpringParams::default(), EasingParams::default()),
+        };
+
+        Self {
+            spring,
+            easing,
+            ..Default::default()
+        }
+    }
+}
+
 /// Per-animation configuration for all niri animations
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct PerAnimationSettings {
@@ -30,7 +30,8 @@ pub struct AppearanceSettings {
     // Corner radius
     pub corner_radius: f32,
 
-    // Background
+    /// Workspace background, niri's `layout { background-color }`; `None`
+    /// keeps niri's default. Not to be confused with the overview backdrop.
     pub background_color: Option<Color>,
 }
 
@@ -67,3 +68,12 @@ impl Default for AppearanceSettings {
         }
     }
 }
+
+impl AppearanceSettings {
+    /// Copies the focus ring colors to the matching border colors
+    pub fn copy_focus_ring_colors_to_border(&mut self) {
+        self.border_active = self.focus_ring_active.clone();
+        self.border_inactive = self.focus_ring_inactive.clone();
+        self.border_urgent = self.focus_ring_urgent.clone();
+    }
+}
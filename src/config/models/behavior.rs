@@ -61,7 +61,7 @@ impl std::fmt::Display for ColumnWidthType {
 
 impl ColumnWidthType {
     pub fn all() -> &'static [Self] {
-        &[Self::Proportion, Self::Fixed]
+        Self::ALL
     }
 }
 
@@ -40,6 +40,83 @@ impl Default for KeyboardSettings {
     }
 }
 
+/// Commonly used xkb options offered as a checklist: (option, label)
+pub const COMMON_XKB_OPTIONS: &[(&str, &str)] = &[
+    ("caps:escape", "Caps Lock is Escape"),
+    ("caps:swapescape", "Swap Caps Lock and Escape"),
+    ("ctrl:nocaps", "Caps Lock is Ctrl"),
+    ("compose:ralt", "Compose on Right Alt"),
+    ("compose:rwin", "Compose on Right Super"),
+    ("compose:menu", "Compose on Menu"),
+    ("compose:caps", "Compose on Caps Lock"),
+    ("grp:alt_shift_toggle", "Switch layout with Alt+Shift"),
+    ("grp:win_space_toggle", "Switch layout with Super+Space"),
+    ("grp:caps_toggle", "Switch layout with Caps Lock"),
+    ("altwin:swap_alt_win", "Swap Alt and Super"),
+];
+
+fn is_common_xkb_option(option: &str) -> bool {
+    COMMON_XKB_OPTIONS.iter().any(|(o, _)| *o == option)
+}
+
+impl KeyboardSettings {
+    /// Checklist options currently present in `xkb_options`, in string order
+    pub fn checked_xkb_options(&self) -> Vec<&str> {
+        self.xkb_options
+            .split(',')
+            .map(str::trim)
+            .filter(|o| is_common_xkb_option(o))
+            .collect()
+    }
+
+    /// The part of `xkb_options` not covered by the checklist, as typed
+    pub fn extra_xkb_options(&self) -> String {
+        self.xkb_options
+            .split(',')
+            .filter(|o| !is_common_xkb_option(o.trim()))
+            .collect::<Vec<_>>()
+            .join(",")
+            .trim_start()
+            .to_string()
+    }
+
+    /// Checks or unchecks a checklist option, keeping everything else
+    pub fn set_xkb_option(&mut self, option: &str, enabled: bool) {
+        let mut checked: Vec<String> = self
+            .checked_xkb_options()
+            .into_iter()
+            .filter(|o| *o != option)
+            .map(String::from)
+            .collect();
+        if enabled {
+            checked.push(option.to_string());
+        }
+        self.xkb_options = join_xkb_options(checked, &self.extra_xkb_options());
+    }
+
+    /// Replaces the free-text options, keeping the checked ones
+    pub fn set_extra_xkb_options(&mut self, extra: &str) {
+        let checked = self
+            .checked_xkb_options()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        self.xkb_options = join_xkb_options(checked, extra);
+    }
+}
+
+/// Joins checked options and free text into a comma-separated option string
+///
+/// The free text is kept verbatim (bar leading whitespace) so a trailing comma
+/// survives while the user is still typing the next option.
+fn join_xkb_options(mut parts: Vec<String>, extra: &str) -> String {
+    let extra = extra.trim_start();
+    if !extra.is_empty() {
+        parts.push(extra.to_string());
+    }
+    parts.join(",")
+}
+
 /// Mouse settings
 #[derive(Debug, Clone, PartialEq)]
 pub struct MouseSettings {
@@ -104,6 +181,9 @@ pub struct TouchpadSettings {
     pub scroll_button: Option<i32>,
     /// Lock scroll button state (don't need to hold)
     pub scroll_button_lock: bool,
+    /// Touchpad options the app doesn't model (e.g. ones added by a newer
+    /// niri), kept verbatim as KDL so a save doesn't strip them
+    pub extra_nodes: Vec<String>,
 }
 
 impl Default for TouchpadSettings {
@@ -128,6 +208,7 @@ impl Default for TouchpadSettings {
             disabled_on_external_mouse: false,
             scroll_button: None,
             scroll_button_lock: false,
+            extra_nodes: Vec::new(),
         }
     }
 }
@@ -386,3 +467,56 @@ impl InputDeviceId {
         matches!(self, Self::Tablet | Self::Touch)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyboard(options: &str) -> KeyboardSettings {
+        KeyboardSettings {
+            xkb_options: options.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_xkb_options() {
+        let kb = keyboard("compose:ralt, lv3:ralt_switch,caps:escape");
+        assert_eq!(
+            kb.checked_xkb_options(),
+            vec!["compose:ralt", "caps:escape"]
+        );
+        assert_eq!(kb.extra_xkb_options(), "lv3:ralt_switch");
+    }
+
+    #[test]
+    fn test_set_xkb_option_preserves_extra() {
+        let mut kb = keyboard("caps:escape,lv3:ralt_switch");
+        kb.set_xkb_option("compose:ralt", true);
+        assert_eq!(kb.xkb_options, "caps:escape,compose:ralt,lv3:ralt_switch");
+
+        kb.set_xkb_option("caps:escape", false);
+        assert_eq!(kb.xkb_options, "compose:ralt,lv3:ralt_switch");
+    }
+
+    #[test]
+    fn test_set_extra_xkb_options_preserves_checked() {
+        let mut kb = keyboard("caps:escape,old:option");
+        kb.set_extra_xkb_options("new:option,");
+        assert_eq!(kb.xkb_options, "caps:escape,new:option,");
+        assert_eq!(kb.extra_xkb_options(), "new:option,");
+
+        kb.set_extra_xkb_options("");
+        assert_eq!(kb.xkb_options, "caps:escape");
+    }
+
+    #[test]
+    fn test_xkb_options_empty() {
+        let mut kb = keyboard("");
+        assert!(kb.checked_xkb_options().is_empty());
+        assert_eq!(kb.extra_xkb_options(), "");
+
+        kb.set_xkb_option("caps:escape", true);
+        assert_eq!(kb.xkb_options, "caps:escape");
+    }
+}
@@ -0,0 +1,301 @@
+//! Ready-made keybinding sets for the template gallery
+//!
+//! Each template builds the bindings of one common set, modelled on niri's
+//! default config. [`KeybindingsSettings::insert_template`] adds the ones whose
+//! key combo isn't bound yet.
+
+use std::ops::Range;
+
+use super::keybindings::{is_repeatable_action, KeybindAction, Keybinding, KeybindingsSettings};
+use crate::config::keys::{normalize_combo, same_combo};
+
+/// A named set of bindings offered in the template gallery
+#[derive(Debug, Clone, Copy)]
+pub struct BindingTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Builds the bindings; ids are assigned when they're inserted
+    pub bindings: fn() -> Vec<Keybinding>,
+}
+
+/// Templates in the order the gallery shows them
+pub const BINDING_TEMPLATES: &[BindingTemplate] = &[
+    BindingTemplate {
+        name: "Basic navigation",
+        description: "Move focus between columns and windows with arrows or HJKL",
+        bindings: basic_navigation,
+    },
+    BindingTemplate {
+        name: "Window management",
+        description: "Close, maximize, fullscreen, float, resize and move columns",
+        bindings: window_management,
+    },
+    BindingTemplate {
+        name: "Workspaces 1-9",
+        description: "Mod+1 to Mod+9 switch to workspaces 1 to 9",
+        bindings: workspaces_1_to_9,
+    },
+    BindingTemplate {
+        name: "Media keys",
+        description: "Volume, mute, brightness and playback keys (wpctl, brightnessctl, playerctl)",
+        bindings: media_keys,
+    },
+];
+
+fn action(key_combo: &str, name: &str) -> Keybinding {
+    Keybinding {
+        key_combo: key_combo.to_string(),
+        repeat: is_repeatable_action(name),
+        action: KeybindAction::NiriAction(name.to_string()),
+        ..Default::default()
+    }
+}
+
+fn action_with_arg(key_combo: &str, name: &str, arg: &str) -> Keybinding {
+    Keybinding {
+        key_combo: key_combo.to_string(),
+        action: KeybindAction::NiriActionWithArgs(name.to_string(), vec![arg.to_string()]),
+        ..Default::default()
+    }
+}
+
+/// A spawn binding that also works on the lock screen, like niri's media keys
+fn media_spawn(key_combo: &str, command: &[&str]) -> Keybinding {
+    Keybinding {
+        key_combo: key_combo.to_string(),
+        allow_when_locked: true,
+        action: KeybindAction::Spawn(command.iter().map(|s| s.to_string()).collect()),
+        ..Default::default()
+    }
+}
+
+fn basic_navigation() -> Vec<Keybinding> {
+    [
+        ("Left", "focus-column-left"),
+        ("Right", "focus-column-right"),
+        ("Up", "focus-window-up"),
+        ("Down", "focus-window-down"),
+        ("H", "focus-column-left"),
+        ("L", "focus-column-right"),
+        ("K", "focus-window-up"),
+        ("J", "focus-window-down"),
+        ("Home", "focus-column-first"),
+        ("End", "focus-column-last"),
+    ]
+    .into_iter()
+    .map(|(key, name)| action(&format!("Mod+{}", key), name))
+    .collect()
+}
+
+fn window_management() -> Vec<Keybinding> {
+    vec![
+        action("Mod+Q", "close-window"),
+        action("Mod+F", "maximize-column"),
+        action("Mod+Shift+F", "fullscreen-window"),
+        action("Mod+C", "center-column"),
+        action("Mod+V", "toggle-window-floating"),
+        action("Mod+R", "switch-preset-column-width"),
+        action_with_arg("Mod+Minus", "set-column-width", "-10%"),
+        action_with_arg("Mod+Equal", "set-column-width", "+10%"),
+        action("Mod+Ctrl+Left", "move-column-left"),
+        action("Mod+Ctrl+Right", "move-column-right"),
+    ]
+}
+
+fn workspaces_1_to_9() -> Vec<Keybinding> {
+    (1..=9)
+        .map(|n| action_with_arg(&format!("Mod+{}", n), "focus-workspace", &n.to_string()))
+        .collect()
+}
+
+fn media_keys() -> Vec<Keybinding> {
+    vec![
+        media_spawn(
+            "XF86AudioRaiseVolume",
+            &["wpctl", "set-volume", "@DEFAULT_AUDIO_SINK@", "0.1+"],
+        ),
+        media_spawn(
+            "XF86AudioLowerVolume",
+            &["wpctl", "set-volume", "@DEFAULT_AUDIO_SINK@", "0.1-"],
+        ),
+        media_spawn(
+            "XF86AudioMute",
+            &["wpctl", "set-mute", "@DEFAULT_AUDIO_SINK@", "toggle"],
+        ),
+        media_spawn(
+            "XF86AudioMicMute",
+            &["wpctl", "set-mute", "@DEFAULT_AUDIO_SOURCE@", "toggle"],
+        ),
+        media_spawn(
+            "XF86MonBrightnessUp",
+            &["brightnessctl", "--class=backlight", "set", "+10%"],
+        ),
+        media_spawn(
+            "XF86MonBrightnessDown",
+            &["brightnessctl", "--class=backlight", "set", "10%-"],
+        ),
+        media_spawn("XF86AudioPlay", &["playerctl", "play-pause"]),
+        media_spawn("XF86AudioNext", &["playerctl", "next"]),
+        media_spawn("XF86AudioPrev", &["playerctl", "previous"]),
+    ]
+}
+
+impl KeybindingsSettings {
+    /// Id for a new binding: one past the largest in use
+    ///
+    /// Ids key the search index, so they must stay unique after removals.
+    pub fn next_id(&self) -> u32 {
+        self.bindings.iter().map(|b| b.id + 1).max().unwrap_or(0)
+    }
+
+    /// Appends the template's bindings whose key combo isn't bound yet
+    ///
+    /// Combos are compared normalized and ignoring case, as niri matches key
+    /// names, so an existing `mod+q` blocks the template's `Mod+Q`. Added
+    /// bindings get fresh ids and are grouped under the template's name.
+    /// Returns the indices of the added bindings.
+    pub fn insert_template(&mut self, template: &BindingTemplate) -> Range<usize> {
+        let start = self.bindings.len();
+        let mut next_id = self.next_id();
+
+        for mut binding in (template.bindings)() {
+            let combo = normalize_combo(&binding.key_combo);
+            if self
+                .bindings
+                .iter()
+                .any(|b| same_combo(&b.key_combo, &combo))
+            {
+                continue;
+            }
+            binding.id = next_id;
+            binding.key_combo = combo;
+            binding.section = Some(template.name.to_string());
+            next_id += 1;
+            self.bindings.push(binding);
+        }
+
+        start..self.bindings.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::keys::is_plausible_combo;
+    use std::collections::HashSet;
+
+    fn template(name: &str) -> &'static BindingTemplate {
+        BINDING_TEMPLATES.iter().find(|t| t.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_workspaces_template_adds_nine_bindings() {
+        let mut settings = KeybindingsSettings {
+            bindings: vec![Keybinding {
+                id: 4,
+                key_combo: "Mod+Q".to_string(),
+                action: KeybindAction::NiriAction("close-window".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let added = settings.insert_template(template("Workspaces 1-9"));
+        assert_eq!(added, 1..10);
+
+        let ids: HashSet<_> = settings.bindings.iter().map(|b| b.id).collect();
+        assert_eq!(ids.len(), 10);
+        let combos: HashSet<_> = settings.bindings.iter().map(|b| &b.key_combo).collect();
+        assert_eq!(combos.len(), 10);
+        assert_eq!(
+            settings.bindings[9].action,
+            KeybindAction::NiriActionWithArgs("focus-workspace".to_string(), vec!["9".to_string()])
+        );
+
+        // Applying it again finds every combo bound already
+        assert!(settings
+            .insert_template(template("Workspaces 1-9"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_template_skips_combos_already_bound() {
+        let mut settings = KeybindingsSettings {
+            bindings: vec![Keybinding {
+                key_combo: "mod+q".to_string(),
+                action: KeybindAction::Spawn(vec!["foot".to_string()]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let added = settings.insert_template(template("Window management"));
+        assert_eq!(added.len(), window_management().len() - 1);
+        assert!(!settings.bindings[added]
+            .iter()
+            .any(|b| b.key_combo == "Mod+Q"));
+        // The existing binding is untouched
+        assert_eq!(
+            settings.bindings[0].action,
+            KeybindAction::Spawn(vec!["foot".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_templates_have_no_repeat_warnings() {
+        // One-shot actions like close-window come with repeat already off
+        for template in BINDING_TEMPLATES {
+            for binding in (template.bindings)() {
+                assert_eq!(binding.repeat_warning(), None, "{}", binding.key_combo);
+            }
+        }
+    }
+
+    #[test]
+    fn test_media_keys_work_when_locked() {
+        let bindings = media_keys();
+        assert!(!bindings.is_empty());
+        for binding in &bindings {
+            // Bare XF86 key names, so no modifier needs holding
+            assert!(
+                binding.key_combo.starts_with("XF86"),
+                "{}",
+                binding.key_combo
+            );
+            assert!(is_plausible_combo(&binding.key_combo));
+            assert!(binding.allow_when_locked, "{}", binding.key_combo);
+            assert!(binding.repeat);
+            assert!(
+                matches!(&binding.action, KeybindAction::Spawn(command) if !command.is_empty())
+            );
+        }
+        for key in [
+            "XF86AudioRaiseVolume",
+            "XF86AudioMute",
+            "XF86MonBrightnessUp",
+        ] {
+            assert!(bindings.iter().any(|b| b.key_combo == key), "{}", key);
+        }
+
+        let mut settings = KeybindingsSettings::default();
+        let added = settings.insert_template(template("Media keys"));
+        assert_eq!(added.len(), bindings.len());
+        let full = crate::config::Settings {
+            keybindings: settings,
+            ..Default::default()
+        };
+        assert!(crate::config::validation::validate_settings(&full).is_valid());
+    }
+
+    #[test]
+    fn test_templates_have_no_internal_duplicates() {
+        for template in BINDING_TEMPLATES {
+            let bindings = (template.bindings)();
+            let combos: HashSet<_> = bindings
+                .iter()
+                .map(|b| normalize_combo(&b.key_combo).to_lowercase())
+                .collect();
+            assert_eq!(combos.len(), bindings.len(), "{}", template.name);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Keybindings settings - keyboard shortcuts and their actions
 
+use std::collections::HashMap;
+
 /// Action type for a keybinding
 #[derive(Debug, Clone, PartialEq)]
 pub enum KeybindAction {
@@ -17,7 +19,395 @@ impl Default for KeybindAction {
     }
 }
 
+/// Kind of action a keybinding runs, as picked in the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    /// Run a command
+    Spawn,
+    /// Built-in niri action without arguments
+    Niri,
+    /// Built-in niri action with arguments
+    NiriWithArgs,
+}
+
+impl std::fmt::Display for ActionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Spawn => write!(f, "Run command"),
+            Self::Niri => write!(f, "niri action"),
+            Self::NiriWithArgs => write!(f, "niri action with arguments"),
+        }
+    }
+}
+
+impl ActionKind {
+    pub fn all() -> &'static [Self] {
+        &[Self::Spawn, Self::Niri, Self::NiriWithArgs]
+    }
+}
+
+/// Converts an action to another kind, keeping whatever still applies
+///
+/// Arguments survive spawn ↔ with-arguments, the action name survives
+/// niri ↔ with-arguments. Only data the new kind can't hold is dropped, so a
+/// command becomes an empty (unset) action name rather than a placeholder.
+pub fn convert_action(from: &KeybindAction, to: ActionKind) -> KeybindAction {
+    match (from, to) {
+        (KeybindAction::Spawn(_), ActionKind::Spawn)
+        | (KeybindAction::NiriAction(_), ActionKind::Niri)
+        | (KeybindAction::NiriActionWithArgs(..), ActionKind::NiriWithArgs) => from.clone(),
+
+        (KeybindAction::NiriActionWithArgs(_, args), ActionKind::Spawn) => {
+            KeybindAction::Spawn(args.clone())
+        }
+        (KeybindAction::NiriAction(_), ActionKind::Spawn) => KeybindAction::Spawn(Vec::new()),
+
+        (KeybindAction::NiriActionWithArgs(name, _), ActionKind::Niri) => {
+            KeybindAction::NiriAction(name.clone())
+        }
+        (KeybindAction::Spawn(_), ActionKind::Niri) => KeybindAction::NiriAction(String::new()),
+
+        (KeybindAction::Spawn(args), ActionKind::NiriWithArgs) => {
+            KeybindAction::NiriActionWithArgs(String::new(), args.clone())
+        }
+        (KeybindAction::NiriAction(name), ActionKind::NiriWithArgs) => {
+            KeybindAction::NiriActionWithArgs(name.clone(), Vec::new())
+        }
+    }
+}
+
+/// Friendly descriptions for niri's built-in actions, keyed by action name
+const ACTION_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("quit", "Quit niri"),
+    ("close-window", "Close window"),
+    ("toggle-overview", "Toggle overview"),
+    ("open-overview", "Open overview"),
+    ("close-overview", "Close overview"),
+    ("show-hotkey-overlay", "Show hotkey overlay"),
+    ("screenshot", "Take a screenshot"),
+    ("screenshot-screen", "Screenshot the screen"),
+    ("screenshot-window", "Screenshot the window"),
+    ("power-off-monitors", "Power off monitors"),
+    ("power-on-monitors", "Power on monitors"),
+    ("suspend", "Suspend"),
+    ("spawn", "Run a command"),
+    ("spawn-sh", "Run a shell command"),
+    ("do-screen-transition", "Screen transition"),
+    (
+        "toggle-keyboard-shortcuts-inhibit",
+        "Toggle shortcuts inhibit",
+    ),
+    ("toggle-debug-tint", "Toggle debug tint"),
+    ("focus-column-left", "Focus column to the left"),
+    ("focus-column-right", "Focus column to the right"),
+    ("focus-column-first", "Focus first column"),
+    ("focus-column-last", "Focus last column"),
+    (
+        "focus-column-right-or-first",
+        "Focus column to the right or wrap to first",
+    ),
+    (
+        "focus-column-left-or-last",
+        "Focus column to the left or wrap to last",
+    ),
+    ("focus-column", "Focus column by index"),
+    ("focus-window-up", "Focus window above"),
+    ("focus-window-down", "Focus window below"),
+    ("focus-window-previous", "Focus previous window"),
+    (
+        "focus-window-or-workspace-up",
+        "Focus window above or workspace up",
+    ),
+    (
+        "focus-window-or-workspace-down",
+        "Focus window below or workspace down",
+    ),
+    (
+        "focus-window-or-monitor-up",
+        "Focus window above or monitor up",
+    ),
+    (
+        "focus-window-or-monitor-down",
+        "Focus window below or monitor down",
+    ),
+    (
+        "focus-column-or-monitor-left",
+        "Focus column or monitor to the left",
+    ),
+    (
+        "focus-column-or-monitor-right",
+        "Focus column or monitor to the right",
+    ),
+    ("focus-window-in-column", "Focus window in column by index"),
+    ("focus-window-top", "Focus top window in column"),
+    ("focus-window-bottom", "Focus bottom window in column"),
+    ("focus-monitor-left", "Focus monitor to the left"),
+    ("focus-monitor-right", "Focus monitor to the right"),
+    ("focus-monitor-up", "Focus monitor above"),
+    ("focus-monitor-down", "Focus monitor below"),
+    ("focus-monitor-next", "Focus next monitor"),
+    ("focus-monitor-previous", "Focus previous monitor"),
+    ("move-column-left", "Move column to the left"),
+    ("move-column-right", "Move column to the right"),
+    ("move-column-to-first", "Move column to the start"),
+    ("move-column-to-last", "Move column to the end"),
+    (
+        "move-column-left-or-to-monitor-left",
+        "Move column left or to monitor on the left",
+    ),
+    (
+        "move-column-right-or-to-monitor-right",
+        "Move column right or to monitor on the right",
+    ),
+    ("move-column-to-index", "Move column to index"),
+    ("move-window-up", "Move window up"),
+    ("move-window-down", "Move window down"),
+    (
+        "move-window-up-or-to-workspace-up",
+        "Move window up or to workspace above",
+    ),
+    (
+        "move-window-down-or-to-workspace-down",
+        "Move window down or to workspace below",
+    ),
+    (
+        "move-window-to-workspace-up",
+        "Move window to workspace above",
+    ),
+    (
+        "move-window-to-workspace-down",
+        "Move window to workspace below",
+    ),
+    ("move-window-to-workspace", "Move window to workspace"),
+    (
+        "move-window-to-monitor-left",
+        "Move window to monitor on the left",
+    ),
+    (
+        "move-window-to-monitor-right",
+        "Move window to monitor on the right",
+    ),
+    ("move-window-to-monitor-up", "Move window to monitor above"),
+    (
+        "move-window-to-monitor-down",
+        "Move window to monitor below",
+    ),
+    (
+        "move-column-to-workspace-up",
+        "Move column to workspace above",
+    ),
+    (
+        "move-column-to-workspace-down",
+        "Move column to workspace below",
+    ),
+    ("move-column-to-workspace", "Move column to workspace"),
+    (
+        "move-column-to-monitor-left",
+        "Move column to monitor on the left",
+    ),
+    (
+        "move-column-to-monitor-right",
+        "Move column to monitor on the right",
+    ),
+    ("move-column-to-monitor-up", "Move column to monitor above"),
+    (
+        "move-column-to-monitor-down",
+        "Move column to monitor below",
+    ),
+    ("focus-workspace-up", "Focus workspace above"),
+    ("focus-workspace-down", "Focus workspace below"),
+    ("focus-workspace-previous", "Focus previous workspace"),
+    ("focus-workspace", "Focus workspace"),
+    ("move-workspace-up", "Move workspace up"),
+    ("move-workspace-down", "Move workspace down"),
+    (
+        "move-workspace-to-monitor-left",
+        "Move workspace to monitor on the left",
+    ),
+    (
+        "move-workspace-to-monitor-right",
+        "Move workspace to monitor on the right",
+    ),
+    (
+        "move-workspace-to-monitor-up",
+        "Move workspace to monitor above",
+    ),
+    (
+        "move-workspace-to-monitor-down",
+        "Move workspace to monitor below",
+    ),
+    ("consume-window-into-column", "Consume window into column"),
+    ("expel-window-from-column", "Expel window from column"),
+    (
+        "consume-or-expel-window-left",
+        "Consume or expel window to the left",
+    ),
+    (
+        "consume-or-expel-window-right",
+        "Consume or expel window to the right",
+    ),
+    ("swap-window-left", "Swap window with column to the left"),
+    ("swap-window-right", "Swap window with column to the right"),
+    (
+        "toggle-column-tabbed-display",
+        "Toggle tabbed column display",
+    ),
+    ("center-column", "Center column"),
+    ("center-window", "Center window"),
+    ("center-visible-columns", "Center visible columns"),
+    ("maximize-column", "Maximize column"),
+    (
+        "maximize-window-to-edges",
+        "Maximize window to screen edges",
+    ),
+    ("fullscreen-window", "Toggle fullscreen"),
+    ("toggle-windowed-fullscreen", "Toggle windowed fullscreen"),
+    (
+        "expand-column-to-available-width",
+        "Expand column to available width",
+    ),
+    ("switch-preset-column-width", "Cycle preset column widths"),
+    ("switch-preset-window-width", "Cycle preset window widths"),
+    ("switch-preset-window-height", "Cycle preset window heights"),
+    ("reset-window-height", "Reset window height"),
+    ("set-column-width", "Set column width"),
+    ("set-window-width", "Set window width"),
+    ("set-window-height", "Set window height"),
+    ("toggle-window-floating", "Toggle floating"),
+    ("move-window-to-floating", "Make window floating"),
+    ("move-window-to-tiling", "Make window tiling"),
+    ("focus-floating", "Focus floating windows"),
+    ("focus-tiling", "Focus tiling windows"),
+    (
+        "switch-focus-between-floating-and-tiling",
+        "Switch focus between floating and tiling",
+    ),
+    ("toggle-window-rule-opacity", "Toggle window rule opacity"),
+    ("switch-layout", "Switch keyboard layout"),
+    ("set-dynamic-cast-window", "Cast this window"),
+    ("set-dynamic-cast-monitor", "Cast this monitor"),
+    ("clear-dynamic-cast-target", "Stop dynamic cast"),
+];
+
+/// Looks up the friendly description for a niri action name
+pub fn action_friendly_name(action: &str) -> Option<&'static str> {
+    ACTION_DESCRIPTIONS
+        .iter()
+        .find(|(name, _)| *name == action)
+        .map(|(_, description)| *description)
+}
+
+/// What a niri action expects as its argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgKind {
+    /// Position counted from 1, e.g. `focus-column 2`
+    Index,
+    /// Workspace index or name, e.g. `focus-workspace 3` or `focus-workspace "chat"`
+    IndexOrName,
+    /// Free text such as an output or workspace name
+    Name,
+    /// New size: `50%`, `+10%`, `-10%`, `1280` or `+100`
+    SizeChange,
+    /// Keyboard layout: `next`, `prev` or an index counted from 0
+    LayoutTarget,
+    /// One of the listed words
+    OneOf(&'static [&'static str]),
+}
+
+/// Argument each niri action takes, for the actions that take exactly one
+const ACTION_ARGS: &[(&str, ArgKind)] = &[
+    ("focus-column", ArgKind::Index),
+    ("focus-window-in-column", ArgKind::Index),
+    ("move-column-to-index", ArgKind::Index),
+    ("move-workspace-to-index", ArgKind::Index),
+    ("focus-workspace", ArgKind::IndexOrName),
+    ("move-window-to-workspace", ArgKind::IndexOrName),
+    ("move-column-to-workspace", ArgKind::IndexOrName),
+    ("set-workspace-name", ArgKind::Name),
+    ("focus-monitor", ArgKind::Name),
+    ("move-window-to-monitor", ArgKind::Name),
+    ("move-column-to-monitor", ArgKind::Name),
+    ("move-workspace-to-monitor", ArgKind::Name),
+    ("set-column-width", ArgKind::SizeChange),
+    ("set-window-width", ArgKind::SizeChange),
+    ("set-window-height", ArgKind::SizeChange),
+    ("switch-layout", ArgKind::LayoutTarget),
+    ("set-column-display", ArgKind::OneOf(&["normal", "tabbed"])),
+];
+
+/// Checks `args` against what niri expects for `action`
+///
+/// Actions missing from the table aren't checked. The error says what the
+/// action expects, for showing next to the arguments field.
+pub fn validate_action_args(action: &str, args: &[String]) -> Result<(), String> {
+    let Some(&(_, kind)) = ACTION_ARGS.iter().find(|(name, _)| *name == action) else {
+        return Ok(());
+    };
+    let arg = match args {
+        [arg] => arg.trim(),
+        [] => return Err(format!("{} needs an argument", action)),
+        _ => return Err(format!("{} takes a single argument", action)),
+    };
+
+    let valid = match kind {
+        ArgKind::Index => arg.parse::<u32>().is_ok(),
+        // niri reads digits as a workspace index (up to 255), anything else as a name
+        ArgKind::IndexOrName => {
+            !arg.is_empty()
+                && (!arg.chars().all(|c| c.is_ascii_digit()) || arg.parse::<u8>().is_ok())
+        }
+        ArgKind::Name => !arg.is_empty(),
+        ArgKind::SizeChange => {
+            let amount = arg.trim_start_matches(['+', '-']);
+            match amount.strip_suffix('%') {
+                Some(percent) => percent.parse::<f64>().is_ok_and(f64::is_finite),
+                None => amount.parse::<i32>().is_ok(),
+            }
+        }
+        ArgKind::LayoutTarget => matches!(arg, "next" | "prev") || arg.parse::<u8>().is_ok(),
+        ArgKind::OneOf(words) => words.contains(&arg),
+    };
+    if valid {
+        return Ok(());
+    }
+
+    let expected = match kind {
+        ArgKind::Index => "a number like 2".to_string(),
+        ArgKind::IndexOrName => "a workspace number (1-255) or name".to_string(),
+        ArgKind::Name => "a name".to_string(),
+        ArgKind::SizeChange => "a size like 50%, +10%, 1280 or -100".to_string(),
+        ArgKind::LayoutTarget => "next, prev or a layout number".to_string(),
+        ArgKind::OneOf(words) => words.join(" or "),
+    };
+    Err(format!("{} expects {}, not \"{}\"", action, expected, arg))
+}
+
 impl KeybindAction {
+    /// Which kind of action this is
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            KeybindAction::Spawn(_) => ActionKind::Spawn,
+            KeybindAction::NiriAction(_) => ActionKind::Niri,
+            KeybindAction::NiriActionWithArgs(..) => ActionKind::NiriWithArgs,
+        }
+    }
+
+    /// Get a friendly description, falling back to the raw action for unknown names
+    ///
+    /// Arguments are kept, so `focus-workspace 3` reads as "Focus workspace 3".
+    pub fn friendly_name(&self) -> String {
+        match self {
+            KeybindAction::Spawn(_) => self.description(),
+            KeybindAction::NiriAction(action) => action_friendly_name(action)
+                .map(str::to_string)
+                .unwrap_or_else(|| action.clone()),
+            KeybindAction::NiriActionWithArgs(action, args) => match action_friendly_name(action) {
+                Some(friendly) => format!("{} {}", friendly, args.join(" ")),
+                None => self.description(),
+            },
+        }
+    }
+
     /// Get a human-readable description of the action
     pub fn description(&self) -> String {
         match self {
@@ -36,8 +426,50 @@ impl KeybindAction {
     }
 }
 
+/// Actions that make no sense to fire repeatedly from a held key
+const NON_REPEATABLE_ACTIONS: &[&str] = &[
+    "quit",
+    "close-window",
+    "screenshot",
+    "screenshot-screen",
+    "screenshot-window",
+    "toggle-overview",
+    "open-overview",
+    "close-overview",
+    "show-hotkey-overlay",
+    "power-off-monitors",
+    "power-on-monitors",
+    "toggle-keyboard-shortcuts-inhibit",
+    "fullscreen-window",
+    "toggle-windowed-fullscreen",
+    "toggle-window-floating",
+    "maximize-column",
+];
+
+/// Whether holding a key bound to niri `action` should keep running it
+pub fn is_repeatable_action(action: &str) -> bool {
+    !NON_REPEATABLE_ACTIONS.contains(&action)
+}
+
+/// Explains what niri does with a binding's `repeat` and `cooldown-ms` combination
+///
+/// A cooldown of zero or less is treated as no cooldown.
+pub fn describe_repeat_behavior(repeat: bool, cooldown_ms: Option<i32>) -> String {
+    match (repeat, cooldown_ms.filter(|ms| *ms > 0)) {
+        (false, None) => "Runs once per key press; holding the key does nothing more".to_string(),
+        (true, None) => {
+            "Runs on press, then again at the keyboard repeat rate while held".to_string()
+        }
+        (false, Some(ms)) => format!(
+            "Runs once per key press; presses within {} ms of the last run are ignored",
+            ms
+        ),
+        (true, Some(ms)) => format!("Repeats while held, but runs at most once every {} ms", ms),
+    }
+}
+
 /// A single keybinding entry
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Keybinding {
     /// Unique identifier for UI
     pub id: u32,
@@ -45,23 +477,145 @@ pub struct Keybinding {
     pub key_combo: String,
     /// Optional title shown in niri's hotkey overlay
     pub hotkey_overlay_title: Option<String>,
+    /// Keeps the binding out of the hotkey overlay (`hotkey-overlay-title=null`);
+    /// takes precedence over the title
+    pub hotkey_overlay_hidden: bool,
+    /// Group for the app's keybindings list; niri's overlay has no sections,
+    /// so this is stored as a `// section:` comment above the binding
+    pub section: Option<String>,
     /// Whether binding works when screen is locked
     pub allow_when_locked: bool,
     /// Cooldown in milliseconds between activations
     pub cooldown_ms: Option<i32>,
-    /// Whether the binding repeats when held
+    /// Whether the binding repeats when held; niri's default is on
     pub repeat: bool,
     /// The action to perform
     pub action: KeybindAction,
+    /// Properties on the binding node that the app doesn't model, written
+    /// back unchanged so editing a binding doesn't strip them
+    pub extra_properties: Vec<(String, kdl::KdlValue)>,
+    /// Problem found while importing this binding (parts that were dropped or
+    /// couldn't be understood); not written back to the config
+    pub parse_warning: Option<String>,
+}
+
+impl Default for Keybinding {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            key_combo: String::new(),
+            hotkey_overlay_title: None,
+            hotkey_overlay_hidden: false,
+            section: None,
+            allow_when_locked: false,
+            cooldown_ms: None,
+            repeat: true,
+            action: KeybindAction::default(),
+            extra_properties: Vec::new(),
+            parse_warning: None,
+        }
+    }
 }
 
 impl Keybinding {
-    /// Get the display name (overlay title or action description)
+    /// Get the display name (overlay title or friendly action description)
     pub fn display_name(&self) -> String {
         self.hotkey_overlay_title
             .clone()
-            .unwrap_or_else(|| self.action.description())
+            .unwrap_or_else(|| self.action.friendly_name())
+    }
+
+    /// How the binding appears in niri's hotkey overlay, for the editor preview
+    pub fn overlay_preview(&self) -> String {
+        let combo = if self.key_combo.is_empty() {
+            "(no key)"
+        } else {
+            self.key_combo.as_str()
+        };
+        if self.hotkey_overlay_hidden {
+            format!("{}: hidden from overlay", combo)
+        } else {
+            format!("{}: {}", combo, self.display_name())
+        }
     }
+
+    /// Effective repeat/cooldown behavior, for inline guidance in the editor
+    pub fn repeat_behavior(&self) -> String {
+        describe_repeat_behavior(self.repeat, self.cooldown_ms)
+    }
+
+    /// Warns when the repeat/cooldown combination is likely a mistake
+    pub fn repeat_warning(&self) -> Option<String> {
+        let action = match &self.action {
+            KeybindAction::NiriAction(action) | KeybindAction::NiriActionWithArgs(action, _) => {
+                Some(action.as_str())
+            }
+            KeybindAction::Spawn(_) => None,
+        };
+        let one_shot = action.is_some_and(|a| !is_repeatable_action(a));
+
+        match self.cooldown_ms {
+            Some(ms) if ms <= 0 => {
+                return Some("A cooldown of 0 ms has no effect; clear it instead".to_string())
+            }
+            Some(_) if !self.repeat && one_shot => {
+                return Some(
+                    "Cooldown is set but repeat is off and this action doesn't repeat, \
+                     so the cooldown only blocks quick double presses"
+                        .to_string(),
+                )
+            }
+            _ => {}
+        }
+
+        if self.repeat && one_shot {
+            return Some("Holding the key will run this action over and over".to_string());
+        }
+        None
+    }
+}
+
+/// Category for the keybindings list, derived from the action name
+///
+/// Checks go from most to least specific, so `move-window-to-workspace`
+/// lands in Workspaces and `focus-monitor-left` in Monitors.
+pub fn categorize_action(action: &KeybindAction) -> &'static str {
+    let name = match action {
+        KeybindAction::Spawn(_) => return "Apps",
+        KeybindAction::NiriAction(name) | KeybindAction::NiriActionWithArgs(name, _) => {
+            name.as_str()
+        }
+    };
+    match name {
+        "spawn" | "spawn-sh" => "Apps",
+        "quit" | "suspend" | "power-off-monitors" | "power-on-monitors" => "Session",
+        _ if name.starts_with("screenshot") => "Screenshots",
+        _ if name.contains("workspace") => "Workspaces",
+        _ if name.contains("monitor") => "Monitors",
+        _ if name.contains("overview") || name.contains("preset") => "Layout",
+        _ if name.contains("column") => "Columns",
+        _ if name.contains("window") || name.contains("floating") => "Windows",
+        _ => "Other",
+    }
+}
+
+/// Groups binding indices for the keybindings list, in order of first use
+///
+/// A binding's own section wins; the rest are grouped by [`categorize_action`].
+/// Only indices are returned, so the bindings keep their saved order.
+pub fn group_by_category(bindings: &[Keybinding]) -> Vec<(&str, Vec<usize>)> {
+    let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+    for (idx, binding) in bindings.iter().enumerate() {
+        let name = binding
+            .section
+            .as_deref()
+            .unwrap_or_else(|| categorize_action(&binding.action));
+        match groups.iter_mut().find(|(group, _)| *group == name) {
+            Some((_, indices)) => indices.push(idx),
+            None => groups.push((name, vec![idx])),
+        }
+    }
+    groups
 }
 
 /// Keybindings settings - managed keyboard shortcuts
@@ -76,3 +630,380 @@ pub struct KeybindingsSettings {
     /// Error message if loading failed
     pub error: Option<String>,
 }
+
+impl KeybindingsSettings {
+    /// Another binding on the same keys as the one at `idx`, if any
+    pub fn conflicting_binding(&self, idx: usize) -> Option<&Keybinding> {
+        self.binding_on_combo(&self.bindings.get(idx)?.key_combo, idx)
+    }
+
+    /// A binding other than the one at `except` that already uses `combo`
+    ///
+    /// Lets a freshly captured combo be checked before it replaces the
+    /// binding's current one.
+    pub fn binding_on_combo(&self, combo: &str, except: usize) -> Option<&Keybinding> {
+        if combo.trim().is_empty() {
+            return None;
+        }
+        self.bindings
+            .iter()
+            .enumerate()
+            .find(|(other, b)| {
+                *other != except && crate::config::keys::same_combo(&b.key_combo, combo)
+            })
+            .map(|(_, b)| b)
+    }
+
+    /// IDs of every pair of bindings on the same keys, in list order
+    ///
+    /// niri only ever runs the first binding of a pair.
+    pub fn find_conflicts(&self) -> Vec<(u32, u32)> {
+        let mut by_combo: HashMap<String, Vec<u32>> = HashMap::new();
+        let mut order = Vec::new();
+        for binding in &self.bindings {
+            if binding.key_combo.trim().is_empty() {
+                continue;
+            }
+            let key = crate::config::keys::combo_key(&binding.key_combo);
+            let ids = by_combo.entry(key.clone()).or_default();
+            if ids.is_empty() {
+                order.push(key);
+            }
+            ids.push(binding.id);
+        }
+
+        let mut conflicts = Vec::new();
+        for key in &order {
+            let ids = &by_combo[key];
+            for (i, &first) in ids.iter().enumerate() {
+                conflicts.extend(ids[i + 1..].iter().map(|&second| (first, second)));
+            }
+        }
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(action: KeybindAction) -> Keybinding {
+        Keybinding {
+            action,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_display_name_uses_friendly_description() {
+        let kb = binding(KeybindAction::NiriAction("focus-column-left".to_string()));
+        assert_eq!(kb.display_name(), "Focus column to the left");
+    }
+
+    #[test]
+    fn test_display_name_falls_back_to_raw_action() {
+        let kb = binding(KeybindAction::NiriAction("some-future-action".to_string()));
+        assert_eq!(kb.display_name(), "some-future-action");
+
+        let kb = binding(KeybindAction::NiriActionWithArgs(
+            "some-future-action".to_string(),
+            vec!["1".to_string()],
+        ));
+        assert_eq!(kb.display_name(), "some-future-action 1");
+    }
+
+    #[test]
+    fn test_display_name_keeps_action_arguments() {
+        let kb = binding(KeybindAction::NiriActionWithArgs(
+            "focus-workspace".to_string(),
+            vec!["3".to_string()],
+        ));
+        assert_eq!(kb.display_name(), "Focus workspace 3");
+    }
+
+    #[test]
+    fn test_display_name_prefers_overlay_title() {
+        let mut kb = binding(KeybindAction::NiriAction("close-window".to_string()));
+        kb.hotkey_overlay_title = Some("Kill it".to_string());
+        assert_eq!(kb.display_name(), "Kill it");
+    }
+
+    #[test]
+    fn test_overlay_preview() {
+        let mut kb = binding(KeybindAction::NiriAction("close-window".to_string()));
+        kb.key_combo = "Mod+Q".to_string();
+        assert_eq!(kb.overlay_preview(), "Mod+Q: Close window");
+
+        kb.hotkey_overlay_title = Some("Kill it".to_string());
+        assert_eq!(kb.overlay_preview(), "Mod+Q: Kill it");
+
+        // Hiding wins over the title
+        kb.hotkey_overlay_hidden = true;
+        assert_eq!(kb.overlay_preview(), "Mod+Q: hidden from overlay");
+    }
+
+    #[test]
+    fn test_action_descriptions_have_no_duplicates() {
+        let mut names: Vec<_> = ACTION_DESCRIPTIONS.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), ACTION_DESCRIPTIONS.len());
+    }
+
+    #[test]
+    fn test_describe_repeat_behavior_combinations() {
+        assert!(describe_repeat_behavior(false, None).contains("once per key press"));
+        assert!(describe_repeat_behavior(true, None).contains("repeat rate"));
+        assert!(describe_repeat_behavior(false, Some(250)).contains("within 250 ms"));
+        assert!(describe_repeat_behavior(true, Some(150)).contains("at most once every 150 ms"));
+    }
+
+    #[test]
+    fn test_describe_repeat_behavior_ignores_non_positive_cooldown() {
+        assert_eq!(
+            describe_repeat_behavior(true, Some(0)),
+            describe_repeat_behavior(true, None)
+        );
+        assert_eq!(
+            describe_repeat_behavior(false, Some(-5)),
+            describe_repeat_behavior(false, None)
+        );
+    }
+
+    #[test]
+    fn test_repeat_warning_flags_likely_mistakes() {
+        let mut kb = binding(KeybindAction::NiriAction("close-window".to_string()));
+        // Binds repeat unless told otherwise
+        assert!(kb.repeat_warning().is_some());
+
+        kb.repeat = false;
+        assert_eq!(kb.repeat_warning(), None);
+
+        kb.cooldown_ms = Some(500);
+        assert!(kb.repeat_warning().unwrap().contains("double presses"));
+
+        kb.cooldown_ms = Some(0);
+        assert!(kb.repeat_warning().unwrap().contains("no effect"));
+    }
+
+    #[test]
+    fn test_repeat_warning_allows_repeatable_actions() {
+        let mut kb = binding(KeybindAction::NiriAction("focus-column-left".to_string()));
+        kb.repeat = true;
+        kb.cooldown_ms = Some(150);
+        assert_eq!(kb.repeat_warning(), None);
+
+        let mut kb = binding(KeybindAction::Spawn(vec!["wpctl".to_string()]));
+        kb.cooldown_ms = Some(100);
+        assert_eq!(kb.repeat_warning(), None);
+    }
+
+    fn spawn(args: &[&str]) -> KeybindAction {
+        KeybindAction::Spawn(args.iter().map(|a| a.to_string()).collect())
+    }
+
+    fn with_args(name: &str, args: &[&str]) -> KeybindAction {
+        KeybindAction::NiriActionWithArgs(
+            name.to_string(),
+            args.iter().map(|a| a.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_convert_action_same_kind_is_unchanged() {
+        for action in [
+            spawn(&["alacritty"]),
+            KeybindAction::NiriAction("close-window".to_string()),
+            with_args("focus-workspace", &["3"]),
+        ] {
+            assert_eq!(convert_action(&action, action.kind()), action);
+        }
+    }
+
+    #[test]
+    fn test_convert_action_spawn_and_with_args_keep_args() {
+        let converted = convert_action(&spawn(&["foot", "-e", "htop"]), ActionKind::NiriWithArgs);
+        assert_eq!(converted, with_args("", &["foot", "-e", "htop"]));
+
+        let converted = convert_action(
+            &with_args("spawn-sh", &["notify-send hi"]),
+            ActionKind::Spawn,
+        );
+        assert_eq!(converted, spawn(&["notify-send hi"]));
+    }
+
+    #[test]
+    fn test_convert_action_niri_and_with_args_keep_name() {
+        let converted = convert_action(
+            &KeybindAction::NiriAction("focus-workspace".to_string()),
+            ActionKind::NiriWithArgs,
+        );
+        assert_eq!(converted, with_args("focus-workspace", &[]));
+
+        let converted = convert_action(&with_args("focus-workspace", &["3"]), ActionKind::Niri);
+        assert_eq!(
+            converted,
+            KeybindAction::NiriAction("focus-workspace".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convert_action_clears_what_does_not_apply() {
+        assert_eq!(
+            convert_action(&spawn(&["alacritty"]), ActionKind::Niri),
+            KeybindAction::NiriAction(String::new())
+        );
+        assert_eq!(
+            convert_action(
+                &KeybindAction::NiriAction("close-window".to_string()),
+                ActionKind::Spawn
+            ),
+            spawn(&[])
+        );
+    }
+
+    #[test]
+    fn test_conflicting_binding_matches_normalized_combo() {
+        let mut settings = KeybindingsSettings::default();
+        for (combo, action) in [
+            ("Mod+T", "close-window"),
+            ("shift+mod+t", "quit"),
+            ("mod+t", "suspend"),
+        ] {
+            let mut kb = binding(KeybindAction::NiriAction(action.to_string()));
+            kb.key_combo = combo.to_string();
+            settings.bindings.push(kb);
+        }
+
+        let other = settings.conflicting_binding(2).unwrap();
+        assert_eq!(other.key_combo, "Mod+T");
+        assert!(settings.conflicting_binding(1).is_none());
+    }
+
+    fn bindings_on(combos: &[&str]) -> KeybindingsSettings {
+        let mut settings = KeybindingsSettings::default();
+        for (id, combo) in combos.iter().enumerate() {
+            let mut kb = binding(KeybindAction::NiriAction("close-window".to_string()));
+            kb.id = id as u32;
+            kb.key_combo = combo.to_string();
+            settings.bindings.push(kb);
+        }
+        settings
+    }
+
+    #[test]
+    fn test_find_conflicts_ignores_modifier_order() {
+        let settings = bindings_on(&["Mod+Shift+Q", "Mod+Q", "Shift+Mod+Q", "Ctrl+Alt+T"]);
+        assert_eq!(settings.find_conflicts(), vec![(0, 2)]);
+
+        let settings = bindings_on(&["Ctrl+Alt+Delete", "Alt+Ctrl+Delete", "Ctrl+Alt+Delete"]);
+        assert_eq!(settings.find_conflicts(), vec![(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_find_conflicts_ignores_case() {
+        let settings = bindings_on(&["Mod+q", "MOD+Q", "XF86AudioMute", "xf86audiomute", ""]);
+        assert_eq!(settings.find_conflicts(), vec![(0, 1), (2, 3)]);
+        // Bindings without keys yet don't conflict with each other
+        assert!(bindings_on(&["", " "]).find_conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_captured_combo_flags_existing_binding() {
+        let mut settings = KeybindingsSettings::default();
+        for (combo, action) in [("Mod+Q", "close-window"), ("", "quit")] {
+            let mut kb = binding(KeybindAction::NiriAction(action.to_string()));
+            kb.key_combo = combo.to_string();
+            settings.bindings.push(kb);
+        }
+
+        // Capturing for the second binding, before the combo is committed
+        let other = settings.binding_on_combo("Mod+q", 1).unwrap();
+        assert_eq!(
+            other.action,
+            KeybindAction::NiriAction("close-window".to_string())
+        );
+        // Re-capturing a binding's own combo isn't a conflict
+        assert!(settings.binding_on_combo("Mod+Q", 0).is_none());
+        assert!(settings.binding_on_combo("Mod+W", 1).is_none());
+    }
+
+    #[test]
+    fn test_group_by_category_buckets_by_action() {
+        let binding = |action: KeybindAction, section: Option<&str>| Keybinding {
+            action,
+            section: section.map(String::from),
+            ..Default::default()
+        };
+        let niri = |name: &str| KeybindAction::NiriAction(name.to_string());
+        let bindings = vec![
+            binding(KeybindAction::Spawn(vec!["alacritty".into()]), None),
+            binding(niri("close-window"), None),
+            binding(
+                KeybindAction::NiriActionWithArgs("focus-workspace".into(), vec!["1".into()]),
+                None,
+            ),
+            binding(niri("move-window-to-workspace-down"), None),
+            binding(niri("focus-monitor-left"), None),
+            binding(niri("screenshot-window"), None),
+            binding(niri("quit"), None),
+            binding(niri("consume-or-expel-window-left"), Some("Tiling")),
+            binding(niri("focus-column-left"), None),
+            binding(niri("toggle-overview"), None),
+            binding(niri("focus-window-down"), None),
+        ];
+
+        let groups = group_by_category(&bindings);
+        let names: Vec<&str> = groups.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            [
+                "Apps",
+                "Windows",
+                "Workspaces",
+                "Monitors",
+                "Screenshots",
+                "Session",
+                "Tiling",
+                "Columns",
+                "Layout"
+            ]
+        );
+        assert_eq!(groups[1].1, [1, 10]);
+        assert_eq!(groups[2].1, [2, 3]);
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_numeric_action_rejects_text() {
+        let err = validate_action_args("focus-column", &args(&["abc"])).unwrap_err();
+        assert!(err.contains("number"), "{}", err);
+        assert!(validate_action_args("focus-column", &args(&["-1"])).is_err());
+        assert!(validate_action_args("focus-column", &args(&[])).is_err());
+        assert!(validate_action_args("focus-column", &args(&["1", "2"])).is_err());
+    }
+
+    #[test]
+    fn test_valid_action_args() {
+        assert!(validate_action_args("focus-column", &args(&["2"])).is_ok());
+        assert!(validate_action_args("focus-workspace", &args(&["3"])).is_ok());
+        // Workspaces can be referred to by name
+        assert!(validate_action_args("focus-workspace", &args(&["chat"])).is_ok());
+        assert!(validate_action_args("focus-workspace", &args(&["300"])).is_err());
+        for size in ["50%", "+10%", "-33.3%", "1280", "-100"] {
+            assert!(
+                validate_action_args("set-column-width", &args(&[size])).is_ok(),
+                "{}",
+                size
+            );
+        }
+        assert!(validate_action_args("set-column-width", &args(&["wide"])).is_err());
+        assert!(validate_action_args("switch-layout", &args(&["prev"])).is_ok());
+        assert!(validate_action_args("set-column-display", &args(&["stacked"])).is_err());
+        // Actions without a schema aren't checked
+        assert!(validate_action_args("some-future-action", &args(&["x", "y"])).is_ok());
+    }
+}
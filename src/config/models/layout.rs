@@ -293,3 +293,106 @@ pub struct LayoutOverride {
 }
 
 // has_any() is now derived via #[derive(HasAny)]
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_all_resets_override_to_inherited() {
+        let mut layout = LayoutOverride {
+            gaps: Some(8.0),
+            border_enabled: Some(false),
+            preset_column_widths: Some(vec![PresetWidth::Proportion(0.5)]),
+            ..Default::default()
+        };
+        assert!(layout.has_any());
+
+        layout.clear_all();
+        assert!(!layout.has_any());
+        assert_eq!(layout, LayoutOverride::default());
+    }
+
+    #[test]
+    fn test_clear_all_keeps_non_option_fields() {
+        #[derive(Debug, Default, HasAny)]
+        struct Partial {
+            name: String,
+            width: Option<i32>,
+        }
+
+        let mut partial = Partial {
+            name: "eDP-1".to_string(),
+            width: Some(4),
+        };
+        partial.clear_all();
+        assert!(!partial.has_any());
+        assert_eq!(partial.name, "eDP-1");
+    }
+
+    #[test]
+    fn test_set_field_count() {
+        #[derive(Default, HasAny)]
+        struct Four {
+            a: Option<i32>,
+            b: Option<bool>,
+            c: Option<f32>,
+            d: Option<String>,
+        }
+
+        let four = Four {
+            b: Some(true),
+            d: Some("x".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(four.set_field_count(), 2);
+        assert_eq!(Four::default().set_field_count(), 0);
+    }
+
+    #[test]
+    fn test_merge_override_only_applies_set_fields() {
+        use nirify_macros::MergeOverride;
+
+        #[derive(Debug, Default, PartialEq)]
+        struct Spacing {
+            gaps: f32,
+            strut_left: f32,
+            center: bool,
+            presets: Vec<PresetWidth>,
+        }
+
+        #[derive(Default, MergeOverride)]
+        #[merge(base = "Spacing")]
+        struct SpacingOverride {
+            gaps: Option<f32>,
+            strut_left: Option<f32>,
+            center: Option<bool>,
+            presets: Option<Vec<PresetWidth>>,
+            // Not an override, so never merged
+            #[allow(dead_code)]
+            label: String,
+        }
+
+        let over = SpacingOverride {
+            gaps: Some(24.0),
+            presets: Some(vec![PresetWidth::Fixed(800)]),
+            label: "eDP-1".to_string(),
+            ..Default::default()
+        };
+        let mut base = Spacing {
+            strut_left: 4.0,
+            ..Default::default()
+        };
+        over.merge_into(&mut base);
+
+        assert_eq!(
+            base,
+            Spacing {
+                gaps: 24.0,
+                strut_left: 4.0,
+                center: false,
+                presets: vec![PresetWidth::Fixed(800)],
+            }
+        );
+    }
+}
@@ -9,6 +9,7 @@ mod behavior;
 mod debug;
 mod gestures;
 mod input;
+mod keybinding_templates;
 mod keybindings;
 mod layout;
 mod misc;
@@ -27,6 +28,7 @@ pub use behavior::*;
 pub use debug::*;
 pub use gestures::*;
 pub use input::*;
+pub use keybinding_templates::*;
 pub use keybindings::*;
 pub use layout::*;
 pub use misc::*;
@@ -38,6 +40,7 @@ pub use startup::*;
 pub use switch_events::*;
 pub use workspaces::*;
 
+use super::SettingsCategory;
 use crate::constants::{
     ACCEL_SPEED_MAX, ACCEL_SPEED_MIN, ANIMATION_SLOWDOWN_MAX, ANIMATION_SLOWDOWN_MIN,
     BORDER_THICKNESS_MAX, BORDER_THICKNESS_MIN, COLUMN_FIXED_MAX, COLUMN_FIXED_MIN,
@@ -301,6 +304,20 @@ impl Settings {
                 }
                 rule.default_column_width = Some(clamped);
             }
+            if let Some(width) = rule.default_column_width_fixed {
+                let clamped = width.clamp(COLUMN_FIXED_MIN as i32, COLUMN_FIXED_MAX as i32);
+                if width != clamped {
+                    log::debug!(
+                        "Clamped window_rule[{}].default_column_width_fixed from {} to {} (range: {}..={})",
+                        i,
+                        width,
+                        clamped,
+                        COLUMN_FIXED_MIN,
+                        COLUMN_FIXED_MAX
+                    );
+                }
+                rule.default_column_width_fixed = Some(clamped);
+            }
         }
     }
 
@@ -336,4 +353,36 @@ impl Settings {
                 .clamp(EASING_DURATION_MIN, EASING_DURATION_MAX);
         }
     }
+
+    /// Replaces the settings `category` covers with those in `from`
+    pub fn copy_category(&mut self, from: &Settings, category: SettingsCategory) {
+        match category {
+            SettingsCategory::Appearance => self.appearance = from.appearance.clone(),
+            SettingsCategory::Behavior => self.behavior = from.behavior.clone(),
+            SettingsCategory::Keyboard => self.keyboard = from.keyboard.clone(),
+            SettingsCategory::Mouse => self.mouse = from.mouse.clone(),
+            SettingsCategory::Touchpad => self.touchpad = from.touchpad.clone(),
+            SettingsCategory::Trackpoint => self.trackpoint = from.trackpoint.clone(),
+            SettingsCategory::Trackball => self.trackball = from.trackball.clone(),
+            SettingsCategory::Tablet => self.tablet = from.tablet.clone(),
+            SettingsCategory::Touch => self.touch = from.touch.clone(),
+            SettingsCategory::Outputs => self.outputs = from.outputs.clone(),
+            SettingsCategory::Animations => self.animations = from.animations.clone(),
+            SettingsCategory::Cursor => self.cursor = from.cursor.clone(),
+            SettingsCategory::Overview => self.overview = from.overview.clone(),
+            SettingsCategory::Workspaces => self.workspaces = from.workspaces.clone(),
+            SettingsCategory::Keybindings => self.keybindings = from.keybindings.clone(),
+            SettingsCategory::LayoutExtras => self.layout_extras = from.layout_extras.clone(),
+            SettingsCategory::Gestures => self.gestures = from.gestures.clone(),
+            SettingsCategory::LayerRules => self.layer_rules = from.layer_rules.clone(),
+            SettingsCategory::WindowRules => self.window_rules = from.window_rules.clone(),
+            SettingsCategory::Miscellaneous => self.miscellaneous = from.miscellaneous.clone(),
+            SettingsCategory::Startup => self.startup = from.startup.clone(),
+            SettingsCategory::Environment => self.environment = from.environment.clone(),
+            SettingsCategory::Debug => self.debug = from.debug.clone(),
+            SettingsCategory::SwitchEvents => self.switch_events = from.switch_events.clone(),
+            SettingsCategory::RecentWindows => self.recent_windows = from.recent_windows.clone(),
+            SettingsCategory::Preferences => self.preferences = from.preferences.clone(),
+        }
+    }
 }
@@ -1,5 +1,6 @@
 //! Output/display configuration
 
+use crate::ipc::FullOutputInfo;
 use crate::types::{Color, Transform, VrrMode};
 
 use super::layout::LayoutOverride;
@@ -34,7 +35,15 @@ impl OutputHotCorners {
 /// Single output/display configuration
 #[derive(Debug, Clone, PartialEq)]
 pub struct OutputConfig {
+    /// Connector name (e.g. "HDMI-A-1")
     pub name: String,
+    /// Monitor identity as niri reports it, for matching the monitor itself
+    /// rather than whichever one is plugged into `name`
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    /// Match the output by make/model/serial instead of connector name
+    pub match_by_identity: bool,
     pub enabled: bool,
     pub scale: f64,
     pub mode: String, // e.g., "1920x1080@60.000"
@@ -44,6 +53,9 @@ pub struct OutputConfig {
     pub modeline: Option<String>,
     pub position_x: i32,
     pub position_y: i32,
+    /// Whether the position is written even at 0,0; without one niri places
+    /// the output itself
+    pub position_set: bool,
     pub transform: Transform,
     pub vrr: VrrMode,
     pub focus_at_startup: bool,
@@ -52,12 +64,19 @@ pub struct OutputConfig {
     pub hot_corners: Option<OutputHotCorners>,
     /// Per-output layout override (v25.11+)
     pub layout_override: Option<LayoutOverride>,
+    /// Output sub-nodes we don't model (e.g. color/HDR options from newer niri),
+    /// kept verbatim as KDL so saving doesn't strip them
+    pub extra_nodes: Vec<String>,
 }
 
 impl Default for OutputConfig {
     fn default() -> Self {
         Self {
             name: String::new(),
+            make: None,
+            model: None,
+            serial: None,
+            match_by_identity: false,
             enabled: true,
             scale: 1.0,
             mode: String::new(),
@@ -65,18 +84,701 @@ impl Default for OutputConfig {
             modeline: None,
             position_x: 0,
             position_y: 0,
+            position_set: false,
             transform: Transform::Normal,
             vrr: VrrMode::Off,
             focus_at_startup: false,
             backdrop_color: None,
             hot_corners: None,
             layout_override: None,
+            extra_nodes: Vec::new(),
         }
     }
 }
 
+impl OutputConfig {
+    /// The "make model serial" string niri matches monitors by, once make
+    /// and model are known; niri fills a missing serial with "Unknown"
+    pub fn identity(&self) -> Option<String> {
+        Some(format!(
+            "{} {} {}",
+            self.make.as_deref()?,
+            self.model.as_deref()?,
+            self.serial.as_deref().unwrap_or("Unknown")
+        ))
+    }
+
+    /// The name written on the `output` node: the identity when matching by
+    /// it and it's known, the connector name otherwise
+    pub fn match_name(&self) -> String {
+        self.identity()
+            .filter(|_| self.match_by_identity)
+            .unwrap_or_else(|| self.name.clone())
+    }
+
+    /// Whether a scale is saved for this output; at 1 it's left out and niri
+    /// picks one itself
+    pub fn has_scale(&self) -> bool {
+        (self.scale - 1.0).abs() > 0.001
+    }
+
+    /// Whether a position is saved for this output, rather than niri
+    /// placing it wherever there's room
+    pub fn has_position(&self) -> bool {
+        self.position_set || self.position_x != 0 || self.position_y != 0
+    }
+
+    /// Places this output at `x`, `y`, 0,0 included
+    pub fn set_position(&mut self, x: i32, y: i32) {
+        self.position_x = x;
+        self.position_y = y;
+        self.position_set = true;
+    }
+
+    /// Logical size of this output for a `mode_w`x`mode_h` physical mode
+    ///
+    /// This is the size niri lays outputs out with: sideways transforms swap
+    /// width and height, then the result is divided by the scale.
+    pub fn logical_size(&self, mode_w: u32, mode_h: u32) -> (u32, u32) {
+        logical_output_size(mode_w, mode_h, self.scale, self.transform)
+    }
+
+    /// Physical width and height of the configured mode, if one is set
+    pub fn mode_size(&self) -> Option<(u32, u32)> {
+        let resolution = self.mode.split('@').next()?;
+        let (w, h) = resolution.split_once('x')?;
+        Some((w.trim().parse().ok()?, h.trim().parse().ok()?))
+    }
+
+    /// Name to show for this output in messages
+    fn label(&self, index: usize) -> String {
+        if self.name.is_empty() {
+            format!("Output {}", index + 1)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// Logical size for a physical mode under the given scale and transform
+pub fn logical_output_size(
+    mode_w: u32,
+    mode_h: u32,
+    scale: f64,
+    transform: Transform,
+) -> (u32, u32) {
+    let (w, h) = if transform.swaps_dimensions() {
+        (mode_h, mode_w)
+    } else {
+        (mode_w, mode_h)
+    };
+    let scale = if scale > 0.0 { scale } else { 1.0 };
+    (
+        (f64::from(w) / scale).round() as u32,
+        (f64::from(h) / scale).round() as u32,
+    )
+}
+
+/// Logical size of a configured output, using the live mode when connected
+///
+/// Falls back to the configured mode, then to 1920x1080, for an output
+/// that isn't connected.
+pub fn configured_output_size(output: &OutputConfig, available: &[FullOutputInfo]) -> (i32, i32) {
+    let (mode_w, mode_h) = match available.iter().find(|info| info.name == output.name) {
+        Some(info) => {
+            let (w, h) = info.current_mode_size();
+            (w.max(0) as u32, h.max(0) as u32)
+        }
+        None => output.mode_size().unwrap_or((1920, 1080)),
+    };
+    let (w, h) = output.logical_size(mode_w, mode_h);
+    (w as i32, h as i32)
+}
+
+/// Scales within this distance of a whole number count as integer
+const INTEGER_SCALE_EPSILON: f64 = 0.001;
+
+/// Which way to round a fractional scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleRounding {
+    /// Nearest whole number, halves rounding up (1.5 → 2)
+    #[default]
+    Nearest,
+    /// Next whole number up (1.25 → 2)
+    Up,
+    /// Next whole number down, but never below 1 (1.75 → 1)
+    Down,
+}
+
+/// Whether a scale is fractional, which Xwayland apps render blurry at
+pub fn is_fractional_scale(scale: f64) -> bool {
+    (scale - scale.round()).abs() > INTEGER_SCALE_EPSILON
+}
+
+/// Rounds a scale to a whole number in the given direction, at least 1
+pub fn round_scale(scale: f64, rounding: ScaleRounding) -> f64 {
+    let rounded = match rounding {
+        ScaleRounding::Nearest => scale.round(),
+        ScaleRounding::Up => scale.ceil(),
+        ScaleRounding::Down => scale.floor(),
+    };
+    rounded.max(1.0)
+}
+
+/// Ready-made layouts for several outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrangementPreset {
+    /// One row, left to right, tops aligned
+    SideBySide,
+    /// One column, top to bottom, centered horizontally
+    Stacked,
+    /// The first output centered at the bottom, the rest in a row above it
+    PrimaryBelowSecondary,
+}
+
+impl ArrangementPreset {
+    pub const ALL: [Self; 3] = [Self::SideBySide, Self::Stacked, Self::PrimaryBelowSecondary];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SideBySide => "Side by side",
+            Self::Stacked => "Stacked",
+            Self::PrimaryBelowSecondary => "Secondary above",
+        }
+    }
+
+    /// Positions for outputs of the given logical sizes, in the same order
+    ///
+    /// All positions are non-negative, with the arrangement touching the
+    /// origin. `None` for fewer than two outputs, which have nothing to
+    /// arrange.
+    pub fn positions(&self, sizes: &[(u32, u32)]) -> Option<Vec<(i32, i32)>> {
+        if sizes.len() < 2 {
+            return None;
+        }
+        let sizes: Vec<(i32, i32)> = sizes.iter().map(|&(w, h)| (w as i32, h as i32)).collect();
+
+        let positions = match self {
+            Self::SideBySide => row_positions(&sizes, 0, |_| 0),
+            Self::Stacked => {
+                let width = sizes.iter().map(|&(w, _)| w).max().unwrap_or(0);
+                let mut y = 0;
+                sizes
+                    .iter()
+                    .map(|&(w, h)| {
+                        let position = ((width - w) / 2, y);
+                        y += h;
+                        position
+                    })
+                    .collect()
+            }
+            Self::PrimaryBelowSecondary => {
+                let (primary_w, _) = sizes[0];
+                let secondary = &sizes[1..];
+                let row_w: i32 = secondary.iter().map(|&(w, _)| w).sum();
+                let row_h = secondary.iter().map(|&(_, h)| h).max().unwrap_or(0);
+                let width = primary_w.max(row_w);
+
+                let mut positions = vec![((width - primary_w) / 2, row_h)];
+                positions.extend(row_positions(secondary, (width - row_w) / 2, |h| row_h - h));
+                positions
+            }
+        };
+        Some(positions)
+    }
+}
+
+/// Positions along a row starting at `x`, with `top` giving each output's y
+/// from its height
+fn row_positions(sizes: &[(i32, i32)], x: i32, top: impl Fn(i32) -> i32) -> Vec<(i32, i32)> {
+    let mut x = x;
+    sizes
+        .iter()
+        .map(|&(w, h)| {
+            let position = (x, top(h));
+            x += w;
+            position
+        })
+        .collect()
+}
+
+/// Something wrong with where enabled outputs are placed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutIssue {
+    /// Two outputs cover some of the same area
+    Overlap { first: String, second: String },
+    /// Outputs that don't touch the rest, so the pointer can't move to them
+    Detached { outputs: Vec<String> },
+}
+
+impl std::fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overlap { first, second } => write!(f, "{} and {} overlap", first, second),
+            Self::Detached { outputs } => write!(
+                f,
+                "{} {} not touching the other outputs",
+                outputs.join(", "),
+                if outputs.len() == 1 { "is" } else { "are" }
+            ),
+        }
+    }
+}
+
+/// Logical pixels within which a dragged output's edge snaps to another's
+pub const ARRANGE_SNAP_DISTANCE: i32 = 48;
+
+/// Step dragged output positions are rounded to when nothing is in snapping
+/// range
+pub const ARRANGE_GRID: i32 = 10;
+
+/// Logical rectangle of a placed output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl OutputRect {
+    fn right(&self) -> i64 {
+        i64::from(self.x) + i64::from(self.width)
+    }
+
+    fn bottom(&self) -> i64 {
+        i64::from(self.y) + i64::from(self.height)
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        i64::from(self.x) < other.right()
+            && i64::from(other.x) < self.right()
+            && i64::from(self.y) < other.bottom()
+            && i64::from(other.y) < self.bottom()
+    }
+
+    /// Whether the two share some length of edge, or overlap; touching at
+    /// a corner alone doesn't let the pointer cross
+    fn touches(&self, other: &Self) -> bool {
+        let shared_x = self.right().min(other.right()) - i64::from(self.x.max(other.x));
+        let shared_y = self.bottom().min(other.bottom()) - i64::from(self.y.max(other.y));
+        (shared_x >= 0 && shared_y > 0) || (shared_x > 0 && shared_y >= 0)
+    }
+
+    /// Where this rectangle, dragged to its current position, gets dropped
+    ///
+    /// On each axis an edge within [`ARRANGE_SNAP_DISTANCE`] of a matching
+    /// edge of a nearby output is pulled flush against it, the closest one
+    /// winning; otherwise the position is rounded to [`ARRANGE_GRID`]. Only
+    /// outputs level with this one on the other axis (again within snapping
+    /// distance) are snapped to, so far-off outputs don't pull it around.
+    pub fn snapped(&self, others: &[OutputRect]) -> (i32, i32) {
+        let near = |start: i64, end: i64, other_start: i64, other_end: i64| {
+            let snap = i64::from(ARRANGE_SNAP_DISTANCE);
+            start <= other_end + snap && other_start <= end + snap
+        };
+
+        let x_edges = others
+            .iter()
+            .filter(|o| near(self.y.into(), self.bottom(), o.y.into(), o.bottom()))
+            .map(|o| (i64::from(o.x), o.right()));
+        let y_edges = others
+            .iter()
+            .filter(|o| near(self.x.into(), self.right(), o.x.into(), o.right()))
+            .map(|o| (i64::from(o.y), o.bottom()));
+
+        (
+            snap_axis(self.x, self.width, x_edges),
+            snap_axis(self.y, self.height, y_edges),
+        )
+    }
+}
+
+/// Start of a `len`-long span at `start` after snapping to the nearest of
+/// the `(start, end)` spans in `edges`, or to the grid
+fn snap_axis(start: i32, len: u32, edges: impl Iterator<Item = (i64, i64)>) -> i32 {
+    let start_i64 = i64::from(start);
+    let len = i64::from(len);
+    edges
+        .flat_map(|(other_start, other_end)| {
+            // Candidate starts lining up either of our edges with either of theirs
+            [other_start, other_end, other_start - len, other_end - len]
+        })
+        .map(|candidate| (candidate, (candidate - start_i64).abs()))
+        .filter(|&(_, distance)| distance <= i64::from(ARRANGE_SNAP_DISTANCE))
+        .min_by_key(|&(_, distance)| distance)
+        .and_then(|(candidate, _)| i32::try_from(candidate).ok())
+        .unwrap_or_else(|| {
+            let grid = f64::from(ARRANGE_GRID);
+            ((f64::from(start) / grid).round() * grid) as i32
+        })
+}
+
 /// Display/output settings - holds configured outputs
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct OutputSettings {
     pub outputs: Vec<OutputConfig>,
 }
+
+impl OutputSettings {
+    /// Index of the output focused at startup (the first flagged one)
+    pub fn startup_focus(&self) -> Option<usize> {
+        self.outputs.iter().position(|o| o.focus_at_startup)
+    }
+
+    /// Makes `index` the only output focused at startup, or none of them
+    pub fn set_startup_focus(&mut self, index: Option<usize>) {
+        for (i, output) in self.outputs.iter_mut().enumerate() {
+            output.focus_at_startup = Some(i) == index;
+        }
+    }
+
+    /// Overlapping or detached outputs, sized by their configured modes
+    ///
+    /// Outputs without a mode are left out, since their size isn't known
+    /// until niri picks one.
+    pub fn layout_issues(&self) -> Vec<LayoutIssue> {
+        self.layout_issues_with(|output| output.mode_size().map(|(w, h)| output.logical_size(w, h)))
+    }
+
+    /// Overlapping or detached outputs, with `size` giving each output's
+    /// logical size
+    ///
+    /// Disabled outputs and those `size` returns `None` for count as absent,
+    /// and so do outputs without a position, which niri places itself
+    /// wherever there's room.
+    pub fn layout_issues_with(
+        &self,
+        size: impl Fn(&OutputConfig) -> Option<(u32, u32)>,
+    ) -> Vec<LayoutIssue> {
+        let placed: Vec<(String, OutputRect)> = self
+            .outputs
+            .iter()
+            .enumerate()
+            .filter(|(_, output)| output.enabled)
+            .filter(|(_, output)| output.has_position())
+            .filter_map(|(i, output)| {
+                let (w, h) = size(output)?;
+                let rect = OutputRect {
+                    x: output.position_x,
+                    y: output.position_y,
+                    width: w,
+                    height: h,
+                };
+                Some((output.label(i), rect))
+            })
+            .collect();
+
+        let mut issues = Vec::new();
+        for (i, (first, a)) in placed.iter().enumerate() {
+            for (second, b) in &placed[i + 1..] {
+                if a.overlaps(b) {
+                    issues.push(LayoutIssue::Overlap {
+                        first: first.clone(),
+                        second: second.clone(),
+                    });
+                }
+            }
+        }
+
+        // Group outputs that can reach each other, starting from the first
+        let mut group = vec![usize::MAX; placed.len()];
+        let mut groups = 0;
+        for start in 0..placed.len() {
+            if group[start] != usize::MAX {
+                continue;
+            }
+            group[start] = groups;
+            let mut pending = vec![start];
+            while let Some(current) = pending.pop() {
+                for next in 0..placed.len() {
+                    if group[next] == usize::MAX && placed[current].1.touches(&placed[next].1) {
+                        group[next] = groups;
+                        pending.push(next);
+                    }
+                }
+            }
+            groups += 1;
+        }
+        for detached in 1..groups {
+            issues.push(LayoutIssue::Detached {
+                outputs: placed
+                    .iter()
+                    .zip(&group)
+                    .filter(|(_, &g)| g == detached)
+                    .map(|((name, _), _)| name.clone())
+                    .collect(),
+            });
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(scale: f64, transform: Transform) -> OutputConfig {
+        OutputConfig {
+            scale,
+            transform,
+            ..Default::default()
+        }
+    }
+
+    const TWO_MONITORS: [(u32, u32); 2] = [(2560, 1440), (1920, 1080)];
+
+    #[test]
+    fn test_side_by_side_preset() {
+        assert_eq!(
+            ArrangementPreset::SideBySide.positions(&TWO_MONITORS),
+            Some(vec![(0, 0), (2560, 0)])
+        );
+    }
+
+    #[test]
+    fn test_stacked_preset_centers_narrower_output() {
+        assert_eq!(
+            ArrangementPreset::Stacked.positions(&TWO_MONITORS),
+            Some(vec![(0, 0), (320, 1440)])
+        );
+    }
+
+    #[test]
+    fn test_secondary_above_preset() {
+        // The smaller secondary sits centered on top of the primary
+        assert_eq!(
+            ArrangementPreset::PrimaryBelowSecondary.positions(&TWO_MONITORS),
+            Some(vec![(0, 1080), (320, 0)])
+        );
+    }
+
+    #[test]
+    fn test_presets_leave_single_output_alone() {
+        for preset in ArrangementPreset::ALL {
+            assert_eq!(preset.positions(&[(1920, 1080)]), None);
+            assert_eq!(preset.positions(&[]), None);
+        }
+    }
+
+    #[test]
+    fn test_logical_size_normal() {
+        assert_eq!(
+            output(1.0, Transform::Normal).logical_size(2560, 1440),
+            (2560, 1440)
+        );
+        assert_eq!(
+            output(2.0, Transform::Normal).logical_size(2560, 1440),
+            (1280, 720)
+        );
+    }
+
+    #[test]
+    fn test_logical_size_rotated_swaps_dimensions() {
+        assert_eq!(
+            output(1.0, Transform::Rotate90).logical_size(1920, 1080),
+            (1080, 1920)
+        );
+        assert_eq!(
+            output(1.5, Transform::Rotate270).logical_size(3840, 2160),
+            (1440, 2560)
+        );
+    }
+
+    #[test]
+    fn test_logical_size_flipped_90_swaps_dimensions() {
+        assert_eq!(
+            output(1.0, Transform::Flipped90).logical_size(1920, 1080),
+            (1080, 1920)
+        );
+        assert_eq!(
+            output(1.0, Transform::Flipped).logical_size(1920, 1080),
+            (1920, 1080)
+        );
+    }
+
+    #[test]
+    fn test_logical_size_ignores_invalid_scale() {
+        assert_eq!(
+            output(0.0, Transform::Normal).logical_size(1920, 1080),
+            (1920, 1080)
+        );
+    }
+
+    #[test]
+    fn test_is_fractional_scale() {
+        assert!(is_fractional_scale(1.5));
+        assert!(is_fractional_scale(1.25));
+        assert!(!is_fractional_scale(1.0));
+        assert!(!is_fractional_scale(2.0));
+        // Float noise from the slider isn't fractional
+        assert!(!is_fractional_scale(2.0000001));
+    }
+
+    #[test]
+    fn test_round_scale_directions() {
+        assert_eq!(round_scale(1.5, ScaleRounding::Nearest), 2.0);
+        assert_eq!(round_scale(1.25, ScaleRounding::Nearest), 1.0);
+        assert_eq!(round_scale(1.5, ScaleRounding::Up), 2.0);
+        assert_eq!(round_scale(1.5, ScaleRounding::Down), 1.0);
+        assert_eq!(round_scale(2.75, ScaleRounding::Down), 2.0);
+    }
+
+    #[test]
+    fn test_round_scale_never_below_one() {
+        assert_eq!(round_scale(0.5, ScaleRounding::Down), 1.0);
+        assert_eq!(round_scale(0.75, ScaleRounding::Nearest), 1.0);
+    }
+
+    #[test]
+    fn test_set_startup_focus_clears_other_outputs() {
+        let mut settings = OutputSettings {
+            outputs: vec![
+                OutputConfig {
+                    focus_at_startup: true,
+                    ..Default::default()
+                },
+                OutputConfig::default(),
+                OutputConfig {
+                    focus_at_startup: true,
+                    ..Default::default()
+                },
+            ],
+        };
+
+        settings.set_startup_focus(Some(1));
+        let flags: Vec<bool> = settings
+            .outputs
+            .iter()
+            .map(|o| o.focus_at_startup)
+            .collect();
+        assert_eq!(flags, [false, true, false]);
+        assert_eq!(settings.startup_focus(), Some(1));
+
+        settings.set_startup_focus(None);
+        assert!(settings.outputs.iter().all(|o| !o.focus_at_startup));
+        assert_eq!(settings.startup_focus(), None);
+    }
+
+    fn placed(name: &str, mode: &str, x: i32, y: i32) -> OutputConfig {
+        let mut output = OutputConfig {
+            name: name.to_string(),
+            mode: mode.to_string(),
+            ..Default::default()
+        };
+        output.set_position(x, y);
+        output
+    }
+
+    #[test]
+    fn test_side_by_side_layout_has_no_issues() {
+        let settings = OutputSettings {
+            outputs: vec![
+                placed("DP-1", "2560x1440@143.912", 0, 0),
+                placed("HDMI-A-1", "1920x1080@60.000", 2560, 360),
+            ],
+        };
+        assert_eq!(settings.layout_issues(), vec![]);
+    }
+
+    #[test]
+    fn test_overlapping_outputs_are_reported() {
+        let mut settings = OutputSettings {
+            outputs: vec![
+                placed("DP-1", "2560x1440@143.912", 0, 0),
+                placed("HDMI-A-1", "1920x1080@60.000", 1920, 0),
+            ],
+        };
+        assert_eq!(
+            settings.layout_issues(),
+            vec![LayoutIssue::Overlap {
+                first: "DP-1".to_string(),
+                second: "HDMI-A-1".to_string(),
+            }]
+        );
+
+        // At scale 2 the first output is only 1280 wide, leaving a gap
+        settings.outputs[0].scale = 2.0;
+        assert_eq!(
+            settings.layout_issues(),
+            vec![LayoutIssue::Detached {
+                outputs: vec!["HDMI-A-1".to_string()],
+            }]
+        );
+
+        // A disabled output isn't in the way
+        settings.outputs[0].enabled = false;
+        assert_eq!(settings.layout_issues(), vec![]);
+    }
+
+    const FULL_HD: (u32, u32) = (1920, 1080);
+
+    fn rect(x: i32, y: i32, (width, height): (u32, u32)) -> OutputRect {
+        OutputRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_dragged_output_snaps_flush_against_neighbour() {
+        let others = [rect(0, 0, FULL_HD)];
+        // Dropped slightly overlapping the right edge, a little below the top
+        assert_eq!(rect(1890, 25, FULL_HD).snapped(&others), (1920, 0));
+        // Dropped with a small gap below it, a little to the left
+        assert_eq!(rect(-30, 1110, FULL_HD).snapped(&others), (0, 1080));
+        // Right edge lined up with the other's left edge
+        assert_eq!(rect(-2580, 0, (2560, 1440)).snapped(&others), (-2560, 0));
+    }
+
+    #[test]
+    fn test_dragged_output_out_of_range_rounds_to_grid() {
+        let others = [rect(0, 0, FULL_HD)];
+        assert_eq!(rect(2004, 333, FULL_HD).snapped(&others), (2000, 330));
+        assert_eq!(rect(-2004, -7, FULL_HD).snapped(&[]), (-2000, -10));
+    }
+
+    #[test]
+    fn test_dragged_output_ignores_distant_outputs_edges() {
+        // The right output's left edge lines up with x=1920, but it's far
+        // below the dragged one, so only the grid applies
+        let others = [rect(1900, 5000, FULL_HD)];
+        assert_eq!(rect(1913, 0, FULL_HD).snapped(&others), (1910, 0));
+    }
+
+    #[test]
+    fn test_outputs_without_a_position_are_left_out() {
+        // Neither has a position written, so niri places both itself
+        let unplaced = |name: &str, mode: &str| OutputConfig {
+            name: name.to_string(),
+            mode: mode.to_string(),
+            ..Default::default()
+        };
+        let settings = OutputSettings {
+            outputs: vec![
+                unplaced("DP-1", "2560x1440"),
+                unplaced("HDMI-A-1", "1920x1080"),
+            ],
+        };
+        assert_eq!(settings.layout_issues(), vec![]);
+    }
+
+    #[test]
+    fn test_outputs_touching_at_a_corner_are_detached() {
+        let settings = OutputSettings {
+            outputs: vec![
+                placed("DP-1", "1920x1080", 0, 0),
+                placed("DP-2", "1920x1080", 1920, 1080),
+            ],
+        };
+        assert_eq!(
+            settings.layout_issues(),
+            vec![LayoutIssue::Detached {
+                outputs: vec!["DP-2".to_string()],
+            }]
+        );
+    }
+}
@@ -2,6 +2,47 @@
 //!
 //! Settings that are specific to this application, not part of niri's configuration.
 
+use std::time::Duration;
+
+use crate::constants::DEFAULT_BACKUP_RETENTION;
+
+/// Which backups are kept when old ones are cleaned up
+///
+/// Snapshots taken by hand with a label are always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupRetention {
+    /// The newest this many backups (files saved together count once)
+    Count(usize),
+    /// Backups younger than this
+    Age(Duration),
+    /// Every backup
+    Unlimited,
+}
+
+impl Default for BackupRetention {
+    fn default() -> Self {
+        Self::Count(DEFAULT_BACKUP_RETENTION)
+    }
+}
+
+impl BackupRetention {
+    /// Seconds in a day, the unit ages are set and stored in
+    pub const DAY_SECS: u64 = 24 * 60 * 60;
+
+    /// `Age` for a whole number of days
+    pub fn days(days: u64) -> Self {
+        Self::Age(Duration::from_secs(days * Self::DAY_SECS))
+    }
+
+    /// Whole days kept by an `Age` policy, rounded up
+    pub fn age_days(&self) -> Option<u64> {
+        match self {
+            Self::Age(age) => Some(age.as_secs().div_ceil(Self::DAY_SECS)),
+            _ => None,
+        }
+    }
+}
+
 /// Application preferences
 #[derive(Debug, Clone, PartialEq)]
 pub struct PreferencesSettings {
@@ -13,6 +54,21 @@ pub struct PreferencesSettings {
     pub show_search_bar: bool,
     /// Keyboard shortcut for opening search (e.g., "Ctrl+K", "Ctrl+/", or empty to disable)
     pub search_hotkey: String,
+    /// Starred setting names (from the search index) shown on the Favorites screen
+    pub favorites: Vec<String>,
+    /// Write every keyboard, mouse, touchpad, trackpoint and trackball value
+    /// even when it matches niri's default, instead of only the changed ones.
+    /// Other generated files come out the same either way, some with default
+    /// values in them (appearance always writes its gaps, for one)
+    pub emit_all_defaults: bool,
+    /// Which backups to keep; the rest are deleted on startup
+    pub backup_retention: BackupRetention,
+    /// Keep the border colors in sync with the focus ring colors
+    pub link_border_colors: bool,
+    /// Take the app's accent color from the niri focus ring color
+    pub match_niri_colors: bool,
+    /// Reload niri after each save; when off, saves only write the files
+    pub auto_reload: bool,
 }
 
 impl Default for PreferencesSettings {
@@ -22,6 +78,28 @@ impl Default for PreferencesSettings {
             float_settings_app: true, // Float by default
             show_search_bar: true,    // Show search bar by default
             search_hotkey: "Ctrl+K".to_string(),
+            favorites: Vec::new(),
+            emit_all_defaults: false,
+            backup_retention: BackupRetention::default(),
+            link_border_colors: false,
+            match_niri_colors: false,
+            auto_reload: true,
+        }
+    }
+}
+
+impl PreferencesSettings {
+    /// Whether the named setting is starred
+    pub fn is_favorite(&self, setting_name: &str) -> bool {
+        self.favorites.iter().any(|f| f == setting_name)
+    }
+
+    /// Stars or unstars the named setting
+    pub fn toggle_favorite(&mut self, setting_name: &str) {
+        if self.is_favorite(setting_name) {
+            self.favorites.retain(|f| f != setting_name);
+        } else {
+            self.favorites.push(setting_name.to_string());
         }
     }
 }
@@ -170,17 +170,7 @@ impl std::fmt::Display for PositionRelativeTo {
 
 impl PositionRelativeTo {
     pub fn all() -> &'static [Self] {
-        &[
-            Self::TopLeft,
-            Self::TopRight,
-            Self::BottomLeft,
-            Self::BottomRight,
-            Self::Top,
-            Self::Bottom,
-            Self::Left,
-            Self::Right,
-            Self::Center,
-        ]
+        Self::ALL
     }
 
     /// Convert to KDL string
@@ -258,6 +248,8 @@ pub struct WindowRule {
     pub default_floating_position: Option<FloatingPosition>,
     /// Default column width proportion (0.0-1.0)
     pub default_column_width: Option<f32>,
+    /// Default column width in logical pixels; used instead of the proportion when set
+    pub default_column_width_fixed: Option<i32>,
 
     // New opening properties (v25.01+)
     /// Default window height proportion (0.0-1.0, None = auto)
@@ -334,6 +326,7 @@ impl Default for WindowRule {
             open_on_workspace: None,
             default_floating_position: None,
             default_column_width: None,
+            default_column_width_fixed: None,
             // New opening properties
             default_window_height: None,
             open_maximized_to_edges: None,
@@ -366,6 +359,46 @@ impl Default for WindowRule {
     }
 }
 
+impl WindowRule {
+    /// What the rule matches, in a few words for its card
+    pub fn match_summary(&self) -> String {
+        match self.matches.as_slice() {
+            [only] => match (&only.app_id, &only.title) {
+                (Some(app_id), _) => format!("app-id: {}", app_id),
+                (None, Some(title)) => format!("title: {}", title),
+                (None, None) => "any window".to_string(),
+            },
+            [] => "any window".to_string(),
+            all => format!("matches: {} conditions", all.len()),
+        }
+    }
+
+    /// Removes the match condition at `idx`
+    ///
+    /// Removing the last one leaves no conditions, so the rule applies to
+    /// every window.
+    pub fn remove_match(&mut self, idx: usize) {
+        if idx < self.matches.len() {
+            self.matches.remove(idx);
+        }
+    }
+}
+
+/// Name of the window rule managed by the screencast privacy tool
+pub const SCREENCAST_BLOCK_RULE_NAME: &str = "Screencast privacy";
+
+/// App-ids suggested for blocking from screencasts (password managers, messengers)
+pub const DEFAULT_SCREENCAST_BLOCK_APP_IDS: &[&str] = &[
+    "org.keepassxc.KeePassXC",
+    "1Password",
+    "Bitwarden",
+    "org.gnome.World.Secrets",
+    "signal",
+    "org.telegram.desktop",
+    "Element",
+    "discord",
+];
+
 /// Window rules settings
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct WindowRulesSettings {
@@ -391,4 +424,164 @@ impl WindowRulesSettings {
         self.rules.retain(|r| r.id != id);
         self.rules.len() < len_before
     }
+
+    /// The rule managed by the screencast privacy tool, if it exists
+    pub fn screencast_block_rule(&self) -> Option<&WindowRule> {
+        self.rules
+            .iter()
+            .find(|r| r.name == SCREENCAST_BLOCK_RULE_NAME)
+    }
+
+    /// Blocks exactly `app_ids` from screencasts through the screencast privacy rule
+    ///
+    /// The rule is created (or re-enabled) as needed. Each app-id gets an
+    /// exact-match pattern, and app-id matches no longer in the list are
+    /// dropped; other matches added on the rules page are kept. An empty list
+    /// leaves everything as it is, since a rule without matches would apply
+    /// to every window. Returns whether anything changed.
+    pub fn sync_screencast_block(&mut self, app_ids: &[String]) -> bool {
+        let patterns: Vec<String> = app_ids
+            .iter()
+            .map(|app_id| app_id.trim())
+            .filter(|app_id| !app_id.is_empty())
+            .map(|app_id| format!("^{}$", regex_syntax::escape(app_id)))
+            .collect();
+        if patterns.is_empty() {
+            return false;
+        }
+
+        let idx = match self
+            .rules
+            .iter()
+            .position(|r| r.name == SCREENCAST_BLOCK_RULE_NAME)
+        {
+            Some(idx) => idx,
+            None => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.rules.push(WindowRule {
+                    id,
+                    name: SCREENCAST_BLOCK_RULE_NAME.to_string(),
+                    matches: Vec::new(),
+                    ..Default::default()
+                });
+                self.rules.len() - 1
+            }
+        };
+        let rule = &mut self.rules[idx];
+        let before = rule.clone();
+        rule.enabled = true;
+        rule.block_out_from_screencast = true;
+
+        // Only plain app-id matches are the tool's own
+        rule.matches.retain(|m| {
+            let plain = WindowRuleMatch {
+                app_id: m.app_id.clone(),
+                ..Default::default()
+            };
+            *m != plain
+                || m.app_id
+                    .as_ref()
+                    .is_some_and(|app_id| patterns.contains(app_id))
+        });
+        for pattern in patterns {
+            if !rule
+                .matches
+                .iter()
+                .any(|m| m.app_id.as_deref() == Some(pattern.as_str()))
+            {
+                rule.matches.push(WindowRuleMatch {
+                    app_id: Some(pattern),
+                    ..Default::default()
+                });
+            }
+        }
+        *rule != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_screencast_block_is_idempotent() {
+        let mut settings = WindowRulesSettings::default();
+        let app_ids = vec![
+            "org.keepassxc.KeePassXC".to_string(),
+            " signal ".to_string(),
+        ];
+
+        assert!(settings.sync_screencast_block(&app_ids));
+        assert!(!settings.sync_screencast_block(&app_ids));
+
+        assert_eq!(settings.rules.len(), 1);
+        let rule = settings.screencast_block_rule().unwrap();
+        assert!(rule.block_out_from_screencast);
+        let patterns: Vec<_> = rule
+            .matches
+            .iter()
+            .filter_map(|m| m.app_id.as_deref())
+            .collect();
+        assert_eq!(patterns, [r"^org\.keepassxc\.KeePassXC$", "^signal$"]);
+
+        // Turning it back on re-enables the same rule
+        settings.rules[0].enabled = false;
+        assert!(settings.sync_screencast_block(&app_ids));
+        assert_eq!(settings.rules.len(), 1);
+        assert!(settings.rules[0].enabled);
+    }
+
+    #[test]
+    fn test_sync_screencast_block_drops_removed_app_ids() {
+        let mut settings = WindowRulesSettings::default();
+        settings.sync_screencast_block(&["signal".to_string(), "discord".to_string()]);
+        // A title match added on the rules page isn't the tool's to remove
+        settings.rules[0].matches.push(WindowRuleMatch {
+            title: Some("Private".to_string()),
+            ..Default::default()
+        });
+
+        assert!(settings.sync_screencast_block(&["discord".to_string()]));
+
+        let rule = settings.screencast_block_rule().unwrap();
+        assert_eq!(rule.matches.len(), 2);
+        assert_eq!(rule.matches[0].app_id.as_deref(), Some("^discord$"));
+        assert_eq!(rule.matches[1].title.as_deref(), Some("Private"));
+    }
+
+    #[test]
+    fn test_removing_matches_down_to_none() {
+        let app = |id: &str| WindowRuleMatch {
+            app_id: Some(id.to_string()),
+            ..Default::default()
+        };
+        let mut rule = WindowRule {
+            matches: vec![app("^firefox$"), app("^chromium$")],
+            ..Default::default()
+        };
+        assert_eq!(rule.match_summary(), "matches: 2 conditions");
+
+        rule.remove_match(0);
+        assert_eq!(rule.match_summary(), "app-id: ^chromium$");
+        rule.remove_match(5);
+        assert_eq!(rule.matches.len(), 1);
+
+        rule.remove_match(0);
+        assert!(rule.matches.is_empty());
+        assert_eq!(rule.match_summary(), "any window");
+    }
+
+    #[test]
+    fn test_sync_screencast_block_without_app_ids_changes_nothing() {
+        let mut settings = WindowRulesSettings::default();
+        assert!(!settings.sync_screencast_block(&[" ".to_string()]));
+        assert!(settings.rules.is_empty());
+
+        // Nor does it turn an existing rule back on
+        settings.sync_screencast_block(&["signal".to_string()]);
+        settings.rules[0].enabled = false;
+        assert!(!settings.sync_screencast_block(&[]));
+        assert!(!settings.rules[0].enabled);
+    }
 }
@@ -27,6 +27,9 @@ impl Default for NamedWorkspace {
 }
 
 /// Named workspaces settings
+///
+/// niri has no global default output for workspaces: unnamed workspaces are
+/// created on whichever output needs one, so `open-on-output` is per workspace.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct WorkspacesSettings {
     pub workspaces: Vec<NamedWorkspace>,
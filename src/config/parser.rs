@@ -58,22 +58,26 @@ pub fn get_f64(doc: &KdlDocument, path: &[&str]) -> Option<f64> {
 /// - `tap false` → false
 /// - (no node) → false
 pub fn has_flag(doc: &KdlDocument, path: &[&str]) -> bool {
-    match navigate_to_node(doc, path) {
-        None => false,
-        Some(node) => {
-            // Check if there's an explicit boolean value
-            if let Some(entry) = node.entries().first() {
-                if entry.name().is_none() {
-                    // Positional argument - check if it's a boolean
-                    if let Some(b) = entry.value().as_bool() {
-                        return b;
-                    }
-                }
+    get_bool(doc, path).unwrap_or(false)
+}
+
+/// Get a boolean option, telling an absent node apart from an explicit false
+///
+/// Read like [`has_flag`], except that a missing node gives `None`. Rule
+/// options need this, as `open-floating false` overrides an earlier rule.
+pub fn get_bool(doc: &KdlDocument, path: &[&str]) -> Option<bool> {
+    let node = navigate_to_node(doc, path)?;
+    // Check if there's an explicit boolean value
+    if let Some(entry) = node.entries().first() {
+        if entry.name().is_none() {
+            // Positional argument - check if it's a boolean
+            if let Some(b) = entry.value().as_bool() {
+                return Some(b);
             }
-            // No explicit false, presence means enabled
-            true
         }
     }
+    // No explicit false, presence means enabled
+    Some(true)
 }
 
 #[cfg(test)]
@@ -134,6 +138,21 @@ mod tests {
         assert!(has_flag(touchpad, &["natural-scroll"])); // Explicitly true
     }
 
+    #[test]
+    fn test_get_bool_keeps_explicit_false() {
+        let content = r#"
+            window-rule {
+                open-maximized-to-edges false
+                draw-border-with-background
+            }
+        "#;
+        let doc = parse_document(content).unwrap();
+        let rule = doc.get("window-rule").unwrap().children().unwrap();
+        assert_eq!(get_bool(rule, &["open-maximized-to-edges"]), Some(false));
+        assert_eq!(get_bool(rule, &["draw-border-with-background"]), Some(true));
+        assert_eq!(get_bool(rule, &["baba-is-float"]), None);
+    }
+
     #[test]
     fn test_has_flag_with_other_values() {
         // Flags with non-boolean values should return true (presence)
@@ -1,9 +1,19 @@
+use super::dirty::SettingsCategory;
 use super::error::ConfigError;
+use super::models::BackupRetention;
 use super::registry::ConfigFile;
 use super::storage::atomic_write;
-use crate::constants::CONFIG_DIR_NAME;
+use crate::constants::{CONFIG_DIR_ENV, CONFIG_DIR_NAME};
 use chrono::Local;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// The niri config directory: [`CONFIG_DIR_ENV`] if set, else `~/.config/niri`
+pub fn niri_config_dir() -> Option<PathBuf> {
+    match std::env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => dirs::config_dir().map(|dir| dir.join("niri")),
+    }
+}
 
 /// Holds all paths for config files
 #[derive(Debug, Clone)]
@@ -65,10 +75,19 @@ pub struct ConfigPaths {
 
 impl ConfigPaths {
     /// Create new ConfigPaths based on XDG config directory
+    ///
+    /// A non-empty [`CONFIG_DIR_ENV`] (set by `--config-dir` too) replaces
+    /// `~/.config/niri`, so a test or alternate config never touches the real one.
     pub fn new() -> Result<Self, ConfigError> {
-        let config_dir = dirs::config_dir().ok_or(ConfigError::ConfigDirNotFound)?;
+        niri_config_dir()
+            .map(Self::with_niri_dir)
+            .ok_or(ConfigError::ConfigDirNotFound)
+    }
 
-        let niri_dir = config_dir.join("niri");
+    /// Create ConfigPaths with every path under `niri_dir` in place of
+    /// `~/.config/niri`
+    pub fn with_niri_dir(niri_dir: impl Into<PathBuf>) -> Self {
+        let niri_dir = niri_dir.into();
         let managed_dir = niri_dir.join(CONFIG_DIR_NAME);
         let input_dir = managed_dir.join("input");
         let advanced_dir = managed_dir.join("advanced");
@@ -106,7 +125,7 @@ impl ConfigPaths {
         let recent_windows_kdl = advanced_dir.join("recent-windows.kdl");
         let preferences_kdl = advanced_dir.join("preferences.kdl");
 
-        Ok(Self {
+        Self {
             niri_config: niri_dir.join("config.kdl"),
             managed_dir,
             input_dir,
@@ -151,7 +170,7 @@ impl ConfigPaths {
             switch_events_kdl,
             recent_windows_kdl,
             preferences_kdl,
-        })
+        }
     }
 
     /// Create all necessary directories if they don't exist
@@ -181,9 +200,43 @@ impl ConfigPaths {
         Ok(())
     }
 
-    /// Check if this is the first run (our directory doesn't exist)
+    /// Check that our directories exist and can be written to
+    ///
+    /// Creates them if missing, then writes and removes a probe file in each,
+    /// so a read-only mount or bad permissions show up at startup instead of
+    /// on the first save. The error names the directory and the OS reason.
+    pub fn check_writable(&self) -> Result<(), String> {
+        self.ensure_directories().map_err(|e| e.to_string())?;
+
+        for dir in [
+            &self.managed_dir,
+            &self.input_dir,
+            &self.advanced_dir,
+            &self.backup_dir,
+        ] {
+            let probe = dir.join(".nirify-write-check");
+            std::fs::write(&probe, b"")
+                .and_then(|_| std::fs::remove_file(&probe))
+                .map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Check if any managed settings file exists
+    pub fn has_managed_files(&self) -> bool {
+        ConfigFile::ALL
+            .iter()
+            .any(|file| self.path_for(*file).exists())
+    }
+
+    /// Check if this is the first run
+    ///
+    /// Setup writes the managed files and adds the include line, so either one
+    /// means it already ran. A `config.kdl` that still includes our files after
+    /// the managed dir was lost needs its files recreated, not the wizard: that
+    /// would treat the stripped `config.kdl` as the user's whole config.
     pub fn is_first_run(&self) -> bool {
-        !self.managed_dir.exists()
+        !self.has_managed_files() && !self.has_include_line()
     }
 
     /// Get the full path for a config file using the registry
@@ -202,6 +255,32 @@ impl ConfigPaths {
         ConfigFile::from_file_name(file_name).map(|f| self.path_for(f))
     }
 
+    /// Settings category whose file is at `path`, if it's one we manage
+    ///
+    /// Relative paths are taken from the niri config directory, the way niri
+    /// resolves includes.
+    pub fn category_for_path(&self, path: &Path) -> Option<SettingsCategory> {
+        let path = match self.niri_config.parent() {
+            Some(niri_dir) if path.is_relative() => niri_dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        ConfigFile::ALL
+            .iter()
+            .find(|f| self.path_for(**f) == path)
+            .map(ConfigFile::category)
+    }
+
+    /// Path of the file `category` is saved to
+    pub fn category_path(&self, category: SettingsCategory) -> Option<PathBuf> {
+        if category == SettingsCategory::Preferences {
+            return Some(self.preferences_kdl.clone());
+        }
+        ConfigFile::ALL
+            .iter()
+            .find(|f| f.category() == category)
+            .map(|f| self.path_for(*f))
+    }
+
     /// Check if the user's config.kdl contains our include line
     ///
     /// Detects both the current relative path format (`nirify/main.kdl`) and
@@ -377,41 +456,33 @@ impl ConfigPaths {
         Ok(())
     }
 
-    /// Clean up old backups, keeping only the most recent N backups
+    /// Deletes the backups `policy` doesn't keep
     ///
-    /// This prevents backup directory from growing indefinitely.
-    /// Defaults to keeping the 10 most recent backups.
-    pub fn cleanup_old_backups(&self, keep_count: usize) -> Result<usize, ConfigError> {
+    /// This prevents backup directory from growing indefinitely. Backups are
+    /// dated by the timestamp in their names; see
+    /// [`backup::expired_backups`](super::backup::expired_backups).
+    pub fn cleanup_old_backups(&self, policy: BackupRetention) -> Result<usize, ConfigError> {
         use std::fs;
 
         if !self.backup_dir.exists() {
             return Ok(0);
         }
 
-        // Collect all backup files with their modification times
-        let mut backups: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
-
-        let entries = fs::read_dir(&self.backup_dir)?;
-        for entry in entries.flatten() {
+        let mut filenames = Vec::new();
+        for entry in fs::read_dir(&self.backup_dir)?.flatten() {
             let path = entry.path();
-            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                // Only consider backup files
-                if filename.starts_with("config.kdl.backup") {
-                    if let Ok(metadata) = fs::metadata(&path) {
-                        if let Ok(modified) = metadata.modified() {
-                            backups.push((path, modified));
-                        }
-                    }
+            if path.is_file() {
+                if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                    filenames.push(filename.to_string());
                 }
             }
         }
+        let filenames: Vec<&str> = filenames.iter().map(String::as_str).collect();
+        let now = Local::now().naive_local();
 
-        // Sort by modification time (newest first)
-        backups.sort_by(|a, b| b.1.cmp(&a.1));
-
-        // Delete backups beyond the keep count
         let mut deleted = 0;
-        for (path, _) in backups.into_iter().skip(keep_count) {
+        for filename in super::backup::expired_backups(&filenames, policy, now) {
+            let path = self.backup_dir.join(filename);
             if let Err(e) = fs::remove_file(&path) {
                 log::warn!("Failed to delete old backup {:?}: {}", path, e);
             } else {
@@ -500,6 +571,47 @@ mod tests {
         assert!(paths.keyboard_kdl.ends_with("keyboard.kdl"));
     }
 
+    #[test]
+    fn test_overridden_niri_dir_contains_every_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+
+        assert_eq!(paths.niri_config, dir.path().join("config.kdl"));
+        for path in [
+            &paths.managed_dir,
+            &paths.input_dir,
+            &paths.advanced_dir,
+            &paths.backup_dir,
+            &paths.main_kdl,
+            &paths.keyboard_kdl,
+            &paths.window_rules_kdl,
+        ] {
+            assert!(path.starts_with(dir.path()), "{}", path.display());
+        }
+        for file in ConfigFile::ALL {
+            let path = paths.path_for(*file);
+            assert!(path.starts_with(&paths.managed_dir), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_category_for_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+
+        assert_eq!(
+            paths.category_for_path(&paths.keyboard_kdl),
+            Some(SettingsCategory::Keyboard)
+        );
+        // niri names included files relative to its config directory
+        assert_eq!(
+            paths.category_for_path(Path::new("nirify/advanced/misc.kdl")),
+            Some(SettingsCategory::Miscellaneous)
+        );
+        assert_eq!(paths.category_for_path(&paths.niri_config), None);
+        assert_eq!(paths.category_for_path(Path::new("outputs.kdl")), None);
+    }
+
     #[test]
     fn test_has_old_include_format_detects_old_path() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -608,4 +720,29 @@ another-node { baz 42 }
         // No backup should be created
         assert!(!paths.backup_dir.exists());
     }
+
+    #[test]
+    fn test_check_writable_creates_missing_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+
+        assert!(paths.check_writable().is_ok());
+        assert!(paths.managed_dir.is_dir());
+        assert!(paths.backup_dir.is_dir());
+        // The probe file is cleaned up
+        assert!(!paths.managed_dir.join(".nirify-write-check").exists());
+    }
+
+    #[test]
+    fn test_check_writable_reports_unwritable_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+
+        // A file where the backup dir should be can't be written into, even
+        // by root, unlike a read-only permission bit
+        std::fs::write(&paths.backup_dir, "").unwrap();
+
+        let error = paths.check_writable().unwrap_err();
+        assert!(error.contains(".nirify-backups"), "{}", error);
+    }
 }
@@ -6,6 +6,8 @@
 
 use std::path::{Path, PathBuf};
 
+use super::SettingsCategory;
+
 /// All config file types managed by Nirify
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConfigFile {
@@ -179,6 +181,37 @@ impl ConfigFile {
         managed_dir.join(self.relative_path())
     }
 
+    /// Settings category saved to this file
+    pub fn category(&self) -> SettingsCategory {
+        match self {
+            Self::Appearance => SettingsCategory::Appearance,
+            Self::Behavior => SettingsCategory::Behavior,
+            Self::Keyboard => SettingsCategory::Keyboard,
+            Self::Mouse => SettingsCategory::Mouse,
+            Self::Touchpad => SettingsCategory::Touchpad,
+            Self::Trackpoint => SettingsCategory::Trackpoint,
+            Self::Trackball => SettingsCategory::Trackball,
+            Self::Tablet => SettingsCategory::Tablet,
+            Self::Touch => SettingsCategory::Touch,
+            Self::Outputs => SettingsCategory::Outputs,
+            Self::Animations => SettingsCategory::Animations,
+            Self::Cursor => SettingsCategory::Cursor,
+            Self::Overview => SettingsCategory::Overview,
+            Self::Workspaces => SettingsCategory::Workspaces,
+            Self::Keybindings => SettingsCategory::Keybindings,
+            Self::LayoutExtras => SettingsCategory::LayoutExtras,
+            Self::Gestures => SettingsCategory::Gestures,
+            Self::LayerRules => SettingsCategory::LayerRules,
+            Self::WindowRules => SettingsCategory::WindowRules,
+            Self::Misc => SettingsCategory::Miscellaneous,
+            Self::Startup => SettingsCategory::Startup,
+            Self::Environment => SettingsCategory::Environment,
+            Self::Debug => SettingsCategory::Debug,
+            Self::SwitchEvents => SettingsCategory::SwitchEvents,
+            Self::RecentWindows => SettingsCategory::RecentWindows,
+        }
+    }
+
     /// Look up a ConfigFile by its file name
     pub fn from_file_name(name: &str) -> Option<Self> {
         Self::ALL.iter().find(|f| f.file_name() == name).copied()
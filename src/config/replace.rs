@@ -46,6 +46,31 @@ pub struct ConflictingInclude {
     pub resolved_path: PathBuf,
     /// Names of top-level managed nodes found in the included file.
     pub conflicting_nodes: Vec<String>,
+    /// Whether every top-level node of the file is managed, so importing it
+    /// into Nirify leaves nothing behind.
+    pub only_managed: bool,
+    /// Whether import reads the file; it only follows includes inside niri's
+    /// config directory, so settings in files elsewhere aren't imported.
+    pub imported: bool,
+}
+
+impl ConflictingInclude {
+    /// Whether importing this include into Nirify covers all of it, so its
+    /// include line can go
+    pub fn fully_imported(&self) -> bool {
+        self.only_managed && self.imported
+    }
+}
+
+/// What setup does with `include` directives that aren't Nirify's
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IncludeHandling {
+    /// Keep them, with Nirify's include placed after them
+    #[default]
+    Keep,
+    /// Drop the ones holding only managed sections; their settings were
+    /// imported already. The files themselves are left alone.
+    Import,
 }
 
 /// Result of analyzing a config.kdl file
@@ -100,6 +125,35 @@ impl ConfigAnalysis {
         }
         false
     }
+
+    /// Reclassifies includes whose files hold only managed sections as
+    /// managed, so the rewrite drops them. Returns how many were dropped.
+    fn import_includes(&mut self) -> usize {
+        let mut imported = 0;
+        for (idx, classification) in &mut self.node_classifications {
+            if *classification != NodeClassification::OtherInclude {
+                continue;
+            }
+            let Some(value) = self.document.nodes()[*idx]
+                .entries()
+                .first()
+                .and_then(|e| e.value().as_string())
+            else {
+                continue;
+            };
+            if self
+                .conflicting_includes
+                .iter()
+                .any(|c| c.include_path == value && c.fully_imported())
+            {
+                *classification = NodeClassification::Managed;
+                imported += 1;
+            }
+        }
+        self.managed_count += imported;
+        self.unmanaged_count -= imported;
+        imported
+    }
 }
 
 /// Result of the smart replace operation
@@ -178,7 +232,7 @@ fn is_nirify_include(node: &KdlNode) -> bool {
 /// Returns `None` for tilde-prefixed paths because niri itself does not expand
 /// tildes in includes (see paths.rs::migrate_include_line); we don't want to
 /// scan a file niri can't actually load.
-fn resolve_include_path(include_value: &str, config_parent: &Path) -> Option<PathBuf> {
+pub(super) fn resolve_include_path(include_value: &str, config_parent: &Path) -> Option<PathBuf> {
     if include_value.starts_with("~/") || include_value == "~" {
         return None;
     }
@@ -220,6 +274,10 @@ fn scan_include_for_conflicts(
         }
     };
 
+    let only_managed = doc
+        .nodes()
+        .iter()
+        .all(|n| is_managed_node(n.name().value()));
     let mut seen = std::collections::HashSet::new();
     let conflicting_nodes: Vec<String> = doc
         .nodes()
@@ -241,10 +299,49 @@ fn scan_include_for_conflicts(
             include_path: include_value.to_string(),
             resolved_path: resolved,
             conflicting_nodes,
+            only_managed,
+            // config.kdl sits in niri's config directory
+            imported: super::loader::resolve_include_within(
+                include_value,
+                config_parent,
+                config_parent,
+            )
+            .is_some(),
         })
     }
 }
 
+/// Files that `config.kdl` includes, other than Nirify's own `main.kdl`
+///
+/// Paths are resolved against the config's directory, in the order the
+/// includes appear. Tilde paths are skipped, as niri can't load them either.
+/// An unreadable or invalid config has no includes.
+pub fn existing_includes(niri_config: &Path) -> Vec<PathBuf> {
+    let Some(document) = fs::read_to_string(niri_config)
+        .ok()
+        .and_then(|content| content.parse::<KdlDocument>().ok())
+    else {
+        return Vec::new();
+    };
+    let config_parent = niri_config.parent().unwrap_or(Path::new("."));
+
+    let mut includes: Vec<PathBuf> = Vec::new();
+    for node in document.nodes() {
+        if node.name().value() != "include" || is_nirify_include(node) {
+            continue;
+        }
+        let resolved = node
+            .entries()
+            .first()
+            .and_then(|e| e.value().as_string())
+            .and_then(|value| resolve_include_path(value, config_parent));
+        if let Some(path) = resolved.filter(|path| !includes.contains(path)) {
+            includes.push(path);
+        }
+    }
+    includes
+}
+
 /// Analyze a config.kdl file and classify its nodes
 ///
 /// Returns a ConfigAnalysis with node classifications for smart replacement.
@@ -430,6 +527,15 @@ fn extract_unmanaged_nodes(analysis: &ConfigAnalysis) -> String {
 ///
 /// Returns Ok(SmartReplaceResult) on success, describing what was done.
 pub fn smart_replace_config(config_path: &Path, backup_dir: &Path) -> Result<SmartReplaceResult> {
+    smart_replace_config_with(config_path, backup_dir, IncludeHandling::Keep)
+}
+
+/// [`smart_replace_config`], choosing what happens to the user's own includes
+pub fn smart_replace_config_with(
+    config_path: &Path,
+    backup_dir: &Path,
+    includes: IncludeHandling,
+) -> Result<SmartReplaceResult> {
     let mut warnings = Vec::new();
 
     // Handle non-existent config
@@ -450,7 +556,7 @@ pub fn smart_replace_config(config_path: &Path, backup_dir: &Path) -> Result<Sma
     }
 
     // Try to analyze existing config
-    let analysis = match analyze_config(config_path) {
+    let mut analysis = match analyze_config(config_path) {
         Ok(a) => a,
         Err(e) => {
             // Config exists but can't be parsed - create backup and replace with minimal
@@ -485,9 +591,18 @@ pub fn smart_replace_config(config_path: &Path, backup_dir: &Path) -> Result<Sma
         }
     };
 
+    if includes == IncludeHandling::Import {
+        let imported = analysis.import_includes();
+        info!("Importing {} include(s) into Nirify", imported);
+    }
+
     // Append conflict warnings to the result so callers can surface them even
     // if no rewrite is needed (e.g. a future "import these settings?" prompt).
-    for conflict in &analysis.conflicting_includes {
+    for conflict in analysis
+        .conflicting_includes
+        .iter()
+        .filter(|c| includes == IncludeHandling::Keep || !c.fully_imported())
+    {
         warnings.push(format!(
             "Include {:?} defines managed sections {:?}; Nirify's include is placed last so it wins",
             conflict.include_path, conflict.conflicting_nodes
@@ -791,6 +906,88 @@ include "nirify/main.kdl"
         );
     }
 
+    #[test]
+    fn test_existing_includes_lists_other_includes_in_order() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.kdl");
+        std::fs::write(
+            &config_path,
+            r#"
+include "cfg/layout.kdl"
+// include "cfg/commented.kdl"
+include "/etc/niri/shared.kdl"
+include "~/elsewhere.kdl"
+include "cfg/layout.kdl"
+include "nirify/main.kdl"
+include "cfg/binds.kdl"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            existing_includes(&config_path),
+            vec![
+                temp_dir.path().join("cfg/layout.kdl"),
+                PathBuf::from("/etc/niri/shared.kdl"),
+                temp_dir.path().join("cfg/binds.kdl"),
+            ]
+        );
+        assert!(existing_includes(&temp_dir.path().join("missing.kdl")).is_empty());
+    }
+
+    #[test]
+    fn test_import_drops_only_includes_holding_managed_sections() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.kdl");
+        std::fs::create_dir_all(temp_dir.path().join("cfg")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("cfg/layout.kdl"),
+            "layout {\n    gaps 16\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("cfg/mixed.kdl"),
+            "layout {\n    gaps 8\n}\nmy-custom-node \"value\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &config_path,
+            "include \"cfg/layout.kdl\"\ninclude \"cfg/mixed.kdl\"\n",
+        )
+        .unwrap();
+
+        let backup_dir = temp_dir.path().join(".nirify-backups");
+        let result =
+            smart_replace_config_with(&config_path, &backup_dir, IncludeHandling::Import).unwrap();
+        assert_eq!(result.replaced_count, 1);
+
+        let new_content = std::fs::read_to_string(&config_path).unwrap();
+        assert!(!new_content.contains("cfg/layout.kdl"));
+        assert!(new_content.contains("cfg/mixed.kdl"));
+        // The included files themselves are left alone
+        assert!(temp_dir.path().join("cfg/layout.kdl").exists());
+    }
+
+    #[test]
+    fn test_import_keeps_includes_it_cannot_read() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let outside = elsewhere.path().join("layout.kdl");
+        std::fs::write(&outside, "layout {\n    gaps 16\n}\n").unwrap();
+        let config_path = temp_dir.path().join("config.kdl");
+        std::fs::write(&config_path, format!("include \"{}\"\n", outside.display())).unwrap();
+
+        let backup_dir = temp_dir.path().join(".nirify-backups");
+        let result =
+            smart_replace_config_with(&config_path, &backup_dir, IncludeHandling::Import).unwrap();
+        assert_eq!(result.replaced_count, 0);
+        assert!(!result.conflicting_includes[0].imported);
+
+        // Import never read it, so its settings only live there
+        let new_content = std::fs::read_to_string(&config_path).unwrap();
+        assert!(new_content.contains(&*outside.to_string_lossy()));
+    }
+
     #[test]
     fn test_resolve_include_path_skips_tilde() {
         let parent = Path::new("/some/dir");
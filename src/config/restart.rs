@@ -0,0 +1,58 @@
+//! Options that niri only reads when it starts
+//!
+//! niri applies almost every setting on config reload. The few listed here are
+//! read once at startup (device setup, D-Bus, startup commands), so changing
+//! them seems to do nothing until niri is restarted.
+
+use super::SettingsCategory;
+
+/// Restart-only options, by category and KDL option name
+const RESTART_ONLY_OPTIONS: &[(SettingsCategory, &str)] = &[
+    (SettingsCategory::Debug, "render-drm-device"),
+    (SettingsCategory::Debug, "ignore-drm-device"),
+    (
+        SettingsCategory::Debug,
+        "dbus-interfaces-in-non-session-instances",
+    ),
+    (SettingsCategory::Startup, "spawn-at-startup"),
+    (SettingsCategory::Miscellaneous, "skip-at-startup"),
+];
+
+/// Whether a change to `field` (its KDL option name) in `category` only takes
+/// effect after niri restarts
+pub fn requires_restart(category: SettingsCategory, field: &str) -> bool {
+    RESTART_ONLY_OPTIONS
+        .iter()
+        .any(|&(c, f)| c == category && f == field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_only_options() {
+        assert!(requires_restart(
+            SettingsCategory::Debug,
+            "render-drm-device"
+        ));
+        assert!(requires_restart(
+            SettingsCategory::Startup,
+            "spawn-at-startup"
+        ));
+    }
+
+    #[test]
+    fn test_reload_safe_options() {
+        assert!(!requires_restart(
+            SettingsCategory::Debug,
+            "disable-direct-scanout"
+        ));
+        assert!(!requires_restart(SettingsCategory::Appearance, "gaps"));
+        // The name alone isn't enough; it has to be in the right category
+        assert!(!requires_restart(
+            SettingsCategory::Appearance,
+            "render-drm-device"
+        ));
+    }
+}
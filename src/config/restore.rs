@@ -0,0 +1,347 @@
+//! Restoring config.kdl from a backup, with rollback if niri rejects it
+//!
+//! A restore is one transaction: the current config is snapshotted (in memory
+//! and as a safety backup on disk), the backup is written, and niri is asked
+//! to reload. If niri reports a failure, the snapshot is written back and niri
+//! reloaded again, so a bad backup never stays in place.
+
+use std::path::{Path, PathBuf};
+
+use super::atomic_write;
+
+/// How a restore ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestoreOutcome {
+    /// The backup is in place and niri accepted it
+    Applied { safety_backup: Option<PathBuf> },
+    /// The backup is in place, but niri wasn't running to check it
+    Unverified { safety_backup: Option<PathBuf> },
+    /// niri rejected the backup, so the previous config was put back
+    RolledBack {
+        /// Why niri rejected the restored config
+        reload_error: String,
+        /// Set if niri also failed to reload the previous config
+        rollback_error: Option<String>,
+        safety_backup: Option<PathBuf>,
+    },
+}
+
+impl RestoreOutcome {
+    /// One-line description for the backups page
+    pub fn status_message(&self) -> String {
+        match self {
+            RestoreOutcome::Applied { .. } => {
+                "Backup restored and niri reloaded successfully".to_string()
+            }
+            RestoreOutcome::Unverified { .. } => {
+                "Backup restored (niri isn't running, so it couldn't be reloaded)".to_string()
+            }
+            RestoreOutcome::RolledBack {
+                reload_error,
+                rollback_error: None,
+                ..
+            } => format!(
+                "niri rejected the backup ({}), so your previous config was restored",
+                reload_error
+            ),
+            RestoreOutcome::RolledBack {
+                reload_error,
+                rollback_error: Some(rollback_error),
+                ..
+            } => format!(
+                "niri rejected the backup ({}); your previous config was put back, \
+                 but reloading it also failed: {}",
+                reload_error, rollback_error
+            ),
+        }
+    }
+
+    /// Whether the backup ended up as the current config
+    pub fn is_applied(&self) -> bool {
+        !matches!(self, RestoreOutcome::RolledBack { .. })
+    }
+}
+
+/// Restores `backup_path` over `config_path`, rolling back if niri rejects it
+///
+/// `reload` asks niri to reload its config and returns `None` when niri isn't
+/// running, in which case the restore is kept unverified. Errors are only
+/// returned when nothing was changed, or when the rollback itself couldn't be
+/// written (the message then points at the safety backup).
+pub fn restore_with_rollback(
+    backup_path: &Path,
+    config_path: &Path,
+    backup_dir: &Path,
+    reload: impl FnMut() -> Option<Result<(), String>>,
+) -> Result<RestoreOutcome, String> {
+    restore_set_with_rollback(
+        &[(backup_path.to_path_buf(), config_path.to_path_buf())],
+        backup_dir,
+        reload,
+    )
+}
+
+/// Restores each `(backup, target)` pair together, reloading niri once
+///
+/// Every backup is read and checked before anything is written. If a write
+/// fails or niri rejects the result, every target is put back as it was, so
+/// the files never end up from different points in time. The current files
+/// get safety backups sharing one timestamp; the outcome names the first.
+pub fn restore_set_with_rollback(
+    files: &[(PathBuf, PathBuf)],
+    backup_dir: &Path,
+    mut reload: impl FnMut() -> Option<Result<(), String>>,
+) -> Result<RestoreOutcome, String> {
+    let mut contents = Vec::with_capacity(files.len());
+    for (backup_path, _) in files {
+        // Read backup content first (validates it exists and is readable)
+        let content = std::fs::read_to_string(backup_path)
+            .map_err(|e| format!("Failed to read backup file: {}", e))?;
+
+        // Validate backup contains valid KDL before restoring
+        if let Err(e) = content.parse::<kdl::KdlDocument>() {
+            return Err(format!("Backup contains invalid KDL: {}", e));
+        }
+        contents.push(content);
+    }
+
+    let mut snapshots = Vec::with_capacity(files.len());
+    for (_, target) in files {
+        snapshots.push(if target.exists() {
+            Some(
+                std::fs::read_to_string(target)
+                    .map_err(|e| format!("Failed to read current config: {}", e))?,
+            )
+        } else {
+            None
+        });
+    }
+
+    // Use microsecond precision to avoid timestamp collisions
+    let timestamp = chrono::Local::now().format("%Y%m%dT%H%M%S%.6f").to_string();
+    let mut safety_backups = Vec::new();
+    for ((_, target), snapshot) in files.iter().zip(&snapshots) {
+        if let Some(content) = snapshot {
+            safety_backups.push(write_safety_backup(
+                content, target, &timestamp, backup_dir,
+            )?);
+        }
+    }
+    let safety_backup = safety_backups.into_iter().next();
+
+    // Write each file using atomic write (safe against crashes)
+    for (i, ((backup_path, target), content)) in files.iter().zip(&contents).enumerate() {
+        if let Err(e) = atomic_write(target, content) {
+            if let Err(put_back_error) = put_back(&files[..i], &snapshots[..i]) {
+                log::error!("Undoing a partial restore failed: {}", put_back_error);
+            }
+            return Err(format!("Failed to write config file: {}", e));
+        }
+        log::info!("Restored backup from: {}", backup_path.display());
+    }
+
+    let reload_error = match reload() {
+        Some(Ok(())) => return Ok(RestoreOutcome::Applied { safety_backup }),
+        None => return Ok(RestoreOutcome::Unverified { safety_backup }),
+        Some(Err(e)) => e,
+    };
+
+    log::warn!(
+        "niri rejected the restored config, rolling back: {}",
+        reload_error
+    );
+    if let Err(e) = put_back(files, &snapshots) {
+        return Err(format!(
+            "niri rejected the backup ({}) and rolling back failed: {}{}",
+            reload_error,
+            e,
+            safety_backup
+                .as_ref()
+                .map(|path| format!(". Your previous config is saved at {}", path.display()))
+                .unwrap_or_default()
+        ));
+    }
+
+    let rollback_error = reload().and_then(Result::err);
+    if let Some(e) = &rollback_error {
+        log::error!("Reloading the rolled-back config failed: {}", e);
+    }
+
+    Ok(RestoreOutcome::RolledBack {
+        reload_error,
+        rollback_error,
+        safety_backup,
+    })
+}
+
+/// Writes each target back as it was before the restore, removing the ones
+/// that didn't exist; every target is tried even after a failure
+fn put_back(files: &[(PathBuf, PathBuf)], snapshots: &[Option<String>]) -> Result<(), String> {
+    let mut first_error = None;
+    for ((_, target), snapshot) in files.iter().zip(snapshots) {
+        let result = match snapshot {
+            Some(content) => atomic_write(target, content).map_err(|e| e.to_string()),
+            None => std::fs::remove_file(target).map_err(|e| e.to_string()),
+        };
+        if let Err(e) = result {
+            first_error.get_or_insert(e);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Saves the current `target` to the backup directory before it's overwritten
+fn write_safety_backup(
+    content: &str,
+    target: &Path,
+    timestamp: &str,
+    backup_dir: &Path,
+) -> Result<PathBuf, String> {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_else(|| "config.kdl".into());
+    let path = backup_dir.join(format!("{}.backup-{}", name, timestamp));
+
+    std::fs::create_dir_all(backup_dir)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    atomic_write(&path, content).map_err(|e| format!("Failed to backup current config: {}", e))?;
+
+    log::info!("Created backup of current config: {}", path.display());
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CURRENT: &str = "layout {\n    gaps 16\n}\n";
+    const BACKUP: &str = "layout {\n    gaps 8\n}\n";
+
+    fn setup() -> (TempDir, PathBuf, PathBuf, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("config.kdl");
+        let backup = dir.path().join("config.kdl.backup-20240115-143045");
+        let backup_dir = dir.path().join("backups");
+        std::fs::write(&config, CURRENT).unwrap();
+        std::fs::write(&backup, BACKUP).unwrap();
+        (dir, config, backup, backup_dir)
+    }
+
+    #[test]
+    fn test_failing_reload_rolls_back() {
+        let (_dir, config, backup, backup_dir) = setup();
+
+        // Fail the first reload (the restored config), accept the rollback
+        let mut seen = Vec::new();
+        let outcome = restore_with_rollback(&backup, &config, &backup_dir, || {
+            seen.push(std::fs::read_to_string(&config).unwrap());
+            Some(if seen.len() == 1 {
+                Err("error parsing config".to_string())
+            } else {
+                Ok(())
+            })
+        })
+        .unwrap();
+
+        assert_eq!(seen, vec![BACKUP.to_string(), CURRENT.to_string()]);
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), CURRENT);
+        let RestoreOutcome::RolledBack {
+            reload_error,
+            rollback_error,
+            safety_backup,
+        } = &outcome
+        else {
+            panic!("expected a rollback, got {:?}", outcome);
+        };
+        assert_eq!(reload_error, "error parsing config");
+        assert_eq!(rollback_error, &None);
+        assert_eq!(
+            std::fs::read_to_string(safety_backup.as_ref().unwrap()).unwrap(),
+            CURRENT
+        );
+        assert!(!outcome.is_applied());
+    }
+
+    #[test]
+    fn test_accepted_reload_keeps_backup() {
+        let (_dir, config, backup, backup_dir) = setup();
+
+        let outcome =
+            restore_with_rollback(&backup, &config, &backup_dir, || Some(Ok(()))).unwrap();
+        assert!(matches!(
+            outcome,
+            RestoreOutcome::Applied {
+                safety_backup: Some(_)
+            }
+        ));
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), BACKUP);
+
+        // Without niri there's nothing to check against, so the restore stays
+        std::fs::write(&config, CURRENT).unwrap();
+        let outcome = restore_with_rollback(&backup, &config, &backup_dir, || None).unwrap();
+        assert!(matches!(outcome, RestoreOutcome::Unverified { .. }));
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), BACKUP);
+    }
+
+    #[test]
+    fn test_rejected_snapshot_puts_every_file_back() {
+        let (dir, config, backup, backup_dir) = setup();
+        let binds = dir.path().join("binds.kdl");
+        let binds_backup = dir.path().join("binds.kdl.backup-20240115-143045");
+        std::fs::write(&binds, "binds {\n}\n").unwrap();
+        std::fs::write(&binds_backup, "binds {\n    Mod+T { spawn \"foot\"; }\n}\n").unwrap();
+        let files = [(backup, config.clone()), (binds_backup, binds.clone())];
+
+        // Both files are in place by the time niri is asked, and only once
+        let mut reloads = 0;
+        let outcome = restore_set_with_rollback(&files, &backup_dir, || {
+            reloads += 1;
+            if reloads == 1 {
+                assert_eq!(std::fs::read_to_string(&config).unwrap(), BACKUP);
+                assert!(std::fs::read_to_string(&binds).unwrap().contains("foot"));
+                Some(Err("bad".to_string()))
+            } else {
+                Some(Ok(()))
+            }
+        })
+        .unwrap();
+        assert_eq!(reloads, 2);
+        assert!(!outcome.is_applied());
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), CURRENT);
+        assert_eq!(std::fs::read_to_string(&binds).unwrap(), "binds {\n}\n");
+
+        // The safety backups of both files share a timestamp
+        let mut names: Vec<String> = std::fs::read_dir(&backup_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 2);
+        assert_eq!(
+            names[0].trim_start_matches("binds.kdl"),
+            names[1].trim_start_matches("config.kdl")
+        );
+    }
+
+    #[test]
+    fn test_rollback_removes_config_that_did_not_exist() {
+        let (_dir, config, backup, backup_dir) = setup();
+        std::fs::remove_file(&config).unwrap();
+
+        let outcome = restore_with_rollback(&backup, &config, &backup_dir, || {
+            Some(Err("bad".to_string()))
+        })
+        .unwrap();
+        assert!(!config.exists());
+        assert!(matches!(
+            outcome,
+            RestoreOutcome::RolledBack {
+                rollback_error: Some(_),
+                safety_backup: None,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+//! Restore points taken before each save reloads niri
+//!
+//! Unlike the timestamped config.kdl backups, a restore point is a copy of
+//! every managed file as it was just before a save was written, so the
+//! reload that follows can be undone with one click. Only the last few are
+//! kept, and they are cleared each time the app starts.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+use super::{atomic_write, ConfigError, ConfigPaths};
+
+/// How many restore points are kept
+pub const RESTORE_POINTS_KEPT: usize = 5;
+
+/// Directory holding the restore points for `paths`
+fn restore_points_dir(paths: &ConfigPaths) -> PathBuf {
+    paths.backup_dir.join("restore-points")
+}
+
+/// Copies every managed `.kdl` file into a new restore point
+///
+/// Older restore points beyond [`RESTORE_POINTS_KEPT`] are removed.
+pub fn capture_restore_point(paths: &ConfigPaths) -> Result<PathBuf, ConfigError> {
+    let dir = restore_points_dir(paths);
+    let stamp = Local::now().format("%Y%m%dT%H%M%S%.6f").to_string();
+    // Two saves in the same microsecond still get separate points
+    let mut point = dir.join(&stamp);
+    let mut n = 1;
+    while point.exists() {
+        point = dir.join(format!("{}-{}", stamp, n));
+        n += 1;
+    }
+    fs::create_dir_all(&point)?;
+
+    for file in managed_files(&paths.managed_dir)? {
+        let relative = file.strip_prefix(&paths.managed_dir).unwrap_or(&file);
+        let content = fs::read_to_string(&file)?;
+        let target = point.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic_write(&target, &content)
+            .map_err(|e| ConfigError::backup_error(&target, e.to_string()))?;
+    }
+
+    let mut points = list_restore_points(paths);
+    while points.len() > RESTORE_POINTS_KEPT {
+        let oldest = points.remove(0);
+        if let Err(e) = fs::remove_dir_all(&oldest) {
+            log::warn!("Failed to delete old restore point {:?}: {}", oldest, e);
+        }
+    }
+
+    Ok(point)
+}
+
+/// Removes a restore point that turned out not to be needed
+pub fn discard_restore_point(point: &Path) {
+    if let Err(e) = fs::remove_dir_all(point) {
+        log::warn!("Failed to delete restore point {:?}: {}", point, e);
+    }
+}
+
+/// Restore points, oldest first
+pub fn list_restore_points(paths: &ConfigPaths) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(restore_points_dir(paths)) else {
+        return Vec::new();
+    };
+    let mut points: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    // Names are timestamps, so they sort chronologically
+    points.sort();
+    points
+}
+
+/// Whether the managed files are still exactly as `point` holds them
+///
+/// A save that wrote nothing (or failed before writing) leaves them so, and
+/// then its restore point would only undo nothing.
+pub fn restore_point_is_current(paths: &ConfigPaths, point: &Path) -> bool {
+    let (Ok(saved), Ok(current)) = (managed_files(point), managed_files(&paths.managed_dir)) else {
+        return false;
+    };
+    saved.len() == current.len()
+        && saved.iter().all(|file| {
+            let relative = file.strip_prefix(point).unwrap_or(file);
+            matches!(
+                (fs::read(file), fs::read(paths.managed_dir.join(relative))),
+                (Ok(a), Ok(b)) if a == b
+            )
+        })
+}
+
+/// Writes the newest restore point back over the managed files and drops it
+///
+/// Managed files the point doesn't hold were created by the save being
+/// reverted, so they are deleted. Returns how many restore points are left,
+/// so repeated reverts step further back.
+pub fn revert_last_restore_point(paths: &ConfigPaths) -> Result<usize, ConfigError> {
+    let mut points = list_restore_points(paths);
+    let Some(point) = points.pop() else {
+        return Err(ConfigError::backup_error(
+            restore_points_dir(paths),
+            "No restore point to revert to",
+        ));
+    };
+
+    for file in managed_files(&paths.managed_dir)? {
+        let relative = file.strip_prefix(&paths.managed_dir).unwrap_or(&file);
+        if !point.join(relative).exists() {
+            fs::remove_file(&file)?;
+        }
+    }
+    for file in managed_files(&point)? {
+        let relative = file.strip_prefix(&point).unwrap_or(&file);
+        let content = fs::read_to_string(&file)?;
+        let target = paths.managed_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic_write(&target, &content)
+            .map_err(|e| ConfigError::backup_error(&target, e.to_string()))?;
+    }
+
+    discard_restore_point(&point);
+    Ok(points.len())
+}
+
+/// Removes all restore points; they only cover the current session
+pub fn clear_restore_points(paths: &ConfigPaths) {
+    let dir = restore_points_dir(paths);
+    if dir.exists() {
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            log::warn!("Failed to clear restore points: {}", e);
+        }
+    }
+}
+
+/// All `.kdl` files under `dir`, recursively
+pub(super) fn managed_files(dir: &Path) -> Result<Vec<PathBuf>, ConfigError> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(managed_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "kdl") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_revert_restores_pre_reload_contents() {
+        let temp = tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(temp.path());
+        paths.ensure_directories().unwrap();
+        fs::write(&paths.appearance_kdl, "layout {\n    gaps 16\n}\n").unwrap();
+        fs::write(&paths.keyboard_kdl, "input {\n    keyboard {}\n}\n").unwrap();
+
+        let before_appearance = fs::read_to_string(&paths.appearance_kdl).unwrap();
+        let before_keyboard = fs::read_to_string(&paths.keyboard_kdl).unwrap();
+        capture_restore_point(&paths).unwrap();
+
+        // The save writes new contents, then niri reloads them
+        fs::write(&paths.appearance_kdl, "layout {\n    gaps 4\n}\n").unwrap();
+        fs::write(&paths.keyboard_kdl, "broken {").unwrap();
+
+        assert_eq!(revert_last_restore_point(&paths).unwrap(), 0);
+        assert_eq!(
+            fs::read_to_string(&paths.appearance_kdl).unwrap(),
+            before_appearance
+        );
+        assert_eq!(
+            fs::read_to_string(&paths.keyboard_kdl).unwrap(),
+            before_keyboard
+        );
+        assert!(revert_last_restore_point(&paths).is_err());
+    }
+
+    #[test]
+    fn test_only_recent_restore_points_are_kept() {
+        let temp = tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(temp.path());
+        paths.ensure_directories().unwrap();
+
+        for gaps in 0..RESTORE_POINTS_KEPT + 2 {
+            fs::write(&paths.appearance_kdl, format!("layout {{ gaps {} }}", gaps)).unwrap();
+            capture_restore_point(&paths).unwrap();
+        }
+        assert_eq!(list_restore_points(&paths).len(), RESTORE_POINTS_KEPT);
+
+        // The newest point holds the last captured contents
+        revert_last_restore_point(&paths).unwrap();
+        assert_eq!(
+            fs::read_to_string(&paths.appearance_kdl).unwrap(),
+            format!("layout {{ gaps {} }}", RESTORE_POINTS_KEPT + 1)
+        );
+
+        clear_restore_points(&paths);
+        assert!(list_restore_points(&paths).is_empty());
+    }
+
+    #[test]
+    fn test_revert_deletes_files_the_save_created() {
+        let temp = tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(temp.path());
+        paths.ensure_directories().unwrap();
+        fs::write(&paths.appearance_kdl, "layout {\n    gaps 16\n}\n").unwrap();
+        let _ = fs::remove_file(&paths.keyboard_kdl);
+
+        capture_restore_point(&paths).unwrap();
+        fs::write(&paths.keyboard_kdl, "input {\n    keyboard {}\n}\n").unwrap();
+
+        revert_last_restore_point(&paths).unwrap();
+        assert!(!paths.keyboard_kdl.exists());
+        assert!(paths.appearance_kdl.exists());
+    }
+
+    #[test]
+    fn test_restore_point_is_current_until_a_file_changes() {
+        let temp = tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(temp.path());
+        paths.ensure_directories().unwrap();
+        fs::write(&paths.appearance_kdl, "layout {\n    gaps 16\n}\n").unwrap();
+        let _ = fs::remove_file(&paths.keyboard_kdl);
+
+        let point = capture_restore_point(&paths).unwrap();
+        assert!(restore_point_is_current(&paths, &point));
+
+        // A new file counts as much as a changed one
+        fs::write(&paths.keyboard_kdl, "input {}\n").unwrap();
+        assert!(!restore_point_is_current(&paths, &point));
+        fs::remove_file(&paths.keyboard_kdl).unwrap();
+        fs::write(&paths.appearance_kdl, "layout {\n    gaps 4\n}\n").unwrap();
+        assert!(!restore_point_is_current(&paths, &point));
+    }
+}
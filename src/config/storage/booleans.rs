@@ -0,0 +1,136 @@
+//! How each boolean option is written for niri
+//!
+//! Most niri booleans are bare flags: present means true, and false is
+//! written by leaving the node out (`natural-scroll`, never
+//! `natural-scroll false`). Window and layer rule options instead take an
+//! explicit argument, since `false` there overrides an earlier rule, and
+//! match criteria and bind options are `name=true` properties. The wrong
+//! form is either rejected or ignored, so the exceptions are listed here and
+//! every other boolean is a flag.
+
+/// Form niri reads a boolean option in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BoolForm {
+    /// Bare `name` when true, left out when false
+    Flag,
+    /// `name true` or `name false`
+    Argument,
+    /// `name=true` or `name=false` on the parent node
+    Property,
+}
+
+/// Boolean options that aren't flags
+const NON_FLAG_OPTIONS: &[(&str, BoolForm)] = &[
+    // Touchpad
+    ("drag", BoolForm::Argument),
+    // Window rules
+    ("open-maximized", BoolForm::Argument),
+    ("open-maximized-to-edges", BoolForm::Argument),
+    ("open-fullscreen", BoolForm::Argument),
+    ("open-floating", BoolForm::Argument),
+    ("open-focused", BoolForm::Argument),
+    ("clip-to-geometry", BoolForm::Argument),
+    ("draw-border-with-background", BoolForm::Argument),
+    // The output option of the same name is a flag, written with `on-demand`
+    ("variable-refresh-rate", BoolForm::Argument),
+    ("tiled-state", BoolForm::Argument),
+    // Window and layer rules
+    ("baba-is-float", BoolForm::Argument),
+    ("place-within-backdrop", BoolForm::Argument),
+    // Shadow, in the layout and in rules
+    ("draw-behind-window", BoolForm::Argument),
+    // Rule match criteria
+    ("is-active", BoolForm::Property),
+    ("is-focused", BoolForm::Property),
+    ("is-active-in-column", BoolForm::Property),
+    ("is-floating", BoolForm::Property),
+    ("is-window-cast-target", BoolForm::Property),
+    ("is-urgent", BoolForm::Property),
+    ("at-startup", BoolForm::Property),
+    // Bind options
+    ("allow-when-locked", BoolForm::Property),
+    ("allow-inhibiting", BoolForm::Property),
+    ("repeat", BoolForm::Property),
+    // Output variable-refresh-rate
+    ("on-demand", BoolForm::Property),
+];
+
+/// Form niri expects for boolean option `name`
+pub(super) fn bool_form(name: &str) -> BoolForm {
+    NON_FLAG_OPTIONS
+        .iter()
+        .find(|(option, _)| *option == name)
+        .map_or(BoolForm::Flag, |(_, form)| *form)
+}
+
+/// Node for boolean option `name`, without indentation
+///
+/// `None` leaves the option unset, and a flag is also left out when false.
+pub(super) fn bool_node(name: &str, value: Option<bool>) -> Option<String> {
+    match (bool_form(name), value?) {
+        (BoolForm::Flag, true) => Some(name.to_string()),
+        (BoolForm::Flag, false) => None,
+        (BoolForm::Argument, value) => Some(format!("{} {}", name, value)),
+        (BoolForm::Property, _) => {
+            debug_assert!(false, "{} is a property, not a node", name);
+            None
+        }
+    }
+}
+
+/// `name=value` for boolean property `name`
+pub(super) fn bool_property(name: &str, value: bool) -> String {
+    debug_assert_eq!(bool_form(name), BoolForm::Property, "{}", name);
+    format!("{}={}", name, value)
+}
+
+/// Appends the node for boolean option `name` as a line at `indent`
+pub(super) fn push_bool(content: &mut String, indent: &str, name: &str, value: Option<bool>) {
+    if let Some(node) = bool_node(name, value) {
+        content.push_str(indent);
+        content.push_str(&node);
+        content.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_are_bare_or_omitted() {
+        assert_eq!(
+            bool_node("natural-scroll", Some(true)).unwrap(),
+            "natural-scroll"
+        );
+        assert_eq!(bool_node("natural-scroll", Some(false)), None);
+        assert_eq!(bool_node("prefer-no-csd", None), None);
+    }
+
+    #[test]
+    fn test_arguments_keep_false() {
+        assert_eq!(
+            bool_node("open-floating", Some(true)).unwrap(),
+            "open-floating true"
+        );
+        assert_eq!(bool_node("drag", Some(false)).unwrap(), "drag false");
+        assert_eq!(bool_node("open-focused", None), None);
+    }
+
+    #[test]
+    fn test_properties() {
+        assert_eq!(bool_property("is-active", false), "is-active=false");
+        assert_eq!(
+            bool_property("allow-when-locked", true),
+            "allow-when-locked=true"
+        );
+    }
+
+    #[test]
+    fn test_table_has_no_duplicates() {
+        let mut names: Vec<_> = NON_FLAG_OPTIONS.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), NON_FLAG_OPTIONS.len());
+    }
+}
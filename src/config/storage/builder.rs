@@ -25,6 +25,7 @@
 //! // }
 //! ```
 
+use super::booleans::{bool_form, bool_node, BoolForm};
 use super::gradient::color_or_gradient_to_kdl;
 use super::helpers::escape_kdl_string;
 use crate::types::{Color, ColorOrGradient};
@@ -104,12 +105,21 @@ impl KdlBuilder {
 
     /// Add a flag only if the condition is true
     pub fn optional_flag(&mut self, name: &str, condition: bool) -> &mut Self {
+        debug_assert_eq!(bool_form(name), BoolForm::Flag, "{}", name);
         if condition {
             self.flag(name);
         }
         self
     }
 
+    /// Add a boolean option in the form niri expects for it
+    pub fn bool_option(&mut self, name: &str, value: Option<bool>) -> &mut Self {
+        if let Some(node) = bool_node(name, value) {
+            self.raw(&node);
+        }
+        self
+    }
+
     /// Add an integer field
     pub fn field_i32(&mut self, name: &str, value: i32) -> &mut Self {
         self.content.push_str(&self.indent());
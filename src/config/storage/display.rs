@@ -2,6 +2,8 @@
 //!
 //! Generates KDL configuration for animations, cursor, overview, and outputs.
 
+use super::booleans::{bool_property, push_bool};
+use super::gradient::color_or_gradient_to_kdl;
 use super::helpers::escape_kdl_string;
 use crate::config::models::{
     AnimationSettings, AnimationType, CursorSettings, LayoutOverride, OutputSettings,
@@ -9,28 +11,21 @@ use crate::config::models::{
 };
 
 /// Generate KDL for a single animation config
+///
+/// A disabled animation is written as `off` followed by its curve, so the
+/// curve survives being turned off and back on.
 fn generate_single_animation_kdl(
     name: &str,
     config: &SingleAnimationConfig,
     indent: &str,
 ) -> Option<String> {
-    match config.animation_type {
-        AnimationType::Default => None, // Don't output, use niri defaults
-        AnimationType::Off => Some(format!(
-            "{}{} {{\n{}    off\n{}}}\n",
-            indent, name, indent, indent
-        )),
+    let curve = match config.animation_type {
+        AnimationType::Default => None, // Use niri defaults
         AnimationType::Spring => {
             let spring = &config.spring;
             Some(format!(
-                "{}{} {{\n{}    spring damping-ratio={:.4} stiffness={} epsilon={:.6}\n{}}}\n",
-                indent,
-                name,
-                indent,
-                spring.damping_ratio,
-                spring.stiffness,
-                spring.epsilon,
-                indent
+                "{}    spring damping-ratio={:.4} stiffness={} epsilon={:.6}\n",
+                indent, spring.damping_ratio, spring.stiffness, spring.epsilon
             ))
         }
         AnimationType::Easing => {
@@ -46,20 +41,30 @@ fn generate_single_animation_kdl(
                 )
             };
             Some(format!(
-                "{}{} {{\n{}    duration-ms {}\n{}    {}\n{}}}\n",
-                indent, name, indent, easing.duration_ms, indent, curve_str, indent
+                "{}    duration-ms {}\n{}    {}\n",
+                indent, easing.duration_ms, indent, curve_str
             ))
         }
-        AnimationType::CustomShader => {
-            // Custom GLSL shader - only valid for window-open, window-close, window-resize
-            config.custom_shader.as_ref().map(|code| {
-                format!(
-                    "{}{} {{\n{}    custom-shader r\"\n{}\n\"\n{}}}\n",
-                    indent, name, indent, code, indent
-                )
-            })
-        }
+        // Custom GLSL shader - only valid for window-open, window-close, window-resize
+        AnimationType::CustomShader => config
+            .custom_shader
+            .as_ref()
+            .map(|code| format!("{}    custom-shader r\"\n{}\n\"\n", indent, code)),
+    };
+
+    if config.enabled && curve.is_none() {
+        return None;
     }
+
+    let mut kdl = format!("{}{} {{\n", indent, name);
+    if !config.enabled {
+        kdl.push_str(&format!("{}    off\n", indent));
+    }
+    if let Some(curve) = curve {
+        kdl.push_str(&curve);
+    }
+    kdl.push_str(&format!("{}}}\n", indent));
+    Some(kdl)
 }
 
 /// Generate animations.kdl content
@@ -171,7 +176,6 @@ pub fn generate_overview_kdl(settings: &OverviewSettings) -> String {
 /// Generate KDL for a layout override block
 pub fn generate_layout_override_kdl(layout: &LayoutOverride, indent: &str) -> String {
     use crate::config::models::{DefaultColumnDisplay, PresetHeight, PresetWidth};
-    use crate::types::ColorOrGradient;
 
     let mut content = String::with_capacity(512);
     let inner_indent = format!("{}    ", indent);
@@ -292,22 +296,18 @@ pub fn generate_layout_override_kdl(layout: &LayoutOverride, indent: &str) -> St
                 content.push_str(&format!("{}width {}\n", deep_indent, w));
             }
             if let Some(ref c) = layout.focus_ring_active {
-                if let ColorOrGradient::Color(color) = c {
-                    content.push_str(&format!(
-                        "{}active-color \"{}\"\n",
-                        deep_indent,
-                        color.to_hex()
-                    ));
-                }
+                content.push_str(&format!(
+                    "{}{}\n",
+                    deep_indent,
+                    color_or_gradient_to_kdl(c, "active")
+                ));
             }
             if let Some(ref c) = layout.focus_ring_inactive {
-                if let ColorOrGradient::Color(color) = c {
-                    content.push_str(&format!(
-                        "{}inactive-color \"{}\"\n",
-                        deep_indent,
-                        color.to_hex()
-                    ));
-                }
+                content.push_str(&format!(
+                    "{}{}\n",
+                    deep_indent,
+                    color_or_gradient_to_kdl(c, "inactive")
+                ));
             }
         }
         content.push_str(&format!("{}}}\n", inner_indent));
@@ -327,22 +327,18 @@ pub fn generate_layout_override_kdl(layout: &LayoutOverride, indent: &str) -> St
                 content.push_str(&format!("{}width {}\n", deep_indent, w));
             }
             if let Some(ref c) = layout.border_active {
-                if let ColorOrGradient::Color(color) = c {
-                    content.push_str(&format!(
-                        "{}active-color \"{}\"\n",
-                        deep_indent,
-                        color.to_hex()
-                    ));
-                }
+                content.push_str(&format!(
+                    "{}{}\n",
+                    deep_indent,
+                    color_or_gradient_to_kdl(c, "active")
+                ));
             }
             if let Some(ref c) = layout.border_inactive {
-                if let ColorOrGradient::Color(color) = c {
-                    content.push_str(&format!(
-                        "{}inactive-color \"{}\"\n",
-                        deep_indent,
-                        color.to_hex()
-                    ));
-                }
+                content.push_str(&format!(
+                    "{}{}\n",
+                    deep_indent,
+                    color_or_gradient_to_kdl(c, "inactive")
+                ));
             }
         }
         content.push_str(&format!("{}}}\n", inner_indent));
@@ -397,15 +393,31 @@ pub fn generate_outputs_kdl(settings: &OutputSettings) -> String {
         content.push_str("// }\n");
     } else {
         for output in &settings.outputs {
+            // niri only sees the identity; the comment keeps its parts and the
+            // connector so they can be edited separately after loading
+            if let (true, Some(make), Some(model)) =
+                (output.match_by_identity, &output.make, &output.model)
+            {
+                content.push_str(&format!(
+                    "// identity name=\"{}\" make=\"{}\" model=\"{}\"",
+                    escape_kdl_string(&output.name),
+                    escape_kdl_string(make),
+                    escape_kdl_string(model)
+                ));
+                if let Some(serial) = &output.serial {
+                    content.push_str(&format!(" serial=\"{}\"", escape_kdl_string(serial)));
+                }
+                content.push('\n');
+            }
             content.push_str(&format!(
                 "output \"{}\" {{\n",
-                escape_kdl_string(&output.name)
+                escape_kdl_string(&output.match_name())
             ));
 
             if !output.enabled {
                 content.push_str("    off\n");
             } else {
-                if (output.scale - 1.0).abs() > 0.001 {
+                if output.has_scale() {
                     content.push_str(&format!("    scale {:.2}\n", output.scale));
                 }
 
@@ -429,7 +441,7 @@ pub fn generate_outputs_kdl(settings: &OutputSettings) -> String {
                     content.push_str(&format!("    modeline {}\n", modeline));
                 }
 
-                if output.position_x != 0 || output.position_y != 0 {
+                if output.has_position() {
                     content.push_str(&format!(
                         "    position x={} y={}\n",
                         output.position_x, output.position_y
@@ -455,12 +467,18 @@ pub fn generate_outputs_kdl(settings: &OutputSettings) -> String {
                         content.push_str("    variable-refresh-rate\n");
                     }
                     crate::types::VrrMode::OnDemand => {
-                        content.push_str("    variable-refresh-rate on-demand=true\n");
+                        content.push_str(&format!(
+                            "    variable-refresh-rate {}\n",
+                            bool_property("on-demand", true)
+                        ));
                     }
                 }
-                if output.focus_at_startup {
-                    content.push_str("    focus-at-startup\n");
-                }
+                push_bool(
+                    &mut content,
+                    "    ",
+                    "focus-at-startup",
+                    Some(output.focus_at_startup),
+                );
                 if let Some(ref color) = output.backdrop_color {
                     content.push_str(&format!("    backdrop-color \"{}\"\n", color.to_hex()));
                 }
@@ -491,6 +509,13 @@ pub fn generate_outputs_kdl(settings: &OutputSettings) -> String {
                 if let Some(ref layout) = output.layout_override {
                     content.push_str(&generate_layout_override_kdl(layout, "    "));
                 }
+
+                // Unmodeled sub-nodes carried over from the loaded config
+                for node in &output.extra_nodes {
+                    for line in node.lines() {
+                        content.push_str(&format!("    {}\n", line));
+                    }
+                }
             }
             content.push_str("}\n\n");
         }
@@ -2,6 +2,7 @@
 //!
 //! Contains shared utilities for converting Rust types to KDL string representations.
 
+use super::booleans::push_bool;
 use crate::types::{AccelProfile, ScrollMethod};
 use std::borrow::Cow;
 
@@ -84,35 +85,55 @@ pub fn click_method_to_kdl(method: crate::types::ClickMethod) -> &'static str {
     }
 }
 
+/// Input settings that mouse and touchpad have in common
+pub struct CommonInputSettings {
+    pub natural_scroll: bool,
+    pub left_handed: bool,
+    pub middle_emulation: bool,
+    pub accel_speed: f64,
+    pub accel_profile: AccelProfile,
+    pub scroll_factor: f64,
+    pub scroll_factor_horizontal: Option<f64>,
+}
+
 /// Write common input device settings (shared between mouse and touchpad).
 ///
 /// Writes boolean flags and numeric settings that are common to both input devices.
 /// If `scroll_factor_horizontal` is Some and differs from `scroll_factor` (vertical),
-/// outputs the split format "horizontal=X vertical=Y".
+/// outputs the split format "horizontal=X vertical=Y". Acceleration and scroll factor
+/// are skipped at niri's defaults unless `emit_all_defaults` is set.
 pub fn write_common_input_settings(
     content: &mut String,
-    natural_scroll: bool,
-    left_handed: bool,
-    middle_emulation: bool,
-    accel_speed: f64,
-    accel_profile: AccelProfile,
-    scroll_factor: f64,
-    scroll_factor_horizontal: Option<f64>,
+    settings: &CommonInputSettings,
+    emit_all_defaults: bool,
 ) {
-    if natural_scroll {
-        content.push_str("        natural-scroll\n");
-    }
-    if left_handed {
-        content.push_str("        left-handed\n");
-    }
-    if middle_emulation {
-        content.push_str("        middle-emulation\n");
+    let CommonInputSettings {
+        natural_scroll,
+        left_handed,
+        middle_emulation,
+        accel_speed,
+        accel_profile,
+        scroll_factor,
+        scroll_factor_horizontal,
+    } = *settings;
+
+    push_bool(content, "        ", "natural-scroll", Some(natural_scroll));
+    push_bool(content, "        ", "left-handed", Some(left_handed));
+    push_bool(
+        content,
+        "        ",
+        "middle-emulation",
+        Some(middle_emulation),
+    );
+    if emit_all_defaults || accel_speed.abs() > 0.001 {
+        content.push_str(&format!("        accel-speed {:.2}\n", accel_speed));
+    }
+    if emit_all_defaults || !matches!(accel_profile, AccelProfile::Adaptive) {
+        content.push_str(&format!(
+            "        accel-profile \"{}\"\n",
+            accel_profile_to_kdl(accel_profile)
+        ));
     }
-    content.push_str(&format!("        accel-speed {:.2}\n", accel_speed));
-    content.push_str(&format!(
-        "        accel-profile \"{}\"\n",
-        accel_profile_to_kdl(accel_profile)
-    ));
 
     // Output scroll-factor: use split format if horizontal differs from vertical
     if let Some(h) = scroll_factor_horizontal {
@@ -120,7 +141,7 @@ pub fn write_common_input_settings(
             "        scroll-factor \"horizontal={:.2} vertical={:.2}\"\n",
             h, scroll_factor
         ));
-    } else {
+    } else if emit_all_defaults || (scroll_factor - 1.0).abs() > 0.001 {
         content.push_str(&format!("        scroll-factor {:.2}\n", scroll_factor));
     }
 }
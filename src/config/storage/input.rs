@@ -3,15 +3,18 @@
 //! Generates KDL configuration for all input devices: keyboard, mouse, touchpad,
 //! trackpoint, trackball, tablet, and touch.
 
+use super::booleans::push_bool;
 use super::helpers::{
     accel_profile_to_kdl, click_method_to_kdl, escape_kdl_string, scroll_method_to_kdl,
-    tap_button_map_to_kdl, write_common_input_settings,
+    tap_button_map_to_kdl, write_common_input_settings, CommonInputSettings,
 };
 use crate::config::models::{
     KeyboardSettings, MouseSettings, TabletSettings, TouchSettings, TouchpadSettings,
     TrackballSettings, TrackpointSettings,
 };
+use crate::constants::{DEFAULT_REPEAT_DELAY, DEFAULT_REPEAT_RATE};
 use crate::types::{AccelProfile, ScrollMethod};
+use crate::version::FeatureCompat;
 
 /// Trait for pointer devices that share common settings (trackpoint, trackball).
 ///
@@ -110,10 +113,16 @@ impl PointerDeviceSettings for TrackballSettings {
 /// # Arguments
 /// * `device_name` - The name of the device (e.g., "trackpoint", "trackball")
 /// * `settings` - The device settings implementing `PointerDeviceSettings`
+/// * `emit_all_defaults` - Also write acceleration and scroll method when they
+///   match niri's defaults
 ///
 /// # Returns
 /// A formatted KDL string for the device configuration
-fn generate_pointer_device_kdl(device_name: &str, settings: &impl PointerDeviceSettings) -> String {
+fn generate_pointer_device_kdl(
+    device_name: &str,
+    settings: &impl PointerDeviceSettings,
+    emit_all_defaults: bool,
+) -> String {
     let mut content = String::with_capacity(512);
     content.push_str(&format!(
         "// {} settings - managed by Nirify\n\ninput {{\n    {} {{\n",
@@ -128,27 +137,34 @@ fn generate_pointer_device_kdl(device_name: &str, settings: &impl PointerDeviceS
         device_name
     ));
 
-    if settings.off() {
-        content.push_str("        off\n");
-    }
+    push_bool(&mut content, "        ", "off", Some(settings.off()));
 
     // Common pointer device settings
-    if settings.natural_scroll() {
-        content.push_str("        natural-scroll\n");
-    }
-    if settings.left_handed() {
-        content.push_str("        left-handed\n");
-    }
-    if settings.middle_emulation() {
-        content.push_str("        middle-emulation\n");
-    }
-    if settings.accel_speed().abs() > 0.001 {
+    push_bool(
+        &mut content,
+        "        ",
+        "natural-scroll",
+        Some(settings.natural_scroll()),
+    );
+    push_bool(
+        &mut content,
+        "        ",
+        "left-handed",
+        Some(settings.left_handed()),
+    );
+    push_bool(
+        &mut content,
+        "        ",
+        "middle-emulation",
+        Some(settings.middle_emulation()),
+    );
+    if emit_all_defaults || settings.accel_speed().abs() > 0.001 {
         content.push_str(&format!(
             "        accel-speed {:.2}\n",
             settings.accel_speed()
         ));
     }
-    if !matches!(settings.accel_profile(), AccelProfile::Adaptive) {
+    if emit_all_defaults || !matches!(settings.accel_profile(), AccelProfile::Adaptive) {
         content.push_str(&format!(
             "        accel-profile \"{}\"\n",
             accel_profile_to_kdl(settings.accel_profile())
@@ -156,7 +172,7 @@ fn generate_pointer_device_kdl(device_name: &str, settings: &impl PointerDeviceS
     }
 
     // Scroll method (only output if different from device default)
-    if settings.scroll_method() != settings.default_scroll_method() {
+    if emit_all_defaults || settings.scroll_method() != settings.default_scroll_method() {
         content.push_str(&format!(
             "        scroll-method \"{}\"\n",
             scroll_method_to_kdl(settings.scroll_method())
@@ -166,16 +182,22 @@ fn generate_pointer_device_kdl(device_name: &str, settings: &impl PointerDeviceS
     if let Some(button) = settings.scroll_button() {
         content.push_str(&format!("        scroll-button {}\n", button));
     }
-    if settings.scroll_button_lock() {
-        content.push_str("        scroll-button-lock\n");
-    }
+    push_bool(
+        &mut content,
+        "        ",
+        "scroll-button-lock",
+        Some(settings.scroll_button_lock()),
+    );
 
     content.push_str("    }\n}\n");
     content
 }
 
 /// Generate keyboard.kdl content
-pub fn generate_keyboard_kdl(settings: &KeyboardSettings) -> String {
+///
+/// Repeat settings and track-layout are only written when they differ from
+/// niri's defaults, unless `emit_all_defaults` is set.
+pub fn generate_keyboard_kdl(settings: &KeyboardSettings, emit_all_defaults: bool) -> String {
     let mut xkb_extra = String::new();
     if !settings.xkb_variant.is_empty() {
         xkb_extra.push_str(&format!(
@@ -209,6 +231,23 @@ pub fn generate_keyboard_kdl(settings: &KeyboardSettings) -> String {
         ));
     }
 
+    let mut keyboard_extra = String::new();
+    if emit_all_defaults || settings.repeat_delay != DEFAULT_REPEAT_DELAY {
+        keyboard_extra.push_str(&format!("\n        repeat-delay {}", settings.repeat_delay));
+    }
+    if emit_all_defaults || settings.repeat_rate != DEFAULT_REPEAT_RATE {
+        keyboard_extra.push_str(&format!("\n        repeat-rate {}", settings.repeat_rate));
+    }
+    if settings.numlock {
+        keyboard_extra.push_str("\n        numlock");
+    }
+    if emit_all_defaults || settings.track_layout != "global" {
+        keyboard_extra.push_str(&format!(
+            "\n        track-layout \"{}\"",
+            escape_kdl_string(&settings.track_layout)
+        ));
+    }
+
     // Note: Keyboard does not support 'off' flag in niri - keyboards cannot be disabled
     format!(
         r#"// Keyboard settings - managed by Nirify
@@ -217,51 +256,42 @@ input {{
     keyboard {{
         xkb {{
             layout "{}"{}
-        }}
-        repeat-delay {}
-        repeat-rate {}{}
-        track-layout "{}"
+        }}{}
     }}
 }}
 "#,
         escape_kdl_string(&settings.xkb_layout),
         xkb_extra,
-        settings.repeat_delay,
-        settings.repeat_rate,
-        if settings.numlock {
-            "\n        numlock"
-        } else {
-            ""
-        },
-        escape_kdl_string(&settings.track_layout),
+        keyboard_extra,
     )
 }
 
 /// Generate mouse.kdl content
-pub fn generate_mouse_kdl(settings: &MouseSettings) -> String {
+pub fn generate_mouse_kdl(settings: &MouseSettings, emit_all_defaults: bool) -> String {
     // Pre-allocate ~512 bytes for typical mouse config
     let mut content = String::with_capacity(512);
     content.push_str("// Mouse settings - managed by Nirify\n\ninput {\n    mouse {\n");
 
     // Check if device is disabled
-    if settings.off {
-        content.push_str("        off\n");
-    }
+    push_bool(&mut content, "        ", "off", Some(settings.off));
 
     // Common input settings
     write_common_input_settings(
         &mut content,
-        settings.natural_scroll,
-        settings.left_handed,
-        settings.middle_emulation,
-        settings.accel_speed,
-        settings.accel_profile,
-        settings.scroll_factor,
-        settings.scroll_factor_horizontal,
+        &CommonInputSettings {
+            natural_scroll: settings.natural_scroll,
+            left_handed: settings.left_handed,
+            middle_emulation: settings.middle_emulation,
+            accel_speed: settings.accel_speed,
+            accel_profile: settings.accel_profile,
+            scroll_factor: settings.scroll_factor,
+            scroll_factor_horizontal: settings.scroll_factor_horizontal,
+        },
+        emit_all_defaults,
     );
 
     // Only output scroll-method if it's not the default (no-scroll for mouse)
-    if !matches!(settings.scroll_method, ScrollMethod::NoScroll) {
+    if emit_all_defaults || !matches!(settings.scroll_method, ScrollMethod::NoScroll) {
         content.push_str(&format!(
             "        scroll-method \"{}\"\n",
             scroll_method_to_kdl(settings.scroll_method)
@@ -274,54 +304,65 @@ pub fn generate_mouse_kdl(settings: &MouseSettings) -> String {
     }
 
     // Scroll button lock
-    if settings.scroll_button_lock {
-        content.push_str("        scroll-button-lock\n");
-    }
+    push_bool(
+        &mut content,
+        "        ",
+        "scroll-button-lock",
+        Some(settings.scroll_button_lock),
+    );
 
     content.push_str("    }\n}\n");
     content
 }
 
 /// Generate touchpad.kdl content
-pub fn generate_touchpad_kdl(settings: &TouchpadSettings) -> String {
+///
+/// `drag` is only written for niri versions that accept it.
+pub fn generate_touchpad_kdl(
+    settings: &TouchpadSettings,
+    emit_all_defaults: bool,
+    compat: FeatureCompat,
+) -> String {
     // Pre-allocate ~768 bytes for typical touchpad config (more options than mouse)
     let mut content = String::with_capacity(768);
     content.push_str("// Touchpad settings - managed by Nirify\n\ninput {\n    touchpad {\n");
 
     // Check if device is disabled
-    if settings.off {
-        content.push_str("        off\n");
-    }
+    push_bool(&mut content, "        ", "off", Some(settings.off));
 
     // Touchpad-specific flags
-    if settings.tap {
-        content.push_str("        tap\n");
-    }
-    if settings.dwt {
-        content.push_str("        dwt\n");
-    }
-    if settings.dwtp {
-        content.push_str("        dwtp\n");
-    }
-    // drag requires a boolean argument in niri
-    content.push_str(&format!("        drag {}\n", settings.drag));
-    if settings.drag_lock {
-        content.push_str("        drag-lock\n");
-    }
-    if settings.disabled_on_external_mouse {
-        content.push_str("        disabled-on-external-mouse\n");
+    push_bool(&mut content, "        ", "tap", Some(settings.tap));
+    push_bool(&mut content, "        ", "dwt", Some(settings.dwt));
+    push_bool(&mut content, "        ", "dwtp", Some(settings.dwtp));
+    if compat.touchpad_drag {
+        push_bool(&mut content, "        ", "drag", Some(settings.drag));
     }
+    push_bool(
+        &mut content,
+        "        ",
+        "drag-lock",
+        Some(settings.drag_lock),
+    );
+    push_bool(
+        &mut content,
+        "        ",
+        "disabled-on-external-mouse",
+        Some(settings.disabled_on_external_mouse),
+    );
 
     // Common input settings (natural_scroll, left_handed, middle_emulation, accel, scroll_factor)
     write_common_input_settings(
         &mut content,
-        settings.natural_scroll,
-        settings.left_handed,
-        settings.middle_emulation,
-        settings.accel_speed,
-        settings.accel_profile,
-        settings.scroll_factor,
-        settings.scroll_factor_horizontal,
+        &CommonInputSettings {
+            natural_scroll: settings.natural_scroll,
+            left_handed: settings.left_handed,
+            middle_emulation: settings.middle_emulation,
+            accel_speed: settings.accel_speed,
+            accel_profile: settings.accel_profile,
+            scroll_factor: settings.scroll_factor,
+            scroll_factor_horizontal: settings.scroll_factor_horizontal,
+        },
+        emit_all_defaults,
     );
 
     // Touchpad-specific enums
@@ -344,8 +385,18 @@ pub fn generate_touchpad_kdl(settings: &TouchpadSettings) -> String {
     }
 
     // Scroll button lock
-    if settings.scroll_button_lock {
-        content.push_str("        scroll-button-lock\n");
+    push_bool(
+        &mut content,
+        "        ",
+        "scroll-button-lock",
+        Some(settings.scroll_button_lock),
+    );
+
+    // Unmodeled options carried over from the loaded config
+    for node in &settings.extra_nodes {
+        for line in node.lines() {
+            content.push_str(&format!("        {}\n", line));
+        }
     }
 
     content.push_str("    }\n}\n");
@@ -353,13 +404,13 @@ pub fn generate_touchpad_kdl(settings: &TouchpadSettings) -> String {
 }
 
 /// Generate trackpoint.kdl content
-pub fn generate_trackpoint_kdl(settings: &TrackpointSettings) -> String {
-    generate_pointer_device_kdl("trackpoint", settings)
+pub fn generate_trackpoint_kdl(settings: &TrackpointSettings, emit_all_defaults: bool) -> String {
+    generate_pointer_device_kdl("trackpoint", settings, emit_all_defaults)
 }
 
 /// Generate trackball.kdl content
-pub fn generate_trackball_kdl(settings: &TrackballSettings) -> String {
-    generate_pointer_device_kdl("trackball", settings)
+pub fn generate_trackball_kdl(settings: &TrackballSettings, emit_all_defaults: bool) -> String {
+    generate_pointer_device_kdl("trackball", settings, emit_all_defaults)
 }
 
 /// Trait for mapped input devices (tablet, touch) that share common settings.
@@ -394,9 +445,7 @@ impl MappedInputDevice for TabletSettings {
     }
     fn write_specific(&self, content: &mut String) {
         // Tablet-specific: left_handed
-        if self.left_handed {
-            content.push_str("        left-handed\n");
-        }
+        push_bool(content, "        ", "left-handed", Some(self.left_handed));
     }
 }
 
@@ -434,9 +483,7 @@ fn generate_mapped_input_kdl(device: &impl MappedInputDevice) -> String {
     ));
 
     // Common properties
-    if device.off() {
-        content.push_str("        off\n");
-    }
+    push_bool(&mut content, "        ", "off", Some(device.off()));
 
     let map_to_output = device.map_to_output();
     if !map_to_output.is_empty() {
@@ -2,7 +2,10 @@
 //!
 //! Generates KDL configuration for keybindings managed by Nirify.
 
+use super::booleans::bool_property;
+use crate::config::keys::{normalize_combo, parse_combo, ComboError};
 use crate::config::models::{KeybindAction, Keybinding, KeybindingsSettings};
+use kdl::KdlValue;
 
 /// Generate keybindings.kdl content from settings.
 ///
@@ -26,7 +29,14 @@ pub fn generate_keybindings_kdl(settings: &KeybindingsSettings) -> String {
     content.push_str("// Keybindings - managed by Nirify-rust\n");
     content.push_str("// Edit these bindings in the Nirify app\n\n");
 
-    if settings.bindings.is_empty() {
+    // Skip invalid keybindings (empty key combo or empty action)
+    let valid: Vec<&Keybinding> = settings
+        .bindings
+        .iter()
+        .filter(|b| is_valid_keybinding(b))
+        .collect();
+
+    if valid.is_empty() {
         content.push_str("// No keybindings configured yet.\n");
         content.push_str("// Add keybindings using the Nirify app.\n");
         return content;
@@ -34,11 +44,7 @@ pub fn generate_keybindings_kdl(settings: &KeybindingsSettings) -> String {
 
     content.push_str("binds {\n");
 
-    for binding in &settings.bindings {
-        // Skip invalid keybindings (empty key combo or empty action)
-        if !is_valid_keybinding(binding) {
-            continue;
-        }
+    for binding in valid {
         content.push_str(&generate_keybinding(binding));
     }
 
@@ -47,10 +53,15 @@ pub fn generate_keybindings_kdl(settings: &KeybindingsSettings) -> String {
 }
 
 /// Check if a keybinding is valid for saving to config
-/// Returns false for keybindings with empty key combo or empty action
+/// Returns false for keybindings without a key or with an empty action.
+/// Combos with a modifier or key name the parser doesn't know are still
+/// saved as written, so imported bindings aren't lost; the editor flags them
 fn is_valid_keybinding(binding: &Keybinding) -> bool {
-    // Must have a non-empty key combo
-    if binding.key_combo.trim().is_empty() {
+    // Must have a key to bind
+    if matches!(
+        parse_combo(&binding.key_combo),
+        Err(ComboError::Empty | ComboError::EmptyPart | ComboError::MissingKey)
+    ) {
         return false;
     }
 
@@ -66,12 +77,29 @@ fn is_valid_keybinding(binding: &Keybinding) -> bool {
 fn generate_keybinding(binding: &Keybinding) -> String {
     let mut line = String::with_capacity(256);
 
+    // niri has no binding sections, so the app-only group rides in a comment
+    if let Some(section) = binding.section.as_deref() {
+        let section = section.lines().collect::<Vec<_>>().join(" ");
+        line.push_str(&format!("    // section: {}\n", section.trim()));
+    }
+
     // Indent + key combo
     line.push_str("    ");
-    line.push_str(&binding.key_combo);
+    let combo = normalize_combo(&binding.key_combo);
+    if combo
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_'))
+    {
+        line.push_str(&combo);
+    } else {
+        // Not a bare node name; quoted so the rest of the file still parses
+        line.push_str(&quote_kdl_string(&combo));
+    }
 
     // Optional properties on the same line
-    if let Some(ref title) = binding.hotkey_overlay_title {
+    if binding.hotkey_overlay_hidden {
+        line.push_str(" hotkey-overlay-title=null");
+    } else if let Some(ref title) = binding.hotkey_overlay_title {
         line.push_str(&format!(
             " hotkey-overlay-title={}",
             quote_kdl_string(title)
@@ -79,15 +107,38 @@ fn generate_keybinding(binding: &Keybinding) -> String {
     }
 
     if binding.allow_when_locked {
-        line.push_str(" allow-when-locked=true");
+        line.push(' ');
+        line.push_str(&bool_property("allow-when-locked", true));
     }
 
     if let Some(cooldown) = binding.cooldown_ms {
         line.push_str(&format!(" cooldown-ms={}", cooldown));
     }
 
-    if binding.repeat {
-        line.push_str(" repeat=true");
+    // niri repeats by default, so only turning it off is written
+    if !binding.repeat {
+        line.push(' ');
+        line.push_str(&bool_property("repeat", false));
+    }
+
+    for (name, value) in &binding.extra_properties {
+        let Some(value) = kdl_value(value) else {
+            log::warn!(
+                "Dropping property {}={:?} of {}: not a finite number",
+                name,
+                value,
+                binding.key_combo
+            );
+            continue;
+        };
+        if name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            line.push_str(&format!(" {}={}", name, value));
+        } else {
+            line.push_str(&format!(" {}={}", quote_kdl_string(name), value));
+        }
     }
 
     // Action block
@@ -143,6 +194,22 @@ fn quote_kdl_string(s: &str) -> String {
     format!("\"{}\"", escaped)
 }
 
+/// Renders a passed-through property value in the same KDL syntax as the
+/// rest of the file
+///
+/// Returns `None` for infinite and NaN floats, which that syntax can't write.
+fn kdl_value(value: &KdlValue) -> Option<String> {
+    Some(match value {
+        KdlValue::String(s) => quote_kdl_string(s),
+        KdlValue::Integer(i) => i.to_string(),
+        KdlValue::Float(f) if !f.is_finite() => return None,
+        // Debug keeps the decimal point, so 1.0 stays a float
+        KdlValue::Float(f) => format!("{:?}", f),
+        KdlValue::Bool(b) => b.to_string(),
+        KdlValue::Null => "null".to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,8 +218,41 @@ mod tests {
     fn test_generate_empty_keybindings() {
         let settings = KeybindingsSettings::default();
         let kdl = generate_keybindings_kdl(&settings);
-        assert!(kdl.contains("No keybindings configured"));
-        assert!(!kdl.contains("binds {"));
+        assert_eq!(
+            kdl,
+            "// Keybindings - managed by Nirify-rust\n\
+             // Edit these bindings in the Nirify app\n\n\
+             // No keybindings configured yet.\n\
+             // Add keybindings using the Nirify app.\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_only_invalid_keybindings_matches_empty() {
+        let settings = KeybindingsSettings {
+            bindings: vec![
+                Keybinding {
+                    key_combo: "  ".to_string(),
+                    action: KeybindAction::NiriAction("close-window".to_string()),
+                    ..Default::default()
+                },
+                Keybinding {
+                    key_combo: "Mod+T".to_string(),
+                    action: KeybindAction::Spawn(vec![]),
+                    ..Default::default()
+                },
+                Keybinding {
+                    key_combo: "Mod++".to_string(),
+                    action: KeybindAction::NiriAction("close-window".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_keybindings_kdl(&settings),
+            generate_keybindings_kdl(&KeybindingsSettings::default())
+        );
     }
 
     #[test]
@@ -162,10 +262,14 @@ mod tests {
                 id: 1,
                 key_combo: "Mod+Space".to_string(),
                 hotkey_overlay_title: Some("App Launcher".to_string()),
+                hotkey_overlay_hidden: false,
+                section: None,
                 allow_when_locked: false,
                 cooldown_ms: None,
-                repeat: false,
+                repeat: true,
                 action: KeybindAction::Spawn(vec!["dmenu_run".to_string()]),
+                extra_properties: Vec::new(),
+                parse_warning: None,
             }],
             ..Default::default()
         };
@@ -184,10 +288,14 @@ mod tests {
                 id: 1,
                 key_combo: "Mod+Q".to_string(),
                 hotkey_overlay_title: None,
+                hotkey_overlay_hidden: false,
+                section: None,
                 allow_when_locked: false,
                 cooldown_ms: None,
-                repeat: false,
+                repeat: true,
                 action: KeybindAction::NiriAction("close-window".to_string()),
+                extra_properties: Vec::new(),
+                parse_warning: None,
             }],
             ..Default::default()
         };
@@ -204,15 +312,19 @@ mod tests {
                 id: 1,
                 key_combo: "XF86AudioMute".to_string(),
                 hotkey_overlay_title: Some("Mute".to_string()),
+                hotkey_overlay_hidden: false,
+                section: None,
                 allow_when_locked: true,
                 cooldown_ms: Some(100),
-                repeat: true,
+                repeat: false,
                 action: KeybindAction::Spawn(vec![
                     "wpctl".to_string(),
                     "set-mute".to_string(),
                     "@DEFAULT_AUDIO_SINK@".to_string(),
                     "toggle".to_string(),
                 ]),
+                extra_properties: Vec::new(),
+                parse_warning: None,
             }],
             ..Default::default()
         };
@@ -221,10 +333,82 @@ mod tests {
         assert!(kdl.contains("XF86AudioMute"));
         assert!(kdl.contains("allow-when-locked=true"));
         assert!(kdl.contains("cooldown-ms=100"));
-        assert!(kdl.contains("repeat=true"));
+        assert!(kdl.contains("repeat=false"));
         assert!(kdl.contains("spawn \"wpctl\" \"set-mute\""));
     }
 
+    #[test]
+    fn test_generate_normalizes_key_combo() {
+        let settings = KeybindingsSettings {
+            bindings: vec![Keybinding {
+                key_combo: "Shift+mod+q".to_string(),
+                action: KeybindAction::NiriAction("close-window".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let kdl = generate_keybindings_kdl(&settings);
+        assert!(kdl.contains("    Mod+Shift+q {"));
+    }
+
+    #[test]
+    fn test_generate_keeps_combos_the_parser_rejects() {
+        let close = |combo: &str| Keybinding {
+            key_combo: combo.to_string(),
+            action: KeybindAction::NiriAction("close-window".to_string()),
+            ..Default::default()
+        };
+        let settings = KeybindingsSettings {
+            bindings: vec![close("Hyper+Q"), close("Mod+a b")],
+            ..Default::default()
+        };
+
+        let kdl = generate_keybindings_kdl(&settings);
+        assert!(kdl.contains("    Hyper+Q {"));
+        assert!(kdl.contains("    \"Mod+a b\" {"));
+        assert!(kdl.parse::<kdl::KdlDocument>().is_ok());
+    }
+
+    #[test]
+    fn test_generate_drops_non_finite_properties() {
+        let settings = KeybindingsSettings {
+            bindings: vec![Keybinding {
+                key_combo: "Mod+Q".to_string(),
+                action: KeybindAction::NiriAction("close-window".to_string()),
+                extra_properties: vec![
+                    ("scale".to_string(), KdlValue::Float(1.5)),
+                    ("far".to_string(), KdlValue::Float(f64::INFINITY)),
+                    ("odd".to_string(), KdlValue::Float(f64::NAN)),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let kdl = generate_keybindings_kdl(&settings);
+        assert!(kdl.contains("    Mod+Q scale=1.5 {"));
+        assert!(!kdl.contains("far="));
+        assert!(!kdl.contains("odd="));
+    }
+
+    #[test]
+    fn test_generate_section_as_comment() {
+        let settings = KeybindingsSettings {
+            bindings: vec![Keybinding {
+                key_combo: "Mod+T".to_string(),
+                section: Some("Apps\nand tools".to_string()),
+                action: KeybindAction::Spawn(vec!["foot".to_string()]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let kdl = generate_keybindings_kdl(&settings);
+        assert!(kdl.contains("    // section: Apps and tools\n    Mod+T {"));
+        assert!(!kdl.contains("section="));
+    }
+
     #[test]
     fn test_quote_kdl_string() {
         assert_eq!(quote_kdl_string("simple"), "\"simple\"");
@@ -31,9 +31,10 @@ pub fn generate_layout_extras_kdl(settings: &LayoutExtrasSettings) -> String {
                 ));
                 s.field_color("color", &settings.shadow.color);
                 s.field_color("inactive-color", &settings.shadow.inactive_color);
-                if settings.shadow.draw_behind_window {
-                    s.raw("draw-behind-window true");
-                }
+                s.bool_option(
+                    "draw-behind-window",
+                    settings.shadow.draw_behind_window.then_some(true),
+                );
             });
         } else {
             b.block("shadow", |s| {
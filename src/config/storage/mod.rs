@@ -6,6 +6,7 @@
 //! # Module Structure
 //!
 //! - `helpers`: Shared utilities for KDL string conversion
+//! - `booleans`: Which form (flag, argument, property) each boolean takes
 //! - `gradient`: Gradient and color KDL generation
 //! - `appearance`: Appearance settings (gaps, focus ring, borders)
 //! - `behavior`: Behavior settings (focus follows mouse, etc.)
@@ -17,11 +18,22 @@
 //! - `workspaces`: Named workspace settings
 //! - `rules`: Window and layer rules
 //! - `system`: Startup, environment, debug, switch events, recent windows
+//!
+//! # Empty Collections
+//!
+//! Files holding a list (window/layer rules, keybindings, outputs, named
+//! workspaces, startup commands, environment variables) always keep their
+//! header. When there is nothing to write, either because the list is empty
+//! or because every entry was skipped as invalid, the header is followed by
+//! the file's fixed "nothing configured" comment and no nodes, never by an
+//! empty block like `binds {}`. The output then depends only on what would
+//! reach niri, which keeps diffs and `smart_replace_config` stable.
 
 pub mod builder;
 
 mod appearance;
 mod behavior;
+mod booleans;
 mod display;
 mod gestures;
 mod gradient;
@@ -140,12 +152,20 @@ pub fn atomic_write(path: &Path, content: &str) -> anyhow::Result<()> {
 }
 
 /// Write content to a file using the specified strategy.
-fn write_config(path: &Path, content: &str, strategy: WriteStrategy) -> anyhow::Result<()> {
+///
+/// A file that already holds exactly `content` is left alone, so saving a
+/// category whose output didn't change doesn't touch the file (no diff, no
+/// new mtime for file watchers). Returns whether the file was written.
+fn write_config(path: &Path, content: &str, strategy: WriteStrategy) -> anyhow::Result<bool> {
+    if fs::read(path).is_ok_and(|existing| existing == content.as_bytes()) {
+        return Ok(false);
+    }
     match strategy {
         WriteStrategy::Atomic => atomic_write(path, content),
         WriteStrategy::Direct => fs::write(path, content).map_err(Into::into),
     }
-    .with_context(|| format!("Failed to write {:?}", path))
+    .with_context(|| format!("Failed to write {:?}", path))?;
+    Ok(true)
 }
 
 /// Write all settings files using the specified strategy.
@@ -178,27 +198,31 @@ fn write_all_settings(
     // Input settings
     write_config(
         &paths.keyboard_kdl,
-        &generate_keyboard_kdl(&settings.keyboard),
+        &generate_keyboard_kdl(&settings.keyboard, settings.preferences.emit_all_defaults),
         strategy,
     )?;
     write_config(
         &paths.mouse_kdl,
-        &generate_mouse_kdl(&settings.mouse),
+        &generate_mouse_kdl(&settings.mouse, settings.preferences.emit_all_defaults),
         strategy,
     )?;
     write_config(
         &paths.touchpad_kdl,
-        &generate_touchpad_kdl(&settings.touchpad),
+        &generate_touchpad_kdl(
+            &settings.touchpad,
+            settings.preferences.emit_all_defaults,
+            compat,
+        ),
         strategy,
     )?;
     write_config(
         &paths.trackpoint_kdl,
-        &generate_trackpoint_kdl(&settings.trackpoint),
+        &generate_trackpoint_kdl(&settings.trackpoint, settings.preferences.emit_all_defaults),
         strategy,
     )?;
     write_config(
         &paths.trackball_kdl,
-        &generate_trackball_kdl(&settings.trackball),
+        &generate_trackball_kdl(&settings.trackball, settings.preferences.emit_all_defaults),
         strategy,
     )?;
     write_config(
@@ -347,7 +371,8 @@ pub fn save_settings(
 /// * `compat` - Feature compatibility flags based on detected niri version
 ///
 /// # Returns
-/// The number of files that were written.
+/// The number of files that were written. Dirty categories whose generated
+/// file matches what's on disk are skipped and not counted.
 pub fn save_dirty(
     paths: &ConfigPaths,
     settings: &Settings,
@@ -373,179 +398,143 @@ pub fn save_dirty(
     }
 
     for category in dirty {
-        match category {
+        let written = match category {
             SettingsCategory::Appearance => {
                 // Appearance includes some behavior settings (struts)
                 write_config(
                     &paths.appearance_kdl,
                     &generate_appearance_kdl(&settings.appearance, &settings.behavior),
                     strategy,
-                )?;
-            }
-            SettingsCategory::Behavior => {
-                write_config(
-                    &paths.behavior_kdl,
-                    &generate_behavior_kdl(&settings.behavior),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Keyboard => {
-                write_config(
-                    &paths.keyboard_kdl,
-                    &generate_keyboard_kdl(&settings.keyboard),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Mouse => {
-                write_config(
-                    &paths.mouse_kdl,
-                    &generate_mouse_kdl(&settings.mouse),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Touchpad => {
-                write_config(
-                    &paths.touchpad_kdl,
-                    &generate_touchpad_kdl(&settings.touchpad),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Trackpoint => {
-                write_config(
-                    &paths.trackpoint_kdl,
-                    &generate_trackpoint_kdl(&settings.trackpoint),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Trackball => {
-                write_config(
-                    &paths.trackball_kdl,
-                    &generate_trackball_kdl(&settings.trackball),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Tablet => {
-                write_config(
-                    &paths.tablet_kdl,
-                    &generate_tablet_kdl(&settings.tablet),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Touch => {
-                write_config(
-                    &paths.touch_kdl,
-                    &generate_touch_kdl(&settings.touch),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Outputs => {
-                write_config(
-                    &paths.outputs_kdl,
-                    &generate_outputs_kdl(&settings.outputs),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Animations => {
-                write_config(
-                    &paths.animations_kdl,
-                    &generate_animations_kdl(&settings.animations),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Cursor => {
-                write_config(
-                    &paths.cursor_kdl,
-                    &generate_cursor_kdl(&settings.cursor),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Overview => {
-                write_config(
-                    &paths.overview_kdl,
-                    &generate_overview_kdl(&settings.overview),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Workspaces => {
-                write_config(
-                    &paths.workspaces_kdl,
-                    &generate_workspaces_kdl(&settings.workspaces),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Keybindings => {
-                write_config(
-                    &paths.keybindings_kdl,
-                    &generate_keybindings_kdl(&settings.keybindings),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::LayoutExtras => {
-                write_config(
-                    &paths.layout_extras_kdl,
-                    &generate_layout_extras_kdl(&settings.layout_extras),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Gestures => {
-                write_config(
-                    &paths.gestures_kdl,
-                    &generate_gestures_kdl(&settings.gestures),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::LayerRules => {
-                write_config(
-                    &paths.layer_rules_kdl,
-                    &generate_layer_rules_kdl(&settings.layer_rules),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::WindowRules => {
-                write_config(
-                    &paths.window_rules_kdl,
-                    &generate_window_rules_kdl(
-                        &settings.window_rules,
-                        settings.preferences.float_settings_app,
-                    ),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Miscellaneous => {
-                write_config(
-                    &paths.misc_kdl,
-                    &generate_misc_kdl(&settings.miscellaneous),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Startup => {
-                write_config(
-                    &paths.startup_kdl,
-                    &generate_startup_kdl(&settings.startup),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Environment => {
-                write_config(
-                    &paths.environment_kdl,
-                    &generate_environment_kdl(&settings.environment),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::Debug => {
-                write_config(
-                    &paths.debug_kdl,
-                    &generate_debug_kdl(&settings.debug),
-                    strategy,
-                )?;
-            }
-            SettingsCategory::SwitchEvents => {
-                write_config(
-                    &paths.switch_events_kdl,
-                    &generate_switch_events_kdl(&settings.switch_events),
-                    strategy,
-                )?;
+                )?
             }
+            SettingsCategory::Behavior => write_config(
+                &paths.behavior_kdl,
+                &generate_behavior_kdl(&settings.behavior),
+                strategy,
+            )?,
+            SettingsCategory::Keyboard => write_config(
+                &paths.keyboard_kdl,
+                &generate_keyboard_kdl(&settings.keyboard, settings.preferences.emit_all_defaults),
+                strategy,
+            )?,
+            SettingsCategory::Mouse => write_config(
+                &paths.mouse_kdl,
+                &generate_mouse_kdl(&settings.mouse, settings.preferences.emit_all_defaults),
+                strategy,
+            )?,
+            SettingsCategory::Touchpad => write_config(
+                &paths.touchpad_kdl,
+                &generate_touchpad_kdl(
+                    &settings.touchpad,
+                    settings.preferences.emit_all_defaults,
+                    compat,
+                ),
+                strategy,
+            )?,
+            SettingsCategory::Trackpoint => write_config(
+                &paths.trackpoint_kdl,
+                &generate_trackpoint_kdl(
+                    &settings.trackpoint,
+                    settings.preferences.emit_all_defaults,
+                ),
+                strategy,
+            )?,
+            SettingsCategory::Trackball => write_config(
+                &paths.trackball_kdl,
+                &generate_trackball_kdl(
+                    &settings.trackball,
+                    settings.preferences.emit_all_defaults,
+                ),
+                strategy,
+            )?,
+            SettingsCategory::Tablet => write_config(
+                &paths.tablet_kdl,
+                &generate_tablet_kdl(&settings.tablet),
+                strategy,
+            )?,
+            SettingsCategory::Touch => write_config(
+                &paths.touch_kdl,
+                &generate_touch_kdl(&settings.touch),
+                strategy,
+            )?,
+            SettingsCategory::Outputs => write_config(
+                &paths.outputs_kdl,
+                &generate_outputs_kdl(&settings.outputs),
+                strategy,
+            )?,
+            SettingsCategory::Animations => write_config(
+                &paths.animations_kdl,
+                &generate_animations_kdl(&settings.animations),
+                strategy,
+            )?,
+            SettingsCategory::Cursor => write_config(
+                &paths.cursor_kdl,
+                &generate_cursor_kdl(&settings.cursor),
+                strategy,
+            )?,
+            SettingsCategory::Overview => write_config(
+                &paths.overview_kdl,
+                &generate_overview_kdl(&settings.overview),
+                strategy,
+            )?,
+            SettingsCategory::Workspaces => write_config(
+                &paths.workspaces_kdl,
+                &generate_workspaces_kdl(&settings.workspaces),
+                strategy,
+            )?,
+            SettingsCategory::Keybindings => write_config(
+                &paths.keybindings_kdl,
+                &generate_keybindings_kdl(&settings.keybindings),
+                strategy,
+            )?,
+            SettingsCategory::LayoutExtras => write_config(
+                &paths.layout_extras_kdl,
+                &generate_layout_extras_kdl(&settings.layout_extras),
+                strategy,
+            )?,
+            SettingsCategory::Gestures => write_config(
+                &paths.gestures_kdl,
+                &generate_gestures_kdl(&settings.gestures),
+                strategy,
+            )?,
+            SettingsCategory::LayerRules => write_config(
+                &paths.layer_rules_kdl,
+                &generate_layer_rules_kdl(&settings.layer_rules),
+                strategy,
+            )?,
+            SettingsCategory::WindowRules => write_config(
+                &paths.window_rules_kdl,
+                &generate_window_rules_kdl(
+                    &settings.window_rules,
+                    settings.preferences.float_settings_app,
+                ),
+                strategy,
+            )?,
+            SettingsCategory::Miscellaneous => write_config(
+                &paths.misc_kdl,
+                &generate_misc_kdl(&settings.miscellaneous),
+                strategy,
+            )?,
+            SettingsCategory::Startup => write_config(
+                &paths.startup_kdl,
+                &generate_startup_kdl(&settings.startup),
+                strategy,
+            )?,
+            SettingsCategory::Environment => write_config(
+                &paths.environment_kdl,
+                &generate_environment_kdl(&settings.environment),
+                strategy,
+            )?,
+            SettingsCategory::Debug => write_config(
+                &paths.debug_kdl,
+                &generate_debug_kdl(&settings.debug),
+                strategy,
+            )?,
+            SettingsCategory::SwitchEvents => write_config(
+                &paths.switch_events_kdl,
+                &generate_switch_events_kdl(&settings.switch_events),
+                strategy,
+            )?,
             SettingsCategory::RecentWindows => {
                 // Recent windows requires niri 25.11+
                 if compat.recent_windows {
@@ -553,21 +542,21 @@ pub fn save_dirty(
                         &paths.recent_windows_kdl,
                         &generate_recent_windows_kdl(&settings.recent_windows),
                         strategy,
-                    )?;
+                    )?
                 } else {
                     // Skip writing this file, don't count as written
                     continue;
                 }
             }
-            SettingsCategory::Preferences => {
-                write_config(
-                    &paths.preferences_kdl,
-                    &generate_preferences_kdl(&settings.preferences),
-                    strategy,
-                )?;
-            }
+            SettingsCategory::Preferences => write_config(
+                &paths.preferences_kdl,
+                &generate_preferences_kdl(&settings.preferences),
+                strategy,
+            )?,
+        };
+        if written {
+            files_written += 1;
         }
-        files_written += 1;
     }
 
     Ok(files_written)
@@ -658,6 +647,7 @@ mod tests {
     fn test_generate_main_kdl_skips_recent_windows_when_disabled() {
         let compat = FeatureCompat {
             recent_windows: false,
+            ..FeatureCompat::all_enabled()
         };
         let content = generate_main_kdl(compat);
         assert!(!content.contains("include \"advanced/recent-windows.kdl\""));
@@ -748,14 +738,47 @@ mod tests {
     fn test_generate_keyboard_kdl() {
         use crate::config::models::KeyboardSettings;
         let keyboard = KeyboardSettings::default();
-        let content = generate_keyboard_kdl(&keyboard);
+        let content = generate_keyboard_kdl(&keyboard, false);
 
         assert!(content.contains("input {"));
         assert!(content.contains("keyboard {"));
         assert!(content.contains("xkb {"));
         assert!(content.contains("layout \"us\""));
-        assert!(content.contains("repeat-delay"));
-        assert!(content.contains("repeat-rate"));
+        // Default repeat values are left to niri
+        assert!(!content.contains("repeat-delay"));
+        assert!(!content.contains("repeat-rate"));
+    }
+
+    #[test]
+    fn test_minimal_vs_verbose_input_output() {
+        let mut settings = Settings::default();
+        settings.keyboard.repeat_rate = 40;
+        settings.mouse.accel_speed = 0.5;
+
+        let minimal_keyboard = generate_keyboard_kdl(&settings.keyboard, false);
+        let verbose_keyboard = generate_keyboard_kdl(&settings.keyboard, true);
+        assert!(minimal_keyboard.contains("repeat-rate 40"));
+        assert!(!minimal_keyboard.contains("repeat-delay"));
+        assert!(!minimal_keyboard.contains("track-layout"));
+        assert!(verbose_keyboard.contains("repeat-rate 40"));
+        assert!(verbose_keyboard.contains("repeat-delay 600"));
+        assert!(verbose_keyboard.contains("track-layout \"global\""));
+
+        let minimal_mouse = generate_mouse_kdl(&settings.mouse, false);
+        let verbose_mouse = generate_mouse_kdl(&settings.mouse, true);
+        assert!(minimal_mouse.contains("accel-speed 0.50"));
+        assert!(!minimal_mouse.contains("accel-profile"));
+        assert!(!minimal_mouse.contains("scroll-factor"));
+        assert!(verbose_mouse.contains("accel-speed 0.50"));
+        assert!(verbose_mouse.contains("accel-profile \"adaptive\""));
+        assert!(verbose_mouse.contains("scroll-factor 1.00"));
+
+        // Untouched devices shrink to an empty section in minimal mode
+        let minimal_trackball = generate_trackball_kdl(&settings.trackball, false);
+        let verbose_trackball = generate_trackball_kdl(&settings.trackball, true);
+        assert!(!minimal_trackball.contains("accel"));
+        assert!(verbose_trackball.contains("accel-speed 0.00"));
+        assert!(verbose_trackball.contains("scroll-method \"on-button-down\""));
     }
 
     #[test]
@@ -812,4 +835,65 @@ mod tests {
 
         assert!(content.contains("backdrop-color \"#ff0000\""));
     }
+
+    #[test]
+    fn test_boolean_options_use_niri_form() {
+        use crate::config::models::{
+            Keybinding, KeybindingsSettings, TouchpadSettings, WindowRule, WindowRuleMatch,
+            WindowRulesSettings,
+        };
+
+        // Flags are bare when true and left out when false; drag takes an argument
+        let touchpad = TouchpadSettings {
+            tap: true,
+            dwt: false,
+            natural_scroll: true,
+            drag: false,
+            ..Default::default()
+        };
+        let content = generate_touchpad_kdl(&touchpad, false, FeatureCompat::all_enabled());
+        let lines: Vec<&str> = content.lines().map(str::trim).collect();
+        assert!(lines.contains(&"tap"));
+        assert!(lines.contains(&"natural-scroll"));
+        assert!(lines.contains(&"drag false"));
+        assert!(!content.contains("dwt"));
+        assert!(!content.contains("tap true"));
+
+        // Rule options keep an explicit false, which overrides earlier rules
+        let rules = WindowRulesSettings {
+            rules: vec![WindowRule {
+                matches: vec![WindowRuleMatch {
+                    is_floating: Some(false),
+                    ..Default::default()
+                }],
+                open_focused: Some(false),
+                open_maximized_to_edges: Some(false),
+                draw_border_with_background: Some(true),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let content = generate_window_rules_kdl(&rules, false);
+        let lines: Vec<&str> = content.lines().map(str::trim).collect();
+        assert!(lines.contains(&"match is-floating=false"));
+        assert!(lines.contains(&"open-focused false"));
+        assert!(lines.contains(&"open-maximized-to-edges false"));
+        assert!(lines.contains(&"draw-border-with-background true"));
+
+        // Binds repeat unless `repeat=false` is written
+        let mut binding = Keybinding {
+            key_combo: "Mod+Q".to_string(),
+            action: crate::config::models::KeybindAction::NiriAction("close-window".to_string()),
+            ..Default::default()
+        };
+        let keybindings = |binding: &Keybinding| {
+            generate_keybindings_kdl(&KeybindingsSettings {
+                bindings: vec![binding.clone()],
+                ..Default::default()
+            })
+        };
+        assert!(!keybindings(&binding).contains("repeat"));
+        binding.repeat = false;
+        assert!(keybindings(&binding).contains("Mod+Q repeat=false {"));
+    }
 }
@@ -3,7 +3,8 @@
 //! Preferences are app-specific settings (like UI theme) that are not part
 //! of niri's configuration.
 
-use crate::config::models::PreferencesSettings;
+use super::helpers::escape_kdl_string;
+use crate::config::models::{BackupRetention, PreferencesSettings};
 
 /// Generate KDL content for preferences
 pub fn generate_preferences_kdl(prefs: &PreferencesSettings) -> String {
@@ -29,6 +30,41 @@ pub fn generate_preferences_kdl(prefs: &PreferencesSettings) -> String {
     // Search hotkey (keyboard shortcut)
     lines.push(format!("    search-hotkey \"{}\"", prefs.search_hotkey));
 
+    // Verbose output mode for the generated input device files
+    lines.push(format!("    emit-all-defaults {}", prefs.emit_all_defaults));
+
+    // Which backups to keep: a count, an age in days, or all of them
+    lines.push(match prefs.backup_retention {
+        BackupRetention::Count(keep) => format!("    backup-retention {}", keep),
+        BackupRetention::Age(_) => format!(
+            "    backup-retention days={}",
+            prefs.backup_retention.age_days().unwrap_or(1)
+        ),
+        BackupRetention::Unlimited => "    backup-retention \"unlimited\"".to_string(),
+    });
+
+    // Border colors follow the focus ring colors
+    lines.push(format!(
+        "    link-border-colors {}",
+        prefs.link_border_colors
+    ));
+
+    // App accent follows the focus ring color
+    lines.push(format!("    match-niri-colors {}", prefs.match_niri_colors));
+
+    // Reload niri after saving
+    lines.push(format!("    auto-reload {}", prefs.auto_reload));
+
+    // Starred settings, one argument per setting name
+    if !prefs.favorites.is_empty() {
+        let names: Vec<String> = prefs
+            .favorites
+            .iter()
+            .map(|name| format!("\"{}\"", escape_kdl_string(name)))
+            .collect();
+        lines.push(format!("    favorites {}", names.join(" ")));
+    }
+
     lines.push("}".to_string());
     lines.push("".to_string()); // Trailing newline
 
@@ -46,6 +82,12 @@ mod tests {
             float_settings_app: true,
             show_search_bar: true,
             search_hotkey: "Ctrl+K".to_string(),
+            favorites: vec!["Window Gaps".to_string(), "Corner Radius".to_string()],
+            emit_all_defaults: true,
+            backup_retention: BackupRetention::Count(25),
+            link_border_colors: true,
+            match_niri_colors: true,
+            auto_reload: false,
         };
 
         let kdl = generate_preferences_kdl(&prefs);
@@ -55,6 +97,12 @@ mod tests {
         assert!(kdl.contains("float-settings-app true"));
         assert!(kdl.contains("show-search-bar true"));
         assert!(kdl.contains("search-hotkey \"Ctrl+K\""));
+        assert!(kdl.contains("favorites \"Window Gaps\" \"Corner Radius\""));
+        assert!(kdl.contains("emit-all-defaults true"));
+        assert!(kdl.contains("backup-retention 25"));
+        assert!(kdl.contains("link-border-colors true"));
+        assert!(kdl.contains("match-niri-colors true"));
+        assert!(kdl.contains("auto-reload false"));
     }
 
     #[test]
@@ -64,6 +112,12 @@ mod tests {
             float_settings_app: false,
             show_search_bar: false,
             search_hotkey: "Ctrl+/".to_string(),
+            favorites: Vec::new(),
+            emit_all_defaults: false,
+            backup_retention: BackupRetention::days(7),
+            link_border_colors: false,
+            match_niri_colors: false,
+            auto_reload: true,
         };
 
         let kdl = generate_preferences_kdl(&prefs);
@@ -72,5 +126,7 @@ mod tests {
         assert!(kdl.contains("float-settings-app false"));
         assert!(kdl.contains("show-search-bar false"));
         assert!(kdl.contains("search-hotkey \"Ctrl+/\""));
+        assert!(!kdl.contains("favorites"));
+        assert!(kdl.contains("backup-retention days=7"));
     }
 }
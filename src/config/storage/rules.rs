@@ -4,6 +4,8 @@
 //!
 //! Uses `build_match_line` helper to reduce duplication in match criteria generation.
 
+use super::booleans::{bool_property, push_bool};
+use super::gradient::color_or_gradient_to_kdl;
 use super::helpers::escape_kdl_string;
 use crate::config::models::{BlockOutFrom, LayerRulesSettings, OpenBehavior, WindowRulesSettings};
 
@@ -52,7 +54,7 @@ fn add_string_criterion(parts: &mut Vec<String>, name: &str, value: &Option<Stri
 /// Helper to add a boolean criterion to match parts
 fn add_bool_criterion(parts: &mut Vec<String>, name: &str, value: Option<bool>) {
     if let Some(v) = value {
-        parts.push(format!("{}={}", name, v));
+        parts.push(bool_property(name, v));
     }
 }
 
@@ -118,15 +120,18 @@ pub fn generate_layer_rules_kdl(settings: &LayerRulesSettings) -> String {
                 content.push_str(&format!("    geometry-corner-radius {}\n", radius));
             }
 
-            // place-within-backdrop
-            if rule.place_within_backdrop {
-                content.push_str("    place-within-backdrop true\n");
-            }
-
-            // baba-is-float
-            if rule.baba_is_float {
-                content.push_str("    baba-is-float true\n");
-            }
+            push_bool(
+                &mut content,
+                "    ",
+                "place-within-backdrop",
+                rule.place_within_backdrop.then_some(true),
+            );
+            push_bool(
+                &mut content,
+                "    ",
+                "baba-is-float",
+                rule.baba_is_float.then_some(true),
+            );
 
             // shadow
             if let Some(ref shadow) = rule.shadow {
@@ -144,9 +149,12 @@ pub fn generate_layer_rules_kdl(settings: &LayerRulesSettings) -> String {
                         "        inactive-color \"{}\"\n",
                         shadow.inactive_color.to_hex()
                     ));
-                    if shadow.draw_behind_window {
-                        content.push_str("        draw-behind-window true\n");
-                    }
+                    push_bool(
+                        &mut content,
+                        "        ",
+                        "draw-behind-window",
+                        shadow.draw_behind_window.then_some(true),
+                    );
                     content.push_str("    }\n");
                 }
             }
@@ -183,17 +191,17 @@ pub fn generate_window_rules_kdl(
         content.push_str("// Auto-generated: Float Nirify app\n");
         content.push_str("window-rule {\n");
         content.push_str("    match app-id=\"^nirify$\"\n");
-        content.push_str("    open-floating true\n");
+        push_bool(&mut content, "    ", "open-floating", Some(true));
         content.push_str("}\n\n");
     }
 
     let has_disabled = settings.rules.iter().any(|r| !r.enabled);
     if has_disabled {
-        content.push_str("// Note: Some rules below use the /- prefix (disabled via Nirify)\n");
+        content.push_str("// Note: Some rules below use the /- prefix (disabled via Nirify)\n\n");
     }
-    content.push_str("\n");
 
-    if settings.rules.is_empty() && !float_settings_app {
+    // The auto-generated rule isn't the user's, so it doesn't replace the placeholder
+    if settings.rules.is_empty() {
         content.push_str("// No window rules configured yet.\n");
         content.push_str("// Add rules through the UI or manually here.\n");
         content.push_str("// Example:\n");
@@ -239,18 +247,15 @@ pub fn generate_window_rules_kdl(
                 });
             }
 
-            // Opening behavior (niri expects an explicit boolean argument)
-            match rule.open_behavior {
-                OpenBehavior::Maximized => {
-                    content.push_str("    open-maximized true\n");
-                }
-                OpenBehavior::Fullscreen => {
-                    content.push_str("    open-fullscreen true\n");
-                }
-                OpenBehavior::Floating => {
-                    content.push_str("    open-floating true\n");
-                }
-                OpenBehavior::Normal => {}
+            // Opening behavior
+            let open_option = match rule.open_behavior {
+                OpenBehavior::Maximized => Some("open-maximized"),
+                OpenBehavior::Fullscreen => Some("open-fullscreen"),
+                OpenBehavior::Floating => Some("open-floating"),
+                OpenBehavior::Normal => None,
+            };
+            if let Some(option) = open_option {
+                push_bool(&mut content, "    ", option, Some(true));
             }
 
             // Default floating position (for floating windows)
@@ -263,14 +268,7 @@ pub fn generate_window_rules_kdl(
                 ));
             }
 
-            // Open focused
-            if let Some(focused) = rule.open_focused {
-                if focused {
-                    content.push_str("    open-focused true\n");
-                } else {
-                    content.push_str("    open-focused false\n");
-                }
-            }
+            push_bool(&mut content, "    ", "open-focused", rule.open_focused);
 
             // Open on specific output
             if let Some(ref output) = rule.open_on_output {
@@ -298,10 +296,12 @@ pub fn generate_window_rules_kdl(
                 content.push_str(&format!("    geometry-corner-radius {}\n", radius));
             }
 
-            // Clip to geometry
-            if let Some(clip) = rule.clip_to_geometry {
-                content.push_str(&format!("    clip-to-geometry {}\n", clip));
-            }
+            push_bool(
+                &mut content,
+                "    ",
+                "clip-to-geometry",
+                rule.clip_to_geometry,
+            );
 
             // Block from screencast
             if rule.block_out_from_screencast {
@@ -309,7 +309,12 @@ pub fn generate_window_rules_kdl(
             }
 
             // Default column width
-            if let Some(width) = rule.default_column_width {
+            if let Some(fixed) = rule.default_column_width_fixed {
+                content.push_str(&format!(
+                    "    default-column-width {{ fixed {}; }}\n",
+                    fixed
+                ));
+            } else if let Some(width) = rule.default_column_width {
                 content.push_str(&format!(
                     "    default-column-width {{ proportion {:.2}; }}\n",
                     width
@@ -324,20 +329,24 @@ pub fn generate_window_rules_kdl(
                 ));
             }
 
-            // Open maximized to edges
-            if let Some(true) = rule.open_maximized_to_edges {
-                content.push_str("    open-maximized-to-edges true\n");
-            }
+            push_bool(
+                &mut content,
+                "    ",
+                "open-maximized-to-edges",
+                rule.open_maximized_to_edges,
+            );
 
             // Scroll factor
             if let Some(factor) = rule.scroll_factor {
                 content.push_str(&format!("    scroll-factor {:.2}\n", factor));
             }
 
-            // Draw border with background
-            if let Some(true) = rule.draw_border_with_background {
-                content.push_str("    draw-border-with-background true\n");
-            }
+            push_bool(
+                &mut content,
+                "    ",
+                "draw-border-with-background",
+                rule.draw_border_with_background,
+            );
 
             // Size constraints
             if let Some(min) = rule.min_width {
@@ -368,13 +377,22 @@ pub fn generate_window_rules_kdl(
                     content.push_str(&format!("        width {}\n", width));
                 }
                 if let Some(ref color) = rule.focus_ring_active {
-                    content.push_str(&format!("        active-color \"{}\"\n", color.to_hex()));
+                    content.push_str(&format!(
+                        "        {}\n",
+                        color_or_gradient_to_kdl(color, "active")
+                    ));
                 }
                 if let Some(ref color) = rule.focus_ring_inactive {
-                    content.push_str(&format!("        inactive-color \"{}\"\n", color.to_hex()));
+                    content.push_str(&format!(
+                        "        {}\n",
+                        color_or_gradient_to_kdl(color, "inactive")
+                    ));
                 }
                 if let Some(ref color) = rule.focus_ring_urgent {
-                    content.push_str(&format!("        urgent-color \"{}\"\n", color.to_hex()));
+                    content.push_str(&format!(
+                        "        {}\n",
+                        color_or_gradient_to_kdl(color, "urgent")
+                    ));
                 }
                 content.push_str("    }\n");
             }
@@ -394,21 +412,32 @@ pub fn generate_window_rules_kdl(
                     content.push_str(&format!("        width {}\n", width));
                 }
                 if let Some(ref color) = rule.border_active {
-                    content.push_str(&format!("        active-color \"{}\"\n", color.to_hex()));
+                    content.push_str(&format!(
+                        "        {}\n",
+                        color_or_gradient_to_kdl(color, "active")
+                    ));
                 }
                 if let Some(ref color) = rule.border_inactive {
-                    content.push_str(&format!("        inactive-color \"{}\"\n", color.to_hex()));
+                    content.push_str(&format!(
+                        "        {}\n",
+                        color_or_gradient_to_kdl(color, "inactive")
+                    ));
                 }
                 if let Some(ref color) = rule.border_urgent {
-                    content.push_str(&format!("        urgent-color \"{}\"\n", color.to_hex()));
+                    content.push_str(&format!(
+                        "        {}\n",
+                        color_or_gradient_to_kdl(color, "urgent")
+                    ));
                 }
                 content.push_str("    }\n");
             }
 
-            // Variable refresh rate (boolean, not string)
-            if let Some(vrr) = rule.variable_refresh_rate {
-                content.push_str(&format!("    variable-refresh-rate {}\n", vrr));
-            }
+            push_bool(
+                &mut content,
+                "    ",
+                "variable-refresh-rate",
+                rule.variable_refresh_rate,
+            );
 
             // Default column display
             if let Some(ref display) = rule.default_column_display {
@@ -421,15 +450,8 @@ pub fn generate_window_rules_kdl(
                 }
             }
 
-            // Tiled state
-            if let Some(tiled) = rule.tiled_state {
-                content.push_str(&format!("    tiled-state {}\n", tiled));
-            }
-
-            // Baba is float
-            if rule.baba_is_float == Some(true) {
-                content.push_str("    baba-is-float true\n");
-            }
+            push_bool(&mut content, "    ", "tiled-state", rule.tiled_state);
+            push_bool(&mut content, "    ", "baba-is-float", rule.baba_is_float);
 
             // Per-window shadow settings
             if let Some(ref shadow) = rule.shadow {
@@ -449,9 +471,12 @@ pub fn generate_window_rules_kdl(
                         "        inactive-color \"{}\"\n",
                         shadow.inactive_color.to_hex()
                     ));
-                    if shadow.draw_behind_window {
-                        content.push_str("        draw-behind-window true\n");
-                    }
+                    push_bool(
+                        &mut content,
+                        "        ",
+                        "draw-behind-window",
+                        shadow.draw_behind_window.then_some(true),
+                    );
                     content.push_str("    }\n");
                 }
             }
@@ -633,6 +658,38 @@ mod tests {
             .expect("generated window-rules KDL should parse");
     }
 
+    #[test]
+    fn placement_and_column_width_are_written() {
+        let settings = WindowRulesSettings {
+            rules: vec![
+                WindowRule {
+                    open_on_output: Some("DP-1".to_string()),
+                    open_on_workspace: Some("browser".to_string()),
+                    default_column_width: Some(0.75),
+                    ..Default::default()
+                },
+                WindowRule {
+                    // A fixed width wins over a leftover proportion
+                    default_column_width: Some(0.5),
+                    default_column_width_fixed: Some(1200),
+                    ..Default::default()
+                },
+            ],
+            next_id: 2,
+        };
+
+        let content = generate_window_rules_kdl(&settings, false);
+
+        assert!(content.contains("open-on-output \"DP-1\""));
+        assert!(content.contains("open-on-workspace \"browser\""));
+        assert!(content.contains("default-column-width { proportion 0.75; }"));
+        assert!(content.contains("default-column-width { fixed 1200; }"));
+        assert!(!content.contains("proportion 0.50"));
+        content
+            .parse::<kdl::KdlDocument>()
+            .expect("generated window-rules KDL should parse");
+    }
+
     #[test]
     fn boolean_layer_rule_properties_are_written_with_arguments() {
         use crate::config::models::LayerRule;
@@ -654,4 +711,25 @@ mod tests {
             .parse::<kdl::KdlDocument>()
             .expect("generated layer-rules KDL should parse");
     }
+
+    #[test]
+    fn generate_window_rules_kdl_empty_is_minimal() {
+        let placeholder = "// No window rules configured yet.\n\
+                           // Add rules through the UI or manually here.\n\
+                           // Example:\n\
+                           // window-rule {\n\
+                           //     match app-id=\"firefox\"\n\
+                           //     open-maximized true\n\
+                           // }\n";
+        let settings = WindowRulesSettings::default();
+
+        assert_eq!(
+            generate_window_rules_kdl(&settings, false),
+            format!("// Window rules - managed by Nirify\n\n{}", placeholder)
+        );
+        // The float rule is added on top; the user section stays the same
+        let with_float = generate_window_rules_kdl(&settings, true);
+        assert!(with_float.contains("match app-id=\"^nirify$\""));
+        assert!(with_float.ends_with(&format!("}}\n\n{}", placeholder)));
+    }
 }
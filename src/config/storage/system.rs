@@ -41,22 +41,26 @@ pub fn generate_startup_kdl(settings: &StartupSettings) -> String {
     kdl.comment("These commands run when niri starts.");
     kdl.newline();
 
-    if settings.commands.is_empty() {
+    let commands: Vec<_> = settings
+        .commands
+        .iter()
+        .filter(|cmd| !cmd.command.is_empty())
+        .collect();
+
+    if commands.is_empty() {
         kdl.comment("No startup commands configured.");
         kdl.comment("Example:");
         kdl.comment("spawn-at-startup \"waybar\"");
         kdl.comment("spawn-at-startup \"swww-daemon\"");
         kdl.comment("spawn-at-startup \"bash\" \"-c\" \"command with args\"");
     } else {
-        for cmd in &settings.commands {
-            if !cmd.command.is_empty() {
-                let args: Vec<String> = cmd
-                    .command
-                    .iter()
-                    .map(|a| format!("\"{}\"", escape_kdl_string(a)))
-                    .collect();
-                kdl.raw(&format!("spawn-at-startup {}", args.join(" ")));
-            }
+        for cmd in commands {
+            let args: Vec<String> = cmd
+                .command
+                .iter()
+                .map(|a| format!("\"{}\"", escape_kdl_string(a)))
+                .collect();
+            kdl.raw(&format!("spawn-at-startup {}", args.join(" ")));
         }
     }
 
@@ -72,7 +76,25 @@ pub fn generate_environment_kdl(settings: &EnvironmentSettings) -> String {
     kdl.comment("These are set for all processes spawned by niri.");
     kdl.newline();
 
-    if settings.variables.is_empty() {
+    // Validate environment variable names follow POSIX conventions
+    // Must start with letter or underscore, contain only alphanumeric and underscore
+    let variables: Vec<_> = settings
+        .variables
+        .iter()
+        .filter(|var| !var.name.is_empty())
+        .filter(|var| {
+            let valid = is_valid_env_var_name(&var.name);
+            if !valid {
+                warn!(
+                    "Skipping invalid environment variable name: {:?} (must match [A-Za-z_][A-Za-z0-9_]*)",
+                    var.name
+                );
+            }
+            valid
+        })
+        .collect();
+
+    if variables.is_empty() {
         kdl.comment("No environment variables configured.");
         kdl.comment("Example:");
         kdl.comment("environment {");
@@ -81,19 +103,8 @@ pub fn generate_environment_kdl(settings: &EnvironmentSettings) -> String {
         kdl.comment("}");
     } else {
         kdl.block("environment", |b| {
-            for var in &settings.variables {
-                if !var.name.is_empty() {
-                    // Validate environment variable name follows POSIX conventions
-                    // Must start with letter or underscore, contain only alphanumeric and underscore
-                    if is_valid_env_var_name(&var.name) {
-                        b.field_string(&var.name, &var.value);
-                    } else {
-                        warn!(
-                            "Skipping invalid environment variable name: {:?} (must match [A-Za-z_][A-Za-z0-9_]*)",
-                            var.name
-                        );
-                    }
-                }
+            for var in &variables {
+                b.field_string(&var.name, &var.value);
             }
         });
     }
@@ -7,6 +7,9 @@ pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Directory name for our managed config files
 pub const CONFIG_DIR_NAME: &str = "nirify";
 
+/// Environment variable overriding the niri config directory (`~/.config/niri`)
+pub const CONFIG_DIR_ENV: &str = "NIRIFY_CONFIG_DIR";
+
 /// Main include file name
 pub const MAIN_KDL_NAME: &str = "main.kdl";
 
@@ -81,6 +84,9 @@ pub const DEFAULT_REPEAT_RATE: i32 = 25;
 /// Default cursor size
 pub const DEFAULT_CURSOR_SIZE: i32 = 24;
 
+/// Inactivity timeout used when hide-after-inactive-ms is first enabled (ms)
+pub const DEFAULT_CURSOR_HIDE_AFTER_MS: i32 = 3000;
+
 /// Cursor size range (pixels)
 pub const CURSOR_SIZE_MIN: i32 = 16;
 pub const CURSOR_SIZE_MAX: i32 = 64;
@@ -165,7 +171,24 @@ pub const EASING_DURATION_DEFAULT: i32 = 150;
 pub const SAVE_DEBOUNCE_MS: u64 = 300;
 
 /// Debounce delay for search input (milliseconds)
-pub const SEARCH_DEBOUNCE_MS: u64 = 200;
+///
+/// The search runs once typing pauses this long, so a burst of keystrokes
+/// costs one search instead of one per character.
+pub const SEARCH_DEBOUNCE_MS: u64 = 100;
+
+/// Number of config.kdl backups kept by default
+pub const DEFAULT_BACKUP_RETENTION: usize = 10;
+
+/// Range offered for the backup retention preference
+pub const BACKUP_RETENTION_MIN: usize = 1;
+pub const BACKUP_RETENTION_MAX: usize = 100;
+
+/// Days backups are kept for by default when retention goes by age
+pub const DEFAULT_BACKUP_RETENTION_DAYS: u64 = 14;
+
+/// Range offered for keeping backups by age, in days
+pub const BACKUP_RETENTION_DAYS_MIN: u64 = 1;
+pub const BACKUP_RETENTION_DAYS_MAX: u64 = 365;
 
 /// Toast notification auto-dismiss delay (milliseconds)
 pub const TOAST_DISMISS_MS: u64 = 3000;
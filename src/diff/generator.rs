@@ -4,19 +4,22 @@ use super::types::{CategoryDiff, ConfigDiff, DiffLine, DiffLineType};
 use crate::config::paths::ConfigPaths;
 use crate::config::storage::*;
 use crate::config::{Settings, SettingsCategory};
+use crate::version::FeatureCompat;
 use std::collections::HashSet;
 use std::fs;
+use std::path::PathBuf;
 
 /// Generate a diff between current settings and what's on disk
 pub fn generate_diff(
     settings: &Settings,
     paths: &ConfigPaths,
     dirty_categories: &HashSet<SettingsCategory>,
+    compat: FeatureCompat,
 ) -> ConfigDiff {
     let mut diff = ConfigDiff::new();
 
     for category in dirty_categories {
-        if let Some(cat_diff) = generate_category_diff(settings, paths, *category) {
+        if let Some(cat_diff) = generate_category_diff(settings, paths, *category, compat) {
             if cat_diff.has_changes {
                 diff.add_category(cat_diff);
             }
@@ -26,13 +29,59 @@ pub fn generate_diff(
     diff
 }
 
+/// Generate a diff between current settings and the last saved snapshot
+///
+/// Unlike [`generate_diff`] this never reads from disk, so only edits made
+/// since the last successful save show up.
+pub fn generate_pending_diff(
+    settings: &Settings,
+    last_saved: &Settings,
+    paths: &ConfigPaths,
+    compat: FeatureCompat,
+) -> ConfigDiff {
+    let mut diff = ConfigDiff::new();
+
+    for &category in SettingsCategory::all() {
+        let (name, file_path, new_content) = category_content(settings, paths, category, compat);
+        let (_, _, old_content) = category_content(last_saved, paths, category, compat);
+        let cat_diff = compute_line_diff(name, file_path, &old_content, &new_content);
+        if cat_diff.has_changes {
+            diff.add_category(cat_diff);
+        }
+    }
+
+    diff
+}
+
 /// Generate diff for a single category
 fn generate_category_diff(
     settings: &Settings,
     paths: &ConfigPaths,
     category: SettingsCategory,
+    compat: FeatureCompat,
 ) -> Option<CategoryDiff> {
-    let (name, file_path, new_content) = match category {
+    let (name, file_path, new_content) = category_content(settings, paths, category, compat);
+
+    // Read old content from disk
+    let old_content = fs::read_to_string(&file_path).unwrap_or_default();
+
+    // Generate line diff
+    Some(compute_line_diff(
+        name,
+        file_path,
+        &old_content,
+        &new_content,
+    ))
+}
+
+/// Category display name, file path and the KDL that would be written for it
+fn category_content(
+    settings: &Settings,
+    paths: &ConfigPaths,
+    category: SettingsCategory,
+    compat: FeatureCompat,
+) -> (&'static str, PathBuf, String) {
+    match category {
         SettingsCategory::Appearance => (
             "Appearance",
             paths.appearance_kdl.clone(),
@@ -46,27 +95,31 @@ fn generate_category_diff(
         SettingsCategory::Keyboard => (
             "Keyboard",
             paths.keyboard_kdl.clone(),
-            generate_keyboard_kdl(&settings.keyboard),
+            generate_keyboard_kdl(&settings.keyboard, settings.preferences.emit_all_defaults),
         ),
         SettingsCategory::Mouse => (
             "Mouse",
             paths.mouse_kdl.clone(),
-            generate_mouse_kdl(&settings.mouse),
+            generate_mouse_kdl(&settings.mouse, settings.preferences.emit_all_defaults),
         ),
         SettingsCategory::Touchpad => (
             "Touchpad",
             paths.touchpad_kdl.clone(),
-            generate_touchpad_kdl(&settings.touchpad),
+            generate_touchpad_kdl(
+                &settings.touchpad,
+                settings.preferences.emit_all_defaults,
+                compat,
+            ),
         ),
         SettingsCategory::Trackpoint => (
             "Trackpoint",
             paths.trackpoint_kdl.clone(),
-            generate_trackpoint_kdl(&settings.trackpoint),
+            generate_trackpoint_kdl(&settings.trackpoint, settings.preferences.emit_all_defaults),
         ),
         SettingsCategory::Trackball => (
             "Trackball",
             paths.trackball_kdl.clone(),
-            generate_trackball_kdl(&settings.trackball),
+            generate_trackball_kdl(&settings.trackball, settings.preferences.emit_all_defaults),
         ),
         SettingsCategory::Tablet => (
             "Tablet",
@@ -116,7 +169,10 @@ fn generate_category_diff(
         SettingsCategory::WindowRules => (
             "Window Rules",
             paths.window_rules_kdl.clone(),
-            generate_window_rules_kdl(&settings.window_rules),
+            generate_window_rules_kdl(
+                &settings.window_rules,
+                settings.preferences.float_settings_app,
+            ),
         ),
         SettingsCategory::LayerRules => (
             "Layer Rules",
@@ -158,24 +214,18 @@ fn generate_category_diff(
             paths.recent_windows_kdl.clone(),
             generate_recent_windows_kdl(&settings.recent_windows),
         ),
-    };
-
-    // Read old content from disk
-    let old_content = fs::read_to_string(&file_path).unwrap_or_default();
-
-    // Generate line diff
-    Some(compute_line_diff(
-        name,
-        file_path,
-        &old_content,
-        &new_content,
-    ))
+        SettingsCategory::Preferences => (
+            "Preferences",
+            paths.preferences_kdl.clone(),
+            generate_preferences_kdl(&settings.preferences),
+        ),
+    }
 }
 
 /// Compute a line-by-line diff between old and new content
 fn compute_line_diff(
     name: &str,
-    file_path: std::path::PathBuf,
+    file_path: PathBuf,
     old_content: &str,
     new_content: &str,
 ) -> CategoryDiff {
@@ -342,6 +392,38 @@ mod tests {
         assert_eq!(diff.deletions, 1);
     }
 
+    #[test]
+    fn test_pending_diff_shows_only_edited_field() {
+        let paths = ConfigPaths::with_niri_dir("/nonexistent/niri");
+        let last_saved = Settings::default();
+        let mut settings = last_saved.clone();
+        assert!(!generate_pending_diff(
+            &settings,
+            &last_saved,
+            &paths,
+            FeatureCompat::all_enabled()
+        )
+        .has_changes());
+
+        settings.appearance.gaps = 24.0;
+        let diff =
+            generate_pending_diff(&settings, &last_saved, &paths, FeatureCompat::all_enabled());
+
+        assert_eq!(diff.categories.len(), 1);
+        let category = &diff.categories[0];
+        assert_eq!(category.name, "Appearance");
+        assert_eq!(category.file_path, paths.appearance_kdl);
+        assert_eq!((category.additions, category.deletions), (1, 1));
+
+        let changed: Vec<_> = category
+            .lines
+            .iter()
+            .filter(|line| line.line_type != DiffLineType::Unchanged)
+            .collect();
+        assert!(changed[0].old_text.contains("gaps"));
+        assert!(changed[1].new_text.contains("gaps 24"));
+    }
+
     #[test]
     fn test_compute_line_diff_modifications() {
         let old = "line1\nold_line\nline3";
@@ -6,5 +6,5 @@
 mod generator;
 mod types;
 
-pub use generator::generate_diff;
+pub use generator::{generate_diff, generate_pending_diff};
 pub use types::{CategoryDiff, ConfigDiff, DiffLine, DiffLineType};
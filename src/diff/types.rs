@@ -25,7 +25,7 @@ impl DiffLineType {
 }
 
 /// A single line in a diff
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DiffLine {
     /// Type of change
     pub line_type: DiffLineType,
@@ -38,7 +38,7 @@ pub struct DiffLine {
 }
 
 /// Diff for a single category/file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CategoryDiff {
     /// Category name (e.g., "Appearance", "Keyboard")
     pub name: String,
@@ -74,7 +74,7 @@ impl CategoryDiff {
 }
 
 /// Complete diff across all changed categories
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct ConfigDiff {
     /// Diffs for each category that has changes
     pub categories: Vec<CategoryDiff>,
@@ -0,0 +1,173 @@
+//! Checks whether niri is actually using the managed config
+//!
+//! `config.kdl` can contain the include line and still be ignored by niri
+//! (e.g. a syntax error elsewhere makes it keep the previous config). The
+//! only settings niri reports back over IPC are output properties, so we
+//! compare the outputs we wrote against the live output state.
+
+use super::FullOutputInfo;
+use crate::config::models::OutputConfig;
+
+/// Scale difference tolerated when comparing configured vs live scale
+const SCALE_TOLERANCE: f64 = 0.01;
+
+/// Result of comparing applied settings against niri's live state
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigEffectiveness {
+    /// Every comparable setting matches; holds the number of outputs checked
+    Effective(usize),
+    /// At least one setting differs from what niri reports
+    Mismatch(Vec<String>),
+    /// Nothing we manage can be read back (no configured output is connected)
+    Unverifiable,
+}
+
+impl ConfigEffectiveness {
+    /// User-facing pass/fail summary for the tools page
+    pub fn summary(&self) -> Result<String, String> {
+        match self {
+            ConfigEffectiveness::Effective(count) => Ok(format!(
+                "niri is using Nirify's config ({} output{} verified)",
+                count,
+                if *count == 1 { "" } else { "s" }
+            )),
+            ConfigEffectiveness::Mismatch(diffs) => Err(format!(
+                "niri does not appear to be using Nirify's config: {}",
+                diffs.join("; ")
+            )),
+            ConfigEffectiveness::Unverifiable => {
+                Err("No configured output is connected, nothing to compare".to_string())
+            }
+        }
+    }
+}
+
+/// Compares configured outputs against the live outputs reported by niri
+///
+/// Only enabled outputs that are currently connected are compared; scale and
+/// transform are checked since niri reports both after applying the config.
+/// A scale of 1 isn't saved, so niri's own choice of scale is accepted then.
+#[must_use]
+pub fn compare_outputs(
+    configured: &[OutputConfig],
+    live: &[FullOutputInfo],
+) -> ConfigEffectiveness {
+    let mut checked = 0;
+    let mut mismatches = Vec::new();
+
+    for output in configured.iter().filter(|o| o.enabled) {
+        let Some(live_output) = live.iter().find(|l| l.name == output.name) else {
+            continue;
+        };
+        // A disabled-by-niri output has no logical state to compare against
+        if live_output.logical.is_none() {
+            continue;
+        }
+        checked += 1;
+
+        if output.has_scale() && (live_output.scale() - output.scale).abs() > SCALE_TOLERANCE {
+            mismatches.push(format!(
+                "{} scale is {} (expected {})",
+                output.name,
+                live_output.scale(),
+                output.scale
+            ));
+        }
+        if live_output.transform() != output.transform {
+            mismatches.push(format!(
+                "{} transform is {} (expected {})",
+                output.name,
+                live_output.transform(),
+                output.transform
+            ));
+        }
+    }
+
+    if !mismatches.is_empty() {
+        ConfigEffectiveness::Mismatch(mismatches)
+    } else if checked == 0 {
+        ConfigEffectiveness::Unverifiable
+    } else {
+        ConfigEffectiveness::Effective(checked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::OutputLogical;
+    use crate::types::Transform;
+
+    fn live(name: &str, scale: f64, transform: &str) -> FullOutputInfo {
+        FullOutputInfo {
+            name: name.to_string(),
+            logical: Some(OutputLogical {
+                x: 0,
+                y: 0,
+                scale,
+                transform: transform.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn configured(name: &str, scale: f64) -> OutputConfig {
+        OutputConfig {
+            name: name.to_string(),
+            scale,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matching_outputs_are_effective() {
+        let result = compare_outputs(
+            &[configured("DP-1", 1.5)],
+            &[live("DP-1", 1.5, "Normal"), live("HDMI-A-1", 1.0, "Normal")],
+        );
+        assert_eq!(result, ConfigEffectiveness::Effective(1));
+        assert!(result.summary().is_ok());
+    }
+
+    #[test]
+    fn test_scale_mismatch_is_reported() {
+        let result = compare_outputs(&[configured("DP-1", 2.0)], &[live("DP-1", 1.0, "Normal")]);
+        match result {
+            ConfigEffectiveness::Mismatch(diffs) => {
+                assert_eq!(diffs.len(), 1);
+                assert!(diffs[0].contains("DP-1 scale"));
+            }
+            other => panic!("Expected mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unsaved_scale_accepts_niris_choice() {
+        // A scale of 1 isn't written, so niri picked 2 for this HiDPI panel
+        let result = compare_outputs(&[configured("eDP-1", 1.0)], &[live("eDP-1", 2.0, "Normal")]);
+        assert_eq!(result, ConfigEffectiveness::Effective(1));
+    }
+
+    #[test]
+    fn test_transform_mismatch_is_reported() {
+        let mut output = configured("DP-1", 1.0);
+        output.transform = Transform::Rotate90;
+        let result = compare_outputs(&[output], &[live("DP-1", 1.0, "Normal")]);
+        assert!(matches!(result, ConfigEffectiveness::Mismatch(_)));
+        assert!(result.summary().is_err());
+    }
+
+    #[test]
+    fn test_disconnected_outputs_are_unverifiable() {
+        let result = compare_outputs(&[configured("DP-2", 1.5)], &[live("DP-1", 1.0, "Normal")]);
+        assert_eq!(result, ConfigEffectiveness::Unverifiable);
+    }
+
+    #[test]
+    fn test_disabled_outputs_are_skipped() {
+        let mut output = configured("DP-1", 2.0);
+        output.enabled = false;
+        let result = compare_outputs(&[output], &[live("DP-1", 1.0, "Normal")]);
+        assert_eq!(result, ConfigEffectiveness::Unverifiable);
+    }
+}
@@ -0,0 +1,344 @@
+//! Live updates from niri's event stream
+//!
+//! niri sends the full window, workspace and keyboard layout state when the
+//! stream opens, then only what changed (a window opened, a workspace was
+//! activated, the layout was switched, ...). A background thread keeps the
+//! state current from those changes and hands the whole list to the app each
+//! time, so the Tools and Keyboard pages never need a manual refresh. When
+//! niri goes away the thread reports it and reconnects with backoff.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::thread;
+use std::time::Duration;
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream, StreamExt};
+use iced::Subscription;
+use log::{debug, info, warn};
+use serde::Deserialize;
+
+use super::{
+    format_json_error, get_socket_path, ActionResponse, IpcError, IpcResult, KeyboardLayouts,
+    WindowInfo, WorkspaceInfo, MAX_RESPONSE_SIZE,
+};
+
+/// Wait before the first reconnect attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Longest wait between reconnect attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Change reported by niri's event stream
+#[derive(Debug, Clone)]
+pub enum NiriEvent {
+    /// Current list of all windows
+    WindowsChanged(Vec<WindowInfo>),
+    /// Current list of all workspaces
+    WorkspacesChanged(Vec<WorkspaceInfo>),
+    /// The focused window, `None` when nothing is focused
+    WindowFocusChanged(Option<u64>),
+    /// Current keyboard layouts, after a reload or a layout switch
+    KeyboardLayoutsChanged(KeyboardLayouts),
+    /// The stream closed or couldn't be opened; niri is likely not running
+    Disconnected,
+}
+
+/// Subscription to niri's event stream
+///
+/// Runs for as long as the app subscribes, reconnecting whenever niri
+/// restarts.
+pub fn event_stream() -> Subscription<NiriEvent> {
+    Subscription::run(event_worker)
+}
+
+fn event_worker() -> impl Stream<Item = NiriEvent> {
+    iced::stream::channel(32, |mut sender: mpsc::Sender<NiriEvent>| async move {
+        // Reads block, so they happen on their own thread
+        let (tx, mut rx) = mpsc::unbounded();
+        thread::spawn(move || run_event_loop(tx));
+
+        while let Some(event) = rx.next().await {
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Reads events until the subscription is dropped, reconnecting as needed
+fn run_event_loop(tx: mpsc::UnboundedSender<NiriEvent>) {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut reported_disconnect = false;
+
+    while !tx.is_closed() {
+        match connect_event_stream() {
+            Ok(mut reader) => {
+                info!("Connected to niri event stream");
+                backoff = INITIAL_BACKOFF;
+                reported_disconnect = false;
+
+                let mut state = EventState::default();
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    match reader.by_ref().take(MAX_RESPONSE_SIZE).read_line(&mut line) {
+                        Ok(0) => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            debug!("Event stream read failed: {}", e);
+                            break;
+                        }
+                    }
+                    if let Some(event) = state.apply_line(&line) {
+                        if tx.unbounded_send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+                warn!("niri event stream closed");
+            }
+            Err(e) => debug!("Couldn't open niri event stream: {}", e),
+        }
+
+        if !reported_disconnect {
+            if tx.unbounded_send(NiriEvent::Disconnected).is_err() {
+                return;
+            }
+            reported_disconnect = true;
+        }
+        thread::sleep(backoff);
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Opens the event stream, returning a reader positioned at the first event
+fn connect_event_stream() -> IpcResult<BufReader<UnixStream>> {
+    let socket_path = get_socket_path().ok_or(IpcError::NotRunning)?;
+    let mut stream = UnixStream::connect(&socket_path)
+        .map_err(|e| IpcError::ConnectionFailed(format!("Failed to connect: {}", e)))?;
+    stream
+        .write_all(b"\"EventStream\"\n")
+        .map_err(|e| IpcError::IoError(format!("Failed to write: {}", e)))?;
+
+    // niri acknowledges the request, then starts sending events
+    let mut reader = BufReader::new(stream);
+    let mut reply = String::new();
+    reader
+        .read_line(&mut reply)
+        .map_err(|e| IpcError::IoError(format!("Failed to read: {}", e)))?;
+    match serde_json::from_str::<ActionResponse>(&reply) {
+        Ok(ActionResponse::Ok { .. }) => Ok(reader),
+        Ok(ActionResponse::Err { error }) => Err(IpcError::NiriError(format_json_error(&error))),
+        Err(e) => Err(IpcError::ParseError(e.to_string())),
+    }
+}
+
+/// Event as niri sends it; events the app doesn't use fail to parse and
+/// are skipped
+#[derive(Debug, Deserialize)]
+enum RawEvent {
+    WorkspacesChanged {
+        workspaces: Vec<WorkspaceInfo>,
+    },
+    WorkspaceActivated {
+        id: u64,
+        focused: bool,
+    },
+    WorkspaceActiveWindowChanged {
+        workspace_id: u64,
+        active_window_id: Option<u64>,
+    },
+    WindowsChanged {
+        windows: Vec<WindowInfo>,
+    },
+    WindowOpenedOrChanged {
+        window: WindowInfo,
+    },
+    WindowClosed {
+        id: u64,
+    },
+    WindowFocusChanged {
+        id: Option<u64>,
+    },
+    KeyboardLayoutsChanged {
+        keyboard_layouts: KeyboardLayouts,
+    },
+    KeyboardLayoutSwitched {
+        idx: u8,
+    },
+}
+
+/// Windows and workspaces as of the last event
+#[derive(Debug, Default)]
+struct EventState {
+    windows: Vec<WindowInfo>,
+    workspaces: Vec<WorkspaceInfo>,
+    keyboard_layouts: KeyboardLayouts,
+}
+
+impl EventState {
+    /// Applies one line of the stream, returning what the app should see
+    fn apply_line(&mut self, line: &str) -> Option<NiriEvent> {
+        let event = serde_json::from_str::<RawEvent>(line).ok()?;
+        Some(self.apply(event))
+    }
+
+    fn apply(&mut self, event: RawEvent) -> NiriEvent {
+        match event {
+            RawEvent::WorkspacesChanged { workspaces } => {
+                self.workspaces = workspaces;
+                NiriEvent::WorkspacesChanged(self.workspaces.clone())
+            }
+            RawEvent::WorkspaceActivated { id, focused } => {
+                let output = self
+                    .workspaces
+                    .iter()
+                    .find(|ws| ws.id == id)
+                    .map(|ws| ws.output.clone());
+                for ws in &mut self.workspaces {
+                    // Only one workspace is active per output
+                    if Some(&ws.output) == output.as_ref() {
+                        ws.is_active = ws.id == id;
+                    }
+                    if focused {
+                        ws.is_focused = ws.id == id;
+                    }
+                }
+                NiriEvent::WorkspacesChanged(self.workspaces.clone())
+            }
+            RawEvent::WorkspaceActiveWindowChanged {
+                workspace_id,
+                active_window_id,
+            } => {
+                if let Some(ws) = self.workspaces.iter_mut().find(|ws| ws.id == workspace_id) {
+                    ws.active_window_id = active_window_id;
+                }
+                NiriEvent::WorkspacesChanged(self.workspaces.clone())
+            }
+            RawEvent::WindowsChanged { windows } => {
+                self.windows = windows;
+                NiriEvent::WindowsChanged(self.windows.clone())
+            }
+            RawEvent::WindowOpenedOrChanged { window } => {
+                if window.is_focused {
+                    for other in &mut self.windows {
+                        other.is_focused = false;
+                    }
+                }
+                match self.windows.iter_mut().find(|w| w.id == window.id) {
+                    Some(existing) => *existing = window,
+                    None => self.windows.push(window),
+                }
+                NiriEvent::WindowsChanged(self.windows.clone())
+            }
+            RawEvent::WindowClosed { id } => {
+                self.windows.retain(|w| w.id != id);
+                NiriEvent::WindowsChanged(self.windows.clone())
+            }
+            RawEvent::WindowFocusChanged { id } => {
+                for window in &mut self.windows {
+                    window.is_focused = Some(window.id) == id;
+                }
+                NiriEvent::WindowFocusChanged(id)
+            }
+            RawEvent::KeyboardLayoutsChanged { keyboard_layouts } => {
+                self.keyboard_layouts = keyboard_layouts;
+                NiriEvent::KeyboardLayoutsChanged(self.keyboard_layouts.clone())
+            }
+            RawEvent::KeyboardLayoutSwitched { idx } => {
+                self.keyboard_layouts.current_idx = idx;
+                NiriEvent::KeyboardLayoutsChanged(self.keyboard_layouts.clone())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window_ids(event: Option<NiriEvent>) -> Vec<u64> {
+        match event {
+            Some(NiriEvent::WindowsChanged(windows)) => windows.iter().map(|w| w.id).collect(),
+            other => panic!("expected WindowsChanged, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_window_events_keep_list_current() {
+        let mut state = EventState::default();
+        let opened = |id: u64, focused: bool| {
+            format!(
+                r#"{{"WindowOpenedOrChanged":{{"window":{{"id":{},"title":"t","app_id":"a","is_focused":{}}}}}}}"#,
+                id, focused
+            )
+        };
+
+        let initial = r#"{"WindowsChanged":{"windows":[{"id":1,"title":null,"app_id":"foot","is_focused":true}]}}"#;
+        assert_eq!(window_ids(state.apply_line(initial)), vec![1]);
+        assert_eq!(window_ids(state.apply_line(&opened(2, true))), vec![1, 2]);
+        // Opening a focused window takes focus from the others
+        assert!(!state.windows[0].is_focused);
+
+        // A change to a known window replaces it in place
+        assert_eq!(window_ids(state.apply_line(&opened(1, false))), vec![1, 2]);
+        assert_eq!(
+            window_ids(state.apply_line(r#"{"WindowClosed":{"id":2}}"#)),
+            vec![1]
+        );
+
+        let focus = state.apply_line(r#"{"WindowFocusChanged":{"id":1}}"#);
+        assert!(matches!(
+            focus,
+            Some(NiriEvent::WindowFocusChanged(Some(1)))
+        ));
+        assert!(state.windows[0].is_focused);
+    }
+
+    #[test]
+    fn test_workspace_activation_is_per_output() {
+        let mut state = EventState::default();
+        let workspaces = r#"{"WorkspacesChanged":{"workspaces":[
+            {"id":1,"idx":1,"output":"DP-1","is_active":true,"is_focused":true},
+            {"id":2,"idx":2,"output":"DP-1","is_active":false,"is_focused":false},
+            {"id":3,"idx":1,"output":"HDMI-A-1","is_active":true,"is_focused":false}
+        ]}}"#
+            .replace('\n', "");
+        assert!(matches!(
+            state.apply_line(&workspaces),
+            Some(NiriEvent::WorkspacesChanged(ref ws)) if ws.len() == 3
+        ));
+
+        state.apply_line(r#"{"WorkspaceActivated":{"id":2,"focused":true}}"#);
+        let active: Vec<_> = state.workspaces.iter().map(|ws| ws.is_active).collect();
+        let focused: Vec<_> = state.workspaces.iter().map(|ws| ws.is_focused).collect();
+        // The other output's active workspace is untouched
+        assert_eq!(active, vec![false, true, true]);
+        assert_eq!(focused, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_layout_switch_keeps_layout_names() {
+        let mut state = EventState::default();
+        state.apply_line(
+            r#"{"KeyboardLayoutsChanged":{"keyboard_layouts":{"names":["English (US)","German"],"current_idx":0}}}"#,
+        );
+
+        match state.apply_line(r#"{"KeyboardLayoutSwitched":{"idx":1}}"#) {
+            Some(NiriEvent::KeyboardLayoutsChanged(layouts)) => {
+                assert_eq!(layouts.names.len(), 2);
+                assert_eq!(layouts.current(), Some("German"));
+            }
+            other => panic!("expected KeyboardLayoutsChanged, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unused_events_are_skipped() {
+        let mut state = EventState::default();
+        assert!(state
+            .apply_line(r#"{"OverviewOpenedOrClosed":{"is_open":true}}"#)
+            .is_none());
+        assert!(state.apply_line("not json").is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! Reads layout values back from niri's live window layout
+//!
+//! niri doesn't report its layout settings over IPC, but since 25.05 each
+//! window carries its tile position and size. The space between neighboring
+//! tiles is exactly the configured gap, so gaps can be measured. Struts can't:
+//! the working area they shrink is also shrunk by layer-shell exclusive zones
+//! (bars, docks), and the two aren't reported separately.
+
+use serde::Deserialize;
+
+use super::WindowInfo;
+use crate::config::models::{AppearanceSettings, BehaviorSettings};
+use crate::constants::{GAP_SIZE_MAX, GAP_SIZE_MIN, STRUT_SIZE_MAX, STRUT_SIZE_MIN};
+
+/// Tile placement of a window, as reported in niri's `Windows` reply
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WindowLayout {
+    /// 1-based (column, tile) index; `None` for floating windows
+    #[serde(default)]
+    pub pos_in_scrolling_layout: Option<(usize, usize)>,
+    /// Tile size in logical pixels, including the border
+    #[serde(default)]
+    pub tile_size: (f64, f64),
+    /// Tile position in the workspace view; `None` when the tile isn't visible
+    #[serde(default)]
+    pub tile_pos_in_workspace_view: Option<(f64, f64)>,
+}
+
+/// Layout values read from the running niri
+///
+/// Each field is `None` when niri's state doesn't reveal it, and only `Some`
+/// fields are adopted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayoutInfo {
+    pub gaps: Option<f32>,
+    pub strut_left: Option<f32>,
+    pub strut_right: Option<f32>,
+    pub strut_top: Option<f32>,
+    pub strut_bottom: Option<f32>,
+}
+
+impl LayoutInfo {
+    /// Whether niri revealed any value at all
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Copies the known values into the settings, clamped to the app's ranges
+    ///
+    /// Returns the names of the settings that changed.
+    pub fn apply(
+        &self,
+        appearance: &mut AppearanceSettings,
+        behavior: &mut BehaviorSettings,
+    ) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        let mut adopt = |name, value: Option<f32>, target: &mut f32, min, max| {
+            if let Some(value) = value.map(|v| v.clamp(min, max)) {
+                if *target != value {
+                    *target = value;
+                    changed.push(name);
+                }
+            }
+        };
+
+        adopt(
+            "gaps",
+            self.gaps,
+            &mut appearance.gaps,
+            GAP_SIZE_MIN,
+            GAP_SIZE_MAX,
+        );
+        for (name, value, target) in [
+            ("left strut", self.strut_left, &mut behavior.strut_left),
+            ("right strut", self.strut_right, &mut behavior.strut_right),
+            ("top strut", self.strut_top, &mut behavior.strut_top),
+            (
+                "bottom strut",
+                self.strut_bottom,
+                &mut behavior.strut_bottom,
+            ),
+        ] {
+            adopt(name, value, target, STRUT_SIZE_MIN, STRUT_SIZE_MAX);
+        }
+        changed
+    }
+}
+
+/// Measures what it can of the layout from niri's window list
+///
+/// Gaps are the smallest non-negative space between visible tiles that sit
+/// next to each other on the same workspace, either stacked in one column or
+/// in adjacent columns. Tabbed columns overlap their tiles and are skipped by
+/// the non-negative check.
+pub fn layout_info_from_windows(windows: &[WindowInfo]) -> LayoutInfo {
+    let tiles: Vec<_> = windows
+        .iter()
+        .filter(|w| !w.is_floating)
+        .filter_map(|w| {
+            let layout = w.layout.as_ref()?;
+            Some((
+                w.workspace_id,
+                layout.pos_in_scrolling_layout?,
+                layout.tile_pos_in_workspace_view?,
+                layout.tile_size,
+            ))
+        })
+        .collect();
+
+    let mut gaps: Option<f64> = None;
+    for (ws, (col, idx), (x, y), (w, h)) in &tiles {
+        for (other_ws, (other_col, other_idx), (ox, oy), _) in &tiles {
+            if ws != other_ws {
+                continue;
+            }
+            let space = if other_col == col && *other_idx == idx + 1 {
+                oy - (y + h)
+            } else if *other_col == col + 1 {
+                ox - (x + w)
+            } else {
+                continue;
+            };
+            if space >= 0.0 && gaps.is_none_or(|g| space < g) {
+                gaps = Some(space);
+            }
+        }
+    }
+
+    LayoutInfo {
+        // Fractional scales leave float noise in tile positions
+        gaps: gaps.map(|g| ((g * 10.0).round() / 10.0) as f32),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(workspace: u64, pos: (usize, usize), at: (f64, f64), size: (f64, f64)) -> WindowInfo {
+        WindowInfo {
+            workspace_id: Some(workspace),
+            layout: Some(WindowLayout {
+                pos_in_scrolling_layout: Some(pos),
+                tile_size: size,
+                tile_pos_in_workspace_view: Some(at),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parses_window_layout_from_ipc_json() {
+        let json = r#"{"id":3,"title":"t","app_id":"foot","workspace_id":1,
+            "is_focused":false,"is_floating":false,
+            "layout":{"pos_in_scrolling_layout":[2,1],"tile_size":[944.0,1048.0],
+            "window_size":[940,1044],"tile_pos_in_workspace_view":[968.0,16.0],
+            "window_offset_in_tile":[2.0,2.0]}}"#;
+        let window: WindowInfo = serde_json::from_str(json).unwrap();
+        let layout = window.layout.unwrap();
+        assert_eq!(layout.pos_in_scrolling_layout, Some((2, 1)));
+        assert_eq!(layout.tile_pos_in_workspace_view, Some((968.0, 16.0)));
+    }
+
+    #[test]
+    fn test_gaps_measured_between_columns_and_stacked_tiles() {
+        let windows = [
+            tile(1, (1, 1), (12.0, 12.0), (600.0, 500.0)),
+            tile(1, (1, 2), (12.0, 524.0), (600.0, 500.0)),
+            tile(1, (2, 1), (624.0, 12.0), (600.0, 1012.0)),
+        ];
+        assert_eq!(layout_info_from_windows(&windows).gaps, Some(12.0));
+    }
+
+    #[test]
+    fn test_nothing_reported_without_neighbors() {
+        let mut floating = tile(1, (1, 1), (0.0, 0.0), (100.0, 100.0));
+        floating.is_floating = true;
+        let windows = [
+            tile(1, (1, 1), (16.0, 16.0), (800.0, 1000.0)),
+            // Adjacent column, but on another workspace
+            tile(2, (2, 1), (900.0, 16.0), (800.0, 1000.0)),
+            floating,
+            WindowInfo::default(),
+        ];
+        let info = layout_info_from_windows(&windows);
+        assert!(info.is_empty());
+    }
+
+    #[test]
+    fn test_apply_maps_only_reported_values() {
+        let mut appearance = AppearanceSettings::default();
+        let mut behavior = BehaviorSettings {
+            strut_top: 30.0,
+            ..Default::default()
+        };
+        let info = LayoutInfo {
+            gaps: Some(8.0),
+            strut_left: Some(1000.0),
+            ..Default::default()
+        };
+
+        let changed = info.apply(&mut appearance, &mut behavior);
+        assert_eq!(changed, vec!["gaps", "left strut"]);
+        assert_eq!(appearance.gaps, 8.0);
+        assert_eq!(behavior.strut_left, STRUT_SIZE_MAX);
+        // Not reported, so the stored value stays
+        assert_eq!(behavior.strut_top, 30.0);
+
+        assert!(info.apply(&mut appearance, &mut behavior).is_empty());
+    }
+}
@@ -26,13 +26,22 @@
 //! }
 //! ```
 
+pub mod effective;
+pub mod events;
+pub mod layout;
+pub mod output;
 pub mod tasks;
+pub mod validate;
+
+pub use events::{event_stream, NiriEvent};
+pub use output::set_output;
+pub use validate::{ValidationError, ValidationReport};
 
 use log::{debug, info, warn};
 use serde::Deserialize;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::os::unix::net::UnixStream;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
 use thiserror::Error;
@@ -129,6 +138,9 @@ pub struct WindowInfo {
     /// Whether this window is focused (added in newer niri versions)
     #[serde(default)]
     pub is_focused: bool,
+    /// Tile position and size (niri 25.05+)
+    #[serde(default)]
+    pub layout: Option<layout::WindowLayout>,
 }
 
 // Response wrapper types for proper JSON parsing
@@ -432,40 +444,48 @@ pub fn quit_niri() -> IpcResult<()> {
 
 /// Validate niri configuration by running `niri validate`
 ///
-/// Returns Ok(message) if valid, Err(error_details) if invalid.
+/// Returns a report of whether niri accepts the config and where any errors
+/// are; `Err` only when niri couldn't be run.
 /// Uses absolute paths to the niri binary for security (avoids PATH manipulation).
-pub fn validate_config() -> IpcResult<String> {
+pub fn validate_config() -> IpcResult<ValidationReport> {
+    run_validate(None)
+}
+
+/// Validate a config file other than niri's own, like an exported one
+pub fn validate_config_file(path: &Path) -> IpcResult<ValidationReport> {
+    run_validate(Some(path))
+}
+
+/// Runs `niri validate`, on `config` if given, else on niri's usual config
+fn run_validate(config: Option<&Path>) -> IpcResult<ValidationReport> {
     let niri_path = find_niri_binary().ok_or_else(|| {
         IpcError::CommandFailed(
             "niri binary not found in /usr/bin/niri, /usr/local/bin/niri, or /bin/niri".to_string(),
         )
     })?;
 
-    let output = Command::new(&niri_path)
-        .arg("validate")
+    let mut command = Command::new(&niri_path);
+    command.arg("validate");
+    if let Some(config) = config {
+        command.arg("--config").arg(config);
+    }
+    let output = command
         .output()
         .map_err(|e| IpcError::CommandFailed(format!("Failed to run 'niri validate': {}", e)))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    if output.status.success() {
-        // niri validate prints to stdout on success
-        let msg = stdout.trim();
-        if msg.is_empty() {
-            Ok("Configuration is valid".to_string())
-        } else {
-            Ok(msg.to_string())
-        }
+    // niri validate prints to stdout on success and error details to stderr
+    let text = if output.status.success() || stderr.trim().is_empty() {
+        stdout
     } else {
-        // On failure, error details are in stderr
-        let error_msg = if stderr.is_empty() {
-            stdout.trim().to_string()
-        } else {
-            stderr.trim().to_string()
-        };
-        Err(IpcError::CommandFailed(error_msg))
-    }
+        stderr
+    };
+    Ok(ValidationReport::from_output(
+        output.status.success(),
+        &text,
+    ))
 }
 
 /// Get niri version (if available)
@@ -527,6 +547,25 @@ pub fn get_windows() -> IpcResult<Vec<WindowInfo>> {
     }
 }
 
+/// Get the layout values niri's live state reveals
+///
+/// niri has no IPC request for layout settings, so gaps are measured from the
+/// positions of neighboring tiles, which niri reports since 25.05. Older
+/// versions get `NiriError` rather than an empty result, so the caller can
+/// tell "nothing to measure" from "can't measure".
+pub fn get_layout_info() -> IpcResult<layout::LayoutInfo> {
+    let version = crate::version::NiriVersion::parse(&get_version()?);
+    let feature = crate::version::NiriFeature::WindowLayout;
+    if !version.is_some_and(|v| feature.is_supported_by(v)) {
+        return Err(IpcError::NiriError(format!(
+            "niri {}+ is required to read layout values",
+            feature.min_version()
+        )));
+    }
+
+    Ok(layout::layout_info_from_windows(&get_windows()?))
+}
+
 /// Get unique app IDs from running windows
 pub fn get_unique_app_ids() -> IpcResult<Vec<String>> {
     let windows = get_windows()?;
@@ -656,6 +695,57 @@ pub fn get_focused_output() -> IpcResult<Option<String>> {
     }
 }
 
+/// Keyboard layouts niri has loaded and which one is in use
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct KeyboardLayouts {
+    /// Layout names as xkb describes them (e.g., "English (US)")
+    pub names: Vec<String>,
+    /// Index into `names` of the active layout
+    pub current_idx: u8,
+}
+
+impl KeyboardLayouts {
+    /// Name of the active layout
+    pub fn current(&self) -> Option<&str> {
+        self.names
+            .get(self.current_idx as usize)
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyboardLayoutsResponse {
+    #[serde(rename = "KeyboardLayouts")]
+    layouts: KeyboardLayouts,
+}
+
+/// Get the keyboard layouts niri is using
+///
+/// Returns `Err(IpcError)` if the query fails.
+pub fn get_keyboard_layouts() -> IpcResult<KeyboardLayouts> {
+    if !is_niri_running() {
+        return Err(IpcError::NotRunning);
+    }
+
+    debug!("Fetching keyboard layouts from niri...");
+    let response = send_simple_request("KeyboardLayouts")?;
+
+    match serde_json::from_str::<NiriResponse<KeyboardLayoutsResponse>>(&response) {
+        Ok(NiriResponse::Ok { inner }) => Ok(inner.layouts),
+        Ok(NiriResponse::Err { error }) => {
+            warn!(
+                "Niri returned error for keyboard layouts request: {:?}",
+                error
+            );
+            Err(IpcError::NiriError(format!("{:?}", error)))
+        }
+        Err(e) => {
+            warn!("Failed to parse keyboard layouts response: {}", e);
+            Err(IpcError::ParseError(format!("{}", e)))
+        }
+    }
+}
+
 /// Information about an output/display from niri IPC (minimal version)
 /// We use deny_unknown_fields=false (default) to ignore extra fields from niri
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -680,7 +770,7 @@ pub struct OutputLogical {
 }
 
 /// Display mode info from niri
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct OutputMode {
     pub width: i32,
     pub height: i32,
@@ -690,6 +780,40 @@ pub struct OutputMode {
     pub is_preferred: bool,
 }
 
+impl OutputMode {
+    /// Mode as written in the config (e.g., "1920x1080@60.00")
+    #[must_use]
+    pub fn mode_string(&self) -> String {
+        // Convert millihertz to Hz with 2 decimal places
+        let refresh_hz = self.refresh_rate as f64 / 1000.0;
+        format!("{}x{}@{:.2}", self.width, self.height, refresh_hz)
+    }
+}
+
+/// The highest-refresh mode at the output's preferred resolution
+///
+/// Without a preferred mode, the largest resolution is used. Ties keep the
+/// first mode niri listed. `None` when there are no modes.
+#[must_use]
+pub fn best_mode(modes: &[OutputMode]) -> Option<&OutputMode> {
+    let (width, height) = modes
+        .iter()
+        .find(|mode| mode.is_preferred)
+        .or_else(|| {
+            modes
+                .iter()
+                .rev()
+                .max_by_key(|mode| i64::from(mode.width) * i64::from(mode.height))
+        })
+        .map(|mode| (mode.width, mode.height))?;
+
+    modes
+        .iter()
+        .filter(|mode| mode.width == width && mode.height == height)
+        .rev()
+        .max_by_key(|mode| mode.refresh_rate)
+}
+
 /// Full output info from niri IPC (includes all settings)
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct FullOutputInfo {
@@ -700,6 +824,8 @@ pub struct FullOutputInfo {
     pub make: String,
     #[serde(default)]
     pub model: String,
+    #[serde(default)]
+    pub serial: Option<String>,
     /// Index into the modes array for current mode
     #[serde(default)]
     pub current_mode: Option<usize>,
@@ -715,19 +841,36 @@ pub struct FullOutputInfo {
 }
 
 impl FullOutputInfo {
+    /// The "make model serial" string niri matches this monitor by
+    #[must_use]
+    pub fn identity(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.make,
+            self.model,
+            self.serial.as_deref().unwrap_or("Unknown")
+        )
+    }
+
     /// Get the current mode as a string (e.g., "1920x1080@60.00")
     #[must_use]
     pub fn current_mode_string(&self) -> String {
         if let Some(mode_idx) = self.current_mode {
             if let Some(mode) = self.modes.get(mode_idx) {
-                // Convert millihertz to Hz with 2 decimal places
-                let refresh_hz = mode.refresh_rate as f64 / 1000.0;
-                return format!("{}x{}@{:.2}", mode.width, mode.height, refresh_hz);
+                return mode.mode_string();
             }
         }
         String::new()
     }
 
+    /// Width and height of the current mode, defaulting to 1920x1080
+    #[must_use]
+    pub fn current_mode_size(&self) -> (i32, i32) {
+        self.current_mode
+            .and_then(|mode_idx| self.modes.get(mode_idx))
+            .map_or((1920, 1080), |mode| (mode.width, mode.height))
+    }
+
     /// Get the scale, defaulting to 1.0 if not set
     #[must_use]
     pub fn scale(&self) -> f64 {
@@ -958,6 +1101,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_keyboard_layouts_response() {
+        let json =
+            r#"{"Ok":{"KeyboardLayouts":{"names":["English (US)","German"],"current_idx":1}}}"#;
+        let parsed: NiriResponse<KeyboardLayoutsResponse> = serde_json::from_str(json).unwrap();
+        match parsed {
+            NiriResponse::Ok { inner } => {
+                assert_eq!(inner.layouts.names, vec!["English (US)", "German"]);
+                assert_eq!(inner.layouts.current(), Some("German"));
+            }
+            NiriResponse::Err { .. } => panic!("Expected Ok variant"),
+        }
+    }
+
     #[test]
     fn test_parse_windows_response_empty() {
         let json = r#"{"Ok":{"Windows":[]}}"#;
@@ -1098,6 +1255,52 @@ mod tests {
         assert!(!info.vrr_enabled);
     }
 
+    fn mode(width: i32, height: i32, refresh_rate: i32, is_preferred: bool) -> OutputMode {
+        OutputMode {
+            width,
+            height,
+            refresh_rate,
+            is_preferred,
+        }
+    }
+
+    #[test]
+    fn test_best_mode_picks_highest_refresh_at_preferred_resolution() {
+        let modes = [
+            mode(3840, 2160, 60000, true),
+            mode(3840, 2160, 143963, false),
+            mode(3840, 2160, 120000, false),
+            // Higher refresh, but not the native resolution
+            mode(1920, 1080, 240000, false),
+        ];
+        assert_eq!(best_mode(&modes), Some(&modes[1]));
+    }
+
+    #[test]
+    fn test_best_mode_ties_keep_first_listed() {
+        // Two modes at the native resolution share the top refresh rate;
+        // only the preferred flag tells them apart
+        let modes = [
+            mode(2560, 1440, 59951, false),
+            mode(2560, 1440, 164956, false),
+            mode(2560, 1440, 120000, true),
+            mode(2560, 1440, 164956, true),
+        ];
+        assert_eq!(best_mode(&modes), Some(&modes[1]));
+    }
+
+    #[test]
+    fn test_best_mode_without_preferred_flag_uses_largest_resolution() {
+        let modes = [
+            mode(1920, 1080, 144000, false),
+            mode(2560, 1440, 60000, false),
+            mode(2560, 1440, 75000, false),
+            mode(1280, 720, 60000, false),
+        ];
+        assert_eq!(best_mode(&modes), Some(&modes[2]));
+        assert_eq!(best_mode(&[]), None);
+    }
+
     #[test]
     fn test_full_output_current_mode_string() {
         let mut info = FullOutputInfo {
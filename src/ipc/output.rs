@@ -0,0 +1,183 @@
+//! Applying output settings to niri live
+//!
+//! niri's `Output` request changes one property of a connected output at a
+//! time, without reloading the config, so other monitors don't flicker.
+//! The changes last until the next config reload, which picks up the same
+//! settings from the saved KDL.
+
+use log::{info, warn};
+use serde_json::{json, Value};
+
+use super::{format_json_error, is_niri_running, send_raw_request, IpcError, IpcResult};
+use crate::config::models::OutputConfig;
+use crate::types::{Transform, VrrMode};
+
+/// niri `OutputAction`s that make an output match `cfg`
+///
+/// A disabled output only gets `Off`. Settings the KDL leaves out (no mode,
+/// a scale of 1, no position) are sent as automatic, so the result matches
+/// a reload.
+pub fn output_actions(cfg: &OutputConfig) -> Vec<Value> {
+    if !cfg.enabled {
+        return vec![json!("Off")];
+    }
+
+    let mut actions = vec![json!("On")];
+
+    let mode = match parse_mode(&cfg.mode) {
+        Some((width, height, refresh)) => json!({
+            "Specific": { "width": width, "height": height, "refresh": refresh }
+        }),
+        None => json!("Automatic"),
+    };
+    if cfg.mode_custom && mode != json!("Automatic") {
+        actions.push(json!({ "CustomMode": { "mode": mode["Specific"] } }));
+    } else {
+        actions.push(json!({ "Mode": { "mode": mode } }));
+    }
+
+    // The KDL leaves out a scale of 1, so niri picks one itself
+    let scale = if cfg.has_scale() {
+        json!({ "Specific": cfg.scale })
+    } else {
+        json!("Automatic")
+    };
+    actions.push(json!({ "Scale": { "scale": scale } }));
+
+    let transform = match cfg.transform {
+        Transform::Normal => "Normal",
+        Transform::Rotate90 => "90",
+        Transform::Rotate180 => "180",
+        Transform::Rotate270 => "270",
+        Transform::Flipped => "Flipped",
+        Transform::Flipped90 => "Flipped90",
+        Transform::Flipped180 => "Flipped180",
+        Transform::Flipped270 => "Flipped270",
+    };
+    actions.push(json!({ "Transform": { "transform": transform } }));
+
+    let position = if cfg.has_position() {
+        json!({ "Specific": { "x": cfg.position_x, "y": cfg.position_y } })
+    } else {
+        json!("Automatic")
+    };
+    actions.push(json!({ "Position": { "position": position } }));
+
+    let (vrr, on_demand) = match cfg.vrr {
+        VrrMode::Off => (false, false),
+        VrrMode::On => (true, false),
+        VrrMode::OnDemand => (true, true),
+    };
+    actions.push(json!({ "Vrr": { "vrr": { "vrr": vrr, "on_demand": on_demand } } }));
+
+    actions
+}
+
+/// Width, height and refresh rate from a mode like `1920x1080@60.000`
+pub fn parse_mode(mode: &str) -> Option<(u32, u32, Option<f64>)> {
+    let (resolution, refresh) = match mode.split_once('@') {
+        Some((resolution, refresh)) => (resolution, Some(refresh.trim().parse().ok()?)),
+        None => (mode, None),
+    };
+    let (w, h) = resolution.split_once('x')?;
+    Some((w.trim().parse().ok()?, h.trim().parse().ok()?, refresh))
+}
+
+/// Applies `cfg` to the connected output `name` without a config reload
+///
+/// Returns `Err(IpcError::NotRunning)` when niri isn't running, and an
+/// error naming the output when it isn't connected.
+pub fn set_output(name: &str, cfg: &OutputConfig) -> IpcResult<()> {
+    if !is_niri_running() {
+        return Err(IpcError::NotRunning);
+    }
+
+    info!("Applying output settings to {} live", name);
+    for action in output_actions(cfg) {
+        let request = json!({ "Output": { "output": name, "action": action } });
+        let response = send_raw_request(&request.to_string())?;
+        let value: Value =
+            serde_json::from_str(&response).map_err(|e| IpcError::ParseError(format!("{}", e)))?;
+
+        if let Some(error) = value.get("Err") {
+            let error_msg = format_json_error(error);
+            warn!("niri rejected output change for {}: {}", name, error_msg);
+            return Err(IpcError::NiriError(error_msg));
+        }
+        if value["Ok"]["OutputConfigChanged"] == "OutputWasMissing" {
+            return Err(IpcError::NiriError(format!("{} is not connected", name)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_config_to_actions() {
+        let cfg = OutputConfig {
+            name: "DP-1".to_string(),
+            scale: 1.5,
+            mode: "2560x1440@143.912".to_string(),
+            position_x: 1920,
+            transform: Transform::Rotate90,
+            vrr: VrrMode::OnDemand,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            output_actions(&cfg),
+            vec![
+                json!("On"),
+                json!({ "Mode": { "mode": { "Specific": {
+                    "width": 2560, "height": 1440, "refresh": 143.912
+                } } } }),
+                json!({ "Scale": { "scale": { "Specific": 1.5 } } }),
+                json!({ "Transform": { "transform": "90" } }),
+                json!({ "Position": { "position": { "Specific": { "x": 1920, "y": 0 } } } }),
+                json!({ "Vrr": { "vrr": { "vrr": true, "on_demand": true } } }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unset_settings_are_automatic() {
+        let cfg = OutputConfig::default();
+        let actions = output_actions(&cfg);
+        assert_eq!(actions[1], json!({ "Mode": { "mode": "Automatic" } }));
+        assert_eq!(actions[2], json!({ "Scale": { "scale": "Automatic" } }));
+        assert_eq!(
+            actions[4],
+            json!({ "Position": { "position": "Automatic" } })
+        );
+
+        let off = OutputConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(output_actions(&off), vec![json!("Off")]);
+    }
+
+    #[test]
+    fn test_output_placed_at_origin_keeps_its_position() {
+        let mut cfg = OutputConfig::default();
+        cfg.set_position(0, 0);
+        assert_eq!(
+            output_actions(&cfg)[4],
+            json!({ "Position": { "position": { "Specific": { "x": 0, "y": 0 } } } })
+        );
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(
+            parse_mode("1920x1080@60.000"),
+            Some((1920, 1080, Some(60.0)))
+        );
+        assert_eq!(parse_mode("1920x1080"), Some((1920, 1080, None)));
+        assert_eq!(parse_mode(""), None);
+        assert_eq!(parse_mode("1920x1080@fast"), None);
+    }
+}
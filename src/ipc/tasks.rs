@@ -21,8 +21,8 @@ use iced::Task;
 
 use super::{
     get_focused_window, get_full_outputs, get_version, get_windows, get_workspaces,
-    is_niri_running, reload_config, validate_config, FullOutputInfo, IpcResult, WindowInfo,
-    WorkspaceInfo,
+    is_niri_running, reload_config, validate_config, FullOutputInfo, IpcResult, ValidationReport,
+    WindowInfo, WorkspaceInfo,
 };
 
 /// Check if niri is running asynchronously.
@@ -111,9 +111,10 @@ where
 
 /// Validate niri config asynchronously.
 ///
-/// Returns a Task that completes with the validation result.
-/// Ok(message) if valid, Err(error_details) if invalid.
-pub fn validate_config_async<M>(f: impl FnOnce(IpcResult<String>) -> M + Send + 'static) -> Task<M>
+/// Returns a Task that completes with the validation report.
+pub fn validate_config_async<M>(
+    f: impl FnOnce(IpcResult<ValidationReport>) -> M + Send + 'static,
+) -> Task<M>
 where
     M: Send + 'static,
 {
@@ -0,0 +1,220 @@
+//! Structured results from `niri validate`
+//!
+//! niri reports config errors as miette diagnostics: a `× message` headline,
+//! then a snippet whose header names the file, line and column, with a
+//! label under the offending span:
+//!
+//! ```text
+//!   × unexpected node `gap`
+//!    ╭─[nirify/appearance.kdl:3:5]
+//!  3 │     gap 16
+//!    ·     ─┬─
+//!    ·      ╰── unexpected node
+//!    ╰────
+//! ```
+//!
+//! Each snippet becomes one [`ValidationError`]. Output that doesn't look
+//! like this is kept as raw text so nothing niri says is lost.
+
+/// Outcome of validating niri's config
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Whether niri accepts the config
+    pub ok: bool,
+    /// Errors that name a location; empty when the output couldn't be parsed
+    pub errors: Vec<ValidationError>,
+    /// niri's output as printed
+    pub raw: String,
+}
+
+/// One config error with the location niri reported for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// File as niri names it, relative to its config directory for includes
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl ValidationReport {
+    /// Builds a report from `niri validate`'s exit status and output
+    pub fn from_output(ok: bool, output: &str) -> Self {
+        Self {
+            ok,
+            errors: if ok { Vec::new() } else { parse_errors(output) },
+            raw: output.trim().to_string(),
+        }
+    }
+
+    /// One-line description for places with no room for the full list
+    pub fn summary(&self) -> String {
+        match self.errors.as_slice() {
+            [] if self.ok && self.raw.is_empty() => "Configuration is valid".to_string(),
+            [] => self.raw.clone(),
+            [only] => only.to_string(),
+            [first, rest @ ..] => format!("{} (and {} more)", first, rest.len()),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+/// Finds every located error in miette output, in the order niri printed them
+fn parse_errors(output: &str) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut headline: Option<&str> = None;
+    let mut lines = output.lines();
+
+    while let Some(line) = lines.next() {
+        if let Some(message) = headline_of(line) {
+            headline = Some(message);
+            continue;
+        }
+        let Some((file, line_no)) = location_of(line) else {
+            continue;
+        };
+
+        // The label under the span usually says more than the headline alone
+        let mut label = None;
+        for snippet_line in lines.by_ref() {
+            if is_snippet_end(snippet_line) {
+                break;
+            }
+            if label.is_none() {
+                label = label_of(snippet_line);
+            }
+        }
+
+        let message = match (headline, label) {
+            (Some(headline), Some(label)) if label != headline => {
+                format!("{} ({})", headline, label)
+            }
+            (Some(message), _) | (None, Some(message)) => message.to_string(),
+            (None, None) => "Invalid config".to_string(),
+        };
+        errors.push(ValidationError {
+            file: file.to_string(),
+            line: line_no,
+            message,
+        });
+    }
+    errors
+}
+
+/// Message from a `× message` headline, with or without an `Error:` prefix
+fn headline_of(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let line = line.strip_prefix("Error:").unwrap_or(line).trim_start();
+    let message = line.strip_prefix('×').or_else(|| line.strip_prefix("x "))?;
+    Some(message.trim()).filter(|m| !m.is_empty())
+}
+
+/// File and line from a snippet header like `╭─[file.kdl:3:5]`
+fn location_of(line: &str) -> Option<(&str, usize)> {
+    let line = line.trim_start();
+    let rest = line
+        .strip_prefix("╭─[")
+        .or_else(|| line.strip_prefix(",-["))?;
+    let location = rest.split_once(']')?.0.trim();
+
+    let (rest, _column) = location.rsplit_once(':')?;
+    let (file, line_no) = rest.rsplit_once(':')?;
+    Some((file, line_no.parse().ok()?))
+}
+
+/// Text of a `╰── label` line under the highlighted span
+fn label_of(line: &str) -> Option<&str> {
+    let (_, label) = line.split_once("╰──").or_else(|| line.split_once("`--"))?;
+    Some(label.trim()).filter(|l| !l.is_empty() && !l.starts_with('─') && !l.starts_with('-'))
+}
+
+/// The `╰────` line that closes a snippet
+fn is_snippet_end(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("╰─") && line.trim_start_matches(['╰', '─']).is_empty()
+        || line.starts_with("`-") && line.trim_start_matches(['`', '-']).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Error:   × error loading config
+  ├─▶ error parsing KDL
+  ╰─▶ 2 errors found
+
+Error:   × node `gap` is not supported
+   ╭─[nirify/appearance.kdl:3:5]
+ 2 │ layout {
+ 3 │     gap 16
+   ·     ─┬─
+   ·      ╰── unexpected node
+ 4 │ }
+   ╰────
+
+Error:   × unexpected token
+    ╭─[/home/user/.config/niri/config.kdl:12:1]
+ 11 │     }
+ 12 │ }}
+    · ┬
+    · ╰── unexpected token
+    ╰────
+";
+
+    #[test]
+    fn test_parses_niri_error_output() {
+        let report = ValidationReport::from_output(false, SAMPLE);
+        assert!(!report.ok);
+        assert_eq!(
+            report.errors,
+            vec![
+                ValidationError {
+                    file: "nirify/appearance.kdl".to_string(),
+                    line: 3,
+                    message: "node `gap` is not supported (unexpected node)".to_string(),
+                },
+                ValidationError {
+                    file: "/home/user/.config/niri/config.kdl".to_string(),
+                    line: 12,
+                    // A label repeating the headline isn't added twice
+                    message: "unexpected token".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            report.summary(),
+            "nirify/appearance.kdl:3: node `gap` is not supported (unexpected node) (and 1 more)"
+        );
+    }
+
+    #[test]
+    fn test_parses_ascii_diagnostics() {
+        let output = "  x invalid value\n   ,-[nirify/outputs.kdl:7:11]\n 7 |     scale abc\n   :           ^|^\n   :            `-- expected a number\n   `----\n";
+        let report = ValidationReport::from_output(false, output);
+        assert_eq!(
+            report.errors,
+            vec![ValidationError {
+                file: "nirify/outputs.kdl".to_string(),
+                line: 7,
+                message: "invalid value (expected a number)".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unparsed_output_falls_back_to_raw_text() {
+        let report = ValidationReport::from_output(false, "  error: config file not found\n");
+        assert!(report.errors.is_empty());
+        assert_eq!(report.summary(), "error: config file not found");
+
+        let valid = ValidationReport::from_output(true, "");
+        assert!(valid.ok);
+        assert_eq!(valid.summary(), "Configuration is valid");
+    }
+}
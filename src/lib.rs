@@ -5,6 +5,7 @@
 pub mod app;
 pub mod config;
 pub mod constants;
+pub mod diff;
 pub mod ipc;
 pub mod messages;
 pub mod save_manager;
@@ -1,6 +1,23 @@
 //! Niri Settings entry point
 
+use nirify::constants::CONFIG_DIR_ENV;
+
 fn main() -> iced::Result {
     env_logger::init();
+
+    // `--config-dir <path>` is passed on through the environment, where
+    // ConfigPaths looks for an override
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let dir = match arg.strip_prefix("--config-dir") {
+            Some("") => args.next(),
+            Some(value) => value.strip_prefix('=').map(str::to_string),
+            None => None,
+        };
+        if let Some(dir) = dir {
+            std::env::set_var(CONFIG_DIR_ENV, dir);
+        }
+    }
+
     nirify::app::run()
 }
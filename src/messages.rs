@@ -39,7 +39,7 @@ use crate::types::{
     AccelProfile, CenterFocusedColumn, ClickMethod, ModKey, ScrollMethod, TapButtonMap,
     WarpMouseMode,
 };
-use crate::views::widgets::GradientPickerMessage;
+use crate::views::widgets::{GradientPickerMessage, ReorderMessage};
 
 /// Root message enum - all possible application events
 #[derive(Debug, Clone)]
@@ -53,6 +53,8 @@ pub enum Message {
     NavigateToPage(Page),
     ToggleSidebar,
     SearchQueryChanged(String),
+    /// Periodic check that runs the search once typing pauses
+    CheckSearch,
     SearchResultSelected(usize),
     ClearSearch,
     ChangeTheme(crate::theme::AppTheme),
@@ -60,6 +62,16 @@ pub enum Message {
     SystemThemeEvent(crate::system_theme::SystemThemeEvent),
     /// Toggle search bar visibility / focus (Ctrl+K)
     ToggleSearch,
+    /// Revert the last settings edit (Ctrl+Z)
+    Undo,
+    /// Make the last undone edit again (Ctrl+Y or Ctrl+Shift+Z)
+    Redo,
+    /// Open the page of a starred setting that has no control on the Favorites screen
+    SelectFavorite(String),
+    /// Move keyboard focus to the next control (Tab)
+    FocusNext,
+    /// Move keyboard focus to the previous control (Shift+Tab)
+    FocusPrevious,
 
     // ═══════════════════════════════════════════════════════════════════════════
     // Visual Settings
@@ -134,8 +146,14 @@ pub enum Message {
     WizardNext,
     WizardBack,
     WizardSetupConfig,
+    /// Choose whether wizard setup keeps or imports the user's own includes
+    WizardIncludeHandling(crate::config::IncludeHandling),
+    /// Import config text pasted into a dialog
+    PasteConfig(PasteConfigMessage),
     /// Toggle a wizard consolidation suggestion
     WizardConsolidationToggle(usize),
+    /// Show or hide a wizard consolidation suggestion's detail
+    WizardConsolidationToggleDetail(usize),
     /// Apply selected wizard consolidation suggestions
     WizardConsolidationApply,
     /// Skip wizard consolidation step
@@ -144,6 +162,8 @@ pub enum Message {
     AnalyzeConsolidation,
     /// Toggle selection of a consolidation suggestion
     ConsolidationToggle(usize),
+    /// Show or hide a consolidation suggestion's detail
+    ConsolidationToggleDetail(usize),
     /// Apply selected consolidation suggestions
     ConsolidationApply,
 
@@ -155,6 +175,8 @@ pub enum Message {
     CheckNiriStatus,
     /// Async niri status check completed
     NiriStatusChecked(bool),
+    /// Live update from niri's event stream
+    NiriEvent(crate::ipc::NiriEvent),
     ClearToast,
     /// No-op message (for optional callbacks that don't need action)
     None,
@@ -180,14 +202,20 @@ pub enum Message {
     CloseKeybindingEditor,
     /// Set keybindings search filter
     SetKeybindingsSearch(String),
+    /// Keybindings table scrolled (drives which rows get built)
+    KeybindingsScrolled(crate::views::widgets::ListViewport),
     /// Change sub-tab within the Rules screen
     SetRulesSubTab(RulesSubTab),
+    /// A rules page's card grid scrolled (drives which cards get built)
+    RulesScrolled(Page, crate::views::widgets::ListViewport),
     /// Change sub-tab within the Gear screen
     SetGearSubTab(GearSubTab),
+    /// A screen's main scrollable moved to this vertical offset
+    PageScrolled(f32),
 }
 
 /// Page navigation enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Page {
     #[default]
     Overview,
@@ -281,6 +309,38 @@ impl Page {
             Page::ConfigEditor | Page::Backups => PageCategory::System,
         }
     }
+
+    /// Page where a settings category is edited
+    pub fn for_category(category: crate::config::SettingsCategory) -> Page {
+        use crate::config::SettingsCategory as C;
+        match category {
+            C::Appearance | C::Overview => Page::Appearance,
+            C::Behavior => Page::Behavior,
+            C::Keyboard => Page::Keyboard,
+            C::Mouse => Page::Mouse,
+            C::Touchpad => Page::Touchpad,
+            C::Trackpoint => Page::Trackpoint,
+            C::Trackball => Page::Trackball,
+            C::Tablet => Page::Tablet,
+            C::Touch => Page::Touch,
+            C::Outputs => Page::Outputs,
+            C::Animations => Page::Animations,
+            C::Cursor => Page::Cursor,
+            C::Workspaces => Page::Workspaces,
+            C::Keybindings => Page::Keybindings,
+            C::LayoutExtras => Page::LayoutExtras,
+            C::Gestures => Page::Gestures,
+            C::LayerRules => Page::LayerRules,
+            C::WindowRules => Page::WindowRules,
+            C::Miscellaneous => Page::Miscellaneous,
+            C::Startup => Page::Startup,
+            C::Environment => Page::Environment,
+            C::Debug => Page::Debug,
+            C::SwitchEvents => Page::SwitchEvents,
+            C::RecentWindows => Page::RecentWindows,
+            C::Preferences => Page::Preferences,
+        }
+    }
 }
 
 /// Page category for sidebar grouping
@@ -312,10 +372,11 @@ impl PageCategory {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Top-level screen in the redesigned navigation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Screen {
     #[default]
     Dashboard,
+    Favorites,
     Layout,
     Visuals,
     Input,
@@ -330,6 +391,7 @@ impl Screen {
     pub fn name(&self) -> &'static str {
         match self {
             Screen::Dashboard => "Dashboard",
+            Screen::Favorites => "Favorites",
             Screen::Layout => "Layout",
             Screen::Visuals => "Visuals",
             Screen::Input => "Input",
@@ -387,6 +449,7 @@ impl Screen {
     pub fn sidebar_items() -> &'static [Screen] {
         &[
             Screen::Dashboard,
+            Screen::Favorites,
             Screen::Layout,
             Screen::Visuals,
             Screen::Input,
@@ -427,6 +490,21 @@ impl InputSubTab {
         }
     }
 
+    /// The page this sub-tab shows
+    pub fn page(&self) -> Page {
+        match self {
+            InputSubTab::Keybindings => Page::Keybindings,
+            InputSubTab::Keyboard => Page::Keyboard,
+            InputSubTab::Mouse => Page::Mouse,
+            InputSubTab::Touchpad => Page::Touchpad,
+            InputSubTab::Trackpoint => Page::Trackpoint,
+            InputSubTab::Trackball => Page::Trackball,
+            InputSubTab::Tablet => Page::Tablet,
+            InputSubTab::Touch => Page::Touch,
+            InputSubTab::Gestures => Page::Gestures,
+        }
+    }
+
     pub fn all() -> &'static [InputSubTab] {
         &[
             InputSubTab::Keybindings,
@@ -677,6 +755,8 @@ pub enum AppearanceMessage {
     BorderActive(GradientPickerMessage),
     BorderInactive(GradientPickerMessage),
     BorderUrgent(GradientPickerMessage),
+    /// Keep the border colors synced to the focus ring colors
+    LinkBorderColors(bool),
 
     // Layout
     SetGaps(f32),
@@ -734,7 +814,10 @@ pub enum BehaviorMessage {
 pub enum KeyboardMessage {
     SetXkbLayout(String),
     SetXkbVariant(String),
-    SetXkbOptions(String),
+    /// Check or uncheck one of the common xkb options
+    ToggleXkbOption(String, bool),
+    /// Set the xkb options not covered by the checklist
+    SetXkbExtraOptions(String),
     SetXkbModel(String),
     SetXkbRules(String),
     SetXkbFile(String),
@@ -742,6 +825,8 @@ pub enum KeyboardMessage {
     SetRepeatRate(i32),
     SetTrackLayout(String),
     SetNumlock(bool),
+    /// Update the layout test field (UI only, not saved)
+    SetTestInput(String),
 }
 
 /// Mouse settings messages
@@ -801,8 +886,8 @@ pub enum AnimationsMessage {
     SetAnimationSpringDampingRatio(String, f32),
     SetAnimationSpringEpsilon(String, f32),
 
-    // Animation type selection (Default, Off, Spring, Easing, CustomShader)
-    SetAnimationType(String, i32), // (animation_name, type_index: 0=Default, 1=Off, 2=Spring, 3=Easing, 4=CustomShader)
+    // Animation type selection (Default, Spring, Easing, CustomShader)
+    SetAnimationType(String, i32), // (animation_name, type_index: 0=Default, 1=Spring, 2=Easing, 3=CustomShader)
 
     // Custom shader support (only for window-open, window-close, window-resize)
     SetCustomShader(String, String), // (animation_name, shader_code)
@@ -826,8 +911,7 @@ pub enum WorkspacesMessage {
     RemoveWorkspace(usize),
     UpdateWorkspaceName(usize, String),
     UpdateWorkspaceOutput(usize, Option<String>),
-    MoveWorkspaceUp(usize),
-    MoveWorkspaceDown(usize),
+    Reorder(ReorderMessage),
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -890,6 +974,8 @@ pub enum WindowRulesMessage {
 
     // Sizing
     SetDefaultColumnWidth(u32, Option<f32>),
+    SetDefaultColumnWidthType(u32, ColumnWidthType),
+    SetDefaultColumnWidthFixed(u32, Option<i32>),
     SetDefaultWindowHeight(u32, Option<f32>),
     SetMinWidth(u32, Option<i32>),
     SetMaxWidth(u32, Option<i32>),
@@ -919,6 +1005,14 @@ pub enum WindowRulesMessage {
     SetBorderInactive(u32, Option<crate::types::ColorOrGradient>),
     SetBorderUrgent(u32, Option<crate::types::ColorOrGradient>),
 
+    // Color overrides (edited in place by the gradient picker)
+    FocusRingActiveColor(u32, GradientPickerMessage),
+    FocusRingInactiveColor(u32, GradientPickerMessage),
+    FocusRingUrgentColor(u32, GradientPickerMessage),
+    BorderActiveColor(u32, GradientPickerMessage),
+    BorderInactiveColor(u32, GradientPickerMessage),
+    BorderUrgentColor(u32, GradientPickerMessage),
+
     // Complex struct overrides
     SetShadow(u32, Option<crate::config::models::ShadowSettings>),
     SetTabIndicator(u32, Option<crate::config::models::TabIndicatorSettings>),
@@ -970,6 +1064,7 @@ pub enum LayerRulesMessage {
 
     // Shadow (nested)
     SetShadow(u32, Option<crate::config::models::ShadowSettings>),
+    SetShadowColor(u32, String, String), // (rule_id, field_name, hex)
 
     // UI state
     ToggleSection(u32, String),
@@ -989,9 +1084,21 @@ pub enum OutputsMessage {
     AddOutput,
     RemoveOutput(usize),
     SelectOutput(usize),
+    /// Output to focus when niri starts; clears the flag on all others
+    SetStartupFocus(Option<usize>),
+    /// Move the enabled outputs into a preset layout
+    ApplyArrangement(crate::config::models::ArrangementPreset),
+    /// Start dragging an output in the arrangement preview
+    StartDrag(usize, crate::views::screens::displays::PreviewFrame),
+    /// Pointer moved while dragging, in preview pixels
+    Drag(iced::Point),
+    /// Drop the dragged output at its snapped position
+    EndDrag,
 
     // Basic properties
     SetOutputName(usize, String),
+    /// Match by make/model/serial (true) or connector name (false)
+    SetMatchByIdentity(usize, bool),
     SetEnabled(usize, bool),
     SetScale(usize, f64),
     SetMode(usize, String),
@@ -1001,7 +1108,6 @@ pub enum OutputsMessage {
     SetPositionY(usize, i32),
     SetTransform(usize, crate::types::Transform),
     SetVrr(usize, crate::types::VrrMode),
-    SetFocusAtStartup(usize, bool),
     SetBackdropColor(usize, Option<crate::types::Color>),
 
     // Hot corners
@@ -1018,6 +1124,8 @@ pub enum OutputsMessage {
     ToggleSection(String),
     OpenEditor(usize),
     CloseEditor,
+    /// Result of applying the closed editor's changes to niri live
+    AppliedLive(Result<(), String>),
 }
 
 /// Keybindings settings messages
@@ -1032,20 +1140,37 @@ pub enum KeybindingsMessage {
     UpdateModifiers(usize, Vec<ModKey>),
     StartKeyCapture(usize),
     CapturedKey(String),
+    /// Keep a captured combo even though another binding already uses it
+    CommitKeyCapture,
     CancelKeyCapture,
+    /// Replace a binding's combo, e.g. with a suggested free one
+    SetKeyCombo(usize, String),
 
     // Action
     UpdateAction(usize, String),
+    SetActionKind(usize, crate::config::models::ActionKind),
     SetCommand(usize, String),
+    SetActionArgs(usize, String),
 
     // Advanced options
     SetAllowWhenLocked(usize, bool),
     SetRepeat(usize, bool),
     SetCooldown(usize, Option<i32>),
     SetHotkeyOverlayTitle(usize, Option<String>),
+    /// Keep a binding out of niri's hotkey overlay
+    SetHotkeyOverlayHidden(usize, bool),
+    /// Set the app-only group a binding is listed under
+    SetSection(usize, Option<String>),
+
+    // Template gallery
+    ToggleTemplateGallery,
+    /// Insert the bindings of a template (index into `BINDING_TEMPLATES`)
+    ApplyTemplate(usize),
 
     // UI state
     ToggleSection(String),
+    /// Collapse or expand a group in the keybindings table
+    ToggleGroup(String),
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1137,6 +1262,7 @@ pub enum EnvironmentMessage {
     RemoveVariable(u32), // Variable ID
     SetVariableName(u32, String),
     SetVariableValue(u32, String),
+    Reorder(ReorderMessage),
 }
 
 /// Switch events settings messages
@@ -1210,6 +1336,9 @@ pub enum TabletMessage {
     SetOff(bool),
     SetLeftHanded(bool),
     SetMapToOutput(String),
+    /// Query niri for the focused output and map the tablet to it
+    MapToFocusedOutput,
+    FocusedOutputLoaded(Result<Option<String>, String>),
     SetCalibrationMatrix(Option<[f64; 6]>),
     // Calibration matrix individual value changes
     SetCalibrationValue(usize, String), // (index 0-5, value as string)
@@ -1222,6 +1351,9 @@ pub enum TabletMessage {
 pub enum TouchMessage {
     SetOff(bool),
     SetMapToOutput(String),
+    /// Query niri for the focused output and map the touch screen to it
+    MapToFocusedOutput,
+    FocusedOutputLoaded(Result<Option<String>, String>),
     SetCalibrationMatrix(Option<[f64; 6]>),
     // Calibration matrix individual value changes
     SetCalibrationValue(usize, String), // (index 0-5, value as string)
@@ -1301,6 +1433,7 @@ pub enum StartupMessage {
     AddCommand,
     RemoveCommand(u32), // Command ID
     SetCommand(u32, String),
+    Reorder(ReorderMessage),
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1325,14 +1458,36 @@ pub enum ToolsMessage {
     OutputsLoaded(Result<Vec<crate::ipc::FullOutputInfo>, String>),
     FocusedWindowLoaded(Result<Option<crate::ipc::WindowInfo>, String>),
     VersionLoaded(Result<String, String>),
+    KeyboardLayoutsLoaded(Result<crate::ipc::KeyboardLayouts, String>),
 
     // IPC actions
     ReloadConfig,
     ValidateConfig,
+    /// Check niri's live state reflects the config we wrote
+    CheckConfigEffective,
+    /// Read layout values from the running niri and adopt them
+    AdoptLayoutFromNiri,
+    /// Edit the comma-separated app-ids the screencast privacy rule blocks
+    SetScreencastBlockAppIds(String),
+    /// Turn the screencast privacy rule on (with exactly the listed app-ids) or off
+    ToggleScreencastBlock,
+    /// Run the best-practice checks over the current settings
+    LintConfig,
+    /// Open the page where a lint or validation error can be fixed
+    OpenLintTarget(crate::config::SettingsCategory),
+    /// Show or hide config.kdl with every include expanded
+    ToggleEffectiveConfig,
+    /// Save every setting as one standalone KDL file the user picks
+    ExportConfig,
 
     // Action results
     ReloadCompleted(Result<(), String>),
-    ValidateCompleted(Result<String, String>),
+    ValidateCompleted(Result<crate::ipc::ValidationReport, String>),
+    EffectiveOutputsLoaded(Result<Vec<crate::ipc::FullOutputInfo>, String>),
+    EffectiveValidateCompleted(Result<crate::ipc::ValidationReport, String>),
+    LayoutInfoLoaded(Result<crate::ipc::layout::LayoutInfo, String>),
+    /// Export written and checked; `None` when the file dialog was cancelled
+    ExportCompleted(Option<Result<String, String>>),
 }
 
 /// App preferences messages
@@ -1344,6 +1499,16 @@ pub enum PreferencesMessage {
     SetShowSearchBar(bool),
     /// Set the keyboard shortcut for opening search (e.g., "Ctrl+K", "Ctrl+/", or empty to disable)
     SetSearchHotkey(String),
+    /// Toggle writing values that match niri's defaults into generated files
+    SetEmitAllDefaults(bool),
+    /// Star or unstar a setting (by search index name)
+    ToggleFavorite(String),
+    /// Set which backups are kept
+    SetBackupRetention(crate::config::models::BackupRetention),
+    /// Toggle taking the app's accent color from the niri focus ring
+    SetMatchNiriColors(bool),
+    /// Toggle reloading niri after each save
+    SetAutoReload(bool),
 }
 
 /// Config editor messages
@@ -1374,16 +1539,51 @@ pub enum BackupsMessage {
     RefreshList,
     /// Backup list loaded
     ListLoaded(Result<Vec<BackupEntry>, String>),
+    /// Edit the label for a new snapshot
+    SetSnapshotLabel(String),
+    /// Back up every config file now, under the entered label
+    CreateSnapshot,
+    /// Snapshot written; how many files it holds
+    SnapshotCreated(Result<usize, String>),
     /// Select a backup to preview
     SelectBackup(usize),
     /// Preview content loaded
     PreviewLoaded(Result<String, String>),
+    /// Diff against the current config loaded
+    DiffLoaded(Result<Vec<crate::config::DiffLine>, String>),
+    /// Switch the preview between the diff (true) and the raw file (false)
+    ShowDiff(bool),
     /// Request to restore a backup
     RestoreBackup(usize),
     /// Show restore confirmation dialog
     ConfirmRestore(usize),
+    /// Show confirmation for restoring every file backed up with this one
+    ConfirmRestoreSnapshot(usize),
+    /// Restore every file backed up with this one, together
+    RestoreSnapshot(usize),
     /// Restore completed
-    RestoreCompleted(Result<(), String>),
+    RestoreCompleted(Result<crate::config::RestoreOutcome, String>),
+}
+
+/// Pasted config import messages
+#[derive(Debug, Clone)]
+pub enum PasteConfigMessage {
+    /// Open the dialog with an empty paste box
+    Open,
+    /// Edit the pasted text
+    Edit(iced::widget::text_editor::Action),
+    /// Parse the pasted text and show what it would import
+    Preview,
+    /// Go back from the preview to the paste box
+    Back,
+    /// Pick a config file to import instead of pasting one
+    ChooseFile,
+    /// File picked to import; `None` when the dialog was cancelled
+    FileChosen(Option<std::path::PathBuf>),
+    /// Apply the previewed import, replacing all settings
+    Replace,
+    /// Apply only the sections the previewed import has
+    Merge(crate::config::MergeStrategy),
 }
 
 /// Entry in the backups list
@@ -1397,6 +1597,8 @@ pub struct BackupEntry {
     pub size: String,
     /// Full path to the backup file
     pub path: std::path::PathBuf,
+    /// Label given to a snapshot taken by hand
+    pub label: Option<String>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1410,6 +1612,19 @@ pub struct BackupEntry {
 pub enum SaveMessage {
     /// Periodic check if save is needed (from subscription)
     CheckSave,
+    /// Open the panel listing edits made since the last save
+    ShowPendingChanges,
+    /// Put the managed files back as they were before the last save
+    RevertLastChange,
+    /// Revert finished; holds how many restore points are left
+    RevertCompleted(Result<usize, String>),
+    /// A file about to be saved was changed outside Nirify since it was last
+    /// written; saving waits until the user picks which version to keep
+    ExternalChangeDetected(crate::config::SettingsCategory),
+    /// Overwrite the outside edit with the settings in Nirify
+    KeepMine(crate::config::SettingsCategory),
+    /// Drop unsaved edits to the category and load the file as it is now
+    ReloadTheirs(crate::config::SettingsCategory),
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1448,6 +1663,19 @@ pub enum DialogState {
         before: String,
         after: String,
     },
+    PendingChanges {
+        diff: crate::diff::ConfigDiff,
+    },
+    /// A managed file was edited outside Nirify while it had unsaved changes
+    ExternalChange {
+        category: crate::config::SettingsCategory,
+    },
+    PasteConfig {
+        /// Why the pasted text couldn't be parsed
+        error: Option<String>,
+        /// What the pasted text would import, once it parsed
+        preview: Option<Box<crate::config::ImportResult>>,
+    },
 }
 
 /// First-run wizard steps
@@ -1466,6 +1694,10 @@ pub enum ConfirmAction {
     DeleteRule(u32), // Rule ID
     ResetSettings,
     ClearAllKeybindings,
+    RestoreBackup(usize),   // Index into the backups list
+    RestoreSnapshot(usize), // Index of any backup in the snapshot
+    TakeInstanceLock,       // Edit even though another instance is running
+    RevertLastChange,       // Revert even though there are unsaved edits
 }
 
 /// Consolidation suggestion for rules
@@ -1484,4 +1716,8 @@ pub struct ConsolidationSuggestion {
     pub is_window_rule: bool,
     /// Whether this suggestion is selected for merging
     pub selected: bool,
+    /// Member rules and the merged pattern, one line each
+    pub detail: Vec<String>,
+    /// Whether the detail is shown
+    pub expanded: bool,
 }
@@ -2,7 +2,15 @@
 //!
 //! This module implements automatic saving of settings with a 300ms debounce.
 //! Changes are batched to avoid excessive disk I/O during rapid slider adjustments.
-
+//!
+//! Before writing, each file is compared against what Nirify last wrote or
+//! loaded, so edits made by hand or by scripts while the app is open aren't
+//! overwritten without asking.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -240,6 +248,17 @@ pub enum SaveResult {
     NothingToSave,
 }
 
+impl SaveResult {
+    /// Whether niri should reload to pick up this save
+    ///
+    /// Only saves that changed a file reload, and never when the user has
+    /// turned auto-reload off.
+    pub fn needs_reload(&self, auto_reload: bool) -> bool {
+        auto_reload
+            && matches!(self, SaveResult::Success { files_written, .. } if *files_written > 0)
+    }
+}
+
 /// Result of niri config reload
 #[derive(Debug, Clone)]
 pub enum ReloadResult {
@@ -248,3 +267,157 @@ pub enum ReloadResult {
     /// Reload failed
     Error { message: String },
 }
+
+impl ReloadResult {
+    /// Short status bar text describing the reload outcome
+    pub fn status_message(&self) -> String {
+        match self {
+            ReloadResult::Success => "niri reloaded".to_string(),
+            ReloadResult::Error { message } => format!("reload failed: {}", message),
+        }
+    }
+
+    /// Whether niri accepted the reload
+    pub fn is_success(&self) -> bool {
+        matches!(self, ReloadResult::Success)
+    }
+}
+
+/// Content hash of each managed file as Nirify last wrote or loaded it
+#[derive(Debug, Clone, Default)]
+pub struct FileFingerprints(HashMap<SettingsCategory, u64>);
+
+impl FileFingerprints {
+    /// Fingerprints of every managed file as it is on disk now
+    pub fn read_all(paths: &ConfigPaths) -> Self {
+        let mut fingerprints = Self::default();
+        fingerprints.record(paths, SettingsCategory::all().iter().copied());
+        fingerprints
+    }
+
+    /// Takes what's on disk now as the expected content of `categories`
+    pub fn record(
+        &mut self,
+        paths: &ConfigPaths,
+        categories: impl IntoIterator<Item = SettingsCategory>,
+    ) {
+        for category in categories {
+            match paths.category_path(category).and_then(|p| fingerprint(&p)) {
+                Some(hash) => self.0.insert(category, hash),
+                None => self.0.remove(&category),
+            };
+        }
+    }
+
+    /// First of `categories` whose file changed since it was recorded
+    ///
+    /// Files never recorded and files that were deleted don't count: saving
+    /// over them loses nothing.
+    pub fn changed_on_disk(
+        &self,
+        paths: &ConfigPaths,
+        categories: &std::collections::HashSet<SettingsCategory>,
+    ) -> Option<SettingsCategory> {
+        SettingsCategory::all()
+            .iter()
+            .copied()
+            .filter(|category| categories.contains(category))
+            .find(|category| {
+                let Some(expected) = self.0.get(category) else {
+                    return false;
+                };
+                paths
+                    .category_path(*category)
+                    .and_then(|p| fingerprint(&p))
+                    .is_some_and(|hash| hash != *expected)
+            })
+    }
+}
+
+/// Hash of a file's content, or `None` if it can't be read
+fn fingerprint(path: &Path) -> Option<u64> {
+    let content = std::fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn save(auto_reload: bool) -> (tempfile::TempDir, ConfigPaths, SaveResult) {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ConfigPaths::with_niri_dir(dir.path());
+        paths.ensure_directories().unwrap();
+
+        let mut settings = Settings::default();
+        settings.preferences.auto_reload = auto_reload;
+        let dirty = HashSet::from([SettingsCategory::Appearance, SettingsCategory::Preferences]);
+        let files_written =
+            crate::config::save_dirty(&paths, &settings, &dirty, FeatureCompat::all_enabled())
+                .unwrap();
+        let result = SaveResult::Success {
+            files_written,
+            categories: dirty.into_iter().collect(),
+        };
+        (dir, paths, result)
+    }
+
+    #[test]
+    fn test_save_without_auto_reload_writes_but_does_not_reload() {
+        let (_dir, paths, result) = save(false);
+        assert!(paths.appearance_kdl.exists());
+        assert!(paths.preferences_kdl.exists());
+        assert!(!result.needs_reload(false));
+
+        let (_dir, _, result) = save(true);
+        assert!(result.needs_reload(true));
+    }
+
+    #[test]
+    fn test_unchanged_save_never_reloads() {
+        let result = SaveResult::Success {
+            files_written: 0,
+            categories: vec![SettingsCategory::Appearance],
+        };
+        assert!(!result.needs_reload(true));
+        assert!(!SaveResult::NothingToSave.needs_reload(true));
+    }
+
+    #[test]
+    fn test_detects_file_edited_since_last_save() {
+        let (_dir, paths, _) = save(false);
+        let mut fingerprints = FileFingerprints::read_all(&paths);
+        let dirty = HashSet::from([SettingsCategory::Appearance, SettingsCategory::Preferences]);
+        assert_eq!(fingerprints.changed_on_disk(&paths, &dirty), None);
+
+        std::fs::write(&paths.appearance_kdl, "layout { gaps 4; }\n").unwrap();
+        assert_eq!(
+            fingerprints.changed_on_disk(&paths, &dirty),
+            Some(SettingsCategory::Appearance)
+        );
+        // A category that isn't about to be saved isn't a conflict
+        let preferences_only = HashSet::from([SettingsCategory::Preferences]);
+        assert_eq!(
+            fingerprints.changed_on_disk(&paths, &preferences_only),
+            None
+        );
+
+        // Once the edit is accepted, saving over it goes ahead
+        fingerprints.record(&paths, [SettingsCategory::Appearance]);
+        assert_eq!(fingerprints.changed_on_disk(&paths, &dirty), None);
+    }
+
+    #[test]
+    fn test_deleted_or_unknown_files_are_not_conflicts() {
+        let (_dir, paths, _) = save(false);
+        let fingerprints = FileFingerprints::read_all(&paths);
+        std::fs::remove_file(&paths.appearance_kdl).unwrap();
+        std::fs::write(&paths.keybindings_kdl, "binds {}\n").unwrap();
+
+        let dirty = HashSet::from([SettingsCategory::Appearance, SettingsCategory::Keybindings]);
+        assert_eq!(fingerprints.changed_on_disk(&paths, &dirty), None);
+    }
+}
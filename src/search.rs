@@ -3,7 +3,17 @@
 //! Indexes individual settings with human-readable labels for intuitive search.
 //! Users can search for things like "shadow", "border", "speed" and find the actual
 //! setting they're looking for.
+//!
+//! Besides the fixed settings, the index holds the user's own keybindings and
+//! rules so they can be found by name, key or app ID. Those entries are keyed
+//! by an [`EntryId`] that survives edits, so changing one binding replaces
+//! just its entry; [`SearchIndex::rebuild`] re-indexes everything after bulk
+//! changes like loading or resetting the config.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
+use crate::config::models::{Keybinding, LayerRule, Settings, WindowRule};
 use crate::messages::Page;
 
 /// Search result pointing to a specific setting
@@ -19,11 +29,23 @@ pub struct SearchResult {
     pub relevance_score: u32,
 }
 
+/// Stable key of an index entry built from user content
+///
+/// Uses the item's own `id`, which doesn't change when it is edited or when
+/// other items are added or removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntryId {
+    Keybinding(u32),
+    WindowRule(u32),
+    LayerRule(u32),
+}
+
 /// A searchable setting entry
-struct SettingEntry {
+#[derive(Debug, Clone)]
+pub struct SettingEntry {
     page: Page,
-    setting_name: &'static str,
-    description: &'static str,
+    setting_name: Cow<'static, str>,
+    description: Cow<'static, str>,
     /// Lowercase versions for faster matching
     name_lower: String,
     desc_lower: String,
@@ -34,24 +56,104 @@ struct SettingEntry {
 impl SettingEntry {
     fn new(
         page: Page,
-        setting_name: &'static str,
-        description: &'static str,
+        setting_name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
         keywords: &'static [&'static str],
     ) -> Self {
+        let setting_name = setting_name.into();
+        let description = description.into();
         Self {
             page,
-            setting_name,
-            description,
             name_lower: setting_name.to_lowercase(),
             desc_lower: description.to_lowercase(),
+            setting_name,
+            description,
             keywords,
         }
     }
+
+    /// Entry for a keybinding, found by its title, key combo or action
+    pub fn keybinding(binding: &Keybinding) -> (EntryId, Self) {
+        let key = if binding.key_combo.is_empty() {
+            "Unassigned"
+        } else {
+            binding.key_combo.as_str()
+        };
+        (
+            EntryId::Keybinding(binding.id),
+            Self::new(
+                Page::Keybindings,
+                binding.display_name(),
+                format!("{} — {}", key, binding.action.description()),
+                &["keybinding", "shortcut", "hotkey", "bind"],
+            ),
+        )
+    }
+
+    /// Entry for a window rule, found by its name or the app IDs and titles
+    /// it matches
+    pub fn window_rule(rule: &WindowRule) -> (EntryId, Self) {
+        let patterns: Vec<&str> = rule
+            .matches
+            .iter()
+            .flat_map(|m| [m.app_id.as_deref(), m.title.as_deref()])
+            .flatten()
+            .filter(|p| !p.is_empty())
+            .collect();
+        (
+            EntryId::WindowRule(rule.id),
+            Self::new(
+                Page::WindowRules,
+                rule.name.clone(),
+                rule_description("Window rule", &patterns),
+                &["window", "rule", "app"],
+            ),
+        )
+    }
+
+    /// Entry for a layer rule, found by its name or the namespaces it matches
+    pub fn layer_rule(rule: &LayerRule) -> (EntryId, Self) {
+        let patterns: Vec<&str> = rule
+            .matches
+            .iter()
+            .filter_map(|m| m.namespace.as_deref())
+            .filter(|p| !p.is_empty())
+            .collect();
+        (
+            EntryId::LayerRule(rule.id),
+            Self::new(
+                Page::LayerRules,
+                rule.name.clone(),
+                rule_description("Layer rule", &patterns),
+                &["layer", "rule", "namespace"],
+            ),
+        )
+    }
+
+    fn to_result(&self, relevance_score: u32) -> SearchResult {
+        SearchResult {
+            page: self.page,
+            setting_name: self.setting_name.to_string(),
+            description: self.description.to_string(),
+            relevance_score,
+        }
+    }
+}
+
+fn rule_description(kind: &str, patterns: &[&str]) -> String {
+    if patterns.is_empty() {
+        kind.to_string()
+    } else {
+        format!("{} matching {}", kind, patterns.join(", "))
+    }
 }
 
 /// Search index containing all searchable settings
 pub struct SearchIndex {
     entries: Vec<SettingEntry>,
+    /// Entries for the user's keybindings and rules, ordered by id so results
+    /// don't depend on the order they were added in
+    user_entries: BTreeMap<EntryId, SettingEntry>,
 }
 
 impl SearchIndex {
@@ -59,9 +161,72 @@ impl SearchIndex {
     pub fn new() -> Self {
         Self {
             entries: build_settings_index(),
+            user_entries: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a search index with all settings plus the user's keybindings
+    /// and rules
+    pub fn with_settings(settings: &Settings) -> Self {
+        let mut index = Self::new();
+        index.rebuild(settings);
+        index
+    }
+
+    /// Re-indexes every keybinding and rule from scratch
+    ///
+    /// For bulk changes (loading, importing, resetting); single edits should
+    /// go through [`Self::update_entry`] and friends.
+    pub fn rebuild(&mut self, settings: &Settings) {
+        self.user_entries = settings
+            .keybindings
+            .bindings
+            .iter()
+            .map(SettingEntry::keybinding)
+            .chain(
+                settings
+                    .window_rules
+                    .rules
+                    .iter()
+                    .map(SettingEntry::window_rule),
+            )
+            .chain(
+                settings
+                    .layer_rules
+                    .rules
+                    .iter()
+                    .map(SettingEntry::layer_rule),
+            )
+            .collect();
+    }
+
+    /// Adds an entry for a new keybinding or rule
+    ///
+    /// An existing entry with the same id is replaced.
+    pub fn add_entry(&mut self, id: EntryId, entry: SettingEntry) {
+        self.user_entries.insert(id, entry);
+    }
+
+    /// Replaces the entry of an edited keybinding or rule
+    ///
+    /// Returns `false` (and changes nothing) if there is no entry for `id`.
+    pub fn update_entry(&mut self, id: EntryId, entry: SettingEntry) -> bool {
+        match self.user_entries.get_mut(&id) {
+            Some(existing) => {
+                *existing = entry;
+                true
+            }
+            None => false,
         }
     }
 
+    /// Removes the entry of a deleted keybinding or rule
+    ///
+    /// Returns whether there was one.
+    pub fn remove_entry(&mut self, id: EntryId) -> bool {
+        self.user_entries.remove(&id).is_some()
+    }
+
     /// Searches for settings matching the query
     pub fn search(&self, query: &str) -> Vec<SearchResult> {
         if query.trim().is_empty() {
@@ -74,6 +239,7 @@ impl SearchIndex {
         let mut results: Vec<SearchResult> = self
             .entries
             .iter()
+            .chain(self.user_entries.values())
             .filter_map(|entry| {
                 let mut score = 0u32;
 
@@ -106,12 +272,7 @@ impl SearchIndex {
                 }
 
                 if score > 0 {
-                    Some(SearchResult {
-                        page: entry.page,
-                        setting_name: entry.setting_name.to_string(),
-                        description: entry.description.to_string(),
-                        relevance_score: score,
-                    })
+                    Some(entry.to_result(score))
                 } else {
                     None
                 }
@@ -126,6 +287,22 @@ impl SearchIndex {
 
         results
     }
+
+    /// Looks up starred setting names, in the order given
+    ///
+    /// Names no longer in the index (e.g. renamed settings) are skipped.
+    pub fn favorites(&self, names: &[String]) -> Vec<SearchResult> {
+        names
+            .iter()
+            .filter_map(|name| {
+                self.entries
+                    .iter()
+                    .chain(self.user_entries.values())
+                    .find(|entry| entry.setting_name == name.as_str())
+                    .map(|entry| entry.to_result(0))
+            })
+            .collect()
+    }
 }
 
 impl Default for SearchIndex {
@@ -178,9 +355,9 @@ fn build_settings_index() -> Vec<SettingEntry> {
         ),
         SettingEntry::new(
             Page::Appearance,
-            "Background Color",
-            "Color behind windows and workspaces",
-            &["background", "color", "wallpaper", "desktop"],
+            "Workspace Background",
+            "Color behind the windows of every workspace",
+            &["background", "color", "wallpaper", "workspace", "layout"],
         ),
         SettingEntry::new(
             Page::Appearance,
@@ -342,6 +519,74 @@ fn build_settings_index() -> Vec<SettingEntry> {
             &["touchpad", "speed", "sensitivity", "acceleration"],
         ),
         // ═══════════════════════════════════════════════════════════════════
+        // TRACKPOINT
+        // ═══════════════════════════════════════════════════════════════════
+        SettingEntry::new(
+            Page::Trackpoint,
+            "Trackpoint Acceleration",
+            "How trackpoint speed scales with pressure",
+            &[
+                "trackpoint",
+                "acceleration",
+                "accel",
+                "speed",
+                "pointing stick",
+            ],
+        ),
+        SettingEntry::new(
+            Page::Trackpoint,
+            "Trackpoint Scroll Method",
+            "How scrolling works with the trackpoint",
+            &["trackpoint", "scroll", "button", "middle"],
+        ),
+        // ═══════════════════════════════════════════════════════════════════
+        // TRACKBALL
+        // ═══════════════════════════════════════════════════════════════════
+        SettingEntry::new(
+            Page::Trackball,
+            "Trackball Acceleration",
+            "How trackball speed scales with movement",
+            &["trackball", "acceleration", "accel", "speed"],
+        ),
+        SettingEntry::new(
+            Page::Trackball,
+            "Trackball Scroll Method",
+            "How scrolling works with the trackball",
+            &["trackball", "scroll", "button", "lock"],
+        ),
+        // ═══════════════════════════════════════════════════════════════════
+        // TABLET
+        // ═══════════════════════════════════════════════════════════════════
+        SettingEntry::new(
+            Page::Tablet,
+            "Tablet Output Mapping",
+            "Which monitor the drawing tablet maps to",
+            &[
+                "tablet", "pen", "stylus", "drawing", "monitor", "output", "map",
+            ],
+        ),
+        SettingEntry::new(
+            Page::Tablet,
+            "Tablet Calibration",
+            "Matrix that corrects tablet coordinates",
+            &["tablet", "calibration", "matrix", "pen", "align"],
+        ),
+        // ═══════════════════════════════════════════════════════════════════
+        // TOUCH
+        // ═══════════════════════════════════════════════════════════════════
+        SettingEntry::new(
+            Page::Touch,
+            "Touchscreen Output Mapping",
+            "Which monitor the touchscreen maps to",
+            &["touch", "touchscreen", "monitor", "output", "map"],
+        ),
+        SettingEntry::new(
+            Page::Touch,
+            "Touchscreen Calibration",
+            "Matrix that corrects touch coordinates",
+            &["touch", "touchscreen", "calibration", "matrix", "align"],
+        ),
+        // ═══════════════════════════════════════════════════════════════════
         // CURSOR
         // ═══════════════════════════════════════════════════════════════════
         SettingEntry::new(
@@ -584,12 +829,43 @@ fn build_settings_index() -> Vec<SettingEntry> {
             "Behavior when device enters tablet mode",
             &["tablet", "mode", "convertible", "touch"],
         ),
+        // ═══════════════════════════════════════════════════════════════════
+        // OVERVIEW
+        // ═══════════════════════════════════════════════════════════════════
+        SettingEntry::new(
+            Page::Overview,
+            "Overview Zoom",
+            "How far workspaces zoom out in the overview",
+            &["overview", "zoom", "expose", "scale"],
+        ),
+        SettingEntry::new(
+            Page::Overview,
+            "Overview Backdrop",
+            "Color behind workspaces in the overview",
+            &["overview", "backdrop", "background", "color"],
+        ),
+        // ═══════════════════════════════════════════════════════════════════
+        // RECENT WINDOWS
+        // ═══════════════════════════════════════════════════════════════════
+        SettingEntry::new(
+            Page::RecentWindows,
+            "Window Switcher",
+            "Alt-Tab switcher for recently used windows",
+            &["recent", "windows", "switcher", "alt", "tab", "mru"],
+        ),
+        SettingEntry::new(
+            Page::RecentWindows,
+            "Switcher Highlight",
+            "Colors and shape of the selected window's highlight",
+            &["recent", "switcher", "highlight", "color", "radius"],
+        ),
     ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::models::{KeybindAction, WindowRuleMatch};
 
     #[test]
     fn test_search_shadow() {
@@ -622,4 +898,143 @@ mod tests {
         let results = index.search("");
         assert!(results.is_empty());
     }
+
+    fn binding(id: u32, key_combo: &str, command: &str) -> Keybinding {
+        Keybinding {
+            id,
+            key_combo: key_combo.to_string(),
+            action: KeybindAction::Spawn(vec![command.to_string()]),
+            ..Default::default()
+        }
+    }
+
+    fn window_rule(id: u32, name: &str, app_id: &str) -> WindowRule {
+        WindowRule {
+            id,
+            name: name.to_string(),
+            matches: vec![WindowRuleMatch {
+                app_id: Some(app_id.to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    const QUERIES: &[&str] = &["firefox", "mod+t", "alacritty", "rule", "shortcut", "pip"];
+
+    fn assert_same_results(incremental: &SearchIndex, rebuilt: &SearchIndex) {
+        for query in QUERIES {
+            assert_eq!(
+                incremental.search(query),
+                rebuilt.search(query),
+                "results differ for {:?}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn test_indexes_keybindings_and_rules() {
+        let mut settings = Settings::default();
+        settings.keybindings.bindings = vec![binding(0, "Mod+T", "alacritty")];
+        settings.window_rules.rules = vec![window_rule(0, "Firefox PiP", "firefox")];
+        let index = SearchIndex::with_settings(&settings);
+
+        let results = index.search("alacritty");
+        assert_eq!(results[0].page, Page::Keybindings);
+        assert!(results[0].description.starts_with("Mod+T"));
+
+        let results = index.search("firefox");
+        assert_eq!(results[0].page, Page::WindowRules);
+        assert_eq!(results[0].setting_name, "Firefox PiP");
+    }
+
+    #[test]
+    fn test_incremental_updates_match_full_rebuild() {
+        let mut settings = Settings::default();
+        settings.keybindings.bindings = vec![
+            binding(0, "Mod+T", "alacritty"),
+            binding(1, "Mod+B", "firefox"),
+        ];
+        settings.window_rules.rules = vec![window_rule(0, "Firefox PiP", "firefox")];
+        let mut index = SearchIndex::with_settings(&settings);
+
+        // Edit one binding
+        settings.keybindings.bindings[0].key_combo = "Mod+Return".to_string();
+        let (id, entry) = SettingEntry::keybinding(&settings.keybindings.bindings[0]);
+        assert!(index.update_entry(id, entry));
+        assert_same_results(&index, &SearchIndex::with_settings(&settings));
+
+        // Remove one, add another with a lower id than an existing one
+        let removed = settings.keybindings.bindings.remove(1);
+        assert!(index.remove_entry(EntryId::Keybinding(removed.id)));
+        settings
+            .window_rules
+            .rules
+            .insert(0, window_rule(5, "Pinned", "mpv"));
+        let (id, entry) = SettingEntry::window_rule(&settings.window_rules.rules[0]);
+        index.add_entry(id, entry);
+        assert_same_results(&index, &SearchIndex::with_settings(&settings));
+
+        // Rename a rule
+        settings.window_rules.rules[1].name = "Browser".to_string();
+        let (id, entry) = SettingEntry::window_rule(&settings.window_rules.rules[1]);
+        assert!(index.update_entry(id, entry));
+        assert_same_results(&index, &SearchIndex::with_settings(&settings));
+        assert!(index
+            .search("pip")
+            .iter()
+            .all(|r| r.setting_name != "Firefox PiP"));
+    }
+
+    #[test]
+    fn test_update_and_remove_of_unknown_entry_change_nothing() {
+        let settings = Settings::default();
+        let mut index = SearchIndex::with_settings(&settings);
+
+        let (id, entry) = SettingEntry::keybinding(&binding(99, "Mod+Z", "zathura"));
+        assert!(!index.update_entry(id, entry));
+        assert!(!index.remove_entry(EntryId::LayerRule(3)));
+        assert!(index.search("zathura").is_empty());
+        assert_same_results(&index, &SearchIndex::with_settings(&settings));
+    }
+
+    #[test]
+    fn test_every_settings_page_can_be_starred() {
+        // Stars come from index entries, so each page needs at least one
+        let index = SearchIndex::new();
+        for page in [
+            Page::Overview,
+            Page::Appearance,
+            Page::Behavior,
+            Page::Keyboard,
+            Page::Mouse,
+            Page::Touchpad,
+            Page::Trackpoint,
+            Page::Trackball,
+            Page::Tablet,
+            Page::Touch,
+            Page::Animations,
+            Page::Cursor,
+            Page::LayoutExtras,
+            Page::Gestures,
+            Page::Workspaces,
+            Page::WindowRules,
+            Page::LayerRules,
+            Page::Keybindings,
+            Page::Outputs,
+            Page::Miscellaneous,
+            Page::Startup,
+            Page::Environment,
+            Page::Debug,
+            Page::SwitchEvents,
+            Page::RecentWindows,
+        ] {
+            assert!(
+                index.entries.iter().any(|entry| entry.page == page),
+                "{} has nothing to star",
+                page.name()
+            );
+        }
+    }
 }
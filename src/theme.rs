@@ -7,6 +7,8 @@ use iced::theme::Palette;
 use iced::widget::{button, container};
 use iced::{Border, Color, Shadow, Theme, Vector};
 
+use crate::config::models::AppearanceSettings;
+
 /// Tokyo Neon color constants — shared across all screens
 pub mod neon {
     use iced::Color;
@@ -76,6 +78,11 @@ pub mod neon {
         0x71 as f32 / 255.0,
         0x6c as f32 / 255.0,
     );
+    pub const WARNING: Color = Color::from_rgb(
+        0xf5 as f32 / 255.0,
+        0x9e as f32 / 255.0,
+        0x0a as f32 / 255.0,
+    );
 }
 
 /// Font constants for consistent typography
@@ -740,6 +747,63 @@ pub fn accent_color(theme: &Theme) -> Color {
     theme.palette().primary
 }
 
+/// Contrast an accent needs against the background to stay legible
+/// (WCAG's minimum for large text and UI components)
+pub const MIN_ACCENT_CONTRAST: f32 = 3.0;
+
+/// WCAG contrast ratio between two colors, from 1.0 (same) to 21.0
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    fn luminance(color: Color) -> f32 {
+        let channel = |c: f32| {
+            if c <= 0.039_28 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+    }
+
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Accent taken from the user's niri focus ring color
+///
+/// The color is lightened (on dark backgrounds) or darkened (on light ones)
+/// just enough to reach [`MIN_ACCENT_CONTRAST`] against `background`.
+pub fn niri_accent(appearance: &AppearanceSettings, background: Color) -> Color {
+    let ring = appearance.focus_ring_active.primary_color();
+    let color = Color::from_rgb8(ring.r, ring.g, ring.b);
+    let toward =
+        if contrast_ratio(background, Color::WHITE) > contrast_ratio(background, Color::BLACK) {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        };
+
+    (0..=10)
+        .map(|step| {
+            let t = step as f32 / 10.0;
+            Color::from_rgb(
+                color.r + (toward.r - color.r) * t,
+                color.g + (toward.g - color.g) * t,
+                color.b + (toward.b - color.b) * t,
+            )
+        })
+        .find(|accent| contrast_ratio(*accent, background) >= MIN_ACCENT_CONTRAST)
+        .unwrap_or(toward)
+}
+
+/// `theme` with its primary color replaced by `accent`
+pub fn with_accent(theme: &Theme, accent: Color) -> Theme {
+    let palette = Palette {
+        primary: accent,
+        ..theme.palette()
+    };
+    Theme::custom(theme.to_string(), palette)
+}
+
 /// Helper: Get muted text color from theme
 pub fn muted_text_color(theme: &Theme) -> Color {
     let text = theme.palette().text;
@@ -928,3 +992,46 @@ pub fn status_pill_style(theme: &Theme, variant: PillVariant) -> container::Styl
         snap: false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ColorOrGradient;
+
+    fn appearance_with_ring(hex: &str) -> AppearanceSettings {
+        AppearanceSettings {
+            focus_ring_active: ColorOrGradient::Color(crate::types::Color::from_hex(hex).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_niri_accent_is_legible_on_theme_background() {
+        // Ring colors close to each background, the hardest cases
+        for hex in ["#1a1a2e", "#7fc8ff", "#f5f5f5", "#808080"] {
+            for theme in AppTheme::all().iter().map(|t| t.to_iced_theme()) {
+                let background = theme.palette().background;
+                let accent = niri_accent(&appearance_with_ring(hex), background);
+                assert!(
+                    contrast_ratio(accent, background) >= MIN_ACCENT_CONTRAST,
+                    "{} on {} is too faint",
+                    hex,
+                    theme
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_niri_accent_keeps_a_legible_ring_color() {
+        let background = Color::from_rgb8(0x0c, 0x0d, 0x18);
+        let accent = niri_accent(&appearance_with_ring("#7fc8ff"), background);
+        assert_eq!(accent, Color::from_rgb8(0x7f, 0xc8, 0xff));
+    }
+
+    #[test]
+    fn test_contrast_ratio_bounds() {
+        assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(Color::WHITE, Color::WHITE) - 1.0).abs() < 0.01);
+    }
+}
@@ -28,6 +28,160 @@ fn parse_hex_pair(high: u8, low: u8) -> Option<u8> {
     Some(h * 16 + l)
 }
 
+/// The CSS named colors, sorted by name for binary search
+///
+/// Includes both `gray` and `grey` spellings, as CSS does.
+const CSS_NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
 impl Color {
     /// Parses a hex color string into a Color struct.
     ///
@@ -89,6 +243,25 @@ impl Color {
         }
     }
 
+    /// Looks up a CSS named color like `rebeccapurple`, ignoring case
+    ///
+    /// Returns `None` for anything that isn't one of the standard CSS names.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        let idx = CSS_NAMED_COLORS
+            .binary_search_by(|(n, _)| (*n).cmp(name.as_str()))
+            .ok()?;
+        let [r, g, b] = CSS_NAMED_COLORS[idx].1;
+        Some(Color { r, g, b, a: 255 })
+    }
+
+    /// Parses a color typed or pasted by the user: hex first, then a CSS name
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::from_hex(input).or_else(|| Self::from_name(input))
+    }
+
     /// Converts the Color to a lowercase hex string.
     ///
     /// Returns a 7-character string (#RRGGBB) for fully opaque colors (alpha = 255),
@@ -106,6 +279,99 @@ impl Color {
             format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
         }
     }
+
+    /// Converts to hue (degrees, 0-360), saturation and lightness (0-1)
+    ///
+    /// Alpha is ignored; grays have a hue and saturation of 0.
+    #[must_use]
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue_max_min();
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        let saturation = if delta == 0.0 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        (hue, saturation, lightness)
+    }
+
+    /// Builds an opaque color from hue (degrees), saturation and lightness (0-1)
+    ///
+    /// Hue wraps around; saturation and lightness are clamped.
+    #[must_use]
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        Self::from_hsla(h, s, l, 255)
+    }
+
+    /// Like [`Color::from_hsl`], keeping the given alpha
+    #[must_use]
+    pub fn from_hsla(h: f32, s: f32, l: f32, a: u8) -> Self {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_hue_chroma(h, chroma, l - chroma / 2.0, a)
+    }
+
+    /// Converts to hue (degrees, 0-360), saturation and value (0-1)
+    #[must_use]
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue_max_min();
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        (hue, saturation, max)
+    }
+
+    /// Builds a color from hue (degrees), saturation and value (0-1), keeping `a`
+    #[must_use]
+    pub fn from_hsva(h: f32, s: f32, v: f32, a: u8) -> Self {
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+        let chroma = v * s;
+        Self::from_hue_chroma(h, chroma, v - chroma, a)
+    }
+
+    /// Hue in degrees plus the largest and smallest channel, all as 0-1 floats
+    fn hue_max_min(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (hue, max, min)
+    }
+
+    /// Shared tail of the HSL and HSV conversions: places `chroma` on the hue
+    /// wheel and lifts every channel by `offset`
+    fn from_hue_chroma(h: f32, chroma: f32, offset: f32, a: u8) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |c: f32| ((c + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Color {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+            a,
+        }
+    }
 }
 
 /// Modifier key used for compositor shortcuts and bindings.
@@ -139,14 +405,7 @@ impl std::fmt::Display for ModKey {
 impl ModKey {
     /// Returns all possible values for UI pickers
     pub fn all() -> &'static [Self] {
-        &[
-            Self::Super,
-            Self::Alt,
-            Self::Ctrl,
-            Self::Shift,
-            Self::Mod3,
-            Self::Mod5,
-        ]
+        Self::ALL
     }
 
     /// Convert to KDL string representation
@@ -199,7 +458,7 @@ impl std::fmt::Display for AccelProfile {
 
 impl AccelProfile {
     pub fn all() -> &'static [Self] {
-        &[Self::Adaptive, Self::Flat]
+        Self::ALL
     }
 }
 
@@ -233,12 +492,7 @@ impl std::fmt::Display for ScrollMethod {
 
 impl ScrollMethod {
     pub fn all() -> &'static [Self] {
-        &[
-            Self::TwoFinger,
-            Self::Edge,
-            Self::OnButtonDown,
-            Self::NoScroll,
-        ]
+        Self::ALL
     }
 }
 
@@ -266,7 +520,7 @@ impl std::fmt::Display for ClickMethod {
 
 impl ClickMethod {
     pub fn all() -> &'static [Self] {
-        &[Self::ButtonAreas, Self::Clickfinger]
+        Self::ALL
     }
 }
 
@@ -327,7 +581,7 @@ impl std::fmt::Display for CenterFocusedColumn {
 
 impl CenterFocusedColumn {
     pub fn all() -> &'static [Self] {
-        &[Self::Never, Self::OnOverflow, Self::Always]
+        Self::ALL
     }
 
     /// Convert to KDL string representation
@@ -374,7 +628,7 @@ impl std::fmt::Display for TapButtonMap {
 
 impl TapButtonMap {
     pub fn all() -> &'static [Self] {
-        &[Self::LeftRightMiddle, Self::LeftMiddleRight]
+        Self::ALL
     }
 }
 
@@ -420,16 +674,15 @@ impl std::fmt::Display for Transform {
 
 impl Transform {
     pub fn all() -> &'static [Self] {
-        &[
-            Self::Normal,
-            Self::Rotate90,
-            Self::Rotate180,
-            Self::Rotate270,
-            Self::Flipped,
-            Self::Flipped90,
-            Self::Flipped180,
-            Self::Flipped270,
-        ]
+        Self::ALL
+    }
+
+    /// Whether this transform turns the output sideways, swapping width and height
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Flipped90 | Self::Flipped270
+        )
     }
 }
 
@@ -460,7 +713,7 @@ impl std::fmt::Display for VrrMode {
 
 impl VrrMode {
     pub fn all() -> &'static [Self] {
-        &[Self::Off, Self::On, Self::OnDemand]
+        Self::ALL
     }
 }
 
@@ -812,6 +1065,36 @@ impl NavigationCategory {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_try_from_index_rejects_unknown_index() {
+        assert_eq!(ModKey::try_from_index(0), Some(ModKey::Super));
+        assert_eq!(ModKey::try_from_index(5), Some(ModKey::Mod5));
+        assert_eq!(ModKey::try_from_index(6), None);
+        assert_eq!(ModKey::try_from_index(-1), None);
+        // from_index still falls back to the default
+        assert_eq!(ModKey::from_index(6), ModKey::Super);
+    }
+
+    #[test]
+    fn test_slint_index_all_lists_every_variant() {
+        #[derive(Debug, Clone, Copy, PartialEq, SlintIndex)]
+        enum Single {
+            Only,
+        }
+
+        assert_eq!(Transform::ALL.len(), Transform::COUNT);
+        assert_eq!(Transform::COUNT, 8);
+        assert_eq!(
+            VrrMode::ALL,
+            &[VrrMode::Off, VrrMode::On, VrrMode::OnDemand]
+        );
+        for (idx, mode) in ModKey::ALL.iter().enumerate() {
+            assert_eq!(mode.to_index(), idx as i32);
+        }
+        assert_eq!(Single::ALL, &[Single::Only]);
+        assert_eq!(Single::ALL.len(), Single::COUNT);
+    }
+
     #[test]
     fn test_color_from_hex_6digit() {
         let color = Color::from_hex("#7fc8ff").unwrap();
@@ -870,6 +1153,104 @@ mod tests {
         assert!(Color::from_hex("#12345z").is_none());
     }
 
+    #[test]
+    fn test_color_from_name() {
+        let purple = Color::from_name("rebeccapurple").unwrap();
+        assert_eq!(purple.to_hex(), "#663399");
+        assert_eq!(
+            Color::from_name("cornflowerblue").unwrap().to_hex(),
+            "#6495ed"
+        );
+        assert_eq!(Color::from_name("grey"), Color::from_name("gray"));
+        assert!(Color::from_name("notacolor").is_none());
+        assert!(Color::from_name("").is_none());
+    }
+
+    #[test]
+    fn test_color_from_name_ignores_case() {
+        let red = Some(Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        });
+        assert_eq!(Color::from_name("red"), red);
+        assert_eq!(Color::from_name("Red"), red);
+        assert_eq!(Color::from_name("RED"), red);
+    }
+
+    #[test]
+    fn test_color_names_are_sorted() {
+        assert!(CSS_NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_color_parse_tries_hex_then_name() {
+        assert_eq!(Color::parse("#ff0000"), Color::from_name("red"));
+        assert_eq!(Color::parse("Tomato").unwrap().to_hex(), "#ff6347");
+        assert!(Color::parse("nope").is_none());
+    }
+
+    #[test]
+    fn test_color_to_hsl() {
+        let (h, s, l) = Color::from_hex("#ff0000").unwrap().to_hsl();
+        assert_eq!((h, s, l), (0.0, 1.0, 0.5));
+
+        let (h, s, l) = Color::from_hex("#808080").unwrap().to_hsl();
+        assert_eq!((h, s), (0.0, 0.0));
+        assert!((l - 0.502).abs() < 0.001);
+
+        let (h, s, l) = Color::from_hex("#663399").unwrap().to_hsl();
+        assert!((h - 270.0).abs() < 0.01);
+        assert!((s - 0.5).abs() < 0.01);
+        assert!((l - 0.4).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_color_rgb_hsl_rgb_is_exact() {
+        for hex in [
+            "#7fc8ff", "#eb6f92", "#505050", "#000000", "#ffffff", "#12ab34",
+        ] {
+            let color = Color::from_hex(hex).unwrap();
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(Color::from_hsl(h, s, l), color, "hsl of {}", hex);
+            let (h, s, v) = color.to_hsv();
+            assert_eq!(Color::from_hsva(h, s, v, 255), color, "hsv of {}", hex);
+        }
+    }
+
+    #[test]
+    fn test_color_hsl_roundtrip_within_epsilon() {
+        let samples = [
+            (0.0, 1.0, 0.5),
+            (120.0, 0.5, 0.25),
+            (210.0, 0.8, 0.6),
+            (300.0, 0.3, 0.75),
+            (45.5, 0.9, 0.1),
+            (359.0, 0.65, 0.45),
+        ];
+        for (h, s, l) in samples {
+            let (h2, s2, l2) = Color::from_hsl(h, s, l).to_hsl();
+            // Hue is the least precise after rounding to 8-bit channels
+            assert!((h - h2).abs() < 2.0, "hue {} became {}", h, h2);
+            assert!((s - s2).abs() < 0.02, "saturation {} became {}", s, s2);
+            assert!((l - l2).abs() < 0.01, "lightness {} became {}", l, l2);
+        }
+    }
+
+    #[test]
+    fn test_color_from_hsl_wraps_hue_and_keeps_alpha() {
+        assert_eq!(
+            Color::from_hsl(360.0, 1.0, 0.5),
+            Color::from_hsl(0.0, 1.0, 0.5)
+        );
+        assert_eq!(
+            Color::from_hsl(-120.0, 1.0, 0.5),
+            Color::from_hsl(240.0, 1.0, 0.5)
+        );
+        assert_eq!(Color::from_hsla(0.0, 1.0, 0.5, 0x80).to_hex(), "#ff000080");
+    }
+
     #[test]
     fn test_color_to_hex() {
         let color = Color {
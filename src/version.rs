@@ -64,6 +64,10 @@ impl Ord for NiriVersion {
 pub enum NiriFeature {
     /// Recent windows (Alt-Tab) switcher configuration
     RecentWindows,
+    /// Per-window tile layout in the `Windows` IPC reply
+    WindowLayout,
+    /// Touchpad `drag` toggle for tap-and-drag
+    TouchpadDrag,
 }
 
 impl NiriFeature {
@@ -74,6 +78,14 @@ impl NiriFeature {
                 major: 25,
                 minor: 11,
             },
+            Self::WindowLayout => NiriVersion {
+                major: 25,
+                minor: 5,
+            },
+            Self::TouchpadDrag => NiriVersion {
+                major: 25,
+                minor: 5,
+            },
         }
     }
 
@@ -81,6 +93,8 @@ impl NiriFeature {
     pub fn display_name(&self) -> &'static str {
         match self {
             Self::RecentWindows => "Recent Windows (Alt-Tab) Switcher",
+            Self::WindowLayout => "Adopt Gaps from Running niri",
+            Self::TouchpadDrag => "Touchpad Tap-and-Drag Toggle",
         }
     }
 
@@ -92,7 +106,11 @@ impl NiriFeature {
 
 /// Check which features are unsupported by the given version
 pub fn get_unsupported_features(version: NiriVersion) -> Vec<NiriFeature> {
-    let all_features = [NiriFeature::RecentWindows];
+    let all_features = [
+        NiriFeature::RecentWindows,
+        NiriFeature::WindowLayout,
+        NiriFeature::TouchpadDrag,
+    ];
 
     all_features
         .into_iter()
@@ -104,6 +122,8 @@ pub fn get_unsupported_features(version: NiriVersion) -> Vec<NiriFeature> {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct FeatureCompat {
     pub recent_windows: bool,
+    /// Touchpad `drag` is written (older niri rejects it)
+    pub touchpad_drag: bool,
 }
 
 impl FeatureCompat {
@@ -112,10 +132,14 @@ impl FeatureCompat {
         match version {
             Some(v) => Self {
                 recent_windows: NiriFeature::RecentWindows.is_supported_by(v),
+                touchpad_drag: NiriFeature::TouchpadDrag.is_supported_by(v),
             },
-            // If we can't detect version, be conservative and disable new features
+            // If we can't detect version, be conservative and disable new features.
+            // `drag` is the exception: leaving it out would drop a setting the
+            // user's own config may already hold
             None => Self {
                 recent_windows: false,
+                touchpad_drag: true,
             },
         }
     }
@@ -124,6 +148,7 @@ impl FeatureCompat {
     pub fn all_enabled() -> Self {
         Self {
             recent_windows: true,
+            touchpad_drag: true,
         }
     }
 }
@@ -191,6 +216,11 @@ mod tests {
 
         assert!(!NiriFeature::RecentWindows.is_supported_by(v25_08));
         assert!(NiriFeature::RecentWindows.is_supported_by(v25_11));
+        assert!(NiriFeature::WindowLayout.is_supported_by(v25_08));
+        assert!(!NiriFeature::WindowLayout.is_supported_by(NiriVersion {
+            major: 25,
+            minor: 2,
+        }));
     }
 
     #[test]
@@ -210,6 +240,13 @@ mod tests {
         assert!(unsupported.is_empty());
     }
 
+    #[test]
+    fn test_unknown_version_keeps_touchpad_drag() {
+        let compat = FeatureCompat::from_version(None);
+        assert!(compat.touchpad_drag);
+        assert!(!compat.recent_windows);
+    }
+
     #[test]
     fn test_display() {
         let v = NiriVersion {
@@ -1,6 +1,6 @@
 //! Animations settings view — neon modal style
 
-use iced::widget::{button, column, container, pick_list, row, scrollable, text, Space};
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, toggler, Space};
 use iced::{Alignment, Element, Length};
 
 use super::widgets::toggle_row;
@@ -9,8 +9,24 @@ use crate::messages::{AnimationsMessage, Message};
 use crate::theme::{fonts, neon};
 
 /// Animation type options for the dropdown
-const ANIMATION_TYPES: [&str; 5] = ["Default", "Off", "Spring", "Easing", "Custom Shader"];
-const ANIMATION_TYPES_NO_SHADER: [&str; 4] = ["Default", "Off", "Spring", "Easing"];
+const ANIMATION_TYPES: [&str; 4] = ["Default", "Spring", "Easing", "Custom Shader"];
+const ANIMATION_TYPES_NO_SHADER: [&str; 3] = ["Default", "Spring", "Easing"];
+
+/// Name the overview open/close animation is edited under, on both the
+/// Animations page and the Overview page
+pub const OVERVIEW_ANIMATION: &str = "overview";
+
+/// Card for the overview open/close animation, shown on the Overview page
+///
+/// It edits the same `overview_open_close` config as the Animations page.
+pub fn overview_animation_card(settings: &AnimationSettings) -> Element<'_, Message> {
+    animation_card(
+        OVERVIEW_ANIMATION,
+        AnimationId::Overview,
+        &settings.per_animation.overview_open_close,
+        false,
+    )
+}
 
 /// Creates the animations settings view
 pub fn view(settings: &AnimationSettings) -> Element<'_, Message> {
@@ -94,7 +110,7 @@ pub fn view(settings: &AnimationSettings) -> Element<'_, Message> {
                     false,
                 ),
                 animation_card(
-                    "overview",
+                    OVERVIEW_ANIMATION,
                     AnimationId::Overview,
                     &settings.per_animation.overview_open_close,
                     false,
@@ -147,10 +163,9 @@ fn animation_card<'a>(
 ) -> Element<'a, Message> {
     let type_index = match config.animation_type {
         AnimationType::Default => 0,
-        AnimationType::Off => 1,
-        AnimationType::Spring => 2,
-        AnimationType::Easing => 3,
-        AnimationType::CustomShader => 4,
+        AnimationType::Spring => 1,
+        AnimationType::Easing => 2,
+        AnimationType::CustomShader => 3,
     };
 
     let type_options: Vec<&str> = if supports_shader {
@@ -165,29 +180,46 @@ fn animation_card<'a>(
     let type_selector = pick_list(type_options, selected_type, move |selected: &str| {
         let idx = match selected {
             "Default" => 0,
-            "Off" => 1,
-            "Spring" => 2,
-            "Easing" => 3,
-            "Custom Shader" => 4,
+            "Spring" => 1,
+            "Easing" => 2,
+            "Custom Shader" => 3,
             _ => 0,
         };
         Message::Animations(AnimationsMessage::SetAnimationType(name_owned.clone(), idx))
     })
     .width(Length::Fixed(140.0));
 
+    let name_enabled = name.to_string();
+    let enabled_toggle = toggler(config.enabled)
+        .on_toggle(move |enabled| {
+            Message::Animations(AnimationsMessage::SetAnimationEnabled(
+                name_enabled.clone(),
+                enabled,
+            ))
+        })
+        .width(Length::Shrink);
+
     let mut card_content = column![row![
+        enabled_toggle,
         text(id.name())
             .size(12)
             .font(fonts::UI_FONT_SEMIBOLD)
-            .color(neon::ON_SURFACE),
+            .color(if config.enabled {
+                neon::ON_SURFACE
+            } else {
+                neon::OUTLINE_VARIANT
+            }),
         Space::new().width(Length::Fill),
         type_selector,
     ]
+    .spacing(8)
     .align_y(Alignment::Center),]
     .spacing(6);
 
     // Show parameters based on animation type
     match config.animation_type {
+        // Disabled animations keep their curve but hide it
+        _ if !config.enabled => {}
         AnimationType::Spring => {
             let name_dr = name.to_string();
             let name_ep = name.to_string();
@@ -3,13 +3,13 @@
 use iced::widget::{column, container, row, scrollable, text, Space};
 use iced::{Alignment, Element, Length};
 
-use super::widgets::{gradient_picker, info_text, toggle_row};
+use super::widgets::{color_picker_row, gradient_picker, info_text, toggle_row};
 use crate::config::models::AppearanceSettings;
 use crate::messages::{AppearanceMessage, Message};
 use crate::theme::{fonts, neon};
 
 /// Creates the full appearance settings view
-pub fn view(settings: &AppearanceSettings) -> Element<'_, Message> {
+pub fn view(settings: &AppearanceSettings, link_border_colors: bool) -> Element<'static, Message> {
     let content = column![
         // ── 2-COLUMN: FOCUS RING | BORDER ──
         row![
@@ -75,12 +75,15 @@ pub fn view(settings: &AppearanceSettings) -> Element<'_, Message> {
                 info_text("Window borders are drawn inside the window geometry."),
                 Space::new().height(4),
                 container(
-                    column![toggle_row(
-                        "Enable border",
-                        "Show a colored border around windows",
-                        settings.border_enabled,
-                        |v| Message::Appearance(AppearanceMessage::ToggleBorder(v)),
-                    ),]
+                    column![
+                        toggle_row(
+                            "Enable border",
+                            "Show a colored border around windows",
+                            settings.border_enabled,
+                            |v| Message::Appearance(AppearanceMessage::ToggleBorder(v)),
+                        ),
+                        link_border_colors_toggle(link_border_colors),
+                    ]
                     .spacing(0),
                 )
                 .padding(8)
@@ -95,31 +98,7 @@ pub fn view(settings: &AppearanceSettings) -> Element<'_, Message> {
                     |v| Message::Appearance(AppearanceMessage::SetBorderThickness(v)),
                 ),
                 Space::new().height(4),
-                container(
-                    column![
-                        gradient_picker(
-                            "Active window border",
-                            "Color or gradient for the active window border",
-                            &settings.border_active,
-                            |msg| Message::Appearance(AppearanceMessage::BorderActive(msg)),
-                        ),
-                        gradient_picker(
-                            "Inactive window border",
-                            "Color or gradient for inactive window borders",
-                            &settings.border_inactive,
-                            |msg| Message::Appearance(AppearanceMessage::BorderInactive(msg)),
-                        ),
-                        gradient_picker(
-                            "Urgent window border",
-                            "Color or gradient for urgent window borders",
-                            &settings.border_urgent,
-                            |msg| Message::Appearance(AppearanceMessage::BorderUrgent(msg)),
-                        ),
-                    ]
-                    .spacing(0),
-                )
-                .padding(8)
-                .style(crate::theme::card_style),
+                border_colors(settings, link_border_colors),
             ]
             .spacing(6)
             .width(Length::FillPortion(1)),
@@ -149,6 +128,8 @@ pub fn view(settings: &AppearanceSettings) -> Element<'_, Message> {
             ),
         ]
         .spacing(16),
+        Space::new().height(4),
+        workspace_background_card(settings),
     ]
     .spacing(0)
     .width(Length::Fill);
@@ -214,17 +195,23 @@ pub fn focus_ring_section(settings: &AppearanceSettings) -> Element<'_, Message>
 }
 
 /// Window border settings — toggle, thickness, active/inactive/urgent colors
-pub fn border_section(settings: &AppearanceSettings) -> Element<'_, Message> {
+pub fn border_section(
+    settings: &AppearanceSettings,
+    link_border_colors: bool,
+) -> Element<'_, Message> {
     column![
         modal_section("\u{25A7}", "BORDER", neon::TERTIARY),
         Space::new().height(4),
         container(
-            column![toggle_row(
-                "Enable border",
-                "Show a colored border around windows",
-                settings.border_enabled,
-                |v| Message::Appearance(AppearanceMessage::ToggleBorder(v)),
-            ),]
+            column![
+                toggle_row(
+                    "Enable border",
+                    "Show a colored border around windows",
+                    settings.border_enabled,
+                    |v| Message::Appearance(AppearanceMessage::ToggleBorder(v)),
+                ),
+                link_border_colors_toggle(link_border_colors),
+            ]
             .spacing(0),
         )
         .padding(8)
@@ -238,36 +225,59 @@ pub fn border_section(settings: &AppearanceSettings) -> Element<'_, Message> {
             1.0,
             |v| Message::Appearance(AppearanceMessage::SetBorderThickness(v)),
         ),
-        container(
-            column![
-                gradient_picker(
-                    "Active window border",
-                    "Color or gradient for the active window border",
-                    &settings.border_active,
-                    |msg| Message::Appearance(AppearanceMessage::BorderActive(msg)),
-                ),
-                gradient_picker(
-                    "Inactive window border",
-                    "Color or gradient for inactive window borders",
-                    &settings.border_inactive,
-                    |msg| Message::Appearance(AppearanceMessage::BorderInactive(msg)),
-                ),
-                gradient_picker(
-                    "Urgent window border",
-                    "Color or gradient for urgent window borders",
-                    &settings.border_urgent,
-                    |msg| Message::Appearance(AppearanceMessage::BorderUrgent(msg)),
-                ),
-            ]
-            .spacing(0),
-        )
-        .padding(8)
-        .style(crate::theme::card_style),
+        border_colors(settings, link_border_colors),
     ]
     .spacing(6)
     .into()
 }
 
+/// Toggle that makes the border use the focus ring colors
+fn link_border_colors_toggle(link_border_colors: bool) -> Element<'static, Message> {
+    toggle_row(
+        "Use focus ring colors for border",
+        "Keep the border colors in sync with the focus ring",
+        link_border_colors,
+        |v| Message::Appearance(AppearanceMessage::LinkBorderColors(v)),
+    )
+}
+
+/// Border color pickers, or a note while they follow the focus ring
+fn border_colors(
+    settings: &AppearanceSettings,
+    link_border_colors: bool,
+) -> Element<'static, Message> {
+    if link_border_colors {
+        return info_text("Border colors follow the focus ring colors.");
+    }
+
+    container(
+        column![
+            gradient_picker(
+                "Active window border",
+                "Color or gradient for the active window border",
+                &settings.border_active,
+                |msg| Message::Appearance(AppearanceMessage::BorderActive(msg)),
+            ),
+            gradient_picker(
+                "Inactive window border",
+                "Color or gradient for inactive window borders",
+                &settings.border_inactive,
+                |msg| Message::Appearance(AppearanceMessage::BorderInactive(msg)),
+            ),
+            gradient_picker(
+                "Urgent window border",
+                "Color or gradient for urgent window borders",
+                &settings.border_urgent,
+                |msg| Message::Appearance(AppearanceMessage::BorderUrgent(msg)),
+            ),
+        ]
+        .spacing(0),
+    )
+    .padding(8)
+    .style(crate::theme::card_style)
+    .into()
+}
+
 /// Gaps & corner radius settings
 pub fn gaps_section(settings: &AppearanceSettings) -> Element<'_, Message> {
     column![
@@ -289,11 +299,42 @@ pub fn gaps_section(settings: &AppearanceSettings) -> Element<'_, Message> {
             1.0,
             |v| Message::Appearance(AppearanceMessage::SetCornerRadius(v)),
         ),
+        workspace_background_card(settings),
     ]
     .spacing(6)
     .into()
 }
 
+/// Workspace background — niri's `layout { background-color }`, shown
+/// around and behind windows, not a background drawn inside them
+fn workspace_background_card(settings: &AppearanceSettings) -> Element<'static, Message> {
+    let mut content = column![toggle_row(
+        "Custom workspace background",
+        "Color behind the windows of every workspace",
+        settings.background_color.is_some(),
+        |on| {
+            // Start from a neutral gray; turning it off goes back to niri's default
+            let color = on.then(|| "#404040".to_string());
+            Message::Appearance(AppearanceMessage::SetBackgroundColor(color))
+        },
+    )]
+    .spacing(0);
+
+    if let Some(color) = &settings.background_color {
+        content = content.push(color_picker_row(
+            "Workspace background color",
+            "Hex color, with optional alpha",
+            color,
+            |hex| Message::Appearance(AppearanceMessage::SetBackgroundColor(Some(hex))),
+        ));
+    }
+
+    container(content)
+        .padding(8)
+        .style(crate::theme::card_style)
+        .into()
+}
+
 // ── Helpers ────────────────────────────────────────────────────────────────
 
 fn modal_section<'a>(icon: &'a str, label: &'a str, accent: iced::Color) -> Element<'a, Message> {
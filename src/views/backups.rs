@@ -3,10 +3,17 @@
 //! Browse and restore configuration backups.
 //! Backups are created when the config is modified through the wizard.
 
-use iced::widget::{button, column, container, row, scrollable, text, Column, Space};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Space};
 use iced::{Alignment, Element, Length};
 
-use crate::messages::{BackupEntry, BackupsMessage, Message};
+use super::widgets::slider_row_int;
+use crate::config::models::BackupRetention;
+use crate::config::DiffLine;
+use crate::constants::{
+    BACKUP_RETENTION_DAYS_MAX, BACKUP_RETENTION_DAYS_MIN, BACKUP_RETENTION_MAX,
+    BACKUP_RETENTION_MIN, DEFAULT_BACKUP_RETENTION, DEFAULT_BACKUP_RETENTION_DAYS,
+};
+use crate::messages::{BackupEntry, BackupsMessage, Message, PreferencesMessage};
 use crate::theme::{fonts, neon};
 
 /// State for the backups page
@@ -14,10 +21,16 @@ use crate::theme::{fonts, neon};
 pub struct BackupsState {
     /// List of available backups
     pub backups: Vec<BackupEntry>,
+    /// Indices into `backups` of files backed up together, by timestamp
+    pub snapshots: Vec<Vec<usize>>,
     /// Currently selected backup index
     pub selected_backup: Option<usize>,
     /// Preview content for selected backup
     pub preview_content: Option<Result<String, String>>,
+    /// What restoring the selected backup would change in the current config
+    pub preview_diff: Option<Result<Vec<DiffLine>, String>>,
+    /// Whether the preview shows the raw file instead of the diff
+    pub show_raw: bool,
     /// Status message (success/error feedback)
     pub status_message: Option<String>,
     /// Whether the list is loading
@@ -26,10 +39,26 @@ pub struct BackupsState {
     pub loading_preview: bool,
     /// Whether restore is in progress
     pub restoring: bool,
+    /// Label typed for the next snapshot
+    pub snapshot_label: String,
+    /// Whether a snapshot is being written
+    pub creating_snapshot: bool,
+}
+
+impl BackupsState {
+    /// Indices of the backups taken together with backup `idx`, itself
+    /// included
+    pub fn snapshot_of(&self, idx: usize) -> &[usize] {
+        self.snapshots
+            .iter()
+            .find(|members| members.contains(&idx))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
 
 /// Creates the backups view
-pub fn view(state: &BackupsState) -> Element<'_, Message> {
+pub fn view(state: &BackupsState, retention: BackupRetention) -> Element<'_, Message> {
     let content = column![
         // ── 2-COLUMN: ACTIONS | BACKUP LIST ──
         row![
@@ -52,6 +81,12 @@ pub fn view(state: &BackupsState) -> Element<'_, Message> {
                 )
                 .padding(12)
                 .style(crate::theme::card_style),
+                container(snapshot_form(state))
+                    .padding(12)
+                    .style(crate::theme::card_style),
+                container(retention_control(retention))
+                    .padding(12)
+                    .style(crate::theme::card_style),
                 // Status message
                 if let Some(status) = &state.status_message {
                     let color = if status.contains("Error") || status.contains("Failed") {
@@ -140,6 +175,7 @@ pub fn view(state: &BackupsState) -> Element<'_, Message> {
                                             )),
                                             neon::TERTIARY,
                                         ),
+                                        snapshot_restore(state, selected_idx),
                                     ]
                                     .spacing(2),
                                 )
@@ -190,41 +226,10 @@ pub fn view(state: &BackupsState) -> Element<'_, Message> {
                 Space::new().height(12),
                 // Preview area
                 modal_section("\u{1F50D}", "PREVIEW", neon::TERTIARY),
+                preview_mode_toggle(state.show_raw),
                 Space::new().height(4),
                 {
-                    let preview_display = if state.selected_backup.is_some() {
-                        match &state.preview_content {
-                            Some(Ok(file_text)) => {
-                                if file_text.is_empty() {
-                                    text("(empty backup)")
-                                        .size(12)
-                                        .color(neon::OUTLINE)
-                                        .font(fonts::MONO_FONT)
-                                } else {
-                                    text(file_text)
-                                        .size(11)
-                                        .font(fonts::MONO_FONT)
-                                        .color(neon::ON_SURFACE)
-                                }
-                            }
-                            Some(Err(error)) => text(format!("Error: {}", error))
-                                .size(12)
-                                .color(neon::ERROR),
-                            None => {
-                                if state.loading_preview {
-                                    text("Loading preview...").size(12).color(neon::OUTLINE)
-                                } else {
-                                    text("Select a backup and click 'Load Preview'")
-                                        .size(12)
-                                        .color(neon::OUTLINE)
-                                }
-                            }
-                        }
-                    } else {
-                        text("Select a backup to preview its contents")
-                            .size(12)
-                            .color(neon::OUTLINE)
-                    };
+                    let preview_display = preview_body(state);
                     container(
                         scrollable(container(preview_display).padding(12).width(Length::Fill))
                             .height(Length::Fixed(300.0)),
@@ -252,11 +257,308 @@ pub fn view(state: &BackupsState) -> Element<'_, Message> {
     .spacing(0)
     .width(Length::Fill);
 
-    scrollable(container(content).padding(8).width(Length::Fill))
+    super::screens::page_scrollable(container(content).padding(8).width(Length::Fill))
         .height(Length::Fill)
         .into()
 }
 
+/// Label field and button for backing up every config file by hand
+fn snapshot_form(state: &BackupsState) -> Element<'_, Message> {
+    let can_create = !state.creating_snapshot
+        && crate::config::backup::sanitize_label(&state.snapshot_label).is_some();
+    let create = Message::Backups(BackupsMessage::CreateSnapshot);
+    column![
+        text("Snapshot every config file under a name you'll recognize")
+            .size(11)
+            .color(neon::ON_SURFACE_VARIANT),
+        text_input("e.g. before keybinding changes", &state.snapshot_label)
+            .on_input(|label| Message::Backups(BackupsMessage::SetSnapshotLabel(label)))
+            .on_submit_maybe(can_create.then(|| create.clone()))
+            .size(12)
+            .padding(8),
+        styled_button(
+            if state.creating_snapshot {
+                "Creating..."
+            } else {
+                "Create Snapshot"
+            },
+            can_create,
+            create,
+            neon::PRIMARY,
+        ),
+    ]
+    .spacing(6)
+    .into()
+}
+
+/// Button restoring every file backed up with backup `idx`, or nothing if
+/// it was backed up alone
+fn snapshot_restore(state: &BackupsState, idx: usize) -> Element<'_, Message> {
+    let members = state.snapshot_of(idx);
+    if members.len() < 2 {
+        return Space::new().height(0).into();
+    }
+    column![
+        Space::new().height(8),
+        text(format!(
+            "Backed up together with {} other file(s)",
+            members.len() - 1
+        ))
+        .size(11)
+        .color(neon::OUTLINE),
+        styled_button(
+            if state.restoring {
+                "Restoring..."
+            } else {
+                "Restore Snapshot"
+            },
+            !state.restoring,
+            Message::Backups(BackupsMessage::ConfirmRestoreSnapshot(idx)),
+            neon::PRIMARY,
+        ),
+    ]
+    .spacing(2)
+    .into()
+}
+
+// ── Preview ────────────────────────────────────────────────────────────────
+
+/// Unchanged lines shown around each change in the diff
+const DIFF_CONTEXT: usize = 3;
+
+/// Contents of the preview box for the selected backup
+fn preview_body(state: &BackupsState) -> Element<'_, Message> {
+    let note = |message: &str| text(message.to_string()).size(12).color(neon::OUTLINE);
+
+    if state.selected_backup.is_none() {
+        return note("Select a backup to preview its contents").into();
+    }
+    if !state.show_raw {
+        return match &state.preview_diff {
+            Some(Ok(lines)) => diff_view(lines),
+            Some(Err(error)) => text(format!("Error: {}", error))
+                .size(12)
+                .color(neon::ERROR)
+                .into(),
+            None => note("Comparing with the current config...").into(),
+        };
+    }
+    match &state.preview_content {
+        Some(Ok(file_text)) if file_text.is_empty() => {
+            note("(empty backup)").font(fonts::MONO_FONT).into()
+        }
+        Some(Ok(file_text)) => text(file_text)
+            .size(11)
+            .font(fonts::MONO_FONT)
+            .color(neon::ON_SURFACE)
+            .into(),
+        Some(Err(error)) => text(format!("Error: {}", error))
+            .size(12)
+            .color(neon::ERROR)
+            .into(),
+        None if state.loading_preview => note("Loading preview...").into(),
+        None => note("Select a backup and click 'Load Preview'").into(),
+    }
+}
+
+/// Switch between the diff and the raw backup
+fn preview_mode_toggle<'a>(show_raw: bool) -> Element<'a, Message> {
+    row![
+        mode_button(
+            "Changes",
+            !show_raw,
+            Message::Backups(BackupsMessage::ShowDiff(true))
+        ),
+        mode_button(
+            "Raw file",
+            show_raw,
+            Message::Backups(BackupsMessage::ShowDiff(false))
+        ),
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// One of a row of buttons picking between modes
+fn mode_button<'a>(label: &'a str, active: bool, message: Message) -> Element<'a, Message> {
+    button(text(label).size(11).font(fonts::UI_FONT_SEMIBOLD))
+        .padding([4, 12])
+        .on_press(message)
+        .style(move |_theme, status| {
+            let alpha = match (active, status) {
+                (true, _) => 0.3,
+                (false, button::Status::Hovered) => 0.15,
+                (false, _) => 0.05,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(iced::Color {
+                    a: alpha,
+                    ..neon::TERTIARY
+                })),
+                text_color: if active {
+                    neon::TERTIARY
+                } else {
+                    neon::OUTLINE
+                },
+                border: iced::Border {
+                    radius: 6.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+// ── Retention ──────────────────────────────────────────────────────────────
+
+/// Picks which backups are kept: the newest few, the recent ones, or all
+pub fn retention_control(retention: BackupRetention) -> Element<'static, Message> {
+    let set = |retention| Message::Preferences(PreferencesMessage::SetBackupRetention(retention));
+
+    let modes = row![
+        mode_button(
+            "Newest",
+            matches!(retention, BackupRetention::Count(_)),
+            set(BackupRetention::Count(DEFAULT_BACKUP_RETENTION)),
+        ),
+        mode_button(
+            "Recent",
+            matches!(retention, BackupRetention::Age(_)),
+            set(BackupRetention::days(DEFAULT_BACKUP_RETENTION_DAYS)),
+        ),
+        mode_button(
+            "Keep all",
+            retention == BackupRetention::Unlimited,
+            set(BackupRetention::Unlimited),
+        ),
+    ]
+    .spacing(4);
+
+    let detail: Element<'static, Message> = match retention {
+        BackupRetention::Count(keep) => retention_slider(slider_row_int(
+            "Backups to keep",
+            "The newest backups are kept",
+            keep as i32,
+            BACKUP_RETENTION_MIN as i32,
+            BACKUP_RETENTION_MAX as i32,
+            "",
+            move |v| set(BackupRetention::Count(v as usize)),
+        )),
+        BackupRetention::Age(_) => {
+            let days = retention
+                .age_days()
+                .unwrap_or(DEFAULT_BACKUP_RETENTION_DAYS);
+            retention_slider(slider_row_int(
+                "Days to keep backups",
+                "Backups older than this are deleted",
+                days as i32,
+                BACKUP_RETENTION_DAYS_MIN as i32,
+                BACKUP_RETENTION_DAYS_MAX as i32,
+                " days",
+                move |v| set(BackupRetention::days(v as u64)),
+            ))
+        }
+        BackupRetention::Unlimited => text("Backups are never deleted automatically.")
+            .size(11)
+            .color(neon::ON_SURFACE_VARIANT)
+            .into(),
+    };
+
+    column![
+        modes,
+        detail,
+        text(
+            "Older backups are removed the next time Nirify starts. Files saved together \
+             count as one backup, and labeled snapshots are always kept."
+        )
+        .size(11)
+        .color(neon::OUTLINE),
+    ]
+    .spacing(6)
+    .into()
+}
+
+/// Retention slider in a card, like the other settings on this page
+fn retention_slider(slider: Element<'_, Message>) -> Element<'_, Message> {
+    container(slider).style(crate::theme::card_style).into()
+}
+
+/// What a restore would change, with long unchanged stretches folded away
+fn diff_view(lines: &[DiffLine]) -> Element<'_, Message> {
+    let added = lines
+        .iter()
+        .filter(|l| matches!(l, DiffLine::Added(_)))
+        .count();
+    let removed = lines
+        .iter()
+        .filter(|l| matches!(l, DiffLine::Removed(_)))
+        .count();
+    if added == 0 && removed == 0 {
+        return text("Restoring this backup wouldn't change the current config")
+            .size(12)
+            .color(neon::OUTLINE)
+            .into();
+    }
+
+    let mut shown = vec![false; lines.len()];
+    for (i, _) in lines.iter().enumerate().filter(|(_, l)| l.is_change()) {
+        let end = (i + DIFF_CONTEXT + 1).min(lines.len());
+        shown[i.saturating_sub(DIFF_CONTEXT)..end].fill(true);
+    }
+
+    let mut list = Column::new().spacing(0).push(
+        text(format!(
+            "Restoring adds {} line(s) and removes {} from the current config",
+            added, removed
+        ))
+        .size(11)
+        .color(neon::OUTLINE),
+    );
+    list = list.push(Space::new().height(8));
+
+    let mut i = 0;
+    while i < lines.len() {
+        if shown[i] {
+            list = list.push(diff_line(&lines[i]));
+            i += 1;
+            continue;
+        }
+        let hidden = shown[i..].iter().take_while(|s| !**s).count();
+        list = list.push(
+            container(
+                text(format!("\u{22EF} {} unchanged line(s)", hidden))
+                    .size(11)
+                    .color(neon::OUTLINE_VARIANT),
+            )
+            .padding([2, 8]),
+        );
+        i += hidden;
+    }
+    list.into()
+}
+
+fn diff_line(line: &DiffLine) -> Element<'_, Message> {
+    let (marker, color, tint) = match line {
+        DiffLine::Added(_) => ("+", neon::SECONDARY, Some(neon::SECONDARY)),
+        DiffLine::Removed(_) => ("-", neon::ERROR, Some(neon::ERROR)),
+        DiffLine::Unchanged(_) => (" ", neon::ON_SURFACE_VARIANT, None),
+    };
+    container(
+        text(format!("{} {}", marker, line.text()))
+            .size(11)
+            .font(fonts::MONO_FONT)
+            .color(color),
+    )
+    .padding([0, 8])
+    .width(Length::Fill)
+    .style(move |_theme| container::Style {
+        background: tint.map(|c| iced::Background::Color(iced::Color { a: 0.1, ..c })),
+        ..Default::default()
+    })
+    .into()
+}
+
 // ── Helpers ────────────────────────────────────────────────────────────────
 
 fn modal_section<'a>(icon: &'a str, label: &'a str, accent: iced::Color) -> Element<'a, Message> {
@@ -339,6 +641,13 @@ fn backup_list_item<'a>(
                         }),
                     text(&backup.date).size(10).color(neon::OUTLINE),
                 ]
+                .extend(backup.label.as_deref().map(|label| {
+                    text(format!("\u{2605} {}", label))
+                        .size(11)
+                        .font(fonts::UI_FONT_MEDIUM)
+                        .color(neon::TERTIARY)
+                        .into()
+                }))
                 .spacing(2)
                 .width(Length::Fill),
                 text(&backup.size)
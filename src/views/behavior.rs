@@ -11,7 +11,7 @@ use crate::theme::{fonts, neon};
 use crate::types::{CenterFocusedColumn, ModKey, WarpMouseMode};
 
 /// Creates the full behavior settings view
-pub fn view(settings: &BehaviorSettings) -> Element<'_, Message> {
+pub fn view(settings: &BehaviorSettings) -> Element<'static, Message> {
     let content = column![
         // ── ROW 1: FOCUS | WORKSPACES ──
         row![
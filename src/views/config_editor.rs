@@ -285,7 +285,7 @@ pub fn view<'a>(
     .spacing(0)
     .width(Length::Fill);
 
-    scrollable(container(content).padding(8).width(Length::Fill))
+    super::screens::page_scrollable(container(content).padding(8).width(Length::Fill))
         .height(Length::Fill)
         .into()
 }
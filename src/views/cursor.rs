@@ -3,12 +3,41 @@
 use iced::widget::{column, container, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
+use super::widgets::toggle_row;
 use crate::config::models::CursorSettings;
+use crate::constants::{DEFAULT_CURSOR_HIDE_AFTER_MS, HIDE_INACTIVE_MAX, HIDE_INACTIVE_MIN};
 use crate::messages::{CursorMessage, Message};
 use crate::theme::{fonts, neon};
 
 /// Creates the cursor settings view
-pub fn view(settings: &CursorSettings) -> Element<'_, Message> {
+pub fn view(settings: &CursorSettings) -> Element<'static, Message> {
+    let mut visibility = column![
+        toggle_row(
+            "Hide when typing",
+            "Hide the cursor on key press until the pointer moves",
+            settings.hide_when_typing,
+            |v| Message::Cursor(CursorMessage::ToggleHideWhenTyping(v)),
+        ),
+        toggle_row(
+            "Hide after inactivity",
+            "Hide the cursor when the pointer hasn't moved for a while",
+            settings.hide_after_inactive_ms.is_some(),
+            |v| Message::Cursor(CursorMessage::SetHideAfterInactive(
+                v.then_some(DEFAULT_CURSOR_HIDE_AFTER_MS)
+            )),
+        ),
+    ]
+    .spacing(0);
+    if let Some(ms) = settings.hide_after_inactive_ms {
+        visibility = visibility.push(styled_slider_int(
+            "INACTIVITY TIMEOUT",
+            &format!("{} ms", ms),
+            HIDE_INACTIVE_MIN..=HIDE_INACTIVE_MAX,
+            ms,
+            |v| Message::Cursor(CursorMessage::SetHideAfterInactive(Some(v))),
+        ));
+    }
+
     let content = column![row![
         // Left: Theme
         column![
@@ -34,6 +63,12 @@ pub fn view(settings: &CursorSettings) -> Element<'_, Message> {
                 settings.size,
                 |v| Message::Cursor(CursorMessage::SetSize(v)),
             ),
+            Space::new().height(12),
+            modal_section("\u{25CC}", "VISIBILITY", neon::TERTIARY),
+            Space::new().height(4),
+            container(visibility)
+                .padding(8)
+                .style(crate::theme::card_style),
         ]
         .spacing(6)
         .width(Length::FillPortion(1)),
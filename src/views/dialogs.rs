@@ -5,20 +5,35 @@
 //! - Confirm dialog
 //! - First-run wizard
 //! - DiffView dialog
+//! - Pending changes dialog
+//! - Paste config dialog
 //! - Consolidation dialog
 //! - Import summary dialog
+//! - External change dialog
 
-use iced::widget::{button, checkbox, column, container, row, scrollable, text, Column};
+use iced::widget::{
+    button, checkbox, column, container, radio, row, scrollable, text, text_editor, Column,
+};
 use iced::{Alignment, Border, Color as IcedColor, Element, Length};
 
-use crate::messages::{ConfirmAction, ConsolidationSuggestion, DialogState, Message, WizardStep};
+use std::path::PathBuf;
+
+use crate::config::{ImportResult, IncludeHandling, MergeStrategy, SettingsCategory};
+use crate::diff::{CategoryDiff, ConfigDiff, DiffLineType};
+use crate::messages::{
+    ConfirmAction, ConsolidationSuggestion, DialogState, Message, PasteConfigMessage, SaveMessage,
+    WizardStep,
+};
 use crate::version::{get_unsupported_features, NiriVersion};
 
 /// Creates the modal overlay with dialog content
 pub fn view<'a>(
     dialog: &'a DialogState,
     wizard_suggestions: &'a [ConsolidationSuggestion],
+    wizard_includes: &'a [PathBuf],
+    include_handling: IncludeHandling,
     niri_version: Option<NiriVersion>,
+    paste_content: &'a text_editor::Content,
 ) -> Option<Element<'a, Message>> {
     match dialog {
         DialogState::None => None,
@@ -33,9 +48,13 @@ pub fn view<'a>(
             confirm_label,
             on_confirm,
         } => Some(confirm_dialog(title, message, confirm_label, on_confirm)),
-        DialogState::FirstRunWizard { step } => {
-            Some(wizard_dialog(step, wizard_suggestions, niri_version))
-        }
+        DialogState::FirstRunWizard { step } => Some(wizard_dialog(
+            step,
+            wizard_suggestions,
+            wizard_includes,
+            include_handling,
+            niri_version,
+        )),
         DialogState::ImportSummary {
             imported_count,
             defaulted_count,
@@ -51,6 +70,12 @@ pub fn view<'a>(
             before,
             after,
         } => Some(diff_view_dialog(title, before, after)),
+        DialogState::PendingChanges { diff } => Some(pending_changes_dialog(diff)),
+        DialogState::ExternalChange { category } => Some(external_change_dialog(*category)),
+        DialogState::PasteConfig { error, preview } => Some(match preview {
+            Some(result) => paste_config_preview(result),
+            None => paste_config_dialog(paste_content, error.as_deref()),
+        }),
     }
 }
 
@@ -151,15 +176,65 @@ fn confirm_dialog<'a>(
     dialog_container(content)
 }
 
+/// Asks which version to keep when a file was edited outside the app
+fn external_change_dialog<'a>(category: SettingsCategory) -> Element<'a, Message> {
+    let content = column![
+        text("File Changed Outside Nirify").size(24),
+        text(format!(
+            "The {} file was edited since Nirify last saved it, and you have unsaved \
+             changes to the same settings.\n\n\
+             Keep mine overwrites the edit. Reload theirs loads the file as it is now \
+             and drops your unsaved {} changes.",
+            category.name(),
+            category.name().to_lowercase()
+        ))
+        .size(14)
+        .color([0.9, 0.9, 0.9]),
+        row![
+            button(text("Reload theirs"))
+                .on_press(Message::Save(SaveMessage::ReloadTheirs(category)))
+                .padding([8, 24])
+                .style(|_theme, _status| button::Style {
+                    background: Some(iced::Background::Color(IcedColor::from_rgb(0.3, 0.3, 0.3))),
+                    text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            button(text("Keep mine"))
+                .on_press(Message::Save(SaveMessage::KeepMine(category)))
+                .padding([8, 24])
+                .style(|_theme, _status| button::Style {
+                    background: Some(iced::Background::Color(IcedColor::from_rgb(0.3, 0.6, 0.9))),
+                    text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center)
+    ]
+    .spacing(16);
+
+    dialog_container(content)
+}
+
 /// First-run wizard dialog
 fn wizard_dialog<'a>(
     step: &WizardStep,
     wizard_suggestions: &'a [ConsolidationSuggestion],
+    wizard_includes: &'a [PathBuf],
+    include_handling: IncludeHandling,
     niri_version: Option<NiriVersion>,
 ) -> Element<'a, Message> {
     let content: Column<'a, Message> = match step {
         WizardStep::Welcome => wizard_welcome(niri_version),
-        WizardStep::ConfigSetup => wizard_config_setup(),
+        WizardStep::ConfigSetup => wizard_config_setup(wizard_includes, include_handling),
         WizardStep::ImportResults => wizard_import_results(),
         WizardStep::Consolidation => wizard_consolidation(wizard_suggestions),
         WizardStep::Complete => wizard_complete(),
@@ -277,8 +352,11 @@ fn wizard_welcome<'a>(niri_version: Option<NiriVersion>) -> Column<'a, Message>
     content
 }
 
-fn wizard_config_setup<'a>() -> Column<'a, Message> {
-    column![
+fn wizard_config_setup<'a>(
+    includes: &'a [PathBuf],
+    include_handling: IncludeHandling,
+) -> Column<'a, Message> {
+    let mut content = column![
         text("Config Setup").size(24),
         text("Niri Settings uses a non-destructive approach to manage your config.")
             .size(14)
@@ -316,29 +394,100 @@ fn wizard_config_setup<'a>() -> Column<'a, Message> {
             },
             ..Default::default()
         }),
-        text("A backup of your config will be created before any changes.")
-            .size(12)
-            .color([0.6, 0.7, 0.6]),
-        row![
-            button(text("Back"))
-                .on_press(Message::WizardBack)
-                .padding([8, 24]),
-            button(text("Set Up Config"))
-                .on_press(Message::WizardSetupConfig)
-                .padding([8, 24])
-                .style(|_theme, _status| button::Style {
-                    background: Some(iced::Background::Color(IcedColor::from_rgb(0.3, 0.6, 0.9))),
-                    text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
-                    border: Border {
-                        radius: 4.0.into(),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                }),
-        ]
-        .spacing(12)
     ]
-    .spacing(16)
+    .spacing(16);
+
+    // A config already split into includes keeps its structure unless the
+    // user asks to fold it into Nirify
+    if !includes.is_empty() {
+        let mut list = column![text("Your config.kdl already includes:")
+            .size(13)
+            .color([0.7, 0.8, 0.9])]
+        .spacing(6);
+        for path in includes {
+            list = list.push(
+                text(format!("  - {}", path.display()))
+                    .size(11)
+                    .color([0.7, 0.7, 0.7]),
+            );
+        }
+        list = list
+            .push(
+                radio(
+                    "Keep them, with Nirify's files alongside",
+                    IncludeHandling::Keep,
+                    Some(include_handling),
+                    Message::WizardIncludeHandling,
+                )
+                .size(14)
+                .text_size(12),
+            )
+            .push(
+                radio(
+                    "Import them into Nirify and remove their include lines",
+                    IncludeHandling::Import,
+                    Some(include_handling),
+                    Message::WizardIncludeHandling,
+                )
+                .size(14)
+                .text_size(12),
+            )
+            .push(
+                text(
+                    "Settings from files in niri's config folder are imported either \
+                     way. Included files from elsewhere, or with other content, stay \
+                     included, and no file is deleted.",
+                )
+                .size(11)
+                .color([0.6, 0.6, 0.6]),
+            );
+
+        content =
+            content.push(
+                container(list)
+                    .padding([12, 16])
+                    .style(|_theme| container::Style {
+                        background: Some(iced::Background::Color(IcedColor::from_rgb(
+                            0.12, 0.14, 0.18,
+                        ))),
+                        border: Border {
+                            color: IcedColor::from_rgb(0.25, 0.3, 0.4),
+                            width: 1.0,
+                            radius: 6.0.into(),
+                        },
+                        ..Default::default()
+                    }),
+            );
+    }
+
+    content
+        .push(
+            text("A backup of your config will be created before any changes.")
+                .size(12)
+                .color([0.6, 0.7, 0.6]),
+        )
+        .push(
+            row![
+                button(text("Back"))
+                    .on_press(Message::WizardBack)
+                    .padding([8, 24]),
+                button(text("Set Up Config"))
+                    .on_press(Message::WizardSetupConfig)
+                    .padding([8, 24])
+                    .style(|_theme, _status| button::Style {
+                        background: Some(iced::Background::Color(IcedColor::from_rgb(
+                            0.3, 0.6, 0.9
+                        ))),
+                        text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
+                        border: Border {
+                            radius: 4.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+            ]
+            .spacing(12),
+        )
 }
 
 fn wizard_import_results<'a>() -> Column<'a, Message> {
@@ -449,6 +598,10 @@ fn wizard_consolidation<'a>(suggestions: &'a [ConsolidationSuggestion]) -> Colum
                         text(format!("→ {}", suggestion.merged_pattern))
                             .size(11)
                             .color([0.5, 0.7, 0.9]),
+                        suggestion_detail(
+                            suggestion,
+                            Message::WizardConsolidationToggleDetail(index)
+                        ),
                     ]
                     .spacing(2)
                     .width(Length::Fill),
@@ -599,6 +752,28 @@ fn import_summary_dialog<'a>(
     dialog_container(content)
 }
 
+/// Expandable list of the rules a suggestion merges
+fn suggestion_detail(
+    suggestion: &ConsolidationSuggestion,
+    on_toggle: Message,
+) -> Column<'_, Message> {
+    let label = if suggestion.expanded {
+        "▾ Hide merged rules"
+    } else {
+        "▸ Show merged rules"
+    };
+    let mut detail = column![button(text(label).size(11).color([0.6, 0.6, 0.6]))
+        .on_press(on_toggle)
+        .padding(0)
+        .style(button::text)];
+    if suggestion.expanded {
+        for line in &suggestion.detail {
+            detail = detail.push(text(line).size(11).color([0.7, 0.7, 0.7]));
+        }
+    }
+    detail.spacing(2)
+}
+
 /// Consolidation dialog
 fn consolidation_dialog<'a>(suggestions: &'a [ConsolidationSuggestion]) -> Element<'a, Message> {
     let suggestion_count = suggestions.len();
@@ -657,6 +832,7 @@ fn consolidation_dialog<'a>(suggestions: &'a [ConsolidationSuggestion]) -> Eleme
                         text(format!("Merged: {}", suggestion.merged_pattern))
                             .size(11)
                             .color([0.5, 0.7, 0.9]),
+                        suggestion_detail(suggestion, Message::ConsolidationToggleDetail(index)),
                     ]
                     .spacing(2)
                     .width(Length::Fill),
@@ -839,6 +1015,224 @@ fn diff_view_dialog<'a>(title: &'a str, before: &'a str, after: &'a str) -> Elem
         .into()
 }
 
+/// Pending changes dialog - what differs from the last save, per file
+fn pending_changes_dialog(diff: &ConfigDiff) -> Element<'_, Message> {
+    let summary = if diff.has_changes() {
+        format!(
+            "{} file(s) changed: +{} −{}",
+            diff.categories.len(),
+            diff.total_additions,
+            diff.total_deletions
+        )
+    } else {
+        "No changes since the last save".to_string()
+    };
+
+    let categories = Column::with_children(diff.categories.iter().map(category_diff_view))
+        .spacing(16)
+        .width(Length::Fill);
+
+    let content = column![
+        text("Changes Since Last Save").size(24),
+        text(summary).size(13).color([0.7, 0.7, 0.7]),
+        scrollable(categories).height(Length::Fixed(360.0)),
+        row![button(text("Close"))
+            .on_press(Message::CloseDialog)
+            .padding([8, 24])
+            .style(|_theme, _status| button::Style {
+                background: Some(iced::Background::Color(IcedColor::from_rgb(0.3, 0.6, 0.9))),
+                text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),]
+        .spacing(12),
+    ]
+    .spacing(16);
+
+    dialog_container(content)
+}
+
+/// One file's changed lines, removals in red and additions in green
+fn category_diff_view(category: &CategoryDiff) -> Element<'_, Message> {
+    let lines = category.lines.iter().filter_map(|line| {
+        let (prefix, line_text, color) = match line.line_type {
+            DiffLineType::Unchanged => return None,
+            DiffLineType::Removed => ("−", &line.old_text, [0.9, 0.5, 0.5]),
+            DiffLineType::Added => ("+", &line.new_text, [0.5, 0.9, 0.5]),
+        };
+        Some(
+            text(format!("{} {}", prefix, line_text))
+                .size(12)
+                .font(iced::Font::MONOSPACE)
+                .color(color)
+                .into(),
+        )
+    });
+
+    column![
+        row![
+            text(&category.name).size(14),
+            text(format!("+{} −{}", category.additions, category.deletions))
+                .size(12)
+                .color([0.6, 0.6, 0.6]),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        container(Column::with_children(lines).spacing(2))
+            .padding(12)
+            .width(Length::Fill)
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(IcedColor::from_rgb(
+                    0.12, 0.12, 0.12
+                ))),
+                border: Border {
+                    color: IcedColor::from_rgb(0.3, 0.3, 0.3),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            }),
+    ]
+    .spacing(6)
+    .into()
+}
+
+/// Paste config dialog - a box for niri config text to import
+fn paste_config_dialog<'a>(
+    content: &'a text_editor::Content,
+    error: Option<&'a str>,
+) -> Element<'a, Message> {
+    let mut col = column![
+        text("Paste Config").size(24),
+        text("Paste a niri config or snippet, e.g. from a dotfiles repo or a friend's setup")
+            .size(13)
+            .color([0.7, 0.7, 0.7]),
+        text_editor(content)
+            .on_action(|action| Message::PasteConfig(PasteConfigMessage::Edit(action)))
+            .font(iced::Font::MONOSPACE)
+            .size(12)
+            .height(Length::Fixed(320.0)),
+    ]
+    .spacing(12);
+
+    if let Some(error) = error {
+        col = col.push(text(error).size(12).color([0.9, 0.4, 0.4]));
+    }
+
+    let preview_button =
+        button(text("Preview Import"))
+            .padding([8, 24])
+            .style(|_theme, _status| button::Style {
+                background: Some(iced::Background::Color(IcedColor::from_rgb(0.3, 0.6, 0.9))),
+                text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+    col = col.push(
+        row![
+            button(text("Cancel"))
+                .on_press(Message::CloseDialog)
+                .padding([8, 24]),
+            if content.text().trim().is_empty() {
+                preview_button
+            } else {
+                preview_button.on_press(Message::PasteConfig(PasteConfigMessage::Preview))
+            },
+        ]
+        .spacing(12),
+    );
+
+    dialog_container(col)
+}
+
+/// Paste config preview - what the pasted text would import
+fn paste_config_preview(result: &ImportResult) -> Element<'_, Message> {
+    let mut content = column![
+        text("Import Preview").size(24),
+        text(result.summary()).size(14).color([0.8, 0.8, 0.8]),
+    ]
+    .spacing(12);
+
+    if !result.imported_sections.is_empty() {
+        content = content.push(
+            text(format!("Sections: {}", result.imported_sections.join(", ")))
+                .size(13)
+                .color([0.7, 0.7, 0.7]),
+        );
+    }
+
+    if !result.warnings.is_empty() {
+        content = content.push(text("Warnings:").size(14).color([0.9, 0.6, 0.3]));
+        content = content.push(
+            scrollable(
+                container(text(result.warnings.join("\n")).size(12))
+                    .padding(8)
+                    .style(|_theme| container::Style {
+                        background: Some(iced::Background::Color(IcedColor::from_rgb(
+                            0.2, 0.15, 0.1,
+                        ))),
+                        border: Border {
+                            color: IcedColor::from_rgb(0.5, 0.3, 0.2),
+                            width: 1.0,
+                            radius: 4.0.into(),
+                        },
+                        ..Default::default()
+                    }),
+            )
+            .height(Length::Fixed(120.0)),
+        );
+    }
+
+    content = content.push(
+        text(
+            "Merge keeps your other settings; Keep Mine also keeps sections you've already \
+             changed. Replace resets everything not in the import to defaults.",
+        )
+        .size(12)
+        .color([0.6, 0.6, 0.6]),
+    );
+
+    content = content.push(
+        row![
+            button(text("Back"))
+                .on_press(Message::PasteConfig(PasteConfigMessage::Back))
+                .padding([8, 24]),
+            button(text("Replace"))
+                .on_press(Message::PasteConfig(PasteConfigMessage::Replace))
+                .padding([8, 24]),
+            button(text("Keep Mine"))
+                .on_press(Message::PasteConfig(PasteConfigMessage::Merge(
+                    MergeStrategy::KeepExisting
+                )))
+                .padding([8, 24]),
+            button(text("Merge"))
+                .on_press(Message::PasteConfig(PasteConfigMessage::Merge(
+                    MergeStrategy::Overwrite
+                )))
+                .padding([8, 24])
+                .style(|_theme, _status| button::Style {
+                    background: Some(iced::Background::Color(IcedColor::from_rgb(0.3, 0.6, 0.9))),
+                    text_color: IcedColor::from_rgb(1.0, 1.0, 1.0),
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+        ]
+        .spacing(12),
+    );
+
+    dialog_container(content)
+}
+
 /// Wraps content in a dialog container with backdrop
 fn dialog_container<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
     // Create dialog box
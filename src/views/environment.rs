@@ -3,7 +3,7 @@
 use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
-use super::widgets::info_text;
+use super::widgets::{info_text, reorder_controls};
 use crate::config::models::EnvironmentSettings;
 use crate::messages::{EnvironmentMessage, Message};
 use crate::theme::{fonts, neon};
@@ -49,7 +49,8 @@ pub fn view_section(settings: &EnvironmentSettings) -> Element<'static, Message>
             .style(crate::theme::card_style),
         );
     } else {
-        for var in &variables {
+        let var_count = variables.len();
+        for (idx, var) in variables.iter().enumerate() {
             let var_id = var.id;
             let var_name = var.name.clone();
             let var_value = var.value.clone();
@@ -63,6 +64,9 @@ pub fn view_section(settings: &EnvironmentSettings) -> Element<'static, Message>
                                 .font(fonts::UI_FONT_SEMIBOLD)
                                 .color(neon::OUTLINE_VARIANT),
                             Space::new().width(Length::Fill),
+                            reorder_controls(idx, var_count, |m| Message::Environment(
+                                EnvironmentMessage::Reorder(m)
+                            )),
                             button(text("Delete").size(10).color(neon::ERROR))
                                 .on_press(Message::Environment(EnvironmentMessage::RemoveVariable(
                                     var_id
@@ -13,7 +13,10 @@ use iced::{Alignment, Element, Length};
 use std::collections::HashMap;
 
 use super::widgets::*;
-use crate::config::models::{KeybindAction, Keybinding, KeybindingsSettings};
+use crate::config::keys::{parse_combo, same_combo, suggest_free_combo};
+use crate::config::models::{
+    validate_action_args, ActionKind, KeybindAction, Keybinding, KeybindingsSettings,
+};
 use crate::messages::{KeybindingsMessage, Message};
 use crate::theme::{fonts, neon};
 use crate::types::ModKey;
@@ -72,6 +75,7 @@ pub fn view<'a>(
     selected_index: Option<usize>,
     sections_expanded: &'a HashMap<String, bool>,
     key_capture_active: Option<usize>,
+    key_capture_pending: Option<&'a str>,
 ) -> Element<'a, Message> {
     // Left panel: List of keybindings
     let list_panel = keybinding_list(settings, selected_index);
@@ -79,7 +83,14 @@ pub fn view<'a>(
     // Right panel: Detail view for selected keybinding
     let detail_panel = if let Some(idx) = selected_index {
         if let Some(binding) = settings.bindings.get(idx) {
-            keybinding_detail_view(binding, idx, sections_expanded, key_capture_active)
+            keybinding_detail_view(
+                settings,
+                binding,
+                idx,
+                sections_expanded,
+                key_capture_active,
+                key_capture_pending,
+            )
         } else {
             empty_detail_view()
         }
@@ -181,15 +192,18 @@ fn empty_detail_view() -> Element<'static, Message> {
 
 /// Detail view for a selected keybinding
 fn keybinding_detail_view<'a>(
+    settings: &'a KeybindingsSettings,
     binding: &'a Keybinding,
     idx: usize,
     sections_expanded: &HashMap<String, bool>,
     key_capture_active: Option<usize>,
+    key_capture_pending: Option<&'a str>,
 ) -> Element<'a, Message> {
     let basic_expanded = sections_expanded.get("basic").copied().unwrap_or(true);
     let advanced_expanded = sections_expanded.get("advanced").copied().unwrap_or(false);
 
     let is_capturing = key_capture_active == Some(idx);
+    let pending = key_capture_pending.filter(|_| is_capturing);
 
     let mut content = column![
         // Header with delete button
@@ -211,7 +225,9 @@ fn keybinding_detail_view<'a>(
         Message::Keybindings(KeybindingsMessage::ToggleSection("basic".to_string())),
         column![
             // Key capture area
-            key_capture_display(binding, idx, is_capturing),
+            key_capture_display(binding, idx, is_capturing, pending),
+            capture_conflict_notice(settings, idx, is_capturing, pending),
+            combo_text_field(binding, idx),
             spacer(8.0),
             info_text("Click the button above to capture a new key combination"),
             spacer(12.0),
@@ -254,6 +270,29 @@ fn keybinding_detail_view<'a>(
             ]
             .spacing(4),
             spacer(8.0),
+            // App-only grouping
+            column![
+                text("Section").size(14),
+                text("Groups this binding in Nirify's list; niri's overlay has no sections")
+                    .size(11)
+                    .color([0.75, 0.75, 0.75]),
+                text_input(
+                    "Leave empty for no section",
+                    binding.section.as_deref().unwrap_or("")
+                )
+                .on_input(move |value| {
+                    let section = if value.trim().is_empty() {
+                        None
+                    } else {
+                        Some(value)
+                    };
+                    Message::Keybindings(KeybindingsMessage::SetSection(idx, section))
+                })
+                .padding(8)
+                .width(Length::Fill),
+            ]
+            .spacing(4),
+            spacer(8.0),
             // Allow when locked toggle
             row![
                 column![
@@ -300,6 +339,8 @@ fn keybinding_detail_view<'a>(
                     text("No cooldown set").size(11).color([0.75, 0.75, 0.75]),
                 ]
             },
+            spacer(8.0),
+            repeat_guidance(binding),
         ]
         .spacing(8),
     ));
@@ -307,34 +348,146 @@ fn keybinding_detail_view<'a>(
     scrollable(content).height(Length::Fill).into()
 }
 
+/// Explains the effective repeat/cooldown behavior, with a warning for likely mistakes
+fn repeat_guidance<'a>(binding: &Keybinding) -> Element<'a, Message> {
+    let mut guidance = column![text(binding.repeat_behavior())
+        .size(11)
+        .color(neon::ON_SURFACE_VARIANT)]
+    .spacing(4);
+    if let Some(warning) = binding.repeat_warning() {
+        guidance = guidance.push(text(format!("⚠ {}", warning)).size(11).color(neon::ERROR));
+    }
+    guidance.into()
+}
+
+/// Inline error when the arguments don't fit what the action expects
+fn action_args_error<'a>(action: &str, args: &[String]) -> Element<'a, Message> {
+    match validate_action_args(action, args) {
+        Ok(()) => column![].into(),
+        Err(e) => text(format!("⚠ {}", e)).size(11).color(neon::ERROR).into(),
+    }
+}
+
+/// Warns when another binding uses the same keys, with a one-click free combo
+fn combo_conflict_notice<'a>(settings: &KeybindingsSettings, idx: usize) -> Element<'a, Message> {
+    let Some(other) = settings.conflicting_binding(idx) else {
+        return column![].into();
+    };
+    let suggestion = suggest_free_combo(&other.key_combo, settings);
+
+    let mut notice = row![
+        text(format!("⚠ Also bound to \"{}\"", other.display_name()))
+            .size(11)
+            .color(neon::ERROR)
+            .width(Length::Fill)
+    ]
+    .spacing(8)
+    .padding([6, 0])
+    .align_y(Alignment::Center);
+    if !same_combo(&suggestion, &other.key_combo) {
+        notice = notice.push(
+            button(text(format!("Use {}", suggestion)).size(11))
+                .on_press(Message::Keybindings(KeybindingsMessage::SetKeyCombo(
+                    idx, suggestion,
+                )))
+                .padding([4, 10])
+                .style(ghost_button_style),
+        );
+    }
+    notice.into()
+}
+
+/// Conflict warning for a combo held back during capture, otherwise for the
+/// binding's current combo
+fn capture_conflict_notice<'a>(
+    settings: &KeybindingsSettings,
+    idx: usize,
+    is_capturing: bool,
+    pending: Option<&str>,
+) -> Element<'a, Message> {
+    if !is_capturing {
+        return combo_conflict_notice(settings, idx);
+    }
+    let Some(other) = pending.and_then(|combo| settings.binding_on_combo(combo, idx)) else {
+        return column![].into();
+    };
+
+    row![
+        text(format!(
+            "⚠ Already bound to \"{}\" - press another combination to re-capture",
+            other.display_name()
+        ))
+        .size(11)
+        .color(neon::ERROR)
+        .width(Length::Fill),
+        button(text("Bind anyway").size(11))
+            .on_press(Message::Keybindings(KeybindingsMessage::CommitKeyCapture))
+            .padding([4, 10])
+            .style(ghost_button_style),
+    ]
+    .spacing(8)
+    .padding([6, 0])
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Field for typing a combo instead of capturing it, with what niri would
+/// reject about it shown underneath
+fn combo_text_field<'a>(binding: &'a Keybinding, idx: usize) -> Element<'a, Message> {
+    let mut field = column![
+        text_input("Or type it, e.g. Mod+Shift+Q", &binding.key_combo)
+            .on_input(move |v| Message::Keybindings(KeybindingsMessage::SetKeyCombo(idx, v)))
+            .padding(8)
+            .size(13)
+            .font(fonts::MONO_FONT)
+    ]
+    .spacing(4);
+
+    // An empty combo already has the capture button asking for one
+    if !binding.key_combo.trim().is_empty() {
+        if let Err(e) = parse_combo(&binding.key_combo) {
+            field = field.push(
+                text(format!(
+                    "⚠ {} - this binding isn't saved until it's fixed",
+                    e
+                ))
+                .size(11)
+                .color(neon::ERROR),
+            );
+        }
+    }
+    field.into()
+}
+
 /// Key capture display and button
 fn key_capture_display<'a>(
     binding: &'a Keybinding,
     idx: usize,
     is_capturing: bool,
+    pending: Option<&str>,
 ) -> Element<'a, Message> {
     if is_capturing {
+        let prompt = match pending {
+            Some(combo) => format!("{}  (press another combination, ESC to cancel)", combo),
+            None => "Press any key combination... (ESC to cancel)".to_string(),
+        };
         container(
-            button(
-                text("Press any key combination... (ESC to cancel)")
-                    .size(16)
-                    .color([0.0, 0.0, 0.0]),
-            )
-            .on_press(Message::Keybindings(KeybindingsMessage::CancelKeyCapture))
-            .padding([12, 20])
-            .width(Length::Fill)
-            .style(|_theme, _status| button::Style {
-                background: Some(iced::Background::Color(iced::Color::from_rgb(
-                    0.9, 0.7, 0.2,
-                ))),
-                text_color: iced::Color::BLACK,
-                border: iced::Border {
-                    color: iced::Color::from_rgb(1.0, 0.8, 0.3),
-                    width: 2.0,
-                    radius: 6.0.into(),
-                },
-                ..Default::default()
-            }),
+            button(text(prompt).size(16).color([0.0, 0.0, 0.0]))
+                .on_press(Message::Keybindings(KeybindingsMessage::CancelKeyCapture))
+                .padding([12, 20])
+                .width(Length::Fill)
+                .style(|_theme, _status| button::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgb(
+                        0.9, 0.7, 0.2,
+                    ))),
+                    text_color: iced::Color::BLACK,
+                    border: iced::Border {
+                        color: iced::Color::from_rgb(1.0, 0.8, 0.3),
+                        width: 2.0,
+                        radius: 6.0.into(),
+                    },
+                    ..Default::default()
+                }),
         )
         .width(Length::Fill)
         .into()
@@ -421,6 +574,18 @@ fn action_editor<'a>(binding: &'a Keybinding, idx: usize) -> Element<'a, Message
     let is_spawn = matches!(&binding.action, KeybindAction::Spawn(_));
 
     let mut content = column![
+        row![
+            text("Type:").size(14).width(Length::Fixed(80.0)),
+            pick_list(
+                ActionKind::all(),
+                Some(binding.action.kind()),
+                move |kind| { Message::Keybindings(KeybindingsMessage::SetActionKind(idx, kind)) }
+            )
+            .width(Length::Fixed(200.0))
+            .padding(8),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center),
         // Action type selector using pick_list
         row![
             text("Action:").size(14).width(Length::Fixed(80.0)),
@@ -466,6 +631,24 @@ fn action_editor<'a>(binding: &'a Keybinding, idx: usize) -> Element<'a, Message
         }
     }
 
+    if let KeybindAction::NiriActionWithArgs(name, args) = &binding.action {
+        content = content.push(spacer(8.0));
+        content = content.push(
+            row![
+                text("Arguments:").size(14).width(Length::Fixed(80.0)),
+                text_input("Enter arguments...", &args.join(" "))
+                    .on_input(move |value| {
+                        Message::Keybindings(KeybindingsMessage::SetActionArgs(idx, value))
+                    })
+                    .padding(8)
+                    .width(Length::Fill),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center),
+        );
+        content = content.push(action_args_error(name, args));
+    }
+
     content.into()
 }
 
@@ -587,12 +770,15 @@ fn modifier_toggle_button<'a>(
 
 /// Creates a modal overlay for editing a keybinding
 pub fn editor_modal<'a>(
+    settings: &'a KeybindingsSettings,
     binding: &'a Keybinding,
     idx: usize,
     sections_expanded: &'a HashMap<String, bool>,
     key_capture_active: Option<usize>,
+    key_capture_pending: Option<&'a str>,
 ) -> Element<'a, Message> {
     let is_capturing = key_capture_active == Some(idx);
+    let pending = key_capture_pending.filter(|_| is_capturing);
 
     let actual_action: &str = match &binding.action {
         KeybindAction::Spawn(_) => "spawn",
@@ -689,7 +875,10 @@ pub fn editor_modal<'a>(
                             .font(fonts::UI_FONT_SEMIBOLD)
                             .color(neon::OUTLINE_VARIANT),
                         Space::new().height(6),
-                        key_capture_display(binding, idx, is_capturing),
+                        key_capture_display(binding, idx, is_capturing, pending),
+                        capture_conflict_notice(settings, idx, is_capturing, pending),
+                        Space::new().height(6),
+                        combo_text_field(binding, idx),
                         Space::new().height(14),
                         text("MODIFIERS")
                             .size(10)
@@ -716,6 +905,16 @@ pub fn editor_modal<'a>(
                             .font(fonts::UI_FONT_SEMIBOLD)
                             .color(neon::OUTLINE_VARIANT),
                         Space::new().height(6),
+                        pick_list(
+                            ActionKind::all(),
+                            Some(binding.action.kind()),
+                            move |kind| {
+                                Message::Keybindings(KeybindingsMessage::SetActionKind(idx, kind))
+                            }
+                        )
+                        .width(Length::Fill)
+                        .padding(10),
+                        Space::new().height(8),
                         pick_list(
                             action_options,
                             Some(actual_action),
@@ -752,6 +951,28 @@ pub fn editor_modal<'a>(
                                     ]
                                     .spacing(0),
                                 )
+                            } else if let KeybindAction::NiriActionWithArgs(name, args) =
+                                &binding.action
+                            {
+                                Element::from(
+                                    column![
+                                        Space::new().height(12),
+                                        text("ARGUMENTS")
+                                            .size(10)
+                                            .font(fonts::UI_FONT_SEMIBOLD)
+                                            .color(neon::OUTLINE_VARIANT),
+                                        Space::new().height(6),
+                                        text_input("e.g., 3", &args.join(" "))
+                                            .on_input(move |v| Message::Keybindings(
+                                                KeybindingsMessage::SetActionArgs(idx, v)
+                                            ))
+                                            .padding(10)
+                                            .size(13),
+                                        Space::new().height(4),
+                                        action_args_error(name, args),
+                                    ]
+                                    .spacing(0),
+                                )
                             } else {
                                 Space::new().into()
                             }
@@ -788,6 +1009,14 @@ pub fn editor_modal<'a>(
                         binding.repeat,
                         move |v| Message::Keybindings(KeybindingsMessage::SetRepeat(idx, v))
                     ),
+                    toggle_row(
+                        "Hide from overlay",
+                        "Leave out of niri's hotkey overlay",
+                        binding.hotkey_overlay_hidden,
+                        move |v| Message::Keybindings(KeybindingsMessage::SetHotkeyOverlayHidden(
+                            idx, v
+                        ))
+                    ),
                 ]
                 .spacing(0)
             )
@@ -812,6 +1041,26 @@ pub fn editor_modal<'a>(
                     })
                     .padding(10)
                     .size(13),
+                    Space::new().height(4),
+                    text(format!("Overlay preview: {}", binding.overlay_preview()))
+                        .size(11)
+                        .color(neon::ON_SURFACE_VARIANT),
+                    Space::new().height(8),
+                    text("SECTION")
+                        .size(10)
+                        .font(fonts::UI_FONT_SEMIBOLD)
+                        .color(neon::OUTLINE_VARIANT),
+                    Space::new().height(4),
+                    text_input(
+                        "Group in this list (not shown by niri)",
+                        binding.section.as_deref().unwrap_or("")
+                    )
+                    .on_input(move |v| {
+                        let section = if v.trim().is_empty() { None } else { Some(v) };
+                        Message::Keybindings(KeybindingsMessage::SetSection(idx, section))
+                    })
+                    .padding(10)
+                    .size(13),
                     Space::new().height(8),
                     text("COOLDOWN")
                         .size(10)
@@ -835,6 +1084,8 @@ pub fn editor_modal<'a>(
                     })
                     .padding(10)
                     .size(13),
+                    Space::new().height(8),
+                    repeat_guidance(binding),
                 ]
                 .spacing(0)
                 .padding(12)
@@ -1,20 +1,27 @@
 //! Keyboard settings view — neon modal style
 
-use iced::widget::{column, container, row, scrollable, text, text_input, Space};
+use iced::widget::{checkbox, column, container, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
 use super::widgets::{info_text, toggle_row};
-use crate::config::models::KeyboardSettings;
+use crate::config::models::{KeyboardSettings, COMMON_XKB_OPTIONS};
+use crate::ipc::KeyboardLayouts;
 use crate::messages::{KeyboardMessage, Message};
 use crate::theme::{fonts, neon};
 
 /// Creates the keyboard settings view (styled for modal display)
-pub fn view(settings: &KeyboardSettings) -> Element<'_, Message> {
+///
+/// `test_input` is the contents of the layout test field, kept in UI state.
+/// `live_layouts` is what niri reports while connected, `None` otherwise.
+pub fn view<'a>(
+    settings: &'a KeyboardSettings,
+    test_input: &'a str,
+    live_layouts: Option<&'a KeyboardLayouts>,
+) -> Element<'a, Message> {
     let xkb_layout = settings.xkb_layout.clone();
     let xkb_variant = settings.xkb_variant.clone();
     let xkb_model = settings.xkb_model.clone();
     let xkb_rules = settings.xkb_rules.clone();
-    let xkb_options = settings.xkb_options.clone();
     let xkb_file = settings.xkb_file.clone();
     let track_layout = settings.track_layout.clone();
 
@@ -26,6 +33,7 @@ pub fn view(settings: &KeyboardSettings) -> Element<'_, Message> {
                 modal_section("⌨", "KEYBOARD LAYOUT", neon::SECONDARY),
                 info_text("Configure layout using XKB settings."),
                 Space::new().height(4),
+                active_layouts(settings, live_layouts),
                 styled_text_input("XKB LAYOUT", "e.g., us, de, fr", &xkb_layout, |v| {
                     Message::Keyboard(KeyboardMessage::SetXkbLayout(v))
                 }),
@@ -38,12 +46,7 @@ pub fn view(settings: &KeyboardSettings) -> Element<'_, Message> {
                 styled_text_input("XKB RULES", "e.g., evdev", &xkb_rules, |v| {
                     Message::Keyboard(KeyboardMessage::SetXkbRules(v))
                 }),
-                styled_text_input(
-                    "XKB OPTIONS",
-                    "e.g., compose:ralt, caps:escape",
-                    &xkb_options,
-                    |v| Message::Keyboard(KeyboardMessage::SetXkbOptions(v))
-                ),
+                xkb_options_checklist(settings),
                 styled_text_input(
                     "XKB FILE",
                     "Path to .xkb keymap (overrides above)",
@@ -85,6 +88,10 @@ pub fn view(settings: &KeyboardSettings) -> Element<'_, Message> {
                 ),)
                 .padding(8)
                 .style(crate::theme::card_style),
+                Space::new().height(12),
+                modal_section("✎", "TEST INPUT", neon::SECONDARY),
+                Space::new().height(4),
+                layout_test_field(test_input),
             ]
             .spacing(6)
             .width(Length::FillPortion(1)),
@@ -124,6 +131,146 @@ fn modal_section<'a>(icon: &'a str, label: &'a str, accent: iced::Color) -> Elem
     .into()
 }
 
+/// Layouts niri has loaded with the active one highlighted, or the
+/// configured layouts when niri isn't connected
+fn active_layouts<'a>(
+    settings: &'a KeyboardSettings,
+    live: Option<&'a KeyboardLayouts>,
+) -> Element<'a, Message> {
+    let mut list = column![text(if live.is_some() {
+        "ACTIVE IN NIRI"
+    } else {
+        "CONFIGURED"
+    })
+    .size(10)
+    .font(fonts::UI_FONT_SEMIBOLD)
+    .color(neon::OUTLINE_VARIANT),]
+    .spacing(4);
+
+    match live {
+        Some(layouts) => {
+            for (i, name) in layouts.names.iter().enumerate() {
+                let active = i == layouts.current_idx as usize;
+                let (marker, color) = if active {
+                    ("●", neon::SECONDARY)
+                } else {
+                    ("○", neon::OUTLINE_VARIANT)
+                };
+                list = list.push(
+                    row![
+                        text(marker).size(10).color(color),
+                        text(name).size(12).color(color),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                );
+            }
+        }
+        None => {
+            let configured = if settings.xkb_layout.trim().is_empty() {
+                "System default".to_string()
+            } else {
+                settings.xkb_layout.replace(',', ", ")
+            };
+            list = list.push(text(configured).size(12).font(fonts::MONO_FONT));
+            list = list.push(
+                text("Connect to niri to see which layout is active")
+                    .size(11)
+                    .color(neon::OUTLINE_VARIANT),
+            );
+        }
+    }
+
+    container(list)
+        .padding(12)
+        .width(Length::Fill)
+        .style(crate::theme::card_style)
+        .into()
+}
+
+/// Checklist of common xkb options plus free text for anything else
+fn xkb_options_checklist(settings: &KeyboardSettings) -> Element<'_, Message> {
+    let checked = settings.checked_xkb_options();
+
+    let mut options = column![text("XKB OPTIONS")
+        .size(10)
+        .font(fonts::UI_FONT_SEMIBOLD)
+        .color(neon::OUTLINE_VARIANT),]
+    .spacing(6);
+
+    for (option, label) in COMMON_XKB_OPTIONS {
+        let toggle = checkbox(checked.contains(option))
+            .label(*label)
+            .text_size(12)
+            .on_toggle(move |enabled| {
+                Message::Keyboard(KeyboardMessage::ToggleXkbOption(
+                    option.to_string(),
+                    enabled,
+                ))
+            });
+        options = options.push(
+            row![
+                toggle,
+                Space::new().width(Length::Fill),
+                text(*option)
+                    .size(10)
+                    .font(fonts::MONO_FONT)
+                    .color(neon::OUTLINE_VARIANT),
+            ]
+            .align_y(Alignment::Center),
+        );
+    }
+
+    options = options.push(Space::new().height(4));
+    options = options.push(
+        text("OTHER OPTIONS")
+            .size(10)
+            .font(fonts::UI_FONT_SEMIBOLD)
+            .color(neon::OUTLINE_VARIANT),
+    );
+    options = options.push(
+        text_input(
+            "e.g., lv3:ralt_switch, terminate:ctrl_alt_bksp",
+            &settings.extra_xkb_options(),
+        )
+        .on_input(|v| Message::Keyboard(KeyboardMessage::SetXkbExtraOptions(v)))
+        .padding(10)
+        .size(13),
+    );
+
+    container(options)
+        .padding(12)
+        .style(crate::theme::card_style)
+        .into()
+}
+
+/// Passive echo field for checking what the active keymap produces
+///
+/// Input goes through the system's current keymap, so pending layout changes
+/// only show up here after they're saved and niri reloads.
+fn layout_test_field(test_input: &str) -> Element<'_, Message> {
+    container(
+        column![
+            text("TYPE TO TEST")
+                .size(10)
+                .font(fonts::UI_FONT_SEMIBOLD)
+                .color(neon::OUTLINE_VARIANT),
+            text_input("Type here to see what your keyboard produces", test_input)
+                .on_input(|v| Message::Keyboard(KeyboardMessage::SetTestInput(v)))
+                .padding(10)
+                .size(13)
+                .font(fonts::MONO_FONT),
+            text("Reflects the live system layout, not unsaved changes above")
+                .size(11)
+                .color(neon::ON_SURFACE_VARIANT),
+        ]
+        .spacing(4),
+    )
+    .padding(12)
+    .style(crate::theme::card_style)
+    .into()
+}
+
 fn styled_text_input<'a>(
     label: &'a str,
     placeholder: &'a str,
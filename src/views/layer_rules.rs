@@ -7,12 +7,18 @@ use iced::widget::{button, column, container, row, scrollable, text, text_input,
 use iced::{Alignment, Element, Length};
 use std::collections::HashMap;
 
+use super::layout_extras::color_input;
 use super::widgets::*;
-use crate::config::models::{BlockOutFrom, LayerRule, LayerRulesSettings};
+use crate::config::models::{BlockOutFrom, LayerRule, LayerRulesSettings, ShadowSettings};
 use crate::messages::{LayerRulesMessage, Message, RulesFilter};
 use crate::theme::{fonts, neon};
 
 const RULE_CARD_HEIGHT: f32 = 320.0;
+const RULE_GRID_COLUMNS: usize = 3;
+const RULE_GRID_SPACING: f32 = 12.0;
+/// Roughly where the grid starts in the scrolled content (search bar plus
+/// spacer); the virtual list's overscan absorbs the difference
+const RULE_GRID_TOP: f32 = 68.0;
 const RULE_CARD_SECTION_HEIGHT: f32 = 68.0;
 const MAX_VISIBLE_SUMMARY_PILLS: usize = 2;
 
@@ -23,8 +29,8 @@ pub fn view<'a>(
     settings: &'a LayerRulesSettings,
     search: &'a str,
     filter: RulesFilter,
-    _sections_expanded: &'a HashMap<(u32, String), bool>,
-    _regex_errors: &'a HashMap<(u32, String), String>,
+    viewport: ListViewport,
+    editing: Option<u32>,
 ) -> Element<'a, Message> {
     let search_owned = search.to_string();
 
@@ -94,28 +100,30 @@ pub fn view<'a>(
     if filtered_rules.is_empty() {
         content = content.push(empty_state(search, filter));
     } else {
-        let mut col1: Vec<Element<'a, Message>> = Vec::new();
-        let mut col2: Vec<Element<'a, Message>> = Vec::new();
-        let mut col3: Vec<Element<'a, Message>> = Vec::new();
-
-        for (i, rule) in filtered_rules.iter().enumerate() {
-            let card = rule_card(rule);
-            match i % 3 {
-                0 => col1.push(card),
-                1 => col2.push(card),
-                _ => col3.push(card),
-            }
-        }
-
-        content = content.push(
-            row![
-                column(col1).spacing(12).width(Length::FillPortion(1)),
-                column(col2).spacing(12).width(Length::FillPortion(1)),
-                column(col3).spacing(12).width(Length::FillPortion(1)),
-            ]
-            .spacing(12)
-            .align_y(Alignment::Start),
+        // Three cards per grid row; only the rows near the viewport are built,
+        // plus the one holding the rule open in the editor
+        let pinned = editing
+            .and_then(|id| filtered_rules.iter().position(|rule| rule.id == id))
+            .map(|i| i / RULE_GRID_COLUMNS);
+        let grid = virtual_column(
+            filtered_rules.len().div_ceil(RULE_GRID_COLUMNS),
+            RULE_CARD_HEIGHT + RULE_GRID_SPACING,
+            viewport.below(RULE_GRID_TOP),
+            pinned,
+            |grid_row| {
+                let first = grid_row * RULE_GRID_COLUMNS;
+                let mut cards = row![].spacing(RULE_GRID_SPACING);
+                for i in first..first + RULE_GRID_COLUMNS {
+                    cards = cards.push(match filtered_rules.get(i) {
+                        Some(rule) => container(rule_card(rule)).width(Length::FillPortion(1)),
+                        None => container(Space::new()).width(Length::FillPortion(1)),
+                    });
+                }
+                cards.into()
+            },
         );
+
+        content = content.push(grid);
     }
 
     content = content.push(Space::new().height(16));
@@ -192,6 +200,9 @@ fn rule_card(rule: &LayerRule) -> Element<'_, Message> {
     if let Some(opacity) = rule.opacity {
         effect_pills.push((format!("◉ Opacity {:.2}", opacity), neon::PRIMARY));
     }
+    if rule.shadow.is_some() {
+        effect_pills.push(("■ Shadow".to_string(), neon::PRIMARY));
+    }
     if rule.place_within_backdrop {
         effect_pills.push(("▤ Backdrop".to_string(), neon::TERTIARY));
     }
@@ -293,6 +304,7 @@ pub fn editor_modal<'a>(
     rule: &'a LayerRule,
     _sections_expanded: &'a HashMap<(u32, String), bool>,
     regex_errors: &'a HashMap<(u32, String), String>,
+    color_drafts: &'a HashMap<(u32, String), String>,
 ) -> Element<'a, Message> {
     let id = rule.id;
 
@@ -518,10 +530,8 @@ pub fn editor_modal<'a>(
         .style(crate::theme::card_style),
     );
 
-    editor = editor.push(Space::new().height(8));
-    editor = editor.push(info_text(
-        "Per-layer shadow overrides can be configured via KDL.",
-    ));
+    editor = editor.push(Space::new().height(20));
+    editor = editor.push(shadow_section(rule, color_drafts));
 
     // ── Footer ──
     editor = editor.push(Space::new().height(20));
@@ -623,6 +633,132 @@ pub fn editor_modal<'a>(
 
 // ── Helpers ────────────────────────────────────────────────────────────────
 
+/// Integer shadow field, for building its slider
+type ShadowPx = fn(&mut ShadowSettings) -> &mut i32;
+
+/// Shadow override: off leaves the layer without one, as niri does by default
+fn shadow_section<'a>(
+    rule: &'a LayerRule,
+    color_drafts: &HashMap<(u32, String), String>,
+) -> Element<'a, Message> {
+    let id = rule.id;
+    let mut section = column![
+        modal_section_header("■", "SHADOW", neon::PRIMARY),
+        container(toggle_row(
+            "Draw shadow",
+            "Give this layer a shadow like the layout's window shadow",
+            rule.shadow.is_some(),
+            move |on| Message::LayerRules(LayerRulesMessage::SetShadow(
+                id,
+                on.then(ShadowSettings::default)
+            )),
+        ))
+        .padding(8)
+        .style(crate::theme::card_style),
+    ]
+    .spacing(8);
+
+    let Some(shadow) = &rule.shadow else {
+        return section.into();
+    };
+
+    let s = shadow.clone();
+    section = section.push(
+        container(toggle_row(
+            "Draw behind layer",
+            "Draw shadow underneath (for transparency)",
+            shadow.draw_behind_window,
+            move |v| set_shadow_field(id, &s, |sh| sh.draw_behind_window = v),
+        ))
+        .padding(8)
+        .style(crate::theme::card_style),
+    );
+
+    let sliders: [(&str, i32, std::ops::RangeInclusive<i32>, ShadowPx); 4] = [
+        ("SOFTNESS", shadow.softness, 0..=100, |sh| &mut sh.softness),
+        ("SPREAD", shadow.spread, 0..=100, |sh| &mut sh.spread),
+        ("OFFSET X", shadow.offset_x, -100..=100, |sh| {
+            &mut sh.offset_x
+        }),
+        ("OFFSET Y", shadow.offset_y, -100..=100, |sh| {
+            &mut sh.offset_y
+        }),
+    ];
+    let mut slider_row = row![].spacing(8);
+    for (label, value, range, field) in sliders {
+        let (min, max) = (*range.start(), *range.end());
+        let s_text = shadow.clone();
+        let s_slide = shadow.clone();
+        slider_row = slider_row.push(
+            container(styled_slider_int(
+                label,
+                &format!("{}px", value),
+                move |text| {
+                    text.replace("px", "")
+                        .parse::<i32>()
+                        .ok()
+                        .map(|v| set_shadow_field(id, &s_text, |sh| *field(sh) = v.clamp(min, max)))
+                },
+                range,
+                value,
+                move |v| set_shadow_field(id, &s_slide, |sh| *field(sh) = v),
+            ))
+            .width(Length::FillPortion(1)),
+        );
+    }
+    section = section.push(slider_row);
+
+    let colors = [
+        ("ACTIVE COLOR", "color", &shadow.color),
+        ("INACTIVE COLOR", "inactive-color", &shadow.inactive_color),
+    ];
+    let mut color_row = row![].spacing(8);
+    for (label, field, color) in colors {
+        color_row = color_row.push(
+            container(color_input(
+                label,
+                &color_text(color_drafts, id, field, color),
+                move |hex| {
+                    Message::LayerRules(LayerRulesMessage::SetShadowColor(
+                        id,
+                        field.to_string(),
+                        hex,
+                    ))
+                },
+            ))
+            .width(Length::FillPortion(1)),
+        );
+    }
+    section.push(color_row).into()
+}
+
+/// Message setting `shadow` with one field changed
+fn set_shadow_field(
+    id: u32,
+    shadow: &ShadowSettings,
+    mutate: impl FnOnce(&mut ShadowSettings),
+) -> Message {
+    let mut shadow = shadow.clone();
+    mutate(&mut shadow);
+    Message::LayerRules(LayerRulesMessage::SetShadow(id, Some(shadow)))
+}
+
+/// Text for a shadow color input: the draft being typed, unless the color
+/// has since been changed some other way (undo, reload)
+fn color_text(
+    drafts: &HashMap<(u32, String), String>,
+    id: u32,
+    field: &str,
+    color: &crate::types::Color,
+) -> String {
+    match drafts.get(&(id, field.to_string())) {
+        Some(draft) if crate::types::Color::parse(draft).is_none_or(|c| c == *color) => {
+            draft.clone()
+        }
+        _ => color.to_hex(),
+    }
+}
+
 fn pill<'a>(label: &str, color: iced::Color) -> Element<'a, Message> {
     container(
         text(label.to_string())
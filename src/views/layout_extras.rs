@@ -346,14 +346,14 @@ fn styled_slider_int<'a>(
     .into()
 }
 
-fn color_input<'a>(
+pub(super) fn color_input<'a>(
     label: &'a str,
     hex: &str,
     msg_fn: impl Fn(String) -> Message + 'a,
 ) -> Element<'a, Message> {
     let hex_owned = hex.to_string();
 
-    let parsed_color = crate::types::Color::from_hex(&hex_owned)
+    let parsed_color = crate::types::Color::parse(&hex_owned)
         .map(|c| {
             iced::Color::from_rgba(
                 c.r as f32 / 255.0,
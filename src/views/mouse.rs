@@ -9,7 +9,7 @@ use crate::messages::{Message, MouseMessage};
 use crate::theme::{fonts, neon};
 use crate::types::{AccelProfile, ScrollMethod};
 
-pub fn view(settings: &MouseSettings) -> Element<'_, Message> {
+pub fn view(settings: &MouseSettings) -> Element<'static, Message> {
     let content = column![
         // ── 2-COLUMN: SCROLLING | ACCELERATION ──
         row![
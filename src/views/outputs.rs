@@ -8,31 +8,160 @@ use iced::{Alignment, Border, Element, Length};
 use std::collections::HashMap;
 
 use super::widgets::*;
-use crate::config::models::{DefaultColumnDisplay, LayoutOverride, OutputConfig, OutputSettings};
-use crate::ipc::FullOutputInfo;
+use crate::config::models::{
+    is_fractional_scale, round_scale, DefaultColumnDisplay, LayoutOverride, OutputConfig,
+    OutputSettings, ScaleRounding,
+};
+use crate::ipc::output::parse_mode;
+use crate::ipc::{best_mode, FullOutputInfo};
 use crate::messages::{Message, OutputsMessage};
-use crate::theme::{fonts, muted_text_container};
+use crate::theme::{fonts, muted_text_container, neon};
 use crate::types::{CenterFocusedColumn, Color, ColorOrGradient, Transform, VrrMode};
 
-/// Represents an available display mode for dropdown selection
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A display mode niri reports for a connected output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ModeOption {
-    /// The mode string (e.g., "1920x1080@60.00")
-    pub mode_string: String,
+    pub width: i32,
+    pub height: i32,
+    /// Refresh rate in millihertz (e.g., 60000 = 60Hz)
+    pub refresh_mhz: i32,
     /// Whether this is the preferred/native mode
     pub is_preferred: bool,
+    /// Whether niri is driving the output at this mode right now
+    pub is_current: bool,
+}
+
+/// Every refresh rate an output offers at one resolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResolutionGroup {
+    width: i32,
+    height: i32,
+    /// Highest first
+    refresh_rates: Vec<RefreshOption>,
 }
 
-impl std::fmt::Display for ModeOption {
+impl ResolutionGroup {
+    /// Refresh rate picked when switching to this resolution: the preferred
+    /// one if there is one, otherwise the highest
+    fn default_refresh(&self) -> Option<&RefreshOption> {
+        self.refresh_rates
+            .iter()
+            .find(|rate| rate.is_preferred)
+            .or_else(|| self.refresh_rates.first())
+    }
+
+    fn mode_string(&self, refresh: &RefreshOption) -> String {
+        format!(
+            "{}x{}@{:.2}",
+            self.width,
+            self.height,
+            refresh.refresh_mhz as f64 / 1000.0
+        )
+    }
+}
+
+/// Choice in the resolution dropdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ResolutionOption {
+    /// No mode in the config, so niri picks the preferred one
+    Auto,
+    Resolution(ResolutionGroup),
+}
+
+impl std::fmt::Display for ResolutionOption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_preferred {
-            write!(f, "{} (preferred)", self.mode_string)
-        } else {
-            write!(f, "{}", self.mode_string)
+        match self {
+            Self::Auto => write!(f, "Default (auto)"),
+            Self::Resolution(group) => {
+                write!(f, "{}x{}", group.width, group.height)?;
+                if group.refresh_rates.iter().any(|rate| rate.is_preferred) {
+                    write!(f, " (preferred)")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Choice in the refresh rate dropdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RefreshOption {
+    refresh_mhz: i32,
+    is_preferred: bool,
+    is_current: bool,
+}
+
+impl std::fmt::Display for RefreshOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2} Hz", self.refresh_mhz as f64 / 1000.0)?;
+        match (self.is_preferred, self.is_current) {
+            (true, true) => write!(f, " (preferred, current)"),
+            (true, false) => write!(f, " (preferred)"),
+            (false, true) => write!(f, " (current)"),
+            (false, false) => Ok(()),
         }
     }
 }
 
+/// Groups modes by resolution, largest first, with refresh rates highest first
+fn group_modes(modes: &[ModeOption]) -> Vec<ResolutionGroup> {
+    let mut groups: Vec<ResolutionGroup> = Vec::new();
+    for mode in modes {
+        let rate = RefreshOption {
+            refresh_mhz: mode.refresh_mhz,
+            is_preferred: mode.is_preferred,
+            is_current: mode.is_current,
+        };
+        match groups
+            .iter_mut()
+            .find(|g| g.width == mode.width && g.height == mode.height)
+        {
+            Some(group) => match group
+                .refresh_rates
+                .iter_mut()
+                .find(|r| r.refresh_mhz == mode.refresh_mhz)
+            {
+                // niri can list the same rate twice (e.g. with different flags)
+                Some(existing) => {
+                    existing.is_preferred |= rate.is_preferred;
+                    existing.is_current |= rate.is_current;
+                }
+                None => group.refresh_rates.push(rate),
+            },
+            None => groups.push(ResolutionGroup {
+                width: mode.width,
+                height: mode.height,
+                refresh_rates: vec![rate],
+            }),
+        }
+    }
+
+    for group in &mut groups {
+        group
+            .refresh_rates
+            .sort_by_key(|rate| std::cmp::Reverse(rate.refresh_mhz));
+    }
+    groups.sort_by(|a, b| {
+        let area = |g: &ResolutionGroup| i64::from(g.width) * i64::from(g.height);
+        area(b).cmp(&area(a)).then(b.width.cmp(&a.width))
+    });
+    groups
+}
+
+/// Choice in the "focus at startup" dropdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StartupFocusOption {
+    /// Output index, or `None` to focus niri's default
+    index: Option<usize>,
+    label: String,
+}
+
+impl std::fmt::Display for StartupFocusOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
 /// Creates the outputs settings view with list-detail pattern
 /// Returns Element<'_> because text_input widgets borrow from settings
 pub fn view<'a>(
@@ -56,7 +185,7 @@ pub fn view<'a>(
     };
 
     // Horizontal split layout (responsive 1:2 ratio)
-    row![
+    let split = row![
         container(list_panel)
             .width(Length::FillPortion(1))
             .height(Length::Fill)
@@ -72,10 +201,67 @@ pub fn view<'a>(
             .height(Length::Fill)
             .padding(20),
     ]
-    .spacing(0)
+    .spacing(0);
+
+    column![
+        container(layout_issues_banner(settings, available_outputs)).padding([0, 20]),
+        split,
+    ]
     .into()
 }
 
+/// Warning listing overlapping or detached outputs, or nothing if the
+/// arrangement is fine
+///
+/// Connected outputs are sized by their live mode, others by their
+/// configured one.
+pub fn layout_issues_banner<'a>(
+    settings: &OutputSettings,
+    available_outputs: &[FullOutputInfo],
+) -> Element<'a, Message> {
+    let issues = settings.layout_issues_with(|output| {
+        let live_mode = available_outputs
+            .iter()
+            .find(|info| info.name == output.name)
+            .and_then(|info| info.modes.get(info.current_mode?))
+            .map(|mode| (mode.width.max(0) as u32, mode.height.max(0) as u32));
+        let (w, h) = live_mode.or_else(|| output.mode_size())?;
+        Some(output.logical_size(w, h))
+    });
+    if issues.is_empty() {
+        return column![].into();
+    }
+
+    let mut list = column![text("Output positions need attention")
+        .size(13)
+        .font(fonts::UI_FONT_SEMIBOLD)
+        .color(neon::ERROR)]
+    .spacing(4);
+    for issue in issues {
+        list = list.push(text(issue.to_string()).size(12));
+    }
+
+    container(list)
+        .padding([8, 12])
+        .width(Length::Fill)
+        .style(|_: &iced::Theme| container::Style {
+            background: Some(iced::Background::Color(IcedColor {
+                a: 0.08,
+                ..neon::ERROR
+            })),
+            border: Border {
+                radius: 8.0.into(),
+                color: IcedColor {
+                    a: 0.2,
+                    ..neon::ERROR
+                },
+                width: 1.0,
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
 /// List panel showing all outputs
 fn output_list<'a>(
     settings: &'a OutputSettings,
@@ -93,6 +279,10 @@ fn output_list<'a>(
     .align_y(Alignment::Center),]
     .spacing(0);
 
+    if !settings.outputs.is_empty() {
+        list = list.push(startup_focus_picker(settings));
+    }
+
     if settings.outputs.is_empty() {
         list = list.push(
             container(
@@ -192,6 +382,45 @@ fn output_list<'a>(
     scrollable(list).height(Length::Fill).into()
 }
 
+/// One dropdown for the output focused at startup, so only one can be picked
+fn startup_focus_picker(settings: &OutputSettings) -> Element<'static, Message> {
+    let options: Vec<StartupFocusOption> = std::iter::once(StartupFocusOption {
+        index: None,
+        label: "None".to_string(),
+    })
+    .chain(
+        settings
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(idx, output)| StartupFocusOption {
+                index: Some(idx),
+                label: if output.name.is_empty() {
+                    format!("Output {}", idx + 1)
+                } else {
+                    output.name.clone()
+                },
+            }),
+    )
+    .collect();
+    let selected = options
+        .iter()
+        .find(|option| option.index == settings.startup_focus())
+        .cloned();
+
+    column![
+        text("Focus at startup").size(13),
+        pick_list(options, selected, |option: StartupFocusOption| {
+            Message::Outputs(OutputsMessage::SetStartupFocus(option.index))
+        })
+        .width(Length::Fill)
+        .padding(6),
+    ]
+    .spacing(4)
+    .padding([4, 20])
+    .into()
+}
+
 /// Empty detail view shown when no output is selected
 fn empty_detail_view() -> Element<'static, Message> {
     container(container(text("Select an output to configure").size(16)).style(muted_text_container))
@@ -208,12 +437,13 @@ fn get_available_modes(output_name: &str, available_outputs: &[FullOutputInfo])
         ipc_out
             .modes
             .iter()
-            .map(|mode| {
-                let refresh_hz = mode.refresh_rate as f64 / 1000.0;
-                ModeOption {
-                    mode_string: format!("{}x{}@{:.2}", mode.width, mode.height, refresh_hz),
-                    is_preferred: mode.is_preferred,
-                }
+            .enumerate()
+            .map(|(mode_idx, mode)| ModeOption {
+                width: mode.width,
+                height: mode.height,
+                refresh_mhz: mode.refresh_rate,
+                is_preferred: mode.is_preferred,
+                is_current: ipc_out.current_mode == Some(mode_idx),
             })
             .collect()
     } else {
@@ -222,60 +452,172 @@ fn get_available_modes(output_name: &str, available_outputs: &[FullOutputInfo])
 }
 
 /// Create the mode selection row - dropdown if modes available, text input as fallback
+///
+/// `disconnected` means niri reported outputs but not this one (e.g. it was
+/// unplugged); the configured mode is kept and stays editable as text.
 fn mode_row<'a>(
     idx: usize,
     current_mode: &'a str,
     available_modes: &[ModeOption],
+    disconnected: bool,
 ) -> Element<'a, Message> {
     if available_modes.is_empty() {
         // No IPC data - fall back to text input
-        text_input_row(
-            "Mode",
-            "Resolution and refresh rate (e.g., 1920x1080@60)",
-            current_mode,
-            move |value| Message::Outputs(OutputsMessage::SetMode(idx, value)),
-        )
+        let description = if disconnected {
+            "Not connected - configured mode is kept until it's plugged back in"
+        } else {
+            "Resolution and refresh rate (e.g., 1920x1080@60)"
+        };
+        text_input_row("Mode", description, current_mode, move |value| {
+            Message::Outputs(OutputsMessage::SetMode(idx, value))
+        })
     } else {
-        // Have available modes - show dropdown
-        let mode_strings: Vec<String> = available_modes.iter().map(|m| m.to_string()).collect();
+        // Have available modes - resolution dropdown, then its refresh rates
+        let groups = group_modes(available_modes);
+        let configured = parse_mode(current_mode);
 
-        // Find the currently selected mode (match by mode_string prefix, ignoring " (preferred)" suffix)
-        let selected: Option<String> = mode_strings
-            .iter()
-            .find(|m| {
-                m.starts_with(current_mode)
-                    || current_mode.starts_with(&m.split(" (").next().unwrap_or(""))
-            })
-            .cloned();
+        let selected_group = configured.and_then(|(width, height, _)| {
+            groups
+                .iter()
+                .find(|g| g.width as u32 == width && g.height as u32 == height)
+                .cloned()
+        });
+        let selected_resolution = match (&selected_group, current_mode.trim().is_empty()) {
+            (_, true) => Some(ResolutionOption::Auto),
+            (Some(group), false) => Some(ResolutionOption::Resolution(group.clone())),
+            (None, false) => None,
+        };
+
+        let description = if selected_resolution.is_none() {
+            format!("{} isn't a mode this output reports", current_mode)
+        } else {
+            "Resolution and refresh rate".to_string()
+        };
+
+        let resolution_options: Vec<ResolutionOption> = std::iter::once(ResolutionOption::Auto)
+            .chain(groups.into_iter().map(ResolutionOption::Resolution))
+            .collect();
+
+        let mut pickers = row![pick_list(
+            resolution_options,
+            selected_resolution,
+            move |option: ResolutionOption| {
+                let mode = match option {
+                    ResolutionOption::Auto => String::new(),
+                    ResolutionOption::Resolution(group) => group
+                        .default_refresh()
+                        .map(|rate| group.mode_string(rate))
+                        .unwrap_or_default(),
+                };
+                Message::Outputs(OutputsMessage::SetMode(idx, mode))
+            },
+        )
+        .width(Length::FillPortion(3))
+        .padding(8)]
+        .spacing(8);
+
+        if let Some(group) = selected_group {
+            // Config refresh rates are rounded, so match to the nearest 0.01 Hz
+            let selected_rate = configured
+                .and_then(|(_, _, refresh)| refresh)
+                .and_then(|hz| {
+                    let mhz = (hz * 1000.0).round() as i32;
+                    group
+                        .refresh_rates
+                        .iter()
+                        .find(|rate| (rate.refresh_mhz - mhz).abs() <= 5)
+                        .copied()
+                });
+            let rates = group.refresh_rates.clone();
+            pickers = pickers.push(
+                pick_list(rates, selected_rate, move |rate: RefreshOption| {
+                    Message::Outputs(OutputsMessage::SetMode(idx, group.mode_string(&rate)))
+                })
+                .placeholder("Refresh rate")
+                .width(Length::FillPortion(2))
+                .padding(8),
+            );
+        }
 
         column![
             row![
                 text("Mode").size(14).width(Length::FillPortion(1)),
-                pick_list(
-                    mode_strings.clone(),
-                    selected,
-                    move |selected_str: String| {
-                        // Extract just the mode string without " (preferred)" suffix
-                        let mode = selected_str
-                            .split(" (")
-                            .next()
-                            .unwrap_or(&selected_str)
-                            .to_string();
-                        Message::Outputs(OutputsMessage::SetMode(idx, mode))
-                    },
-                )
-                .width(Length::FillPortion(2))
-                .padding(8),
+                container(pickers).width(Length::FillPortion(3)),
             ]
             .spacing(12)
             .align_y(Alignment::Center),
-            container(text("Resolution and refresh rate").size(12)).style(muted_text_container),
+            container(text(description).size(12)).style(muted_text_container),
         ]
         .spacing(4)
         .into()
     }
 }
 
+/// "Best mode" quick action: highest refresh rate at the native resolution
+///
+/// Disabled when niri didn't report modes for this output.
+fn best_mode_row<'a>(idx: usize, current_mode: &str, best: Option<String>) -> Element<'a, Message> {
+    let description = match &best {
+        Some(mode) if mode == current_mode => "Already using the best mode".to_string(),
+        Some(mode) => format!("Highest refresh rate at native resolution: {}", mode),
+        None => "Needs niri running with this output connected".to_string(),
+    };
+
+    row![
+        container(text(description).size(12))
+            .width(Length::Fill)
+            .style(muted_text_container),
+        button(text("Best mode").size(12))
+            .on_press_maybe(
+                best.filter(|mode| mode != current_mode)
+                    .map(|mode| Message::Outputs(OutputsMessage::SetMode(idx, mode)))
+            )
+            .padding([4, 10])
+            .style(action_button_style),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Advisory for fractional scales, with a button to round to a whole number
+///
+/// Empty for integer scales.
+fn fractional_scale_note<'a>(idx: usize, scale: f64) -> Element<'a, Message> {
+    if !is_fractional_scale(scale) {
+        return column![].into();
+    }
+    let rounded = round_scale(scale, ScaleRounding::Nearest);
+
+    row![
+        container(
+            text("Xwayland apps render blurry at fractional scales; native Wayland apps are unaffected")
+                .size(12)
+        )
+        .width(Length::Fill)
+        .style(muted_text_container),
+        button(text(format!("Round to {}x", rounded)).size(12))
+            .on_press(Message::Outputs(OutputsMessage::SetScale(idx, rounded)))
+            .padding([4, 10])
+            .style(action_button_style),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Shows which monitor identity the output is (or would be) matched by
+fn identity_note<'a>(output: &OutputConfig) -> Element<'a, Message> {
+    let note = match output.identity() {
+        Some(identity) => format!("Identity: {}", identity),
+        None => "Identity unknown until niri reports this monitor".to_string(),
+    };
+    container(text(note).size(12))
+        .padding([0, 8])
+        .style(muted_text_container)
+        .into()
+}
+
 /// Detail view for a selected output
 /// Borrows from output to allow text_input widgets, returns Element<'a>
 pub fn output_detail_view<'a>(
@@ -290,6 +632,13 @@ pub fn output_detail_view<'a>(
     let mode_str = output.mode.as_str();
     let modeline_str = output.modeline.as_deref().unwrap_or("");
     let available_modes = get_available_modes(&output.name, available_outputs);
+    let best = available_outputs
+        .iter()
+        .find(|o| o.name == output.name)
+        .and_then(|o| best_mode(&o.modes))
+        .map(|mode| mode.mode_string());
+    let disconnected =
+        !available_outputs.is_empty() && !available_outputs.iter().any(|o| o.name == output.name);
 
     let modal_section = |icon: &'a str,
                          label: &'a str,
@@ -334,7 +683,8 @@ pub fn output_detail_view<'a>(
                 .padding(8)
                 .style(crate::theme::card_style),
                 Space::new().height(4),
-                mode_row(idx, mode_str, &available_modes),
+                mode_row(idx, mode_str, &available_modes, disconnected),
+                best_mode_row(idx, mode_str, best),
                 slider_row(
                     "Scale",
                     "HiDPI scaling factor",
@@ -344,6 +694,7 @@ pub fn output_detail_view<'a>(
                     "x",
                     move |v| Message::Outputs(OutputsMessage::SetScale(idx, v as f64))
                 ),
+                fractional_scale_note(idx, output.scale),
             ]
             .spacing(6)
             .width(Length::FillPortion(1)),
@@ -367,16 +718,20 @@ pub fn output_detail_view<'a>(
                             move |v| Message::Outputs(OutputsMessage::SetVrr(idx, v))
                         ),
                         toggle_row(
-                            "Focus at startup",
-                            "Focus this output on niri start",
-                            output.focus_at_startup,
-                            move |v| Message::Outputs(OutputsMessage::SetFocusAtStartup(idx, v))
+                            "Match by make/model/serial",
+                            "Follow this monitor across connectors",
+                            output.match_by_identity,
+                            move |v| Message::Outputs(OutputsMessage::SetMatchByIdentity(idx, v))
                         ),
+                        identity_note(output),
                     ]
                     .spacing(0)
                 )
                 .padding(8)
                 .style(crate::theme::card_style),
+                info_text(
+                    "niri has no gamma or night light setting; use a gamma client such as wlsunset or gammastep"
+                ),
             ]
             .spacing(6)
             .width(Length::FillPortion(1)),
@@ -506,6 +861,24 @@ pub fn output_detail_view<'a>(
         .style(crate::theme::card_style),
     ];
 
+    // ── PRESERVED OPTIONS ──
+    if !output.extra_nodes.is_empty() {
+        let mut preserved = column![info_text(
+            "These options aren't editable here yet but are kept as-is when this output is saved."
+        )]
+        .spacing(6);
+        for node in &output.extra_nodes {
+            preserved = preserved.push(text(node).size(12).font(fonts::MONO_FONT));
+        }
+        content = content.push(Space::new().height(20));
+        content = content.push(modal_section("⋯", "PRESERVED OPTIONS", neon::OUTLINE));
+        content = content.push(
+            container(preserved)
+                .padding(8)
+                .style(crate::theme::card_style),
+        );
+    }
+
     // ── LAYOUT OVERRIDE ──
     content = content.push(Space::new().height(20));
     content = content.push(modal_section("⊡", "LAYOUT OVERRIDE", neon::TERTIARY));
@@ -523,10 +896,29 @@ fn layout_override_content(output: &OutputConfig, idx: usize) -> Element<'_, Mes
     if let Some(lo) = output.layout_override.as_ref() {
         let mut content = column![
             info_text("Override global layout settings for this output. Fields set to \"Use Global\" inherit from the global layout."),
-            button(text("Remove All Overrides").size(14))
-                .on_press(Message::Outputs(OutputsMessage::SetLayoutOverride(idx, None)))
-                .padding([8, 16])
-                .style(delete_button_style),
+            row![
+                button(text("Reset to Inherited").size(14))
+                    .on_press_maybe(lo.has_any().then(|| {
+                        let mut cleared = lo.clone();
+                        cleared.clear_all();
+                        Message::Outputs(OutputsMessage::SetLayoutOverride(idx, Some(cleared)))
+                    }))
+                    .padding([8, 16]),
+                button(text("Remove All Overrides").size(14))
+                    .on_press(Message::Outputs(OutputsMessage::SetLayoutOverride(idx, None)))
+                    .padding([8, 16])
+                    .style(delete_button_style),
+                container(
+                    text(match lo.set_field_count() {
+                        1 => "1 override set".to_string(),
+                        count => format!("{} overrides set", count),
+                    })
+                    .size(12)
+                )
+                .style(muted_text_container),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
         ]
         .spacing(8);
 
@@ -911,7 +1303,7 @@ fn optional_color_row<'a>(
 
         let hex_input = text_input("", &hex_value)
             .on_input(move |hex| {
-                if let Some(c) = Color::from_hex(&hex) {
+                if let Some(c) = Color::parse(&hex) {
                     on_change_input(Some(c))
                 } else {
                     Message::None
@@ -987,7 +1379,7 @@ fn optional_color_or_gradient_row<'a>(
 
         let hex_input = text_input("", &hex_value)
             .on_input(move |hex| {
-                if let Some(c) = Color::from_hex(&hex) {
+                if let Some(c) = Color::parse(&hex) {
                     on_change_input(Some(ColorOrGradient::Color(c)))
                 } else {
                     Message::None
@@ -1046,3 +1438,78 @@ fn add_button_style(theme: &iced::Theme, status: button::Status) -> button::Styl
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(width: i32, height: i32, refresh_mhz: i32) -> ModeOption {
+        ModeOption {
+            width,
+            height,
+            refresh_mhz,
+            is_preferred: false,
+            is_current: false,
+        }
+    }
+
+    #[test]
+    fn test_group_modes_by_resolution() {
+        // Roughly what niri reports for a 1440p high-refresh monitor
+        let modes = vec![
+            ModeOption {
+                is_preferred: true,
+                ..mode(2560, 1440, 59951)
+            },
+            ModeOption {
+                is_current: true,
+                ..mode(2560, 1440, 164958)
+            },
+            mode(2560, 1440, 143912),
+            mode(1920, 1080, 60000),
+            mode(1920, 1080, 119880),
+            mode(1920, 1080, 60000),
+            mode(3840, 1080, 60000),
+            mode(640, 480, 59940),
+        ];
+
+        let groups = group_modes(&modes);
+        let resolutions: Vec<(i32, i32)> = groups.iter().map(|g| (g.width, g.height)).collect();
+        assert_eq!(
+            resolutions,
+            vec![(3840, 1080), (2560, 1440), (1920, 1080), (640, 480)]
+        );
+
+        let native = &groups[1];
+        let rates: Vec<i32> = native.refresh_rates.iter().map(|r| r.refresh_mhz).collect();
+        assert_eq!(rates, vec![164958, 143912, 59951]);
+        assert!(native.refresh_rates[0].is_current);
+        assert!(native.refresh_rates[2].is_preferred);
+        // Switching to the native resolution keeps niri's preferred rate
+        assert_eq!(
+            native.default_refresh().map(|r| native.mode_string(r)),
+            Some("2560x1440@59.95".to_string())
+        );
+        assert_eq!(
+            ResolutionOption::Resolution(native.clone()).to_string(),
+            "2560x1440 (preferred)"
+        );
+
+        // Duplicate rates collapse into one choice; without a preferred rate
+        // the highest one is the default
+        let fhd = &groups[2];
+        assert_eq!(fhd.refresh_rates.len(), 2);
+        assert_eq!(
+            fhd.default_refresh().map(|r| fhd.mode_string(r)),
+            Some("1920x1080@119.88".to_string())
+        );
+        assert_eq!(fhd.refresh_rates[1].to_string(), "60.00 Hz");
+        assert_eq!(native.refresh_rates[0].to_string(), "164.96 Hz (current)");
+    }
+
+    #[test]
+    fn test_group_modes_empty() {
+        assert!(group_modes(&[]).is_empty());
+        assert_eq!(ResolutionOption::Auto.to_string(), "Default (auto)");
+    }
+}
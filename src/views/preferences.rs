@@ -1,25 +1,59 @@
 //! Preferences settings view — neon modal style
+//!
+//! Everything here is Nirify's own state, stored in preferences.kdl and never
+//! written into niri's config.
 
-use iced::widget::{column, container, row, scrollable, text, text_input, Space};
+use iced::widget::{button, column, container, pick_list, row, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
-use super::widgets::{info_text, toggle_row};
+use super::widgets::{action_button_style, info_text, toggle_row};
+use crate::config::models::PreferencesSettings;
 use crate::messages::{Message, PreferencesMessage};
-use crate::theme::{fonts, neon};
+use crate::theme::{fonts, neon, AppTheme};
 
 /// Creates the preferences settings view
-pub fn view(
-    float_settings_app: bool,
-    show_search_bar: bool,
-    search_hotkey: &str,
-) -> Element<'static, Message> {
-    let search_hotkey_owned = search_hotkey.to_string();
+pub fn view(preferences: &PreferencesSettings, show_search_bar: bool) -> Element<'static, Message> {
+    let current_theme = preferences.theme.parse::<AppTheme>().unwrap_or_default();
+    let float_settings_app = preferences.float_settings_app;
+    let emit_all_defaults = preferences.emit_all_defaults;
+    let match_niri_colors = preferences.match_niri_colors;
+    let auto_reload = preferences.auto_reload;
+    let search_hotkey_owned = preferences.search_hotkey.clone();
 
     let content = column![
+        info_text(
+            "App preferences for Nirify itself. They're saved to preferences.kdl and never \
+             written into niri's config."
+        ),
+        Space::new().height(8),
         // ── 2-COLUMN: WINDOW | NAVIGATION ──
         row![
-            // Left: Window Behavior + About
+            // Left: Appearance, Window Behavior + About
             column![
+                modal_section("\u{25D0}", "APPEARANCE", neon::SECONDARY),
+                Space::new().height(4),
+                container(
+                    column![
+                        text("THEME")
+                            .size(10)
+                            .font(fonts::UI_FONT_SEMIBOLD)
+                            .color(neon::OUTLINE_VARIANT),
+                        pick_list(AppTheme::all(), Some(current_theme), Message::ChangeTheme)
+                            .width(Length::Fill)
+                            .padding(10),
+                        toggle_row(
+                            "Match My niri Colors",
+                            "Use your focus ring color as the accent, lightened or darkened \
+                             if needed to stay readable",
+                            match_niri_colors,
+                            |v| Message::Preferences(PreferencesMessage::SetMatchNiriColors(v)),
+                        ),
+                    ]
+                    .spacing(4),
+                )
+                .padding(12)
+                .style(crate::theme::card_style),
+                Space::new().height(16),
                 modal_section("\u{2699}", "WINDOW BEHAVIOR", neon::SECONDARY),
                 info_text("Configure how this settings application behaves."),
                 Space::new().height(4),
@@ -32,6 +66,28 @@ pub fn view(
                 .padding(8)
                 .style(crate::theme::card_style),
                 Space::new().height(16),
+                modal_section("\u{270E}", "CONFIG OUTPUT", neon::PRIMARY),
+                Space::new().height(4),
+                container(
+                    column![
+                        toggle_row(
+                            "Write Default Input Values",
+                            "Keep keyboard, mouse, touchpad, trackpoint and trackball values that match niri's defaults",
+                            emit_all_defaults,
+                            |v| Message::Preferences(PreferencesMessage::SetEmitAllDefaults(v)),
+                        ),
+                        toggle_row(
+                            "Reload niri After Saving",
+                            "Turn off to only write the files and reload niri yourself",
+                            auto_reload,
+                            |v| Message::Preferences(PreferencesMessage::SetAutoReload(v)),
+                        ),
+                    ]
+                    .spacing(4),
+                )
+                .padding(8)
+                .style(crate::theme::card_style),
+                Space::new().height(16),
                 modal_section("\u{2139}", "ABOUT", neon::TERTIARY),
                 Space::new().height(4),
                 container(
@@ -88,6 +144,14 @@ pub fn view(
                         .color(neon::OUTLINE),
                 )
                 .padding([4, 12]),
+                Space::new().height(16),
+                modal_section("\u{21BA}", "BACKUPS", neon::TERTIARY),
+                Space::new().height(4),
+                super::backups::retention_control(preferences.backup_retention),
+                Space::new().height(16),
+                modal_section("\u{2605}", "FAVORITES", neon::SECONDARY),
+                Space::new().height(4),
+                favorites_list(&preferences.favorites),
             ]
             .spacing(6)
             .width(Length::FillPortion(1)),
@@ -98,13 +162,51 @@ pub fn view(
     .spacing(0)
     .width(Length::Fill);
 
-    scrollable(container(content).padding(8).width(Length::Fill))
+    super::screens::page_scrollable(container(content).padding(8).width(Length::Fill))
         .height(Length::Fill)
         .into()
 }
 
 // ── Helpers ────────────────────────────────────────────────────────────────
 
+/// Starred settings with a button to unstar each
+fn favorites_list(favorites: &[String]) -> Element<'static, Message> {
+    if favorites.is_empty() {
+        return container(
+            text("Star settings from search to collect them on the Favorites screen.")
+                .size(12)
+                .color(neon::ON_SURFACE_VARIANT),
+        )
+        .padding(12)
+        .style(crate::theme::card_style)
+        .into();
+    }
+
+    let mut list = column![].spacing(2);
+    for name in favorites {
+        list = list.push(
+            row![
+                text(name.clone())
+                    .size(13)
+                    .color(neon::ON_SURFACE)
+                    .width(Length::Fill),
+                button(text("Remove").size(11).color(neon::ON_SURFACE_VARIANT))
+                    .on_press(Message::Preferences(PreferencesMessage::ToggleFavorite(
+                        name.clone()
+                    )))
+                    .padding([4, 10])
+                    .style(action_button_style),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+    container(list)
+        .padding(12)
+        .style(crate::theme::card_style)
+        .into()
+}
+
 fn modal_section<'a>(icon: &'a str, label: &'a str, accent: iced::Color) -> Element<'a, Message> {
     row![
         text(icon).size(14).color(accent),
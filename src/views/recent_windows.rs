@@ -375,7 +375,7 @@ fn color_input<'a>(
     on_change: impl Fn(String) -> Message + 'a,
 ) -> Element<'a, Message> {
     let hex_owned = hex.to_string();
-    let parsed = crate::types::Color::from_hex(&hex_owned)
+    let parsed = crate::types::Color::parse(&hex_owned)
         .map(|c| {
             iced::Color::from_rgba(
                 c.r as f32 / 255.0,
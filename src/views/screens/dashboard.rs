@@ -1,4 +1,5 @@
-//! Dashboard screen — compositor status, workspace overview, session actions
+//! Dashboard screen — compositor status, config summary, workspace overview,
+//! session actions
 //!
 //! Designed to match the Tokyo Neon "Command Center" aesthetic.
 
@@ -7,7 +8,7 @@ use iced::{Alignment, Element, Length};
 
 use crate::config::Settings;
 use crate::ipc::WorkspaceInfo;
-use crate::messages::Message;
+use crate::messages::{Message, Screen};
 use crate::theme::{fonts, neon, PillVariant};
 use crate::version::NiriVersion;
 use crate::views::status_bar::NiriStatus;
@@ -15,20 +16,56 @@ use crate::views::tools::ToolsState;
 
 use neon::{
     ON_SURFACE_VARIANT, OUTLINE_VARIANT, PRIMARY, SECONDARY, SURFACE_CONTAINER,
-    SURFACE_CONTAINER_HIGH, SURFACE_CONTAINER_HIGHEST,
+    SURFACE_CONTAINER_HIGH, SURFACE_CONTAINER_HIGHEST, WARNING,
 };
 
+/// At-a-glance counts of the user's config, read straight from `Settings`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfigSummary {
+    pub outputs: usize,
+    pub window_rules: usize,
+    pub keybindings: usize,
+    pub startup_commands: usize,
+}
+
+impl ConfigSummary {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            outputs: settings.outputs.outputs.len(),
+            window_rules: settings.window_rules.rules.len(),
+            keybindings: settings.keybindings.bindings.len(),
+            startup_commands: settings.startup.commands.len(),
+        }
+    }
+}
+
+/// Screens linked from the dashboard's quick links, most used first
+const QUICK_LINKS: &[Screen] = &[
+    Screen::Displays,
+    Screen::Input,
+    Screen::Visuals,
+    Screen::Layout,
+    Screen::Rules,
+];
+
 /// Dashboard screen
+///
+/// `unsaved_changes` is the number of settings categories with pending edits.
 pub fn view<'a>(
     niri_status: NiriStatus,
     niri_version: Option<NiriVersion>,
     tools_state: &'a ToolsState,
     settings: &'a Settings,
+    unsaved_changes: usize,
 ) -> Element<'a, Message> {
     let content = column![
         // ── Hero: Compositor Status ─────────────────────────────────────
         compositor_status_card(niri_status, niri_version),
         Space::new().height(8),
+        // ── Config summary ──────────────────────────────────────────────
+        label_text("YOUR CONFIG"),
+        config_summary_row(ConfigSummary::from_settings(settings), unsaved_changes),
+        Space::new().height(8),
         // ── Main grid: two columns ──────────────────────────────────────
         row![
             // Left column
@@ -43,6 +80,9 @@ pub fn view<'a>(
                     "Reload Config",
                     Message::Tools(crate::messages::ToolsMessage::ReloadConfig),
                 ),
+                Space::new().height(8),
+                label_text("QUICK LINKS"),
+                quick_links(),
             ]
             .spacing(8)
             .width(Length::FillPortion(4)),
@@ -64,7 +104,7 @@ pub fn view<'a>(
     .padding(32)
     .width(Length::Fill);
 
-    scrollable(content).height(Length::Fill).into()
+    super::page_scrollable(content).height(Length::Fill).into()
 }
 
 // ── Compositor Status Card ──────────────────────────────────────────────────
@@ -328,6 +368,47 @@ fn session_action_card<'a>(label: &'a str, on_press: Message) -> Element<'a, Mes
     .into()
 }
 
+// ── Config Summary ──────────────────────────────────────────────────────────
+
+fn config_summary_row<'a>(summary: ConfigSummary, unsaved_changes: usize) -> Element<'a, Message> {
+    let unsaved_accent = if unsaved_changes > 0 {
+        WARNING
+    } else {
+        OUTLINE_VARIANT
+    };
+
+    row![
+        stat_card("OUTPUTS", &summary.outputs.to_string(), PRIMARY),
+        stat_card("WINDOW RULES", &summary.window_rules.to_string(), SECONDARY),
+        stat_card("KEYBINDINGS", &summary.keybindings.to_string(), PRIMARY),
+        stat_card(
+            "STARTUP COMMANDS",
+            &summary.startup_commands.to_string(),
+            SECONDARY,
+        ),
+        stat_card(
+            "UNSAVED CHANGES",
+            &unsaved_changes.to_string(),
+            unsaved_accent
+        ),
+    ]
+    .spacing(12)
+    .into()
+}
+
+// ── Quick Links ─────────────────────────────────────────────────────────────
+
+fn quick_links<'a>() -> Element<'a, Message> {
+    let mut links = column![].spacing(8);
+    for screen in QUICK_LINKS {
+        links = links.push(session_action_card(
+            screen.name(),
+            Message::NavigateToScreen(*screen),
+        ));
+    }
+    links.into()
+}
+
 // ── Bottom Stats Row ────────────────────────────────────────────────────────
 
 fn stats_row<'a>(window_count: usize, workspace_count: usize) -> Element<'a, Message> {
@@ -394,13 +475,7 @@ fn status_pill<'a>(label: &'a str, variant: PillVariant) -> Element<'a, Message>
             },
             iced::Color::from_rgb(1.0, 0.44, 0.42),
         ),
-        PillVariant::Warning => (
-            iced::Color {
-                a: 0.12,
-                ..iced::Color::from_rgb(0.96, 0.62, 0.04)
-            },
-            iced::Color::from_rgb(0.96, 0.62, 0.04),
-        ),
+        PillVariant::Warning => (iced::Color { a: 0.12, ..WARNING }, WARNING),
         PillVariant::Muted => (SURFACE_CONTAINER_HIGHEST, OUTLINE_VARIANT),
     };
 
@@ -453,3 +528,36 @@ fn neon_card<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Messag
         })
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::models::{Keybinding, OutputConfig, StartupCommand, WindowRule};
+
+    #[test]
+    fn config_summary_counts_settings_entries() {
+        let mut settings = Settings::default();
+        settings.outputs.outputs = vec![OutputConfig::default(); 2];
+        settings.window_rules.rules = vec![WindowRule::default(); 3];
+        settings.keybindings.bindings = vec![Keybinding::default(); 4];
+        settings.startup.commands = vec![StartupCommand::default()];
+
+        assert_eq!(
+            ConfigSummary::from_settings(&settings),
+            ConfigSummary {
+                outputs: 2,
+                window_rules: 3,
+                keybindings: 4,
+                startup_commands: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn config_summary_of_empty_settings_is_zero() {
+        assert_eq!(
+            ConfigSummary::from_settings(&Settings::default()),
+            ConfigSummary::default()
+        );
+    }
+}
@@ -3,11 +3,14 @@
 //! Two-section layout: visual monitor preview at top, per-output config cards below.
 //! Full output editing is done through a modal overlay.
 
-use iced::widget::{button, column, container, row, scrollable, stack, text, Space};
-use iced::{Alignment, Element, Length};
+use iced::widget::{button, column, container, mouse_area, row, scrollable, stack, text, Space};
+use iced::{mouse, Alignment, Element, Length, Point};
 use std::collections::HashMap;
 
-use crate::config::models::{OutputConfig, OutputSettings};
+use crate::config::models::{
+    configured_output_size, logical_output_size, ArrangementPreset, OutputConfig, OutputRect,
+    OutputSettings,
+};
 use crate::ipc::FullOutputInfo;
 use crate::messages::{Message, OutputsMessage};
 use crate::theme::{fonts, neon};
@@ -19,6 +22,7 @@ pub fn view<'a>(
     _selected_output_index: Option<usize>,
     _sections_expanded: &'a HashMap<String, bool>,
     available_outputs: &'a [FullOutputInfo],
+    drag: Option<&OutputDrag>,
 ) -> Element<'a, Message> {
     let output_count = outputs.outputs.len();
     let connected_count = available_outputs.len();
@@ -51,7 +55,11 @@ pub fn view<'a>(
         Space::new().height(20),
 
         // ── Monitor Arrangement Preview ────────────────────────────────
-        monitor_preview(outputs, available_outputs),
+        monitor_preview(outputs, available_outputs, drag),
+        Space::new().height(12),
+        arrangement_presets(outputs),
+        Space::new().height(12),
+        views::outputs::layout_issues_banner(outputs, available_outputs),
 
         Space::new().height(32),
 
@@ -99,14 +107,104 @@ pub fn view<'a>(
     .padding(32)
     .width(Length::Fill);
 
-    scrollable(content).height(Length::Fill).into()
+    super::page_scrollable(content).height(Length::Fill).into()
 }
 
 // ── Monitor Arrangement Preview ────────────────────────────────────────────
 
+/// Where the arrangement preview draws the layout: the logical point at its
+/// top-left corner, preview pixels per logical pixel, and its size in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewFrame {
+    pub min_x: i32,
+    pub min_y: i32,
+    pub scale: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// An output being dragged around the arrangement preview
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputDrag {
+    pub index: usize,
+    /// The preview's frame when the drag started, kept so the preview
+    /// doesn't rescale under the pointer
+    pub frame: PreviewFrame,
+    /// Position before the drag
+    pub origin: (i32, i32),
+    /// Pointer position in preview pixels when the drag was first seen
+    pub anchor: Option<Point>,
+    /// Snapped position the output is dropped at
+    pub position: (i32, i32),
+}
+
+impl OutputDrag {
+    pub fn new(index: usize, frame: PreviewFrame, origin: (i32, i32)) -> Self {
+        Self {
+            index,
+            frame,
+            origin,
+            anchor: None,
+            position: origin,
+        }
+    }
+
+    /// Logical position, before snapping, for the pointer at `cursor`
+    pub fn target(&self, cursor: Point) -> (i32, i32) {
+        let Some(anchor) = self.anchor else {
+            return self.origin;
+        };
+        let scale = if self.frame.scale > 0.0 {
+            self.frame.scale
+        } else {
+            1.0
+        };
+        let dx = ((cursor.x - anchor.x) / scale).round() as i32;
+        let dy = ((cursor.y - anchor.y) / scale).round() as i32;
+        (
+            self.origin.0.saturating_add(dx),
+            self.origin.1.saturating_add(dy),
+        )
+    }
+}
+
+/// Logical rectangles of the enabled outputs other than `except`, to snap a
+/// dragged output against
+pub(crate) fn snap_targets(
+    outputs: &OutputSettings,
+    available: &[FullOutputInfo],
+    except: usize,
+) -> Vec<OutputRect> {
+    outputs
+        .outputs
+        .iter()
+        .enumerate()
+        .filter(|&(idx, output)| idx != except && output.enabled)
+        .map(|(_, output)| output_rect(output, available, output.position_x, output.position_y))
+        .collect()
+}
+
+/// Logical rectangle `output` covers at `(x, y)`
+pub(crate) fn output_rect(
+    output: &OutputConfig,
+    available: &[FullOutputInfo],
+    x: i32,
+    y: i32,
+) -> OutputRect {
+    let (width, height) = configured_output_size(output, available);
+    OutputRect {
+        x,
+        y,
+        width: width.max(0) as u32,
+        height: height.max(0) as u32,
+    }
+}
+
+/// Preview of the output layout; enabled outputs can be dragged into place
 fn monitor_preview<'a>(
     outputs: &'a OutputSettings,
     available: &'a [FullOutputInfo],
+    drag: Option<&OutputDrag>,
 ) -> Element<'a, Message> {
     if outputs.outputs.is_empty() && available.is_empty() {
         return container(
@@ -128,18 +226,29 @@ fn monitor_preview<'a>(
         .into();
     }
 
-    let monitors = collect_monitors(outputs, available);
+    let mut monitors = collect_monitors(outputs, available);
 
     if monitors.is_empty() {
         return Space::new().into();
     }
 
-    let Some(layout) = compute_preview_layout(&monitors, 200.0, 960.0) else {
-        return Space::new().into();
+    let layout = match drag {
+        Some(drag) => {
+            if let Some(monitor) = monitors.iter_mut().find(|m| m.index == drag.index) {
+                (monitor.x, monitor.y) = drag.position;
+            }
+            layout_in_frame(&monitors, drag.frame)
+        }
+        None => match compute_preview_layout(&monitors, 200.0, 960.0) {
+            Some(layout) => layout,
+            None => return Space::new().into(),
+        },
     };
 
-    let canvas_width = layout.width;
-    let canvas_height = layout.height;
+    let frame = layout.frame;
+    let canvas_width = frame.width;
+    let canvas_height = frame.height;
+    let configured_count = outputs.outputs.len();
     let monitor_layers: Vec<Element<'a, Message>> = layout
         .monitors
         .into_iter()
@@ -224,9 +333,28 @@ fn monitor_preview<'a>(
             })
             .into();
 
+            // Only configured outputs have a position to change
+            let monitor_box = if enabled && mon.index < configured_count {
+                mouse_area(monitor_box)
+                    .on_press(Message::Outputs(OutputsMessage::StartDrag(
+                        mon.index, frame,
+                    )))
+                    .interaction(mouse::Interaction::Grab)
+                    .into()
+            } else {
+                monitor_box
+            };
+
+            // Keep an output dragged past the edge drawn inside the preview
+            let left = preview
+                .left
+                .clamp(0.0, (canvas_width - preview.width).max(0.0));
+            let top = preview
+                .top
+                .clamp(0.0, (canvas_height - preview.height).max(0.0));
             container(column![
-                Space::new().height(Length::Fixed(preview.top)),
-                row![Space::new().width(Length::Fixed(preview.left)), monitor_box,],
+                Space::new().height(Length::Fixed(top)),
+                row![Space::new().width(Length::Fixed(left)), monitor_box,],
             ])
             .width(Length::Fixed(canvas_width))
             .height(Length::Fixed(canvas_height))
@@ -238,6 +366,16 @@ fn monitor_preview<'a>(
         .width(Length::Fixed(canvas_width))
         .height(Length::Fixed(canvas_height));
 
+    // Leaving the preview drops the output where it was last shown
+    let mut preview_canvas = mouse_area(preview_canvas);
+    if drag.is_some() {
+        preview_canvas = preview_canvas
+            .on_move(|point| Message::Outputs(OutputsMessage::Drag(point)))
+            .on_release(Message::Outputs(OutputsMessage::EndDrag))
+            .on_exit(Message::Outputs(OutputsMessage::EndDrag))
+            .interaction(mouse::Interaction::Grabbing);
+    }
+
     container(preview_canvas)
         .width(Length::Fill)
         .padding(24)
@@ -253,6 +391,52 @@ fn monitor_preview<'a>(
         .into()
 }
 
+/// Buttons placing the enabled outputs in a preset layout, for two or more
+fn arrangement_presets<'a>(outputs: &OutputSettings) -> Element<'a, Message> {
+    let enabled = outputs
+        .outputs
+        .iter()
+        .filter(|output| output.enabled)
+        .count();
+    let mut presets = row![text("ARRANGE")
+        .size(10)
+        .font(fonts::UI_FONT_SEMIBOLD)
+        .color(neon::OUTLINE_VARIANT)]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    for preset in ArrangementPreset::ALL {
+        presets = presets.push(
+            button(text(preset.name()).size(11).font(fonts::UI_FONT_MEDIUM))
+                .on_press_maybe(
+                    (enabled >= 2)
+                        .then_some(Message::Outputs(OutputsMessage::ApplyArrangement(preset))),
+                )
+                .padding([6, 12])
+                .style(|_: &iced::Theme, status| {
+                    let alpha = match status {
+                        iced::widget::button::Status::Hovered => 0.15,
+                        iced::widget::button::Status::Disabled => 0.03,
+                        _ => 0.08,
+                    };
+                    iced::widget::button::Style {
+                        background: Some(iced::Background::Color(iced::Color {
+                            a: alpha,
+                            ..neon::SECONDARY
+                        })),
+                        text_color: neon::SECONDARY,
+                        border: iced::Border {
+                            radius: 8.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }
+                }),
+        );
+    }
+    presets.into()
+}
+
 // ── Output Cards Grid ──────────────────────────────────────────────────────
 
 fn output_cards_grid<'a>(
@@ -541,15 +725,6 @@ fn calculate_canvas_size(outputs: &OutputSettings, available: &[FullOutputInfo])
         .unwrap_or((1920, 1080))
 }
 
-fn parse_resolution(mode: &str) -> Option<(i32, i32)> {
-    let at_idx = mode.find('@').unwrap_or(mode.len());
-    let res = &mode[..at_idx];
-    let mut parts = res.split('x');
-    let w = parts.next()?.parse().ok()?;
-    let h = parts.next()?.parse().ok()?;
-    Some((w, h))
-}
-
 fn collect_monitors(outputs: &OutputSettings, available: &[FullOutputInfo]) -> Vec<MonitorRect> {
     let mut monitors: Vec<MonitorRect> = outputs
         .outputs
@@ -591,19 +766,16 @@ fn collect_monitors(outputs: &OutputSettings, available: &[FullOutputInfo]) -> V
     monitors
 }
 
-fn configured_output_size(output: &OutputConfig, available: &[FullOutputInfo]) -> (i32, i32) {
-    available
-        .iter()
-        .find(|info| info.name == output.name)
-        .map(ipc_output_size)
-        .unwrap_or_else(|| parse_resolution(&output.mode).unwrap_or((1920, 1080)))
-}
-
+/// Logical size of an output that isn't configured, from niri's live state
 fn ipc_output_size(info: &FullOutputInfo) -> (i32, i32) {
-    info.current_mode
-        .and_then(|mode_idx| info.modes.get(mode_idx))
-        .map(|mode| (mode.width, mode.height))
-        .unwrap_or((1920, 1080))
+    let (mode_w, mode_h) = info.current_mode_size();
+    let (w, h) = logical_output_size(
+        mode_w.max(0) as u32,
+        mode_h.max(0) as u32,
+        info.scale(),
+        info.transform(),
+    );
+    (w as i32, h as i32)
 }
 
 fn monitor_bounds(monitors: &[MonitorRect]) -> Option<MonitorBounds> {
@@ -635,23 +807,30 @@ fn compute_preview_layout(
     }
 
     let scale = (max_height / total_height).min(max_width / total_width);
+    let frame = PreviewFrame {
+        min_x: bounds.min_x,
+        min_y: bounds.min_y,
+        scale,
+        width: total_width * scale,
+        height: total_height * scale,
+    };
+    Some(layout_in_frame(monitors, frame))
+}
+
+fn layout_in_frame(monitors: &[MonitorRect], frame: PreviewFrame) -> PreviewLayout {
     let monitors = monitors
         .iter()
         .cloned()
         .map(|rect| PreviewMonitor {
-            left: (rect.x - bounds.min_x) as f32 * scale,
-            top: (rect.y - bounds.min_y) as f32 * scale,
-            width: rect.width as f32 * scale,
-            height: rect.height as f32 * scale,
+            left: (rect.x - frame.min_x) as f32 * frame.scale,
+            top: (rect.y - frame.min_y) as f32 * frame.scale,
+            width: rect.width as f32 * frame.scale,
+            height: rect.height as f32 * frame.scale,
             rect,
         })
         .collect();
 
-    Some(PreviewLayout {
-        width: total_width * scale,
-        height: total_height * scale,
-        monitors,
-    })
+    PreviewLayout { frame, monitors }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -676,8 +855,7 @@ struct MonitorBounds {
 
 #[derive(Clone, Debug, PartialEq)]
 struct PreviewLayout {
-    width: f32,
-    height: f32,
+    frame: PreviewFrame,
     monitors: Vec<PreviewMonitor>,
 }
 
@@ -874,6 +1052,28 @@ mod tests {
         assert_eq!(calculate_canvas_size(&outputs, &[]), (3840, 1080));
     }
 
+    #[test]
+    fn calculate_canvas_size_uses_rotated_dimensions() {
+        let outputs = OutputSettings {
+            outputs: vec![
+                OutputConfig {
+                    name: "DP-1".to_string(),
+                    mode: "1920x1080@60.00".to_string(),
+                    ..Default::default()
+                },
+                OutputConfig {
+                    name: "DP-2".to_string(),
+                    mode: "1920x1080@60.00".to_string(),
+                    position_x: 1920,
+                    transform: crate::types::Transform::Rotate90,
+                    ..Default::default()
+                },
+            ],
+        };
+
+        assert_eq!(calculate_canvas_size(&outputs, &[]), (3000, 1920));
+    }
+
     #[test]
     fn compute_preview_layout_preserves_vertical_offsets() {
         let monitors = vec![
@@ -914,4 +1114,21 @@ mod tests {
         assert_eq!(top_monitor.left, bottom_monitor.left);
         assert!(bottom_monitor.top > top_monitor.top);
     }
+
+    #[test]
+    fn output_drag_converts_pointer_movement_to_logical_pixels() {
+        let frame = PreviewFrame {
+            min_x: 0,
+            min_y: 0,
+            scale: 0.1,
+            width: 384.0,
+            height: 108.0,
+        };
+        let mut drag = OutputDrag::new(1, frame, (1920, 0));
+        // Nothing moves until the pointer's starting point is known
+        assert_eq!(drag.target(Point::new(50.0, 50.0)), (1920, 0));
+
+        drag.anchor = Some(Point::new(200.0, 50.0));
+        assert_eq!(drag.target(Point::new(212.5, 45.0)), (2045, -50));
+    }
 }
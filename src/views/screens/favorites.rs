@@ -0,0 +1,469 @@
+//! Favorites screen — starred settings gathered in one place
+//!
+//! Each starred setting gets a card holding its own control, so it can be
+//! changed right here. The controls send the same messages as their pages;
+//! settings that need a whole page to edit get a button that opens it.
+
+use iced::widget::{button, column, container, row, scrollable, text, Space};
+use iced::{Alignment, Element, Length};
+
+use super::super::widgets::{
+    action_button, picker_row, slider_row, slider_row_int, text_input_row, toggle_row,
+};
+use crate::config::Settings;
+use crate::constants::{
+    ACCEL_SPEED_MAX, ACCEL_SPEED_MIN, BORDER_THICKNESS_MAX, BORDER_THICKNESS_MIN,
+    CORNER_RADIUS_MAX, CORNER_RADIUS_MIN, CURSOR_SIZE_MAX, CURSOR_SIZE_MIN, FOCUS_RING_WIDTH_MAX,
+    FOCUS_RING_WIDTH_MIN, GAP_SIZE_MAX, GAP_SIZE_MIN, REPEAT_DELAY_MAX, REPEAT_DELAY_MIN,
+    REPEAT_RATE_MAX, REPEAT_RATE_MIN,
+};
+use crate::messages::{
+    AnimationsMessage, AppearanceMessage, BehaviorMessage, CursorMessage, KeyboardMessage,
+    LayoutExtrasMessage, Message, MouseMessage, PreferencesMessage, TouchpadMessage,
+    TrackballMessage, TrackpointMessage,
+};
+use crate::search::SearchResult;
+use crate::theme::{fonts, neon};
+use crate::types::{AccelProfile, ModKey, ScrollMethod, WarpMouseMode};
+
+/// Favorites screen: one card per starred setting, in the order they were starred
+pub fn view<'a>(favorites: Vec<SearchResult>, settings: &'a Settings) -> Element<'a, Message> {
+    let header = super::hero_header(
+        "QUICK ACCESS",
+        "Favorites",
+        "Settings you've starred from search, gathered in one place.",
+        neon::SECONDARY,
+    );
+
+    if favorites.is_empty() {
+        let empty = container(
+            column![
+                text("No favorites yet")
+                    .size(14)
+                    .color(neon::ON_SURFACE_VARIANT),
+                text("Search for a setting and star it to pin it here")
+                    .size(12)
+                    .color(neon::OUTLINE_VARIANT),
+            ]
+            .spacing(6)
+            .align_x(Alignment::Center),
+        )
+        .padding(32)
+        .center(Length::Fill)
+        .style(crate::theme::card_style);
+
+        return column![header, empty]
+            .spacing(24)
+            .padding(32)
+            .width(Length::Fill)
+            .into();
+    }
+
+    let mut cards = column![header].spacing(12);
+    for favorite in favorites {
+        cards = cards.push(favorite_card(favorite, settings));
+    }
+
+    scrollable(cards.padding(32).width(Length::Fill))
+        .height(Length::Fill)
+        .into()
+}
+
+/// One starred setting: its control (or a link to its page) plus an unstar button
+fn favorite_card<'a>(favorite: SearchResult, settings: &'a Settings) -> Element<'a, Message> {
+    let star = favorite_star(&favorite.setting_name, true);
+
+    let body: Element<'a, Message> = match favorite_control(&favorite.setting_name, settings) {
+        Some(control) => column![
+            control,
+            text(favorite.page.name())
+                .size(10)
+                .color(neon::OUTLINE_VARIANT),
+        ]
+        .spacing(2)
+        .width(Length::Fill)
+        .into(),
+        None => row![
+            column![
+                text(favorite.setting_name.clone())
+                    .size(15)
+                    .font(fonts::UI_FONT_MEDIUM),
+                text(favorite.description)
+                    .size(11)
+                    .color(neon::ON_SURFACE_VARIANT),
+                text(favorite.page.name())
+                    .size(10)
+                    .color(neon::OUTLINE_VARIANT),
+            ]
+            .spacing(2)
+            .width(Length::Fill),
+            action_button(
+                "Open page",
+                Message::SelectFavorite(favorite.setting_name.clone())
+            ),
+        ]
+        .spacing(20)
+        .padding(12)
+        .align_y(Alignment::Center)
+        .into(),
+    };
+
+    container(
+        row![body, Space::new().width(4), star]
+            .align_y(Alignment::Center)
+            .padding([4, 8]),
+    )
+    .width(Length::Fill)
+    .style(crate::theme::card_style)
+    .into()
+}
+
+/// The control for a starred setting, built from the same widgets and
+/// messages as its page
+///
+/// Returns `None` for settings that can't be edited with a single control
+/// (colors, rules, lists and the like); those link to their page instead.
+fn favorite_control<'a>(name: &str, settings: &'a Settings) -> Option<Element<'a, Message>> {
+    let appearance = &settings.appearance;
+    let behavior = &settings.behavior;
+    let control = match name {
+        // Appearance
+        "Enable Focus Ring" => toggle_row(
+            "Enable Focus Ring",
+            "Show a colored ring around the focused window",
+            appearance.focus_ring_enabled,
+            |v| Message::Appearance(AppearanceMessage::ToggleFocusRing(v)),
+        ),
+        "Focus Ring Width" => slider_row(
+            "Focus Ring Width",
+            "Thickness of the focus ring in pixels",
+            appearance.focus_ring_width,
+            FOCUS_RING_WIDTH_MIN,
+            FOCUS_RING_WIDTH_MAX,
+            " px",
+            |v| Message::Appearance(AppearanceMessage::SetFocusRingWidth(v)),
+        ),
+        "Window Gaps" => slider_row(
+            "Window Gaps",
+            "Space between windows",
+            appearance.gaps,
+            GAP_SIZE_MIN,
+            GAP_SIZE_MAX,
+            " px",
+            |v| Message::Appearance(AppearanceMessage::SetGaps(v)),
+        ),
+        "Corner Radius" => slider_row(
+            "Corner Radius",
+            "Rounded corners on windows",
+            appearance.corner_radius,
+            CORNER_RADIUS_MIN,
+            CORNER_RADIUS_MAX,
+            " px",
+            |v| Message::Appearance(AppearanceMessage::SetCornerRadius(v)),
+        ),
+        "Border Width" => slider_row(
+            "Border Width",
+            "Thickness of window borders",
+            appearance.border_thickness,
+            BORDER_THICKNESS_MIN,
+            BORDER_THICKNESS_MAX,
+            " px",
+            |v| Message::Appearance(AppearanceMessage::SetBorderThickness(v)),
+        ),
+
+        // Layout extras
+        "Enable Window Shadow" => toggle_row(
+            "Enable Window Shadow",
+            "Show shadow behind windows",
+            settings.layout_extras.shadow.enabled,
+            |v| Message::LayoutExtras(LayoutExtrasMessage::SetShadowEnabled(v)),
+        ),
+        "Shadow Softness" => slider_row_int(
+            "Shadow Softness",
+            "Blur amount for window shadows",
+            settings.layout_extras.shadow.softness,
+            0,
+            100,
+            " px",
+            |v| Message::LayoutExtras(LayoutExtrasMessage::SetShadowSoftness(v)),
+        ),
+        "Center Single Column" => toggle_row(
+            "Center Single Column",
+            "Center windows when only one column exists",
+            behavior.always_center_single_column,
+            |v| Message::Behavior(BehaviorMessage::ToggleAlwaysCenterSingleColumn(v)),
+        ),
+
+        // Behavior
+        "Focus Follows Mouse" => toggle_row(
+            "Focus Follows Mouse",
+            "Window focus follows the mouse cursor",
+            behavior.focus_follows_mouse,
+            |v| Message::Behavior(BehaviorMessage::ToggleFocusFollowsMouse(v)),
+        ),
+        "Warp Mouse on Focus" => picker_row(
+            "Warp Mouse on Focus",
+            "Move cursor to focused window",
+            WarpMouseMode::all(),
+            Some(behavior.warp_mouse_to_focus),
+            |v| Message::Behavior(BehaviorMessage::SetWarpMouseToFocus(v)),
+        ),
+        "Workspace Auto Back-and-Forth" => toggle_row(
+            "Workspace Auto Back-and-Forth",
+            "Switching to current workspace goes to previous",
+            behavior.workspace_auto_back_and_forth,
+            |v| Message::Behavior(BehaviorMessage::ToggleWorkspaceAutoBackAndForth(v)),
+        ),
+        "Modifier Key" => picker_row(
+            "Modifier Key",
+            "Key used for window management (Super, Alt, etc.)",
+            ModKey::all(),
+            Some(behavior.mod_key),
+            |v| Message::Behavior(BehaviorMessage::SetModKey(v)),
+        ),
+
+        // Keyboard
+        "Keyboard Layout" => text_input_row(
+            "Keyboard Layout",
+            "Keyboard layout (e.g., 'us', 'de')",
+            &settings.keyboard.xkb_layout,
+            |v| Message::Keyboard(KeyboardMessage::SetXkbLayout(v)),
+        ),
+        "Repeat Rate" => slider_row_int(
+            "Repeat Rate",
+            "How fast keys repeat when held",
+            settings.keyboard.repeat_rate,
+            REPEAT_RATE_MIN,
+            REPEAT_RATE_MAX,
+            "/sec",
+            |v| Message::Keyboard(KeyboardMessage::SetRepeatRate(v)),
+        ),
+        "Repeat Delay" => slider_row_int(
+            "Repeat Delay",
+            "Delay before key repeat starts",
+            settings.keyboard.repeat_delay,
+            REPEAT_DELAY_MIN,
+            REPEAT_DELAY_MAX,
+            "ms",
+            |v| Message::Keyboard(KeyboardMessage::SetRepeatDelay(v)),
+        ),
+
+        // Mouse
+        "Mouse Acceleration" => picker_row(
+            "Mouse Acceleration",
+            "How mouse speed scales with movement",
+            AccelProfile::all(),
+            Some(settings.mouse.accel_profile),
+            |v| Message::Mouse(MouseMessage::SetAccelProfile(v)),
+        ),
+        "Mouse Speed" => slider_row(
+            "Mouse Speed",
+            "Base speed multiplier for mouse movement",
+            settings.mouse.accel_speed as f32,
+            ACCEL_SPEED_MIN as f32,
+            ACCEL_SPEED_MAX as f32,
+            "",
+            |v| Message::Mouse(MouseMessage::SetAccelSpeed(v)),
+        ),
+        "Natural Scrolling (Mouse)" => toggle_row(
+            "Natural Scrolling (Mouse)",
+            "Reverse scroll direction",
+            settings.mouse.natural_scroll,
+            |v| Message::Mouse(MouseMessage::ToggleNaturalScroll(v)),
+        ),
+        "Left-Handed Mouse" => toggle_row(
+            "Left-Handed Mouse",
+            "Swap left and right mouse buttons",
+            settings.mouse.left_handed,
+            |v| Message::Mouse(MouseMessage::ToggleLeftHanded(v)),
+        ),
+
+        // Touchpad
+        "Tap to Click" => toggle_row(
+            "Tap to Click",
+            "Tap the touchpad to click",
+            settings.touchpad.tap,
+            |v| Message::Touchpad(TouchpadMessage::ToggleTapToClick(v)),
+        ),
+        "Natural Scrolling (Touchpad)" => toggle_row(
+            "Natural Scrolling (Touchpad)",
+            "Reverse scroll direction on touchpad",
+            settings.touchpad.natural_scroll,
+            |v| Message::Touchpad(TouchpadMessage::ToggleNaturalScroll(v)),
+        ),
+        "Two-Finger Scroll" => picker_row(
+            "Two-Finger Scroll",
+            "Scroll using two fingers on touchpad",
+            ScrollMethod::all(),
+            Some(settings.touchpad.scroll_method),
+            |v| Message::Touchpad(TouchpadMessage::SetScrollMethod(v)),
+        ),
+        "Disable While Typing" => toggle_row(
+            "Disable While Typing",
+            "Disable touchpad while using keyboard",
+            settings.touchpad.dwt,
+            |v| Message::Touchpad(TouchpadMessage::ToggleDwt(v)),
+        ),
+        "Touchpad Speed" => slider_row(
+            "Touchpad Speed",
+            "Cursor speed when using touchpad",
+            settings.touchpad.accel_speed as f32,
+            ACCEL_SPEED_MIN as f32,
+            ACCEL_SPEED_MAX as f32,
+            "",
+            |v| Message::Touchpad(TouchpadMessage::SetAccelSpeed(v)),
+        ),
+
+        // Trackpoint and trackball
+        "Trackpoint Acceleration" => picker_row(
+            "Trackpoint Acceleration",
+            "How trackpoint speed scales with pressure",
+            AccelProfile::all(),
+            Some(settings.trackpoint.accel_profile),
+            |v| Message::Trackpoint(TrackpointMessage::SetAccelProfile(v)),
+        ),
+        "Trackpoint Scroll Method" => picker_row(
+            "Trackpoint Scroll Method",
+            "How scrolling works with the trackpoint",
+            ScrollMethod::all(),
+            Some(settings.trackpoint.scroll_method),
+            |v| Message::Trackpoint(TrackpointMessage::SetScrollMethod(v)),
+        ),
+        "Trackball Acceleration" => picker_row(
+            "Trackball Acceleration",
+            "How trackball speed scales with movement",
+            AccelProfile::all(),
+            Some(settings.trackball.accel_profile),
+            |v| Message::Trackball(TrackballMessage::SetAccelProfile(v)),
+        ),
+        "Trackball Scroll Method" => picker_row(
+            "Trackball Scroll Method",
+            "How scrolling works with the trackball",
+            ScrollMethod::all(),
+            Some(settings.trackball.scroll_method),
+            |v| Message::Trackball(TrackballMessage::SetScrollMethod(v)),
+        ),
+
+        // Cursor
+        "Cursor Theme" => text_input_row(
+            "Cursor Theme",
+            "Visual theme for the mouse cursor",
+            &settings.cursor.theme,
+            |v| Message::Cursor(CursorMessage::SetTheme(v)),
+        ),
+        "Cursor Size" => slider_row_int(
+            "Cursor Size",
+            "Size of the mouse cursor",
+            settings.cursor.size,
+            CURSOR_SIZE_MIN,
+            CURSOR_SIZE_MAX,
+            " px",
+            |v| Message::Cursor(CursorMessage::SetSize(v)),
+        ),
+
+        // Animations
+        "Animation Speed" => slider_row(
+            "Animation Speed",
+            "How fast animations play",
+            settings.animations.slowdown as f32,
+            1.0,
+            10.0,
+            "x",
+            |v| Message::Animations(AnimationsMessage::SetSlowdownFactor(v)),
+        ),
+
+        _ => return None,
+    };
+    Some(control)
+}
+
+/// Star toggle for a setting from the search index
+pub fn favorite_star<'a>(setting_name: &str, is_favorite: bool) -> Element<'a, Message> {
+    let (icon, color) = if is_favorite {
+        ("★", neon::SECONDARY)
+    } else {
+        ("☆", neon::OUTLINE_VARIANT)
+    };
+    button(text(icon).size(16).color(color))
+        .on_press(Message::Preferences(PreferencesMessage::ToggleFavorite(
+            setting_name.to_string(),
+        )))
+        .padding([6, 8])
+        .style(|_: &iced::Theme, status: button::Status| {
+            let bg = match status {
+                button::Status::Hovered => iced::Color {
+                    a: 0.1,
+                    ..neon::SECONDARY
+                },
+                _ => iced::Color::TRANSPARENT,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(bg)),
+                border: iced::Border {
+                    radius: 999.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SearchIndex;
+
+    #[test]
+    fn controlled_favorites_match_search_index_names() {
+        let names: Vec<String> = [
+            "Enable Focus Ring",
+            "Focus Ring Width",
+            "Window Gaps",
+            "Corner Radius",
+            "Border Width",
+            "Enable Window Shadow",
+            "Shadow Softness",
+            "Center Single Column",
+            "Focus Follows Mouse",
+            "Warp Mouse on Focus",
+            "Workspace Auto Back-and-Forth",
+            "Modifier Key",
+            "Keyboard Layout",
+            "Repeat Rate",
+            "Repeat Delay",
+            "Mouse Acceleration",
+            "Mouse Speed",
+            "Natural Scrolling (Mouse)",
+            "Left-Handed Mouse",
+            "Tap to Click",
+            "Natural Scrolling (Touchpad)",
+            "Two-Finger Scroll",
+            "Disable While Typing",
+            "Touchpad Speed",
+            "Trackpoint Acceleration",
+            "Trackpoint Scroll Method",
+            "Trackball Acceleration",
+            "Trackball Scroll Method",
+            "Cursor Theme",
+            "Cursor Size",
+            "Animation Speed",
+        ]
+        .map(String::from)
+        .to_vec();
+        let settings = Settings::default();
+
+        // A control keyed on a misspelled name would never be shown
+        assert_eq!(SearchIndex::new().favorites(&names).len(), names.len());
+        for name in &names {
+            assert!(favorite_control(name, &settings).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn favorites_without_a_single_control_link_to_their_page() {
+        let settings = Settings::default();
+        assert!(favorite_control("Focus Ring Color", &settings).is_none());
+        assert!(favorite_control("Window Rules", &settings).is_none());
+    }
+}
@@ -27,15 +27,11 @@ pub fn view<'a>(
 
     let tab_content: Element<'a, Message> = match sub_tab {
         GearSubTab::Tools => views::tools::view(tools_state, niri_connected),
-        GearSubTab::Preferences => views::preferences::view(
-            preferences.float_settings_app,
-            show_search_bar,
-            &preferences.search_hotkey,
-        ),
+        GearSubTab::Preferences => views::preferences::view(preferences, show_search_bar),
         GearSubTab::ConfigEditor => {
             views::config_editor::view(config_editor_state, config_editor_content)
         }
-        GearSubTab::Backups => views::backups::view(backups_state),
+        GearSubTab::Backups => views::backups::view(backups_state, preferences.backup_retention),
     };
 
     let content = column![
@@ -3,14 +3,17 @@
 //! Two-section layout: device cards at top, keybindings table at bottom.
 //! Device details and keybinding editing are done through modal overlays.
 
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, tooltip, Space};
 use iced::{Alignment, Element, Length};
 
 use crate::app::UiState;
+use crate::config::models::BINDING_TEMPLATES;
 use crate::config::Settings;
 use crate::messages::{EditableDevice, KeybindingsMessage, Message};
 use crate::theme::{fonts, neon};
 use crate::views;
+use crate::views::status_bar::NiriStatus;
+use crate::views::widgets::{badge, virtual_column, BADGE_VISIBILITY};
 
 /// Input screen with device cards + keybindings table
 pub fn view<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a, Message> {
@@ -73,6 +76,7 @@ pub fn view<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a, Message>
                 ),
             ]
             .width(Length::Fill),
+            templates_button(ui.show_binding_templates),
             button(
                 row![
                     text("+").size(16),
@@ -96,8 +100,10 @@ pub fn view<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a, Message>
                 }
             }),
         ]
+        .spacing(12)
         .align_y(Alignment::End),
         Space::new().height(16),
+        template_gallery(ui.show_binding_templates),
         // Search bar
         container(
             row![
@@ -144,7 +150,7 @@ pub fn view<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a, Message>
     .padding(32)
     .width(Length::Fill);
 
-    scrollable(content).height(Length::Fill).into()
+    super::page_scrollable(content).height(Length::Fill).into()
 }
 
 // ── Device Card ────────────────────────────────────────────────────────────
@@ -245,6 +251,62 @@ fn device_card<'a>(
 
 // ── Keybindings Table ──────────────────────────────────────────────────────
 
+/// Height of a binding row (and a section header)
+const KEYBINDING_ROW_HEIGHT: f32 = 56.0;
+/// Distance between the tops of consecutive rows
+const KEYBINDING_ROW_STRIDE: f32 = KEYBINDING_ROW_HEIGHT + 2.0;
+/// The table scrolls on its own past this height, so only the bindings in
+/// view are built
+const KEYBINDINGS_TABLE_MAX_HEIGHT: f32 = 640.0;
+
+/// A row of the keybindings table
+#[derive(Debug, Clone, Copy)]
+enum TableEntry<'a> {
+    Section {
+        name: &'a str,
+        /// Bindings in the group that match the search
+        count: usize,
+        collapsed: bool,
+    },
+    Binding(usize, &'a crate::config::models::Keybinding),
+}
+
+/// Table rows: a header per group, then its bindings unless it's collapsed
+///
+/// `filtered` holds the bindings matching the search, by index. While
+/// searching, collapsed groups still show their matches.
+fn table_entries<'a>(
+    bindings: &'a [crate::config::models::Keybinding],
+    filtered: &[(usize, &'a crate::config::models::Keybinding)],
+    collapsed: &std::collections::HashSet<String>,
+    searching: bool,
+) -> Vec<TableEntry<'a>> {
+    let mut entries = Vec::with_capacity(filtered.len());
+    for (name, indices) in crate::config::models::group_by_category(bindings) {
+        let members: Vec<_> = filtered
+            .iter()
+            .filter(|(idx, _)| indices.contains(idx))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        let is_collapsed = !searching && collapsed.contains(name);
+        entries.push(TableEntry::Section {
+            name,
+            count: members.len(),
+            collapsed: is_collapsed,
+        });
+        if !is_collapsed {
+            entries.extend(
+                members
+                    .into_iter()
+                    .map(|&(idx, kb)| TableEntry::Binding(idx, kb)),
+            );
+        }
+    }
+    entries
+}
+
 fn keybindings_table<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a, Message> {
     let search = &ui.keybindings_search;
 
@@ -267,13 +329,53 @@ fn keybindings_table<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a,
     let total = settings.keybindings.bindings.len();
     let shown = filtered.len();
 
-    let mut rows = column![].spacing(2);
-    for (idx, kb) in &filtered {
-        rows = rows.push(keybinding_row(*idx, kb));
-    }
+    // The binding each one shares its keys with, if any
+    let conflicts = settings.keybindings.find_conflicts();
+    let conflict_of = |kb: &crate::config::models::Keybinding| {
+        let other = conflicts.iter().find_map(|&(a, b)| match kb.id {
+            id if id == a => Some(b),
+            id if id == b => Some(a),
+            _ => None,
+        })?;
+        settings.keybindings.bindings.iter().find(|b| b.id == other)
+    };
+
+    // Section headers and bindings share one fixed row height so the table
+    // can be virtualized
+    let entries = table_entries(
+        &settings.keybindings.bindings,
+        &filtered,
+        &ui.keybinding_groups_collapsed,
+        !search.is_empty(),
+    );
+
+    // The binding open in the editor stays built while it's scrolled away
+    let pinned = ui.editing_keybinding_index.and_then(|editing| {
+        entries
+            .iter()
+            .position(|entry| matches!(entry, TableEntry::Binding(idx, _) if *idx == editing))
+    });
+    let rows = virtual_column(
+        entries.len(),
+        KEYBINDING_ROW_STRIDE,
+        ui.keybindings_viewport,
+        pinned,
+        |i| match entries[i] {
+            TableEntry::Section {
+                name,
+                count,
+                collapsed,
+            } => binding_section_header(name, count, collapsed),
+            TableEntry::Binding(idx, kb) => keybinding_row(idx, kb, conflict_of(kb)),
+        },
+    );
+    let table_height =
+        (entries.len() as f32 * KEYBINDING_ROW_STRIDE).min(KEYBINDINGS_TABLE_MAX_HEIGHT);
 
     column![
-        container(rows).width(Length::Fill),
+        scrollable(rows)
+            .height(Length::Fixed(table_height))
+            .on_scroll(|viewport| Message::KeybindingsScrolled(viewport.into())),
         Space::new().height(8),
         // Footer
         container(
@@ -289,9 +391,102 @@ fn keybindings_table<'a>(settings: &'a Settings, ui: &'a UiState) -> Element<'a,
     .into()
 }
 
+// ── Template Gallery ───────────────────────────────────────────────────────
+
+fn templates_button<'a>(open: bool) -> Element<'a, Message> {
+    button(text("Templates").size(14).font(fonts::UI_FONT_MEDIUM))
+        .on_press(Message::Keybindings(
+            KeybindingsMessage::ToggleTemplateGallery,
+        ))
+        .padding([10, 20])
+        .style(move |_: &iced::Theme, status| {
+            let hovered = matches!(status, iced::widget::button::Status::Hovered);
+            iced::widget::button::Style {
+                background: Some(iced::Background::Color(if open || hovered {
+                    neon::SURFACE_CONTAINER_HIGH
+                } else {
+                    neon::SURFACE_LOW
+                })),
+                text_color: neon::PRIMARY,
+                border: iced::Border {
+                    radius: 12.0.into(),
+                    color: iced::Color {
+                        a: 0.4,
+                        ..neon::PRIMARY
+                    },
+                    width: 1.0,
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+/// Ready-made binding sets; bindings whose key is already bound are skipped
+fn template_gallery<'a>(open: bool) -> Element<'a, Message> {
+    if !open {
+        return Space::new().into();
+    }
+
+    let mut cards = row![].spacing(12);
+    for (idx, template) in BINDING_TEMPLATES.iter().enumerate() {
+        let count = (template.bindings)().len();
+        cards = cards.push(
+            container(
+                column![
+                    text(template.name).size(14).font(fonts::UI_FONT_SEMIBOLD),
+                    text(template.description)
+                        .size(11)
+                        .color(neon::OUTLINE_VARIANT),
+                    Space::new().height(Length::Fill),
+                    button(text(format!("Add {} bindings", count)).size(12))
+                        .on_press(Message::Keybindings(KeybindingsMessage::ApplyTemplate(idx)))
+                        .padding([6, 12])
+                        .style(views::widgets::action_button_style),
+                ]
+                .spacing(6),
+            )
+            .padding(12)
+            .width(Length::FillPortion(1))
+            .height(Length::Fixed(140.0))
+            .style(crate::theme::card_style),
+        );
+    }
+
+    column![
+        text("Keys that are already bound are skipped.")
+            .size(12)
+            .color(neon::OUTLINE_VARIANT),
+        cards,
+        Space::new().height(16),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Group heading with its binding count; clicking collapses the group
+fn binding_section_header<'a>(name: &str, count: usize, collapsed: bool) -> Element<'a, Message> {
+    let arrow = if collapsed { "▸" } else { "▾" };
+    button(
+        text(format!("{} {} ({})", arrow, name.to_uppercase(), count))
+            .size(10)
+            .font(fonts::UI_FONT_SEMIBOLD)
+            .color(neon::OUTLINE_VARIANT),
+    )
+    .on_press(Message::Keybindings(KeybindingsMessage::ToggleGroup(
+        name.to_string(),
+    )))
+    .padding([10, 16])
+    .width(Length::Fill)
+    .height(Length::Fixed(KEYBINDING_ROW_HEIGHT))
+    .style(|_: &iced::Theme, _| iced::widget::button::Style::default())
+    .into()
+}
+
 fn keybinding_row<'a>(
     idx: usize,
     kb: &'a crate::config::models::Keybinding,
+    conflicts_with: Option<&crate::config::models::Keybinding>,
 ) -> Element<'a, Message> {
     // Parse key combo into modifiers + key
     let parts: Vec<&str> = kb.key_combo.split('+').collect();
@@ -318,12 +513,22 @@ fn keybinding_row<'a>(
         })
         .collect();
 
+    let mut title = row![text(action_name.to_string())
+        .size(13)
+        .font(fonts::UI_FONT_MEDIUM)]
+    .spacing(8)
+    .align_y(Alignment::Center);
+    if let Some(warning) = &kb.parse_warning {
+        title = title.push(parse_warning_badge(warning));
+    }
+    if let Some(other) = conflicts_with {
+        title = title.push(conflict_badge(other));
+    }
+
     let row_content = row![
         // Command / Action column
         column![
-            text(action_name.to_string())
-                .size(13)
-                .font(fonts::UI_FONT_MEDIUM),
+            title,
             text(action_subtitle.to_string())
                 .size(11)
                 .color(neon::ON_SURFACE_VARIANT),
@@ -342,6 +547,7 @@ fn keybinding_row<'a>(
     button(row_content)
         .on_press(Message::OpenKeybindingEditor(idx))
         .width(Length::Fill)
+        .height(Length::Fixed(KEYBINDING_ROW_HEIGHT))
         .style(|_: &iced::Theme, status| {
             let bg = match status {
                 iced::widget::button::Status::Hovered => iced::Color {
@@ -363,6 +569,45 @@ fn keybinding_row<'a>(
         .into()
 }
 
+/// Badge for bindings that weren't fully understood on import; hover shows why
+fn parse_warning_badge<'a>(warning: &'a str) -> Element<'a, Message> {
+    tooltip(
+        badge("⚠ partial import", BADGE_VISIBILITY),
+        container(text(warning).size(11))
+            .padding([6, 10])
+            .max_width(360)
+            .style(crate::theme::card_style),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Badge for bindings on the same keys as another; niri only runs the first
+fn conflict_badge<'a>(other: &crate::config::models::Keybinding) -> Element<'a, Message> {
+    container(
+        text(format!("conflicts with {}", other.display_name()))
+            .size(10)
+            .color(neon::ERROR),
+    )
+    .padding([2, 6])
+    .style(|_: &iced::Theme| container::Style {
+        background: Some(iced::Background::Color(iced::Color {
+            a: 0.12,
+            ..neon::ERROR
+        })),
+        border: iced::Border {
+            radius: 3.0.into(),
+            color: iced::Color {
+                a: 0.4,
+                ..neon::ERROR
+            },
+            width: 1.0,
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
 // ── Device Editor Modal ────────────────────────────────────────────────────
 
 /// Wraps an existing device view in a modal overlay
@@ -379,17 +624,30 @@ pub fn device_editor_modal<'a>(
         _ => neon::SECONDARY,
     };
 
+    let niri_connected = matches!(ui.niri_status, NiriStatus::Connected);
+
     // Get the device view content from existing views
     let device_content: Element<'a, Message> = match device {
-        EditableDevice::Keyboard => views::keyboard::view(&settings.keyboard),
+        EditableDevice::Keyboard => views::keyboard::view(
+            &settings.keyboard,
+            &ui.keyboard_test_input,
+            ui.tools_state
+                .keyboard_layouts
+                .as_ref()
+                .filter(|_| niri_connected),
+        ),
         EditableDevice::Mouse => views::mouse::view(&settings.mouse),
         EditableDevice::Touchpad => views::touchpad::view(&settings.touchpad),
         EditableDevice::Trackpoint => views::trackpoint::view(&settings.trackpoint),
         EditableDevice::Trackball => views::trackball::view(&settings.trackball),
-        EditableDevice::Tablet => {
-            views::tablet::view(&settings.tablet, &ui.tablet_calibration_cache)
+        EditableDevice::Tablet => views::tablet::view(
+            &settings.tablet,
+            &ui.tablet_calibration_cache,
+            niri_connected,
+        ),
+        EditableDevice::Touch => {
+            views::touch::view(&settings.touch, &ui.touch_calibration_cache, niri_connected)
         }
-        EditableDevice::Touch => views::touch::view(&settings.touch, &ui.touch_calibration_cache),
         EditableDevice::Gestures => views::gestures::view(&settings.gestures),
     };
 
@@ -594,3 +852,68 @@ fn key_pill<'a>(label: &str) -> Element<'a, Message> {
     })
     .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::models::{KeybindAction, Keybinding};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_collapsing_a_group_keeps_binding_order() {
+        let niri = |name: &str| Keybinding {
+            action: KeybindAction::NiriAction(name.to_string()),
+            ..Default::default()
+        };
+        let bindings = vec![
+            niri("close-window"),
+            niri("focus-workspace-down"),
+            niri("maximize-window"),
+        ];
+        let filtered: Vec<_> = bindings.iter().enumerate().collect();
+
+        let headers = |entries: &[TableEntry]| -> Vec<String> {
+            entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    TableEntry::Section { name, count, .. } => Some(format!("{name} {count}")),
+                    TableEntry::Binding(..) => None,
+                })
+                .collect()
+        };
+        let shown = |entries: &[TableEntry]| -> Vec<usize> {
+            entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    TableEntry::Binding(idx, _) => Some(*idx),
+                    TableEntry::Section { .. } => None,
+                })
+                .collect()
+        };
+
+        let entries = table_entries(&bindings, &filtered, &HashSet::new(), false);
+        assert_eq!(headers(&entries), ["Windows 2", "Workspaces 1"]);
+        assert_eq!(shown(&entries), [0, 2, 1]);
+
+        let collapsed = HashSet::from(["Windows".to_string()]);
+        let entries = table_entries(&bindings, &filtered, &collapsed, false);
+        assert_eq!(shown(&entries), [1]);
+        assert!(matches!(
+            entries[0],
+            TableEntry::Section {
+                name: "Windows",
+                count: 2,
+                collapsed: true
+            }
+        ));
+        // Searching shows matches in collapsed groups too
+        let entries = table_entries(&bindings, &filtered, &collapsed, true);
+        assert_eq!(shown(&entries), [0, 2, 1]);
+
+        // Only matches are counted, and empty groups drop out
+        let matching = [filtered[1]];
+        let entries = table_entries(&bindings, &matching, &HashSet::new(), true);
+        assert_eq!(headers(&entries), ["Workspaces 1"]);
+        assert_eq!(shown(&entries), [1]);
+    }
+}
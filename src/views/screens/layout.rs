@@ -1,12 +1,12 @@
 //! Layout screen — "Infinite Ribbon" with summary cards + modal editors
 
-use iced::widget::{column, container, row, scrollable, text, Space};
+use iced::widget::{column, container, row, text, Space};
 use iced::{Alignment, Element, Length};
 
 use crate::config::models::{
     AppearanceSettings, BehaviorSettings, LayoutExtrasSettings, WorkspacesSettings,
 };
-use crate::messages::{EditableSection, Message};
+use crate::messages::{EditableSection, Message, ToolsMessage};
 use crate::theme::{fonts, neon};
 
 use neon::{
@@ -14,12 +14,13 @@ use neon::{
 };
 
 /// Layout screen
-pub fn view<'a>(
-    layout_extras: &'a LayoutExtrasSettings,
-    workspaces: &'a WorkspacesSettings,
-    behavior: &'a BehaviorSettings,
-    appearance: &'a AppearanceSettings,
-) -> Element<'a, Message> {
+pub fn view(
+    layout_extras: &LayoutExtrasSettings,
+    workspaces: &WorkspacesSettings,
+    behavior: &BehaviorSettings,
+    appearance: &AppearanceSettings,
+    niri_connected: bool,
+) -> Element<'static, Message> {
     let content = column![
         // ── Hero header ─────────────────────────────────────────────
         super::hero_header(
@@ -31,6 +32,8 @@ pub fn view<'a>(
         Space::new().height(16),
         // ── Ribbon visualizer ───────────────────────────────────────
         ribbon_preview(),
+        Space::new().height(12),
+        adopt_bar(niri_connected),
         Space::new().height(24),
         // ── Summary Cards Row 1 ─────────────────────────────────────
         row![
@@ -76,7 +79,58 @@ pub fn view<'a>(
     .padding(32)
     .width(Length::Fill);
 
-    scrollable(content).height(Length::Fill).into()
+    super::page_scrollable(content).height(Length::Fill).into()
+}
+
+// ── Adopt From niri ────────────────────────────────────────────────────────
+
+/// Button to pull the live gaps from niri, with a note on what can be read
+fn adopt_bar<'a>(niri_connected: bool) -> Element<'a, Message> {
+    let note = if niri_connected {
+        "Gaps are measured from your open windows; struts can't be read from niri"
+    } else {
+        "Start niri to read its current layout"
+    };
+
+    let button = iced::widget::button(
+        text("ADOPT FROM RUNNING NIRI")
+            .size(10)
+            .font(fonts::UI_FONT_SEMIBOLD)
+            .color(SECONDARY),
+    )
+    .on_press_maybe(niri_connected.then_some(Message::Tools(ToolsMessage::AdoptLayoutFromNiri)))
+    .padding([6, 12])
+    .style(|_: &iced::Theme, status| {
+        let alpha = match status {
+            iced::widget::button::Status::Hovered => 0.15,
+            iced::widget::button::Status::Disabled => 0.03,
+            _ => 0.08,
+        };
+        iced::widget::button::Style {
+            background: Some(iced::Background::Color(iced::Color {
+                a: alpha,
+                ..SECONDARY
+            })),
+            text_color: SECONDARY,
+            border: iced::Border {
+                radius: 8.0.into(),
+                color: iced::Color {
+                    a: 0.2,
+                    ..SECONDARY
+                },
+                width: 1.0,
+            },
+            ..Default::default()
+        }
+    });
+
+    row![
+        text(note).size(11).color(OUTLINE_VARIANT),
+        Space::new().width(Length::Fill),
+        button,
+    ]
+    .align_y(Alignment::Center)
+    .into()
 }
 
 // ── Summary Card ───────────────────────────────────────────────────────────
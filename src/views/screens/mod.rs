@@ -4,6 +4,7 @@
 
 pub mod dashboard;
 pub mod displays;
+pub mod favorites;
 pub mod gear;
 pub mod input;
 pub mod layout;
@@ -11,11 +12,26 @@ pub mod rules;
 pub mod system;
 pub mod visuals;
 
-use iced::widget::{column, container, row, text, Space};
+use iced::widget::{column, container, row, scrollable, text, Id, Scrollable, Space};
 use iced::{Alignment, Element, Length};
 
 use crate::theme::{fonts, neon};
 
+/// ID of the scrollable holding the current page, so its offset can be
+/// restored when the page is shown again
+pub fn page_scroll_id() -> Id {
+    Id::new("page-scroll")
+}
+
+/// Main scrollable of a page, reporting its offset as it moves
+pub fn page_scrollable<'a>(
+    content: impl Into<Element<'a, crate::messages::Message>>,
+) -> Scrollable<'a, crate::messages::Message> {
+    scrollable(content)
+        .id(page_scroll_id())
+        .on_scroll(|viewport| crate::messages::Message::PageScrolled(viewport.absolute_offset().y))
+}
+
 /// Creates a consistent screen header: large title + subtitle
 pub fn screen_header<'a, Message: 'a>(title: &'a str, subtitle: &'a str) -> Element<'a, Message> {
     column![
@@ -2,41 +2,38 @@
 
 use iced::widget::{button, column, container, row, scrollable, text, Space};
 use iced::{Alignment, Element, Length};
-use std::collections::HashMap;
 
+use crate::app::UiState;
 use crate::config::models::{LayerRulesSettings, WindowRulesSettings};
-use crate::messages::{LayerRulesMessage, Message, RulesFilter, RulesSubTab, WindowRulesMessage};
+use crate::messages::{LayerRulesMessage, Message, Page, RulesSubTab, WindowRulesMessage};
 use crate::theme::{fonts, neon};
 use crate::views;
 
 /// Rules screen with Window/Layer sub-tabs
 pub fn view<'a>(
-    sub_tab: RulesSubTab,
     window_rules: &'a WindowRulesSettings,
-    search: &'a str,
-    filter: RulesFilter,
-    window_rule_sections: &'a HashMap<(u32, String), bool>,
-    window_rule_errors: &'a HashMap<(u32, String), String>,
-    available_workspaces: &'a [String],
     layer_rules: &'a LayerRulesSettings,
-    layer_rule_sections: &'a HashMap<(u32, String), bool>,
-    layer_rule_errors: &'a HashMap<(u32, String), String>,
+    ui: &'a UiState,
 ) -> Element<'a, Message> {
+    let sub_tab = ui.rules_sub_tab;
+    let page = match sub_tab {
+        RulesSubTab::WindowRules => Page::WindowRules,
+        RulesSubTab::LayerRules => Page::LayerRules,
+    };
     let tab_content: Element<'a, Message> = match sub_tab {
         RulesSubTab::WindowRules => views::window_rules::view(
             window_rules,
-            search,
-            filter,
-            window_rule_sections,
-            window_rule_errors,
-            available_workspaces,
+            &ui.rules_search,
+            ui.rules_filter,
+            ui.rules_viewport(page),
+            ui.editing_window_rule_id,
         ),
         RulesSubTab::LayerRules => views::layer_rules::view(
             layer_rules,
-            search,
-            filter,
-            layer_rule_sections,
-            layer_rule_errors,
+            &ui.rules_search,
+            ui.rules_filter,
+            ui.rules_viewport(page),
+            ui.editing_layer_rule_id,
         ),
     };
 
@@ -94,7 +91,9 @@ pub fn view<'a>(
             container(tab_content)
                 .width(Length::Fill)
         )
-        .height(Length::Fill),
+        .id(super::page_scroll_id())
+        .height(Length::Fill)
+        .on_scroll(move |viewport| Message::RulesScrolled(page, viewport.into())),
     ]
     .spacing(12)
     .padding(32)
@@ -1,6 +1,6 @@
 //! System screen — summary cards + modal editors
 
-use iced::widget::{column, container, row, scrollable, text, Space};
+use iced::widget::{column, container, row, text, Space};
 use iced::{Alignment, Element, Length};
 
 use crate::config::models::{
@@ -10,14 +10,14 @@ use crate::config::models::{
 use crate::messages::{EditableSection, Message};
 use crate::theme::{fonts, neon};
 
-pub fn view<'a>(
-    startup: &'a StartupSettings,
-    environment: &'a EnvironmentSettings,
-    miscellaneous: &'a MiscSettings,
-    switch_events: &'a SwitchEventsSettings,
-    debug: &'a DebugSettings,
-    recent_windows: &'a RecentWindowsSettings,
-) -> Element<'a, Message> {
+pub fn view(
+    startup: &StartupSettings,
+    environment: &EnvironmentSettings,
+    miscellaneous: &MiscSettings,
+    switch_events: &SwitchEventsSettings,
+    debug: &DebugSettings,
+    recent_windows: &RecentWindowsSettings,
+) -> Element<'static, Message> {
     let content = column![
         super::hero_header(
             "SYSTEM CORE",
@@ -62,7 +62,7 @@ pub fn view<'a>(
     .padding(32)
     .width(Length::Fill);
 
-    scrollable(content).height(Length::Fill).into()
+    super::page_scrollable(content).height(Length::Fill).into()
 }
 
 fn summary_card<'a>(
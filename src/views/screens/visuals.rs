@@ -1,6 +1,6 @@
 //! Visuals screen — summary cards + modal editors
 
-use iced::widget::{column, container, row, scrollable, text, Space};
+use iced::widget::{column, container, row, text, Space};
 use iced::{Alignment, Element, Length};
 
 use crate::config::models::{
@@ -9,13 +9,13 @@ use crate::config::models::{
 use crate::messages::{EditableSection, Message};
 use crate::theme::{fonts, neon};
 
-pub fn view<'a>(
-    appearance: &'a AppearanceSettings,
-    animations: &'a AnimationSettings,
-    cursor: &'a CursorSettings,
-    layout_extras: &'a LayoutExtrasSettings,
-    behavior: &'a BehaviorSettings,
-) -> Element<'a, Message> {
+pub fn view(
+    appearance: &AppearanceSettings,
+    animations: &AnimationSettings,
+    cursor: &CursorSettings,
+    layout_extras: &LayoutExtrasSettings,
+    behavior: &BehaviorSettings,
+) -> Element<'static, Message> {
     let content = column![
         super::hero_header(
             "VISUAL ENGINE",
@@ -60,7 +60,7 @@ pub fn view<'a>(
     .padding(32)
     .width(Length::Fill);
 
-    scrollable(content).height(Length::Fill).into()
+    super::page_scrollable(content).height(Length::Fill).into()
 }
 
 fn summary_card<'a>(
@@ -14,22 +14,30 @@ const MAX_DROPDOWN_RESULTS: usize = 6;
 
 /// Creates the search dropdown if there are results to show
 /// Returns None if query is empty
-pub fn view(results: &[SearchResult], query: &str) -> Option<Element<'static, Message>> {
+///
+/// `pending` means the query's search hasn't run yet (typing hasn't paused),
+/// so an empty result list isn't reported as "no matches".
+pub fn view(
+    results: &[SearchResult],
+    query: &str,
+    pending: bool,
+) -> Option<Element<'static, Message>> {
     if query.trim().is_empty() {
         return None;
     }
 
     let dropdown = if results.is_empty() {
         // No results message
-        container(
-            text("No matching settings found")
-                .size(13)
-                .color([0.6, 0.6, 0.6]),
-        )
-        .padding([12, 16])
-        .width(Length::Fixed(320.0))
-        .style(search_dropdown_style)
-        .into()
+        let message = if pending {
+            "Searching…"
+        } else {
+            "No matching settings found"
+        };
+        container(text(message).size(13).color([0.6, 0.6, 0.6]))
+            .padding([12, 16])
+            .width(Length::Fixed(320.0))
+            .style(search_dropdown_style)
+            .into()
     } else {
         build_results_list(results)
     };
@@ -3,7 +3,7 @@
 use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
-use super::widgets::info_text;
+use super::widgets::{info_text, reorder_controls};
 use crate::config::models::StartupSettings;
 use crate::messages::{Message, StartupMessage};
 use crate::theme::{fonts, neon};
@@ -49,7 +49,8 @@ pub fn view_section(settings: &StartupSettings) -> Element<'static, Message> {
             .style(crate::theme::card_style),
         );
     } else {
-        for cmd in &commands {
+        let cmd_count = commands.len();
+        for (idx, cmd) in commands.iter().enumerate() {
             let cmd_id = cmd.id;
             let cmd_display = cmd.display();
 
@@ -62,6 +63,9 @@ pub fn view_section(settings: &StartupSettings) -> Element<'static, Message> {
                                 .font(fonts::UI_FONT_SEMIBOLD)
                                 .color(neon::OUTLINE_VARIANT),
                             Space::new().width(Length::Fill),
+                            reorder_controls(idx, cmd_count, |m| Message::Startup(
+                                StartupMessage::Reorder(m)
+                            )),
                             button(text("\u{00D7}").size(14).color(neon::ERROR))
                                 .on_press(Message::Startup(StartupMessage::RemoveCommand(cmd_id)))
                                 .padding([2, 8])
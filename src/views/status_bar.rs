@@ -3,7 +3,8 @@
 use iced::widget::{button, container, row, text};
 use iced::{Alignment, Element, Length, Theme};
 
-use crate::messages::Message;
+use crate::messages::{Message, SaveMessage, ToolsMessage};
+use crate::save_manager::ReloadResult;
 use crate::theme::{muted_text_container, secondary_text_container, status_bar_style, AppTheme};
 
 /// Niri connection status
@@ -16,21 +17,28 @@ pub enum NiriStatus {
 }
 
 /// Creates the status bar at the bottom of the window
+#[allow(clippy::too_many_arguments)]
 pub fn view(
     dirty: bool,
     save_status: Option<String>,
+    last_reload: Option<&ReloadResult>,
+    can_revert: bool,
+    read_only: bool,
     current_theme: AppTheme,
     niri_status: NiriStatus,
+    auto_reload: bool,
 ) -> Element<'static, Message> {
     // Status indicator - uses theme's warning/success colors
-    let status_text = if dirty {
+    let status_text = if read_only {
+        "● Read-only: another Nirify is running"
+    } else if dirty {
         "● Unsaved changes"
     } else {
         "✓ Changes saved automatically"
     };
 
     let status = container(text(status_text).size(12)).style(move |theme: &Theme| {
-        let color = if dirty {
+        let color = if dirty || read_only {
             theme.palette().warning
         } else {
             theme.palette().success
@@ -41,6 +49,12 @@ pub fn view(
         }
     });
 
+    // Clicking the status opens the pending changes panel
+    let status = button(status)
+        .padding(0)
+        .style(|_theme, _status| button::Style::default())
+        .on_press(Message::Save(SaveMessage::ShowPendingChanges));
+
     let status_row = row![status].spacing(8).align_y(Alignment::Center);
 
     // Niri connection status - uses theme's success/danger colors
@@ -81,6 +95,53 @@ pub fn view(
             content.push(container(text(message.clone()).size(12)).style(secondary_text_container));
     }
 
+    // Last reload outcome, so users can tell their change took effect
+    if let Some(reload) = last_reload {
+        let success = reload.is_success();
+        let icon = if success { "✓" } else { "✗" };
+        content = content.push(
+            container(text(format!("{} {}", icon, reload.status_message())).size(12)).style(
+                move |theme: &Theme| {
+                    let color = if success {
+                        theme.palette().success
+                    } else {
+                        theme.palette().danger
+                    };
+                    container::Style {
+                        text_color: Some(color),
+                        ..Default::default()
+                    }
+                },
+            ),
+        );
+    }
+
+    // Saves don't reach niri on their own, so offer the reload here
+    if !auto_reload {
+        content = content.push(container(text("Auto-reload off").size(12)).style(
+            move |theme: &Theme| container::Style {
+                text_color: Some(theme.palette().warning),
+                ..Default::default()
+            },
+        ));
+        content = content.push(
+            button(text("⟳ Reload niri now").size(12))
+                .padding([2, 8])
+                .style(theme_button_style)
+                .on_press(Message::Tools(ToolsMessage::ReloadConfig)),
+        );
+    }
+
+    // One-click undo for the last save and the reload it triggered
+    if can_revert {
+        content = content.push(
+            button(text("↶ Revert last change").size(12))
+                .padding([2, 8])
+                .style(theme_button_style)
+                .on_press(Message::Save(SaveMessage::RevertLastChange)),
+        );
+    }
+
     // Theme selector - cycles through available themes
     let theme_button = button(
         row![
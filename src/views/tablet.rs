@@ -3,7 +3,9 @@
 use iced::widget::{column, container, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
-use super::widgets::{calibration_matrix, toggle_row, CalibrationMatrixMessage};
+use super::widgets::{
+    calibration_matrix, focused_output_button, toggle_row, CalibrationMatrixMessage,
+};
 use crate::config::models::TabletSettings;
 use crate::messages::{Message, TabletMessage};
 use crate::theme::{fonts, neon};
@@ -11,6 +13,7 @@ use crate::theme::{fonts, neon};
 pub fn view<'a>(
     settings: &'a TabletSettings,
     calibration_cache: &'a [String; 6],
+    niri_connected: bool,
 ) -> Element<'a, Message> {
     let map_to_output = settings.map_to_output.clone();
 
@@ -27,6 +30,10 @@ pub fn view<'a>(
                     &map_to_output,
                     |v| Message::Tablet(TabletMessage::SetMapToOutput(v))
                 ),
+                focused_output_button(
+                    niri_connected,
+                    Message::Tablet(TabletMessage::MapToFocusedOutput)
+                ),
                 Space::new().height(12),
                 modal_section("\u{2699}", "CALIBRATION", neon::TERTIARY),
                 Space::new().height(4),
@@ -3,12 +3,13 @@
 //! IPC tools for interacting with niri - query windows, workspaces,
 //! outputs, reload config, and validate config.
 
-use iced::widget::{button, column, container, row, scrollable, text, Column, Space};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Column, Space};
 use iced::{Alignment, Element, Length};
 
 use super::widgets::*;
-use crate::ipc::{FullOutputInfo, WindowInfo, WorkspaceInfo};
-use crate::messages::{Message, ToolsMessage};
+use crate::config::{Lint, LintSeverity, SettingsCategory};
+use crate::ipc::{FullOutputInfo, KeyboardLayouts, ValidationReport, WindowInfo, WorkspaceInfo};
+use crate::messages::{Message, PasteConfigMessage, ToolsMessage};
 use crate::theme::{fonts, neon};
 
 /// State for the tools page (cached IPC data)
@@ -24,6 +25,8 @@ pub struct ToolsState {
     pub outputs: Vec<FullOutputInfo>,
     /// Currently focused window
     pub focused_window: Option<WindowInfo>,
+    /// Keyboard layouts niri has loaded, shown on the Keyboard page
+    pub keyboard_layouts: Option<KeyboardLayouts>,
     /// Loading states
     pub loading_windows: bool,
     pub loading_workspaces: bool,
@@ -32,11 +35,27 @@ pub struct ToolsState {
     /// Last error message (if any)
     pub last_error: Option<String>,
     /// Last validation result
-    pub validation_result: Option<Result<String, String>>,
+    pub validation_result: Option<Result<ValidationReport, String>>,
+    /// Settings page for each validation error, where it's in a file we manage
+    pub validation_targets: Vec<Option<SettingsCategory>>,
     /// Is reload in progress
     pub reloading: bool,
     /// Is validation in progress
     pub validating: bool,
+    /// Last "is niri using our config" check result
+    pub effective_result: Option<Result<String, String>>,
+    /// Is the effectiveness check in progress
+    pub checking_effective: bool,
+    /// Comma-separated app-ids for the screencast privacy rule
+    pub screencast_block_app_ids: String,
+    /// Findings from the last "Lint Config" run
+    pub lint_results: Option<Vec<Lint>>,
+    /// Config niri loads, while "View Effective Config" is open
+    pub effective_config: Option<String>,
+    /// Is an export being written
+    pub exporting: bool,
+    /// Last "Export Config" result
+    pub export_result: Option<Result<String, String>>,
 }
 
 /// Creates the tools view
@@ -62,6 +81,11 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
     } else {
         "Validate Config"
     };
+    let effective_label = if state.checking_effective {
+        "Checking..."
+    } else {
+        "Check Config Is Active"
+    };
 
     let neon_btn = |_: &iced::Theme, status: iced::widget::button::Status| {
         let bg = match status {
@@ -120,6 +144,9 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
     let mut validate_btn = button(text(validate_label).size(12).font(fonts::UI_FONT_MEDIUM))
         .padding([8, 14])
         .style(neon_btn);
+    let mut effective_btn = button(text(effective_label).size(12).font(fonts::UI_FONT_MEDIUM))
+        .padding([8, 14])
+        .style(neon_btn);
     let consolidate_btn = button(
         text("Consolidate Rules")
             .size(12)
@@ -128,13 +155,66 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
     .padding([8, 14])
     .style(neon_btn)
     .on_press(Message::AnalyzeConsolidation);
+    let paste_config_btn = button(text("Paste Config").size(12).font(fonts::UI_FONT_MEDIUM))
+        .padding([8, 14])
+        .style(neon_btn)
+        .on_press(Message::PasteConfig(PasteConfigMessage::Open));
+    let import_file_btn = button(
+        text("Import from File")
+            .size(12)
+            .font(fonts::UI_FONT_MEDIUM),
+    )
+    .padding([8, 14])
+    .style(neon_btn)
+    .on_press(Message::PasteConfig(PasteConfigMessage::ChooseFile));
+    let lint_btn = button(text("Lint Config").size(12).font(fonts::UI_FONT_MEDIUM))
+        .padding([8, 14])
+        .style(neon_btn)
+        .on_press(Message::Tools(ToolsMessage::LintConfig));
+    let effective_config_btn = button(
+        text(if state.effective_config.is_some() {
+            "Hide Effective Config"
+        } else {
+            "View Effective Config"
+        })
+        .size(12)
+        .font(fonts::UI_FONT_MEDIUM),
+    )
+    .padding([8, 14])
+    .style(neon_btn)
+    .on_press(Message::Tools(ToolsMessage::ToggleEffectiveConfig));
+    let mut export_btn = button(
+        text(if state.exporting {
+            "Exporting..."
+        } else {
+            "Export Config"
+        })
+        .size(12)
+        .font(fonts::UI_FONT_MEDIUM),
+    )
+    .padding([8, 14])
+    .style(neon_btn);
+    let screencast_btn = button(
+        text("Toggle Screencast Block")
+            .size(12)
+            .font(fonts::UI_FONT_MEDIUM),
+    )
+    .padding([8, 14])
+    .style(neon_btn)
+    .on_press(Message::Tools(ToolsMessage::ToggleScreencastBlock));
 
     if niri_connected && !state.reloading {
         reload_btn = reload_btn.on_press(Message::Tools(ToolsMessage::ReloadConfig));
     }
+    if !state.exporting {
+        export_btn = export_btn.on_press(Message::Tools(ToolsMessage::ExportConfig));
+    }
     if !state.validating {
         validate_btn = validate_btn.on_press(Message::Tools(ToolsMessage::ValidateConfig));
     }
+    if niri_connected && !state.checking_effective {
+        effective_btn = effective_btn.on_press(Message::Tools(ToolsMessage::CheckConfigEffective));
+    }
 
     let refresh_all_disabled = !niri_connected;
     let mut refresh_windows_btn = button(
@@ -199,28 +279,10 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
             refresh_version_btn.on_press(Message::Tools(ToolsMessage::RefreshVersion));
     }
 
-    // Validation result
-    let validation_element: Element<'_, Message> = if let Some(result) = &state.validation_result {
-        let (result_text, color) = match result {
-            Ok(msg) => (msg.clone(), neon::SECONDARY),
-            Err(msg) => (msg.clone(), neon::ERROR),
-        };
-        container(text(result_text).size(11).color(color))
-            .padding([8, 12])
-            .width(Length::Fill)
-            .style(move |_: &iced::Theme| container::Style {
-                background: Some(iced::Background::Color(iced::Color { a: 0.08, ..color })),
-                border: iced::Border {
-                    radius: 8.0.into(),
-                    color: iced::Color { a: 0.2, ..color },
-                    width: 1.0,
-                },
-                ..Default::default()
-            })
-            .into()
-    } else {
-        Space::new().into()
-    };
+    // Validation and effectiveness results
+    let validation_element =
+        validation_panel(state.validation_result.as_ref(), &state.validation_targets);
+    let effective_element = result_banner(state.effective_result.as_ref());
 
     let error_element: Element<'_, Message> = if let Some(error) = &state.last_error {
         container(
@@ -276,7 +338,13 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
                     column![
                         row![reload_btn, validate_btn].spacing(8),
                         Space::new().height(6),
-                        row![consolidate_btn].spacing(8),
+                        row![effective_btn, consolidate_btn].spacing(8),
+                        Space::new().height(6),
+                        row![paste_config_btn, import_file_btn].spacing(8),
+                        Space::new().height(6),
+                        row![effective_config_btn, export_btn].spacing(8),
+                        Space::new().height(6),
+                        lint_btn,
                     ]
                     .spacing(0)
                 )
@@ -284,6 +352,10 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
                 .style(crate::theme::card_style),
                 Space::new().height(4),
                 validation_element,
+                effective_element,
+                result_banner(state.export_result.as_ref()),
+                lint_panel(state.lint_results.as_deref()),
+                effective_config_panel(state.effective_config.as_deref()),
                 error_element,
                 Space::new().height(16),
                 modal_section("◎", "REFRESH DATA", neon::PRIMARY),
@@ -297,6 +369,25 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
                 )
                 .padding(12)
                 .style(crate::theme::card_style),
+                Space::new().height(16),
+                modal_section("◐", "SCREENCAST PRIVACY", neon::TERTIARY),
+                container(
+                    column![
+                        info_text(
+                            "Hide these apps from screencasts with one window rule. Toggle again to turn it off."
+                        ),
+                        text_input("App-ids, comma-separated", &state.screencast_block_app_ids)
+                            .on_input(|value| Message::Tools(
+                                ToolsMessage::SetScreencastBlockAppIds(value)
+                            ))
+                            .size(12)
+                            .padding(8),
+                        screencast_btn,
+                    ]
+                    .spacing(8)
+                )
+                .padding(12)
+                .style(crate::theme::card_style),
             ]
             .spacing(4)
             .width(Length::FillPortion(1)),
@@ -353,11 +444,154 @@ pub fn view(state: &ToolsState, niri_connected: bool) -> Element<'_, Message> {
     ]
     .spacing(0);
 
-    scrollable(container(content).padding(8).width(Length::Fill))
+    super::screens::page_scrollable(container(content).padding(8).width(Length::Fill))
         .height(Length::Fill)
         .into()
 }
 
+/// Lint findings, each with a button to the page that fixes it
+fn lint_panel(lints: Option<&[Lint]>) -> Element<'_, Message> {
+    let Some(lints) = lints else {
+        return Space::new().into();
+    };
+    if lints.is_empty() {
+        return container(
+            text("No suggestions - the config looks good")
+                .size(11)
+                .color(neon::SECONDARY),
+        )
+        .padding([8, 12])
+        .into();
+    }
+
+    let mut list = column![].spacing(6);
+    for lint in lints {
+        let color = match lint.severity {
+            LintSeverity::Warning => neon::ERROR,
+            LintSeverity::Suggestion => neon::TERTIARY,
+        };
+        let page = crate::messages::Page::for_category(lint.category);
+        list = list.push(
+            row![
+                text("●").size(10).color(color),
+                text(&lint.message).size(11).width(Length::Fill),
+                button(text(format!("Open {}", page.name())).size(11))
+                    .padding([4, 8])
+                    .style(button::text)
+                    .on_press(Message::Tools(ToolsMessage::OpenLintTarget(lint.category))),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+    container(list)
+        .padding([8, 12])
+        .width(Length::Fill)
+        .style(crate::theme::card_style)
+        .into()
+}
+
+/// config.kdl as niri loads it, read-only
+fn effective_config_panel(effective: Option<&str>) -> Element<'_, Message> {
+    let Some(effective) = effective else {
+        return Space::new().into();
+    };
+    container(
+        scrollable(
+            container(text(effective).size(11).font(fonts::MONO_FONT))
+                .padding(12)
+                .width(Length::Fill),
+        )
+        .height(Length::Fixed(300.0)),
+    )
+    .width(Length::Fill)
+    .style(crate::theme::card_style)
+    .into()
+}
+
+/// Pass/fail banner for an action result, or nothing if the action hasn't run
+fn result_banner(result: Option<&Result<String, String>>) -> Element<'_, Message> {
+    let Some(result) = result else {
+        return Space::new().into();
+    };
+    match result {
+        Ok(msg) => banner(text(msg).size(11).color(neon::SECONDARY), neon::SECONDARY),
+        Err(msg) => banner(text(msg).size(11).color(neon::ERROR), neon::ERROR),
+    }
+}
+
+/// `niri validate` result; located errors are listed with a button to the
+/// page that writes their file
+fn validation_panel<'a>(
+    result: Option<&'a Result<ValidationReport, String>>,
+    targets: &[Option<SettingsCategory>],
+) -> Element<'a, Message> {
+    let report = match result {
+        None => return Space::new().into(),
+        Some(Err(e)) => {
+            let msg = format!("Could not validate: {}", e);
+            return banner(text(msg).size(11).color(neon::ERROR), neon::ERROR);
+        }
+        Some(Ok(report)) => report,
+    };
+    if report.ok || report.errors.is_empty() {
+        let color = if report.ok {
+            neon::SECONDARY
+        } else {
+            neon::ERROR
+        };
+        return banner(text(report.summary()).size(11).color(color), color);
+    }
+
+    let mut list = column![text("niri rejects the config")
+        .size(11)
+        .font(fonts::UI_FONT_SEMIBOLD)
+        .color(neon::ERROR)]
+    .spacing(6);
+    for (i, error) in report.errors.iter().enumerate() {
+        let mut entry = row![
+            text(format!("{}:{}", error.file, error.line))
+                .size(11)
+                .font(fonts::MONO_FONT)
+                .color(neon::ERROR),
+            text(&error.message).size(11).width(Length::Fill),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+        if let Some(category) = targets.get(i).copied().flatten() {
+            let page = crate::messages::Page::for_category(category);
+            entry = entry.push(
+                button(text(format!("Open {}", page.name())).size(11))
+                    .padding([4, 8])
+                    .style(button::text)
+                    .on_press(Message::Tools(ToolsMessage::OpenLintTarget(category))),
+            );
+        }
+        list = list.push(entry);
+    }
+    banner(list, neon::ERROR)
+}
+
+/// Tinted box around a result
+fn banner<'a>(
+    content: impl Into<Element<'a, Message>>,
+    color: iced::Color,
+) -> Element<'a, Message> {
+    container(content)
+        .padding([8, 12])
+        .width(Length::Fill)
+        .style(move |_: &iced::Theme| container::Style {
+            background: Some(iced::Background::Color(iced::Color { a: 0.08, ..color })),
+            border: iced::Border {
+                radius: 8.0.into(),
+                color: iced::Color { a: 0.2, ..color },
+                width: 1.0,
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
 fn modal_section<'a>(icon: &'a str, label: &'a str, accent: iced::Color) -> Element<'a, Message> {
     row![
         text(icon).size(14).color(accent),
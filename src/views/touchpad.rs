@@ -3,14 +3,14 @@
 use iced::widget::{column, container, row, scrollable, text_input, Space};
 use iced::{Alignment, Element, Length};
 
-use super::widgets::{picker_row, toggle_row};
+use super::widgets::{info_text, picker_row, toggle_row};
 use crate::config::models::TouchpadSettings;
 use crate::messages::{Message, TouchpadMessage};
 use crate::theme::{fonts, neon};
 use crate::types::{AccelProfile, ClickMethod, ScrollMethod, TapButtonMap};
 
-pub fn view(settings: &TouchpadSettings) -> Element<'_, Message> {
-    let content = column![
+pub fn view(settings: &TouchpadSettings) -> Element<'static, Message> {
+    let mut content = column![
         // ── ROW 1: TAP & BEHAVIOR | SCROLLING ──
         row![
             column![
@@ -197,6 +197,23 @@ pub fn view(settings: &TouchpadSettings) -> Element<'_, Message> {
     .spacing(0)
     .width(Length::Fill);
 
+    // ── PRESERVED OPTIONS ──
+    if !settings.extra_nodes.is_empty() {
+        let mut preserved = column![info_text(
+            "These options aren't editable here yet but are kept as-is when the touchpad is saved."
+        )]
+        .spacing(6);
+        for node in &settings.extra_nodes {
+            preserved = preserved.push(text(node.clone()).size(12).font(fonts::MONO_FONT));
+        }
+        content = content.push(modal_section("⋯", "PRESERVED OPTIONS", neon::OUTLINE));
+        content = content.push(
+            container(preserved)
+                .padding(8)
+                .style(crate::theme::card_style),
+        );
+    }
+
     scrollable(container(content).padding(8).width(Length::Fill))
         .height(Length::Fill)
         .into()
@@ -9,7 +9,7 @@ use crate::messages::{Message, TrackballMessage};
 use crate::theme::{fonts, neon};
 use crate::types::{AccelProfile, ScrollMethod};
 
-pub fn view(settings: &TrackballSettings) -> Element<'_, Message> {
+pub fn view(settings: &TrackballSettings) -> Element<'static, Message> {
     let content = column![
         // -- 2-COLUMN: SCROLLING | ACCELERATION --
         row![
@@ -9,7 +9,7 @@ use crate::messages::{Message, TrackpointMessage};
 use crate::theme::{fonts, neon};
 use crate::types::{AccelProfile, ScrollMethod};
 
-pub fn view(settings: &TrackpointSettings) -> Element<'_, Message> {
+pub fn view(settings: &TrackpointSettings) -> Element<'static, Message> {
     let content = column![
         // -- 2-COLUMN: SCROLLING | ACCELERATION --
         row![
@@ -0,0 +1,262 @@
+//! Keyboard focus for setting rows
+//!
+//! Wraps a row so it can take keyboard focus and be edited without a mouse:
+//! arrow keys step a slider, Home/End jump to its bounds and Space flips a
+//! toggle. Focus is kept in the widget tree like a text input's, so Tab and
+//! Shift+Tab (iced's `focus_next`/`focus_previous`) move between rows.
+
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::widget::operation::Focusable;
+use iced::advanced::widget::{tree, Operation, Tree};
+use iced::advanced::{mouse, renderer, Clipboard, Shell, Widget};
+use iced::keyboard::key::Named;
+use iced::keyboard::{self, Key};
+use iced::{Border, Color, Element, Event, Length, Rectangle, Size, Theme, Vector};
+
+/// New value for a focused slider after `key`, or `None` for other keys
+///
+/// Up/Right step up and Down/Left step down, clamped to the slider's range;
+/// Home and End jump to `min` and `max`.
+pub fn slider_key_value(key: &Key, value: f32, min: f32, max: f32, step: f32) -> Option<f32> {
+    let new_value = match key {
+        Key::Named(Named::ArrowUp | Named::ArrowRight) => value + step,
+        Key::Named(Named::ArrowDown | Named::ArrowLeft) => value - step,
+        Key::Named(Named::Home) => min,
+        Key::Named(Named::End) => max,
+        _ => return None,
+    };
+    Some(new_value.clamp(min, max))
+}
+
+/// Whether `key` flips a focused toggle
+pub fn is_toggle_key(key: &Key) -> bool {
+    matches!(key, Key::Named(Named::Space | Named::Enter))
+}
+
+/// Makes `content` focusable; while focused, key presses go to `on_key`
+///
+/// Keys `on_key` maps to a message are consumed; the rest pass through.
+pub fn keyboard_focus<'a, Message: 'a>(
+    content: impl Into<Element<'a, Message>>,
+    on_key: impl Fn(&Key) -> Option<Message> + 'a,
+) -> Element<'a, Message> {
+    Element::new(KeyboardFocus {
+        content: content.into(),
+        on_key: Box::new(on_key),
+    })
+}
+
+/// Maps a key press on the focused row to a message
+type OnKey<'a, Message> = Box<dyn Fn(&Key) -> Option<Message> + 'a>;
+
+struct KeyboardFocus<'a, Message> {
+    content: Element<'a, Message>,
+    on_key: OnKey<'a, Message>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    is_focused: bool,
+}
+
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+    }
+}
+
+impl<Message> Widget<Message, Theme, iced::Renderer> for KeyboardFocus<'_, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &iced::Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        operation.focusable(None, layout.bounds(), state);
+
+        self.content
+            .as_widget_mut()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &iced::Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            // Clicking a row focuses it; clicking anywhere else lets go
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.is_focused = cursor.is_over(layout.bounds());
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) if state.is_focused => {
+                if let Some(message) = (self.on_key)(key) {
+                    shell.publish(message);
+                    shell.capture_event();
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &iced::Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+
+        // Focus ring so keyboard users can see where they are
+        if tree.state.downcast_ref::<State>().is_focused {
+            <iced::Renderer as renderer::Renderer>::fill_quad(
+                renderer,
+                renderer::Quad {
+                    bounds: layout.bounds(),
+                    border: Border {
+                        color: theme.palette().primary,
+                        width: 1.5,
+                        radius: 6.0.into(),
+                    },
+                    ..Default::default()
+                },
+                Color::TRANSPARENT,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &iced::Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, iced::Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slider_key_value() {
+        let up = Key::Named(Named::ArrowUp);
+        assert_eq!(slider_key_value(&up, 5.0, 0.0, 10.0, 1.0), Some(6.0));
+        // Stepping past the end stops at max
+        assert_eq!(slider_key_value(&up, 9.5, 0.0, 10.0, 1.0), Some(10.0));
+        assert_eq!(
+            slider_key_value(&Key::Named(Named::ArrowLeft), 0.5, 0.0, 10.0, 1.0),
+            Some(0.0)
+        );
+        assert_eq!(
+            slider_key_value(&Key::Named(Named::Home), 5.0, 1.0, 10.0, 1.0),
+            Some(1.0)
+        );
+        assert_eq!(
+            slider_key_value(&Key::Named(Named::End), 5.0, 1.0, 10.0, 1.0),
+            Some(10.0)
+        );
+        assert_eq!(
+            slider_key_value(&Key::Character("a".into()), 5.0, 0.0, 10.0, 1.0),
+            None
+        );
+    }
+}
@@ -93,6 +93,23 @@ pub fn action_button<'a, M: Clone + 'a>(label: &'a str, on_press: M) -> Element<
         .into()
 }
 
+/// "Map to focused output" action for tablet and touch, disabled while niri isn't running
+pub fn focused_output_button<'a, M: Clone + 'a>(
+    niri_connected: bool,
+    on_press: M,
+) -> Element<'a, M> {
+    let label = if niri_connected {
+        "Map to focused output"
+    } else {
+        "Map to focused output (niri not running)"
+    };
+    button(text(label).size(12))
+        .on_press_maybe(niri_connected.then_some(on_press))
+        .padding([6, 12])
+        .style(action_button_style)
+        .into()
+}
+
 /// Style function for neutral action buttons.
 /// Uses theme background colors for subtle appearance.
 pub fn action_button_style(theme: &iced::Theme, status: button::Status) -> button::Style {
@@ -2,10 +2,13 @@
 //!
 //! Helper functions for creating common UI patterns:
 //! - Setting rows (toggle, slider, text input)
+//! - Keyboard focus for setting rows
 //! - Section headers
 //! - Color pickers
 //! - Expandable sections
 //! - List items
+//! - Reorder controls
+//! - Virtualized lists
 //! - etc.
 
 pub mod calibration_matrix;
@@ -14,10 +17,13 @@ pub mod expandable_section;
 pub mod file_path;
 pub mod gradient_picker;
 pub mod key_capture;
+pub mod keyboard_focus;
 pub mod list_detail;
 pub mod list_item;
 pub mod optional_picker;
+pub mod reorderable_list;
 pub mod setting_row;
+pub mod virtual_list;
 
 // Re-export commonly used helpers
 pub use calibration_matrix::{calibration_matrix, format_matrix_values, CalibrationMatrixMessage};
@@ -28,17 +34,20 @@ pub use gradient_picker::{gradient_picker, GradientPickerMessage};
 pub use key_capture::{
     format_key_combination, is_modifier_only, key_capture_row, KeyCaptureMessage, KeyCaptureState,
 };
+pub use keyboard_focus::keyboard_focus;
 pub use list_detail::{
     action_button, action_button_style, add_button, add_button_style, add_item_button, badge,
     delete_button, delete_button_style, empty_detail_placeholder, empty_list_placeholder,
-    list_detail_layout, list_item_style, list_panel_style, match_container_style, remove_button,
-    selection_indicator, BADGE_BEHAVIOR, BADGE_VISIBILITY,
+    focused_output_button, list_detail_layout, list_item_style, list_panel_style,
+    match_container_style, remove_button, selection_indicator, BADGE_BEHAVIOR, BADGE_VISIBILITY,
 };
 pub use list_item::list_item;
 pub use optional_picker::{optional_bool_picker, OptionalBool};
+pub use reorderable_list::{move_down, move_up, reorder_controls, ReorderMessage};
 pub use setting_row::{
     card, card_group, info_text, optional_picker_row, optional_slider_row, page_title, picker_row,
     section_header, slider_row, slider_row_int, slider_row_int_with_state, slider_row_with_state,
     spacer, status_pill, subsection_header, text_input_row, text_input_with_suggestions,
     toggle_row,
 };
+pub use virtual_list::{virtual_column, ListViewport};
@@ -0,0 +1,169 @@
+//! Reorderable list helpers - up/down controls for ordered lists
+//!
+//! Pages with ordered items (workspaces, startup commands, environment
+//! variables, rules) share the same move semantics: the view renders
+//! `reorder_controls` next to each item and the handler applies the emitted
+//! `ReorderMessage` to its vector.
+
+use iced::widget::{button, row, text};
+use iced::Element;
+
+/// Messages emitted by reorder controls, carrying the item's index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReorderMessage {
+    MoveUp(usize),
+    MoveDown(usize),
+}
+
+impl ReorderMessage {
+    /// Applies the move to `items`, returning whether anything changed
+    pub fn apply<T>(self, items: &mut [T]) -> bool {
+        match self {
+            ReorderMessage::MoveUp(index) => move_up(items, index),
+            ReorderMessage::MoveDown(index) => move_down(items, index),
+        }
+    }
+}
+
+/// Swaps the item at `index` with the one before it
+///
+/// No-op (returns false) for the first item or an out-of-range index.
+pub fn move_up<T>(items: &mut [T], index: usize) -> bool {
+    if index == 0 || index >= items.len() {
+        return false;
+    }
+    items.swap(index - 1, index);
+    true
+}
+
+/// Swaps the item at `index` with the one after it
+///
+/// No-op (returns false) for the last item or an out-of-range index.
+pub fn move_down<T>(items: &mut [T], index: usize) -> bool {
+    if index + 1 >= items.len() {
+        return false;
+    }
+    items.swap(index, index + 1);
+    true
+}
+
+/// Creates the ↑/↓ button pair for the item at `index` in a list of `len`
+///
+/// Buttons at the list boundaries are rendered dimmed and don't emit.
+///
+/// # Example
+/// ```rust,ignore
+/// reorder_controls(idx, commands.len(), |m| {
+///     Message::Startup(StartupMessage::Reorder(m))
+/// })
+/// ```
+pub fn reorder_controls<'a, Message: Clone + 'a>(
+    index: usize,
+    len: usize,
+    on_reorder: impl Fn(ReorderMessage) -> Message,
+) -> Element<'a, Message> {
+    let up = button(text("↑").size(14)).padding([4, 8]);
+    let up = if index > 0 {
+        up.on_press(on_reorder(ReorderMessage::MoveUp(index)))
+            .style(move_button_style)
+    } else {
+        up.style(disabled_button_style)
+    };
+
+    let down = button(text("↓").size(14)).padding([4, 8]);
+    let down = if index + 1 < len {
+        down.on_press(on_reorder(ReorderMessage::MoveDown(index)))
+            .style(move_button_style)
+    } else {
+        down.style(disabled_button_style)
+    };
+
+    row![up, down].spacing(4).into()
+}
+
+/// Style for move buttons - uses theme text color
+fn move_button_style(theme: &iced::Theme, status: button::Status) -> button::Style {
+    let text_color = theme.palette().text;
+    let bg = match status {
+        button::Status::Hovered => iced::Color {
+            a: 0.2,
+            ..text_color
+        },
+        _ => iced::Color::TRANSPARENT,
+    };
+    button::Style {
+        background: Some(iced::Background::Color(bg)),
+        text_color: iced::Color {
+            a: 0.7,
+            ..text_color
+        },
+        ..Default::default()
+    }
+}
+
+/// Style for move buttons at a list boundary
+fn disabled_button_style(theme: &iced::Theme, _status: button::Status) -> button::Style {
+    let text_color = theme.palette().text;
+    button::Style {
+        background: Some(iced::Background::Color(iced::Color::TRANSPARENT)),
+        text_color: iced::Color {
+            a: 0.3,
+            ..text_color
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_up_swaps_with_previous() {
+        let mut items = vec!['a', 'b', 'c'];
+        assert!(move_up(&mut items, 2));
+        assert_eq!(items, vec!['a', 'c', 'b']);
+    }
+
+    #[test]
+    fn test_move_up_at_start_is_noop() {
+        let mut items = vec!['a', 'b', 'c'];
+        assert!(!move_up(&mut items, 0));
+        assert_eq!(items, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_move_down_swaps_with_next() {
+        let mut items = vec!['a', 'b', 'c'];
+        assert!(move_down(&mut items, 0));
+        assert_eq!(items, vec!['b', 'a', 'c']);
+    }
+
+    #[test]
+    fn test_move_down_at_end_is_noop() {
+        let mut items = vec!['a', 'b', 'c'];
+        assert!(!move_down(&mut items, 2));
+        assert_eq!(items, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_out_of_range_is_noop() {
+        let mut items = vec!['a', 'b'];
+        assert!(!move_up(&mut items, 5));
+        assert!(!move_down(&mut items, 5));
+        assert_eq!(items, vec!['a', 'b']);
+
+        let mut empty: Vec<char> = Vec::new();
+        assert!(!move_up(&mut empty, 0));
+        assert!(!move_down(&mut empty, 0));
+    }
+
+    #[test]
+    fn test_reorder_message_apply() {
+        let mut items = vec![1, 2, 3];
+        assert!(ReorderMessage::MoveDown(1).apply(&mut items));
+        assert_eq!(items, vec![1, 3, 2]);
+        assert!(ReorderMessage::MoveUp(1).apply(&mut items));
+        assert_eq!(items, vec![3, 1, 2]);
+    }
+}
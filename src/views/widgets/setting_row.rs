@@ -3,12 +3,14 @@
 //! These helper functions create consistent, well-styled setting rows
 //! that are used throughout the application.
 
+use super::keyboard_focus::{is_toggle_key, keyboard_focus, slider_key_value};
 use crate::theme::{
     card_style, disabled_text_container, fonts, info_block_style, muted_text_container,
     secondary_text_container,
 };
 use iced::widget::{column, container, pick_list, row, slider, text, text_input, toggler, Space};
 use iced::{Alignment, Element, Length};
+use std::rc::Rc;
 
 /// Creates a toggle row with label and description
 ///
@@ -27,7 +29,9 @@ pub fn toggle_row<'a, Message: 'a>(
     value: bool,
     on_toggle: impl Fn(bool) -> Message + 'a,
 ) -> Element<'a, Message> {
-    row![
+    let on_toggle = Rc::new(on_toggle);
+    let on_switch = Rc::clone(&on_toggle);
+    let content = row![
         // Left side: Label and description
         column![
             text(label).size(15).font(fonts::UI_FONT_MEDIUM),
@@ -36,12 +40,17 @@ pub fn toggle_row<'a, Message: 'a>(
         .spacing(2)
         .width(Length::Fill),
         // Right side: Toggle switch
-        toggler(value).on_toggle(on_toggle).width(Length::Shrink),
+        toggler(value)
+            .on_toggle(move |v| on_switch(v))
+            .width(Length::Shrink),
     ]
     .spacing(20)
     .padding(12)
-    .align_y(Alignment::Center)
-    .into()
+    .align_y(Alignment::Center);
+
+    keyboard_focus(content, move |key| {
+        is_toggle_key(key).then(|| on_toggle(!value))
+    })
 }
 
 /// Creates a slider row with label, description, and value display
@@ -69,7 +78,9 @@ pub fn slider_row<'a, Message: Clone + 'a>(
     unit: &'a str,
     on_change: impl Fn(f32) -> Message + 'a,
 ) -> Element<'a, Message> {
-    column![
+    let on_change = Rc::new(on_change);
+    let on_slide = Rc::clone(&on_change);
+    let content = column![
         // Top: Label and current value
         row![
             text(label)
@@ -87,11 +98,12 @@ pub fn slider_row<'a, Message: Clone + 'a>(
         // Middle: Description
         container(text(description).size(11)).style(muted_text_container),
         // Bottom: Slider
-        slider(min..=max, value, on_change).step(0.1),
+        slider(min..=max, value, move |v| on_slide(v)).step(0.1),
     ]
     .spacing(6)
-    .padding(12)
-    .into()
+    .padding(12);
+
+    slider_keys(content, value, min, max, 0.1, move |v| on_change(v))
 }
 
 /// Creates a slider row with optional enabled/disabled state
@@ -122,6 +134,8 @@ pub fn slider_row_with_state<'a, Message: Clone + 'a>(
     enabled: bool,
     on_change: impl Fn(f32) -> Message + 'a,
 ) -> Element<'a, Message> {
+    let on_change = Rc::new(on_change);
+    let on_slide = Rc::clone(&on_change);
     let content = column![
         // Top: Label and current value
         row![
@@ -140,16 +154,17 @@ pub fn slider_row_with_state<'a, Message: Clone + 'a>(
         // Middle: Description
         container(text(description).size(11)).style(muted_text_container),
         // Bottom: Slider
-        slider(min..=max, value, on_change).step(0.1),
+        slider(min..=max, value, move |v| on_slide(v)).step(0.1),
     ]
     .spacing(6)
     .padding(12);
 
-    if enabled {
+    let content: Element<'a, Message> = if enabled {
         content.into()
     } else {
         container(content).style(disabled_text_container).into()
-    }
+    };
+    slider_keys(content, value, min, max, 0.1, move |v| on_change(v))
 }
 
 /// Creates a slider row with integer values
@@ -175,7 +190,9 @@ pub fn slider_row_int<'a, Message: Clone + 'a>(
     unit: &'a str,
     on_change: impl Fn(i32) -> Message + 'a,
 ) -> Element<'a, Message> {
-    column![
+    let on_change = Rc::new(on_change);
+    let on_slide = Rc::clone(&on_change);
+    let content = column![
         // Top: Label and current value
         row![
             text(label)
@@ -193,11 +210,19 @@ pub fn slider_row_int<'a, Message: Clone + 'a>(
         // Middle: Description
         container(text(description).size(11)).style(muted_text_container),
         // Bottom: Slider
-        slider(min..=max, value, on_change).step(1),
+        slider(min..=max, value, move |v| on_slide(v)).step(1),
     ]
     .spacing(6)
-    .padding(12)
-    .into()
+    .padding(12);
+
+    slider_keys(
+        content,
+        value as f32,
+        min as f32,
+        max as f32,
+        1.0,
+        move |v| on_change(v.round() as i32),
+    )
 }
 
 /// Creates an integer slider row with optional enabled/disabled state
@@ -228,6 +253,8 @@ pub fn slider_row_int_with_state<'a, Message: Clone + 'a>(
     enabled: bool,
     on_change: impl Fn(i32) -> Message + 'a,
 ) -> Element<'a, Message> {
+    let on_change = Rc::new(on_change);
+    let on_slide = Rc::clone(&on_change);
     let content = column![
         // Top: Label and current value
         row![
@@ -246,16 +273,38 @@ pub fn slider_row_int_with_state<'a, Message: Clone + 'a>(
         // Middle: Description
         container(text(description).size(11)).style(muted_text_container),
         // Bottom: Slider
-        slider(min..=max, value, on_change).step(1),
+        slider(min..=max, value, move |v| on_slide(v)).step(1),
     ]
     .spacing(6)
     .padding(12);
 
-    if enabled {
+    let content: Element<'a, Message> = if enabled {
         content.into()
     } else {
         container(content).style(disabled_text_container).into()
-    }
+    };
+    slider_keys(
+        content,
+        value as f32,
+        min as f32,
+        max as f32,
+        1.0,
+        move |v| on_change(v.round() as i32),
+    )
+}
+
+/// Lets the keyboard step a slider row once it has focus
+fn slider_keys<'a, Message: 'a>(
+    content: impl Into<Element<'a, Message>>,
+    value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    on_change: impl Fn(f32) -> Message + 'a,
+) -> Element<'a, Message> {
+    keyboard_focus(content, move |key| {
+        slider_key_value(key, value, min, max, step).map(&on_change)
+    })
 }
 
 /// Creates a text input row with label and description
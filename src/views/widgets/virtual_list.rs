@@ -0,0 +1,210 @@
+//! Virtualized lists - only the rows near the viewport are built
+//!
+//! Every row of a long list (a few hundred keybindings, a big rule set) used
+//! to be built and laid out each frame. With a fixed row height, the rows in
+//! view follow from the scroll offset alone, so the rest are replaced by empty
+//! space of the same height and the scrollbar behaves as if they were there.
+
+use std::ops::Range;
+
+use iced::widget::{column, container, scrollable, Space};
+use iced::{Element, Length};
+
+/// Rows built above and below the viewport, so fast scrolling and small
+/// errors in where the list starts don't show blank space
+pub const OVERSCAN_ROWS: usize = 2;
+
+/// Viewport height assumed until the first scroll event reports the real one
+const DEFAULT_VIEWPORT_HEIGHT: f32 = 1080.0;
+
+/// Scroll position and visible height of a scrollable, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListViewport {
+    pub offset: f32,
+    pub height: f32,
+}
+
+impl Default for ListViewport {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            height: DEFAULT_VIEWPORT_HEIGHT,
+        }
+    }
+}
+
+impl From<scrollable::Viewport> for ListViewport {
+    fn from(viewport: scrollable::Viewport) -> Self {
+        Self {
+            offset: viewport.absolute_offset().y,
+            height: viewport.bounds().height,
+        }
+    }
+}
+
+impl ListViewport {
+    /// The viewport as seen by a list that starts `top` pixels into the
+    /// scrolled content
+    pub fn below(self, top: f32) -> Self {
+        Self {
+            offset: self.offset - top,
+            ..self
+        }
+    }
+}
+
+/// Indices of the rows that are at least partly inside the viewport, plus
+/// [`OVERSCAN_ROWS`] on either side
+///
+/// `scroll` is clamped to the list the same way the scrollable clamps it, so
+/// a stale offset from before the list shrank (e.g. after filtering) still
+/// shows the last rows instead of nothing. A non-positive `row_height` makes
+/// every row visible.
+pub fn visible_range(scroll: f32, viewport: f32, row_height: f32, count: usize) -> Range<usize> {
+    if row_height <= 0.0 || count == 0 {
+        return 0..count;
+    }
+
+    let max_scroll = (count as f32 * row_height - viewport).max(0.0);
+    let scroll = scroll.clamp(0.0, max_scroll);
+
+    let first = (scroll / row_height).floor() as usize;
+    let last = ((scroll + viewport.max(0.0)) / row_height).ceil() as usize;
+
+    first.saturating_sub(OVERSCAN_ROWS).min(count)..(last + OVERSCAN_ROWS).min(count)
+}
+
+/// One entry of a virtualized column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    /// A row that gets built
+    Row(usize),
+    /// Empty space standing in for this many rows
+    Gap(usize),
+}
+
+/// Lays out `count` rows as built rows and gaps
+///
+/// `pinned` is always built even outside `range` - it's the row being edited,
+/// and dropping it would lose its widget state mid-edit. It keeps its place,
+/// with gaps on both sides.
+pub fn slots(range: Range<usize>, count: usize, pinned: Option<usize>) -> Vec<Slot> {
+    let mut built: Vec<usize> = range.filter(|&i| i < count).collect();
+    if let Some(pinned) = pinned.filter(|&p| p < count && !built.contains(&p)) {
+        built.push(pinned);
+        built.sort_unstable();
+    }
+
+    let mut slots = Vec::with_capacity(built.len() + 2);
+    let mut next = 0;
+    for index in built {
+        if index > next {
+            slots.push(Slot::Gap(index - next));
+        }
+        slots.push(Slot::Row(index));
+        next = index + 1;
+    }
+    if count > next {
+        slots.push(Slot::Gap(count - next));
+    }
+    slots
+}
+
+/// A column of `count` fixed-height rows where only the visible ones (and
+/// `pinned`) are built by `view_row`
+///
+/// `row_height` is the distance from one row to the next; a row shorter than
+/// that leaves the difference as spacing below it.
+pub fn virtual_column<'a, Message: 'a>(
+    count: usize,
+    row_height: f32,
+    viewport: ListViewport,
+    pinned: Option<usize>,
+    view_row: impl Fn(usize) -> Element<'a, Message>,
+) -> Element<'a, Message> {
+    let range = visible_range(viewport.offset, viewport.height, row_height, count);
+
+    let mut list = column![].width(Length::Fill);
+    for slot in slots(range, count, pinned) {
+        list = list.push(match slot {
+            Slot::Row(index) => container(view_row(index))
+                .width(Length::Fill)
+                .height(Length::Fixed(row_height))
+                .into(),
+            Slot::Gap(rows) => Element::from(Space::new().height(rows as f32 * row_height)),
+        });
+    }
+    list.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_range_at_top() {
+        // 10 rows fit, plus the overscan below
+        assert_eq!(visible_range(0.0, 500.0, 50.0, 100), 0..12);
+    }
+
+    #[test]
+    fn test_visible_range_partial_rows_and_overscan() {
+        // Rows 4 (half hidden) through 14 (half shown) intersect the viewport
+        assert_eq!(visible_range(225.0, 500.0, 50.0, 100), 2..17);
+    }
+
+    #[test]
+    fn test_visible_range_clamps_to_list_end() {
+        assert_eq!(visible_range(4_500.0, 500.0, 50.0, 100), 88..100);
+        // Offset left over from before the list shrank
+        assert_eq!(visible_range(4_500.0, 500.0, 50.0, 20), 8..20);
+        assert_eq!(visible_range(-30.0, 500.0, 50.0, 100), 0..12);
+    }
+
+    #[test]
+    fn test_visible_range_short_and_degenerate_lists() {
+        assert_eq!(visible_range(0.0, 500.0, 50.0, 3), 0..3);
+        assert_eq!(visible_range(100.0, 500.0, 50.0, 0), 0..0);
+        assert_eq!(visible_range(100.0, 500.0, 0.0, 7), 0..7);
+    }
+
+    #[test]
+    fn test_slots_fill_around_visible_rows() {
+        assert_eq!(
+            slots(3..5, 8, None),
+            vec![Slot::Gap(3), Slot::Row(3), Slot::Row(4), Slot::Gap(3)]
+        );
+        assert_eq!(slots(0..2, 2, None), vec![Slot::Row(0), Slot::Row(1)]);
+    }
+
+    #[test]
+    fn test_pinned_row_outside_viewport_is_built_in_place() {
+        let range = visible_range(0.0, 500.0, 50.0, 100);
+        assert_eq!(range, 0..12);
+
+        let slots = slots(range, 100, Some(60));
+        assert_eq!(slots[12], Slot::Gap(48));
+        assert_eq!(slots[13], Slot::Row(60));
+        assert_eq!(slots[14], Slot::Gap(39));
+
+        // Gaps and rows still add up to the whole list
+        let total: usize = slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Row(_) => 1,
+                Slot::Gap(rows) => *rows,
+            })
+            .sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn test_pinned_row_inside_viewport_is_not_duplicated() {
+        assert_eq!(
+            slots(0..2, 4, Some(1)),
+            vec![Slot::Row(0), Slot::Row(1), Slot::Gap(2)]
+        );
+        // Out-of-range pins are ignored
+        assert_eq!(slots(0..2, 2, Some(9)), vec![Slot::Row(0), Slot::Row(1)]);
+    }
+}
@@ -9,14 +9,20 @@ use std::collections::HashMap;
 
 use super::widgets::*;
 use crate::config::models::{
-    DefaultColumnDisplay, FloatingPosition, OpenBehavior, PositionRelativeTo, WindowRule,
-    WindowRulesSettings,
+    ColumnWidthType, DefaultColumnDisplay, FloatingPosition, OpenBehavior, PositionRelativeTo,
+    WindowRule, WindowRulesSettings,
 };
+use crate::constants::{COLUMN_FIXED_MAX, COLUMN_FIXED_MIN};
 use crate::messages::{Message, RulesFilter, WindowRulesMessage};
 use crate::theme::{fonts, neon};
 use crate::types::{Color as NiriColor, ColorOrGradient};
 
 const RULE_CARD_HEIGHT: f32 = 320.0;
+const RULE_GRID_COLUMNS: usize = 3;
+const RULE_GRID_SPACING: f32 = 12.0;
+/// Roughly where the grid starts in the scrolled content (search bar plus
+/// spacer); the virtual list's overscan absorbs the difference
+const RULE_GRID_TOP: f32 = 68.0;
 const RULE_CARD_SECTION_HEIGHT: f32 = 68.0;
 const MAX_VISIBLE_SUMMARY_PILLS: usize = 2;
 
@@ -27,9 +33,8 @@ pub fn view<'a>(
     settings: &'a WindowRulesSettings,
     search: &'a str,
     filter: RulesFilter,
-    _sections_expanded: &'a HashMap<(u32, String), bool>,
-    _regex_errors: &'a HashMap<(u32, String), String>,
-    _available_workspaces: &'a [String],
+    viewport: ListViewport,
+    editing: Option<u32>,
 ) -> Element<'a, Message> {
     let search_owned = search.to_string();
 
@@ -103,27 +108,28 @@ pub fn view<'a>(
     if filtered_rules.is_empty() {
         content = content.push(empty_state(search, filter));
     } else {
-        // Distribute cards across 3 columns (round-robin)
-        let mut col1_items: Vec<Element<'a, Message>> = Vec::new();
-        let mut col2_items: Vec<Element<'a, Message>> = Vec::new();
-        let mut col3_items: Vec<Element<'a, Message>> = Vec::new();
-
-        for (i, rule) in filtered_rules.iter().enumerate() {
-            let card = rule_card(rule);
-            match i % 3 {
-                0 => col1_items.push(card),
-                1 => col2_items.push(card),
-                _ => col3_items.push(card),
-            }
-        }
-
-        let grid = row![
-            column(col1_items).spacing(12).width(Length::FillPortion(1)),
-            column(col2_items).spacing(12).width(Length::FillPortion(1)),
-            column(col3_items).spacing(12).width(Length::FillPortion(1)),
-        ]
-        .spacing(12)
-        .align_y(Alignment::Start);
+        // Three cards per grid row; only the rows near the viewport are built,
+        // plus the one holding the rule open in the editor
+        let pinned = editing
+            .and_then(|id| filtered_rules.iter().position(|rule| rule.id == id))
+            .map(|i| i / RULE_GRID_COLUMNS);
+        let grid = virtual_column(
+            filtered_rules.len().div_ceil(RULE_GRID_COLUMNS),
+            RULE_CARD_HEIGHT + RULE_GRID_SPACING,
+            viewport.below(RULE_GRID_TOP),
+            pinned,
+            |grid_row| {
+                let first = grid_row * RULE_GRID_COLUMNS;
+                let mut cards = row![].spacing(RULE_GRID_SPACING);
+                for i in first..first + RULE_GRID_COLUMNS {
+                    cards = cards.push(match filtered_rules.get(i) {
+                        Some(rule) => container(rule_card(rule)).width(Length::FillPortion(1)),
+                        None => container(Space::new()).width(Length::FillPortion(1)),
+                    });
+                }
+                cards.into()
+            },
+        );
 
         content = content.push(grid);
     }
@@ -179,13 +185,8 @@ fn rule_card(rule: &WindowRule) -> Element<'_, Message> {
         ..Default::default()
     });
 
-    // App ID subtitle (owned string for text widget)
-    let app_id_display: String = rule
-        .matches
-        .first()
-        .and_then(|m| m.app_id.as_ref())
-        .map(|id| format!("app-id: {}", id))
-        .unwrap_or_else(|| "any window".to_string());
+    // Match subtitle (owned string for text widget)
+    let match_display = rule.match_summary();
 
     // Matcher pills
     let mut matcher_pills: Vec<(String, iced::Color)> = Vec::new();
@@ -231,9 +232,7 @@ fn rule_card(rule: &WindowRule) -> Element<'_, Message> {
             avatar,
             column![
                 text(&rule.name).size(15).font(fonts::UI_FONT_SEMIBOLD),
-                text(app_id_display)
-                    .size(11)
-                    .color(neon::ON_SURFACE_VARIANT),
+                text(match_display).size(11).color(neon::ON_SURFACE_VARIANT),
             ]
             .spacing(2)
             .width(Length::Fill),
@@ -325,6 +324,7 @@ pub fn editor_modal<'a>(
     _sections_expanded: &'a HashMap<(u32, String), bool>,
     regex_errors: &'a HashMap<(u32, String), String>,
     available_workspaces: &'a [String],
+    available_outputs: &'a [String],
 ) -> Element<'a, Message> {
     let id = rule.id;
 
@@ -535,20 +535,25 @@ pub fn editor_modal<'a>(
                 .padding(8),
             );
 
-            if rule.matches.len() > 1 {
-                match_content = match_content.push(
-                    button(
-                        text(format!("Remove Match {}", match_idx + 1))
-                            .size(11)
-                            .color(neon::ERROR),
-                    )
-                    .on_press(Message::WindowRules(WindowRulesMessage::RemoveMatch(
-                        id, match_idx,
-                    )))
-                    .padding([4, 8])
-                    .style(ghost_button_style),
-                );
-            }
+            match_content = match_content.push(
+                button(
+                    text(format!("Remove Match {}", match_idx + 1))
+                        .size(11)
+                        .color(neon::ERROR),
+                )
+                .on_press(Message::WindowRules(WindowRulesMessage::RemoveMatch(
+                    id, match_idx,
+                )))
+                .padding([4, 8])
+                .style(ghost_button_style),
+            );
+        }
+        if rule.matches.is_empty() {
+            match_content = match_content.push(
+                text("No match criteria - this rule applies to every window")
+                    .size(11)
+                    .color(neon::OUTLINE_VARIANT),
+            );
         }
         match_content = match_content.push(
             button(text("+ Add Match Criteria").size(12).color(neon::SECONDARY))
@@ -574,51 +579,120 @@ pub fn editor_modal<'a>(
             let app_id_value = exclude_match.app_id.clone().unwrap_or_default();
             let title_value = exclude_match.title.clone().unwrap_or_default();
 
+            let app_id_error = regex_errors.get(&(id, format!("exclude_app_id_{}", idx)));
+            let title_error = regex_errors.get(&(id, format!("exclude_title_{}", idx)));
+
+            let mut app_col = column![
+                text("APP ID (REGEX)")
+                    .size(10)
+                    .font(fonts::UI_FONT_SEMIBOLD)
+                    .color(neon::OUTLINE_VARIANT),
+                text_input("e.g., ^firefox$", &app_id_value)
+                    .on_input(move |value| Message::WindowRules(
+                        WindowRulesMessage::SetExcludeAppId(
+                            id,
+                            idx,
+                            if value.is_empty() { None } else { Some(value) }
+                        )
+                    ))
+                    .padding(12),
+            ]
+            .spacing(6)
+            .width(Length::FillPortion(1));
+            if let Some(error) = app_id_error {
+                app_col = app_col.push(text(error).size(11).color(neon::ERROR));
+            }
+
+            let mut title_col = column![
+                text("WINDOW TITLE (REGEX)")
+                    .size(10)
+                    .font(fonts::UI_FONT_SEMIBOLD)
+                    .color(neon::OUTLINE_VARIANT),
+                text_input("e.g., ^Picture-in-Picture$", &title_value)
+                    .on_input(move |value| Message::WindowRules(
+                        WindowRulesMessage::SetExcludeTitle(
+                            id,
+                            idx,
+                            if value.is_empty() { None } else { Some(value) }
+                        )
+                    ))
+                    .padding(12),
+            ]
+            .spacing(6)
+            .width(Length::FillPortion(1));
+            if let Some(error) = title_error {
+                title_col = title_col.push(text(error).size(11).color(neon::ERROR));
+            }
+
+            exclude_content = exclude_content.push(row![app_col, title_col].spacing(12));
+
             exclude_content = exclude_content.push(
-                row![
+                container(
                     column![
-                        text("APP ID")
-                            .size(10)
-                            .font(fonts::UI_FONT_SEMIBOLD)
-                            .color(neon::OUTLINE_VARIANT),
-                        text_input("e.g., firefox", &app_id_value)
-                            .on_input(move |value| Message::WindowRules(
-                                WindowRulesMessage::SetExcludeAppId(
-                                    id,
-                                    idx,
-                                    if value.is_empty() { None } else { Some(value) }
+                        row![
+                            compact_bool("Floating", exclude_match.is_floating, move |v| {
+                                Message::WindowRules(WindowRulesMessage::SetExcludeIsFloating(
+                                    id, idx, v,
+                                ))
+                            }),
+                            compact_bool("Focused", exclude_match.is_focused, move |v| {
+                                Message::WindowRules(WindowRulesMessage::SetExcludeIsFocused(
+                                    id, idx, v,
+                                ))
+                            }),
+                            compact_bool("Active", exclude_match.is_active, move |v| {
+                                Message::WindowRules(WindowRulesMessage::SetExcludeIsActive(
+                                    id, idx, v,
+                                ))
+                            }),
+                            compact_bool("Urgent", exclude_match.is_urgent, move |v| {
+                                Message::WindowRules(WindowRulesMessage::SetExcludeIsUrgent(
+                                    id, idx, v,
+                                ))
+                            }),
+                        ]
+                        .spacing(8)
+                        .wrap(),
+                        row![
+                            compact_bool(
+                                "Active in column",
+                                exclude_match.is_active_in_column,
+                                move |v| Message::WindowRules(
+                                    WindowRulesMessage::SetExcludeIsActiveInColumn(id, idx, v)
                                 )
-                            ))
-                            .padding(10),
-                    ]
-                    .spacing(4)
-                    .width(Length::FillPortion(1)),
-                    column![
-                        text("TITLE")
-                            .size(10)
-                            .font(fonts::UI_FONT_SEMIBOLD)
-                            .color(neon::OUTLINE_VARIANT),
-                        text_input("e.g., .*YouTube.*", &title_value)
-                            .on_input(move |value| Message::WindowRules(
-                                WindowRulesMessage::SetExcludeTitle(
-                                    id,
-                                    idx,
-                                    if value.is_empty() { None } else { Some(value) }
+                            ),
+                            compact_bool(
+                                "Cast target",
+                                exclude_match.is_window_cast_target,
+                                move |v| Message::WindowRules(
+                                    WindowRulesMessage::SetExcludeIsWindowCastTarget(id, idx, v)
                                 )
-                            ))
-                            .padding(10),
+                            ),
+                            compact_bool("At startup", exclude_match.at_startup, move |v| {
+                                Message::WindowRules(WindowRulesMessage::SetExcludeAtStartup(
+                                    id, idx, v,
+                                ))
+                            }),
+                        ]
+                        .spacing(8)
+                        .wrap(),
                     ]
-                    .spacing(4)
-                    .width(Length::FillPortion(1)),
-                    button(text("✕").size(12).color(neon::ERROR))
-                        .on_press(Message::WindowRules(WindowRulesMessage::RemoveExclude(
-                            id, idx
-                        )))
-                        .padding([8, 10])
-                        .style(ghost_button_style),
-                ]
-                .spacing(8)
-                .align_y(Alignment::End),
+                    .spacing(4),
+                )
+                .padding(8),
+            );
+
+            exclude_content = exclude_content.push(
+                button(
+                    text(format!("Remove Exclude {}", idx + 1))
+                        .size(11)
+                        .color(neon::ERROR),
+                )
+                .on_press(Message::WindowRules(WindowRulesMessage::RemoveExclude(
+                    id, idx,
+                )))
+                .padding([4, 8])
+                .style(ghost_button_style),
             );
         }
         exclude_content = exclude_content.push(
@@ -682,10 +756,11 @@ pub fn editor_modal<'a>(
             .width(Length::FillPortion(1)),
             column![
                 modal_section_header("▦", "PLACEMENT", neon::PRIMARY),
-                text_input_row(
+                text_input_with_suggestions(
                     "Open on output",
-                    "Output name (e.g., HDMI-1)",
+                    "Output name (e.g., HDMI-A-1)",
                     rule.open_on_output.as_deref().unwrap_or(""),
+                    available_outputs,
                     move |value| Message::WindowRules(WindowRulesMessage::SetOpenOnOutput(
                         id,
                         if value.is_empty() { None } else { Some(value) }
@@ -712,6 +787,47 @@ pub fn editor_modal<'a>(
     editor = editor.push(Space::new().height(20));
 
     // ── ROW 2: SIZE & DIMENSIONS | VISUAL STYLING ──
+    let column_width_type = if rule.default_column_width_fixed.is_some() {
+        ColumnWidthType::Fixed
+    } else {
+        ColumnWidthType::Proportion
+    };
+    let column_width_control = match rule.default_column_width_fixed {
+        Some(fixed) => styled_slider_int(
+            "COLUMN WIDTH (PX)",
+            &format!("{}", fixed),
+            move |s| {
+                s.parse::<i32>().ok().map(|v| {
+                    Message::WindowRules(WindowRulesMessage::SetDefaultColumnWidthFixed(
+                        id,
+                        Some(v.clamp(COLUMN_FIXED_MIN as i32, COLUMN_FIXED_MAX as i32)),
+                    ))
+                })
+            },
+            COLUMN_FIXED_MIN as i32..=COLUMN_FIXED_MAX as i32,
+            fixed,
+            move |v| {
+                Message::WindowRules(WindowRulesMessage::SetDefaultColumnWidthFixed(id, Some(v)))
+            },
+        ),
+        None => styled_slider(
+            "COLUMN WIDTH",
+            &format!("{:.0}%", rule.default_column_width.unwrap_or(0.5) * 100.0),
+            move |s| {
+                s.replace('%', "").parse::<f32>().ok().map(|v| {
+                    Message::WindowRules(WindowRulesMessage::SetDefaultColumnWidth(
+                        id,
+                        Some((v / 100.0).clamp(0.1, 1.0)),
+                    ))
+                })
+            },
+            0.1..=1.0,
+            rule.default_column_width.unwrap_or(0.5),
+            0.01,
+            move |v| Message::WindowRules(WindowRulesMessage::SetDefaultColumnWidth(id, Some(v))),
+        ),
+    };
+
     editor = editor.push(
         row![
             column![
@@ -728,24 +844,17 @@ pub fn editor_modal<'a>(
                     0.01,
                     move |v| Message::WindowRules(WindowRulesMessage::SetOpacity(id, Some(v))),
                 ),
-                row![
-                    styled_slider(
-                        "COLUMN WIDTH",
-                        &format!("{:.0}%", rule.default_column_width.unwrap_or(0.5) * 100.0),
-                        move |s| s.replace('%', "").parse::<f32>().ok().map(|v| {
-                            Message::WindowRules(WindowRulesMessage::SetDefaultColumnWidth(
-                                id,
-                                Some((v / 100.0).clamp(0.1, 1.0)),
-                            ))
-                        }),
-                        0.1..=1.0,
-                        rule.default_column_width.unwrap_or(0.5),
-                        0.01,
-                        move |v| Message::WindowRules(WindowRulesMessage::SetDefaultColumnWidth(
-                            id,
-                            Some(v)
-                        )),
+                picker_row(
+                    "Column width",
+                    "Share of the screen or a fixed size in pixels",
+                    ColumnWidthType::all(),
+                    Some(column_width_type),
+                    move |value| Message::WindowRules(
+                        WindowRulesMessage::SetDefaultColumnWidthType(id, value)
                     ),
+                ),
+                row![
+                    column_width_control,
                     styled_slider(
                         "WINDOW HEIGHT",
                         &format!("{:.0}%", rule.default_window_height.unwrap_or(0.5) * 100.0),
@@ -928,43 +1037,51 @@ pub fn editor_modal<'a>(
                     move |v| Message::WindowRules(WindowRulesMessage::SetBorderWidth(id, Some(v))),
                 ),
                 // Focus ring colors
-                color_picker_row(
+                gradient_picker(
                     "Focus ring active",
-                    "Active color override",
-                    &color_or_gradient_to_niri(rule.focus_ring_active.as_ref()),
-                    move |hex| Message::WindowRules(WindowRulesMessage::SetFocusRingActive(
-                        id,
-                        Some(ColorOrGradient::Color(hex_to_niri_color(&hex)))
+                    "Active color or gradient override",
+                    &rule_color(rule.focus_ring_active.as_ref()),
+                    move |msg| Message::WindowRules(WindowRulesMessage::FocusRingActiveColor(
+                        id, msg
                     )),
                 ),
-                color_picker_row(
+                gradient_picker(
                     "Focus ring inactive",
-                    "Inactive color override",
-                    &color_or_gradient_to_niri(rule.focus_ring_inactive.as_ref()),
-                    move |hex| Message::WindowRules(WindowRulesMessage::SetFocusRingInactive(
-                        id,
-                        Some(ColorOrGradient::Color(hex_to_niri_color(&hex)))
+                    "Inactive color or gradient override",
+                    &rule_color(rule.focus_ring_inactive.as_ref()),
+                    move |msg| Message::WindowRules(WindowRulesMessage::FocusRingInactiveColor(
+                        id, msg
+                    )),
+                ),
+                gradient_picker(
+                    "Focus ring urgent",
+                    "Urgent color or gradient override",
+                    &rule_color(rule.focus_ring_urgent.as_ref()),
+                    move |msg| Message::WindowRules(WindowRulesMessage::FocusRingUrgentColor(
+                        id, msg
                     )),
                 ),
                 // Border colors
-                color_picker_row(
+                gradient_picker(
                     "Border active",
-                    "Active color override",
-                    &color_or_gradient_to_niri(rule.border_active.as_ref()),
-                    move |hex| Message::WindowRules(WindowRulesMessage::SetBorderActive(
-                        id,
-                        Some(ColorOrGradient::Color(hex_to_niri_color(&hex)))
-                    )),
+                    "Active color or gradient override",
+                    &rule_color(rule.border_active.as_ref()),
+                    move |msg| Message::WindowRules(WindowRulesMessage::BorderActiveColor(id, msg)),
                 ),
-                color_picker_row(
+                gradient_picker(
                     "Border inactive",
-                    "Inactive color override",
-                    &color_or_gradient_to_niri(rule.border_inactive.as_ref()),
-                    move |hex| Message::WindowRules(WindowRulesMessage::SetBorderInactive(
-                        id,
-                        Some(ColorOrGradient::Color(hex_to_niri_color(&hex)))
+                    "Inactive color or gradient override",
+                    &rule_color(rule.border_inactive.as_ref()),
+                    move |msg| Message::WindowRules(WindowRulesMessage::BorderInactiveColor(
+                        id, msg
                     )),
                 ),
+                gradient_picker(
+                    "Border urgent",
+                    "Urgent color or gradient override",
+                    &rule_color(rule.border_urgent.as_ref()),
+                    move |msg| Message::WindowRules(WindowRulesMessage::BorderUrgentColor(id, msg)),
+                ),
             ]
             .spacing(4)
             .width(Length::FillPortion(1)),
@@ -1225,8 +1342,13 @@ fn rule_to_kdl_preview(rule: &WindowRule) -> String {
     if rule.block_out_from_screencast {
         lines.push("    block-out-from \"screencast\"".to_string());
     }
-    if let Some(width) = rule.default_column_width {
-        lines.push(format!("    default-column-width {:.2}", width));
+    if let Some(fixed) = rule.default_column_width_fixed {
+        lines.push(format!("    default-column-width {{ fixed {}; }}", fixed));
+    } else if let Some(width) = rule.default_column_width {
+        lines.push(format!(
+            "    default-column-width {{ proportion {:.2}; }}",
+            width
+        ));
     }
     if let Some(height) = rule.default_window_height {
         lines.push(format!("    default-window-height {:.2}", height));
@@ -1720,39 +1842,18 @@ fn truncate_str(s: &str, max_len: usize) -> String {
     }
 }
 
-/// Extract NiriColor from ColorOrGradient (uses first color of gradient)
-fn color_or_gradient_to_niri(cog: Option<&ColorOrGradient>) -> NiriColor {
-    match cog {
-        Some(ColorOrGradient::Color(c)) => c.clone(),
-        Some(ColorOrGradient::Gradient(g)) => g.from.clone(),
-        None => NiriColor {
-            r: 128,
-            g: 128,
-            b: 128,
-            a: 255,
-        },
-    }
-}
+/// Color the editor shows for a color override that isn't set
+pub const UNSET_RULE_COLOR: NiriColor = NiriColor {
+    r: 128,
+    g: 128,
+    b: 128,
+    a: 255,
+};
 
-/// Parse hex string to NiriColor
-fn hex_to_niri_color(hex: &str) -> NiriColor {
-    let hex = hex.trim_start_matches('#');
-    let (r, g, b, a) = match hex.len() {
-        6 => (
-            u8::from_str_radix(&hex[0..2], 16).unwrap_or(255),
-            u8::from_str_radix(&hex[2..4], 16).unwrap_or(255),
-            u8::from_str_radix(&hex[4..6], 16).unwrap_or(255),
-            255,
-        ),
-        8 => (
-            u8::from_str_radix(&hex[0..2], 16).unwrap_or(255),
-            u8::from_str_radix(&hex[2..4], 16).unwrap_or(255),
-            u8::from_str_radix(&hex[4..6], 16).unwrap_or(255),
-            u8::from_str_radix(&hex[6..8], 16).unwrap_or(255),
-        ),
-        _ => (255, 255, 255, 255),
-    };
-    NiriColor { r, g, b, a }
+/// A rule's color override, or the placeholder when it isn't set
+fn rule_color(cog: Option<&ColorOrGradient>) -> ColorOrGradient {
+    cog.cloned()
+        .unwrap_or(ColorOrGradient::Color(UNSET_RULE_COLOR))
 }
 
 // Implement Display for OpenBehavior to use with picker_row
@@ -1769,11 +1870,6 @@ impl std::fmt::Display for OpenBehavior {
 
 impl OpenBehavior {
     pub fn all() -> &'static [OpenBehavior] {
-        &[
-            OpenBehavior::Normal,
-            OpenBehavior::Maximized,
-            OpenBehavior::Fullscreen,
-            OpenBehavior::Floating,
-        ]
+        Self::ALL
     }
 }
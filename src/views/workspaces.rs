@@ -50,32 +50,9 @@ pub fn view(settings: &WorkspacesSettings) -> Element<'static, Message> {
                         container(text(format!("Workspace {}", idx + 1)).size(14))
                             .style(muted_text_container),
                         row![
-                            // Move up button
-                            if idx > 0 {
-                                button(text("↑").size(14))
-                                    .on_press(Message::Workspaces(
-                                        WorkspacesMessage::MoveWorkspaceUp(idx),
-                                    ))
-                                    .padding([4, 8])
-                                    .style(move_button_style)
-                            } else {
-                                button(text("↑").size(14))
-                                    .padding([4, 8])
-                                    .style(disabled_button_style)
-                            },
-                            // Move down button
-                            if idx < ws_len - 1 {
-                                button(text("↓").size(14))
-                                    .on_press(Message::Workspaces(
-                                        WorkspacesMessage::MoveWorkspaceDown(idx),
-                                    ))
-                                    .padding([4, 8])
-                                    .style(move_button_style)
-                            } else {
-                                button(text("↓").size(14))
-                                    .padding([4, 8])
-                                    .style(disabled_button_style)
-                            },
+                            reorder_controls(idx, ws_len, |m| Message::Workspaces(
+                                WorkspacesMessage::Reorder(m)
+                            )),
                             // Delete button
                             button(text("×").size(16))
                                 .on_press(Message::Workspaces(WorkspacesMessage::RemoveWorkspace(
@@ -154,39 +131,6 @@ pub fn view(settings: &WorkspacesSettings) -> Element<'static, Message> {
         .into()
 }
 
-/// Style for move buttons - uses theme text color
-fn move_button_style(theme: &iced::Theme, status: button::Status) -> button::Style {
-    let text_color = theme.palette().text;
-    let bg = match status {
-        button::Status::Hovered => iced::Color {
-            a: 0.2,
-            ..text_color
-        },
-        _ => iced::Color::TRANSPARENT,
-    };
-    button::Style {
-        background: Some(iced::Background::Color(bg)),
-        text_color: iced::Color {
-            a: 0.7,
-            ..text_color
-        },
-        ..Default::default()
-    }
-}
-
-/// Style for disabled buttons
-fn disabled_button_style(theme: &iced::Theme, _status: button::Status) -> button::Style {
-    let text_color = theme.palette().text;
-    button::Style {
-        background: Some(iced::Background::Color(iced::Color::TRANSPARENT)),
-        text_color: iced::Color {
-            a: 0.3,
-            ..text_color
-        },
-        ..Default::default()
-    }
-}
-
 /// Style for delete buttons - uses theme danger color
 fn delete_button_style(theme: &iced::Theme, status: button::Status) -> button::Style {
     let danger = theme.palette().danger;
@@ -2,7 +2,10 @@
 //!
 //! Tests for importing settings from user's existing niri config.kdl
 
-use nirify::config::{import_from_niri_config, import_from_niri_config_with_result};
+use nirify::config::{
+    import_from_niri_config, import_from_niri_config_with_result, import_from_str, MergeStrategy,
+    Settings,
+};
 use std::fs;
 use tempfile::tempdir;
 
@@ -188,6 +191,80 @@ animations {
     assert!((settings.animations.slowdown - 2.5).abs() < 0.01);
 }
 
+#[test]
+fn test_import_partial_animation_takes_niri_defaults() {
+    use nirify::config::models::{AnimationType, EasingCurve};
+
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    fs::write(
+        &config,
+        r#"
+animations {
+    window-open {
+        duration-ms 200
+    }
+    workspace-switch {
+        spring stiffness=600
+    }
+    exit-confirmation-open-close {
+        spring damping-ratio=0.8
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    let anims = &settings.animations.per_animation;
+
+    // Missing curve takes niri's window-open curve, not the generic default
+    assert_eq!(anims.window_open.animation_type, AnimationType::Easing);
+    assert_eq!(anims.window_open.easing.duration_ms, 200);
+    assert_eq!(anims.window_open.easing.curve, EasingCurve::EaseOutExpo);
+
+    // Missing spring parameters take niri's values for that animation
+    assert_eq!(anims.workspace_switch.animation_type, AnimationType::Spring);
+    assert_eq!(anims.workspace_switch.spring.stiffness, 600);
+    assert_eq!(anims.workspace_switch.spring.damping_ratio, 1.0);
+    assert_eq!(anims.workspace_switch.spring.epsilon, 0.0001);
+
+    let exit = &anims.exit_confirmation_open_close;
+    assert_eq!(exit.spring.damping_ratio, 0.8);
+    assert_eq!(exit.spring.stiffness, 500);
+    assert_eq!(exit.spring.epsilon, 0.01);
+}
+
+#[test]
+fn test_import_gradient_attributes() {
+    use nirify::types::{ColorOrGradient, ColorSpace, GradientRelativeTo, HueInterpolation};
+
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    fs::write(
+        &config,
+        r##"
+layout {
+    focus-ring {
+        active-gradient from="#80c8ff" to="#bbddff" angle=45 relative-to="workspace-view" in="oklch longer hue"
+    }
+}
+"##,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    let ColorOrGradient::Gradient(gradient) = &settings.appearance.focus_ring_active else {
+        panic!("Expected a gradient");
+    };
+    assert_eq!(gradient.angle, 45);
+    assert_eq!(gradient.relative_to, GradientRelativeTo::WorkspaceView);
+    assert_eq!(gradient.color_space, ColorSpace::Oklch);
+    assert_eq!(gradient.hue_interpolation, Some(HueInterpolation::Longer));
+}
+
 #[test]
 fn test_import_input_keyboard() {
     let dir = tempdir().unwrap();
@@ -215,6 +292,33 @@ input {
     assert_eq!(settings.keyboard.repeat_rate, 30);
 }
 
+#[test]
+fn test_import_xkb_options_checklist() {
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    fs::write(
+        &config,
+        r#"
+input {
+    keyboard {
+        xkb {
+            options "grp:win_space_toggle,lv3:ralt_switch,compose:ralt"
+        }
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    assert_eq!(
+        settings.keyboard.checked_xkb_options(),
+        vec!["grp:win_space_toggle", "compose:ralt"]
+    );
+    assert_eq!(settings.keyboard.extra_xkb_options(), "lv3:ralt_switch");
+}
+
 #[test]
 fn test_import_input_touchpad() {
     let dir = tempdir().unwrap();
@@ -240,6 +344,42 @@ input {
     assert!((settings.touchpad.accel_speed - 0.3).abs() < 0.01);
 }
 
+#[test]
+fn test_import_pointer_device_scroll_options() {
+    use nirify::types::ScrollMethod;
+
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+    fs::write(
+        &config,
+        r#"
+input {
+    trackpoint {
+        scroll-method "on-button-down"
+        scroll-button 274
+        scroll-button-lock
+    }
+    trackball {
+        scroll-method "no-scroll"
+        scroll-button 275
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    assert_eq!(
+        settings.trackpoint.scroll_method,
+        ScrollMethod::OnButtonDown
+    );
+    assert_eq!(settings.trackpoint.scroll_button, Some(274));
+    assert!(settings.trackpoint.scroll_button_lock);
+    assert_eq!(settings.trackball.scroll_method, ScrollMethod::NoScroll);
+    assert_eq!(settings.trackball.scroll_button, Some(275));
+    assert!(!settings.trackball.scroll_button_lock);
+}
+
 #[test]
 fn test_import_output_settings() {
     let dir = tempdir().unwrap();
@@ -265,6 +405,90 @@ output "DP-1" {
     assert!((output.scale - 1.5).abs() < 0.01);
 }
 
+#[test]
+fn test_import_output_preserves_unknown_options() {
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    fs::write(
+        &config,
+        r##"
+output "DP-1" {
+    mode "2560x1440@144"
+    background-color "#000000"
+}
+"##,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    let output = &settings.outputs.outputs[0];
+    assert_eq!(output.mode, "2560x1440@144");
+    assert_eq!(output.extra_nodes, vec![r##"background-color "#000000""##]);
+}
+
+#[test]
+fn test_unknown_output_block_survives_editing_other_fields() {
+    use nirify::config::storage::generate_outputs_kdl;
+
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    // niri has no gamma settings; a block like this must still come back intact
+    fs::write(
+        &config,
+        r#"
+output "DP-1" {
+    scale 1
+    night-light {
+        temperature 4500
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let mut settings = import_from_niri_config(&config);
+    let imported = settings.outputs.outputs[0].extra_nodes.clone();
+    assert_eq!(imported.len(), 1);
+    assert!(imported[0].starts_with("night-light {"));
+
+    settings.outputs.outputs[0].scale = 2.0;
+    fs::write(&config, generate_outputs_kdl(&settings.outputs)).unwrap();
+
+    let reloaded = import_from_niri_config(&config);
+    let output = &reloaded.outputs.outputs[0];
+    assert!((output.scale - 2.0).abs() < 0.01);
+    assert_eq!(output.extra_nodes, imported);
+}
+
+#[test]
+fn test_import_flags_malformed_keybinding() {
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+    fs::write(
+        &config,
+        r#"
+binds {
+    Mod+Q { close-window; }
+    Hyper+T { spawn "alacritty"; }
+    Mod+1 { focus-workspace 1; }
+}
+"#,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    let bindings = &settings.keybindings.bindings;
+    assert_eq!(bindings.len(), 3);
+    assert!(bindings[0].parse_warning.is_none());
+    assert!(bindings[1]
+        .parse_warning
+        .as_deref()
+        .is_some_and(|w| w.contains("Hyper")));
+    assert!(bindings[2].parse_warning.is_none());
+}
+
 #[test]
 fn test_import_window_rule() {
     let dir = tempdir().unwrap();
@@ -289,6 +513,73 @@ window-rule {
     assert!((rule.opacity.unwrap() - 0.95).abs() < 0.01);
 }
 
+#[test]
+fn test_import_window_rule_excludes() {
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    fs::write(
+        &config,
+        r#"
+window-rule {
+    match app-id="firefox"
+    exclude title="^Picture-in-Picture$"
+    exclude app-id="firefox" is-floating=true
+    open-maximized true
+}
+"#,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    let rule = &settings.window_rules.rules[0];
+    assert_eq!(rule.matches.len(), 1);
+    assert_eq!(rule.excludes.len(), 2);
+    assert_eq!(
+        rule.excludes[0].title,
+        Some("^Picture-in-Picture$".to_string())
+    );
+    assert_eq!(rule.excludes[1].app_id, Some("firefox".to_string()));
+    assert_eq!(rule.excludes[1].is_floating, Some(true));
+}
+
+#[test]
+fn test_import_window_rule_keeps_gradients() {
+    use nirify::types::ColorOrGradient;
+
+    let dir = tempdir().unwrap();
+    let config = dir.path().join("config.kdl");
+
+    fs::write(
+        &config,
+        r##"
+window-rule {
+    match app-id="kitty"
+    focus-ring {
+        active-gradient from="#80c8ff" to="#bbddff" angle=45
+        inactive-color "#505050"
+    }
+}
+"##,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&config);
+    let rule = &settings.window_rules.rules[0];
+    match &rule.focus_ring_active {
+        Some(ColorOrGradient::Gradient(g)) => {
+            assert_eq!(g.from.to_hex(), "#80c8ff");
+            assert_eq!(g.to.to_hex(), "#bbddff");
+            assert_eq!(g.angle, 45);
+        }
+        other => panic!("expected a gradient, got {:?}", other),
+    }
+    assert!(matches!(
+        rule.focus_ring_inactive,
+        Some(ColorOrGradient::Color(_))
+    ));
+}
+
 #[test]
 fn test_import_multiple_window_rules() {
     let dir = tempdir().unwrap();
@@ -519,3 +810,103 @@ fn test_import_result_missing_file_has_warning() {
     assert!(!result.warnings.is_empty());
     assert!(result.warnings[0].contains("Could not read"));
 }
+
+#[test]
+fn test_import_from_str_reads_pasted_snippet() {
+    let result = import_from_str(
+        r#"
+layout {
+    gaps 24
+}
+binds {
+    Mod+T { spawn "alacritty"; }
+}
+include "other.kdl"
+"#,
+    );
+
+    assert_eq!(result.parse_error, None);
+    assert_eq!(result.settings.appearance.gaps, 24.0);
+    assert_eq!(result.settings.keybindings.bindings.len(), 1);
+    assert!(result.imported_sections.iter().any(|s| s == "appearance"));
+    // Includes can't be followed from pasted text
+    assert!(result.warnings.iter().any(|w| w.contains("other.kdl")));
+}
+
+#[test]
+fn test_import_from_str_reports_parse_error() {
+    let result = import_from_str("this { is not { valid");
+    assert!(result.parse_error.is_some());
+    assert!(!result.has_imports());
+}
+
+#[test]
+fn test_import_merge_keeps_sections_not_in_paste() {
+    let mut settings = Settings::default();
+    settings.behavior.focus_follows_mouse = true;
+
+    import_from_str("layout {\n    gaps 24\n}").merge_into(&mut settings);
+
+    assert_eq!(settings.appearance.gaps, 24.0);
+    assert!(settings.behavior.focus_follows_mouse);
+}
+
+const SHARED_BINDS: &str = r#"
+binds {
+    Mod+Return { spawn "foot"; }
+    Mod+Q { close-window; }
+}
+"#;
+
+#[test]
+fn test_import_merge_file_with_only_keybindings() {
+    let dir = tempdir().unwrap();
+    let shared = dir.path().join("friends-binds.kdl");
+    fs::write(&shared, SHARED_BINDS).unwrap();
+
+    let mut settings = Settings::default();
+    settings.appearance.gaps = 32.0;
+    settings.behavior.focus_follows_mouse = true;
+    settings.cursor.size = 40;
+    let before = settings.clone();
+
+    let merged = import_from_niri_config_with_result(&shared)
+        .merge_with(&mut settings, MergeStrategy::Overwrite);
+
+    let combos: Vec<&str> = settings
+        .keybindings
+        .bindings
+        .iter()
+        .map(|b| b.key_combo.as_str())
+        .collect();
+    assert_eq!(combos, ["Mod+Return", "Mod+Q"]);
+    assert_eq!(merged.applied, ["Keybindings"]);
+
+    // Everything but the keybindings is as it was
+    let mut untouched = settings.clone();
+    untouched.keybindings = before.keybindings.clone();
+    assert_eq!(untouched, before);
+}
+
+#[test]
+fn test_import_merge_keep_existing_skips_changed_sections() {
+    let dir = tempdir().unwrap();
+    let shared = dir.path().join("shared.kdl");
+    fs::write(
+        &shared,
+        format!("{}\ncursor {{\n    xcursor-size 48\n}}\n", SHARED_BINDS),
+    )
+    .unwrap();
+
+    // Cursor is already customized; keybindings are still at the defaults
+    let mut settings = Settings::default();
+    settings.cursor.size = 40;
+
+    let merged = import_from_niri_config_with_result(&shared)
+        .merge_with(&mut settings, MergeStrategy::KeepExisting);
+    assert_eq!(merged.applied, ["Keybindings"]);
+    assert_eq!(merged.kept, ["Cursor"]);
+    assert_eq!(settings.cursor.size, 40);
+    assert_eq!(settings.keybindings.bindings.len(), 2);
+    assert_eq!(merged.summary(), "Merged Keybindings; kept your Cursor");
+}
@@ -225,6 +225,199 @@ fn test_window_rules_lifecycle() {
     assert_eq!(rule.corner_radius, Some(12));
 }
 
+#[test]
+fn test_window_rule_excludes_roundtrip() {
+    use nirify::config::models::{WindowRule, WindowRuleMatch};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.preferences.float_settings_app = false;
+    settings.window_rules.rules.push(WindowRule {
+        id: 1,
+        name: "Firefox but not PiP".to_string(),
+        matches: vec![WindowRuleMatch {
+            app_id: Some("^firefox$".to_string()),
+            ..Default::default()
+        }],
+        excludes: vec![WindowRuleMatch {
+            title: Some("^Picture-in-Picture$".to_string()),
+            is_floating: Some(true),
+            ..Default::default()
+        }],
+        opacity: Some(0.9),
+        ..Default::default()
+    });
+    settings.window_rules.next_id = 2;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let kdl = fs::read_to_string(&paths.window_rules_kdl).unwrap();
+    assert!(kdl.contains(r#"exclude title="^Picture-in-Picture$" is-floating=true"#));
+
+    let loaded = load_settings(&paths);
+    let rule = &loaded.window_rules.rules[0];
+    assert_eq!(rule.matches, settings.window_rules.rules[0].matches);
+    assert_eq!(rule.excludes, settings.window_rules.rules[0].excludes);
+}
+
+#[test]
+fn test_window_rule_with_matches_removed_roundtrip() {
+    use nirify::config::models::{WindowRule, WindowRuleMatch};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.preferences.float_settings_app = false;
+    let mut rule = WindowRule {
+        id: 1,
+        name: "Every window".to_string(),
+        matches: vec![WindowRuleMatch {
+            app_id: Some("^firefox$".to_string()),
+            ..Default::default()
+        }],
+        opacity: Some(0.9),
+        ..Default::default()
+    };
+    rule.remove_match(0);
+    settings.window_rules.rules.push(rule);
+    settings.window_rules.next_id = 2;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+    let rule = &loaded.window_rules.rules[0];
+    assert!(rule.matches.is_empty());
+    assert_eq!(rule.opacity, Some(0.9));
+}
+
+#[test]
+fn test_window_rule_placement_roundtrip() {
+    use nirify::config::models::{OpenBehavior, WindowRule, WindowRuleMatch};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.preferences.float_settings_app = false;
+    settings.window_rules.rules.push(WindowRule {
+        id: 1,
+        name: "Browser on the big monitor".to_string(),
+        matches: vec![WindowRuleMatch {
+            app_id: Some("^firefox$".to_string()),
+            ..Default::default()
+        }],
+        open_behavior: OpenBehavior::Maximized,
+        open_on_output: Some("DP-1".to_string()),
+        open_on_workspace: Some("web".to_string()),
+        default_column_width_fixed: Some(1400),
+        ..Default::default()
+    });
+    settings.window_rules.rules.push(WindowRule {
+        id: 2,
+        name: "Terminals".to_string(),
+        matches: vec![WindowRuleMatch {
+            app_id: Some("^kitty$".to_string()),
+            ..Default::default()
+        }],
+        default_column_width: Some(0.25),
+        ..Default::default()
+    });
+    settings.window_rules.next_id = 3;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    let browser = &loaded.window_rules.rules[0];
+    assert_eq!(browser.open_on_output.as_deref(), Some("DP-1"));
+    assert_eq!(browser.open_on_workspace.as_deref(), Some("web"));
+    assert_eq!(browser.default_column_width_fixed, Some(1400));
+    assert_eq!(browser.default_column_width, None);
+
+    let terminals = &loaded.window_rules.rules[1];
+    assert_eq!(terminals.default_column_width_fixed, None);
+    assert!((terminals.default_column_width.unwrap() - 0.25).abs() < 0.01);
+}
+
+#[test]
+fn test_window_rule_gradient_roundtrip() {
+    use nirify::config::models::{WindowRule, WindowRuleMatch};
+    use nirify::types::{Color, ColorOrGradient, Gradient, GradientRelativeTo};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let gradient = ColorOrGradient::Gradient(Gradient {
+        from: Color::from_hex("#80c8ff").unwrap(),
+        to: Color::from_hex("#bbddff").unwrap(),
+        angle: 45,
+        relative_to: GradientRelativeTo::WorkspaceView,
+        ..Default::default()
+    });
+    let solid = ColorOrGradient::Color(Color::from_hex("#505050").unwrap());
+
+    let mut settings = Settings::default();
+    settings.preferences.float_settings_app = false;
+    settings.window_rules.rules.push(WindowRule {
+        id: 1,
+        matches: vec![WindowRuleMatch {
+            app_id: Some("^kitty$".to_string()),
+            ..Default::default()
+        }],
+        focus_ring_active: Some(gradient.clone()),
+        focus_ring_inactive: Some(solid.clone()),
+        border_urgent: Some(gradient.clone()),
+        ..Default::default()
+    });
+    settings.window_rules.next_id = 2;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let kdl = fs::read_to_string(&paths.window_rules_kdl).unwrap();
+    assert!(kdl.contains(r##"active-gradient from="#80c8ff" to="#bbddff" angle=45"##));
+
+    let loaded = load_settings(&paths);
+    let rule = &loaded.window_rules.rules[0];
+    assert_eq!(rule.focus_ring_active, Some(gradient.clone()));
+    assert_eq!(rule.focus_ring_inactive, Some(solid));
+    assert_eq!(rule.border_urgent, Some(gradient));
+}
+
+#[test]
+fn test_workspace_layout_gradient_roundtrip() {
+    use nirify::config::models::{LayoutOverride, NamedWorkspace};
+    use nirify::types::{Color, ColorOrGradient, Gradient};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let gradient = ColorOrGradient::Gradient(Gradient {
+        from: Color::from_hex("#eb6f92").unwrap(),
+        to: Color::from_hex("#f6c177").unwrap(),
+        ..Default::default()
+    });
+
+    let mut settings = Settings::default();
+    settings.workspaces.workspaces.push(NamedWorkspace {
+        id: 1,
+        name: "chat".to_string(),
+        layout_override: Some(LayoutOverride {
+            border_active: Some(gradient.clone()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    settings.workspaces.next_id = 2;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    let layout = loaded.workspaces.workspaces[0]
+        .layout_override
+        .as_ref()
+        .expect("layout override should survive a save");
+    assert_eq!(layout.border_active, Some(gradient));
+}
+
 #[test]
 fn test_output_settings_lifecycle() {
     use nirify::config::models::OutputConfig;
@@ -282,6 +475,617 @@ fn test_output_settings_lifecycle() {
     assert!(!hdmi.enabled);
 }
 
+#[test]
+fn test_output_extra_nodes_roundtrip() {
+    use nirify::config::models::OutputConfig;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.outputs.outputs.push(OutputConfig {
+        name: "DP-1".to_string(),
+        extra_nodes: vec![r##"background-color "#000000""##.to_string()],
+        ..Default::default()
+    });
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    let dp1 = &loaded.outputs.outputs[0];
+    assert_eq!(dp1.extra_nodes, settings.outputs.outputs[0].extra_nodes);
+}
+
+#[test]
+fn test_output_placed_at_origin_roundtrip() {
+    use nirify::config::models::OutputConfig;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    let mut placed = OutputConfig {
+        name: "DP-1".to_string(),
+        ..Default::default()
+    };
+    placed.set_position(0, 0);
+    settings.outputs.outputs.push(placed);
+    settings.outputs.outputs.push(OutputConfig {
+        name: "HDMI-A-1".to_string(),
+        ..Default::default()
+    });
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let kdl = fs::read_to_string(&paths.outputs_kdl).unwrap();
+    // Only the output that was placed pins itself to the origin
+    assert_eq!(kdl.matches("position x=0 y=0").count(), 1);
+
+    let loaded = load_settings(&paths);
+    assert!(loaded.outputs.outputs[0].has_position());
+    assert!(!loaded.outputs.outputs[1].has_position());
+}
+
+#[test]
+fn test_output_identity_roundtrip() {
+    use nirify::config::models::OutputConfig;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.outputs.outputs.push(OutputConfig {
+        name: "DP-1".to_string(),
+        make: Some("Dell Inc.".to_string()),
+        model: Some("U2720Q".to_string()),
+        serial: Some("ABC123".to_string()),
+        match_by_identity: true,
+        scale: 1.5,
+        ..Default::default()
+    });
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let kdl = fs::read_to_string(&paths.outputs_kdl).unwrap();
+    assert!(kdl.contains(r#"output "Dell Inc. U2720Q ABC123""#));
+
+    let loaded = load_settings(&paths);
+    let output = &loaded.outputs.outputs[0];
+    assert_eq!(output.name, "DP-1");
+    assert_eq!(output.make.as_deref(), Some("Dell Inc."));
+    assert_eq!(output.model.as_deref(), Some("U2720Q"));
+    assert_eq!(output.serial.as_deref(), Some("ABC123"));
+    assert!(output.match_by_identity);
+    assert!((output.scale - 1.5).abs() < 0.01);
+}
+
+#[test]
+fn test_single_animation_off_roundtrip() {
+    use nirify::config::models::AnimationType;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    let per = &mut settings.animations.per_animation;
+    per.window_resize.animation_type = AnimationType::Spring;
+    per.window_resize.spring.stiffness = 600;
+    per.window_resize.enabled = false;
+    per.window_open.animation_type = AnimationType::Easing;
+    per.window_open.easing.duration_ms = 250;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let kdl = fs::read_to_string(&paths.animations_kdl).unwrap();
+    assert!(kdl.contains("window-resize {\n        off\n"));
+
+    let loaded = load_settings(&paths);
+    let per = &loaded.animations.per_animation;
+    // Only window-resize is off, and it keeps its spring for turning back on
+    assert!(loaded.animations.enabled);
+    assert!(!per.window_resize.enabled);
+    assert_eq!(per.window_resize.animation_type, AnimationType::Spring);
+    assert_eq!(per.window_resize.spring.stiffness, 600);
+    assert!(per.window_open.enabled);
+    assert_eq!(per.window_open.easing.duration_ms, 250);
+    assert!(per.workspace_switch.enabled);
+}
+
+#[test]
+fn test_workspace_background_and_overview_backdrop_stay_apart() {
+    use nirify::types::Color;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.appearance.background_color = Color::from_hex("#112233");
+    settings.overview.backdrop_color = Color::from_hex("#445566");
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let appearance = fs::read_to_string(&paths.appearance_kdl).unwrap();
+    let layout = &appearance[appearance.find("layout {").unwrap()..];
+    assert!(layout.contains(r##"background-color "#112233""##));
+    assert!(!appearance.contains("445566"));
+
+    let loaded = load_settings(&paths);
+    assert_eq!(
+        loaded.appearance.background_color,
+        Color::from_hex("#112233")
+    );
+    assert_eq!(loaded.overview.backdrop_color, Color::from_hex("#445566"));
+
+    // Clearing one leaves the other alone
+    settings.appearance.background_color = None;
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+    assert_eq!(loaded.appearance.background_color, None);
+    assert_eq!(loaded.overview.backdrop_color, Color::from_hex("#445566"));
+}
+
+#[test]
+fn test_cursor_hide_options_roundtrip() {
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.cursor.hide_when_typing = true;
+    settings.cursor.hide_after_inactive_ms = Some(1500);
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    assert!(loaded.cursor.hide_when_typing);
+    assert_eq!(loaded.cursor.hide_after_inactive_ms, Some(1500));
+}
+
+#[test]
+fn test_cursor_unset_hide_options_emit_nothing() {
+    use nirify::config::storage::generate_cursor_kdl;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let settings = Settings::default();
+    let kdl = generate_cursor_kdl(&settings.cursor);
+    assert!(!kdl.contains("hide-when-typing"));
+    assert!(!kdl.contains("hide-after-inactive-ms"));
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+    assert!(!loaded.cursor.hide_when_typing);
+    assert_eq!(loaded.cursor.hide_after_inactive_ms, None);
+}
+
+#[test]
+fn test_gradient_attributes_roundtrip() {
+    use nirify::types::{
+        Color, ColorOrGradient, ColorSpace, Gradient, GradientRelativeTo, HueInterpolation,
+    };
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let gradient = ColorOrGradient::Gradient(Gradient {
+        from: Color::from_hex("#80c8ff").unwrap(),
+        to: Color::from_hex("#bbddff").unwrap(),
+        angle: 45,
+        relative_to: GradientRelativeTo::WorkspaceView,
+        color_space: ColorSpace::Oklch,
+        hue_interpolation: Some(HueInterpolation::Longer),
+    });
+    let mut settings = Settings::default();
+    settings.appearance.focus_ring_active = gradient.clone();
+    settings.appearance.border_enabled = true;
+    settings.appearance.border_active = gradient.clone();
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    assert_eq!(loaded.appearance.focus_ring_active, gradient);
+    assert_eq!(loaded.appearance.border_active, gradient);
+}
+
+#[test]
+fn test_trackpoint_scroll_options_roundtrip() {
+    use nirify::types::ScrollMethod;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.trackpoint.scroll_method = ScrollMethod::TwoFinger;
+    settings.trackpoint.scroll_button = Some(274);
+    settings.trackpoint.scroll_button_lock = true;
+    settings.trackpoint.middle_emulation = true;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    assert_eq!(loaded.trackpoint, settings.trackpoint);
+}
+
+#[test]
+fn test_trackball_scroll_options_roundtrip() {
+    use nirify::types::ScrollMethod;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.trackball.scroll_method = ScrollMethod::NoScroll;
+    settings.trackball.scroll_button = Some(275);
+    settings.trackball.scroll_button_lock = true;
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_settings(&paths);
+
+    assert_eq!(loaded.trackball, settings.trackball);
+}
+
+#[test]
+fn test_touchpad_unknown_options_survive_save() {
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+    fs::create_dir_all(paths.touchpad_kdl.parent().unwrap()).unwrap();
+    fs::write(
+        &paths.touchpad_kdl,
+        "input {\n    touchpad {\n        tap\n        palm-size 40\n        dwt-timeout-ms 300\n    }\n}\n",
+    )
+    .unwrap();
+
+    let loaded = load_settings(&paths);
+    assert!(loaded.touchpad.tap);
+    assert_eq!(
+        loaded.touchpad.extra_nodes,
+        ["palm-size 40", "dwt-timeout-ms 300"]
+    );
+
+    save_settings(&paths, &loaded, FeatureCompat::all_enabled()).expect("Failed to save");
+    let reloaded = load_settings(&paths);
+    assert_eq!(reloaded.touchpad, loaded.touchpad);
+}
+
+#[test]
+fn test_touchpad_drag_roundtrip() {
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.touchpad.drag = false;
+    settings.touchpad.drag_lock = true;
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    assert_eq!(load_settings(&paths).touchpad, settings.touchpad);
+
+    // niri before 25.05 rejects `drag`, so it's left out and reads back as
+    // libinput's default
+    let compat = FeatureCompat {
+        touchpad_drag: false,
+        ..FeatureCompat::all_enabled()
+    };
+    save_settings(&paths, &settings, compat).expect("Failed to save");
+    let content = fs::read_to_string(&paths.touchpad_kdl).unwrap();
+    assert!(!content.contains("drag false"));
+    assert!(content.contains("drag-lock"));
+    assert!(load_settings(&paths).touchpad.drag);
+}
+
+#[test]
+fn test_favorites_lifecycle() {
+    use nirify::search::SearchIndex;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    // Star a setting from the search index
+    let mut settings = Settings::default();
+    settings.preferences.toggle_favorite("Window Gaps");
+    assert!(settings.preferences.is_favorite("Window Gaps"));
+
+    let index = SearchIndex::new();
+    let favorites = index.favorites(&settings.preferences.favorites);
+    assert_eq!(favorites.len(), 1);
+    assert_eq!(favorites[0].setting_name, "Window Gaps");
+
+    // Favorites persist in preferences
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let mut loaded = load_settings(&paths);
+    assert_eq!(
+        loaded.preferences.favorites,
+        vec!["Window Gaps".to_string()]
+    );
+
+    // Unstarring removes it again
+    loaded.preferences.toggle_favorite("Window Gaps");
+    assert!(index.favorites(&loaded.preferences.favorites).is_empty());
+}
+
+#[test]
+fn test_preferences_stay_out_of_niri_files() {
+    use nirify::config::ConfigFile;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.preferences.theme = "CatppuccinMocha".to_string();
+    settings.preferences.search_hotkey = "Ctrl+/".to_string();
+    settings.preferences.show_search_bar = false;
+    settings.preferences.emit_all_defaults = true;
+    settings.preferences.backup_retention = nirify::config::models::BackupRetention::days(30);
+    settings.preferences.toggle_favorite("Window Gaps");
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+
+    // Preferences round-trip through their own file
+    let loaded = load_settings(&paths);
+    assert_eq!(loaded.preferences, settings.preferences);
+    let prefs_kdl = fs::read_to_string(&paths.preferences_kdl).unwrap();
+    assert!(prefs_kdl.contains("backup-retention days=30"));
+
+    // None of the niri-managed files mention them
+    for file in ConfigFile::ALL {
+        let Ok(content) = fs::read_to_string(paths.path_for(*file)) else {
+            continue;
+        };
+        for key in [
+            "preferences",
+            "CatppuccinMocha",
+            "search-hotkey",
+            "show-search-bar",
+            "emit-all-defaults",
+            "backup-retention",
+            "favorites",
+        ] {
+            assert!(
+                !content.contains(key),
+                "{:?} leaked into {}",
+                key,
+                file.relative_path()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_keybinding_sections_roundtrip_as_comments() {
+    use nirify::config::models::{KeybindAction, Keybinding};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.keybindings.bindings = vec![
+        Keybinding {
+            key_combo: "Mod+T".to_string(),
+            section: Some("Apps".to_string()),
+            action: KeybindAction::Spawn(vec!["foot".to_string()]),
+            ..Default::default()
+        },
+        Keybinding {
+            key_combo: "Mod+Q".to_string(),
+            action: KeybindAction::NiriAction("close-window".to_string()),
+            ..Default::default()
+        },
+    ];
+
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+
+    // niri has no binding sections, so nothing section-related reaches it as config
+    let kdl = fs::read_to_string(&paths.keybindings_kdl).unwrap();
+    assert!(kdl.contains("// section: Apps"));
+    assert!(!kdl.contains("section="));
+
+    let loaded = load_settings(&paths);
+    let sections: Vec<_> = loaded
+        .keybindings
+        .bindings
+        .iter()
+        .map(|b| b.section.as_deref())
+        .collect();
+    assert_eq!(sections, vec![Some("Apps"), None]);
+}
+
+#[test]
+fn test_lost_managed_dir_is_not_a_first_run() {
+    use nirify::config::{ensure_required_files_exist, load_or_import_settings};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    // Set up before, then the managed files were deleted; the user has since
+    // put a setting back into config.kdl by hand
+    fs::write(
+        &paths.niri_config,
+        "layout {\n    gaps 20\n}\n\n// Managed by Nirify - do not remove this line\ninclude \"nirify/main.kdl\"\n",
+    )
+    .unwrap();
+    assert!(!paths.has_managed_files());
+    assert!(!paths.is_first_run());
+
+    let settings = load_or_import_settings(&paths);
+    assert_eq!(settings.appearance.gaps, 20.0);
+
+    // Recreating the files keeps the imported value rather than defaults
+    ensure_required_files_exist(&paths, &settings, FeatureCompat::all_enabled()).unwrap();
+    assert!(paths.has_managed_files());
+    assert_eq!(load_or_import_settings(&paths).appearance.gaps, 20.0);
+}
+
+#[test]
+fn test_managed_files_win_over_config_kdl() {
+    use nirify::config::load_or_import_settings;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    let mut settings = Settings::default();
+    settings.appearance.gaps = 8.0;
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    fs::write(&paths.niri_config, "layout {\n    gaps 20\n}\n").unwrap();
+
+    assert!(!paths.is_first_run());
+    assert_eq!(load_or_import_settings(&paths).appearance.gaps, 8.0);
+}
+
+#[test]
+fn test_workspace_output_roundtrip() {
+    use nirify::config::{import_from_niri_config, load_or_import_settings};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+
+    // Connector names and make/model/serial descriptions both work in niri
+    fs::write(
+        &paths.niri_config,
+        r#"
+workspace "main"
+workspace "dev" {
+    open-on-output "DP-1"
+}
+workspace "chat" {
+    open-on-output "Dell Inc. DELL U2720Q \"B\""
+}
+"#,
+    )
+    .unwrap();
+    let imported = import_from_niri_config(&paths.niri_config);
+    let outputs: Vec<_> = imported
+        .workspaces
+        .workspaces
+        .iter()
+        .map(|ws| ws.open_on_output.as_deref())
+        .collect();
+    assert_eq!(
+        outputs,
+        vec![None, Some("DP-1"), Some("Dell Inc. DELL U2720Q \"B\"")]
+    );
+
+    save_settings(&paths, &imported, FeatureCompat::all_enabled()).expect("Failed to save");
+    let loaded = load_or_import_settings(&paths);
+    assert_eq!(loaded.workspaces, imported.workspaces);
+
+    // Clearing the output drops the line rather than writing an empty one
+    let mut settings = loaded;
+    settings.workspaces.workspaces[1].open_on_output = None;
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let content = fs::read_to_string(&paths.workspaces_kdl).unwrap();
+    assert!(!content.contains("open-on-output \"\""));
+    assert_eq!(load_settings(&paths).workspaces, settings.workspaces);
+}
+
+#[test]
+fn test_unknown_keybinding_property_survives_save() {
+    use nirify::config::{import_from_niri_config, load_or_import_settings};
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+    fs::write(
+        &paths.niri_config,
+        r#"
+binds {
+    Mod+T allow-inhibiting=false "some flag"=1.5 note="keep \"me\"" { spawn "foot"; }
+}
+"#,
+    )
+    .unwrap();
+
+    let mut settings = import_from_niri_config(&paths.niri_config);
+    let binding = &settings.keybindings.bindings[0];
+    assert_eq!(binding.extra_properties.len(), 3);
+    assert!(binding.parse_warning.is_none());
+
+    // Edit a modelled field, then save and load it back
+    settings.keybindings.bindings[0].hotkey_overlay_title = Some("Terminal".to_string());
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+    let content = fs::read_to_string(&paths.keybindings_kdl).unwrap();
+    assert!(content.contains("allow-inhibiting=false"));
+
+    let loaded = load_or_import_settings(&paths);
+    let binding = &loaded.keybindings.bindings[0];
+    assert_eq!(binding.hotkey_overlay_title.as_deref(), Some("Terminal"));
+    assert_eq!(
+        binding.extra_properties,
+        settings.keybindings.bindings[0].extra_properties
+    );
+}
+
+#[test]
+fn test_noop_save_of_imported_config_leaves_files_untouched() {
+    use nirify::config::{import_from_niri_config, save_dirty, SettingsCategory};
+    use std::collections::HashSet;
+
+    let dir = tempdir().unwrap();
+    let paths = create_test_paths(dir.path());
+    fs::write(
+        &paths.niri_config,
+        r#"
+layout {
+    gaps 12
+}
+input {
+    touchpad {
+        tap
+    }
+}
+binds {
+    Mod+T { spawn "foot"; }
+}
+"#,
+    )
+    .unwrap();
+
+    let settings = import_from_niri_config(&paths.niri_config);
+    save_settings(&paths, &settings, FeatureCompat::all_enabled()).expect("Failed to save");
+
+    // A hand edit in a category that isn't dirty is kept as-is
+    let touchpad = fs::read_to_string(&paths.touchpad_kdl).unwrap();
+    let hand_edited = format!("// tuned by hand\n{}", touchpad);
+    fs::write(&paths.touchpad_kdl, &hand_edited).unwrap();
+
+    let snapshot = |paths: &nirify::config::ConfigPaths| -> Vec<(String, Vec<u8>)> {
+        let mut files: Vec<_> = [&paths.managed_dir, &paths.input_dir, &paths.advanced_dir]
+            .into_iter()
+            .flat_map(|dir| fs::read_dir(dir).unwrap().flatten())
+            .filter(|e| e.path().is_file())
+            .map(|e| {
+                (
+                    e.file_name().to_string_lossy().into_owned(),
+                    fs::read(e.path()).unwrap(),
+                )
+            })
+            .collect();
+        files.sort();
+        files
+    };
+    let before = snapshot(&paths);
+
+    // Every other category is dirty, but nothing was changed
+    let dirty: HashSet<_> = SettingsCategory::all()
+        .iter()
+        .copied()
+        .filter(|c| *c != SettingsCategory::Touchpad)
+        .collect();
+    let written = save_dirty(&paths, &settings, &dirty, FeatureCompat::all_enabled()).unwrap();
+    assert_eq!(written, 0);
+    assert_eq!(snapshot(&paths), before);
+    assert_eq!(
+        fs::read_to_string(&paths.touchpad_kdl).unwrap(),
+        hand_edited
+    );
+
+    // A real change rewrites only its own file
+    let mut changed = settings.clone();
+    changed.appearance.gaps = 20.0;
+    let written = save_dirty(&paths, &changed, &dirty, FeatureCompat::all_enabled()).unwrap();
+    assert_eq!(written, 1);
+    let after = snapshot(&paths);
+    let differing: Vec<_> = before
+        .iter()
+        .zip(&after)
+        .filter(|(a, b)| a != b)
+        .map(|(a, _)| a.0.as_str())
+        .collect();
+    assert_eq!(differing, vec!["appearance.kdl"]);
+}
+
 #[test]
 fn test_check_config_health_all_valid() {
     let dir = tempdir().unwrap();
@@ -236,7 +236,7 @@ proptest! {
             ..Default::default()
         };
 
-        let kdl_str = generate_keyboard_kdl(&keyboard);
+        let kdl_str = generate_keyboard_kdl(&keyboard, false);
 
         let result: Result<kdl::KdlDocument, _> = kdl_str.parse();
         prop_assert!(result.is_ok(), "Generated keyboard KDL failed to parse:\n{}\nError: {:?}", kdl_str, result.err());
@@ -258,7 +258,7 @@ proptest! {
             ..Default::default()
         };
 
-        let kdl_str = generate_mouse_kdl(&mouse);
+        let kdl_str = generate_mouse_kdl(&mouse, false);
 
         let result: Result<kdl::KdlDocument, _> = kdl_str.parse();
         prop_assert!(result.is_ok(), "Generated mouse KDL failed to parse:\n{}\nError: {:?}", kdl_str, result.err());
@@ -346,7 +346,7 @@ proptest! {
             ..Default::default()
         };
 
-        let kdl_str = generate_keyboard_kdl(&keyboard);
+        let kdl_str = generate_keyboard_kdl(&keyboard, false);
 
         // Should contain our layout
         let expected_layout = format!("layout \"{}\"", layout);
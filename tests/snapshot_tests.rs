@@ -11,6 +11,7 @@ use insta::assert_snapshot;
 use nirify::config::models::*;
 use nirify::config::storage::*;
 use nirify::types::*;
+use nirify::version::FeatureCompat;
 
 // ============================================================================
 // APPEARANCE SNAPSHOTS
@@ -133,7 +134,7 @@ fn snapshot_behavior_center_focused() {
 #[test]
 fn snapshot_keyboard_default() {
     let keyboard = KeyboardSettings::default();
-    let kdl = generate_keyboard_kdl(&keyboard);
+    let kdl = generate_keyboard_kdl(&keyboard, false);
     assert_snapshot!("keyboard_default", kdl);
 }
 
@@ -147,14 +148,14 @@ fn snapshot_keyboard_custom_layout() {
         repeat_rate: 30,
         ..Default::default()
     };
-    let kdl = generate_keyboard_kdl(&keyboard);
+    let kdl = generate_keyboard_kdl(&keyboard, false);
     assert_snapshot!("keyboard_custom_layout", kdl);
 }
 
 #[test]
 fn snapshot_mouse_default() {
     let mouse = MouseSettings::default();
-    let kdl = generate_mouse_kdl(&mouse);
+    let kdl = generate_mouse_kdl(&mouse, false);
     assert_snapshot!("mouse_default", kdl);
 }
 
@@ -167,14 +168,14 @@ fn snapshot_mouse_custom() {
         scroll_factor: 1.5,
         ..Default::default()
     };
-    let kdl = generate_mouse_kdl(&mouse);
+    let kdl = generate_mouse_kdl(&mouse, false);
     assert_snapshot!("mouse_custom", kdl);
 }
 
 #[test]
 fn snapshot_touchpad_default() {
     let touchpad = TouchpadSettings::default();
-    let kdl = generate_touchpad_kdl(&touchpad);
+    let kdl = generate_touchpad_kdl(&touchpad, false, FeatureCompat::all_enabled());
     assert_snapshot!("touchpad_default", kdl);
 }
 
@@ -190,7 +191,7 @@ fn snapshot_touchpad_custom() {
         click_method: ClickMethod::Clickfinger,
         ..Default::default()
     };
-    let kdl = generate_touchpad_kdl(&touchpad);
+    let kdl = generate_touchpad_kdl(&touchpad, false, FeatureCompat::all_enabled());
     assert_snapshot!("touchpad_custom", kdl);
 }
 